@@ -0,0 +1,167 @@
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use clap::Args;
+use leancoin::context::__client_accounts_change_authority_context::ChangeAuthorityContext;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Signer};
+
+use crate::transaction::{process_instructions, TransactionArgs};
+use crate::utils::{fetch_contract_state, find_program_address, signer_from_path, CliResult};
+
+/// Arguments of the rotate-authority command.
+#[derive(Args)]
+pub struct RotateAuthorityArgs {
+    /// New authority of the contract
+    #[clap(long = "new")]
+    new_authority: Pubkey,
+
+    /// Current authority: path to the keypair file, hardware wallet URL, e.g. usb://ledger,
+    /// or its public key when the transaction is only written with --unsigned-out
+    #[clap(long)]
+    signer: String,
+
+    #[clap(flatten)]
+    transaction: TransactionArgs,
+}
+
+/// Builds the instruction changing the contract's authority.
+///
+/// ### Arguments
+///
+/// * `authority` - the current authority of the contract
+/// * `new_authority` - the new authority of the contract
+///
+/// ### Returns
+/// The instruction
+pub fn change_authority_instruction(authority: &Pubkey, new_authority: &Pubkey) -> Instruction {
+    let data = leancoin::instruction::ChangeAuthority {
+        new_authority: *new_authority,
+    }
+    .data();
+
+    let accounts = ChangeAuthorityContext {
+        contract_state: find_program_address(leancoin::CONTRACT_STATE_SEED),
+        signer: *authority,
+    };
+
+    Instruction::new_with_bytes(leancoin::ID, &data, accounts.to_account_metas(None))
+}
+
+/// Asks the user to type the new authority again and checks if it matches the one passed as an argument.
+///
+/// ### Arguments
+///
+/// * `new_authority` - the new authority passed as an argument
+/// * `input` - the input from which the confirmation is read
+///
+/// ### Returns
+/// An error if the typed authority does not match, otherwise a successful result.
+pub fn confirm_new_authority(new_authority: &Pubkey, input: &mut impl BufRead) -> CliResult<()> {
+    print!("Type the new authority again to confirm: ");
+    io::stdout().flush()?;
+
+    let mut line = String::new();
+    input.read_line(&mut line)?;
+
+    if Pubkey::from_str(line.trim()).ok().as_ref() != Some(new_authority) {
+        return Err("The typed authority does not match the new authority".into());
+    }
+
+    Ok(())
+}
+
+/// Changes the contract's authority.
+/// The transaction is signed by the current authority which can be stored in a keypair file or on a hardware wallet,
+/// or it is written unsigned to the file and signed offline.
+pub fn rotate_authority(url: &str, args: RotateAuthorityArgs) -> CliResult<()> {
+    let client = RpcClient::new(url.to_string());
+    let contract_state = fetch_contract_state(&client)?;
+
+    println!("Current authority: {}", contract_state.authority);
+    println!("New authority: {}", args.new_authority);
+
+    confirm_new_authority(&args.new_authority, &mut io::stdin().lock())?;
+
+    let signer = signer_from_path(&args.signer, "authority")?;
+    if signer.pubkey() != contract_state.authority {
+        return Err(format!(
+            "The signer {} is not the current authority",
+            signer.pubkey()
+        )
+        .into());
+    }
+
+    process_instructions(
+        &client,
+        &[change_authority_instruction(
+            &signer.pubkey(),
+            &args.new_authority,
+        )],
+        signer.as_ref(),
+        &args.transaction,
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::transaction::build_transaction;
+    use solana_sdk::{hash::Hash, signature::Keypair};
+    use std::io::Cursor;
+
+    #[test]
+    fn test_build_change_authority_transaction() {
+        let signer = Keypair::new();
+        let new_authority = Pubkey::new_unique();
+        let recent_blockhash = Hash::new_unique();
+
+        let mut transaction = build_transaction(
+            &[change_authority_instruction(
+                &signer.pubkey(),
+                &new_authority,
+            )],
+            &signer.pubkey(),
+            None,
+            recent_blockhash,
+        );
+        transaction.try_sign(&[&signer], recent_blockhash).unwrap();
+
+        transaction.verify().unwrap();
+        assert_eq!(transaction.message.account_keys[0], signer.pubkey());
+        assert_eq!(transaction.message.instructions.len(), 1);
+
+        let instruction = &transaction.message.instructions[0];
+        assert_eq!(
+            transaction.message.account_keys[usize::from(instruction.program_id_index)],
+            leancoin::ID
+        );
+        assert_eq!(
+            instruction.data,
+            leancoin::instruction::ChangeAuthority { new_authority }.data()
+        );
+        assert_eq!(
+            transaction.message.account_keys[usize::from(instruction.accounts[0])],
+            find_program_address(leancoin::CONTRACT_STATE_SEED)
+        );
+    }
+
+    #[test]
+    fn test_confirm_new_authority() {
+        let new_authority = Pubkey::new_unique();
+        let mut input = Cursor::new(format!("{}\n", new_authority));
+
+        confirm_new_authority(&new_authority, &mut input).unwrap();
+    }
+
+    #[test]
+    fn test_fail_confirm_new_authority() {
+        let new_authority = Pubkey::new_unique();
+        let mut input = Cursor::new(format!("{}\n", Pubkey::new_unique()));
+
+        assert!(confirm_new_authority(&new_authority, &mut input).is_err());
+    }
+}
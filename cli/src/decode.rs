@@ -0,0 +1,87 @@
+use std::fs;
+use std::path::Path;
+
+use clap::Args;
+use leancoin::decode::{decode_account, DecodedAccountKind};
+
+use crate::utils::CliResult;
+
+/// Arguments of the decode command.
+#[derive(Args)]
+pub struct DecodeArgs {
+    /// Kind of the account: contract_state or vesting_state
+    #[clap(long)]
+    account: DecodedAccountKind,
+
+    /// Base64 encoded account data or path to a file containing either base64 encoded or raw account data
+    #[clap(long)]
+    data: String,
+
+    /// Unix timestamp used to calculate the derived values, e.g. months since vesting start
+    #[clap(long)]
+    timestamp: Option<i64>,
+}
+
+/// Reads the account data passed as an argument.
+/// If the argument is a path to an existing file, the file contains either base64 encoded or raw account data,
+/// otherwise the argument itself is base64 encoded account data.
+///
+/// ### Arguments
+///
+/// * `data` - base64 encoded account data or path to a file containing it
+///
+/// ### Returns
+/// The raw account data
+pub fn read_account_data(data: &str) -> CliResult<Vec<u8>> {
+    if !Path::new(data).is_file() {
+        return Ok(base64::decode(data.trim())?);
+    }
+
+    let file_content = fs::read(data)?;
+    let content = String::from_utf8_lossy(&file_content);
+
+    Ok(base64::decode(content.trim()).unwrap_or(file_content))
+}
+
+/// Decodes the account data without connecting to the RPC and prints it as pretty JSON.
+pub fn decode(args: DecodeArgs) -> CliResult<()> {
+    let data = read_account_data(&args.data)?;
+    let decoded_account = decode_account(args.account, &data, args.timestamp)?;
+
+    println!("{}", serde_json::to_string_pretty(&decoded_account)?);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_read_base64_account_data() {
+        assert_eq!(read_account_data("AQIDBA==").unwrap(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_read_account_data_from_file() {
+        let base64_path = env::temp_dir().join("leancoin-decode-base64.txt");
+        fs::write(&base64_path, "AQIDBA==\n").unwrap();
+        let raw_path = env::temp_dir().join("leancoin-decode-raw.bin");
+        fs::write(&raw_path, [0xff, 0xfe, 0xfd]).unwrap();
+
+        assert_eq!(
+            read_account_data(base64_path.to_str().unwrap()).unwrap(),
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(
+            read_account_data(raw_path.to_str().unwrap()).unwrap(),
+            vec![0xff, 0xfe, 0xfd]
+        );
+    }
+
+    #[test]
+    fn test_fail_read_invalid_account_data() {
+        assert!(read_account_data("not base64 data").is_err());
+    }
+}
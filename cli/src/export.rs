@@ -0,0 +1,782 @@
+use std::{fs, path::PathBuf};
+
+use anchor_lang::{solana_program::program_pack::Pack, Discriminator};
+use anchor_spl::token::spl_token::state::{Account as TokenAccount, Mint};
+use clap::Args;
+use leancoin::account::{
+    Attestation, ContractState, DonorAccount, Labels, OtcDeal, StakeAccount, StakingState,
+    VestingState, WalletDelegation, WithdrawalIntent, WithdrawalReceipt,
+};
+use leancoin::decode::{decode_account, DecodedAccount, DecodedAccountKind};
+use leancoin::utils::calculate_locked_amount;
+use leancoin::{
+    WalletKind, BURNING_ACCOUNT_SEED, CONTRACT_STATE_SEED, MINT_SEED, PROGRAM_ACCOUNT_SEED,
+    VESTING_STATE_SEED,
+};
+use leancoin_sdk::pda::{address, wallet_address};
+use leancoin_sdk::vesting::{vesting_status_at, WALLET_KINDS};
+use serde::Serialize;
+use serde_json::Value;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{account::Account, clock::Clock, pubkey::Pubkey, sysvar};
+
+use crate::utils::CliResult;
+
+/// Version of the layout of the exported files.
+/// It is increased whenever a field or a column is removed, renamed or changes its meaning, adding new ones keeps it.
+pub const EXPORT_SCHEMA_VERSION: u32 = 1;
+
+/// Format of the exported files.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Csv,
+}
+
+/// Arguments of the export command.
+#[derive(Args)]
+pub struct ExportArgs {
+    /// Format of the exported files: json or csv
+    #[clap(long, value_parser = parse_export_format)]
+    format: ExportFormat,
+
+    /// Directory to which the files are written, it is created if it does not exist
+    #[clap(long)]
+    out: PathBuf,
+}
+
+/// Parses the name of the export format.
+pub fn parse_export_format(format: &str) -> Result<ExportFormat, String> {
+    match format {
+        "json" => Ok(ExportFormat::Json),
+        "csv" => Ok(ExportFormat::Csv),
+        _ => Err(format!("Unknown format {}, expected json or csv", format)),
+    }
+}
+
+/// The vesting figures of a vested wallet derived at the time of the export.
+#[derive(Serialize)]
+pub struct WalletReport {
+    pub wallet_kind: WalletKind,
+    pub initial_balance: u64,
+    /// the amount of tokens unlocked so far, including the already withdrawn ones
+    pub unlocked: u64,
+    pub already_withdrawn: u64,
+    /// the amount of tokens which can be withdrawn at the time of the export
+    pub available: u64,
+    /// the amount of tokens which have not been unlocked yet, see `calculate_locked_amount`
+    pub locked: u64,
+    pub finalized: bool,
+}
+
+/// The balance of a program token account, the supply of the mint.
+#[derive(Serialize)]
+pub struct BalanceReport {
+    pub account: &'static str,
+    pub address: String,
+    /// `None` if the account does not exist, e.g. the token account of a finalized wallet
+    pub amount: Option<u64>,
+}
+
+/// An account owned by the contract.
+#[derive(Serialize)]
+pub struct AccountReport {
+    pub address: String,
+    /// the snake case name of the account type recognized by the discriminator, `unknown` if not recognized
+    pub kind: &'static str,
+    pub lamports: u64,
+    pub data_len: usize,
+    /// base64 encoded data of the accounts which are not decoded into the report
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+}
+
+/// The point-in-time dump of the contract.
+/// The state accounts are `None` if they do not exist or cannot be decoded, e.g. because they have not been migrated yet.
+#[derive(Serialize)]
+pub struct Report {
+    pub schema_version: u32,
+    pub program_id: String,
+    pub slot: u64,
+    pub timestamp: i64,
+    pub contract_state: Option<ContractState>,
+    pub vesting_state: Option<VestingState>,
+    pub months_since_start: Option<u64>,
+    pub vesting: Vec<WalletReport>,
+    pub balances: Vec<BalanceReport>,
+    pub accounts: Vec<AccountReport>,
+}
+
+/// Returns the names and the addresses of the accounts whose balances are exported, the mint first.
+/// The accounts are named by their seeds.
+pub fn balance_addresses() -> Vec<(&'static str, Pubkey)> {
+    [MINT_SEED, PROGRAM_ACCOUNT_SEED, BURNING_ACCOUNT_SEED]
+        .into_iter()
+        .chain(WALLET_KINDS.iter().map(WalletKind::seed))
+        .map(|seed| (seed, address(seed)))
+        .collect()
+}
+
+/// Recognizes the type of the contract account by its discriminator.
+///
+/// ### Arguments
+///
+/// * `data` - the data of the account
+///
+/// ### Returns
+/// The snake case name of the account type or `unknown`, e.g. for the account types added after the export
+pub fn account_kind(data: &[u8]) -> &'static str {
+    let kinds = [
+        (ContractState::discriminator(), "contract_state"),
+        (VestingState::discriminator(), "vesting_state"),
+        (Attestation::discriminator(), "attestation"),
+        (StakingState::discriminator(), "staking_state"),
+        (StakeAccount::discriminator(), "stake_account"),
+        (OtcDeal::discriminator(), "otc_deal"),
+        (DonorAccount::discriminator(), "donor_account"),
+        (Labels::discriminator(), "labels"),
+        (WithdrawalIntent::discriminator(), "withdrawal_intent"),
+        (WalletDelegation::discriminator(), "wallet_delegation"),
+        (WithdrawalReceipt::discriminator(), "withdrawal_receipt"),
+    ];
+
+    kinds
+        .iter()
+        .find(|(discriminator, _)| data.starts_with(discriminator))
+        .map_or("unknown", |(_, kind)| *kind)
+}
+
+/// Builds the report from the fetched accounts.
+///
+/// ### Arguments
+///
+/// * `slot` - the slot of the export
+/// * `timestamp` - the unix timestamp at which the vesting figures are derived
+/// * `program_accounts` - all accounts owned by the contract
+/// * `balance_accounts` - the accounts listed by `balance_addresses` with their names, `None` if they do not exist
+///
+/// ### Returns
+/// The report with the accounts ordered by their addresses
+pub fn build_report(
+    slot: u64,
+    timestamp: i64,
+    mut program_accounts: Vec<(Pubkey, Account)>,
+    balance_accounts: &[(&'static str, Pubkey, Option<Account>)],
+) -> CliResult<Report> {
+    program_accounts.sort_by_key(|(address, _)| *address);
+
+    let contract_state_address = address(CONTRACT_STATE_SEED);
+    let vesting_state_address = address(VESTING_STATE_SEED);
+    let mut contract_state = None;
+    let mut vesting_state = None;
+    let mut months_since_start = None;
+    let mut accounts = vec![];
+
+    for (account_address, account) in &program_accounts {
+        let kind = account_kind(&account.data);
+        let decoded = match kind {
+            "contract_state" if *account_address == contract_state_address => {
+                decode_account(DecodedAccountKind::ContractState, &account.data, None).ok()
+            }
+            "vesting_state" if *account_address == vesting_state_address => decode_account(
+                DecodedAccountKind::VestingState,
+                &account.data,
+                Some(timestamp),
+            )
+            .ok(),
+            _ => None,
+        };
+
+        let decoded = match decoded {
+            Some(DecodedAccount::ContractState { state }) => {
+                contract_state = Some(state);
+                true
+            }
+            Some(DecodedAccount::VestingState {
+                state,
+                months_since_start: months,
+            }) => {
+                vesting_state = Some(state);
+                months_since_start = months;
+                true
+            }
+            None => false,
+        };
+
+        accounts.push(AccountReport {
+            address: account_address.to_string(),
+            kind,
+            lamports: account.lamports,
+            data_len: account.data.len(),
+            data: (!decoded).then(|| base64::encode(&account.data)),
+        });
+    }
+
+    let vesting = match &vesting_state {
+        Some(vesting_state) => vesting_status_at(vesting_state, timestamp)?
+            .wallets
+            .iter()
+            .map(|wallet| -> CliResult<WalletReport> {
+                Ok(WalletReport {
+                    wallet_kind: wallet.wallet_kind,
+                    initial_balance: wallet.initial_balance,
+                    unlocked: wallet.unlocked,
+                    already_withdrawn: wallet.already_withdrawn,
+                    available: wallet.available,
+                    locked: calculate_locked_amount(vesting_state, wallet.wallet_kind, timestamp)?,
+                    finalized: vesting_state.finalized_wallets & wallet.wallet_kind.mask() != 0,
+                })
+            })
+            .collect::<CliResult<Vec<WalletReport>>>()?,
+        None => vec![],
+    };
+
+    let balances = balance_accounts
+        .iter()
+        .map(
+            |(name, account_address, account)| -> CliResult<BalanceReport> {
+                let amount = match account {
+                    Some(account) if *name == MINT_SEED => {
+                        Some(Mint::unpack(&account.data)?.supply)
+                    }
+                    Some(account) => Some(TokenAccount::unpack(&account.data)?.amount),
+                    None => None,
+                };
+
+                Ok(BalanceReport {
+                    account: name,
+                    address: account_address.to_string(),
+                    amount,
+                })
+            },
+        )
+        .collect::<CliResult<Vec<BalanceReport>>>()?;
+
+    Ok(Report {
+        schema_version: EXPORT_SCHEMA_VERSION,
+        program_id: leancoin::ID.to_string(),
+        slot,
+        timestamp,
+        contract_state,
+        vesting_state,
+        months_since_start,
+        vesting,
+        balances,
+        accounts,
+    })
+}
+
+/// Flattens the JSON value into the rows of field paths and values, e.g. `wallets.0.initial_balance`.
+fn flatten(path: &str, value: &Value, rows: &mut Vec<(String, String)>) {
+    let child_path = |key: &str| match path {
+        "" => key.to_string(),
+        _ => format!("{}.{}", path, key),
+    };
+
+    match value {
+        Value::Object(fields) => fields
+            .iter()
+            .for_each(|(key, value)| flatten(&child_path(key), value, rows)),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .for_each(|(index, value)| flatten(&child_path(&index.to_string()), value, rows)),
+        Value::String(value) => rows.push((path.to_string(), value.clone())),
+        Value::Null => rows.push((path.to_string(), String::new())),
+        value => rows.push((path.to_string(), value.to_string())),
+    }
+}
+
+/// Returns the CSV file with the given header and rows, the values never contain commas.
+fn csv(header: &str, rows: impl Iterator<Item = String>) -> String {
+    let mut content = format!("{}\n", header);
+    for row in rows {
+        content.push_str(&row);
+        content.push('\n');
+    }
+
+    content
+}
+
+/// Converts the optional value to the CSV value, `None` is an empty value.
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+/// Returns the names and the contents of the exported files.
+/// The JSON format is a single `report.json` file, the CSV format consists of the following files:
+/// - `metadata.csv` - the schema version, the program id, the slot and the timestamp of the export,
+/// - `state.csv` - the fields of the contract and the vesting state as `account,field,value` rows,
+/// - `vesting.csv` - the vesting figures of the vested wallets derived at the timestamp of the export,
+/// - `balances.csv` - the supply of the mint and the balances of the program token accounts,
+/// - `accounts.csv` - all accounts owned by the contract, the data of the accounts missing in `state.csv` is base64 encoded.
+///
+/// ### Arguments
+///
+/// * `report` - the report to export
+/// * `format` - the format of the files
+///
+/// ### Returns
+/// The names and the contents of the files
+pub fn export_files(
+    report: &Report,
+    format: ExportFormat,
+) -> CliResult<Vec<(&'static str, String)>> {
+    if format == ExportFormat::Json {
+        return Ok(vec![(
+            "report.json",
+            format!("{}\n", serde_json::to_string_pretty(report)?),
+        )]);
+    }
+
+    let mut state_rows = vec![];
+    for (account, state) in [
+        (
+            "contract_state",
+            serde_json::to_value(&report.contract_state)?,
+        ),
+        (
+            "vesting_state",
+            serde_json::to_value(&report.vesting_state)?,
+        ),
+    ] {
+        if state.is_null() {
+            continue;
+        }
+        let mut rows = vec![];
+        flatten("", &state, &mut rows);
+        state_rows.extend(
+            rows.into_iter()
+                .map(|(field, value)| format!("{},{},{}", account, field, value)),
+        );
+    }
+
+    Ok(vec![
+        (
+            "metadata.csv",
+            csv(
+                "schema_version,program_id,slot,timestamp,months_since_start",
+                std::iter::once(format!(
+                    "{},{},{},{},{}",
+                    report.schema_version,
+                    report.program_id,
+                    report.slot,
+                    report.timestamp,
+                    optional(report.months_since_start)
+                )),
+            ),
+        ),
+        (
+            "state.csv",
+            csv("account,field,value", state_rows.into_iter()),
+        ),
+        (
+            "vesting.csv",
+            csv(
+                "wallet_kind,initial_balance,unlocked,already_withdrawn,available,locked,finalized",
+                report.vesting.iter().map(|wallet| {
+                    format!(
+                        "{:?},{},{},{},{},{},{}",
+                        wallet.wallet_kind,
+                        wallet.initial_balance,
+                        wallet.unlocked,
+                        wallet.already_withdrawn,
+                        wallet.available,
+                        wallet.locked,
+                        wallet.finalized
+                    )
+                }),
+            ),
+        ),
+        (
+            "balances.csv",
+            csv(
+                "account,address,amount",
+                report.balances.iter().map(|balance| {
+                    format!(
+                        "{},{},{}",
+                        balance.account,
+                        balance.address,
+                        optional(balance.amount)
+                    )
+                }),
+            ),
+        ),
+        (
+            "accounts.csv",
+            csv(
+                "address,kind,lamports,data_len,data",
+                report.accounts.iter().map(|account| {
+                    format!(
+                        "{},{},{},{},{}",
+                        account.address,
+                        account.kind,
+                        account.lamports,
+                        account.data_len,
+                        account.data.as_deref().unwrap_or_default()
+                    )
+                }),
+            ),
+        ),
+    ])
+}
+
+/// Exports the state of the contract at the current slot of the cluster to the files in the given directory.
+pub fn export(url: &str, args: ExportArgs) -> CliResult<()> {
+    let client = RpcClient::new(url.to_string());
+    let clock: Clock = solana_sdk::account::from_account(&client.get_account(&sysvar::clock::ID)?)
+        .ok_or("Invalid clock sysvar account")?;
+
+    let program_accounts = client.get_program_accounts(&leancoin::ID)?;
+    let addresses = balance_addresses();
+    let accounts = client.get_multiple_accounts(
+        &addresses
+            .iter()
+            .map(|(_, address)| *address)
+            .collect::<Vec<Pubkey>>(),
+    )?;
+    let balance_accounts = addresses
+        .into_iter()
+        .zip(accounts)
+        .map(|((name, address), account)| (name, address, account))
+        .collect::<Vec<(&'static str, Pubkey, Option<Account>)>>();
+
+    let report = build_report(
+        clock.slot,
+        clock.unix_timestamp,
+        program_accounts,
+        &balance_accounts,
+    )?;
+
+    fs::create_dir_all(&args.out)?;
+    for (name, content) in export_files(&report, args.format)? {
+        let path = args.out.join(name);
+        fs::write(&path, content)?;
+        println!("Written {}", path.display());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::AccountSerialize;
+    use anchor_spl::token::spl_token::state::AccountState;
+    use leancoin::account::{LegacyVestingState, CONTRACT_STATE_VERSION};
+    use leancoin::{
+        DEFAULT_BURN_RATE_BPS, DEFAULT_BURN_WINDOW_DAYS, DEFAULT_LATE_BURN_WINDOW_FIRST_DAY,
+        DEFAULT_LATE_BURN_WINDOW_LAST_DAY, MAX_KEEPERS, MIGRATION_SOURCES_COUNT,
+    };
+
+    // Sunday, 5 March 2023 01:01:01
+    const START_TIMESTAMP: i64 = 1677978061;
+
+    fn account(data: Vec<u8>, owner: Pubkey) -> Account {
+        Account {
+            lamports: 1000,
+            data,
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    fn contract_state_data(authority: Pubkey) -> Vec<u8> {
+        let contract_state = ContractState {
+            version: CONTRACT_STATE_VERSION,
+            authority,
+            performed_imports: 1,
+            wallets_initialized: true,
+            cpi_allowed: false,
+            staking_enabled: false,
+            configuration_locked: true,
+            contract_state_nonce: 255,
+            mint_nonce: 254,
+            program_account_nonce: 253,
+            burning_account_nonce: 252,
+            sequence: 7,
+            max_supply: 10000000000000000000,
+            total_donated: 0,
+            imported_minted: [10000000000000000000; MIGRATION_SOURCES_COUNT],
+            imported_burned: [1470000000000000000; MIGRATION_SOURCES_COUNT],
+            last_burning_year: 2023,
+            last_burning_month: 3,
+            metadata_program_id: Pubkey::default(),
+            burn_remainder_numerator: 0,
+            withdrawal_grace_period_seconds: 0,
+            keepers: [Pubkey::default(); MAX_KEEPERS],
+            late_burn_window_first_day: DEFAULT_LATE_BURN_WINDOW_FIRST_DAY,
+            late_burn_window_last_day: DEFAULT_LATE_BURN_WINDOW_LAST_DAY,
+            paused: false,
+            renounced: false,
+            burn_authority: Pubkey::default(),
+            withdraw_authority: Pubkey::default(),
+            metadata_authority: Pubkey::default(),
+            mint_authority_revoked: false,
+            decimals: 9,
+            mint: Pubkey::default(),
+            burn_rate_bps: DEFAULT_BURN_RATE_BPS,
+            burn_window_days: DEFAULT_BURN_WINDOW_DAYS,
+            burn_caller_reward_bps: 0,
+            total_donated_for_burning: 0,
+            total_burned: 0,
+        };
+
+        let mut data = vec![];
+        contract_state.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn vesting_state_data() -> Vec<u8> {
+        let vesting_state = VestingState::from(LegacyVestingState {
+            vesting_state_nonce: 255,
+            community_wallet_nonce: 254,
+            initial_community_wallet_balance: 1000000000000000000,
+            already_withdrawn_community_wallet_amount: 25000000000000000,
+            community_beneficiary_owner: Pubkey::default(),
+            partnership_wallet_nonce: 253,
+            initial_partnership_wallet_balance: 2000000000000000000,
+            already_withdrawn_partnership_wallet_amount: 0,
+            partnership_beneficiary_owner: Pubkey::default(),
+            marketing_wallet_nonce: 252,
+            initial_marketing_wallet_balance: 1500000000000000000,
+            already_withdrawn_marketing_wallet_amount: 0,
+            marketing_beneficiary_owner: Pubkey::default(),
+            liquidity_wallet_nonce: 251,
+            initial_liquidity_wallet_balance: 1000000000000000000,
+            already_withdrawn_liquidity_wallet_amount: 1,
+            liquidity_beneficiary_owner: Pubkey::default(),
+            liquidity_drip_recipient: Pubkey::default(),
+            liquidity_drip_amount_per_month: 0,
+            last_liquidity_drip_month: 0,
+            last_liquidity_drip_year: 0,
+            finalized_wallets: 0,
+            start_timestamp: START_TIMESTAMP,
+        });
+
+        let mut data = VestingState::discriminator().to_vec();
+        data.extend_from_slice(anchor_lang::__private::bytemuck::bytes_of(&vesting_state));
+        data
+    }
+
+    fn token_account_data(amount: u64) -> Vec<u8> {
+        let mut data = vec![0; TokenAccount::LEN];
+        TokenAccount {
+            mint: address(MINT_SEED),
+            owner: address(PROGRAM_ACCOUNT_SEED),
+            amount,
+            state: AccountState::Initialized,
+            ..TokenAccount::default()
+        }
+        .pack_into_slice(&mut data);
+        data
+    }
+
+    fn mint_data(supply: u64) -> Vec<u8> {
+        let mut data = vec![0; Mint::LEN];
+        Mint {
+            supply,
+            decimals: 9,
+            is_initialized: true,
+            ..Mint::default()
+        }
+        .pack_into_slice(&mut data);
+        data
+    }
+
+    /// The report at the vesting start, the liquidity wallet has been finalized and its token account closed.
+    fn report_fixture(authority: Pubkey, unknown_account: Pubkey) -> Report {
+        let program_accounts = vec![
+            (
+                address(CONTRACT_STATE_SEED),
+                account(contract_state_data(authority), leancoin::ID),
+            ),
+            (
+                address(VESTING_STATE_SEED),
+                account(vesting_state_data(), leancoin::ID),
+            ),
+            // an account type added after the export, e.g. a receipt
+            (unknown_account, account(vec![1, 2, 3], leancoin::ID)),
+        ];
+        let token_program = anchor_spl::token::ID;
+        let balances = [
+            8530000000000000000,
+            1230000000000000000,
+            1800000000000000000,
+            975000000000000000,
+            2000000000000000000,
+            1500000000000000000,
+        ];
+        let balance_accounts = balance_addresses()
+            .into_iter()
+            .enumerate()
+            .map(|(index, (name, address))| {
+                let data = match index {
+                    0 => Some(mint_data(balances[index])),
+                    6 => None,
+                    _ => Some(token_account_data(balances[index])),
+                };
+
+                (name, address, data.map(|data| account(data, token_program)))
+            })
+            .collect::<Vec<(&'static str, Pubkey, Option<Account>)>>();
+
+        build_report(100, START_TIMESTAMP, program_accounts, &balance_accounts).unwrap()
+    }
+
+    fn file(files: &[(&'static str, String)], name: &str) -> String {
+        files
+            .iter()
+            .find(|(file_name, _)| *file_name == name)
+            .unwrap()
+            .1
+            .clone()
+    }
+
+    #[test]
+    fn test_export_csv_snapshot() {
+        let authority = Pubkey::new_from_array([1; 32]);
+        let unknown_account = Pubkey::new_from_array([2; 32]);
+        let report = report_fixture(authority, unknown_account);
+
+        let files = export_files(&report, ExportFormat::Csv).unwrap();
+
+        assert_eq!(
+            files.iter().map(|(name, _)| *name).collect::<Vec<&str>>(),
+            vec![
+                "metadata.csv",
+                "state.csv",
+                "vesting.csv",
+                "balances.csv",
+                "accounts.csv"
+            ]
+        );
+        assert_eq!(
+            file(&files, "metadata.csv"),
+            format!(
+                "schema_version,program_id,slot,timestamp,months_since_start\n1,{},100,1677978061,0\n",
+                leancoin::ID
+            )
+        );
+        assert_eq!(
+            file(&files, "vesting.csv"),
+            "wallet_kind,initial_balance,unlocked,already_withdrawn,available,locked,finalized\n\
+             Community,1000000000000000000,25000000000000000,25000000000000000,0,975000000000000000,false\n\
+             Partnership,2000000000000000000,0,0,0,2000000000000000000,false\n\
+             Marketing,1500000000000000000,0,0,0,1500000000000000000,false\n\
+             Liquidity,1000000000000000000,500000000000000000,1,499999999999999999,500000000000000000,false\n"
+        );
+        let addresses = balance_addresses();
+        assert_eq!(
+            file(&files, "balances.csv"),
+            format!(
+                "account,address,amount\n\
+                 mint,{},8530000000000000000\n\
+                 program_account,{},1230000000000000000\n\
+                 burning_account,{},1800000000000000000\n\
+                 community_account,{},975000000000000000\n\
+                 partnership_account,{},2000000000000000000\n\
+                 marketing_account,{},1500000000000000000\n\
+                 liquidity_account,{},\n",
+                addresses[0].1,
+                addresses[1].1,
+                addresses[2].1,
+                addresses[3].1,
+                addresses[4].1,
+                addresses[5].1,
+                addresses[6].1
+            )
+        );
+
+        let state = file(&files, "state.csv");
+        assert!(state.starts_with("account,field,value\n"));
+        assert!(state.contains(&format!("contract_state,authority,{}\n", authority)));
+        assert!(state.contains("contract_state,imported_burned.0,1470000000000000000\n"));
+        assert!(state.contains("vesting_state,wallets.1.initial_balance,2000000000000000000\n"));
+        assert!(state.contains(&format!(
+            "vesting_state,start_timestamp,{}\n",
+            START_TIMESTAMP
+        )));
+
+        let accounts = file(&files, "accounts.csv");
+        assert!(accounts.starts_with("address,kind,lamports,data_len,data\n"));
+        assert!(accounts.contains(&format!(
+            "{},contract_state,1000,{},\n",
+            address(CONTRACT_STATE_SEED),
+            contract_state_data(authority).len()
+        )));
+        assert!(accounts.contains(&format!("{},unknown,1000,3,AQID\n", unknown_account)));
+        assert_eq!(accounts.lines().count(), 4);
+    }
+
+    #[test]
+    fn test_export_json_snapshot() {
+        let authority = Pubkey::new_from_array([1; 32]);
+        let unknown_account = Pubkey::new_from_array([2; 32]);
+        let report = report_fixture(authority, unknown_account);
+
+        let files = export_files(&report, ExportFormat::Json).unwrap();
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].0, "report.json");
+        let json: Value = serde_json::from_str(&files[0].1).unwrap();
+        assert_eq!(json["schema_version"], EXPORT_SCHEMA_VERSION);
+        assert_eq!(json["program_id"], leancoin::ID.to_string());
+        assert_eq!(json["slot"], 100);
+        assert_eq!(json["timestamp"], START_TIMESTAMP);
+        assert_eq!(json["months_since_start"], 0);
+        assert_eq!(json["contract_state"]["authority"], authority.to_string());
+        assert_eq!(
+            json["vesting_state"]["wallets"][3]["initial_balance"],
+            1000000000000000000u64
+        );
+        assert_eq!(
+            json["vesting"][0],
+            serde_json::json!({
+                "wallet_kind": "Community",
+                "initial_balance": 1000000000000000000u64,
+                "unlocked": 25000000000000000u64,
+                "already_withdrawn": 25000000000000000u64,
+                "available": 0,
+                "locked": 975000000000000000u64,
+                "finalized": false,
+            })
+        );
+        assert_eq!(
+            json["balances"][6],
+            serde_json::json!({
+                "account": "liquidity_account",
+                "address": wallet_address(WalletKind::Liquidity).to_string(),
+                "amount": null,
+            })
+        );
+        assert_eq!(json["accounts"].as_array().unwrap().len(), 3);
+        assert!(json["accounts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|account| account["kind"] != "unknown" || account["data"] == "AQID"));
+    }
+
+    #[test]
+    fn test_account_kind() {
+        assert_eq!(account_kind(&vesting_state_data()), "vesting_state");
+        assert_eq!(
+            account_kind(&WithdrawalIntent::discriminator()),
+            "withdrawal_intent"
+        );
+        assert_eq!(
+            account_kind(&WithdrawalReceipt::discriminator()),
+            "withdrawal_receipt"
+        );
+        assert_eq!(account_kind(&[0; 8]), "unknown");
+        assert_eq!(account_kind(&[]), "unknown");
+    }
+
+    #[test]
+    fn test_parse_export_format() {
+        assert_eq!(parse_export_format("csv").unwrap(), ExportFormat::Csv);
+        assert!(parse_export_format("xml").is_err());
+    }
+}
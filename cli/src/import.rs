@@ -0,0 +1,243 @@
+use std::fs;
+
+use clap::Args;
+use leancoin::{AccountInfoFromEthereum, MigrationSource};
+use leancoin_sdk::lookup_table;
+use serde_json::Value;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::Signer,
+    transaction::{Transaction, VersionedTransaction},
+};
+
+use crate::utils::{signer_from_path, CliResult};
+
+/// Arguments of the create-lookup-table command.
+#[derive(Args)]
+pub struct CreateLookupTableArgs {
+    /// Path to the JSON file with the entries of the import
+    #[clap(long)]
+    entries: String,
+
+    /// Existing lookup table to extend by the addresses missing in it, a new one is created if not set
+    #[clap(long)]
+    lookup_table: Option<Pubkey>,
+
+    /// Authority of the lookup table and the payer: path to the keypair file or hardware wallet URL, e.g. usb://ledger
+    #[clap(long)]
+    signer: String,
+}
+
+/// Arguments of the import command.
+#[derive(Args)]
+pub struct ImportArgs {
+    /// Migration source: ethereum or bsc
+    #[clap(long, value_parser = parse_migration_source)]
+    source: MigrationSource,
+
+    /// Path to the JSON file with the entries of the import
+    #[clap(long)]
+    entries: String,
+
+    /// Amount of tokens to mint, expressed with decimals of the source token
+    #[clap(long)]
+    amount_to_mint: u128,
+
+    /// Amount of tokens to burn, expressed with decimals of the source token
+    #[clap(long, default_value = "0")]
+    amount_to_burn: u128,
+
+    /// Number of decimals of the source token
+    #[clap(long)]
+    source_decimals: u8,
+
+    /// Number of months before the first unlock of the community wallet
+    #[clap(long, default_value = "0")]
+    community_unlock_delay_months: u8,
+
+    /// Number of seconds after the import during which no vested wallet can withdraw tokens, at most 90 days
+    #[clap(long, default_value = "0")]
+    withdrawal_grace_period_seconds: i64,
+
+    /// Lookup table containing the accounts of the import, see the create-lookup-table command
+    #[clap(long)]
+    lookup_table: Pubkey,
+
+    /// Contract's authority: path to the keypair file or hardware wallet URL, e.g. usb://ledger
+    #[clap(long)]
+    signer: String,
+
+    /// Only simulates the transaction without sending it
+    #[clap(long)]
+    dry_run: bool,
+}
+
+/// Parses the name of the migration source.
+pub fn parse_migration_source(source: &str) -> Result<MigrationSource, String> {
+    match source {
+        "ethereum" => Ok(MigrationSource::Ethereum),
+        "bsc" => Ok(MigrationSource::Bsc),
+        _ => Err(format!(
+            "Unknown migration source {}, expected ethereum or bsc",
+            source
+        )),
+    }
+}
+
+/// Parses the entries of the import.
+/// The entries are a JSON array of objects with `wallet_name`, `account_public_key` and `account_balance` fields,
+/// the balance is a string because it does not fit into a JSON number.
+///
+/// ### Arguments
+///
+/// * `json` - the content of the entries file
+///
+/// ### Returns
+/// The entries of the import
+pub fn parse_import_entries(json: &str) -> CliResult<Vec<AccountInfoFromEthereum>> {
+    let entries: Vec<Value> = serde_json::from_str(json)?;
+
+    entries
+        .iter()
+        .map(|entry| -> CliResult<AccountInfoFromEthereum> {
+            let field = |name: &str| {
+                entry[name]
+                    .as_str()
+                    .ok_or_else(|| format!("Missing {} of the entry {}", name, entry))
+            };
+
+            Ok(AccountInfoFromEthereum {
+                wallet_name: field("wallet_name")?.to_string(),
+                account_public_key: field("account_public_key")?.parse()?,
+                account_balance: field("account_balance")?.parse()?,
+            })
+        })
+        .collect()
+}
+
+/// Creates the lookup table containing the accounts of the import or extends the existing one.
+/// Every instruction is sent in its own transaction, the table can be used in the slots following the last one.
+pub fn create_lookup_table(url: &str, args: CreateLookupTableArgs) -> CliResult<()> {
+    let client = RpcClient::new(url.to_string());
+    let entries = parse_import_entries(&fs::read_to_string(&args.entries)?)?;
+    let addresses = lookup_table::import_addresses(&entries);
+    let signer = signer_from_path(&args.signer, "authority")?;
+
+    let (lookup_table, instructions) = match args.lookup_table {
+        Some(lookup_table) => {
+            let data = client.get_account_data(&lookup_table)?;
+            let existing_addresses =
+                lookup_table::lookup_table_account(&lookup_table, &data)?.addresses;
+            let missing_addresses = addresses
+                .into_iter()
+                .filter(|address| !existing_addresses.contains(address))
+                .collect::<Vec<Pubkey>>();
+
+            (
+                lookup_table,
+                lookup_table::extend_lookup_table(
+                    &lookup_table,
+                    &signer.pubkey(),
+                    &signer.pubkey(),
+                    &missing_addresses,
+                ),
+            )
+        }
+        None => lookup_table::create_lookup_table(
+            &signer.pubkey(),
+            &signer.pubkey(),
+            client.get_slot()?,
+            &addresses,
+        ),
+    };
+
+    for instruction in instructions {
+        let mut transaction = Transaction::new_with_payer(&[instruction], Some(&signer.pubkey()));
+        transaction.try_sign(&[signer.as_ref()], client.get_latest_blockhash()?)?;
+
+        let signature = client.send_and_confirm_transaction(&transaction)?;
+        println!("Signature: {}", signature);
+    }
+
+    println!("Lookup table: {}", lookup_table);
+
+    Ok(())
+}
+
+/// Imports the token state of the migration source in a versioned transaction loading its accounts from the lookup table.
+pub fn import(url: &str, args: ImportArgs) -> CliResult<()> {
+    let client = RpcClient::new(url.to_string());
+    let entries = parse_import_entries(&fs::read_to_string(&args.entries)?)?;
+    let signer = signer_from_path(&args.signer, "authority")?;
+
+    let data = client.get_account_data(&args.lookup_table)?;
+    let lookup_table_account = lookup_table::lookup_table_account(&args.lookup_table, &data)?;
+
+    let message = lookup_table::import_message(
+        &signer.pubkey(),
+        args.source,
+        entries,
+        args.amount_to_mint,
+        args.amount_to_burn,
+        args.source_decimals,
+        args.community_unlock_delay_months,
+        args.withdrawal_grace_period_seconds,
+        &[lookup_table_account],
+        client.get_latest_blockhash()?,
+    )?;
+    let transaction = VersionedTransaction::try_new(message, &[signer.as_ref()])?;
+
+    if args.dry_run {
+        let simulation = client.simulate_transaction(&transaction)?.value;
+        for log in simulation.logs.unwrap_or_default() {
+            println!("  {}", log);
+        }
+        match simulation.err {
+            Some(error) => return Err(format!("Simulation failed: {}", error).into()),
+            None => println!("Simulation succeeded, the transaction has not been sent"),
+        }
+    } else {
+        let signature = client.send_and_confirm_transaction(&transaction)?;
+        println!("Signature: {}", signature);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_import_entries() {
+        let account_public_key = Pubkey::new_unique();
+        let json = format!(
+            r#"[{{"wallet_name": "holder", "account_public_key": "{}", "account_balance": "340282366920938463463374607431768211455"}}]"#,
+            account_public_key
+        );
+
+        let entries = parse_import_entries(&json).unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].wallet_name, "holder");
+        assert_eq!(entries[0].account_public_key, account_public_key);
+        assert_eq!(entries[0].account_balance, u128::MAX);
+    }
+
+    #[test]
+    fn test_fail_parse_import_entries_with_numeric_balance() {
+        let json = format!(
+            r#"[{{"wallet_name": "holder", "account_public_key": "{}", "account_balance": 1}}]"#,
+            Pubkey::new_unique()
+        );
+
+        assert!(parse_import_entries(&json).is_err());
+    }
+
+    #[test]
+    fn test_parse_migration_source() {
+        assert_eq!(parse_migration_source("bsc").unwrap(), MigrationSource::Bsc);
+        assert!(parse_migration_source("solana").is_err());
+    }
+}
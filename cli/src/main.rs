@@ -0,0 +1,71 @@
+//! Leancoin admin CLI
+
+mod authority;
+mod decode;
+mod export;
+mod import;
+mod metadata;
+mod transaction;
+mod utils;
+mod watch;
+mod withdraw;
+
+use clap::{Parser, Subcommand};
+
+/// Command line tool to administer the Leancoin contract.
+#[derive(Parser)]
+#[clap(name = "leancoin-admin", version)]
+struct Cli {
+    /// URL of the Solana JSON RPC endpoint
+    #[clap(long, global = true, default_value = "http://localhost:8899")]
+    url: String,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Creates or updates the token metadata
+    SetMetadata(metadata::SetMetadataArgs),
+    /// Changes the contract's authority
+    RotateAuthority(authority::RotateAuthorityArgs),
+    /// Decodes raw account data offline and prints it as JSON
+    Decode(decode::DecodeArgs),
+    /// Creates or extends the address lookup table containing the accounts of an import
+    CreateLookupTable(import::CreateLookupTableArgs),
+    /// Imports the token state of a migration source in a versioned transaction using the lookup table
+    Import(import::ImportArgs),
+    /// Withdraws vested tokens from a vested wallet
+    Withdraw(withdraw::WithdrawArgs),
+    /// Signs the transaction written with --unsigned-out, e.g. on an air-gapped machine
+    Sign(transaction::SignArgs),
+    /// Sends the transaction signed with the sign command
+    Submit(transaction::SubmitArgs),
+    /// Exports the state of the contract with the derived vesting figures to JSON or CSV files
+    Export(export::ExportArgs),
+    /// Subscribes to the contract's logs via the websocket endpoint and prints the emitted events
+    Watch(watch::WatchArgs),
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::SetMetadata(args) => metadata::set_metadata(&cli.url, args),
+        Command::RotateAuthority(args) => authority::rotate_authority(&cli.url, args),
+        Command::Decode(args) => decode::decode(args),
+        Command::CreateLookupTable(args) => import::create_lookup_table(&cli.url, args),
+        Command::Import(args) => import::import(&cli.url, args),
+        Command::Withdraw(args) => withdraw::withdraw(&cli.url, args),
+        Command::Sign(args) => transaction::sign(args),
+        Command::Submit(args) => transaction::submit(&cli.url, args),
+        Command::Export(args) => export::export(&cli.url, args),
+        Command::Watch(args) => watch::watch(&cli.url, args),
+    };
+
+    if let Err(error) = result {
+        eprintln!("Error: {}", error);
+        std::process::exit(1);
+    }
+}
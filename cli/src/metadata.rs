@@ -0,0 +1,224 @@
+use anchor_lang::{InstructionData, ToAccountMetas};
+use clap::Args;
+use leancoin::context::__client_accounts_set_token_metadata_context::SetTokenMetadataContext;
+use leancoin::context::__client_accounts_update_token_metadata_context::UpdateTokenMetadataContext;
+use mpl_token_metadata::state::{
+    Metadata, TokenMetadataAccount, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH,
+};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Signer, system_program};
+
+use crate::transaction::{process_instructions, TransactionArgs};
+use crate::utils::{fetch_contract_state, find_program_address, signer_from_path, CliResult};
+
+/// Arguments of the set-metadata command.
+#[derive(Args)]
+pub struct SetMetadataArgs {
+    /// Token name
+    #[clap(long)]
+    name: String,
+
+    /// Token symbol
+    #[clap(long)]
+    symbol: String,
+
+    /// Token metadata URI
+    #[clap(long)]
+    uri: String,
+
+    /// Contract's authority: path to the keypair file, hardware wallet URL, e.g. usb://ledger,
+    /// or its public key when the transaction is only written with --unsigned-out
+    #[clap(long)]
+    keypair: String,
+
+    /// Updates the existing metadata instead of creating it
+    #[clap(long)]
+    update: bool,
+
+    #[clap(flatten)]
+    transaction: TransactionArgs,
+}
+
+/// Validates the token metadata against the limits of the Metaplex token metadata program.
+///
+/// ### Arguments
+///
+/// * `name` - token name
+/// * `symbol` - token symbol
+/// * `uri` - token metadata URI
+///
+/// ### Returns
+/// An error describing the first exceeded limit, otherwise a successful result.
+pub fn validate_metadata(name: &str, symbol: &str, uri: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("Name must not be empty".to_string());
+    }
+    if name.len() > MAX_NAME_LENGTH {
+        return Err(format!(
+            "Name is {} bytes long, the limit is {} bytes",
+            name.len(),
+            MAX_NAME_LENGTH
+        ));
+    }
+    if symbol.len() > MAX_SYMBOL_LENGTH {
+        return Err(format!(
+            "Symbol is {} bytes long, the limit is {} bytes",
+            symbol.len(),
+            MAX_SYMBOL_LENGTH
+        ));
+    }
+    if uri.len() > MAX_URI_LENGTH {
+        return Err(format!(
+            "URI is {} bytes long, the limit is {} bytes",
+            uri.len(),
+            MAX_URI_LENGTH
+        ));
+    }
+
+    Ok(())
+}
+
+/// Finds the address of the metadata account of the given mint.
+///
+/// ### Arguments
+///
+/// * `metadata_program_id` - the token metadata program id stored in the contract state
+/// * `mint` - the mint address
+///
+/// ### Returns
+/// The address of the metadata account
+pub fn find_metadata_pda(metadata_program_id: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", metadata_program_id.as_ref(), mint.as_ref()],
+        metadata_program_id,
+    )
+    .0
+}
+
+/// Creates or updates the token metadata and prints the resulting metadata account once the transaction is sent.
+pub fn set_metadata(url: &str, args: SetMetadataArgs) -> CliResult<()> {
+    validate_metadata(&args.name, &args.symbol, &args.uri)?;
+
+    let client = RpcClient::new(url.to_string());
+    let authority = signer_from_path(&args.keypair, "authority")?;
+
+    let contract_state = fetch_contract_state(&client)?;
+    let mint = find_program_address(leancoin::MINT_SEED);
+    let metadata_pda = find_metadata_pda(&contract_state.metadata_program_id, &mint);
+
+    let instruction = if args.update {
+        let data = leancoin::instruction::UpdateTokenMetadata {
+            name: args.name,
+            symbol: args.symbol,
+            uri: args.uri,
+        }
+        .data();
+
+        let accounts = UpdateTokenMetadataContext {
+            contract_state: find_program_address(leancoin::CONTRACT_STATE_SEED),
+            mint,
+            metadata_pda,
+            metadata_program: contract_state.metadata_program_id,
+            signer: authority.pubkey(),
+        };
+
+        Instruction::new_with_bytes(leancoin::ID, &data, accounts.to_account_metas(None))
+    } else {
+        let data = leancoin::instruction::SetTokenMetadata {
+            name: args.name,
+            symbol: args.symbol,
+            uri: args.uri,
+        }
+        .data();
+
+        let accounts = SetTokenMetadataContext {
+            contract_state: find_program_address(leancoin::CONTRACT_STATE_SEED),
+            mint,
+            metadata_pda,
+            metadata_program: contract_state.metadata_program_id,
+            signer: authority.pubkey(),
+            system_program: system_program::ID,
+            token_program: anchor_spl::token::ID,
+        };
+
+        Instruction::new_with_bytes(leancoin::ID, &data, accounts.to_account_metas(None))
+    };
+    if process_instructions(
+        &client,
+        &[instruction],
+        authority.as_ref(),
+        &args.transaction,
+    )?
+    .is_none()
+    {
+        return Ok(());
+    }
+
+    let metadata = Metadata::safe_deserialize(&client.get_account_data(&metadata_pda)?)?;
+    println!("Metadata account: {}", metadata_pda);
+    println!("  Name: {}", metadata.data.name.trim_end_matches('\0'));
+    println!("  Symbol: {}", metadata.data.symbol.trim_end_matches('\0'));
+    println!("  URI: {}", metadata.data.uri.trim_end_matches('\0'));
+    println!("  Update authority: {}", metadata.update_authority);
+    println!("  Mutable: {}", metadata.is_mutable);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_validate_metadata() {
+        validate_metadata("Leancoin", "LEAN", "https://leancoin.io/metadata.json").unwrap();
+    }
+
+    #[test]
+    fn test_validate_metadata_at_limits() {
+        validate_metadata(
+            &"n".repeat(MAX_NAME_LENGTH),
+            &"s".repeat(MAX_SYMBOL_LENGTH),
+            &"u".repeat(MAX_URI_LENGTH),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_fail_validate_metadata_empty_name() {
+        assert!(validate_metadata("", "LEAN", "https://leancoin.io").is_err());
+    }
+
+    #[test]
+    fn test_fail_validate_metadata_too_long_name() {
+        assert!(validate_metadata(&"n".repeat(MAX_NAME_LENGTH + 1), "LEAN", "").is_err());
+    }
+
+    #[test]
+    fn test_fail_validate_metadata_too_long_symbol() {
+        assert!(validate_metadata("Leancoin", &"s".repeat(MAX_SYMBOL_LENGTH + 1), "").is_err());
+    }
+
+    #[test]
+    fn test_fail_validate_metadata_too_long_uri() {
+        assert!(validate_metadata("Leancoin", "LEAN", &"u".repeat(MAX_URI_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_find_metadata_pda() {
+        let mint = Pubkey::new_unique();
+        let (expected, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::id().to_bytes(),
+                &mint.to_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
+
+        assert_eq!(
+            find_metadata_pda(&mpl_token_metadata::id(), &mint),
+            expected
+        );
+    }
+}
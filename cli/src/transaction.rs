@@ -0,0 +1,443 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use clap::Args;
+use solana_client::{nonce_utils, rpc_client::RpcClient};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    instruction::Instruction,
+    message::Message,
+    pubkey::Pubkey,
+    signature::{Signature, Signer},
+    transaction::Transaction,
+};
+
+use crate::utils::{signer_from_path, CliResult};
+
+/// Arguments shared by the commands sending a transaction.
+#[derive(Args)]
+pub struct TransactionArgs {
+    /// Durable nonce account whose nonce is used instead of a recent blockhash, so the transaction does not expire
+    #[clap(long)]
+    nonce_account: Option<Pubkey>,
+
+    /// Authority of the nonce account: path to the keypair file, hardware wallet URL or its public key when the
+    /// transaction is only written with --unsigned-out, the signer of the command is the authority if not set
+    #[clap(long, requires = "nonce_account")]
+    nonce_authority: Option<String>,
+
+    /// Writes the unsigned transaction to the file instead of sending it, see the sign and submit commands
+    #[clap(long)]
+    unsigned_out: Option<PathBuf>,
+
+    /// Only simulates the transaction without sending it
+    #[clap(long)]
+    dry_run: bool,
+}
+
+/// Arguments of the sign command.
+#[derive(Args)]
+pub struct SignArgs {
+    /// Path to the transaction file written with --unsigned-out
+    transaction: PathBuf,
+
+    /// Signer of the transaction: path to the keypair file or hardware wallet URL, e.g. usb://ledger
+    #[clap(long)]
+    signer: String,
+
+    /// Path to which the signed transaction is written, the transaction file is overwritten if not set
+    #[clap(long)]
+    out: Option<PathBuf>,
+}
+
+/// Arguments of the submit command.
+#[derive(Args)]
+pub struct SubmitArgs {
+    /// Path to the transaction file signed by all signers
+    transaction: PathBuf,
+
+    /// Only simulates the transaction without sending it
+    #[clap(long)]
+    dry_run: bool,
+}
+
+/// Builds the unsigned transaction.
+/// If the durable nonce is used, the instruction advancing the nonce account is the first one
+/// and the nonce is used as the recent blockhash.
+///
+/// ### Arguments
+///
+/// * `instructions` - the instructions of the transaction
+/// * `payer` - the fee payer
+/// * `nonce` - the nonce account and its authority, if the durable nonce is used
+/// * `blockhash` - the recent blockhash or the nonce stored in the nonce account
+///
+/// ### Returns
+/// The unsigned transaction
+pub fn build_transaction(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    nonce: Option<(&Pubkey, &Pubkey)>,
+    blockhash: Hash,
+) -> Transaction {
+    let mut message = match nonce {
+        Some((nonce_account, nonce_authority)) => Message::new_with_nonce(
+            instructions.to_vec(),
+            Some(payer),
+            nonce_account,
+            nonce_authority,
+        ),
+        None => Message::new(instructions, Some(payer)),
+    };
+    message.recent_blockhash = blockhash;
+
+    Transaction::new_unsigned(message)
+}
+
+/// Fetches the nonce stored in the nonce account.
+///
+/// ### Arguments
+///
+/// * `client` - the RPC client
+/// * `nonce_account` - the address of the nonce account
+/// * `nonce_authority` - the expected authority of the nonce account
+///
+/// ### Returns
+/// The nonce or an error if the account is not an initialized nonce account of the given authority
+pub fn fetch_nonce(
+    client: &RpcClient,
+    nonce_account: &Pubkey,
+    nonce_authority: &Pubkey,
+) -> CliResult<Hash> {
+    let account = nonce_utils::get_account_with_commitment(
+        client,
+        nonce_account,
+        CommitmentConfig::confirmed(),
+    )?;
+    let data = nonce_utils::data_from_account(&account)?;
+
+    if data.authority != *nonce_authority {
+        return Err(format!(
+            "The authority of the nonce account is {}, not {}",
+            data.authority, nonce_authority
+        )
+        .into());
+    }
+
+    Ok(data.blockhash())
+}
+
+/// Writes the transaction to the file.
+fn write_transaction(path: &Path, transaction: &Transaction) -> CliResult<()> {
+    fs::write(path, bincode::serialize(transaction)?)?;
+
+    Ok(())
+}
+
+/// Reads the transaction from the file.
+fn read_transaction(path: &Path) -> CliResult<Transaction> {
+    Ok(bincode::deserialize(&fs::read(path)?)?)
+}
+
+/// Simulates or sends the signed transaction.
+///
+/// ### Arguments
+///
+/// * `client` - the RPC client
+/// * `transaction` - the transaction signed by all signers
+/// * `dry_run` - whether to only simulate the transaction
+///
+/// ### Returns
+/// The signature of the sent transaction or `None` if it has only been simulated
+pub fn send_transaction(
+    client: &RpcClient,
+    transaction: &Transaction,
+    dry_run: bool,
+) -> CliResult<Option<Signature>> {
+    if !transaction.is_signed() {
+        return Err("The transaction is not signed by all signers, see the sign command".into());
+    }
+
+    if dry_run {
+        let simulation = client.simulate_transaction(transaction)?.value;
+        for log in simulation.logs.unwrap_or_default() {
+            println!("  {}", log);
+        }
+        return match simulation.err {
+            Some(error) => Err(format!("Simulation failed: {}", error).into()),
+            None => {
+                println!("Simulation succeeded, the transaction has not been sent");
+                Ok(None)
+            }
+        };
+    }
+
+    let signature = client.send_and_confirm_transaction(transaction)?;
+    println!("Signature: {}", signature);
+
+    Ok(Some(signature))
+}
+
+/// Builds the transaction of an admin command and either writes it unsigned to the file or signs and sends it.
+///
+/// ### Arguments
+///
+/// * `client` - the RPC client
+/// * `instructions` - the instructions of the command
+/// * `signer` - the signer of the command, it is also the fee payer
+/// * `args` - the transaction arguments of the command
+///
+/// ### Returns
+/// The signature of the sent transaction or `None` if it has been written to the file or only simulated
+pub fn process_instructions(
+    client: &RpcClient,
+    instructions: &[Instruction],
+    signer: &dyn Signer,
+    args: &TransactionArgs,
+) -> CliResult<Option<Signature>> {
+    let nonce_authority = match &args.nonce_authority {
+        Some(path) => Some(signer_from_path(path, "nonce authority")?),
+        None => None,
+    };
+    let nonce_authority = nonce_authority.as_deref().unwrap_or(signer);
+
+    let (nonce, blockhash) = match &args.nonce_account {
+        Some(nonce_account) => (
+            Some((nonce_account, nonce_authority.pubkey())),
+            fetch_nonce(client, nonce_account, &nonce_authority.pubkey())?,
+        ),
+        None => (None, client.get_latest_blockhash()?),
+    };
+
+    let mut transaction = build_transaction(
+        instructions,
+        &signer.pubkey(),
+        nonce
+            .as_ref()
+            .map(|(nonce_account, nonce_authority)| (*nonce_account, nonce_authority)),
+        blockhash,
+    );
+
+    if let Some(path) = &args.unsigned_out {
+        write_transaction(path, &transaction)?;
+        println!(
+            "Unsigned transaction written to {}, sign it with the sign command and send it with the submit command",
+            path.display()
+        );
+        return Ok(None);
+    }
+
+    let mut signers = vec![signer];
+    if nonce.is_some() && nonce_authority.pubkey() != signer.pubkey() {
+        signers.push(nonce_authority);
+    }
+    transaction.try_sign(&signers, blockhash)?;
+
+    send_transaction(client, &transaction, args.dry_run)
+}
+
+/// Signs the transaction written with --unsigned-out by one of its signers, e.g. on an air-gapped machine.
+pub fn sign(args: SignArgs) -> CliResult<()> {
+    let mut transaction = read_transaction(&args.transaction)?;
+    let signer = signer_from_path(&args.signer, "signer")?;
+
+    let blockhash = transaction.message.recent_blockhash;
+    transaction.try_partial_sign(&[signer.as_ref()], blockhash)?;
+
+    let out = args.out.as_ref().unwrap_or(&args.transaction);
+    write_transaction(out, &transaction)?;
+
+    let missing_signers = transaction
+        .message
+        .account_keys
+        .iter()
+        .zip(&transaction.signatures)
+        .filter(|(_, signature)| **signature == Signature::default())
+        .map(|(signer, _)| signer.to_string())
+        .collect::<Vec<String>>();
+    println!("Signed transaction written to {}", out.display());
+    if !missing_signers.is_empty() {
+        println!("Missing signatures of: {}", missing_signers.join(", "));
+    }
+
+    Ok(())
+}
+
+/// Sends the transaction signed by all signers.
+pub fn submit(url: &str, args: SubmitArgs) -> CliResult<()> {
+    let client = RpcClient::new(url.to_string());
+    let transaction = read_transaction(&args.transaction)?;
+
+    send_transaction(&client, &transaction, args.dry_run)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solana_program_test::{tokio, ProgramTest};
+    use solana_sdk::{
+        nonce::{state::Versions, State},
+        signature::Keypair,
+        system_instruction, system_program,
+    };
+
+    #[test]
+    fn test_build_transaction_with_nonce() {
+        let payer = Pubkey::new_unique();
+        let nonce_account = Pubkey::new_unique();
+        let nonce_authority = Pubkey::new_unique();
+        let nonce = Hash::new_unique();
+        let instruction = system_instruction::transfer(&payer, &Pubkey::new_unique(), 1);
+
+        let transaction = build_transaction(
+            &[instruction.clone()],
+            &payer,
+            Some((&nonce_account, &nonce_authority)),
+            nonce,
+        );
+
+        let message = &transaction.message;
+        assert_eq!(message.recent_blockhash, nonce);
+        assert_eq!(message.account_keys[0], payer);
+        assert_eq!(message.header.num_required_signatures, 2);
+        assert_eq!(message.instructions.len(), 2);
+        assert_eq!(
+            message.instructions[0],
+            message.compile_instruction(&system_instruction::advance_nonce_account(
+                &nonce_account,
+                &nonce_authority
+            ))
+        );
+        assert_eq!(
+            message.instructions[1],
+            message.compile_instruction(&instruction)
+        );
+        assert!(!transaction.is_signed());
+    }
+
+    #[test]
+    fn test_build_transaction_without_nonce() {
+        let payer = Pubkey::new_unique();
+        let blockhash = Hash::new_unique();
+        let instruction = system_instruction::transfer(&payer, &Pubkey::new_unique(), 1);
+
+        let transaction = build_transaction(&[instruction], &payer, None, blockhash);
+
+        assert_eq!(transaction.message.recent_blockhash, blockhash);
+        assert_eq!(transaction.message.instructions.len(), 1);
+        assert_eq!(transaction.message.header.num_required_signatures, 1);
+    }
+
+    #[test]
+    fn test_write_and_partially_sign_transaction() {
+        let payer = Keypair::new();
+        let nonce_authority = Keypair::new();
+        let path = std::env::temp_dir().join("leancoin-unsigned-transaction.bin");
+        let transaction = build_transaction(
+            &[system_instruction::transfer(
+                &payer.pubkey(),
+                &Pubkey::new_unique(),
+                1,
+            )],
+            &payer.pubkey(),
+            Some((&Pubkey::new_unique(), &nonce_authority.pubkey())),
+            Hash::new_unique(),
+        );
+        write_transaction(&path, &transaction).unwrap();
+
+        let mut transaction = read_transaction(&path).unwrap();
+        let blockhash = transaction.message.recent_blockhash;
+        transaction.try_partial_sign(&[&payer], blockhash).unwrap();
+        assert!(!transaction.is_signed());
+        transaction
+            .try_partial_sign(&[&nonce_authority], blockhash)
+            .unwrap();
+
+        transaction.verify().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_with_nonce_account() {
+        let mut context = ProgramTest::default().start_with_context().await;
+        let payer = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+        let nonce_account = Keypair::new();
+        let nonce_authority = Keypair::new();
+        let recipient = Pubkey::new_unique();
+
+        let create_instructions = system_instruction::create_nonce_account(
+            &payer.pubkey(),
+            &nonce_account.pubkey(),
+            &nonce_authority.pubkey(),
+            10_000_000,
+        );
+        let transaction = Transaction::new_signed_with_payer(
+            &create_instructions,
+            Some(&payer.pubkey()),
+            &[&payer, &nonce_account],
+            context.last_blockhash,
+        );
+        context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        let nonce_data = |account: solana_sdk::account::Account| match bincode::deserialize::<
+            Versions,
+        >(&account.data)
+        .unwrap()
+        .state()
+        {
+            State::Initialized(data) => data.clone(),
+            State::Uninitialized => panic!("uninitialized nonce account"),
+        };
+        let account = context
+            .banks_client
+            .get_account(nonce_account.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(account.owner, system_program::ID);
+        let nonce = nonce_data(account);
+        assert_eq!(nonce.authority, nonce_authority.pubkey());
+
+        // the nonce can be advanced only in a later slot
+        context.warp_to_slot(100).unwrap();
+
+        let mut transaction = build_transaction(
+            &[system_instruction::transfer(
+                &payer.pubkey(),
+                &recipient,
+                1_000_000,
+            )],
+            &payer.pubkey(),
+            Some((&nonce_account.pubkey(), &nonce_authority.pubkey())),
+            nonce.blockhash(),
+        );
+        transaction
+            .try_sign(&[&payer, &nonce_authority], nonce.blockhash())
+            .unwrap();
+        context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            context.banks_client.get_balance(recipient).await.unwrap(),
+            1_000_000
+        );
+        let account = context
+            .banks_client
+            .get_account(nonce_account.pubkey())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_ne!(nonce_data(account).blockhash(), nonce.blockhash());
+    }
+}
@@ -0,0 +1,94 @@
+use std::str::FromStr;
+
+use anchor_lang::AccountDeserialize;
+use leancoin::account::{ContractState, VestingState};
+use leancoin::decode::{decode_account, DecodedAccount, DecodedAccountKind};
+use solana_client::rpc_client::RpcClient;
+use solana_remote_wallet::{
+    locator::Locator, remote_keypair::generate_remote_keypair, remote_wallet::maybe_wallet_manager,
+};
+use solana_sdk::{
+    derivation_path::DerivationPath,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Signer},
+    signer::null_signer::NullSigner,
+};
+
+/// The result type used by CLI commands.
+pub type CliResult<T> = Result<T, Box<dyn std::error::Error>>;
+
+/// Finds the address of the program derived account created with the given seed.
+///
+/// ### Arguments
+///
+/// * `seed` - the seed of the account, e.g. `leancoin::MINT_SEED`
+///
+/// ### Returns
+/// The address of the account
+pub fn find_program_address(seed: &str) -> Pubkey {
+    Pubkey::find_program_address(&[seed.as_bytes()], &leancoin::ID).0
+}
+
+/// Fetches and deserializes the contract state.
+///
+/// ### Arguments
+///
+/// * `client` - the RPC client
+///
+/// ### Returns
+/// The contract state
+pub fn fetch_contract_state(client: &RpcClient) -> CliResult<ContractState> {
+    let data = client.get_account_data(&find_program_address(leancoin::CONTRACT_STATE_SEED))?;
+
+    Ok(ContractState::try_deserialize(&mut data.as_slice())?)
+}
+
+/// Fetches and decodes the zero-copy vesting state.
+///
+/// ### Arguments
+///
+/// * `client` - the RPC client
+///
+/// ### Returns
+/// The vesting state
+pub fn fetch_vesting_state(client: &RpcClient) -> CliResult<VestingState> {
+    let data = client.get_account_data(&find_program_address(leancoin::VESTING_STATE_SEED))?;
+
+    match decode_account(DecodedAccountKind::VestingState, &data, None)? {
+        DecodedAccount::VestingState { state, .. } => Ok(state),
+        DecodedAccount::ContractState { .. } => Err("Unexpected account kind".into()),
+    }
+}
+
+/// Loads the signer from the given path.
+/// The path is either a path to a keypair file or a hardware wallet URL, e.g. `usb://ledger`.
+/// A public key is loaded as a signer which does not sign, it is used to build transactions signed offline.
+///
+/// ### Arguments
+///
+/// * `path` - the path to the keypair file or the hardware wallet URL
+/// * `keypair_name` - the name of the keypair displayed by the hardware wallet
+///
+/// ### Returns
+/// The signer
+pub fn signer_from_path(path: &str, keypair_name: &str) -> CliResult<Box<dyn Signer>> {
+    if let Ok(pubkey) = Pubkey::from_str(path) {
+        return Ok(Box::new(NullSigner::new(&pubkey)));
+    }
+
+    if path.starts_with("usb://") {
+        let wallet_manager = maybe_wallet_manager()?.ok_or("No hardware wallet found")?;
+        let locator = Locator::new_from_path(path)?;
+        let keypair = generate_remote_keypair(
+            locator,
+            DerivationPath::default(),
+            &wallet_manager,
+            true,
+            keypair_name,
+        )?;
+
+        Ok(Box::new(keypair))
+    } else {
+        Ok(Box::new(read_keypair_file(path)?))
+    }
+}
@@ -0,0 +1,278 @@
+use std::{thread, time::Duration};
+
+use clap::Args;
+use leancoin::events::{parse_events, LeancoinEvent, EVENT_NAMES};
+use serde_json::Value;
+use solana_client::{
+    pubsub_client::PubsubClient,
+    rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter},
+    rpc_response::RpcLogsResponse,
+};
+use solana_sdk::commitment_config::CommitmentConfig;
+
+use crate::utils::CliResult;
+
+/// Delay before reconnecting to the websocket endpoint after the subscription has been dropped.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Arguments of the watch command.
+#[derive(Args)]
+pub struct WatchArgs {
+    /// Names of the events to print, e.g. BurnExecuted, all events are printed if not set
+    #[clap(long = "event", value_parser = parse_event_name)]
+    events: Vec<String>,
+
+    /// Prints every event as a JSON object in a single line
+    #[clap(long)]
+    json: bool,
+}
+
+/// Parses the name of the event to print.
+pub fn parse_event_name(name: &str) -> Result<String, String> {
+    if EVENT_NAMES.contains(&name) {
+        Ok(name.to_string())
+    } else {
+        Err(format!(
+            "Unknown event {}, expected one of: {}",
+            name,
+            EVENT_NAMES.join(", ")
+        ))
+    }
+}
+
+/// Returns the URL of the websocket endpoint.
+/// The HTTP URL of the JSON RPC endpoint is converted to the websocket one the same way as by the Solana CLI,
+/// i.e. the scheme is changed and the default port 8899 of the local validator is replaced by 8900.
+///
+/// ### Arguments
+///
+/// * `url` - the URL of the websocket or the JSON RPC endpoint
+///
+/// ### Returns
+/// The URL of the websocket endpoint
+pub fn websocket_url(url: &str) -> String {
+    let url = if let Some(rest) = url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        return url.to_string();
+    };
+
+    url.replacen(":8899", ":8900", 1)
+}
+
+/// Formats the event as a line printed by the watch command.
+/// The line is either a JSON object or the signature and the name of the event followed by its fields as `name=value` pairs.
+///
+/// ### Arguments
+///
+/// * `signature` - the signature of the transaction emitting the event
+/// * `event` - the event
+/// * `json` - whether to format the event as JSON
+///
+/// ### Returns
+/// The formatted line
+pub fn format_event(signature: &str, event: &LeancoinEvent, json: bool) -> CliResult<String> {
+    let mut value = serde_json::to_value(event)?;
+    let fields = value
+        .as_object_mut()
+        .ok_or("The event is not serialized as an object")?;
+
+    if json {
+        fields.insert("signature".to_string(), Value::from(signature));
+        return Ok(value.to_string());
+    }
+
+    fields.remove("event");
+    let fields = fields
+        .iter()
+        .map(|(name, value)| match value {
+            Value::String(value) => format!("{}={}", name, value),
+            value => format!("{}={}", name, value),
+        })
+        .collect::<Vec<String>>();
+
+    Ok(format!(
+        "{} {} {}",
+        signature,
+        event.name(),
+        fields.join(" ")
+    ))
+}
+
+/// Decodes the events emitted by the contract in the logs of a transaction and formats the ones to print.
+/// The logs of failed transactions are skipped, because their events have not been committed.
+///
+/// ### Arguments
+///
+/// * `logs` - the logs of the transaction received from the subscription
+/// * `event_names` - the names of the events to print, all events are printed if empty
+/// * `json` - whether to format the events as JSON
+///
+/// ### Returns
+/// The formatted lines in the order of emission
+pub fn format_logs(
+    logs: &RpcLogsResponse,
+    event_names: &[String],
+    json: bool,
+) -> CliResult<Vec<String>> {
+    if logs.err.is_some() {
+        return Ok(vec![]);
+    }
+
+    parse_events(&logs.logs)
+        .iter()
+        .filter(|event| {
+            event_names.is_empty() || event_names.iter().any(|name| name == event.name())
+        })
+        .map(|event| format_event(&logs.signature, event, json))
+        .collect()
+}
+
+/// Subscribes to the logs of the transactions invoking the contract and prints the decoded events.
+/// The subscription is renewed whenever the connection is dropped, the command runs until it is interrupted.
+pub fn watch(url: &str, args: WatchArgs) -> CliResult<()> {
+    let url = websocket_url(url);
+
+    loop {
+        match PubsubClient::logs_subscribe(
+            &url,
+            RpcTransactionLogsFilter::Mentions(vec![leancoin::ID.to_string()]),
+            RpcTransactionLogsConfig {
+                commitment: Some(CommitmentConfig::confirmed()),
+            },
+        ) {
+            Ok((_subscription, receiver)) => {
+                eprintln!("Subscribed to the logs of {} at {}", leancoin::ID, url);
+
+                // the receiver is disconnected when the websocket connection is dropped
+                while let Ok(response) = receiver.recv() {
+                    for line in format_logs(&response.value, &args.events, args.json)? {
+                        println!("{}", line);
+                    }
+                }
+
+                eprintln!("Subscription dropped, reconnecting");
+            }
+            Err(error) => eprintln!("Subscription failed: {}, reconnecting", error),
+        }
+
+        thread::sleep(RECONNECT_DELAY);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use leancoin::events::{BurnExecuted, KeeperAdded};
+    use solana_sdk::{
+        instruction::InstructionError, pubkey::Pubkey, transaction::TransactionError,
+    };
+
+    const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+    fn program_data<T: anchor_lang::Event>(event: &T) -> String {
+        format!("Program data: {}", base64::encode(event.data()))
+    }
+
+    /// The logs of a transaction burning the monthly amount and registering a keeper.
+    fn logs(err: Option<TransactionError>) -> RpcLogsResponse {
+        let program_id = leancoin::ID.to_string();
+        let burn_event = BurnExecuted {
+            amount: 90000000000000000,
+            rewards: 0,
+            month: 3,
+            year: 2023,
+            sequence: 4,
+            supply_before: 8530000000000000000,
+            supply_after: 8440000000000000000,
+            late: false,
+            burning_account_balance: 1710000000000000000,
+            burn_rate_bps: 500,
+            caller_reward: 0,
+            manual: false,
+        };
+        let keeper_event = KeeperAdded {
+            keeper: Pubkey::new_from_array([7; 32]),
+            sequence: 5,
+        };
+
+        RpcLogsResponse {
+            signature: "signature".to_string(),
+            err,
+            logs: vec![
+                format!("Program {} invoke [1]", program_id),
+                "Program log: Instruction: Burn".to_string(),
+                format!("Program {} invoke [2]", TOKEN_PROGRAM_ID),
+                "Program log: Instruction: Burn".to_string(),
+                format!("Program {} success", TOKEN_PROGRAM_ID),
+                program_data(&burn_event),
+                format!("Program {} success", program_id),
+                format!("Program {} invoke [1]", program_id),
+                "Program log: Instruction: AddKeeper".to_string(),
+                program_data(&keeper_event),
+                format!("Program {} success", program_id),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_format_logs() {
+        let lines = format_logs(&logs(None), &[], false).unwrap();
+
+        assert_eq!(
+            lines,
+            vec![
+                "signature BurnExecuted amount=90000000000000000 late=false month=3 rewards=0 sequence=4 supply_after=8440000000000000000 supply_before=8530000000000000000 year=2023".to_string(),
+                format!(
+                    "signature KeeperAdded keeper={} sequence=5",
+                    Pubkey::new_from_array([7; 32])
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_logs_as_json_filtered_by_event() {
+        let lines = format_logs(&logs(None), &["KeeperAdded".to_string()], true).unwrap();
+
+        assert_eq!(lines.len(), 1);
+        let json: Value = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(json["event"], "KeeperAdded");
+        assert_eq!(json["signature"], "signature");
+        assert_eq!(json["keeper"], Pubkey::new_from_array([7; 32]).to_string());
+        assert_eq!(json["sequence"], 5);
+    }
+
+    #[test]
+    fn test_format_logs_of_failed_transaction() {
+        let err = TransactionError::InstructionError(0, InstructionError::Custom(6000));
+
+        assert!(format_logs(&logs(Some(err)), &[], false)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_parse_event_name() {
+        assert_eq!(parse_event_name("BurnExecuted").unwrap(), "BurnExecuted");
+        assert!(parse_event_name("Burn").is_err());
+    }
+
+    #[test]
+    fn test_websocket_url() {
+        assert_eq!(
+            websocket_url("http://localhost:8899"),
+            "ws://localhost:8900"
+        );
+        assert_eq!(
+            websocket_url("https://api.mainnet-beta.solana.com"),
+            "wss://api.mainnet-beta.solana.com"
+        );
+        assert_eq!(
+            websocket_url("wss://rpc.example.com:8899"),
+            "wss://rpc.example.com:8899"
+        );
+    }
+}
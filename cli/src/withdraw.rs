@@ -0,0 +1,79 @@
+use clap::Args;
+use leancoin::WalletKind;
+use leancoin_sdk::instruction;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{pubkey::Pubkey, signature::Signer};
+
+use crate::transaction::{process_instructions, TransactionArgs};
+use crate::utils::{fetch_vesting_state, signer_from_path, CliResult};
+
+/// Arguments of the withdraw command.
+#[derive(Args)]
+pub struct WithdrawArgs {
+    /// Vested wallet: community, partnership, marketing or liquidity
+    #[clap(long, value_parser = parse_wallet_kind)]
+    wallet: WalletKind,
+
+    /// Amount of tokens to withdraw, expressed with mint decimals
+    #[clap(long)]
+    amount: u64,
+
+    /// Token account to which the tokens are transferred
+    #[clap(long)]
+    deposit_wallet: Pubkey,
+
+    /// Beneficiary owner of the wallet: path to the keypair file, hardware wallet URL, e.g. usb://ledger,
+    /// or its public key when the transaction is only written with --unsigned-out
+    #[clap(long)]
+    signer: String,
+
+    #[clap(flatten)]
+    transaction: TransactionArgs,
+}
+
+/// Parses the name of the vested wallet.
+pub fn parse_wallet_kind(wallet: &str) -> Result<WalletKind, String> {
+    match wallet {
+        "community" => Ok(WalletKind::Community),
+        "partnership" => Ok(WalletKind::Partnership),
+        "marketing" => Ok(WalletKind::Marketing),
+        "liquidity" => Ok(WalletKind::Liquidity),
+        _ => Err(format!(
+            "Unknown wallet {}, expected community, partnership, marketing or liquidity",
+            wallet
+        )),
+    }
+}
+
+/// Withdraws vested tokens from the wallet to the deposit wallet.
+pub fn withdraw(url: &str, args: WithdrawArgs) -> CliResult<()> {
+    let client = RpcClient::new(url.to_string());
+    let signer = signer_from_path(&args.signer, "beneficiary owner")?;
+    let receipt_index = fetch_vesting_state(&client)?.next_receipt_index[args.wallet.index()];
+
+    let instruction = instruction::withdraw(
+        &signer.pubkey(),
+        args.wallet,
+        args.amount,
+        &args.deposit_wallet,
+        None,
+        receipt_index,
+    );
+    process_instructions(&client, &[instruction], signer.as_ref(), &args.transaction)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_wallet_kind() {
+        assert_eq!(
+            parse_wallet_kind("liquidity").unwrap(),
+            WalletKind::Liquidity
+        );
+        assert!(parse_wallet_kind("swap").is_err());
+    }
+}
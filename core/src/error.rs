@@ -0,0 +1,25 @@
+use std::fmt;
+
+/// Errors of the tokenomics calculations, the contract maps them to the errors with the same names.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoreError {
+    InvalidTimestamp,
+    EndTimeMustBeLaterThanStartTime,
+    TimestampTooFarInFuture,
+}
+
+impl fmt::Display for CoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            CoreError::InvalidTimestamp => "Invalid timestamp",
+            CoreError::EndTimeMustBeLaterThanStartTime => "End time must be later than start time",
+            CoreError::TimestampTooFarInFuture => "Timestamp is too far in the future",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for CoreError {}
+
+/// Result of the tokenomics calculations.
+pub type Result<T> = std::result::Result<T, CoreError>;
@@ -0,0 +1,21 @@
+//! Pure tokenomics calculations of the Leancoin token: the calendar arithmetic of timestamps and the vesting curves of the wallets.
+//! The crate has no dependency on Anchor or Solana, so the same implementation is used by the contract
+//! and by the off-chain tooling. The contract maps `CoreError` to its own errors.
+//! With the `wasm` feature the crate also exposes `wasm-bindgen` bindings for the web dashboard, see the `wasm` module.
+
+pub mod error;
+pub mod time;
+pub mod vesting;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use error::{CoreError, Result};
+pub use time::{
+    calculate_month_difference, days_in_month, parse_timestamp, DateTime, MAX_MONTH_DIFFERENCE,
+};
+pub use vesting::{
+    calculate_unlocked_amount_community_wallet, calculate_unlocked_amount_liquidity_wallet,
+    calculate_unlocked_amount_marketing_wallet, calculate_unlocked_amount_partnership_wallet,
+    UnlockSchedule, VestingCurve, BPS_DENOMINATOR, COMMUNITY_WALLET_SCHEDULE,
+    LIQUIDITY_WALLET_SCHEDULE, MARKETING_WALLET_SCHEDULE, PARTNERSHIP_WALLET_SCHEDULE,
+};
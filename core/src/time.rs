@@ -0,0 +1,267 @@
+use crate::error::{CoreError, Result};
+
+/// DAYS_PER_MONTH is an array of integers that contains the number of days for each month, excluding December
+const DAYS_PER_MONTH: [i64; 11] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30];
+
+/// SECONDS_PER_DAY is the number of seconds in a day
+const SECONDS_PER_DAY: i64 = 60 * 60 * 24;
+
+/// MAX_MONTH_DIFFERENCE is the maximal number of months between two timestamps accepted by the month arithmetic
+pub const MAX_MONTH_DIFFERENCE: i64 = 1200;
+
+/// Date time struct for the timestamp parsing
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DateTime {
+    pub year: i64,
+    pub month: u8,
+    pub days: u8,
+}
+
+/// Accepts the timestamp as an integer (i64) and returns DateTime struct
+///
+/// ### Arguments
+///
+/// * `timestamp` - the timestamp as a signed integer
+///
+/// ### Returns
+/// DateTime struct created from the timestamp
+pub fn parse_timestamp(timestamp: i64) -> Result<DateTime> {
+    if timestamp < 0 {
+        return Err(CoreError::InvalidTimestamp);
+    }
+
+    let mut remaining_days = timestamp / SECONDS_PER_DAY;
+    let mut year = 1970;
+    let mut month = 1;
+
+    while remaining_days >= 365 {
+        if is_leap_year(year) {
+            if remaining_days >= 366 {
+                remaining_days -= 366;
+                year += 1;
+            } else {
+                break;
+            }
+        } else {
+            remaining_days -= 365;
+            year += 1;
+        }
+    }
+
+    let leap_year = is_leap_year(year);
+    while month < 12 {
+        let month_length = if month == 2 && leap_year {
+            29
+        } else {
+            DAYS_PER_MONTH[month - 1]
+        };
+
+        if remaining_days < month_length {
+            break;
+        }
+        remaining_days -= month_length;
+        month += 1;
+    }
+    remaining_days += 1;
+
+    let month: u8 = month.try_into().unwrap();
+    let days: u8 = remaining_days.try_into().unwrap();
+
+    Ok(DateTime { year, month, days })
+}
+
+/// Checks whether the given year is a leap year.
+///
+/// A year is a leap year if it is divisible by 4 and not by 100, or if it is
+/// divisible by 400. For example, 1992 and 1996 are leap years, but 1900 is not.
+///
+/// ### Arguments
+///
+/// * `year` - the year to be checked
+///
+/// ### Returns
+/// True if the provided year is a leap year, false otherwise.
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/// Returns the number of days of the given month, e.g. of the month of `DateTime`.
+///
+/// ### Arguments
+///
+/// * `year` - the year of the month
+/// * `month` - the month number, from 1 to 12
+///
+/// ### Returns
+/// The number of days of the month, 31 for December and month numbers out of range.
+pub fn days_in_month(year: i64, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        1..=11 => DAYS_PER_MONTH[usize::from(month) - 1].try_into().unwrap(),
+        _ => 31,
+    }
+}
+
+/// Calculates the number of months between two timestamps.
+/// Only month numbers are compared, days are ignored.
+///
+/// Examples:
+/// - when start date is 01/04/2023 and end date is 01/05/2023, then the result is 1
+/// - when start date is 27/04/2023 and end date is 01/05/2023, then the result is 1
+/// - when start date is 01/04/2023 and end date is 01/06/2023, then the result is 2
+/// - when start date is 27/04/2023 and end date is 01/06/2023, then the result is 2
+/// - when start date is 27/04/2023 and end date is 01/12/2023, then the result is 8
+/// - when start date is 27/04/2023 and end date is 01/04/2024, then the result is 12
+///
+/// ### Arguments
+///
+/// * `start` - the earlier timestamp
+/// * `end` - the later timestamp
+///
+/// The difference is limited to `MAX_MONTH_DIFFERENCE` months, so a corrupted clock cannot overflow the arithmetic.
+/// Timestamps obviously beyond the limit are rejected before they are parsed.
+///
+/// ### Returns
+/// Number of months between two timestamps, ignoring days.
+pub fn calculate_month_difference(start: i64, end: i64) -> Result<u64> {
+    if end < start {
+        return Err(CoreError::EndTimeMustBeLaterThanStartTime);
+    }
+    let start_date = parse_timestamp(start)?;
+    if end - start > (MAX_MONTH_DIFFERENCE + 2) * 31 * SECONDS_PER_DAY {
+        return Err(CoreError::TimestampTooFarInFuture);
+    }
+    let end_date = parse_timestamp(end)?;
+
+    let month_difference = i64::from(end_date.month) - i64::from(start_date.month);
+    let months = end_date
+        .year
+        .checked_sub(start_date.year)
+        .and_then(|years| years.checked_mul(12))
+        .and_then(|months| months.checked_add(month_difference))
+        .ok_or(CoreError::TimestampTooFarInFuture)?;
+    if months > MAX_MONTH_DIFFERENCE {
+        return Err(CoreError::TimestampTooFarInFuture);
+    }
+
+    Ok(u64::try_from(months).unwrap())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::{Datelike, NaiveDate};
+    use test_case::test_case;
+
+    #[test_case( 0, DateTime { year: 1970, month: 1, days: 1 }; "timestamp 0")]
+    #[test_case( 162000, DateTime { year: 1970, month: 1, days: 2 }; "timestamp 162000")]
+    #[test_case( 94694400, DateTime { year: 1973, month: 1, days: 1 }; "timestamp 94694400")]
+    #[test_case( 2678400, DateTime { year: 1970, month: 2, days: 1 }; "timestamp 2678400")]
+    #[test_case( 5097600, DateTime { year: 1970, month: 3, days: 1 }; "timestamp 5097600")]
+    #[test_case( 68256000, DateTime { year: 1972, month: 3, days: 1 }; "timestamp 68256000")]
+    #[test_case( 31449600, DateTime { year: 1970, month: 12, days: 31 }; "timestamp 31449600")]
+    #[test_case( 220838400, DateTime { year: 1976, month: 12, days: 31 }; "timestamp 220838400")]
+    #[test_case( 978220800, DateTime { year: 2000, month: 12, days: 31 }; "timestamp 978220800")]
+    #[test_case( 1609335304, DateTime { year: 2020, month: 12, days: 30 }; "timestamp 1609335304" )]
+    #[test_case( 1620000000, DateTime { year: 2021, month: 5, days: 3 }; "timestamp 1620000000")]
+    #[test_case( 1620002137, DateTime { year: 2021, month: 5, days: 3 }; "timestamp 1620002137")]
+    #[test_case( 1378183924, DateTime { year: 2013, month: 9, days: 3 }; "timestamp 1378183924")]
+    #[test_case( 959249016, DateTime { year: 2000, month: 5, days: 25 }; "timestamp 959249016")]
+    #[test_case( 1336937134, DateTime { year: 2012, month: 5, days: 13 }; "timestamp 1336937134")]
+    #[test_case( 1836183646,  DateTime { year: 2028, month: 3, days: 9 }; "timestamp 1836183646")]
+    #[test_case( 1641052800,  DateTime { year: 2022, month: 1, days: 1 }; "timestamp 1641052800")]
+    fn test_parse_timestamp(timestamp: i64, expected: DateTime) {
+        let parsed_timestamp = parse_timestamp(timestamp).unwrap();
+        assert_eq!(parsed_timestamp, expected);
+    }
+
+    #[test]
+    fn test_parse_timestamp_error() {
+        let parsed_timestamp = parse_timestamp(-1);
+        assert!(parsed_timestamp.is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_parse_timestamp_negative() {
+        let timestamp: i64 = -1;
+        parse_timestamp(timestamp).unwrap();
+    }
+
+    #[test_case( 1620000000, 1620000000 + 60 * 60 * 24 * 15, 0; "start = 03/05/21, end = 18/05/21, same month")]
+    #[test_case( 1620000000, 1620000000, 0; "start = 03/05/21, end = 03/05/21, same month")]
+    #[test_case( 1620000000, 1620000000 + 60 * 60 * 24 * 31 - (2 * 24 * 60 * 60), 1; "start = 03/05/21, end = 01/06/21, 1 month")]
+    #[test_case( 1620000000, 1620000000 + 60 * 60 * 24 * 31, 1; "start = 03/05/21, end = 03/06/21, 1 month")]
+    #[test_case( 1620000000 + 60 * 60 * 24 * 15, 1620000000 + 60 * 60 * 24 * 30, 1; "start = 18/05/21, end = 02/06/21, 1 month")]
+    #[test_case( 1620000000, 1620000000 + 60 * 60 * 24 * 31 * 2, 2; "start = 03/05/21, end = 04/07/21, 2 months")]
+    #[test_case( 1620000000, 1620000000 + 60 * 60 * 24 * 31 * 3, 3; "start = 03/05/21, end = 04/08/21, 3 months")]
+    #[test_case( 1620000000, 1620000000 + 60 * 60 * 24 * 31 * 11, 11; "start = 03/05/21, end = 09/04/22, 11 months")]
+    #[test_case( 1620000000, 1620000000 + 60 * 60 * 24 * 31 * 12, 12; "start = 03/05/21, end = 10/05/22, 12 months")]
+    #[test_case( 1620000000, 1620000000 + 60 * 60 * 24 * 31 * 13, 13; "start = 03/05/21, end = 10/06/22, 13 months")]
+    #[test_case( 0, 3155760000, 1200; "start = 01/01/1970, end = 01/01/2070, 1200 months")]
+    #[test_case( 253370764800, 253402300799, 11; "start = 01/01/9999, end = 31/12/9999, 11 months")]
+
+    fn test_calculate_month_difference(start: i64, end: i64, expected: u64) {
+        let months_since_vesting_start = calculate_month_difference(start, end).unwrap();
+        assert_eq!(months_since_vesting_start, expected);
+    }
+
+    #[test_case( 0, 3158438400; "start = 01/01/1970, end = 01/02/2070, 1201 months")]
+    #[test_case( 1677978061, 253402300799; "start = 05/03/2023, end = 31/12/9999")]
+    #[test_case( 0, i64::MAX; "end = maximal timestamp")]
+    fn test_fail_calculate_month_difference_too_far_in_future(start: i64, end: i64) {
+        let months_since_vesting_start = calculate_month_difference(start, end);
+        assert!(months_since_vesting_start.is_err());
+    }
+
+    #[test_case(2023, 1, 31; "January")]
+    #[test_case(2023, 2, 28; "February")]
+    #[test_case(2024, 2, 29; "February of a leap year")]
+    #[test_case(2100, 2, 28; "February of a century year")]
+    #[test_case(2023, 4, 30; "April")]
+    #[test_case(2023, 12, 31; "December")]
+    fn test_days_in_month(year: i64, month: u8, expected: u8) {
+        assert_eq!(days_in_month(year, month), expected);
+    }
+
+    /// Compares every day from 1970-01-01 to 2100-12-31 with chrono, at midnight and at a pseudo-random second of the day.
+    /// It checks about 48 thousand days, so it is ignored by default, run it with `cargo test -p leancoin-core -- --ignored`.
+    #[test]
+    #[ignore]
+    fn test_parse_timestamp_matches_chrono() {
+        let mut date = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+        let last_date = NaiveDate::from_ymd_opt(2100, 12, 31).unwrap();
+        // xorshift, so the offsets are reproducible without a random number generator dependency
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+
+        while date <= last_date {
+            let midnight = date.and_hms_opt(0, 0, 0).unwrap().timestamp();
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            let offset = i64::try_from(seed % u64::try_from(SECONDS_PER_DAY).unwrap()).unwrap();
+
+            let expected = DateTime {
+                year: i64::from(date.year()),
+                month: u8::try_from(date.month()).unwrap(),
+                days: u8::try_from(date.day()).unwrap(),
+            };
+            assert_eq!(parse_timestamp(midnight).unwrap(), expected, "{}", date);
+            assert_eq!(
+                parse_timestamp(midnight + offset).unwrap(),
+                expected,
+                "{} + {}s",
+                date,
+                offset
+            );
+            assert_eq!(
+                parse_timestamp(midnight + SECONDS_PER_DAY - 1).unwrap(),
+                expected,
+                "{} 23:59:59",
+                date
+            );
+
+            date = date.succ_opt().unwrap();
+        }
+    }
+}
@@ -0,0 +1,592 @@
+/// BPS_DENOMINATOR is the number of basis points in the whole balance of a wallet
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Parameters of a vesting schedule. Nothing is unlocked before the cliff,
+/// the initial unlock is released at the cliff and the monthly unlock every following month,
+/// the whole balance is unlocked `full_unlock_months` months after the cliff.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UnlockSchedule {
+    /// the part of the balance unlocked at the cliff, in basis points
+    pub initial_unlock_bps: u16,
+    /// the part of the balance unlocked every month after the cliff, in basis points
+    pub monthly_unlock_bps: u16,
+    /// number of months since the vesting start before the first unlock
+    pub cliff_months: u16,
+    /// number of months since the cliff after which the whole balance is unlocked
+    pub full_unlock_months: u8,
+    /// the minimal amount unlocked from the cliff on if the percentage math truncates to less, i.e. to zero for the minimum of 1,
+    /// limited by the balance
+    pub minimum_unlock: u8,
+}
+
+/// COMMUNITY_WALLET_SCHEDULE unlocks 2.5% at the vesting start and 2.5% every month, so the whole balance after 39 months.
+/// The unlock delay set during the import is added to its cliff, see `calculate_unlocked_amount_community_wallet`.
+pub const COMMUNITY_WALLET_SCHEDULE: UnlockSchedule = UnlockSchedule {
+    initial_unlock_bps: 250,
+    monthly_unlock_bps: 250,
+    cliff_months: 0,
+    full_unlock_months: 39,
+    minimum_unlock: 1,
+};
+
+/// PARTNERSHIP_WALLET_SCHEDULE unlocks 50% after 1 month and the rest after 2 months
+pub const PARTNERSHIP_WALLET_SCHEDULE: UnlockSchedule = UnlockSchedule {
+    initial_unlock_bps: 5000,
+    monthly_unlock_bps: 0,
+    cliff_months: 1,
+    full_unlock_months: 1,
+    minimum_unlock: 0,
+};
+
+/// MARKETING_WALLET_SCHEDULE unlocks 40% after 1 year and 5% every following month, so the whole balance after 24 months
+pub const MARKETING_WALLET_SCHEDULE: UnlockSchedule = UnlockSchedule {
+    initial_unlock_bps: 4000,
+    monthly_unlock_bps: 500,
+    cliff_months: 12,
+    full_unlock_months: 12,
+    minimum_unlock: 1,
+};
+
+/// LIQUIDITY_WALLET_SCHEDULE unlocks 50% at the vesting start and the rest after 1 year
+pub const LIQUIDITY_WALLET_SCHEDULE: UnlockSchedule = UnlockSchedule {
+    initial_unlock_bps: 5000,
+    monthly_unlock_bps: 0,
+    cliff_months: 0,
+    full_unlock_months: 12,
+    minimum_unlock: 0,
+};
+
+impl UnlockSchedule {
+    /// Checks whether the unlocks scheduled until the full unlock do not exceed the whole balance,
+    /// i.e. the initial unlock and the monthly unlocks of `full_unlock_months` months sum to at most `BPS_DENOMINATOR`.
+    ///
+    /// ### Returns
+    /// True if the schedule is valid, false otherwise.
+    pub fn is_valid(&self) -> bool {
+        u64::from(self.initial_unlock_bps)
+            + u64::from(self.monthly_unlock_bps) * u64::from(self.full_unlock_months)
+            <= BPS_DENOMINATOR
+    }
+
+    /// Returns the schedule with the cliff postponed by the given number of months.
+    ///
+    /// ### Arguments
+    ///
+    /// * `delay_months` - number of months added to the cliff, e.g. the unlock delay of the community wallet
+    ///
+    /// ### Returns
+    /// The delayed schedule
+    pub fn delayed(&self, delay_months: u8) -> UnlockSchedule {
+        UnlockSchedule {
+            cliff_months: self.cliff_months.saturating_add(u16::from(delay_months)),
+            ..*self
+        }
+    }
+
+    /// Calculates the amount of unlocked tokens of the wallet vested with the schedule.
+    ///
+    /// ### Arguments
+    ///
+    /// * `vesting_start_account_balance` - the initial balance of the wallet after Ethereum token state import
+    /// * `months_since_vesting_start` - number of full months since the Ethereum token state import
+    ///
+    /// ### Returns
+    /// The amount of unlocked tokens, never greater than the initial balance
+    pub fn unlocked_amount(
+        &self,
+        vesting_start_account_balance: u64,
+        months_since_vesting_start: u64,
+    ) -> u64 {
+        let months_since_cliff =
+            match months_since_vesting_start.checked_sub(u64::from(self.cliff_months)) {
+                Some(months) => months,
+                None => return 0,
+            };
+        // the whole balance is unlocked at the full unlock month, later months do not extrapolate further
+        if months_since_cliff >= u64::from(self.full_unlock_months) {
+            return vesting_start_account_balance;
+        }
+
+        let unlocked_bps = u128::from(self.initial_unlock_bps)
+            + u128::from(self.monthly_unlock_bps) * u128::from(months_since_cliff);
+        let amount_unlocked =
+            u128::from(vesting_start_account_balance) * unlocked_bps / u128::from(BPS_DENOMINATOR);
+        // the cliff has been reached here, so a small balance unlocks the minimal amount instead of nothing,
+        // the minimum is limited by the balance, so an empty wallet never unlocks anything
+        u64::try_from(amount_unlocked)
+            .unwrap_or(u64::MAX)
+            .max(u64::from(self.minimum_unlock))
+            .min(vesting_start_account_balance)
+    }
+
+    /// Calculates the amount of unlocked tokens of the wallet vested with the schedule, interpolating the unlock
+    /// of the next month linearly over the days of the current month instead of releasing it at the month boundary.
+    /// Nothing is interpolated before the cliff, so the initial unlock is still released at the cliff.
+    /// The interpolated amount reaches the next monthly step on the first day of the next month, so it never decreases.
+    ///
+    /// ### Arguments
+    ///
+    /// * `vesting_start_account_balance` - the initial balance of the wallet after Ethereum token state import
+    /// * `months_since_vesting_start` - number of full months since the Ethereum token state import
+    /// * `elapsed_days` - number of full days elapsed in the current month, 0 on its first day
+    /// * `days_in_month` - number of days of the current month, see `days_in_month`
+    ///
+    /// ### Returns
+    /// The amount of unlocked tokens, never greater than the initial balance
+    pub fn linearly_unlocked_amount(
+        &self,
+        vesting_start_account_balance: u64,
+        months_since_vesting_start: u64,
+        elapsed_days: u8,
+        days_in_month: u8,
+    ) -> u64 {
+        let unlocked_amount =
+            self.unlocked_amount(vesting_start_account_balance, months_since_vesting_start);
+        if months_since_vesting_start < u64::from(self.cliff_months) || days_in_month == 0 {
+            return unlocked_amount;
+        }
+
+        let next_unlocked_amount = self.unlocked_amount(
+            vesting_start_account_balance,
+            months_since_vesting_start.saturating_add(1),
+        );
+        let interpolated_amount = u128::from(next_unlocked_amount.saturating_sub(unlocked_amount))
+            * u128::from(elapsed_days.min(days_in_month))
+            / u128::from(days_in_month);
+
+        unlocked_amount + u64::try_from(interpolated_amount).unwrap()
+    }
+}
+
+/// Calculates the amount of unlocked tokens for the partnership wallet.
+/// 50% of the initial wallet's balance is unlocked after 1 month.
+/// The remaining part is unlocked after 2 months.
+///
+/// ### Arguments
+///
+/// * `vesting_start_account_balance` - the initial balance of the partnership wallet after Ethereum token state import
+/// * `months_since_vesting_start` - number of full months since the Ethereum token state import
+///
+/// ### Returns
+/// The amount of unlocked tokens for partnership wallet
+pub fn calculate_unlocked_amount_partnership_wallet(
+    vesting_start_account_balance: u64,
+    months_since_vesting_start: u64,
+) -> u64 {
+    PARTNERSHIP_WALLET_SCHEDULE
+        .unlocked_amount(vesting_start_account_balance, months_since_vesting_start)
+}
+
+/// Calculates the amount of unlocked tokens for the marketing wallet.
+/// 40% of the initial wallet's balance is unlocked after 1 year.
+/// Starting from the 13th month, 5% of the initial wallet's balance is unlocked every month.
+///
+/// From the 12th month on, if the percentage math truncates to zero for a non-zero balance, then 1 is returned as the calculated amount,
+/// so a wallet with a balance too small for the percentages still unlocks its tokens once the cliff passes.
+/// Nothing is unlocked before the cliff and an empty wallet never unlocks anything.
+///
+/// ### Arguments
+///
+/// * `vesting_start_account_balance` - the initial balance of the marketing wallet after Ethereum token state import
+/// * `months_since_vesting_start` - number of full months since the Ethereum token state import
+///
+/// ### Returns
+/// The amount of unlocked tokens for marketing wallet, 0 before the cliff and not less than 1 afterwards unless the balance is 0
+pub fn calculate_unlocked_amount_marketing_wallet(
+    vesting_start_account_balance: u64,
+    months_since_vesting_start: u64,
+) -> u64 {
+    MARKETING_WALLET_SCHEDULE
+        .unlocked_amount(vesting_start_account_balance, months_since_vesting_start)
+}
+
+/// Calculates the amount of unlocked tokens for the community wallet.
+/// Nothing is unlocked until the unlock delay passes, then 2.5% of the initial wallet's balance is unlocked immediately.
+/// Additional 2.5% of the initial wallet's balance is unlocked every month.
+/// So without the delay after 2 months: 7.5% of the initial balance is unlocked, after 3 months: 10%, after 4 months: 12.5% etc.
+/// With the delay of 3 months: 2.5% is unlocked after 3 months, 5% after 4 months etc.
+///
+/// Once the delay passes (from the vesting start without a delay), if the percentage math truncates to zero for a non-zero balance,
+/// then 1 is returned as the calculated amount, so a wallet with a balance too small for the 2.5% steps still unlocks its tokens.
+/// An empty wallet never unlocks anything.
+///
+/// ### Arguments
+///
+/// * `vesting_start_account_balance` - the initial balance of the community wallet after Ethereum token state import
+/// * `months_since_vesting_start` - number of full months since the Ethereum token state import
+/// * `unlock_delay_months` - number of months before the first unlock, set during the Ethereum token state import
+///
+/// ### Returns
+/// The amount of unlocked tokens for community wallet, 0 before the delay passes and not less than 1 afterwards unless the balance is 0
+pub fn calculate_unlocked_amount_community_wallet(
+    vesting_start_account_balance: u64,
+    months_since_vesting_start: u64,
+    unlock_delay_months: u8,
+) -> u64 {
+    COMMUNITY_WALLET_SCHEDULE
+        .delayed(unlock_delay_months)
+        .unlocked_amount(vesting_start_account_balance, months_since_vesting_start)
+}
+
+/// Calculates the amount of unlocked tokens for the liquidity wallet.
+/// 50% of the initial wallet's balance is unlocked immediately.
+/// The remaining part is unlocked after 1 year.
+///
+/// ### Arguments
+///
+/// * `vesting_start_account_balance` - the initial balance of the liquidity wallet after Ethereum token state import
+/// * `months_since_vesting_start` - number of full months since the Ethereum token state import
+///
+/// ### Returns
+/// The amount of unlocked tokens for liquidity wallet
+pub fn calculate_unlocked_amount_liquidity_wallet(
+    vesting_start_account_balance: u64,
+    months_since_vesting_start: u64,
+) -> u64 {
+    LIQUIDITY_WALLET_SCHEDULE
+        .unlocked_amount(vesting_start_account_balance, months_since_vesting_start)
+}
+
+/// Vesting curve of a vested wallet, i.e. the function mapping the initial balance of the wallet
+/// and the number of months since the vesting start to the amount of unlocked tokens.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VestingCurve {
+    /// 2.5% is unlocked every month after the unlock delay, see `calculate_unlocked_amount_community_wallet`
+    Community { unlock_delay_months: u8 },
+    /// 50% is unlocked after 1 month and the rest after 2 months, see `calculate_unlocked_amount_partnership_wallet`
+    Partnership,
+    /// 40% is unlocked after 1 year and 5% every following month, see `calculate_unlocked_amount_marketing_wallet`
+    Marketing,
+    /// 50% is unlocked immediately and the rest after 1 year, see `calculate_unlocked_amount_liquidity_wallet`
+    Liquidity,
+}
+
+impl VestingCurve {
+    /// Calculates the amount of unlocked tokens of the wallet vested with the curve.
+    ///
+    /// ### Arguments
+    ///
+    /// * `vesting_start_account_balance` - the initial balance of the wallet after Ethereum token state import
+    /// * `months_since_vesting_start` - number of full months since the Ethereum token state import
+    ///
+    /// ### Returns
+    /// The amount of unlocked tokens, never greater than the initial balance
+    pub fn unlocked_amount(
+        &self,
+        vesting_start_account_balance: u64,
+        months_since_vesting_start: u64,
+    ) -> u64 {
+        match *self {
+            VestingCurve::Community {
+                unlock_delay_months,
+            } => calculate_unlocked_amount_community_wallet(
+                vesting_start_account_balance,
+                months_since_vesting_start,
+                unlock_delay_months,
+            ),
+            VestingCurve::Partnership => calculate_unlocked_amount_partnership_wallet(
+                vesting_start_account_balance,
+                months_since_vesting_start,
+            ),
+            VestingCurve::Marketing => calculate_unlocked_amount_marketing_wallet(
+                vesting_start_account_balance,
+                months_since_vesting_start,
+            ),
+            VestingCurve::Liquidity => calculate_unlocked_amount_liquidity_wallet(
+                vesting_start_account_balance,
+                months_since_vesting_start,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(1000000000, 0, 0; "0 months")]
+    #[test_case(1000000000, 1, 500000000; "1 month")]
+    #[test_case(1000000000, 2, 1000000000; "2 months")]
+    #[test_case(1000000000, 3, 1000000000; "3 months")]
+    #[test_case(u64::MAX, u64::MAX, u64::MAX; "maximal balance and months")]
+    fn test_calculate_unlocked_amount_partnership_wallet(
+        vesting_start_account_balance: u64,
+        months_since_vesting_start: u64,
+        expected: u64,
+    ) {
+        let amount_unlocked = calculate_unlocked_amount_partnership_wallet(
+            vesting_start_account_balance,
+            months_since_vesting_start,
+        );
+        assert_eq!(amount_unlocked, expected);
+    }
+
+    #[test_case(1000000000, 1, 0; "1 month")]
+    #[test_case(1000000000, 2, 0; "2 months")]
+    #[test_case(1000000000, 3, 0; "3 months")]
+    #[test_case(1000000000, 12, 400000000; "12 months")]
+    #[test_case(1000000000, 24, 1000000000; "24 months")]
+    #[test_case(u64::MAX, u64::MAX, u64::MAX; "maximal balance and months")]
+    #[test_case(1000000000, 13, 450000000; "13 months")]
+    #[test_case(1000000000, 50, 1000000000; "50 months")]
+    #[test_case(1000000000, 100, 1000000000; "100 months")]
+    #[test_case(0, 11, 0; "11 months with 0 tokens - no unlocked tokens")]
+    #[test_case(0, 12, 0; "12 months with 0 tokens - no unlocked tokens")]
+    #[test_case(0, 13, 0; "13 months with 0 tokens - no unlocked tokens")]
+    #[test_case(0, 50, 0; "50 months with 0 tokens - no unlocked tokens")]
+    #[test_case(0, 100, 0; "100 months with 0 tokens - no unlocked tokens")]
+    #[test_case(1, 11, 0; "11 months with 1 token - no unlocked tokens")]
+    #[test_case(1, 12, 1; "12 months with 1 token - one token unlocked")]
+    #[test_case(1, 13, 1; "13 months with 1 token - one token unlocked")]
+    #[test_case(1, 50, 1; "50 months with 1 token - one token unlocked")]
+    #[test_case(1, 100, 1; "100 months with 1 token - one token unlocked")]
+    #[test_case(2, 11, 0; "11 months with 2 tokens - no unlocked tokens before the cliff")]
+    #[test_case(2, 12, 1; "12 months with 2 tokens - truncated to zero, one token unlocked")]
+    #[test_case(2, 24, 2; "24 months with 2 tokens - all tokens unlocked")]
+    #[test_case(10, 12, 4; "12 months with 10 tokens - not truncated to zero, no minimum")]
+    fn test_calculate_unlocked_amount_marketing_wallet(
+        vesting_start_account_balance: u64,
+        months_since_vesting_start: u64,
+        expected: u64,
+    ) {
+        let amount_unlocked = calculate_unlocked_amount_marketing_wallet(
+            vesting_start_account_balance,
+            months_since_vesting_start,
+        );
+        assert_eq!(amount_unlocked, expected);
+    }
+
+    #[test_case(1000000000, 0, 0, 25000000; "vesting start")]
+    #[test_case(1000000000, 1, 0, 50000000; "1 month")]
+    #[test_case(1000000000, 2, 0, 75000000; "2 months")]
+    #[test_case(1000000000, 3, 0, 100000000; "3 months")]
+    #[test_case(1000000000, 4, 0, 125000000; "4 months")]
+    #[test_case(1000000000, 5, 0, 150000000; "5 months")]
+    #[test_case(1000000000, 11, 0, 300000000; "11 months")]
+    #[test_case(1000000000, 12, 0, 325000000; "12 months")]
+    #[test_case(1000000000, 13, 0, 350000000; "13 months")]
+    #[test_case(1000000000, 38, 0, 975000000; "38 months")]
+    #[test_case(1000000000, 39, 0, 1000000000; "39 months")]
+    #[test_case(1000000000, 40, 0, 1000000000; "40 months")]
+    #[test_case(1000000000, 100, 0, 1000000000; "100 months")]
+    #[test_case(0, 1, 0, 0; "1 month with 0 tokens - no unlocked tokens")]
+    #[test_case(0, 38, 0, 0; "38 months with 0 tokens - no unlocked tokens")]
+    #[test_case(0, 39, 0, 0; "39 months with 0 tokens - no unlocked tokens")]
+    #[test_case(0, 100, 0, 0; "100 months with 0 tokens - no unlocked tokens")]
+    #[test_case(1, 1, 0, 1; "1 month with 1 token - one token unlocked")]
+    #[test_case(1, 38, 0, 1; "38 months with 1 token - one token unlocked")]
+    #[test_case(1, 39, 0, 1; "39 months with 1 token - one token unlocked")]
+    #[test_case(1, 100, 0, 1; "100 months with 1 token - one token unlocked")]
+    #[test_case(1000000000000000000, 100, 0, 1000000000000000000; "100 months with 1000000000000000000 token - 1000000000000000000 token unlocked")]
+    #[test_case(u64::MAX, u64::MAX, 0, u64::MAX; "maximal balance and months")]
+    #[test_case(1000000000, 0, 3, 0; "vesting start with 3 months delay - no unlocked tokens")]
+    #[test_case(1000000000, 2, 3, 0; "2 months with 3 months delay - no unlocked tokens")]
+    #[test_case(1000000000, 3, 3, 25000000; "3 months with 3 months delay - first unlock")]
+    #[test_case(1000000000, 4, 3, 50000000; "4 months with 3 months delay")]
+    #[test_case(1000000000, 15, 3, 325000000; "15 months with 3 months delay")]
+    #[test_case(1000000000, 41, 3, 975000000; "41 months with 3 months delay")]
+    #[test_case(1000000000, 42, 3, 1000000000; "42 months with 3 months delay")]
+    #[test_case(1000000000, 100, 3, 1000000000; "100 months with 3 months delay")]
+    #[test_case(1, 2, 3, 0; "2 months with 3 months delay and 1 token - no unlocked tokens")]
+    #[test_case(1, 3, 3, 1; "3 months with 3 months delay and 1 token - one token unlocked")]
+    #[test_case(0, 0, 0, 0; "vesting start with 0 tokens - no unlocked tokens")]
+    #[test_case(1, 0, 0, 1; "vesting start with 1 token - first unlock month, one token unlocked")]
+    #[test_case(39, 0, 0, 1; "vesting start with 39 tokens - truncated to zero, one token unlocked")]
+    #[test_case(39, 1, 0, 1; "1 month with 39 tokens - not truncated to zero")]
+    #[test_case(40, 0, 0, 1; "vesting start with 40 tokens - not truncated to zero")]
+    #[test_case(80, 0, 0, 2; "vesting start with 80 tokens - no minimum")]
+    #[test_case(39, 2, 3, 0; "2 months with 3 months delay and 39 tokens - no unlocked tokens")]
+    #[test_case(u64::MAX, u64::MAX, u8::MAX, u64::MAX; "maximal balance, months and delay")]
+    fn test_calculate_unlocked_amount_community_wallet(
+        vesting_start_account_balance: u64,
+        months_since_vesting_start: u64,
+        unlock_delay_months: u8,
+        expected: u64,
+    ) {
+        let amount_unlocked = calculate_unlocked_amount_community_wallet(
+            vesting_start_account_balance,
+            months_since_vesting_start,
+            unlock_delay_months,
+        );
+        assert_eq!(amount_unlocked, expected);
+    }
+
+    #[test_case(1000000000, 1, 500000000; "1 month")]
+    #[test_case(1000000000, 2, 500000000; "2 months")]
+    #[test_case(1000000000, 3, 500000000; "3 months")]
+    #[test_case(1000000000, 4, 500000000; "4 months")]
+    #[test_case(1000000000, 5, 500000000; "5 months")]
+    #[test_case(1000000000, 11, 500000000; "11 months")]
+    #[test_case(1000000000, 12, 1000000000; "12 months")]
+    #[test_case(1000000000, 13, 1000000000; "13 months")]
+    #[test_case(1000000000, 100, 1000000000; "100 months")]
+    #[test_case(u64::MAX, u64::MAX, u64::MAX; "maximal balance and months")]
+    fn test_calculate_unlocked_amount_liquidity_wallet(
+        vesting_start_account_balance: u64,
+        months_since_vesting_start: u64,
+        expected: u64,
+    ) {
+        let amount_unlocked = calculate_unlocked_amount_liquidity_wallet(
+            vesting_start_account_balance,
+            months_since_vesting_start,
+        );
+        assert_eq!(amount_unlocked, expected);
+    }
+
+    #[test_case(VestingCurve::Community { unlock_delay_months: 3 }, 1000000000, 4, 50000000; "community with delay")]
+    #[test_case(VestingCurve::Partnership, 1000000000, 1, 500000000; "partnership")]
+    #[test_case(VestingCurve::Marketing, 1000000000, 13, 450000000; "marketing")]
+    #[test_case(VestingCurve::Liquidity, 1000000000, 0, 500000000; "liquidity")]
+    fn test_vesting_curve_unlocked_amount(
+        curve: VestingCurve,
+        vesting_start_account_balance: u64,
+        months_since_vesting_start: u64,
+        expected: u64,
+    ) {
+        assert_eq!(
+            curve.unlocked_amount(vesting_start_account_balance, months_since_vesting_start),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_default_schedules_are_valid() {
+        for schedule in [
+            COMMUNITY_WALLET_SCHEDULE,
+            PARTNERSHIP_WALLET_SCHEDULE,
+            MARKETING_WALLET_SCHEDULE,
+            LIQUIDITY_WALLET_SCHEDULE,
+        ] {
+            assert!(schedule.is_valid());
+        }
+    }
+
+    #[test_case(10000, 0, 0, true; "whole balance at the cliff")]
+    #[test_case(1000, 1000, 9, true; "monthly unlocks sum to the whole balance")]
+    #[test_case(1000, 1000, 10, false; "monthly unlocks exceed the whole balance")]
+    #[test_case(10001, 0, 12, false; "initial unlock exceeds the whole balance")]
+    #[test_case(0, u16::MAX, u8::MAX, false; "maximal monthly unlock")]
+    fn test_unlock_schedule_is_valid(
+        initial_unlock_bps: u16,
+        monthly_unlock_bps: u16,
+        full_unlock_months: u8,
+        expected: bool,
+    ) {
+        let schedule = UnlockSchedule {
+            initial_unlock_bps,
+            monthly_unlock_bps,
+            full_unlock_months,
+            ..UnlockSchedule::default()
+        };
+        assert_eq!(schedule.is_valid(), expected);
+    }
+
+    #[test_case(1000000000, 2, 0; "before the cliff")]
+    #[test_case(1000000000, 3, 100000000; "at the cliff")]
+    #[test_case(1000000000, 5, 300000000; "2 months after the cliff")]
+    #[test_case(1000000000, 11, 900000000; "8 months after the cliff")]
+    #[test_case(1000000000, 12, 1000000000; "full unlock")]
+    #[test_case(5, 3, 2; "minimum unlock")]
+    #[test_case(1, 3, 1; "minimum unlock limited by the balance")]
+    #[test_case(5, 2, 0; "no minimum unlock before the cliff")]
+    #[test_case(30, 3, 3; "no minimum unlock above the minimum")]
+    #[test_case(0, 3, 0; "no tokens")]
+    #[test_case(u64::MAX, 11, 16602069666338596453; "maximal balance")]
+    fn test_unlock_schedule_unlocked_amount(
+        vesting_start_account_balance: u64,
+        months_since_vesting_start: u64,
+        expected: u64,
+    ) {
+        let schedule = UnlockSchedule {
+            initial_unlock_bps: 1000,
+            monthly_unlock_bps: 1000,
+            cliff_months: 3,
+            full_unlock_months: 9,
+            minimum_unlock: 2,
+        };
+        assert_eq!(
+            schedule.unlocked_amount(vesting_start_account_balance, months_since_vesting_start),
+            expected
+        );
+    }
+
+    #[test_case(COMMUNITY_WALLET_SCHEDULE, 0, 0, 30, 25000000; "community on the first day")]
+    #[test_case(COMMUNITY_WALLET_SCHEDULE, 0, 15, 30, 37500000; "community in the middle of a month")]
+    #[test_case(COMMUNITY_WALLET_SCHEDULE, 1, 30, 31, 74193548; "community on the last day of a month")]
+    #[test_case(COMMUNITY_WALLET_SCHEDULE, 39, 15, 30, 1000000000; "community after the full unlock")]
+    #[test_case(COMMUNITY_WALLET_SCHEDULE.delayed(3), 2, 15, 30, 0; "community before the delayed cliff")]
+    #[test_case(MARKETING_WALLET_SCHEDULE, 11, 29, 30, 0; "marketing before the cliff")]
+    #[test_case(MARKETING_WALLET_SCHEDULE, 12, 0, 31, 400000000; "marketing at the cliff")]
+    #[test_case(MARKETING_WALLET_SCHEDULE, 12, 14, 28, 425000000; "marketing in the middle of February")]
+    #[test_case(MARKETING_WALLET_SCHEDULE, 23, 15, 30, 975000000; "marketing in the last month")]
+    #[test_case(MARKETING_WALLET_SCHEDULE, 24, 0, 31, 1000000000; "marketing at the full unlock")]
+    fn test_linearly_unlocked_amount(
+        schedule: UnlockSchedule,
+        months_since_vesting_start: u64,
+        elapsed_days: u8,
+        days_in_month: u8,
+        expected: u64,
+    ) {
+        assert_eq!(
+            schedule.linearly_unlocked_amount(
+                1000000000,
+                months_since_vesting_start,
+                elapsed_days,
+                days_in_month
+            ),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_linearly_unlocked_amount_is_monotonic() {
+        for schedule in [COMMUNITY_WALLET_SCHEDULE, MARKETING_WALLET_SCHEDULE] {
+            let mut previous_amount = 0;
+            for months in 0..40 {
+                for elapsed_days in 0..31 {
+                    let amount =
+                        schedule.linearly_unlocked_amount(1000000007, months, elapsed_days, 31);
+                    assert!(amount >= previous_amount);
+                    assert!(amount <= 1000000007);
+                    previous_amount = amount;
+                }
+                // the interpolation ends just below the monthly step of the next month
+                assert!(previous_amount <= schedule.unlocked_amount(1000000007, months + 1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_delayed_schedule() {
+        let schedule = COMMUNITY_WALLET_SCHEDULE.delayed(3);
+        assert_eq!(schedule.cliff_months, 3);
+        assert_eq!(schedule.unlocked_amount(1000000000, 4), 50000000);
+        assert_eq!(
+            UnlockSchedule {
+                cliff_months: u16::MAX,
+                ..COMMUNITY_WALLET_SCHEDULE
+            }
+            .delayed(u8::MAX)
+            .cliff_months,
+            u16::MAX
+        );
+    }
+
+    #[test]
+    fn test_vesting_curves_never_exceed_initial_balance() {
+        let curves = [
+            VestingCurve::Community {
+                unlock_delay_months: 0,
+            },
+            VestingCurve::Partnership,
+            VestingCurve::Marketing,
+            VestingCurve::Liquidity,
+        ];
+        for curve in curves {
+            for months in 0..100 {
+                assert!(curve.unlocked_amount(1000000007, months) <= 1000000007);
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! `wasm-bindgen` bindings of the tokenomics calculations used by the web dashboard, enabled by the `wasm` feature.
+//! The 64-bit amounts and timestamps are passed as decimal strings, so no precision is lost on the JavaScript side.
+//! The errors are thrown as JavaScript errors with the message of `CoreError`.
+
+use js_sys::{Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+use crate::time::{self, DateTime};
+use crate::vesting;
+
+/// Parses a decimal string passed from JavaScript into an integer
+///
+/// ### Arguments
+///
+/// * `name` - the name of the argument, used in the error message
+/// * `value` - the decimal string
+///
+/// ### Returns
+/// The parsed integer or JavaScript error if the string is not a valid integer of the type
+fn parse_argument<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, JsError> {
+    value
+        .parse()
+        .map_err(|_| JsError::new(&format!("Invalid {}: {}", name, value)))
+}
+
+/// Converts DateTime struct to a plain JavaScript object with `year`, `month` and `days` number fields
+fn date_time_to_object(date_time: DateTime) -> Result<Object, JsValue> {
+    let object = Object::new();
+    Reflect::set(
+        &object,
+        &"year".into(),
+        &JsValue::from_f64(date_time.year as f64),
+    )?;
+    Reflect::set(&object, &"month".into(), &date_time.month.into())?;
+    Reflect::set(&object, &"days".into(), &date_time.days.into())?;
+    Ok(object)
+}
+
+/// Accepts the timestamp as a decimal string and returns `{ year, month, days }` object, see `parse_timestamp`
+#[wasm_bindgen(js_name = parseTimestamp)]
+pub fn parse_timestamp(timestamp: &str) -> Result<Object, JsValue> {
+    let timestamp = parse_argument("timestamp", timestamp)?;
+    let date_time = time::parse_timestamp(timestamp).map_err(JsError::from)?;
+    date_time_to_object(date_time)
+}
+
+/// Number of months between two timestamps passed as decimal strings, ignoring days, see `calculate_month_difference`
+#[wasm_bindgen(js_name = calculateMonthDifference)]
+pub fn calculate_month_difference(start: &str, end: &str) -> Result<String, JsError> {
+    let months = time::calculate_month_difference(
+        parse_argument("start", start)?,
+        parse_argument("end", end)?,
+    )?;
+    Ok(months.to_string())
+}
+
+/// The amount of unlocked tokens for partnership wallet as a decimal string, see `calculate_unlocked_amount_partnership_wallet`
+#[wasm_bindgen(js_name = calculateUnlockedAmountPartnershipWallet)]
+pub fn calculate_unlocked_amount_partnership_wallet(
+    vesting_start_account_balance: &str,
+    months_since_vesting_start: &str,
+) -> Result<String, JsError> {
+    Ok(vesting::calculate_unlocked_amount_partnership_wallet(
+        parse_argument("balance", vesting_start_account_balance)?,
+        parse_argument("months", months_since_vesting_start)?,
+    )
+    .to_string())
+}
+
+/// The amount of unlocked tokens for marketing wallet as a decimal string, see `calculate_unlocked_amount_marketing_wallet`
+#[wasm_bindgen(js_name = calculateUnlockedAmountMarketingWallet)]
+pub fn calculate_unlocked_amount_marketing_wallet(
+    vesting_start_account_balance: &str,
+    months_since_vesting_start: &str,
+) -> Result<String, JsError> {
+    Ok(vesting::calculate_unlocked_amount_marketing_wallet(
+        parse_argument("balance", vesting_start_account_balance)?,
+        parse_argument("months", months_since_vesting_start)?,
+    )
+    .to_string())
+}
+
+/// The amount of unlocked tokens for community wallet as a decimal string, see `calculate_unlocked_amount_community_wallet`
+#[wasm_bindgen(js_name = calculateUnlockedAmountCommunityWallet)]
+pub fn calculate_unlocked_amount_community_wallet(
+    vesting_start_account_balance: &str,
+    months_since_vesting_start: &str,
+    unlock_delay_months: u8,
+) -> Result<String, JsError> {
+    Ok(vesting::calculate_unlocked_amount_community_wallet(
+        parse_argument("balance", vesting_start_account_balance)?,
+        parse_argument("months", months_since_vesting_start)?,
+        unlock_delay_months,
+    )
+    .to_string())
+}
+
+/// The amount of unlocked tokens for liquidity wallet as a decimal string, see `calculate_unlocked_amount_liquidity_wallet`
+#[wasm_bindgen(js_name = calculateUnlockedAmountLiquidityWallet)]
+pub fn calculate_unlocked_amount_liquidity_wallet(
+    vesting_start_account_balance: &str,
+    months_since_vesting_start: &str,
+) -> Result<String, JsError> {
+    Ok(vesting::calculate_unlocked_amount_liquidity_wallet(
+        parse_argument("balance", vesting_start_account_balance)?,
+        parse_argument("months", months_since_vesting_start)?,
+    )
+    .to_string())
+}
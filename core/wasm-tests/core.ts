@@ -0,0 +1,129 @@
+import { assert } from "chai";
+import {
+    calculateMonthDifference,
+    calculateUnlockedAmountCommunityWallet,
+    calculateUnlockedAmountLiquidityWallet,
+    calculateUnlockedAmountMarketingWallet,
+    calculateUnlockedAmountPartnershipWallet,
+    parseTimestamp,
+} from "../pkg/leancoin_core";
+
+// The expectations are copied from the unit tests of the `time` and `vesting` modules,
+// so the bindings are checked against the same values as the Rust implementation.
+
+const U64_MAX = "18446744073709551615";
+
+describe("leancoin-core wasm bindings", () => {
+    it("parses timestamps", () => {
+        const cases: [string, { year: number; month: number; days: number }][] = [
+            ["0", { year: 1970, month: 1, days: 1 }],
+            ["162000", { year: 1970, month: 1, days: 2 }],
+            ["68256000", { year: 1972, month: 3, days: 1 }],
+            ["31449600", { year: 1970, month: 12, days: 31 }],
+            ["978220800", { year: 2000, month: 12, days: 31 }],
+            ["1609335304", { year: 2020, month: 12, days: 30 }],
+            ["1620002137", { year: 2021, month: 5, days: 3 }],
+            ["1836183646", { year: 2028, month: 3, days: 9 }],
+            ["1641052800", { year: 2022, month: 1, days: 1 }],
+        ];
+        for (const [timestamp, expected] of cases) {
+            assert.deepEqual(parseTimestamp(timestamp), expected, `timestamp ${timestamp}`);
+        }
+    });
+
+    it("fails to parse a negative timestamp", () => {
+        assert.throws(() => parseTimestamp("-1"), "Invalid timestamp");
+    });
+
+    it("fails to parse a timestamp which is not an integer", () => {
+        assert.throws(() => parseTimestamp("1.5"));
+    });
+
+    it("calculates the month difference", () => {
+        const cases: [string, string, string][] = [
+            ["1620000000", "1621296000", "0"],
+            ["1620000000", "1622505600", "1"],
+            ["1620000000", "1622678400", "1"],
+            ["1620000000", "1625356800", "2"],
+            ["1620000000", "1652140800", "12"],
+            ["0", "3155760000", "1200"],
+            ["253370764800", "253402300799", "11"],
+        ];
+        for (const [start, end, expected] of cases) {
+            assert.equal(calculateMonthDifference(start, end), expected, `${start} - ${end}`);
+        }
+    });
+
+    it("fails to calculate the month difference", () => {
+        assert.throws(() => calculateMonthDifference("1620000000", "0"), "End time must be later than start time");
+        assert.throws(() => calculateMonthDifference("0", "3158438400"), "Timestamp is too far in the future");
+        assert.throws(() => calculateMonthDifference("0", "9223372036854775807"), "Timestamp is too far in the future");
+    });
+
+    it("calculates the unlocked amount of the partnership wallet", () => {
+        const cases: [string, string, string][] = [
+            ["1000000000", "0", "0"],
+            ["1000000000", "1", "500000000"],
+            ["1000000000", "2", "1000000000"],
+            [U64_MAX, U64_MAX, U64_MAX],
+        ];
+        for (const [balance, months, expected] of cases) {
+            assert.equal(calculateUnlockedAmountPartnershipWallet(balance, months), expected, `${months} months`);
+        }
+    });
+
+    it("calculates the unlocked amount of the marketing wallet", () => {
+        const cases: [string, string, string][] = [
+            ["1000000000", "11", "0"],
+            ["1000000000", "12", "400000000"],
+            ["1000000000", "13", "450000000"],
+            ["1000000000", "24", "1000000000"],
+            ["1000000000", "100", "1000000000"],
+            ["1", "12", "1"],
+            ["0", "13", "0"],
+            [U64_MAX, U64_MAX, U64_MAX],
+        ];
+        for (const [balance, months, expected] of cases) {
+            assert.equal(calculateUnlockedAmountMarketingWallet(balance, months), expected, `${months} months`);
+        }
+    });
+
+    it("calculates the unlocked amount of the community wallet", () => {
+        const cases: [string, string, number, string][] = [
+            ["1000000000", "0", 0, "25000000"],
+            ["1000000000", "1", 0, "50000000"],
+            ["1000000000", "38", 0, "975000000"],
+            ["1000000000", "39", 0, "1000000000"],
+            ["1", "1", 0, "1"],
+            ["1000000000000000000", "100", 0, "1000000000000000000"],
+            ["1000000000", "2", 3, "0"],
+            ["1000000000", "3", 3, "25000000"],
+            ["1000000000", "42", 3, "1000000000"],
+            ["1", "3", 3, "1"],
+            [U64_MAX, U64_MAX, 255, U64_MAX],
+        ];
+        for (const [balance, months, delay, expected] of cases) {
+            assert.equal(
+                calculateUnlockedAmountCommunityWallet(balance, months, delay),
+                expected,
+                `${months} months with ${delay} months delay`,
+            );
+        }
+    });
+
+    it("calculates the unlocked amount of the liquidity wallet", () => {
+        const cases: [string, string, string][] = [
+            ["1000000000", "1", "500000000"],
+            ["1000000000", "11", "500000000"],
+            ["1000000000", "12", "1000000000"],
+            [U64_MAX, U64_MAX, U64_MAX],
+        ];
+        for (const [balance, months, expected] of cases) {
+            assert.equal(calculateUnlockedAmountLiquidityWallet(balance, months), expected, `${months} months`);
+        }
+    });
+
+    it("fails on a balance which does not fit into u64", () => {
+        assert.throws(() => calculateUnlockedAmountLiquidityWallet("18446744073709551616", "0"), "Invalid balance");
+    });
+});
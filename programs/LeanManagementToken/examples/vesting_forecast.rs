@@ -0,0 +1,42 @@
+//! Prints the vesting forecast as CSV.
+//!
+//! Usage: `cargo run --example vesting_forecast -- <start_timestamp> <community> <partnership> <marketing> <liquidity> [months] [community_unlock_delay_months]`
+
+use leancoin::forecast::{forecast, MonthlyForecast, WalletBalances};
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.len() < 5 {
+        eprintln!("Usage: vesting_forecast <start_timestamp> <community> <partnership> <marketing> <liquidity> [months] [community_unlock_delay_months]");
+        std::process::exit(1);
+    }
+
+    let start_timestamp: i64 = args[0].parse().expect("invalid start timestamp");
+    let initial_balances = WalletBalances {
+        community: args[1].parse().expect("invalid community balance"),
+        partnership: args[2].parse().expect("invalid partnership balance"),
+        marketing: args[3].parse().expect("invalid marketing balance"),
+        liquidity: args[4].parse().expect("invalid liquidity balance"),
+    };
+    let months: u16 = args
+        .get(5)
+        .map(|months| months.parse().expect("invalid number of months"))
+        .unwrap_or(48);
+    let community_unlock_delay_months: u8 = args
+        .get(6)
+        .map(|delay| delay.parse().expect("invalid community unlock delay"))
+        .unwrap_or(0);
+
+    let forecast = forecast(
+        initial_balances,
+        start_timestamp,
+        months,
+        community_unlock_delay_months,
+    )
+    .expect("failed to forecast vesting");
+
+    println!("{}", MonthlyForecast::CSV_HEADER);
+    for month in forecast {
+        println!("{}", month.to_csv_row());
+    }
+}
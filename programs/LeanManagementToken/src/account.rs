@@ -1,24 +1,255 @@
 use anchor_lang::{
-    prelude::{account, borsh, AnchorDeserialize, AnchorSerialize, InitSpace},
+    prelude::{account, borsh, zero_copy, AnchorDeserialize, AnchorSerialize, InitSpace},
     solana_program::pubkey::Pubkey,
 };
 
+use leancoin_core::{
+    UnlockSchedule, COMMUNITY_WALLET_SCHEDULE, LIQUIDITY_WALLET_SCHEDULE,
+    MARKETING_WALLET_SCHEDULE, PARTNERSHIP_WALLET_SCHEDULE,
+};
+
+use crate::{
+    MigrationSource, Role, WalletKind, BURN_HISTORY_CAPACITY, DEFAULT_BURN_RATE_BPS,
+    DEFAULT_BURN_WINDOW_DAYS, DEFAULT_LATE_BURN_WINDOW_FIRST_DAY,
+    DEFAULT_LATE_BURN_WINDOW_LAST_DAY, LABELED_ACCOUNTS_COUNT, LABEL_LENGTH, LEGACY_MINT_DECIMALS,
+    MAX_KEEPERS, MAX_STAGED_IMPORT_ENTRIES, MIGRATION_SOURCES_COUNT, VESTED_WALLETS_COUNT,
+};
+
+/// The current version of the `ContractState` layout.
+/// The versions 2 to 17 were written by the intermediate layouts, which are no longer migrated, see `read_outdated_contract_state`.
+/// A version is never reused, so an account written in any of them cannot be mistaken for the current layout.
+pub const CONTRACT_STATE_VERSION: u8 = 18;
+
 /// The account that holds the state of the contract.
 /// It is initialized only once during contract initialization.
 /// Part of the state is never updated (nonces and authority) while the other parts can be updated one or more times.
 ///
+/// The fields are ordered so the ones indexers filter on come first and every field has a fixed, documented offset
+/// (see the `*_OFFSET` constants), new fields must be appended at the end so the offsets never change.
+/// The accounts created in the deployed borsh layout (`LegacyContractState`) are converted by `migrate_contract_state`.
+///
 /// It is used to store the following data:
-/// - information if the Ethereum token state import has already been performed,
+/// - the version of the layout,
+/// - the authority which is set to the signer of the transaction when contract is initialized so the signer becomes contract's owner,
+/// - the bitmask of migration sources whose token state has already been imported,
+/// - information if the vested wallets have already been initialized (the second phase of the initialization),
+/// - information if the burn and withdraw instructions can be invoked via CPI,
+/// - information if staking has been initialized, in which case part of every monthly burn is redirected to the stakers,
+/// - information if the configuration has been locked, after which the allocation of the vested wallets cannot be changed anymore,
 /// - contract state nonce,
 /// - the mint nonce,
 /// - the program account nonce,
 /// - the burning account nonce,
-/// - the last burning month and year,
-/// - the authority which is set to the signer of the transaction when contract is initialized so the signer becomes contract's owner.
+/// - the sequence number of the last state-mutating instruction, it is included in the emitted events to order them,
+/// - the maximal supply of the token which is raised only by the imports, no instruction can mint tokens above it,
+/// - the total amount of tokens donated to the burning account,
+/// - the amounts of tokens minted and burned by the import of each migration source,
+/// - the last burning year and month,
+/// - the token metadata program id used to set the token metadata, it can be changed only before Ethereum token state import,
+/// - the numerator of the fraction of a token which was owed by the previous burns but not burned because of rounding down,
+///   it is carried over to the next burn (in basis points of a token, see `calculate_monthly_burn_amount`),
+/// - the period after the vesting start during which no vested wallet can withdraw tokens, it is set during the first import,
+/// - the keepers allowed to run the automation instructions besides the authority, the free slots are set to the default public key,
+///   the registry has a fixed size so the offsets of the fields appended after it do not depend on the number of keepers,
+/// - the first and the last day of the secondary window in which the monthly burn missed in the primary window can be executed,
+/// - information if the withdrawals and burns have been paused by the owner, e.g. when the authority key is suspected to be compromised,
+/// - information if the owner has renounced the authority, the contract then runs autonomously and no owner instruction can be executed,
+/// - the burn, withdraw and metadata role authorities allowed to run the instructions of the role besides the owner,
+///   the default public key means the role is not assigned and only the owner can run them (see `Role`),
+/// - information if the mint authority has been revoked, no tokens can be minted afterwards,
+/// - the number of decimals of the mint chosen at the initialization,
+/// - the address of the mint, the mint accounts passed to the instructions and the mints of the token accounts are validated against it,
+/// - the rate of the monthly burn in basis points of the burning account balance, see `calculate_monthly_burn_amount`,
+/// - the number of days of the primary burn window starting on the 1st day of the month,
+/// - the reward of the burn caller in basis points of the burned amount, zero disables the reward,
+/// - the total amount of tokens transferred to the burning account by the fund_burning_account instruction,
+/// - the total amount of tokens burned from the burning account by the monthly and the manual burns.
 #[account]
 #[derive(InitSpace)]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
 pub struct ContractState {
-    pub import_ethereum_token_state_already_performed: bool,
+    pub version: u8,
+
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkey")
+    )]
+    pub authority: Pubkey,
+
+    pub performed_imports: u8,
+    pub wallets_initialized: bool,
+    pub cpi_allowed: bool,
+    pub staking_enabled: bool,
+    pub configuration_locked: bool,
+
+    pub contract_state_nonce: u8,
+    pub mint_nonce: u8,
+    pub program_account_nonce: u8,
+    pub burning_account_nonce: u8,
+
+    pub sequence: u64,
+    pub max_supply: u64,
+    pub total_donated: u64,
+    pub imported_minted: [u64; MIGRATION_SOURCES_COUNT],
+    pub imported_burned: [u64; MIGRATION_SOURCES_COUNT],
+
+    pub last_burning_year: i64,
+    pub last_burning_month: u8,
+
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkey")
+    )]
+    pub metadata_program_id: Pubkey,
+
+    pub burn_remainder_numerator: u64,
+
+    pub withdrawal_grace_period_seconds: i64,
+
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkeys")
+    )]
+    pub keepers: [Pubkey; MAX_KEEPERS],
+
+    pub late_burn_window_first_day: u8,
+    pub late_burn_window_last_day: u8,
+
+    pub paused: bool,
+
+    pub renounced: bool,
+
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkey")
+    )]
+    pub burn_authority: Pubkey,
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkey")
+    )]
+    pub withdraw_authority: Pubkey,
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkey")
+    )]
+    pub metadata_authority: Pubkey,
+
+    pub mint_authority_revoked: bool,
+
+    pub decimals: u8,
+
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkey")
+    )]
+    pub mint: Pubkey,
+
+    pub burn_rate_bps: u16,
+
+    pub burn_window_days: u8,
+
+    pub burn_caller_reward_bps: u16,
+
+    pub total_donated_for_burning: u64,
+
+    pub total_burned: u64,
+}
+
+/// Byte offsets of the `ContractState` fields in the account data, including the 8-byte discriminator.
+/// They are used to build `memcmp` filters of `getProgramAccounts` requests.
+impl ContractState {
+    pub const VERSION_OFFSET: usize = 8;
+    pub const AUTHORITY_OFFSET: usize = Self::VERSION_OFFSET + 1;
+
+    pub const PERFORMED_IMPORTS_OFFSET: usize = Self::AUTHORITY_OFFSET + 32;
+    pub const WALLETS_INITIALIZED_OFFSET: usize = Self::PERFORMED_IMPORTS_OFFSET + 1;
+    pub const CPI_ALLOWED_OFFSET: usize = Self::WALLETS_INITIALIZED_OFFSET + 1;
+    pub const STAKING_ENABLED_OFFSET: usize = Self::CPI_ALLOWED_OFFSET + 1;
+    pub const CONFIGURATION_LOCKED_OFFSET: usize = Self::STAKING_ENABLED_OFFSET + 1;
+
+    pub const CONTRACT_STATE_NONCE_OFFSET: usize = Self::CONFIGURATION_LOCKED_OFFSET + 1;
+    pub const MINT_NONCE_OFFSET: usize = Self::CONTRACT_STATE_NONCE_OFFSET + 1;
+    pub const PROGRAM_ACCOUNT_NONCE_OFFSET: usize = Self::MINT_NONCE_OFFSET + 1;
+    pub const BURNING_ACCOUNT_NONCE_OFFSET: usize = Self::PROGRAM_ACCOUNT_NONCE_OFFSET + 1;
+
+    pub const SEQUENCE_OFFSET: usize = Self::BURNING_ACCOUNT_NONCE_OFFSET + 1;
+    pub const MAX_SUPPLY_OFFSET: usize = Self::SEQUENCE_OFFSET + 8;
+    pub const TOTAL_DONATED_OFFSET: usize = Self::MAX_SUPPLY_OFFSET + 8;
+    pub const IMPORTED_MINTED_OFFSET: usize = Self::TOTAL_DONATED_OFFSET + 8;
+    pub const IMPORTED_BURNED_OFFSET: usize =
+        Self::IMPORTED_MINTED_OFFSET + 8 * MIGRATION_SOURCES_COUNT;
+
+    pub const LAST_BURNING_YEAR_OFFSET: usize =
+        Self::IMPORTED_BURNED_OFFSET + 8 * MIGRATION_SOURCES_COUNT;
+    pub const LAST_BURNING_MONTH_OFFSET: usize = Self::LAST_BURNING_YEAR_OFFSET + 8;
+
+    pub const METADATA_PROGRAM_ID_OFFSET: usize = Self::LAST_BURNING_MONTH_OFFSET + 1;
+
+    pub const BURN_REMAINDER_NUMERATOR_OFFSET: usize = Self::METADATA_PROGRAM_ID_OFFSET + 32;
+
+    pub const WITHDRAWAL_GRACE_PERIOD_SECONDS_OFFSET: usize =
+        Self::BURN_REMAINDER_NUMERATOR_OFFSET + 8;
+
+    pub const KEEPERS_OFFSET: usize = Self::WITHDRAWAL_GRACE_PERIOD_SECONDS_OFFSET + 8;
+
+    pub const LATE_BURN_WINDOW_FIRST_DAY_OFFSET: usize = Self::KEEPERS_OFFSET + 32 * MAX_KEEPERS;
+    pub const LATE_BURN_WINDOW_LAST_DAY_OFFSET: usize = Self::LATE_BURN_WINDOW_FIRST_DAY_OFFSET + 1;
+
+    pub const PAUSED_OFFSET: usize = Self::LATE_BURN_WINDOW_LAST_DAY_OFFSET + 1;
+
+    pub const RENOUNCED_OFFSET: usize = Self::PAUSED_OFFSET + 1;
+
+    pub const BURN_AUTHORITY_OFFSET: usize = Self::RENOUNCED_OFFSET + 1;
+    pub const WITHDRAW_AUTHORITY_OFFSET: usize = Self::BURN_AUTHORITY_OFFSET + 32;
+    pub const METADATA_AUTHORITY_OFFSET: usize = Self::WITHDRAW_AUTHORITY_OFFSET + 32;
+
+    pub const MINT_AUTHORITY_REVOKED_OFFSET: usize = Self::METADATA_AUTHORITY_OFFSET + 32;
+
+    pub const DECIMALS_OFFSET: usize = Self::MINT_AUTHORITY_REVOKED_OFFSET + 1;
+
+    pub const MINT_OFFSET: usize = Self::DECIMALS_OFFSET + 1;
+
+    pub const BURN_RATE_BPS_OFFSET: usize = Self::MINT_OFFSET + 32;
+
+    pub const BURN_WINDOW_DAYS_OFFSET: usize = Self::BURN_RATE_BPS_OFFSET + 2;
+
+    pub const BURN_CALLER_REWARD_BPS_OFFSET: usize = Self::BURN_WINDOW_DAYS_OFFSET + 1;
+
+    pub const TOTAL_DONATED_FOR_BURNING_OFFSET: usize = Self::BURN_CALLER_REWARD_BPS_OFFSET + 2;
+
+    pub const TOTAL_BURNED_OFFSET: usize = Self::TOTAL_DONATED_FOR_BURNING_OFFSET + 8;
+}
+
+impl ContractState {
+    /// Returns the authority assigned to the role, the default public key if the role is not assigned.
+    pub fn role_authority(&self, role: Role) -> Pubkey {
+        match role {
+            Role::Burn => self.burn_authority,
+            Role::Withdraw => self.withdraw_authority,
+            Role::Metadata => self.metadata_authority,
+        }
+    }
+
+    /// Returns a mutable reference to the authority assigned to the role.
+    pub fn role_authority_mut(&mut self, role: Role) -> &mut Pubkey {
+        match role {
+            Role::Burn => &mut self.burn_authority,
+            Role::Withdraw => &mut self.withdraw_authority,
+            Role::Metadata => &mut self.metadata_authority,
+        }
+    }
+}
+
+/// The borsh layout of `ContractState` used before the versioned layout with fixed offsets.
+/// It is only read by `migrate_contract_state` which converts the account to the current layout.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacyContractState {
+    pub performed_imports: u8,
+    pub imported_minted: [u64; MIGRATION_SOURCES_COUNT],
+    pub imported_burned: [u64; MIGRATION_SOURCES_COUNT],
+    pub max_supply: u64,
+    pub wallets_initialized: bool,
 
     pub contract_state_nonce: u8,
     pub mint_nonce: u8,
@@ -28,44 +259,1170 @@ pub struct ContractState {
     pub last_burning_month: u8,
     pub last_burning_year: i64,
 
+    pub cpi_allowed: bool,
+    pub staking_enabled: bool,
+
+    pub total_donated: u64,
+
+    pub metadata_program_id: Pubkey,
+
+    pub configuration_locked: bool,
+
+    pub sequence: u64,
+
     pub authority: Pubkey,
 }
 
+impl From<LegacyContractState> for ContractState {
+    fn from(legacy: LegacyContractState) -> Self {
+        ContractState {
+            version: CONTRACT_STATE_VERSION,
+            authority: legacy.authority,
+
+            performed_imports: legacy.performed_imports,
+            wallets_initialized: legacy.wallets_initialized,
+            cpi_allowed: legacy.cpi_allowed,
+            staking_enabled: legacy.staking_enabled,
+            configuration_locked: legacy.configuration_locked,
+
+            contract_state_nonce: legacy.contract_state_nonce,
+            mint_nonce: legacy.mint_nonce,
+            program_account_nonce: legacy.program_account_nonce,
+            burning_account_nonce: legacy.burning_account_nonce,
+
+            sequence: legacy.sequence,
+            max_supply: legacy.max_supply,
+            total_donated: legacy.total_donated,
+            imported_minted: legacy.imported_minted,
+            imported_burned: legacy.imported_burned,
+
+            last_burning_year: legacy.last_burning_year,
+            last_burning_month: legacy.last_burning_month,
+
+            metadata_program_id: legacy.metadata_program_id,
+
+            burn_remainder_numerator: 0,
+
+            withdrawal_grace_period_seconds: 0,
+
+            keepers: [Pubkey::default(); MAX_KEEPERS],
+
+            late_burn_window_first_day: DEFAULT_LATE_BURN_WINDOW_FIRST_DAY,
+            late_burn_window_last_day: DEFAULT_LATE_BURN_WINDOW_LAST_DAY,
+
+            paused: false,
+
+            renounced: false,
+
+            burn_authority: Pubkey::default(),
+            withdraw_authority: Pubkey::default(),
+            metadata_authority: Pubkey::default(),
+
+            mint_authority_revoked: false,
+            decimals: LEGACY_MINT_DECIMALS,
+            mint: Pubkey::default(),
+            burn_rate_bps: DEFAULT_BURN_RATE_BPS,
+            burn_window_days: DEFAULT_BURN_WINDOW_DAYS,
+            burn_caller_reward_bps: 0,
+            total_donated_for_burning: 0,
+            total_burned: 0,
+        }
+    }
+}
+
+/// The vesting state of a single vested wallet, see `VestingState::wallets`.
+///
+/// It is used to store the following data:
+/// - the initial balance of the wallet after Ethereum token state import,
+/// - the amount already withdrawn from the wallet,
+/// - the beneficiary owner which, when configured, must be the owner of the deposit wallet receiving withdrawn tokens,
+/// - the nonce of the wallet's token account.
+#[zero_copy]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub struct WalletVesting {
+    pub initial_balance: u64,
+    pub withdrawn: u64,
+
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkey")
+    )]
+    pub beneficiary_owner: Pubkey,
+
+    pub nonce: u8,
+
+    #[cfg_attr(not(target_os = "solana"), serde(skip))]
+    pub padding: [u8; 7],
+}
+
+/// The withdrawal history of a single vested wallet, see `VestingState::withdrawal_history`.
+/// Only the withdraw instructions which moved tokens from the wallet are recorded.
+///
+/// It is used to store the following data:
+/// - the timestamp of the last withdrawal, zero if the wallet has not been withdrawn from yet,
+/// - the number of withdrawals.
+#[zero_copy]
+#[derive(Default, Debug, PartialEq, Eq)]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub struct WithdrawalHistory {
+    pub last_withdrawal_timestamp: i64,
+    pub withdrawal_count: u32,
+
+    #[cfg_attr(not(target_os = "solana"), serde(skip))]
+    pub padding: [u8; 4],
+}
+
+/// The vesting schedule of a single vested wallet, see `VestingState::schedules`.
+/// It is set during the initialization of the wallets, the defaults match the schedules of the Ethereum token.
+/// The unlock delay of the community wallet set during Ethereum token state import is added to its cliff.
+///
+/// It is used to store the following data:
+/// - the part of the initial balance unlocked at the cliff, in basis points,
+/// - the part of the initial balance unlocked every month after the cliff, in basis points,
+/// - the number of months since the vesting start before the first unlock,
+/// - the number of months since the cliff after which the whole initial balance is unlocked,
+/// - the minimal amount unlocked after the cliff if the percentage math truncates to less.
+#[zero_copy]
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, PartialEq, Eq)]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub struct VestingSchedule {
+    pub initial_unlock_bps: u16,
+    pub monthly_unlock_bps: u16,
+    pub cliff_months: u16,
+    pub full_unlock_months: u8,
+    pub minimum_unlock: u8,
+}
+
+impl VestingSchedule {
+    /// Returns the default vesting schedules of the vested wallets ordered like `WalletKind`.
+    pub fn defaults() -> [VestingSchedule; VESTED_WALLETS_COUNT] {
+        [
+            COMMUNITY_WALLET_SCHEDULE.into(),
+            PARTNERSHIP_WALLET_SCHEDULE.into(),
+            MARKETING_WALLET_SCHEDULE.into(),
+            LIQUIDITY_WALLET_SCHEDULE.into(),
+        ]
+    }
+}
+
+impl From<UnlockSchedule> for VestingSchedule {
+    fn from(schedule: UnlockSchedule) -> Self {
+        VestingSchedule {
+            initial_unlock_bps: schedule.initial_unlock_bps,
+            monthly_unlock_bps: schedule.monthly_unlock_bps,
+            cliff_months: schedule.cliff_months,
+            full_unlock_months: schedule.full_unlock_months,
+            minimum_unlock: schedule.minimum_unlock,
+        }
+    }
+}
+
+impl From<VestingSchedule> for UnlockSchedule {
+    fn from(schedule: VestingSchedule) -> Self {
+        UnlockSchedule {
+            initial_unlock_bps: schedule.initial_unlock_bps,
+            monthly_unlock_bps: schedule.monthly_unlock_bps,
+            cliff_months: schedule.cliff_months,
+            full_unlock_months: schedule.full_unlock_months,
+            minimum_unlock: schedule.minimum_unlock,
+        }
+    }
+}
+
 /// The account that holds the state of the vesting.
 /// It is initialized only once during contract initialization.
-/// The state is updated only once after the initialization - during Ethereum token state import.
+/// The initial balances are set during Ethereum token state import and can be rebalanced until the configuration is locked.
+///
+/// The account is zero-copy so it is not deserialized by every instruction which touches it.
+/// The fields are ordered by alignment (per-wallet states, 8-byte integers, public keys, then single bytes) and padded
+/// to a multiple of 8 bytes so the layout has no implicit padding, the fields added later are appended at the end.
+/// The accounts created in the borsh layout, in the zero-copy layout with separate fields for every wallet
+/// or without the swap wallet balance, the vesting schedules, the beneficiaries, the withdrawal history, the receipt indexes
+/// or the revoked amounts
+/// are converted by `migrate_vesting_state`.
 ///
 /// It is used to store the following data:
+/// - the vesting state of each wallet indexed by `WalletKind`, see `WalletVesting`,
+/// - the monthly amount of the liquidity incentive drip together with the last year of the drip,
+/// - the vesting start timestamp which is used to calculate the amount of unlocked tokens for each wallet, it is set to the timestamp of Ethereum token state import,
+/// - the recipient of the liquidity incentive drip,
 /// - vesting state nonce,
-/// - the community wallet nonce,
-/// - the community wallet initial balance after Ethereum token state import,
-/// - the partnership wallet nonce,
-/// - the partnership wallet initial balance after Ethereum token state import,
-/// - the marketing wallet nonce,
-/// - the marketing wallet initial balance after Ethereum token state import,
-/// - the liquidity wallet nonce,
-/// - the liquidity wallet initial balance after Ethereum token state import,
-/// - the vesting start timestamp which is used to calculate the amount of unlocked tokens for each wallet, it is set to the timestamp of Ethereum token state import.
-#[account]
-#[derive(InitSpace)]
+/// - the last month of the liquidity incentive drip,
+/// - the bitmask of finalized wallets whose token accounts have been closed after full vesting and withdrawal,
+/// - the number of months before the first unlock of the community wallet, it is set during Ethereum token state import,
+/// - the bitmask of wallets whose monthly unlock is interpolated over the days of the month, see `linear_daily_unlock`,
+/// - the bitmask of wallets whose unvested tokens have been revoked by `revoke_unvested`,
+/// - the balance of the swap wallet set by the imports, the swap wallet is not vested but its allocation is tracked,
+/// - the vesting schedule of each wallet indexed by `WalletKind`, see `VestingSchedule`,
+/// - the beneficiary of each wallet indexed by `WalletKind` which is allowed to withdraw from the wallet besides the contract's owner,
+///   the default public key means that only the contract's owner can withdraw,
+/// - the withdrawal history of each wallet indexed by `WalletKind`, see `WithdrawalHistory`,
+/// - the index of the next withdrawal receipt of each wallet indexed by `WalletKind`, see `WithdrawalReceipt`,
+/// - the amount unlocked in each revoked wallet indexed by `WalletKind` at its revocation, the unlocked amount is frozen at it.
+#[account(zero_copy)]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
 pub struct VestingState {
+    pub wallets: [WalletVesting; VESTED_WALLETS_COUNT],
+
+    pub liquidity_drip_amount_per_month: u64,
+    pub last_liquidity_drip_year: i64,
+
+    pub start_timestamp: i64,
+
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkey")
+    )]
+    pub liquidity_drip_recipient: Pubkey,
+
+    pub vesting_state_nonce: u8,
+
+    pub last_liquidity_drip_month: u8,
+    pub finalized_wallets: u8,
+    pub community_unlock_delay_months: u8,
+    pub linear_daily_unlock_wallets: u8,
+    pub revoked_wallets: u8,
+
+    #[cfg_attr(not(target_os = "solana"), serde(skip))]
+    pub padding: [u8; 2],
+
+    pub initial_swap_wallet_balance: u64,
+
+    pub schedules: [VestingSchedule; VESTED_WALLETS_COUNT],
+
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkeys")
+    )]
+    pub beneficiaries: [Pubkey; VESTED_WALLETS_COUNT],
+
+    pub withdrawal_history: [WithdrawalHistory; VESTED_WALLETS_COUNT],
+
+    pub next_receipt_index: [u64; VESTED_WALLETS_COUNT],
+
+    pub revoked_unlocked: [u64; VESTED_WALLETS_COUNT],
+}
+
+/// The space of `VestingState` in the layout with per-wallet states which ended before the swap wallet balance.
+/// The fields of that layout have the same offsets, so it is converted by appending the new fields.
+pub const VESTING_STATE_V2_SPACE: usize = 288;
+
+/// The space of `VestingState` in the layout which ended before the vesting schedules.
+/// It is converted by appending the default schedules.
+pub const VESTING_STATE_V3_SPACE: usize = 296;
+
+/// The space of `VestingState` in the layout which ended before the beneficiaries.
+/// It is converted by appending the default beneficiaries, i.e. only the contract's owner can withdraw.
+pub const VESTING_STATE_V4_SPACE: usize = 328;
+
+/// The space of `VestingState` in the layout which ended before the withdrawal history.
+/// It is converted by appending the empty withdrawal history.
+pub const VESTING_STATE_V5_SPACE: usize = 456;
+
+/// The space of `VestingState` in the layout which ended before the receipt indexes.
+/// It is converted by appending zero indexes, i.e. no receipts have been created yet.
+pub const VESTING_STATE_V6_SPACE: usize = 520;
+
+/// The space of `VestingState` in the layout which ended before the revoked amounts.
+/// It is converted by appending zero amounts, no wallet could have been revoked in that layout.
+pub const VESTING_STATE_V7_SPACE: usize = 552;
+
+// the layouts must not change, the existing accounts are read with them
+const _: () = assert!(std::mem::size_of::<WalletVesting>() == 56);
+const _: () = assert!(std::mem::size_of::<VestingSchedule>() == 8);
+const _: () = assert!(std::mem::size_of::<WithdrawalHistory>() == 16);
+const _: () = assert!(std::mem::size_of::<VestingState>() == VESTING_STATE_V7_SPACE + 32);
+const _: () = assert!(std::mem::size_of::<VestingStateV1>() == 256);
+
+impl VestingState {
+    /// Returns the vesting state of the given vested wallet.
+    pub fn wallet(&self, wallet_kind: WalletKind) -> &WalletVesting {
+        &self.wallets[wallet_kind.index()]
+    }
+
+    /// Returns a mutable reference to the vesting state of the given vested wallet.
+    pub fn wallet_mut(&mut self, wallet_kind: WalletKind) -> &mut WalletVesting {
+        &mut self.wallets[wallet_kind.index()]
+    }
+
+    /// Returns the vesting schedule of the given vested wallet.
+    pub fn schedule(&self, wallet_kind: WalletKind) -> &VestingSchedule {
+        &self.schedules[wallet_kind.index()]
+    }
+
+    /// Returns the withdrawal history of the given vested wallet.
+    pub fn history(&self, wallet_kind: WalletKind) -> &WithdrawalHistory {
+        &self.withdrawal_history[wallet_kind.index()]
+    }
+
+    /// Returns whether the monthly unlock of the given vested wallet is interpolated over the days of the month,
+    /// see `UnlockSchedule::linearly_unlocked_amount`. It is set for the community and marketing wallets only, at initialization.
+    pub fn linear_daily_unlock(&self, wallet_kind: WalletKind) -> bool {
+        self.linear_daily_unlock_wallets & wallet_kind.mask() != 0
+    }
+
+    /// Returns whether the unvested tokens of the given vested wallet have been revoked, see `revoked_unlocked`.
+    pub fn revoked(&self, wallet_kind: WalletKind) -> bool {
+        self.revoked_wallets & wallet_kind.mask() != 0
+    }
+}
+
+/// The zero-copy layout of `VestingState` with separate fields for every wallet, used before the per-wallet states.
+/// It is only read by `migrate_vesting_state` which converts the account to the current layout.
+#[zero_copy]
+pub struct VestingStateV1 {
+    pub initial_community_wallet_balance: u64,
+    pub initial_partnership_wallet_balance: u64,
+    pub initial_marketing_wallet_balance: u64,
+    pub initial_liquidity_wallet_balance: u64,
+
+    pub already_withdrawn_community_wallet_amount: u64,
+    pub already_withdrawn_partnership_wallet_amount: u64,
+    pub already_withdrawn_marketing_wallet_amount: u64,
+    pub already_withdrawn_liquidity_wallet_amount: u64,
+
+    pub liquidity_drip_amount_per_month: u64,
+    pub last_liquidity_drip_year: i64,
+
+    pub start_timestamp: i64,
+
+    pub community_beneficiary_owner: Pubkey,
+    pub partnership_beneficiary_owner: Pubkey,
+    pub marketing_beneficiary_owner: Pubkey,
+    pub liquidity_beneficiary_owner: Pubkey,
+
+    pub liquidity_drip_recipient: Pubkey,
+
+    pub vesting_state_nonce: u8,
+    pub community_wallet_nonce: u8,
+    pub partnership_wallet_nonce: u8,
+    pub marketing_wallet_nonce: u8,
+    pub liquidity_wallet_nonce: u8,
+
+    pub last_liquidity_drip_month: u8,
+    pub finalized_wallets: u8,
+
+    pub padding: [u8; 1],
+}
+
+impl WalletVesting {
+    /// Creates the vesting state of a wallet.
+    pub fn new(nonce: u8, initial_balance: u64, withdrawn: u64, beneficiary_owner: Pubkey) -> Self {
+        WalletVesting {
+            initial_balance,
+            withdrawn,
+            beneficiary_owner,
+            nonce,
+            padding: [0; 7],
+        }
+    }
+}
+
+impl From<VestingStateV1> for VestingState {
+    fn from(v1: VestingStateV1) -> Self {
+        VestingState {
+            wallets: [
+                WalletVesting::new(
+                    v1.community_wallet_nonce,
+                    v1.initial_community_wallet_balance,
+                    v1.already_withdrawn_community_wallet_amount,
+                    v1.community_beneficiary_owner,
+                ),
+                WalletVesting::new(
+                    v1.partnership_wallet_nonce,
+                    v1.initial_partnership_wallet_balance,
+                    v1.already_withdrawn_partnership_wallet_amount,
+                    v1.partnership_beneficiary_owner,
+                ),
+                WalletVesting::new(
+                    v1.marketing_wallet_nonce,
+                    v1.initial_marketing_wallet_balance,
+                    v1.already_withdrawn_marketing_wallet_amount,
+                    v1.marketing_beneficiary_owner,
+                ),
+                WalletVesting::new(
+                    v1.liquidity_wallet_nonce,
+                    v1.initial_liquidity_wallet_balance,
+                    v1.already_withdrawn_liquidity_wallet_amount,
+                    v1.liquidity_beneficiary_owner,
+                ),
+            ],
+
+            liquidity_drip_amount_per_month: v1.liquidity_drip_amount_per_month,
+            last_liquidity_drip_year: v1.last_liquidity_drip_year,
+
+            start_timestamp: v1.start_timestamp,
+
+            liquidity_drip_recipient: v1.liquidity_drip_recipient,
+
+            vesting_state_nonce: v1.vesting_state_nonce,
+
+            last_liquidity_drip_month: v1.last_liquidity_drip_month,
+            finalized_wallets: v1.finalized_wallets,
+            community_unlock_delay_months: 0,
+            linear_daily_unlock_wallets: 0,
+            revoked_wallets: 0,
+
+            padding: [0; 2],
+
+            initial_swap_wallet_balance: 0,
+
+            schedules: VestingSchedule::defaults(),
+            beneficiaries: [Pubkey::default(); VESTED_WALLETS_COUNT],
+            withdrawal_history: [WithdrawalHistory::default(); VESTED_WALLETS_COUNT],
+            next_receipt_index: [0; VESTED_WALLETS_COUNT],
+            revoked_unlocked: [0; VESTED_WALLETS_COUNT],
+        }
+    }
+}
+
+/// The borsh layout of `VestingState` used before the account became zero-copy.
+/// It is only read by `migrate_vesting_state` which converts the account to the zero-copy layout.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacyVestingState {
     pub vesting_state_nonce: u8,
 
     pub community_wallet_nonce: u8,
     pub initial_community_wallet_balance: u64,
     pub already_withdrawn_community_wallet_amount: u64,
+    pub community_beneficiary_owner: Pubkey,
 
     pub partnership_wallet_nonce: u8,
     pub initial_partnership_wallet_balance: u64,
     pub already_withdrawn_partnership_wallet_amount: u64,
+    pub partnership_beneficiary_owner: Pubkey,
 
     pub marketing_wallet_nonce: u8,
     pub initial_marketing_wallet_balance: u64,
     pub already_withdrawn_marketing_wallet_amount: u64,
+    pub marketing_beneficiary_owner: Pubkey,
 
     pub liquidity_wallet_nonce: u8,
     pub initial_liquidity_wallet_balance: u64,
     pub already_withdrawn_liquidity_wallet_amount: u64,
+    pub liquidity_beneficiary_owner: Pubkey,
+
+    pub liquidity_drip_recipient: Pubkey,
+    pub liquidity_drip_amount_per_month: u64,
+    pub last_liquidity_drip_month: u8,
+    pub last_liquidity_drip_year: i64,
+
+    pub finalized_wallets: u8,
 
     pub start_timestamp: i64,
 }
+
+impl From<LegacyVestingState> for VestingState {
+    fn from(legacy: LegacyVestingState) -> Self {
+        VestingState {
+            wallets: [
+                WalletVesting::new(
+                    legacy.community_wallet_nonce,
+                    legacy.initial_community_wallet_balance,
+                    legacy.already_withdrawn_community_wallet_amount,
+                    legacy.community_beneficiary_owner,
+                ),
+                WalletVesting::new(
+                    legacy.partnership_wallet_nonce,
+                    legacy.initial_partnership_wallet_balance,
+                    legacy.already_withdrawn_partnership_wallet_amount,
+                    legacy.partnership_beneficiary_owner,
+                ),
+                WalletVesting::new(
+                    legacy.marketing_wallet_nonce,
+                    legacy.initial_marketing_wallet_balance,
+                    legacy.already_withdrawn_marketing_wallet_amount,
+                    legacy.marketing_beneficiary_owner,
+                ),
+                WalletVesting::new(
+                    legacy.liquidity_wallet_nonce,
+                    legacy.initial_liquidity_wallet_balance,
+                    legacy.already_withdrawn_liquidity_wallet_amount,
+                    legacy.liquidity_beneficiary_owner,
+                ),
+            ],
+
+            liquidity_drip_amount_per_month: legacy.liquidity_drip_amount_per_month,
+            last_liquidity_drip_year: legacy.last_liquidity_drip_year,
+
+            start_timestamp: legacy.start_timestamp,
+
+            liquidity_drip_recipient: legacy.liquidity_drip_recipient,
+
+            vesting_state_nonce: legacy.vesting_state_nonce,
+
+            last_liquidity_drip_month: legacy.last_liquidity_drip_month,
+            finalized_wallets: legacy.finalized_wallets,
+            community_unlock_delay_months: 0,
+            linear_daily_unlock_wallets: 0,
+            revoked_wallets: 0,
+
+            padding: [0; 2],
+
+            initial_swap_wallet_balance: 0,
+
+            schedules: VestingSchedule::defaults(),
+            beneficiaries: [Pubkey::default(); VESTED_WALLETS_COUNT],
+            withdrawal_history: [WithdrawalHistory::default(); VESTED_WALLETS_COUNT],
+            next_receipt_index: [0; VESTED_WALLETS_COUNT],
+            revoked_unlocked: [0; VESTED_WALLETS_COUNT],
+        }
+    }
+}
+
+/// The account that holds the latest proof-of-reserve attestation.
+/// It is initialized during the first attestation and updated by every following attestation.
+///
+/// It is used to store the following data:
+/// - attestation nonce,
+/// - the slot in which the latest attestation was performed,
+/// - the timestamp of the latest attestation which is used to limit attestations to one per day,
+/// - the hash of the slot, mint supply and balances of all program-custodied token accounts.
+#[account]
+#[derive(InitSpace)]
+pub struct Attestation {
+    pub attestation_nonce: u8,
+
+    pub last_attestation_slot: u64,
+    pub last_attestation_timestamp: i64,
+    pub last_attestation_hash: [u8; 32],
+}
+
+/// The account that holds the global staking state.
+/// It is initialized by the `initialize_staking` instruction and updated by every stake, unstake and burn.
+///
+/// It is used to store the following data:
+/// - staking state nonce,
+/// - the stake vault nonce,
+/// - the reward vault nonce,
+/// - the percentage points of the burning account balance redirected to the reward vault instead of being burned,
+/// - the number of seconds unstaked tokens stay locked before they can be withdrawn,
+/// - the total amount of staked tokens,
+/// - the accumulated rewards per staked token, scaled by `REWARD_PER_TOKEN_PRECISION`.
+#[account]
+#[derive(InitSpace)]
+pub struct StakingState {
+    pub staking_state_nonce: u8,
+    pub stake_vault_nonce: u8,
+    pub reward_vault_nonce: u8,
+
+    pub reward_percentage: u8,
+    pub unbonding_period: i64,
+
+    pub total_staked: u64,
+    pub reward_per_token: u128,
+}
+
+/// The account that holds the stake of a single staker.
+/// It is initialized during the first stake of the staker.
+///
+/// It is used to store the following data:
+/// - stake account nonce,
+/// - the owner of the stake,
+/// - the amount of staked tokens,
+/// - the rewards already accounted for the staked amount, scaled like in a masterchef-style pool,
+/// - the rewards earned but not claimed yet,
+/// - the amount of unstaked tokens waiting for the end of the unbonding period,
+/// - the timestamp when the unbonding period ends.
+#[account]
+#[derive(InitSpace)]
+pub struct StakeAccount {
+    pub stake_account_nonce: u8,
+    pub owner: Pubkey,
+
+    pub staked_amount: u64,
+    pub reward_debt: u128,
+    pub pending_rewards: u64,
+
+    pub unbonding_amount: u64,
+    pub unbonding_end_timestamp: i64,
+}
+
+/// The account that holds an OTC deal with a partner, there is at most one open deal per partner.
+/// It is initialized during the first deal with the partner and reused by the following deals.
+///
+/// It is used to store the following data:
+/// - OTC deal nonce,
+/// - the OTC vault nonce, the vault escrows the tokens of the deal,
+/// - the partner who receives the tokens,
+/// - the amount of escrowed tokens, zero if there is no open deal,
+/// - the timestamp after which the partner can settle the deal.
+#[account]
+#[derive(InitSpace)]
+pub struct OtcDeal {
+    pub otc_deal_nonce: u8,
+    pub otc_vault_nonce: u8,
+
+    pub partner: Pubkey,
+    pub lean_amount: u64,
+    pub unlock_timestamp: i64,
+}
+
+/// The account that holds the cumulative donations of a single donor to the burning account.
+/// It is initialized during the first donation of the donor.
+///
+/// It is used to store the following data:
+/// - donor account nonce,
+/// - the donor,
+/// - the total amount of tokens donated by the donor.
+#[account]
+#[derive(InitSpace)]
+pub struct DonorAccount {
+    pub donor_account_nonce: u8,
+    pub donor: Pubkey,
+    pub total_donated: u64,
+}
+
+/// The account that holds the human-readable labels of the program token accounts.
+/// It is initialized together with the vested wallets and it can be updated until the configuration is locked.
+///
+/// It is used to store the following data:
+/// - labels nonce,
+/// - the UTF-8 encoded labels of the program token accounts ordered like `LabeledAccountKind`, padded with zeros.
+#[account]
+#[derive(InitSpace)]
+pub struct Labels {
+    pub labels_nonce: u8,
+    pub labels: [[u8; LABEL_LENGTH]; LABELED_ACCOUNTS_COUNT],
+}
+
+/// The account that records a withdrawal submitted with a client-supplied idempotency key.
+/// It is initialized by the withdrawal and its existence rejects any replay of the withdrawal with the same key.
+/// It can be closed by its payer once the retention period has elapsed.
+///
+/// It is used to store the following data:
+/// - withdrawal intent nonce,
+/// - the payer of the account rent who receives it back when the account is closed,
+/// - the amount of withdrawn tokens,
+/// - the timestamp of the withdrawal.
+#[account]
+#[derive(InitSpace)]
+pub struct WithdrawalIntent {
+    pub withdrawal_intent_nonce: u8,
+    pub payer: Pubkey,
+    pub amount: u64,
+    pub created_timestamp: i64,
+}
+
+/// The account that records a withdrawal from a vested wallet made by one of the per-wallet withdraw instructions.
+/// A receipt is created by every withdrawal and it is never modified or closed, so it is a permanent trail of the withdrawals.
+/// The receipts of a wallet are numbered from zero, see `VestingState::next_receipt_index`.
+///
+/// It is used to store the following data:
+/// - withdrawal receipt nonce,
+/// - the vested wallet the tokens were withdrawn from,
+/// - the index of the receipt among the receipts of the wallet,
+/// - the amount of withdrawn tokens, zero if nothing was available to withdraw,
+/// - the deposit wallet which received the tokens,
+/// - the timestamp of the withdrawal,
+/// - the amount unlocked in the wallet as of the withdrawal,
+/// - the amount withdrawn from the wallet in total, including this withdrawal.
+#[account]
+#[derive(InitSpace)]
+pub struct WithdrawalReceipt {
+    pub withdrawal_receipt_nonce: u8,
+    pub wallet_kind: WalletKind,
+    pub index: u64,
+    pub amount: u64,
+    pub destination: Pubkey,
+    pub timestamp: i64,
+    pub unlocked_total: u64,
+    pub already_withdrawn_total: u64,
+}
+
+/// The account that records the delegate of a vested wallet approved by `approve_wallet_delegate`.
+/// The allowance is counted as withdrawn from the wallet as soon as it is approved, so it cannot be withdrawn twice.
+/// The amounts transferred by the delegate are tracked by comparing the allowance with the delegated amount of the token account.
+///
+/// It is used to store the following data:
+/// - wallet delegation nonce,
+/// - the current delegate of the wallet, the default public key if there is none,
+/// - the allowance not transferred by the delegate yet, as of the last reconciliation,
+/// - the total amount of tokens transferred by the delegates of the wallet.
+#[account]
+#[derive(InitSpace)]
+pub struct WalletDelegation {
+    pub wallet_delegation_nonce: u8,
+    pub delegate: Pubkey,
+    pub allowance: u64,
+    pub transferred: u64,
+}
+
+/// The account that tracks a staged import whose entries are transferred by several `import_batch` instructions.
+/// It is initialized by `begin_import` and closed by `finalize_import` once the tokens of every entry have been transferred.
+///
+/// It is used to store the following data:
+/// - import progress nonce,
+/// - the migration source of the imported token state,
+/// - number of decimals of the imported balances,
+/// - whether it is the first import which funds the vested wallets,
+/// - number of entries of the import,
+/// - the bitmap of the entries whose tokens have already been transferred, a retried batch skips them.
+#[account]
+#[derive(InitSpace)]
+pub struct ImportProgress {
+    pub import_progress_nonce: u8,
+    pub source: MigrationSource,
+    pub source_decimals: u8,
+    pub first_import: bool,
+    pub entries_count: u16,
+    pub transferred: [u8; MAX_STAGED_IMPORT_ENTRIES / 8],
+}
+
+/// A burn recorded in the burn history, see `BurnHistory`.
+///
+/// It is used to store the following data:
+/// - the timestamp of the burn,
+/// - the amount of burned tokens, without the staking rewards and the reward of the burn caller,
+/// - the balance left in the burning account after the burn.
+#[derive(
+    AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, Default, Debug, PartialEq, Eq,
+)]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub struct BurnRecord {
+    pub timestamp: i64,
+    pub amount: u64,
+    pub burning_account_balance_after: u64,
+}
+
+/// The account that keeps the latest burns, so they can be shown without relying on the retention of the events.
+/// It is initialized by `initialize_burn_history` and every burn, monthly, catch-up or manual, appends a record to it.
+/// The records form a ring buffer of `BURN_HISTORY_CAPACITY` entries: once it is full, the next burn overwrites the oldest record.
+///
+/// It is used to store the following data:
+/// - burn history nonce,
+/// - the index of the record overwritten by the next burn,
+/// - the number of burns recorded since the initialization, including the overwritten ones,
+/// - the records, zeroed until they are written for the first time.
+#[account]
+#[derive(InitSpace)]
+pub struct BurnHistory {
+    pub burn_history_nonce: u8,
+    pub cursor: u16,
+    pub burns_count: u64,
+    pub records: [BurnRecord; BURN_HISTORY_CAPACITY],
+}
+
+impl BurnHistory {
+    /// Appends the record of a burn, overwriting the oldest record once the history is full.
+    pub fn record(&mut self, record: BurnRecord) {
+        self.records[usize::from(self.cursor)] = record;
+        self.cursor = ((usize::from(self.cursor) + 1) % BURN_HISTORY_CAPACITY) as u16;
+        self.burns_count += 1;
+    }
+
+    /// Returns the kept records ordered from the oldest to the latest burn.
+    pub fn latest_burns(&self) -> Vec<BurnRecord> {
+        let kept = (self.burns_count as usize).min(BURN_HISTORY_CAPACITY);
+        let oldest =
+            (usize::from(self.cursor) + BURN_HISTORY_CAPACITY - kept) % BURN_HISTORY_CAPACITY;
+
+        (0..kept)
+            .map(|offset| self.records[(oldest + offset) % BURN_HISTORY_CAPACITY])
+            .collect()
+    }
+}
+
+impl ImportProgress {
+    /// Returns whether the tokens of the entry with the given index have already been transferred.
+    pub fn is_transferred(&self, index: u16) -> bool {
+        self.transferred[usize::from(index / 8)] & (1 << (index % 8)) != 0
+    }
+
+    /// Marks the entry with the given index as transferred.
+    pub fn mark_transferred(&mut self, index: u16) {
+        self.transferred[usize::from(index / 8)] |= 1 << (index % 8);
+    }
+
+    /// Returns whether the tokens of all entries of the import have been transferred.
+    pub fn all_transferred(&self) -> bool {
+        (0..self.entries_count).all(|index| self.is_transferred(index))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::{AccountSerialize, Discriminator};
+    use leancoin_core::VestingCurve;
+
+    fn contract_state_fixture() -> ContractState {
+        ContractState {
+            version: CONTRACT_STATE_VERSION,
+            authority: Pubkey::new_unique(),
+            performed_imports: 3,
+            wallets_initialized: true,
+            cpi_allowed: false,
+            staking_enabled: true,
+            configuration_locked: true,
+            contract_state_nonce: 255,
+            mint_nonce: 254,
+            program_account_nonce: 253,
+            burning_account_nonce: 252,
+            sequence: 0x0102030405060708,
+            max_supply: 10000000000000000000,
+            total_donated: 400,
+            imported_minted: [10000000000000000000, 1000000000000],
+            imported_burned: [1470000000000000000, 0],
+            last_burning_year: 2023,
+            last_burning_month: 3,
+            metadata_program_id: Pubkey::new_unique(),
+            burn_remainder_numerator: 9500,
+            withdrawal_grace_period_seconds: 604800,
+            keepers: std::array::from_fn(|_| Pubkey::new_unique()),
+            late_burn_window_first_day: 14,
+            late_burn_window_last_day: 21,
+            paused: true,
+            renounced: true,
+            burn_authority: Pubkey::new_unique(),
+            withdraw_authority: Pubkey::new_unique(),
+            metadata_authority: Pubkey::new_unique(),
+            mint_authority_revoked: true,
+            decimals: 6,
+            mint: Pubkey::new_unique(),
+            burn_rate_bps: 250,
+            burn_window_days: 10,
+            burn_caller_reward_bps: 25,
+            total_donated_for_burning: 1000,
+            total_burned: 90000000000000000,
+        }
+    }
+
+    #[test]
+    fn test_contract_state_field_offsets() {
+        let state = contract_state_fixture();
+        let mut data = vec![];
+        state.try_serialize(&mut data).unwrap();
+
+        let field = |offset: usize, len: usize| &data[offset..offset + len];
+
+        assert_eq!(data.len(), 8 + ContractState::INIT_SPACE);
+        assert_eq!(field(0, 8), ContractState::discriminator());
+        assert_eq!(
+            field(ContractState::VERSION_OFFSET, 1),
+            [CONTRACT_STATE_VERSION]
+        );
+        assert_eq!(
+            field(ContractState::AUTHORITY_OFFSET, 32),
+            state.authority.as_ref()
+        );
+        assert_eq!(field(ContractState::PERFORMED_IMPORTS_OFFSET, 1), [3]);
+        assert_eq!(field(ContractState::WALLETS_INITIALIZED_OFFSET, 1), [1]);
+        assert_eq!(field(ContractState::CPI_ALLOWED_OFFSET, 1), [0]);
+        assert_eq!(field(ContractState::STAKING_ENABLED_OFFSET, 1), [1]);
+        assert_eq!(field(ContractState::CONFIGURATION_LOCKED_OFFSET, 1), [1]);
+        assert_eq!(field(ContractState::CONTRACT_STATE_NONCE_OFFSET, 1), [255]);
+        assert_eq!(field(ContractState::MINT_NONCE_OFFSET, 1), [254]);
+        assert_eq!(field(ContractState::PROGRAM_ACCOUNT_NONCE_OFFSET, 1), [253]);
+        assert_eq!(field(ContractState::BURNING_ACCOUNT_NONCE_OFFSET, 1), [252]);
+        assert_eq!(
+            field(ContractState::SEQUENCE_OFFSET, 8),
+            state.sequence.to_le_bytes()
+        );
+        assert_eq!(
+            field(ContractState::MAX_SUPPLY_OFFSET, 8),
+            state.max_supply.to_le_bytes()
+        );
+        assert_eq!(
+            field(ContractState::TOTAL_DONATED_OFFSET, 8),
+            state.total_donated.to_le_bytes()
+        );
+        for source in 0..MIGRATION_SOURCES_COUNT {
+            assert_eq!(
+                field(ContractState::IMPORTED_MINTED_OFFSET + 8 * source, 8),
+                state.imported_minted[source].to_le_bytes()
+            );
+            assert_eq!(
+                field(ContractState::IMPORTED_BURNED_OFFSET + 8 * source, 8),
+                state.imported_burned[source].to_le_bytes()
+            );
+        }
+        assert_eq!(
+            field(ContractState::LAST_BURNING_YEAR_OFFSET, 8),
+            state.last_burning_year.to_le_bytes()
+        );
+        assert_eq!(field(ContractState::LAST_BURNING_MONTH_OFFSET, 1), [3]);
+        assert_eq!(
+            field(ContractState::METADATA_PROGRAM_ID_OFFSET, 32),
+            state.metadata_program_id.as_ref()
+        );
+        assert_eq!(
+            field(ContractState::BURN_REMAINDER_NUMERATOR_OFFSET, 8),
+            state.burn_remainder_numerator.to_le_bytes()
+        );
+        assert_eq!(
+            field(ContractState::WITHDRAWAL_GRACE_PERIOD_SECONDS_OFFSET, 8),
+            state.withdrawal_grace_period_seconds.to_le_bytes()
+        );
+        for (index, keeper) in state.keepers.iter().enumerate() {
+            assert_eq!(
+                field(ContractState::KEEPERS_OFFSET + 32 * index, 32),
+                keeper.as_ref()
+            );
+        }
+        assert_eq!(
+            field(ContractState::LATE_BURN_WINDOW_FIRST_DAY_OFFSET, 1),
+            [14]
+        );
+        assert_eq!(
+            field(ContractState::LATE_BURN_WINDOW_LAST_DAY_OFFSET, 1),
+            [21]
+        );
+        assert_eq!(field(ContractState::PAUSED_OFFSET, 1), [1]);
+        assert_eq!(field(ContractState::RENOUNCED_OFFSET, 1), [1]);
+        assert_eq!(
+            field(ContractState::BURN_AUTHORITY_OFFSET, 32),
+            state.burn_authority.as_ref()
+        );
+        assert_eq!(
+            field(ContractState::WITHDRAW_AUTHORITY_OFFSET, 32),
+            state.withdraw_authority.as_ref()
+        );
+        assert_eq!(
+            field(ContractState::METADATA_AUTHORITY_OFFSET, 32),
+            state.metadata_authority.as_ref()
+        );
+        assert_eq!(field(ContractState::MINT_AUTHORITY_REVOKED_OFFSET, 1), [1]);
+        assert_eq!(field(ContractState::DECIMALS_OFFSET, 1), [6]);
+        assert_eq!(field(ContractState::MINT_OFFSET, 32), state.mint.as_ref());
+        assert_eq!(
+            field(ContractState::BURN_RATE_BPS_OFFSET, 2),
+            250u16.to_le_bytes()
+        );
+        assert_eq!(field(ContractState::BURN_WINDOW_DAYS_OFFSET, 1), [10]);
+        assert_eq!(
+            field(ContractState::BURN_CALLER_REWARD_BPS_OFFSET, 2),
+            25u16.to_le_bytes()
+        );
+        assert_eq!(
+            field(ContractState::TOTAL_DONATED_FOR_BURNING_OFFSET, 8),
+            state.total_donated_for_burning.to_le_bytes()
+        );
+        assert_eq!(
+            field(ContractState::TOTAL_BURNED_OFFSET, 8),
+            state.total_burned.to_le_bytes()
+        );
+        assert_eq!(ContractState::TOTAL_BURNED_OFFSET + 8, data.len());
+    }
+
+    #[test]
+    fn test_convert_legacy_contract_state() {
+        let state = ContractState {
+            burn_remainder_numerator: 0,
+            withdrawal_grace_period_seconds: 0,
+            keepers: [Pubkey::default(); MAX_KEEPERS],
+            late_burn_window_first_day: DEFAULT_LATE_BURN_WINDOW_FIRST_DAY,
+            late_burn_window_last_day: DEFAULT_LATE_BURN_WINDOW_LAST_DAY,
+            paused: false,
+            renounced: false,
+            burn_authority: Pubkey::default(),
+            withdraw_authority: Pubkey::default(),
+            metadata_authority: Pubkey::default(),
+            mint_authority_revoked: false,
+            decimals: LEGACY_MINT_DECIMALS,
+            mint: Pubkey::default(),
+            burn_rate_bps: DEFAULT_BURN_RATE_BPS,
+            burn_window_days: DEFAULT_BURN_WINDOW_DAYS,
+            burn_caller_reward_bps: 0,
+            total_donated_for_burning: 0,
+            total_burned: 0,
+            ..contract_state_fixture()
+        };
+        let legacy = LegacyContractState {
+            performed_imports: state.performed_imports,
+            imported_minted: state.imported_minted,
+            imported_burned: state.imported_burned,
+            max_supply: state.max_supply,
+            wallets_initialized: state.wallets_initialized,
+            contract_state_nonce: state.contract_state_nonce,
+            mint_nonce: state.mint_nonce,
+            program_account_nonce: state.program_account_nonce,
+            burning_account_nonce: state.burning_account_nonce,
+            last_burning_month: state.last_burning_month,
+            last_burning_year: state.last_burning_year,
+            cpi_allowed: state.cpi_allowed,
+            staking_enabled: state.staking_enabled,
+            total_donated: state.total_donated,
+            metadata_program_id: state.metadata_program_id,
+            configuration_locked: state.configuration_locked,
+            sequence: state.sequence,
+            authority: state.authority,
+        };
+
+        let mut migrated_data = vec![];
+        ContractState::from(legacy)
+            .try_serialize(&mut migrated_data)
+            .unwrap();
+        let mut expected_data = vec![];
+        state.try_serialize(&mut expected_data).unwrap();
+
+        assert_eq!(migrated_data, expected_data);
+        // the legacy fields fill the versioned layout up to the burn remainder, the fields after it are new
+        assert_eq!(
+            ContractState::VERSION_OFFSET + 1 + LegacyContractState::INIT_SPACE,
+            ContractState::BURN_REMAINDER_NUMERATOR_OFFSET
+        );
+    }
+
+    #[test]
+    fn test_convert_vesting_state_v1() {
+        let beneficiary_owner = Pubkey::new_unique();
+        let v1 = VestingStateV1 {
+            initial_community_wallet_balance: 1000000000000000000,
+            initial_partnership_wallet_balance: 2000000000000000000,
+            initial_marketing_wallet_balance: 1500000000000000000,
+            initial_liquidity_wallet_balance: 1000000000000000000,
+            already_withdrawn_community_wallet_amount: 1,
+            already_withdrawn_partnership_wallet_amount: 2,
+            already_withdrawn_marketing_wallet_amount: 3,
+            already_withdrawn_liquidity_wallet_amount: 4,
+            liquidity_drip_amount_per_month: 5,
+            last_liquidity_drip_year: 2023,
+            start_timestamp: 1677978061,
+            community_beneficiary_owner: Pubkey::default(),
+            partnership_beneficiary_owner: Pubkey::default(),
+            marketing_beneficiary_owner: beneficiary_owner,
+            liquidity_beneficiary_owner: Pubkey::default(),
+            liquidity_drip_recipient: Pubkey::default(),
+            vesting_state_nonce: 255,
+            community_wallet_nonce: 254,
+            partnership_wallet_nonce: 253,
+            marketing_wallet_nonce: 252,
+            liquidity_wallet_nonce: 251,
+            last_liquidity_drip_month: 3,
+            finalized_wallets: 0b1000,
+            padding: [0; 1],
+        };
+
+        let state = VestingState::from(v1);
+
+        let wallets = WalletKind::ALL.map(|wallet_kind| *state.wallet(wallet_kind));
+        assert_eq!(wallets.map(|wallet| wallet.nonce), [254, 253, 252, 251]);
+        assert_eq!(
+            wallets.map(|wallet| wallet.initial_balance),
+            [
+                1000000000000000000,
+                2000000000000000000,
+                1500000000000000000,
+                1000000000000000000
+            ]
+        );
+        assert_eq!(wallets.map(|wallet| wallet.withdrawn), [1, 2, 3, 4]);
+        assert_eq!(
+            state.wallet(WalletKind::Marketing).beneficiary_owner,
+            beneficiary_owner
+        );
+        assert_eq!(
+            state.wallet(WalletKind::Community).beneficiary_owner,
+            Pubkey::default()
+        );
+        assert_eq!(state.vesting_state_nonce, 255);
+        assert_eq!(state.liquidity_drip_amount_per_month, 5);
+        assert_eq!(state.last_liquidity_drip_year, 2023);
+        assert_eq!(state.last_liquidity_drip_month, 3);
+        assert_eq!(state.start_timestamp, 1677978061);
+        assert_eq!(state.community_unlock_delay_months, 0);
+        assert_eq!(state.linear_daily_unlock_wallets, 0);
+        assert_eq!(state.initial_swap_wallet_balance, 0);
+        assert_eq!(state.finalized_wallets, 0b1000);
+        assert_eq!(state.schedules, VestingSchedule::defaults());
+        assert_eq!(
+            state.beneficiaries,
+            [Pubkey::default(); VESTED_WALLETS_COUNT]
+        );
+        assert_eq!(
+            state.withdrawal_history,
+            [WithdrawalHistory::default(); VESTED_WALLETS_COUNT]
+        );
+        assert_eq!(state.next_receipt_index, [0; VESTED_WALLETS_COUNT]);
+        assert_eq!(state.revoked_wallets, 0);
+        assert_eq!(state.revoked_unlocked, [0; VESTED_WALLETS_COUNT]);
+    }
+
+    #[test]
+    fn test_default_vesting_schedules() {
+        let schedules = VestingSchedule::defaults();
+
+        for (wallet_kind, curve) in WalletKind::ALL.into_iter().zip([
+            VestingCurve::Community {
+                unlock_delay_months: 0,
+            },
+            VestingCurve::Partnership,
+            VestingCurve::Marketing,
+            VestingCurve::Liquidity,
+        ]) {
+            let schedule = UnlockSchedule::from(schedules[wallet_kind.index()]);
+            assert_eq!(
+                VestingSchedule::from(schedule),
+                schedules[wallet_kind.index()]
+            );
+            for months in 0..50 {
+                assert_eq!(
+                    schedule.unlocked_amount(1000000007, months),
+                    curve.unlocked_amount(1000000007, months),
+                    "{:?} {}",
+                    wallet_kind,
+                    months
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_import_progress_bitmap() {
+        let mut progress = ImportProgress {
+            import_progress_nonce: 255,
+            source: MigrationSource::Ethereum,
+            source_decimals: 18,
+            first_import: true,
+            entries_count: 10,
+            transferred: [0; MAX_STAGED_IMPORT_ENTRIES / 8],
+        };
+
+        for index in [0, 7, 8, 9] {
+            progress.mark_transferred(index);
+        }
+        assert!(progress.is_transferred(8));
+        assert!(!progress.is_transferred(1));
+        assert!(!progress.all_transferred());
+
+        for index in 1..7 {
+            progress.mark_transferred(index);
+        }
+        assert!(progress.all_transferred());
+        assert_eq!(progress.transferred[..2], [0xff, 0b11]);
+
+        progress.mark_transferred(255);
+        assert!(progress.is_transferred(255));
+    }
+
+    #[test]
+    fn test_burn_history_ring() {
+        let mut history = BurnHistory {
+            burn_history_nonce: 255,
+            cursor: 0,
+            burns_count: 0,
+            records: [BurnRecord::default(); BURN_HISTORY_CAPACITY],
+        };
+        assert!(history.latest_burns().is_empty());
+
+        let records: Vec<BurnRecord> = (1..=BURN_HISTORY_CAPACITY as u64 + 1)
+            .map(|month| BurnRecord {
+                timestamp: month as i64,
+                amount: month * 100,
+                burning_account_balance_after: 10000 - month * 100,
+            })
+            .collect();
+
+        history.record(records[0]);
+        assert_eq!(history.latest_burns(), records[..1].to_vec());
+        assert_eq!(history.cursor, 1);
+
+        for record in &records[1..] {
+            history.record(*record);
+        }
+        assert_eq!(history.burns_count, records.len() as u64);
+        assert_eq!(history.cursor, 1);
+        assert_eq!(history.latest_burns(), records[1..].to_vec());
+    }
+}
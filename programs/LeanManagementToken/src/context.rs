@@ -1,55 +1,59 @@
 use anchor_lang::{
     error,
     prelude::{
-        account, require_keys_neq, Account, AccountInfo, Accounts, Key, Program, Pubkey, Rent,
-        Signer, SolanaSysvar, System, ToAccountInfo,
+        account, require_keys_neq, Account, AccountInfo, AccountLoader, Accounts, Key, Program,
+        Pubkey, Rent, Result, Signer, SolanaSysvar, System, ToAccountInfo,
     },
-    solana_program::system_program,
+    solana_program::{system_program, sysvar},
     Id, Space,
 };
 use anchor_spl::token::{Mint, Token, TokenAccount};
-use mpl_token_metadata;
 
-use crate::account::{ContractState, VestingState};
+use crate::account::{
+    Attestation, BurnHistory, ContractState, DonorAccount, ImportProgress, Labels, OtcDeal,
+    StakeAccount, StakingState, VestingState, WalletDelegation, WithdrawalIntent,
+    WithdrawalReceipt,
+};
+use crate::error_codes::LeancoinError;
+use crate::utils::{create_program_address, is_program_token_account, program_token_account_nonce};
 
 use crate::{
-    BURNING_ACCOUNT_SEED, COMMUNITY_ACCOUNT_SEED, CONTRACT_STATE_SEED, LIQUIDITY_ACCOUNT_SEED,
-    MARKETING_ACCOUNT_SEED, MINT_SEED, PARTNERSHIP_ACCOUNT_SEED, PROGRAM_ACCOUNT_SEED,
-    VESTING_STATE_SEED,
+    LabeledAccountKind, StateAccountKind, WalletKind, ATTESTATION_SEED, BURNING_ACCOUNT_SEED,
+    BURN_HISTORY_SEED, COMMUNITY_ACCOUNT_SEED, CONTRACT_STATE_SEED, DONOR_ACCOUNT_SEED,
+    IMPORT_PROGRESS_SEED, LABELS_SEED, LIQUIDITY_ACCOUNT_SEED, MARKETING_ACCOUNT_SEED, MINT_SEED,
+    OTC_DEAL_SEED, OTC_VAULT_SEED, PARTNERSHIP_ACCOUNT_SEED, PROGRAM_ACCOUNT_SEED,
+    REWARD_VAULT_SEED, STAKE_ACCOUNT_SEED, STAKE_VAULT_SEED, STAKING_STATE_SEED,
+    VESTING_STATE_SEED, WALLET_DELEGATION_SEED, WITHDRAWAL_INTENT_SEED, WITHDRAWAL_RECEIPT_SEED,
 };
 
 /// The discriminator is defined by the first 8 bytes of the SHA256 hash of the account's Rust identifier.
 /// It includes the name of struct type and lets Anchor know what type of account it should deserialize the data as.
-const DISCRIMINATOR_LEN: usize = 8;
+pub(crate) const DISCRIMINATOR_LEN: usize = 8;
 
-/// Context for the initialize instruction.
+/// Context for the initialize_state instruction.
 ///
 /// This context is used to initialize the contract state and the vesting state.
+/// It is the first phase of the initialization, the second one is performed using `InitializeWalletsContext`.
 ///
-/// The contract state is initialized with the following accounts:
+/// The context includes the following accounts initialized by the instruction:
 ///
 /// - `contract_state` - the account that contains the contract state,
 /// - `vesting_state` - the account that contains the vesting state,
-/// - `mint` - the mint account,
+/// - `mint` - the mint account, created with the number of decimals passed to the instruction,
 /// - `program_account` - the account that contains the tokens that will be distributed to the users,
 /// - `burning_account` - the account that contains the tokens that will be burned.
 ///
-/// The vesting state is initialized with the following accounts:
-///
-/// - `community_wallet` - the account that contains the tokens that will be distributed to the community wallet,
-/// - `partnership_wallet` - the account that contains the tokens that will be distributed to the partnership wallet,
-/// - `marketing_wallet` - the account that contains the tokens that will be distributed to the marketing wallet,
-/// - `liquidity_wallet` - the account that contains the tokens that will be distributed to the liquidity wallet.
-///
 /// The context includes also:
 /// - `token_program` - the Solana token program account,
 /// - `system_program` - the Solana system program account,
-/// - `signer` - the signer of the transaction which executes initialize instruction, the signer becomes contract's owner.
+/// - `signer` - the signer of the transaction which executes initialize_state instruction, the signer becomes contract's owner,
+/// - `payer` - the account paying the rent of the created accounts, it can be the same account as `signer`.
 #[derive(Accounts)]
-pub struct InitializeContext<'info> {
+#[instruction(decimals: u8)]
+pub struct InitializeStateContext<'info> {
     #[account(
         init,
-        payer = signer,
+        payer = payer,
         space = DISCRIMINATOR_LEN + ContractState::INIT_SPACE,
         seeds = [CONTRACT_STATE_SEED.as_bytes()],
         bump
@@ -57,25 +61,25 @@ pub struct InitializeContext<'info> {
     pub contract_state: Box<Account<'info, ContractState>>,
     #[account(
         init,
-        payer = signer,
-        space = DISCRIMINATOR_LEN + VestingState::INIT_SPACE,
+        payer = payer,
+        space = DISCRIMINATOR_LEN + std::mem::size_of::<VestingState>(),
         seeds = [VESTING_STATE_SEED.as_bytes()],
         bump
     )]
-    pub vesting_state: Box<Account<'info, VestingState>>,
+    pub vesting_state: AccountLoader<'info, VestingState>,
     #[account(
         init,
-        payer = signer,
+        payer = payer,
         seeds = [MINT_SEED.as_bytes()],
         bump,
-        mint::decimals = 9,
+        mint::decimals = decimals,
         mint::authority = mint
     )]
     pub mint: Box<Account<'info, Mint>>,
 
     #[account(
         init,
-        payer = signer,
+        payer = payer,
         token::mint = mint,
         token::authority = program_account,
         seeds = [PROGRAM_ACCOUNT_SEED.as_bytes()],
@@ -85,7 +89,7 @@ pub struct InitializeContext<'info> {
 
     #[account(
         init,
-        payer = signer,
+        payer = payer,
         token::mint = mint,
         token::authority = burning_account,
         seeds = [BURNING_ACCOUNT_SEED.as_bytes()],
@@ -93,9 +97,57 @@ pub struct InitializeContext<'info> {
     )]
     pub burning_account: Box<Account<'info, TokenAccount>>,
 
+    pub token_program: Program<'info, Token>,
+    pub signer: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for the initialize_wallets instruction.
+///
+/// This context is used to initialize the wallets affected by vesting mechanism.
+/// It is the second phase of the initialization, it can be performed only after the `initialize_state` instruction.
+///
+/// The context includes the following accounts initialized by the instruction:
+///
+/// - `community_wallet` - the account that contains the tokens that will be distributed to the community wallet,
+/// - `partnership_wallet` - the account that contains the tokens that will be distributed to the partnership wallet,
+/// - `marketing_wallet` - the account that contains the tokens that will be distributed to the marketing wallet,
+/// - `liquidity_wallet` - the account that contains the tokens that will be distributed to the liquidity wallet,
+/// - `labels` - the account that contains the labels of the program token accounts.
+///
+/// The context includes also:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `mint` - the mint account,
+/// - `token_program` - the Solana token program account,
+/// - `system_program` - the Solana system program account,
+/// - `signer` - the signer of the transaction which must be the contract's owner,
+/// - `payer` - the account paying the rent of the created accounts, it can be the same account as `signer`.
+#[derive(Accounts)]
+pub struct InitializeWalletsContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+    #[account(
+        address = contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
     #[account(
         init,
-        payer = signer,
+        payer = payer,
         token::mint = mint,
         token::authority = community_account,
         seeds = [COMMUNITY_ACCOUNT_SEED.as_bytes()],
@@ -105,7 +157,7 @@ pub struct InitializeContext<'info> {
 
     #[account(
         init,
-        payer = signer,
+        payer = payer,
         token::mint = mint,
         token::authority = partnership_account,
         seeds = [PARTNERSHIP_ACCOUNT_SEED.as_bytes()],
@@ -115,7 +167,7 @@ pub struct InitializeContext<'info> {
 
     #[account(
         init,
-        payer = signer,
+        payer = payer,
         token::mint = mint,
         token::authority = marketing_account,
         seeds = [MARKETING_ACCOUNT_SEED.as_bytes()],
@@ -125,7 +177,7 @@ pub struct InitializeContext<'info> {
 
     #[account(
         init,
-        payer = signer,
+        payer = payer,
         token::mint = mint,
         token::authority = liquidity_account,
         seeds = [LIQUIDITY_ACCOUNT_SEED.as_bytes()],
@@ -133,16 +185,26 @@ pub struct InitializeContext<'info> {
     )]
     pub liquidity_account: Box<Account<'info, TokenAccount>>,
 
+    #[account(
+        init,
+        payer = payer,
+        space = DISCRIMINATOR_LEN + Labels::INIT_SPACE,
+        seeds = [LABELS_SEED.as_bytes()],
+        bump,
+    )]
+    pub labels: Box<Account<'info, Labels>>,
+
     pub token_program: Program<'info, Token>,
-    #[account(mut)]
     pub signer: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
     #[account(address = system_program::ID)]
     pub system_program: Program<'info, System>,
 }
 
-/// Context for the import_ethereum_token_state instruction.
+/// Context for the import_external_token_state and import_ethereum_token_state instructions.
 ///
-/// This context is used to update the contract state and the vesting state using some data from the Ethereum contract.
+/// This context is used to update the contract state and the vesting state using some data from the token contract on the migration source chain.
 ///
 /// The contract state is updated using the following accounts:
 ///
@@ -158,7 +220,7 @@ pub struct InitializeContext<'info> {
 /// - `token_program` - the Solana token program account,
 /// - `signer` - the signer of the transaction which must be the contract's owner.
 #[derive(Accounts)]
-pub struct ImportEthereumTokenStateContext<'info> {
+pub struct ImportTokenStateContext<'info> {
     #[account(
         mut,
         seeds = [CONTRACT_STATE_SEED.as_bytes()],
@@ -169,14 +231,13 @@ pub struct ImportEthereumTokenStateContext<'info> {
     #[account(
         mut,
         seeds = [VESTING_STATE_SEED.as_bytes()],
-        bump = vesting_state.vesting_state_nonce,
+        bump = vesting_state.load()?.vesting_state_nonce,
     )]
-    pub vesting_state: Box<Account<'info, VestingState>>,
+    pub vesting_state: AccountLoader<'info, VestingState>,
 
     #[account(
         mut,
-        seeds = [MINT_SEED.as_bytes()],
-        bump = contract_state.mint_nonce,
+        address = contract_state.mint @ LeancoinError::WrongMint,
     )]
     pub mint: Box<Account<'info, Mint>>,
     #[account(
@@ -189,23 +250,21 @@ pub struct ImportEthereumTokenStateContext<'info> {
     pub signer: Signer<'info>,
 }
 
-/// Context for the burn instruction.
+/// Context for the begin_import instruction.
 ///
-/// This context is used to burn tokens from burning_account.
+/// This context is used to start a staged import: the tokens are minted and burned and the import progress account is created.
 ///
 /// The context includes:
-/// - `burning_account` - the account that holds tokens to be burned,
-/// - `mint` - the mint account used to mint tokens that should be burned,
 /// - `contract_state` - the account that contains the contract state,
-/// - `token_program` - the Solana token program account.
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `mint` - the mint account,
+/// - `program_account` - the account that contains the tokens that will be distributed to the users,
+/// - `import_progress` - the account tracking the entries transferred by the batches of the import,
+/// - `token_program` - the Solana token program account,
+/// - `signer` - the signer of the transaction which must be the contract's owner, it pays for the import progress account,
+/// - `system_program` - the Solana system program account.
 #[derive(Accounts)]
-pub struct BurnContext<'info> {
-    #[account(
-        mut,
-        seeds = [MINT_SEED.as_bytes()],
-        bump = contract_state.mint_nonce,
-    )]
-    pub mint: Box<Account<'info, Mint>>,
+pub struct BeginImportContext<'info> {
     #[account(
         mut,
         seeds = [CONTRACT_STATE_SEED.as_bytes()],
@@ -214,88 +273,135 @@ pub struct BurnContext<'info> {
     pub contract_state: Box<Account<'info, ContractState>>,
     #[account(
         mut,
-        seeds = [BURNING_ACCOUNT_SEED.as_bytes()],
-        bump = contract_state.burning_account_nonce,
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
     )]
-    pub burning_account: Box<Account<'info, TokenAccount>>,
+    pub vesting_state: AccountLoader<'info, VestingState>,
+    #[account(
+        mut,
+        address = contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [PROGRAM_ACCOUNT_SEED.as_bytes()],
+        bump = contract_state.program_account_nonce,
+    )]
+    pub program_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = signer,
+        space = DISCRIMINATOR_LEN + ImportProgress::INIT_SPACE,
+        seeds = [IMPORT_PROGRESS_SEED.as_bytes()],
+        bump
+    )]
+    pub import_progress: Box<Account<'info, ImportProgress>>,
     pub token_program: Program<'info, Token>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
 }
 
-/// Context for the change_authority instruction.
+/// Context for the import_batch instruction.
 ///
-/// This context is used to set new authority on contract state.
+/// This context is used to transfer the tokens of a batch of entries of the staged import from the program account.
+/// The accounts of the entries are passed as the remaining accounts.
 ///
 /// The context includes:
 /// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `mint` - the mint account,
+/// - `program_account` - the account that contains the tokens that will be distributed to the users,
+/// - `import_progress` - the account tracking the entries transferred by the batches of the import,
+/// - `token_program` - the Solana token program account,
 /// - `signer` - the signer of the transaction which must be the contract's owner.
 #[derive(Accounts)]
-pub struct ChangeAuthorityContext<'info> {
+pub struct ImportBatchContext<'info> {
     #[account(
-        mut,
         seeds = [CONTRACT_STATE_SEED.as_bytes()],
         bump = contract_state.contract_state_nonce,
     )]
     pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+    #[account(
+        address = contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [PROGRAM_ACCOUNT_SEED.as_bytes()],
+        bump = contract_state.program_account_nonce,
+    )]
+    pub program_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [IMPORT_PROGRESS_SEED.as_bytes()],
+        bump = import_progress.import_progress_nonce,
+    )]
+    pub import_progress: Box<Account<'info, ImportProgress>>,
+    pub token_program: Program<'info, Token>,
     pub signer: Signer<'info>,
 }
 
-/// Context for the set token metadata instruction.
+/// Context for the finalize_import instruction.
 ///
-/// This context is used to set the token metadata.
+/// This context is used to complete a staged import once the tokens of all its entries have been transferred.
 ///
 /// The context includes:
-///
-/// - contract_state - the account containing the contract state,
-/// - mint - the mint account,
-/// - signer - the signer of the transaction, who must be the contract's owner,
-/// - metadata_pda - the metadata PDA account,
-/// - system_program - the Solana system program account,
-/// - token_program - the Solana token program account,
-/// - metadata_program - the Metaplex metadata program account,
-///
-/// There are also check comments within the context:
-/// - metadata_pda and metadata_program are checked by the inner instruction.
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `program_account` - the account that contained the tokens distributed to the users,
+/// - `import_progress` - the account tracking the entries transferred by the batches of the import, it is closed,
+/// - `signer` - the signer of the transaction which must be the contract's owner, it receives the rent of the import progress account.
 #[derive(Accounts)]
-pub struct SetTokenMetadataContext<'info> {
+pub struct FinalizeImportContext<'info> {
     #[account(
         mut,
         seeds = [CONTRACT_STATE_SEED.as_bytes()],
         bump = contract_state.contract_state_nonce,
     )]
     pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+    #[account(
+        seeds = [PROGRAM_ACCOUNT_SEED.as_bytes()],
+        bump = contract_state.program_account_nonce,
+    )]
+    pub program_account: Box<Account<'info, TokenAccount>>,
     #[account(
         mut,
-        seeds = [MINT_SEED.as_bytes()],
-        bump = contract_state.mint_nonce,
+        close = signer,
+        seeds = [IMPORT_PROGRESS_SEED.as_bytes()],
+        bump = import_progress.import_progress_nonce,
     )]
-    pub mint: Box<Account<'info, Mint>>,
-
-    /// CHECK: The metadata program account. It is considered safe because it is checked by the inner instruction, ensuring it is the correct account.
-    #[account(mut, address = Pubkey::find_program_address(&[b"metadata", &mpl_token_metadata::id().to_bytes(), &mint.key().to_bytes()], &mpl_token_metadata::id()).0)]
-    pub metadata_pda: AccountInfo<'info>,
-
-    /// CHECK: The metadata program account. It is considered safe because it is checked by the inner instruction, ensuring it is the correct account.
-    #[account(address = mpl_token_metadata::id())]
-    pub metadata_program: AccountInfo<'info>,
-
+    pub import_progress: Box<Account<'info, ImportProgress>>,
+    #[account(mut)]
     pub signer: Signer<'info>,
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
 }
 
-/// Context for the withdraw_tokens_from_community_wallet instruction.
+/// Context for the recover_program_account_tokens instruction.
 ///
-/// This context is used to withdraw tokens from the community wallet.
+/// This context is used to transfer the tokens sent to the program account after the import to another token account.
 ///
 /// The context includes:
 /// - `contract_state` - the account that contains the contract state,
-/// - `vesting_state` - the account that contains the vesting state,
-/// - `community_account` - the community wallet account which is the source of tokens to be transferred,
-/// - `deposit_wallet` - the destination account receiving tokens transferred from community_account,
-/// - `signer` - the signer of the transaction which must be the contract's owner.
+/// - `program_account` - the program account holding the tokens to recover,
+/// - `import_progress` - the account of the staged import, it must not exist, so the tokens of an import in progress cannot be recovered,
+/// - `destination` - the account receiving the recovered tokens, it must be a token account of the program's mint
+///   other than the program and burning accounts,
+/// - `signer` - the signer of the transaction which must be the contract's owner,
 /// - `token_program` - the Solana token program account.
 #[derive(Accounts)]
-pub struct WithdrawTokensFromCommunityWalletContext<'info> {
+pub struct RecoverProgramAccountTokensContext<'info> {
     #[account(
         mut,
         seeds = [CONTRACT_STATE_SEED.as_bytes()],
@@ -304,37 +410,46 @@ pub struct WithdrawTokensFromCommunityWalletContext<'info> {
     pub contract_state: Box<Account<'info, ContractState>>,
     #[account(
         mut,
-        seeds = [VESTING_STATE_SEED.as_bytes()],
-        bump = vesting_state.vesting_state_nonce,
+        seeds = [PROGRAM_ACCOUNT_SEED.as_bytes()],
+        bump = contract_state.program_account_nonce,
     )]
-    pub vesting_state: Box<Account<'info, VestingState>>,
-
+    pub program_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: The import progress account. It is considered safe because its address is checked and it is only checked to be empty.
     #[account(
-        mut,
-        seeds = [COMMUNITY_ACCOUNT_SEED.as_bytes()],
+        seeds = [IMPORT_PROGRESS_SEED.as_bytes()],
         bump,
+        constraint = import_progress.data_is_empty() @ LeancoinError::ImportNotComplete,
     )]
-    pub community_account: Box<Account<'info, TokenAccount>>,
-    #[account(mut)]
-    pub deposit_wallet: Box<Account<'info, TokenAccount>>,
-
+    pub import_progress: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = destination.mint == contract_state.mint @ LeancoinError::WrongDepositWalletMint,
+        constraint = !is_program_token_account(
+            &contract_state,
+            program_account.key(),
+            destination.key(),
+        )? @ LeancoinError::DepositWalletIsProgramAccount,
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>,
     pub signer: Signer<'info>,
     pub token_program: Program<'info, Token>,
 }
 
-/// Context for the withdraw_tokens_from_partnership_wallet instruction.
+/// Context for the recover_foreign_tokens instruction.
 ///
-/// This context is used to withdraw tokens from the partnership wallet.
+/// This context is used to transfer the tokens of a foreign mint sent to a token account owned by one of the program's PDAs.
 ///
 /// The context includes:
 /// - `contract_state` - the account that contains the contract state,
 /// - `vesting_state` - the account that contains the vesting state,
-/// - `partnership_account` - the partnership wallet account which is the source of tokens to be transferred,
-/// - `deposit_wallet` - the destination account receiving tokens transferred from partnership_account,
-/// - `signer` - the signer of the transaction which must be the contract's owner.
+/// - `source_authority` - the PDA of the given program token account which owns the foreign token account,
+/// - `foreign_token_account` - the token account holding the tokens to recover, it must not be a token account of the program's mint,
+/// - `destination` - the account receiving the recovered tokens,
+/// - `signer` - the signer of the transaction which must be the contract's owner,
 /// - `token_program` - the Solana token program account.
 #[derive(Accounts)]
-pub struct WithdrawTokensFromPartnershipWalletContext<'info> {
+#[instruction(account_kind: LabeledAccountKind)]
+pub struct RecoverForeignTokensContext<'info> {
     #[account(
         mut,
         seeds = [CONTRACT_STATE_SEED.as_bytes()],
@@ -342,38 +457,55 @@ pub struct WithdrawTokensFromPartnershipWalletContext<'info> {
     )]
     pub contract_state: Box<Account<'info, ContractState>>,
     #[account(
-        mut,
         seeds = [VESTING_STATE_SEED.as_bytes()],
-        bump = vesting_state.vesting_state_nonce,
+        bump = vesting_state.load()?.vesting_state_nonce,
     )]
-    pub vesting_state: Box<Account<'info, VestingState>>,
-
+    pub vesting_state: AccountLoader<'info, VestingState>,
+    /// CHECK: The PDA of the program token account. It is considered safe because its address is checked.
+    #[account(
+        address = create_program_address(
+            account_kind.seed(),
+            program_token_account_nonce(&contract_state, &*vesting_state.load()?, account_kind),
+        )?,
+    )]
+    pub source_authority: AccountInfo<'info>,
     #[account(
         mut,
-        seeds = [PARTNERSHIP_ACCOUNT_SEED.as_bytes()],
-        bump = vesting_state.partnership_wallet_nonce,
+        token::authority = source_authority,
+        constraint = foreign_token_account.mint != contract_state.mint @ LeancoinError::NativeMintRecoveryNotAllowed,
     )]
-    pub partnership_account: Box<Account<'info, TokenAccount>>,
-    #[account(mut)]
-    pub deposit_wallet: Box<Account<'info, TokenAccount>>,
-
+    pub foreign_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = foreign_token_account.mint,
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>,
     pub signer: Signer<'info>,
     pub token_program: Program<'info, Token>,
 }
 
-/// Context for the withdraw_tokens_from_marketing_wallet instruction.
+/// Context for the burn instruction.
 ///
-/// This context is used to withdraw tokens from the marketing wallet.
+/// This context is used to burn tokens from burning_account.
 ///
 /// The context includes:
+/// - `burning_account` - the account that holds tokens to be burned,
+/// - `mint` - the mint account used to mint tokens that should be burned,
 /// - `contract_state` - the account that contains the contract state,
-/// - `vesting_state` - the account that contains the vesting state,
-/// - `marketing_account` - the marketing wallet account which is the source of tokens to be transferred,
-/// - `deposit_wallet` - the destination account receiving tokens transferred from marketing_account,
-/// - `signer` - the signer of the transaction which must be the contract's owner.
-/// - `token_program` - the Solana token program account.
+/// - `token_program` - the Solana token program account,
+/// - `instructions_sysvar` - the instructions sysvar account used to check that the instruction is not invoked via CPI,
+/// - `staking_state` - the account that contains the staking state, required once staking is enabled,
+/// - `reward_vault` - the account receiving the part of the burn redirected to the stakers, required once staking is enabled,
+/// - `signer` - the signer of the transaction which must be the contract's owner or a registered keeper,
+/// - `caller_token_account` - the optional token account of the mint receiving the reward of the burn caller,
+/// - `burn_history` - the account that keeps the latest burns.
 #[derive(Accounts)]
-pub struct WithdrawTokensFromMarketingWalletContext<'info> {
+pub struct BurnContext<'info> {
+    #[account(
+        mut,
+        address = contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
     #[account(
         mut,
         seeds = [CONTRACT_STATE_SEED.as_bytes()],
@@ -382,37 +514,63 @@ pub struct WithdrawTokensFromMarketingWalletContext<'info> {
     pub contract_state: Box<Account<'info, ContractState>>,
     #[account(
         mut,
-        seeds = [VESTING_STATE_SEED.as_bytes()],
-        bump = vesting_state.vesting_state_nonce,
+        seeds = [BURNING_ACCOUNT_SEED.as_bytes()],
+        bump = contract_state.burning_account_nonce,
     )]
-    pub vesting_state: Box<Account<'info, VestingState>>,
+    pub burning_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: The instructions sysvar account. It is considered safe because its address is checked.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
 
     #[account(
         mut,
-        seeds = [MARKETING_ACCOUNT_SEED.as_bytes()],
-        bump = vesting_state.marketing_wallet_nonce,
+        seeds = [STAKING_STATE_SEED.as_bytes()],
+        bump = staking_state.staking_state_nonce,
     )]
-    pub marketing_account: Box<Account<'info, TokenAccount>>,
-    #[account(mut)]
-    pub deposit_wallet: Box<Account<'info, TokenAccount>>,
-
+    pub staking_state: Option<Box<Account<'info, StakingState>>>,
+    #[account(
+        mut,
+        seeds = [REWARD_VAULT_SEED.as_bytes()],
+        bump,
+    )]
+    pub reward_vault: Option<Box<Account<'info, TokenAccount>>>,
     pub signer: Signer<'info>,
-    pub token_program: Program<'info, Token>,
+    #[account(
+        mut,
+        constraint = caller_token_account.mint == contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub caller_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        seeds = [BURN_HISTORY_SEED.as_bytes()],
+        bump = burn_history.burn_history_nonce,
+    )]
+    pub burn_history: Box<Account<'info, BurnHistory>>,
 }
 
-/// Context for the withdraw_tokens_from_liquidity_wallet instruction.
+/// Context for the catch_up_burn instruction.
 ///
-/// This context is used to withdraw tokens from the liquidity wallet.
+/// This context is used to burn tokens from burning_account for the monthly burns missed since the last one.
 ///
 /// The context includes:
+/// - `mint` - the mint account used to mint tokens that should be burned,
 /// - `contract_state` - the account that contains the contract state,
-/// - `vesting_state` - the account that contains the vesting state,
-/// - `liquidity_account` - the community wallet account which is the source of tokens to be transferred,
-/// - `deposit_wallet` - the destination account receiving tokens transferred from liquidity_account,
-/// - `signer` - the signer of the transaction which must be the contract's owner.
-/// - `token_program` - the Solana token program account.
+/// - `burning_account` - the account that holds tokens to be burned,
+/// - `token_program` - the Solana token program account,
+/// - `instructions_sysvar` - the instructions sysvar account used to check that the instruction is not invoked via CPI,
+/// - `staking_state` - the account that contains the staking state, required once staking is enabled,
+/// - `reward_vault` - the account receiving the part of the burn redirected to the stakers, required once staking is enabled,
+/// - `signer` - the signer of the transaction which must be the contract's owner or a registered keeper,
+/// - `burn_history` - the account that keeps the latest burns.
 #[derive(Accounts)]
-pub struct WithdrawTokensFromLiquidityWalletContext<'info> {
+pub struct CatchUpBurnContext<'info> {
+    #[account(
+        mut,
+        address = contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
     #[account(
         mut,
         seeds = [CONTRACT_STATE_SEED.as_bytes()],
@@ -421,73 +579,2443 @@ pub struct WithdrawTokensFromLiquidityWalletContext<'info> {
     pub contract_state: Box<Account<'info, ContractState>>,
     #[account(
         mut,
-        seeds = [VESTING_STATE_SEED.as_bytes()],
-        bump = vesting_state.vesting_state_nonce,
+        seeds = [BURNING_ACCOUNT_SEED.as_bytes()],
+        bump = contract_state.burning_account_nonce,
     )]
-    pub vesting_state: Box<Account<'info, VestingState>>,
+    pub burning_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: The instructions sysvar account. It is considered safe because its address is checked.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
 
     #[account(
         mut,
-        seeds = [LIQUIDITY_ACCOUNT_SEED.as_bytes()],
-        bump = vesting_state.liquidity_wallet_nonce,
+        seeds = [STAKING_STATE_SEED.as_bytes()],
+        bump = staking_state.staking_state_nonce,
     )]
-    pub liquidity_account: Box<Account<'info, TokenAccount>>,
-    #[account(mut)]
-    pub deposit_wallet: Box<Account<'info, TokenAccount>>,
-
+    pub staking_state: Option<Box<Account<'info, StakingState>>>,
+    #[account(
+        mut,
+        seeds = [REWARD_VAULT_SEED.as_bytes()],
+        bump,
+    )]
+    pub reward_vault: Option<Box<Account<'info, TokenAccount>>>,
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [BURN_HISTORY_SEED.as_bytes()],
+        bump = burn_history.burn_history_nonce,
+    )]
+    pub burn_history: Box<Account<'info, BurnHistory>>,
+}
+
+/// Context for the burn_exact instruction.
+///
+/// This context is used to burn the given amount of tokens from burning_account outside of the monthly schedule.
+///
+/// The context includes:
+/// - `mint` - the mint account used to mint tokens that should be burned,
+/// - `contract_state` - the account that contains the contract state,
+/// - `burning_account` - the account that holds tokens to be burned,
+/// - `token_program` - the Solana token program account,
+/// - `signer` - the signer of the transaction which must be the contract's owner or the burn authority,
+/// - `burn_history` - the account that keeps the latest burns.
+#[derive(Accounts)]
+pub struct BurnExactContext<'info> {
+    #[account(
+        mut,
+        address = contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [BURNING_ACCOUNT_SEED.as_bytes()],
+        bump = contract_state.burning_account_nonce,
+    )]
+    pub burning_account: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [BURN_HISTORY_SEED.as_bytes()],
+        bump = burn_history.burn_history_nonce,
+    )]
+    pub burn_history: Box<Account<'info, BurnHistory>>,
+}
+
+/// Context for the initialize_burn_history instruction.
+///
+/// This context is used to initialize the account that keeps the latest burns.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `burn_history` - the account that keeps the latest burns, initialized by the instruction,
+/// - `payer` - the signer of the transaction paying the rent of the burn history,
+/// - `system_program` - the Solana system program account.
+#[derive(Accounts)]
+pub struct InitializeBurnHistoryContext<'info> {
+    #[account(
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        init,
+        payer = payer,
+        space = DISCRIMINATOR_LEN + BurnHistory::INIT_SPACE,
+        seeds = [BURN_HISTORY_SEED.as_bytes()],
+        bump,
+    )]
+    pub burn_history: Box<Account<'info, BurnHistory>>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for the donate_to_burn instruction.
+///
+/// This context is used to transfer tokens of the signer to the burning account.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `burning_account` - the account that holds tokens to be burned,
+/// - `donor_token_account` - the source account of the donated tokens, owned by the signer,
+/// - `donor_account` - the account that contains the cumulative donations of the signer, initialized during the first donation,
+/// - `signer` - the signer of the transaction who is the donor,
+/// - `token_program` - the Solana token program account,
+/// - `system_program` - the Solana system program account.
+#[derive(Accounts)]
+pub struct DonateToBurnContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [BURNING_ACCOUNT_SEED.as_bytes()],
+        bump = contract_state.burning_account_nonce,
+    )]
+    pub burning_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = donor_token_account.mint == contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub donor_token_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_LEN + DonorAccount::INIT_SPACE,
+        seeds = [DONOR_ACCOUNT_SEED.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub donor_account: Box<Account<'info, DonorAccount>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for the fund_burning_account instruction.
+///
+/// This context is used to transfer tokens of the signer to the burning account without recording the donor in a donor account.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `burning_account` - the account that holds tokens to be burned,
+/// - `donor_token_account` - the source account of the transferred tokens, owned by the signer,
+/// - `signer` - the signer of the transaction who is the donor,
+/// - `token_program` - the Solana token program account.
+#[derive(Accounts)]
+pub struct FundBurningAccountContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [BURNING_ACCOUNT_SEED.as_bytes()],
+        bump = contract_state.burning_account_nonce,
+    )]
+    pub burning_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = donor_token_account.mint == contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub donor_token_account: Box<Account<'info, TokenAccount>>,
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for the change_authority instruction.
+///
+/// This context is used to set new authority on contract state.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `signer` - the signer of the transaction which must be the contract's owner.
+#[derive(Accounts)]
+pub struct ChangeAuthorityContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    pub signer: Signer<'info>,
+}
+
+/// Context for the set token metadata instruction.
+///
+/// This context is used to set the token metadata.
+///
+/// The context includes:
+///
+/// - contract_state - the account containing the contract state,
+/// - mint - the mint account,
+/// - signer - the signer of the transaction, who must be the contract's owner,
+/// - metadata_pda - the metadata PDA account,
+/// - system_program - the Solana system program account,
+/// - token_program - the Solana token program account,
+/// - metadata_program - the Metaplex metadata program account,
+///
+/// There are also check comments within the context:
+/// - metadata_pda is checked by the instruction,
+/// - metadata_program is checked against the metadata program id stored in the contract state.
+#[cfg(feature = "metadata")]
+#[derive(Accounts)]
+pub struct SetTokenMetadataContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        address = contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: The metadata PDA account. It is considered safe because its derivation from the mint and the metadata program is checked in the instruction.
+    #[account(mut)]
+    pub metadata_pda: AccountInfo<'info>,
+
+    /// CHECK: The metadata program account. It is considered safe because its address is checked against the one stored in the contract state.
+    #[account(address = contract_state.metadata_program_id)]
+    pub metadata_program: AccountInfo<'info>,
+
+    pub signer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for the update_token_metadata instruction.
+///
+/// This context is used to update the existing token metadata.
+///
+/// The context includes:
+///
+/// - contract_state - the account containing the contract state,
+/// - mint - the mint account which is the update authority of the metadata,
+/// - metadata_pda - the metadata PDA account, it must already be created by the metadata program,
+/// - metadata_program - the Metaplex metadata program account,
+/// - signer - the signer of the transaction, who must be the contract's owner or the metadata authority.
+///
+/// There are also check comments within the context:
+/// - metadata_pda is checked by the instruction,
+/// - metadata_program is checked against the metadata program id stored in the contract state.
+#[cfg(feature = "metadata")]
+#[derive(Accounts)]
+pub struct UpdateTokenMetadataContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        address = contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: The metadata PDA account. It is considered safe because its derivation from the mint and the metadata program is checked in the instruction
+    /// and it must be owned by the metadata program, i.e. the metadata must have been created.
+    #[account(
+        mut,
+        owner = contract_state.metadata_program_id @ LeancoinError::TokenMetadataNotCreated,
+    )]
+    pub metadata_pda: AccountInfo<'info>,
+
+    /// CHECK: The metadata program account. It is considered safe because its address is checked against the one stored in the contract state.
+    #[account(address = contract_state.metadata_program_id)]
+    pub metadata_program: AccountInfo<'info>,
+
+    pub signer: Signer<'info>,
+}
+
+/// Context for the set_beneficiary_owner instruction.
+///
+/// This context is used to set the beneficiary owner of one of the vested wallets.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `signer` - the signer of the transaction which must be the contract's owner.
+#[derive(Accounts)]
+pub struct SetBeneficiaryOwnerContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+    pub signer: Signer<'info>,
+}
+
+/// Context for the set_wallet_beneficiary instruction.
+///
+/// This context is used to set the beneficiary allowed to withdraw from one of the vested wallets.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `signer` - the signer of the transaction which must be the contract's owner.
+#[derive(Accounts)]
+pub struct SetWalletBeneficiaryContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+    pub signer: Signer<'info>,
+}
+
+/// Context for the set_cpi_allowed instruction.
+///
+/// This context is used to allow or forbid invoking the burn and withdraw instructions via CPI.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `signer` - the signer of the transaction which must be the contract's owner.
+#[derive(Accounts)]
+pub struct SetCpiAllowedContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    pub signer: Signer<'info>,
+}
+
+/// Context for the set_paused instruction.
+///
+/// This context is used to pause or unpause the withdrawals and burning.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `signer` - the signer of the transaction which must be the contract's owner.
+#[derive(Accounts)]
+pub struct SetPausedContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    pub signer: Signer<'info>,
+}
+
+/// Context for the set_role instruction.
+///
+/// This context is used to assign a role to an authority.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `signer` - the signer of the transaction which must be the contract's owner.
+#[derive(Accounts)]
+pub struct SetRoleContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    pub signer: Signer<'info>,
+}
+
+/// Context for the renounce_authority instruction.
+///
+/// This context is used to permanently renounce the authority over the contract.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `signer` - the signer of the transaction which must be the contract's owner.
+#[derive(Accounts)]
+pub struct RenounceAuthorityContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    pub signer: Signer<'info>,
+}
+
+/// Context for the revoke_mint_authority instruction.
+///
+/// This context is used to revoke the mint authority of the token mint.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `mint` - the mint account whose mint authority is revoked, it is its own mint authority,
+/// - `signer` - the signer of the transaction which must be the contract's owner,
+/// - `token_program` - the token program.
+#[derive(Accounts)]
+pub struct RevokeMintAuthorityContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        address = contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for the add_keeper instruction.
+///
+/// This context is used to register a keeper allowed to run the automation instructions.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `signer` - the signer of the transaction which must be the contract's owner.
+#[derive(Accounts)]
+pub struct AddKeeperContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    pub signer: Signer<'info>,
+}
+
+/// Context for the remove_keeper instruction.
+///
+/// This context is used to unregister a keeper.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `signer` - the signer of the transaction which must be the contract's owner.
+#[derive(Accounts)]
+pub struct RemoveKeeperContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    pub signer: Signer<'info>,
+}
+
+/// Context for the set_burn_caller_reward instruction.
+///
+/// This context is used to configure the reward of the burn caller.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `signer` - the signer of the transaction which must be the contract's owner.
+#[derive(Accounts)]
+pub struct SetBurnCallerRewardContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    pub signer: Signer<'info>,
+}
+
+/// Context for the set_burn_window instruction.
+///
+/// This context is used to configure the length of the primary burn window.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `signer` - the signer of the transaction which must be the contract's owner.
+#[derive(Accounts)]
+pub struct SetBurnWindowContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    pub signer: Signer<'info>,
+}
+
+/// Context for the set_late_burn_window instruction.
+///
+/// This context is used to configure the secondary burn window.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `signer` - the signer of the transaction which must be the contract's owner.
+#[derive(Accounts)]
+pub struct SetLateBurnWindowContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    pub signer: Signer<'info>,
+}
+
+/// Context for the set_burn_rate instruction.
+///
+/// This context is used to configure the rate of the monthly burn.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `signer` - the signer of the transaction which must be the contract's owner.
+#[derive(Accounts)]
+pub struct SetBurnRateContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    pub signer: Signer<'info>,
+}
+
+/// Context for the set_label instruction.
+///
+/// This context is used to set the label of one of the program token accounts.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `labels` - the account that contains the labels of the program token accounts,
+/// - `signer` - the signer of the transaction which must be the contract's owner.
+#[derive(Accounts)]
+pub struct SetLabelContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [LABELS_SEED.as_bytes()],
+        bump = labels.labels_nonce,
+    )]
+    pub labels: Box<Account<'info, Labels>>,
+    pub signer: Signer<'info>,
+}
+
+/// Context for the lock_configuration instruction.
+///
+/// This context is used to lock the configuration of the contract.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `signer` - the signer of the transaction which must be the contract's owner.
+#[derive(Accounts)]
+pub struct LockConfigurationContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    pub signer: Signer<'info>,
+}
+
+/// Context for the migrate_vesting_state instruction.
+///
+/// This context is used to convert the vesting state account from one of the previous layouts to the current layout.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state in one of the previous layouts,
+/// - `system_program` - the Solana system program account,
+/// - `signer` - the signer of the transaction which must be the contract's owner, it pays for the additional rent.
+#[derive(Accounts)]
+pub struct MigrateVestingStateContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    /// CHECK: the previous layouts cannot be loaded as the zero-copy `VestingState`, they are read by `read_outdated_vesting_state` instead
+    #[account(
+        mut,
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump,
+    )]
+    pub vesting_state: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+/// Context for the migrate_contract_state instruction.
+///
+/// This context is used to convert the contract state account from the deployed layout to the current layout.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state in the deployed layout,
+/// - `system_program` - the Solana system program account,
+/// - `signer` - the signer of the transaction which must be the contract's owner, it pays for the additional rent.
+#[derive(Accounts)]
+pub struct MigrateContractStateContext<'info> {
+    /// CHECK: the deployed layout cannot be loaded as `ContractState`, they are read by `read_outdated_contract_state` instead
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump,
+    )]
+    pub contract_state: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+/// Context for the top_up_rent instruction.
+///
+/// This context is used to make a state account rent-exempt again.
+///
+/// The context includes:
+/// - `state_account` - the contract state or the vesting state account, depending on the instruction argument,
+/// - `system_program` - the Solana system program account,
+/// - `signer` - the signer of the transaction which pays the shortfall, it can be anyone.
+#[derive(Accounts)]
+#[instruction(account_kind: StateAccountKind)]
+pub struct TopUpRentContext<'info> {
+    /// CHECK: lamports are only transferred to the account, its address is checked by the seeds and its data is not read
+    #[account(
+        mut,
+        seeds = [account_kind.seed().as_bytes()],
+        bump,
+    )]
+    pub state_account: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+    #[account(mut)]
+    pub signer: Signer<'info>,
+}
+
+/// Context for the rebalance instruction.
+///
+/// This context is used to move tokens together with their vesting allocation between two vested wallets.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `community_account` - the community wallet account,
+/// - `partnership_account` - the partnership wallet account,
+/// - `marketing_account` - the marketing wallet account,
+/// - `liquidity_account` - the liquidity wallet account,
+/// - `signer` - the signer of the transaction which must be the contract's owner,
+/// - `token_program` - the Solana token program account.
+#[derive(Accounts)]
+pub struct RebalanceContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+
+    /// CHECK: The community wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        mut,
+        seeds = [COMMUNITY_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Community).nonce,
+    )]
+    pub community_account: AccountInfo<'info>,
+    /// CHECK: The partnership wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        mut,
+        seeds = [PARTNERSHIP_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Partnership).nonce,
+    )]
+    pub partnership_account: AccountInfo<'info>,
+    /// CHECK: The marketing wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        mut,
+        seeds = [MARKETING_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Marketing).nonce,
+    )]
+    pub marketing_account: AccountInfo<'info>,
+    /// CHECK: The liquidity wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Liquidity).nonce,
+    )]
+    pub liquidity_account: AccountInfo<'info>,
+
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for the finalize_vested_wallet instruction.
+///
+/// This context is used to close the token account of a fully vested and emptied wallet.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `wallet_account` - the token account of the finalized wallet, closed by the instruction,
+/// - `destination` - the account receiving the rent of the closed token account,
+/// - `signer` - the signer of the transaction which must be the contract's owner,
+/// - `token_program` - the Solana token program account.
+#[derive(Accounts)]
+#[instruction(wallet_kind: WalletKind)]
+pub struct FinalizeVestedWalletContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+    #[account(
+        mut,
+        seeds = [wallet_kind.seed().as_bytes()],
+        bump = vesting_state.load()?.wallet(wallet_kind).nonce,
+    )]
+    pub wallet_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: The account receiving the rent of the closed token account. It can be any account chosen by the contract's owner.
+    #[account(mut)]
+    pub destination: AccountInfo<'info>,
+
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for the revoke_unvested instruction.
+///
+/// This context is used to transfer the tokens of a vested wallet which have not been unlocked yet out of the wallet.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `wallet_account` - the token account of the revoked vested wallet,
+/// - `destination` - the token account receiving the revoked tokens,
+/// - `signer` - the signer of the transaction which must be the contract's owner,
+/// - `token_program` - the Solana token program account.
+#[derive(Accounts)]
+#[instruction(wallet_kind: WalletKind)]
+pub struct RevokeUnvestedContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+    #[account(
+        mut,
+        seeds = [wallet_kind.seed().as_bytes()],
+        bump = vesting_state.load()?.wallet(wallet_kind).nonce,
+    )]
+    pub wallet_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = destination.mint == contract_state.mint @ LeancoinError::WrongDepositWalletMint,
+        constraint = !is_program_token_account(
+            &contract_state,
+            wallet_account.key(),
+            destination.key(),
+        )? @ LeancoinError::DepositWalletIsProgramAccount,
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>,
+
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for the add_to_vesting instruction.
+///
+/// This context is used to transfer tokens of the signer to a vested wallet and to add them to the wallet's initial balance.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `wallet_account` - the token account of the topped up vested wallet,
+/// - `source` - the source account of the added tokens, owned by the signer,
+/// - `signer` - the signer of the transaction which must be the contract's owner,
+/// - `token_program` - the Solana token program account.
+#[derive(Accounts)]
+#[instruction(wallet_kind: WalletKind)]
+pub struct AddToVestingContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+    #[account(
+        mut,
+        seeds = [wallet_kind.seed().as_bytes()],
+        bump = vesting_state.load()?.wallet(wallet_kind).nonce,
+    )]
+    pub wallet_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = source.mint == contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub source: Box<Account<'info, TokenAccount>>,
+
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for the approve_wallet_delegate instruction.
+///
+/// This context is used to approve a delegate allowed to transfer tokens out of a vested wallet.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `wallet_account` - the token account of the vested wallet, it is the authority approving the delegate,
+/// - `wallet_delegation` - the account tracking the delegation of the wallet, it is initialized during the first approval,
+/// - `delegate` - the account approved as the delegate,
+/// - `signer` - the signer of the transaction which must be the contract's owner, it pays for the wallet delegation account,
+/// - `token_program` - the Solana token program account,
+/// - `system_program` - the Solana system program account.
+#[derive(Accounts)]
+#[instruction(wallet_kind: WalletKind, delegate: Pubkey)]
+pub struct ApproveWalletDelegateContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+    #[account(
+        mut,
+        seeds = [wallet_kind.seed().as_bytes()],
+        bump = vesting_state.load()?.wallet(wallet_kind).nonce,
+    )]
+    pub wallet_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_LEN + WalletDelegation::INIT_SPACE,
+        seeds = [WALLET_DELEGATION_SEED.as_bytes(), wallet_kind.seed().as_bytes()],
+        bump
+    )]
+    pub wallet_delegation: Box<Account<'info, WalletDelegation>>,
+    /// CHECK: The account approved as the delegate. It can be any account chosen by the contract's owner.
+    #[account(address = delegate)]
+    pub delegate: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for the reconcile_wallet_delegate and revoke_wallet_delegate instructions.
+///
+/// This context is used to account the tokens transferred by the delegate of a vested wallet and to revoke the delegate.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `wallet_account` - the token account of the vested wallet,
+/// - `wallet_delegation` - the account tracking the delegation of the wallet,
+/// - `signer` - the signer of the transaction, it must be the contract's owner to revoke the delegate,
+/// - `token_program` - the Solana token program account.
+#[derive(Accounts)]
+#[instruction(wallet_kind: WalletKind)]
+pub struct WalletDelegationContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+    #[account(
+        mut,
+        seeds = [wallet_kind.seed().as_bytes()],
+        bump = vesting_state.load()?.wallet(wallet_kind).nonce,
+    )]
+    pub wallet_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [WALLET_DELEGATION_SEED.as_bytes(), wallet_kind.seed().as_bytes()],
+        bump = wallet_delegation.wallet_delegation_nonce,
+    )]
+    pub wallet_delegation: Box<Account<'info, WalletDelegation>>,
+
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for the set_metadata_program_id instruction.
+///
+/// This context is used to set the token metadata program id stored in the contract state.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `signer` - the signer of the transaction which must be the contract's owner.
+#[cfg(feature = "metadata")]
+#[derive(Accounts)]
+pub struct SetMetadataProgramIdContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    pub signer: Signer<'info>,
+}
+
+/// Context for the get_months_since_start instruction.
+///
+/// This context is used to read the number of full vesting months since the vesting start.
+///
+/// The context includes:
+/// - `vesting_state` - the account that contains the vesting state.
+#[derive(Accounts)]
+pub struct GetMonthsSinceStartContext<'info> {
+    #[account(
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+}
+
+/// Context for the project_supply instruction.
+///
+/// This context is used to read the mint supply and the burning account balance from which the supply projection starts.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state.
+/// - `mint` - the mint account.
+/// - `burning_account` - the account from which the tokens are burned.
+/// - `staking_state` - the optional staking state, its reward percentage is used if staking is enabled.
+#[derive(Accounts)]
+pub struct ProjectSupplyContext<'info> {
+    #[account(
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        address = contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+    #[account(
+        seeds = [BURNING_ACCOUNT_SEED.as_bytes()],
+        bump = contract_state.burning_account_nonce,
+    )]
+    pub burning_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        seeds = [STAKING_STATE_SEED.as_bytes()],
+        bump = staking_state.staking_state_nonce,
+    )]
+    pub staking_state: Option<Box<Account<'info, StakingState>>>,
+}
+
+/// Context for the get_program_addresses instruction.
+///
+/// This context is used to read the addresses and nonces of all program derived accounts together with the labels of the program token accounts.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `labels` - the account that contains the labels of the program token accounts.
+#[derive(Accounts)]
+pub struct GetProgramAddressesContext<'info> {
+    #[account(
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+    #[account(
+        seeds = [LABELS_SEED.as_bytes()],
+        bump = labels.labels_nonce,
+    )]
+    pub labels: Box<Account<'info, Labels>>,
+}
+
+/// Context for the get_token_info instruction.
+///
+/// This context is used to read the token information consolidated from the state accounts and the live balances.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `mint` - the mint account,
+/// - `program_account` - the program account,
+/// - `burning_account` - the burning account,
+/// - `community_account` - the community wallet account,
+/// - `partnership_account` - the partnership wallet account,
+/// - `marketing_account` - the marketing wallet account,
+/// - `liquidity_account` - the liquidity wallet account.
+#[derive(Accounts)]
+pub struct GetTokenInfoContext<'info> {
+    #[account(
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+    #[account(
+        address = contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+    #[account(
+        seeds = [PROGRAM_ACCOUNT_SEED.as_bytes()],
+        bump = contract_state.program_account_nonce,
+    )]
+    pub program_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        seeds = [BURNING_ACCOUNT_SEED.as_bytes()],
+        bump = contract_state.burning_account_nonce,
+    )]
+    pub burning_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: The community wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        seeds = [COMMUNITY_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Community).nonce,
+    )]
+    pub community_account: AccountInfo<'info>,
+    /// CHECK: The partnership wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        seeds = [PARTNERSHIP_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Partnership).nonce,
+    )]
+    pub partnership_account: AccountInfo<'info>,
+    /// CHECK: The marketing wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        seeds = [MARKETING_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Marketing).nonce,
+    )]
+    pub marketing_account: AccountInfo<'info>,
+    /// CHECK: The liquidity wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        seeds = [LIQUIDITY_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Liquidity).nonce,
+    )]
+    pub liquidity_account: AccountInfo<'info>,
+}
+
+/// Context for the forecast_unlocked instruction.
+///
+/// This context is used to forecast the unlocked amounts of the vested wallets, none of the accounts is modified.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state.
+#[derive(Accounts)]
+pub struct ForecastUnlockedContext<'info> {
+    #[account(
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+}
+
+/// Context for the get_vesting_status instruction.
+///
+/// This context is used to read the vesting status of the vested wallets, none of the accounts is modified.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `community_account` - the community wallet account,
+/// - `partnership_account` - the partnership wallet account,
+/// - `marketing_account` - the marketing wallet account,
+/// - `liquidity_account` - the liquidity wallet account.
+#[derive(Accounts)]
+pub struct GetVestingStatusContext<'info> {
+    #[account(
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+    /// CHECK: The community wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        seeds = [COMMUNITY_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Community).nonce,
+    )]
+    pub community_account: AccountInfo<'info>,
+    /// CHECK: The partnership wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        seeds = [PARTNERSHIP_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Partnership).nonce,
+    )]
+    pub partnership_account: AccountInfo<'info>,
+    /// CHECK: The marketing wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        seeds = [MARKETING_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Marketing).nonce,
+    )]
+    pub marketing_account: AccountInfo<'info>,
+    /// CHECK: The liquidity wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        seeds = [LIQUIDITY_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Liquidity).nonce,
+    )]
+    pub liquidity_account: AccountInfo<'info>,
+}
+
+/// Context for the withdraw_tokens_from_community_wallet instruction.
+///
+/// This context is used to withdraw tokens from the community wallet.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `community_account` - the community wallet account which is the source of tokens to be transferred,
+/// - `deposit_wallet` - the destination account receiving tokens transferred from community_account, it must be a token account of the program's mint
+///   other than the vested, program and burning accounts,
+/// - `withdrawal_intent` - the account recording the withdrawal, it is passed and initialized only if an idempotency key is given,
+/// - `withdrawal_receipt` - the account permanently recording the withdrawal, it is created by every withdrawal with the next receipt index of the wallet,
+/// - `signer` - the signer of the transaction which must be the contract's owner or the beneficiary of the wallet, it pays for the withdrawal intent and receipt accounts,
+/// - `token_program` - the Solana token program account,
+/// - `instructions_sysvar` - the instructions sysvar account used to check that the instruction is not invoked via CPI,
+/// - `system_program` - the Solana system program account.
+#[derive(Accounts)]
+#[instruction(amount_to_withdraw: u64, idempotency_key: Option<[u8; 16]>)]
+pub struct WithdrawTokensFromCommunityWalletContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+
+    /// CHECK: The community wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        mut,
+        seeds = [COMMUNITY_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Community).nonce,
+    )]
+    pub community_account: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = deposit_wallet.mint == contract_state.mint @ LeancoinError::WrongDepositWalletMint,
+        constraint = !is_program_token_account(
+            &contract_state,
+            community_account.key(),
+            deposit_wallet.key(),
+        )? @ LeancoinError::DepositWalletIsProgramAccount,
+    )]
+    pub deposit_wallet: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_LEN + WithdrawalIntent::INIT_SPACE,
+        seeds = [WITHDRAWAL_INTENT_SEED.as_bytes(), idempotency_key.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub withdrawal_intent: Option<Box<Account<'info, WithdrawalIntent>>>,
+    #[account(
+        init,
+        payer = signer,
+        space = DISCRIMINATOR_LEN + WithdrawalReceipt::INIT_SPACE,
+        seeds = [
+            WITHDRAWAL_RECEIPT_SEED.as_bytes(),
+            &[WalletKind::Community as u8],
+            &vesting_state.load()?.next_receipt_index[WalletKind::Community.index()].to_le_bytes(),
+        ],
+        bump
+    )]
+    pub withdrawal_receipt: Box<Account<'info, WithdrawalReceipt>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: The instructions sysvar account. It is considered safe because its address is checked.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for the withdraw_tokens_from_partnership_wallet instruction.
+///
+/// This context is used to withdraw tokens from the partnership wallet.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `partnership_account` - the partnership wallet account which is the source of tokens to be transferred,
+/// - `deposit_wallet` - the destination account receiving tokens transferred from partnership_account, it must be a token account of the program's mint
+///   other than the vested, program and burning accounts,
+/// - `withdrawal_intent` - the account recording the withdrawal, it is passed and initialized only if an idempotency key is given,
+/// - `withdrawal_receipt` - the account permanently recording the withdrawal, it is created by every withdrawal with the next receipt index of the wallet,
+/// - `signer` - the signer of the transaction which must be the contract's owner or the beneficiary of the wallet, it pays for the withdrawal intent and receipt accounts,
+/// - `token_program` - the Solana token program account,
+/// - `instructions_sysvar` - the instructions sysvar account used to check that the instruction is not invoked via CPI,
+/// - `system_program` - the Solana system program account.
+#[derive(Accounts)]
+#[instruction(amount_to_withdraw: u64, idempotency_key: Option<[u8; 16]>)]
+pub struct WithdrawTokensFromPartnershipWalletContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+
+    /// CHECK: The partnership wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        mut,
+        seeds = [PARTNERSHIP_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Partnership).nonce,
+    )]
+    pub partnership_account: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = deposit_wallet.mint == contract_state.mint @ LeancoinError::WrongDepositWalletMint,
+        constraint = !is_program_token_account(
+            &contract_state,
+            partnership_account.key(),
+            deposit_wallet.key(),
+        )? @ LeancoinError::DepositWalletIsProgramAccount,
+    )]
+    pub deposit_wallet: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_LEN + WithdrawalIntent::INIT_SPACE,
+        seeds = [WITHDRAWAL_INTENT_SEED.as_bytes(), idempotency_key.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub withdrawal_intent: Option<Box<Account<'info, WithdrawalIntent>>>,
+    #[account(
+        init,
+        payer = signer,
+        space = DISCRIMINATOR_LEN + WithdrawalReceipt::INIT_SPACE,
+        seeds = [
+            WITHDRAWAL_RECEIPT_SEED.as_bytes(),
+            &[WalletKind::Partnership as u8],
+            &vesting_state.load()?.next_receipt_index[WalletKind::Partnership.index()].to_le_bytes(),
+        ],
+        bump
+    )]
+    pub withdrawal_receipt: Box<Account<'info, WithdrawalReceipt>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: The instructions sysvar account. It is considered safe because its address is checked.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for the withdraw_tokens_from_marketing_wallet instruction.
+///
+/// This context is used to withdraw tokens from the marketing wallet.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `marketing_account` - the marketing wallet account which is the source of tokens to be transferred,
+/// - `deposit_wallet` - the destination account receiving tokens transferred from marketing_account, it must be a token account of the program's mint
+///   other than the vested, program and burning accounts,
+/// - `withdrawal_intent` - the account recording the withdrawal, it is passed and initialized only if an idempotency key is given,
+/// - `withdrawal_receipt` - the account permanently recording the withdrawal, it is created by every withdrawal with the next receipt index of the wallet,
+/// - `signer` - the signer of the transaction which must be the contract's owner or the beneficiary of the wallet, it pays for the withdrawal intent and receipt accounts,
+/// - `token_program` - the Solana token program account,
+/// - `instructions_sysvar` - the instructions sysvar account used to check that the instruction is not invoked via CPI,
+/// - `system_program` - the Solana system program account.
+#[derive(Accounts)]
+#[instruction(amount_to_withdraw: u64, idempotency_key: Option<[u8; 16]>)]
+pub struct WithdrawTokensFromMarketingWalletContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+
+    /// CHECK: The marketing wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        mut,
+        seeds = [MARKETING_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Marketing).nonce,
+    )]
+    pub marketing_account: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = deposit_wallet.mint == contract_state.mint @ LeancoinError::WrongDepositWalletMint,
+        constraint = !is_program_token_account(
+            &contract_state,
+            marketing_account.key(),
+            deposit_wallet.key(),
+        )? @ LeancoinError::DepositWalletIsProgramAccount,
+    )]
+    pub deposit_wallet: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_LEN + WithdrawalIntent::INIT_SPACE,
+        seeds = [WITHDRAWAL_INTENT_SEED.as_bytes(), idempotency_key.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub withdrawal_intent: Option<Box<Account<'info, WithdrawalIntent>>>,
+    #[account(
+        init,
+        payer = signer,
+        space = DISCRIMINATOR_LEN + WithdrawalReceipt::INIT_SPACE,
+        seeds = [
+            WITHDRAWAL_RECEIPT_SEED.as_bytes(),
+            &[WalletKind::Marketing as u8],
+            &vesting_state.load()?.next_receipt_index[WalletKind::Marketing.index()].to_le_bytes(),
+        ],
+        bump
+    )]
+    pub withdrawal_receipt: Box<Account<'info, WithdrawalReceipt>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: The instructions sysvar account. It is considered safe because its address is checked.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for the withdraw_tokens_from_liquidity_wallet instruction.
+///
+/// This context is used to withdraw tokens from the liquidity wallet.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `liquidity_account` - the community wallet account which is the source of tokens to be transferred,
+/// - `deposit_wallet` - the destination account receiving tokens transferred from liquidity_account, it must be a token account of the program's mint
+///   other than the vested, program and burning accounts,
+/// - `withdrawal_intent` - the account recording the withdrawal, it is passed and initialized only if an idempotency key is given,
+/// - `withdrawal_receipt` - the account permanently recording the withdrawal, it is created by every withdrawal with the next receipt index of the wallet,
+/// - `signer` - the signer of the transaction which must be the contract's owner or the beneficiary of the wallet, it pays for the withdrawal intent and receipt accounts,
+/// - `token_program` - the Solana token program account,
+/// - `instructions_sysvar` - the instructions sysvar account used to check that the instruction is not invoked via CPI,
+/// - `system_program` - the Solana system program account.
+#[derive(Accounts)]
+#[instruction(amount_to_withdraw: u64, idempotency_key: Option<[u8; 16]>)]
+pub struct WithdrawTokensFromLiquidityWalletContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+
+    /// CHECK: The liquidity wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Liquidity).nonce,
+    )]
+    pub liquidity_account: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = deposit_wallet.mint == contract_state.mint @ LeancoinError::WrongDepositWalletMint,
+        constraint = !is_program_token_account(
+            &contract_state,
+            liquidity_account.key(),
+            deposit_wallet.key(),
+        )? @ LeancoinError::DepositWalletIsProgramAccount,
+    )]
+    pub deposit_wallet: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_LEN + WithdrawalIntent::INIT_SPACE,
+        seeds = [WITHDRAWAL_INTENT_SEED.as_bytes(), idempotency_key.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub withdrawal_intent: Option<Box<Account<'info, WithdrawalIntent>>>,
+    #[account(
+        init,
+        payer = signer,
+        space = DISCRIMINATOR_LEN + WithdrawalReceipt::INIT_SPACE,
+        seeds = [
+            WITHDRAWAL_RECEIPT_SEED.as_bytes(),
+            &[WalletKind::Liquidity as u8],
+            &vesting_state.load()?.next_receipt_index[WalletKind::Liquidity.index()].to_le_bytes(),
+        ],
+        bump
+    )]
+    pub withdrawal_receipt: Box<Account<'info, WithdrawalReceipt>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: The instructions sysvar account. It is considered safe because its address is checked.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for the withdraw_vested instruction.
+///
+/// This context is used to withdraw tokens from any of the vested wallets.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `wallet_account` - the token account of the vested wallet chosen by the instruction argument,
+///   it must be the wallet's PDA, otherwise the instruction fails with `VestedWalletAccountMismatch`,
+/// - `deposit_wallet` - the destination account receiving tokens transferred from wallet_account, it must be a token account of the program's mint
+///   other than the vested, program and burning accounts,
+/// - `withdrawal_intent` - the account recording the withdrawal, it is passed and initialized only if an idempotency key is given,
+/// - `withdrawal_receipt` - the account permanently recording the withdrawal, it is created by every withdrawal with the next receipt index of the wallet,
+/// - `signer` - the signer of the transaction which must be the contract's owner or the beneficiary of the wallet, it pays for the withdrawal intent and receipt accounts,
+/// - `token_program` - the Solana token program account,
+/// - `instructions_sysvar` - the instructions sysvar account used to check that the instruction is not invoked via CPI,
+/// - `system_program` - the Solana system program account.
+#[derive(Accounts)]
+#[instruction(wallet_kind: WalletKind, amount_to_withdraw: u64, idempotency_key: Option<[u8; 16]>)]
+pub struct WithdrawVestedContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+
+    /// CHECK: The vested wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        mut,
+        constraint = wallet_account.key() == create_program_address(
+            wallet_kind.seed(),
+            vesting_state.load()?.wallet(wallet_kind).nonce,
+        )? @ LeancoinError::VestedWalletAccountMismatch,
+    )]
+    pub wallet_account: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = deposit_wallet.mint == contract_state.mint @ LeancoinError::WrongDepositWalletMint,
+        constraint = !is_program_token_account(
+            &contract_state,
+            wallet_account.key(),
+            deposit_wallet.key(),
+        )? @ LeancoinError::DepositWalletIsProgramAccount,
+    )]
+    pub deposit_wallet: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_LEN + WithdrawalIntent::INIT_SPACE,
+        seeds = [WITHDRAWAL_INTENT_SEED.as_bytes(), idempotency_key.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub withdrawal_intent: Option<Box<Account<'info, WithdrawalIntent>>>,
+    #[account(
+        init,
+        payer = signer,
+        space = DISCRIMINATOR_LEN + WithdrawalReceipt::INIT_SPACE,
+        seeds = [
+            WITHDRAWAL_RECEIPT_SEED.as_bytes(),
+            &[wallet_kind as u8],
+            &vesting_state.load()?.next_receipt_index[wallet_kind.index()].to_le_bytes(),
+        ],
+        bump
+    )]
+    pub withdrawal_receipt: Box<Account<'info, WithdrawalReceipt>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: The instructions sysvar account. It is considered safe because its address is checked.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for the withdraw_all_unlocked instruction.
+///
+/// This context is used to withdraw the unlocked tokens from all vested wallets in a single instruction.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `community_account` - the community wallet account,
+/// - `partnership_account` - the partnership wallet account,
+/// - `marketing_account` - the marketing wallet account,
+/// - `liquidity_account` - the liquidity wallet account,
+/// - `community_deposit_wallet` - the destination account receiving tokens transferred from community_account,
+/// - `partnership_deposit_wallet` - the destination account receiving tokens transferred from partnership_account,
+/// - `marketing_deposit_wallet` - the destination account receiving tokens transferred from marketing_account,
+/// - `liquidity_deposit_wallet` - the destination account receiving tokens transferred from liquidity_account,
+/// - `community_withdrawal_receipt` - the account permanently recording the withdrawal from community_account,
+/// - `partnership_withdrawal_receipt` - the account permanently recording the withdrawal from partnership_account,
+/// - `marketing_withdrawal_receipt` - the account permanently recording the withdrawal from marketing_account,
+/// - `liquidity_withdrawal_receipt` - the account permanently recording the withdrawal from liquidity_account,
+/// - `signer` - the signer of the transaction which must be the contract's owner, it pays for the withdrawal receipt accounts,
+/// - `token_program` - the Solana token program account,
+/// - `instructions_sysvar` - the instructions sysvar account used to check that the instruction is not invoked via CPI,
+/// - `system_program` - the Solana system program account.
+///
+/// The deposit wallets must be token accounts of the program's mint other than the vested, program and burning accounts,
+/// the same deposit wallet can be passed for several wallets. The withdrawal receipts are created with the next receipt index
+/// of each wallet, including the wallets with nothing available.
+#[derive(Accounts)]
+pub struct WithdrawAllUnlockedContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+
+    /// CHECK: The community wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        mut,
+        seeds = [COMMUNITY_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Community).nonce,
+    )]
+    pub community_account: AccountInfo<'info>,
+    /// CHECK: The partnership wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        mut,
+        seeds = [PARTNERSHIP_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Partnership).nonce,
+    )]
+    pub partnership_account: AccountInfo<'info>,
+    /// CHECK: The marketing wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        mut,
+        seeds = [MARKETING_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Marketing).nonce,
+    )]
+    pub marketing_account: AccountInfo<'info>,
+    /// CHECK: The liquidity wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Liquidity).nonce,
+    )]
+    pub liquidity_account: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = community_deposit_wallet.mint == contract_state.mint @ LeancoinError::WrongDepositWalletMint,
+    )]
+    pub community_deposit_wallet: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = partnership_deposit_wallet.mint == contract_state.mint @ LeancoinError::WrongDepositWalletMint,
+    )]
+    pub partnership_deposit_wallet: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = marketing_deposit_wallet.mint == contract_state.mint @ LeancoinError::WrongDepositWalletMint,
+    )]
+    pub marketing_deposit_wallet: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = liquidity_deposit_wallet.mint == contract_state.mint @ LeancoinError::WrongDepositWalletMint,
+    )]
+    pub liquidity_deposit_wallet: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = DISCRIMINATOR_LEN + WithdrawalReceipt::INIT_SPACE,
+        seeds = [
+            WITHDRAWAL_RECEIPT_SEED.as_bytes(),
+            &[WalletKind::Community as u8],
+            &vesting_state.load()?.next_receipt_index[WalletKind::Community.index()].to_le_bytes(),
+        ],
+        bump
+    )]
+    pub community_withdrawal_receipt: Box<Account<'info, WithdrawalReceipt>>,
+    #[account(
+        init,
+        payer = signer,
+        space = DISCRIMINATOR_LEN + WithdrawalReceipt::INIT_SPACE,
+        seeds = [
+            WITHDRAWAL_RECEIPT_SEED.as_bytes(),
+            &[WalletKind::Partnership as u8],
+            &vesting_state.load()?.next_receipt_index[WalletKind::Partnership.index()].to_le_bytes(),
+        ],
+        bump
+    )]
+    pub partnership_withdrawal_receipt: Box<Account<'info, WithdrawalReceipt>>,
+    #[account(
+        init,
+        payer = signer,
+        space = DISCRIMINATOR_LEN + WithdrawalReceipt::INIT_SPACE,
+        seeds = [
+            WITHDRAWAL_RECEIPT_SEED.as_bytes(),
+            &[WalletKind::Marketing as u8],
+            &vesting_state.load()?.next_receipt_index[WalletKind::Marketing.index()].to_le_bytes(),
+        ],
+        bump
+    )]
+    pub marketing_withdrawal_receipt: Box<Account<'info, WithdrawalReceipt>>,
+    #[account(
+        init,
+        payer = signer,
+        space = DISCRIMINATOR_LEN + WithdrawalReceipt::INIT_SPACE,
+        seeds = [
+            WITHDRAWAL_RECEIPT_SEED.as_bytes(),
+            &[WalletKind::Liquidity as u8],
+            &vesting_state.load()?.next_receipt_index[WalletKind::Liquidity.index()].to_le_bytes(),
+        ],
+        bump
+    )]
+    pub liquidity_withdrawal_receipt: Box<Account<'info, WithdrawalReceipt>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: The instructions sysvar account. It is considered safe because its address is checked.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for the close_withdrawal_intent instruction.
+///
+/// This context is used to close a withdrawal intent account once its retention period has elapsed.
+///
+/// The context includes:
+/// - `withdrawal_intent` - the account recording the withdrawal with the given idempotency key,
+/// - `payer` - the signer of the transaction which must be the payer of the withdrawal intent account, it receives its rent back.
+#[derive(Accounts)]
+#[instruction(idempotency_key: [u8; 16])]
+pub struct CloseWithdrawalIntentContext<'info> {
+    #[account(
+        mut,
+        close = payer,
+        has_one = payer,
+        seeds = [WITHDRAWAL_INTENT_SEED.as_bytes(), idempotency_key.as_ref()],
+        bump = withdrawal_intent.withdrawal_intent_nonce,
+    )]
+    pub withdrawal_intent: Box<Account<'info, WithdrawalIntent>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+/// Context for the attest_reserves instruction.
+///
+/// This context is used to attest the mint supply and balances of all program-custodied token accounts.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `attestation` - the account that contains the latest attestation, it is initialized during the first attestation,
+/// - `mint` - the mint account,
+/// - `program_account` - the program account,
+/// - `burning_account` - the burning account,
+/// - `community_account` - the community wallet account,
+/// - `partnership_account` - the partnership wallet account,
+/// - `marketing_account` - the marketing wallet account,
+/// - `liquidity_account` - the liquidity wallet account,
+/// - `signer` - the signer of the transaction which pays for the attestation account if it is not initialized yet,
+///   it must be the contract's owner or a registered keeper,
+/// - `system_program` - the Solana system program account.
+#[derive(Accounts)]
+pub struct AttestReservesContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_LEN + Attestation::INIT_SPACE,
+        seeds = [ATTESTATION_SEED.as_bytes()],
+        bump
+    )]
+    pub attestation: Box<Account<'info, Attestation>>,
+
+    #[account(
+        address = contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+    #[account(
+        seeds = [PROGRAM_ACCOUNT_SEED.as_bytes()],
+        bump = contract_state.program_account_nonce,
+    )]
+    pub program_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        seeds = [BURNING_ACCOUNT_SEED.as_bytes()],
+        bump = contract_state.burning_account_nonce,
+    )]
+    pub burning_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: The community wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        seeds = [COMMUNITY_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Community).nonce,
+    )]
+    pub community_account: AccountInfo<'info>,
+    /// CHECK: The partnership wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        seeds = [PARTNERSHIP_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Partnership).nonce,
+    )]
+    pub partnership_account: AccountInfo<'info>,
+    /// CHECK: The marketing wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        seeds = [MARKETING_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Marketing).nonce,
+    )]
+    pub marketing_account: AccountInfo<'info>,
+    /// CHECK: The liquidity wallet account. It is considered safe because its address is checked and it is deserialized only if the wallet has not been finalized.
+    #[account(
+        seeds = [LIQUIDITY_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Liquidity).nonce,
+    )]
+    pub liquidity_account: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for the configure_liquidity_drip instruction.
+///
+/// This context is used to configure the monthly liquidity incentive drip.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `signer` - the signer of the transaction which must be the contract's owner.
+#[derive(Accounts)]
+pub struct ConfigureLiquidityDripContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+    pub signer: Signer<'info>,
+}
+
+/// Context for the execute_liquidity_drip instruction.
+///
+/// This context is used to transfer the monthly liquidity incentive from the liquidity wallet.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `liquidity_account` - the liquidity wallet account which is the source of tokens to be transferred,
+/// - `recipient` - the configured token account receiving the liquidity incentive,
+/// - `token_program` - the Solana token program account,
+/// - `signer` - the signer of the transaction which must be the contract's owner or a registered keeper.
+#[derive(Accounts)]
+pub struct ExecuteLiquidityDripContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Liquidity).nonce,
+    )]
+    pub liquidity_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        address = vesting_state.load()?.liquidity_drip_recipient,
+        constraint = recipient.mint == contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub recipient: Box<Account<'info, TokenAccount>>,
+    pub token_program: Program<'info, Token>,
+    pub signer: Signer<'info>,
+}
+
+/// Context for the initialize_staking instruction.
+///
+/// This context is used to initialize the staking state together with the stake and reward vaults.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `staking_state` - the account that contains the staking state, initialized by the instruction,
+/// - `mint` - the mint account,
+/// - `stake_vault` - the account holding staked tokens, initialized by the instruction,
+/// - `reward_vault` - the account holding staking rewards, initialized by the instruction,
+/// - `signer` - the signer of the transaction which must be the contract's owner,
+/// - `token_program` - the Solana token program account,
+/// - `system_program` - the Solana system program account.
+#[derive(Accounts)]
+pub struct InitializeStakingContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        init,
+        payer = signer,
+        space = DISCRIMINATOR_LEN + StakingState::INIT_SPACE,
+        seeds = [STAKING_STATE_SEED.as_bytes()],
+        bump
+    )]
+    pub staking_state: Box<Account<'info, StakingState>>,
+
+    #[account(
+        address = contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+    #[account(
+        init,
+        payer = signer,
+        token::mint = mint,
+        token::authority = stake_vault,
+        seeds = [STAKE_VAULT_SEED.as_bytes()],
+        bump,
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = signer,
+        token::mint = mint,
+        token::authority = reward_vault,
+        seeds = [REWARD_VAULT_SEED.as_bytes()],
+        bump,
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for the set_staking_reward_percentage instruction.
+///
+/// This context is used to change the part of the monthly burn redirected to the stakers.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `staking_state` - the account that contains the staking state,
+/// - `signer` - the signer of the transaction which must be the contract's owner.
+#[derive(Accounts)]
+pub struct SetStakingRewardPercentageContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [STAKING_STATE_SEED.as_bytes()],
+        bump = staking_state.staking_state_nonce,
+    )]
+    pub staking_state: Box<Account<'info, StakingState>>,
+    pub signer: Signer<'info>,
+}
+
+/// Context for the stake instruction.
+///
+/// This context is used to lock tokens in the stake vault.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `staking_state` - the account that contains the staking state,
+/// - `stake_account` - the account that contains the stake of the signer, initialized during the first stake,
+/// - `stake_vault` - the account holding staked tokens,
+/// - `staker_token_account` - the source account of the staked tokens, owned by the signer,
+/// - `signer` - the signer of the transaction who is the staker,
+/// - `token_program` - the Solana token program account,
+/// - `system_program` - the Solana system program account.
+#[derive(Accounts)]
+pub struct StakeContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [STAKING_STATE_SEED.as_bytes()],
+        bump = staking_state.staking_state_nonce,
+    )]
+    pub staking_state: Box<Account<'info, StakingState>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_LEN + StakeAccount::INIT_SPACE,
+        seeds = [STAKE_ACCOUNT_SEED.as_bytes(), signer.key().as_ref()],
+        bump
+    )]
+    pub stake_account: Box<Account<'info, StakeAccount>>,
+    #[account(
+        mut,
+        seeds = [STAKE_VAULT_SEED.as_bytes()],
+        bump = staking_state.stake_vault_nonce,
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = staker_token_account.mint == contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub staker_token_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for the unstake instruction.
+///
+/// This context is used to start the unbonding of staked tokens.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `staking_state` - the account that contains the staking state,
+/// - `stake_account` - the account that contains the stake of the signer,
+/// - `signer` - the signer of the transaction who is the staker.
+#[derive(Accounts)]
+pub struct UnstakeContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [STAKING_STATE_SEED.as_bytes()],
+        bump = staking_state.staking_state_nonce,
+    )]
+    pub staking_state: Box<Account<'info, StakingState>>,
+    #[account(
+        mut,
+        seeds = [STAKE_ACCOUNT_SEED.as_bytes(), signer.key().as_ref()],
+        bump = stake_account.stake_account_nonce,
+    )]
+    pub stake_account: Box<Account<'info, StakeAccount>>,
+    pub signer: Signer<'info>,
+}
+
+/// Context for the withdraw_unstaked instruction.
+///
+/// This context is used to withdraw unstaked tokens after the unbonding period.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `staking_state` - the account that contains the staking state,
+/// - `stake_account` - the account that contains the stake of the signer,
+/// - `stake_vault` - the account holding staked tokens,
+/// - `staker_token_account` - the destination account of the unstaked tokens,
+/// - `signer` - the signer of the transaction who is the staker,
+/// - `token_program` - the Solana token program account.
+#[derive(Accounts)]
+pub struct WithdrawUnstakedContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        seeds = [STAKING_STATE_SEED.as_bytes()],
+        bump = staking_state.staking_state_nonce,
+    )]
+    pub staking_state: Box<Account<'info, StakingState>>,
+    #[account(
+        mut,
+        seeds = [STAKE_ACCOUNT_SEED.as_bytes(), signer.key().as_ref()],
+        bump = stake_account.stake_account_nonce,
+    )]
+    pub stake_account: Box<Account<'info, StakeAccount>>,
+    #[account(
+        mut,
+        seeds = [STAKE_VAULT_SEED.as_bytes()],
+        bump = staking_state.stake_vault_nonce,
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = staker_token_account.mint == contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub staker_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for the claim_rewards instruction.
+///
+/// This context is used to transfer the staking rewards earned by the signer.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `staking_state` - the account that contains the staking state,
+/// - `stake_account` - the account that contains the stake of the signer,
+/// - `reward_vault` - the account holding staking rewards,
+/// - `staker_token_account` - the destination account of the rewards,
+/// - `signer` - the signer of the transaction who is the staker,
+/// - `token_program` - the Solana token program account.
+#[derive(Accounts)]
+pub struct ClaimRewardsContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        seeds = [STAKING_STATE_SEED.as_bytes()],
+        bump = staking_state.staking_state_nonce,
+    )]
+    pub staking_state: Box<Account<'info, StakingState>>,
+    #[account(
+        mut,
+        seeds = [STAKE_ACCOUNT_SEED.as_bytes(), signer.key().as_ref()],
+        bump = stake_account.stake_account_nonce,
+    )]
+    pub stake_account: Box<Account<'info, StakeAccount>>,
+    #[account(
+        mut,
+        seeds = [REWARD_VAULT_SEED.as_bytes()],
+        bump = staking_state.reward_vault_nonce,
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = staker_token_account.mint == contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub staker_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for the create_otc_deal instruction.
+///
+/// This context is used to escrow tokens from the partnership wallet for a partner.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `mint` - the mint account,
+/// - `partnership_account` - the partnership wallet account which is the source of escrowed tokens,
+/// - `otc_deal` - the account that contains the deal with the partner, initialized during the first deal,
+/// - `otc_vault` - the account escrowing tokens of the deal, initialized during the first deal,
+/// - `signer` - the signer of the transaction which must be the contract's owner,
+/// - `token_program` - the Solana token program account,
+/// - `system_program` - the Solana system program account.
+#[derive(Accounts)]
+#[instruction(partner: Pubkey)]
+pub struct CreateOtcDealContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+    #[account(
+        address = contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+    #[account(
+        mut,
+        seeds = [PARTNERSHIP_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Partnership).nonce,
+    )]
+    pub partnership_account: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DISCRIMINATOR_LEN + OtcDeal::INIT_SPACE,
+        seeds = [OTC_DEAL_SEED.as_bytes(), partner.as_ref()],
+        bump
+    )]
+    pub otc_deal: Box<Account<'info, OtcDeal>>,
+    #[account(
+        init_if_needed,
+        payer = signer,
+        token::mint = mint,
+        token::authority = otc_vault,
+        seeds = [OTC_VAULT_SEED.as_bytes(), partner.as_ref()],
+        bump,
+    )]
+    pub otc_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+/// Context for the settle_otc_deal instruction.
+///
+/// This context is used to transfer the escrowed tokens to the partner.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `otc_deal` - the account that contains the deal with the signer,
+/// - `otc_vault` - the account escrowing tokens of the deal,
+/// - `partner_token_account` - the destination account of the escrowed tokens,
+/// - `signer` - the signer of the transaction who must be the partner,
+/// - `token_program` - the Solana token program account.
+#[derive(Accounts)]
+pub struct SettleOtcDealContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [OTC_DEAL_SEED.as_bytes(), signer.key().as_ref()],
+        bump = otc_deal.otc_deal_nonce,
+    )]
+    pub otc_deal: Box<Account<'info, OtcDeal>>,
+    #[account(
+        mut,
+        seeds = [OTC_VAULT_SEED.as_bytes(), signer.key().as_ref()],
+        bump = otc_deal.otc_vault_nonce,
+    )]
+    pub otc_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = partner_token_account.mint == contract_state.mint @ LeancoinError::WrongMint,
+    )]
+    pub partner_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub signer: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Context for the cancel_otc_deal instruction.
+///
+/// This context is used to return the escrowed tokens to the partnership wallet.
+///
+/// The context includes:
+/// - `contract_state` - the account that contains the contract state,
+/// - `vesting_state` - the account that contains the vesting state,
+/// - `partnership_account` - the partnership wallet account receiving the escrowed tokens back,
+/// - `otc_deal` - the account that contains the deal with the partner,
+/// - `otc_vault` - the account escrowing tokens of the deal,
+/// - `signer` - the signer of the transaction which must be the contract's owner,
+/// - `token_program` - the Solana token program account.
+#[derive(Accounts)]
+#[instruction(partner: Pubkey)]
+pub struct CancelOtcDealContext<'info> {
+    #[account(
+        mut,
+        seeds = [CONTRACT_STATE_SEED.as_bytes()],
+        bump = contract_state.contract_state_nonce,
+    )]
+    pub contract_state: Box<Account<'info, ContractState>>,
+    #[account(
+        mut,
+        seeds = [VESTING_STATE_SEED.as_bytes()],
+        bump = vesting_state.load()?.vesting_state_nonce,
+    )]
+    pub vesting_state: AccountLoader<'info, VestingState>,
+    #[account(
+        mut,
+        seeds = [PARTNERSHIP_ACCOUNT_SEED.as_bytes()],
+        bump = vesting_state.load()?.wallet(WalletKind::Partnership).nonce,
+    )]
+    pub partnership_account: Box<Account<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [OTC_DEAL_SEED.as_bytes(), partner.as_ref()],
+        bump = otc_deal.otc_deal_nonce,
+    )]
+    pub otc_deal: Box<Account<'info, OtcDeal>>,
+    #[account(
+        mut,
+        seeds = [OTC_VAULT_SEED.as_bytes(), partner.as_ref()],
+        bump = otc_deal.otc_vault_nonce,
+    )]
+    pub otc_vault: Box<Account<'info, TokenAccount>>,
+
     pub signer: Signer<'info>,
     pub token_program: Program<'info, Token>,
 }
 
 /// Generic vesting wallet context which is a trait to be implemented by all vesting wallet contexts where:
+/// - `vesting_state` refers to the account that contains the vesting state,
 /// - `vested_account` refers to the account (wallet) who is the source of vested tokens that can be transferred, e.g. community account, partnership account, marketing account or liquidity account,
 /// - `deposit_wallet` refers to the destination account who receives the tokens from `vested_account`,
+/// - `beneficiary_owner` refers to the owner required for `deposit_wallet`, it is not checked if it equals the default public key,
+///   by default it is the beneficiary owner of the wallet configured in the vesting state,
 /// - `token_program` refers to native Solana token program account.
 pub trait VestedWalletContext<'info> {
-    fn vested_account(&self) -> Box<Account<'info, TokenAccount>>;
-    fn vested_account_nonce(&self) -> u8;
-    fn vested_account_seed(&self) -> &str;
+    fn vesting_state(&self) -> &AccountLoader<'info, VestingState>;
+    fn vested_account(&self) -> AccountInfo<'info>;
     fn deposit_wallet(&self) -> Box<Account<'info, TokenAccount>>;
+    fn beneficiary_owner(&self, wallet_kind: WalletKind) -> Result<Pubkey> {
+        Ok(self
+            .vesting_state()
+            .load()?
+            .wallet(wallet_kind)
+            .beneficiary_owner)
+    }
     fn token_program(&self) -> Program<'info, Token>;
 }
 
-impl<'info> VestedWalletContext<'info> for WithdrawTokensFromCommunityWalletContext<'info> {
-    fn vested_account(&self) -> Box<Account<'info, TokenAccount>> {
-        self.community_account.to_owned()
-    }
+/// Generic vested wallet withdrawal context which extends `VestedWalletContext` for the withdraw instructions where:
+/// - `contract_state` refers to the contract state which sequences the withdrawal,
+/// - `withdrawal_intent` refers to the account recording the withdrawal, it is passed only if an idempotency key is given,
+/// - `withdrawal_receipt` refers to the account permanently recording the withdrawal,
+/// - `signer` refers to the contract's owner or the beneficiary of the wallet who pays for the withdrawal intent and receipt accounts.
+pub trait VestedWithdrawalContext<'info>: VestedWalletContext<'info> {
+    fn contract_state(&mut self) -> &mut ContractState;
+    fn withdrawal_intent(&mut self) -> Option<&mut WithdrawalIntent>;
+    fn withdrawal_receipt(&mut self) -> &mut WithdrawalReceipt;
+    fn signer(&self) -> Pubkey;
+}
 
-    fn vested_account_nonce(&self) -> u8 {
-        self.vesting_state.community_wallet_nonce
-    }
+/// Implements `VestedWalletContext` and `VestedWithdrawalContext` for a withdraw context
+/// with the given field of the vested wallet's token account.
+macro_rules! vested_withdrawal_context {
+    ($($context:ident => $vested_account:ident),+ $(,)?) => {
+        $(
+            impl<'info> VestedWalletContext<'info> for $context<'info> {
+                fn vesting_state(&self) -> &AccountLoader<'info, VestingState> {
+                    &self.vesting_state
+                }
 
-    fn vested_account_seed(&self) -> &'info str {
-        COMMUNITY_ACCOUNT_SEED
-    }
+                fn vested_account(&self) -> AccountInfo<'info> {
+                    self.$vested_account.to_account_info()
+                }
 
-    fn deposit_wallet(&self) -> Box<Account<'info, TokenAccount>> {
-        self.deposit_wallet.to_owned()
-    }
+                fn deposit_wallet(&self) -> Box<Account<'info, TokenAccount>> {
+                    self.deposit_wallet.to_owned()
+                }
 
-    fn token_program(&self) -> Program<'info, Token> {
-        self.token_program.to_owned()
-    }
+                fn token_program(&self) -> Program<'info, Token> {
+                    self.token_program.to_owned()
+                }
+            }
+
+            impl<'info> VestedWithdrawalContext<'info> for $context<'info> {
+                fn contract_state(&mut self) -> &mut ContractState {
+                    &mut self.contract_state
+                }
+
+                fn withdrawal_intent(&mut self) -> Option<&mut WithdrawalIntent> {
+                    self.withdrawal_intent
+                        .as_deref_mut()
+                        .map(|withdrawal_intent| &mut **withdrawal_intent)
+                }
+
+                fn withdrawal_receipt(&mut self) -> &mut WithdrawalReceipt {
+                    &mut self.withdrawal_receipt
+                }
+
+                fn signer(&self) -> Pubkey {
+                    self.signer.key()
+                }
+            }
+        )+
+    };
 }
 
-impl<'info> VestedWalletContext<'info> for WithdrawTokensFromPartnershipWalletContext<'info> {
-    fn vested_account(&self) -> Box<Account<'info, TokenAccount>> {
-        self.partnership_account.to_owned()
-    }
+vested_withdrawal_context! {
+    WithdrawVestedContext => wallet_account,
+    WithdrawTokensFromCommunityWalletContext => community_account,
+    WithdrawTokensFromPartnershipWalletContext => partnership_account,
+    WithdrawTokensFromMarketingWalletContext => marketing_account,
+    WithdrawTokensFromLiquidityWalletContext => liquidity_account,
+}
 
-    fn vested_account_nonce(&self) -> u8 {
-        self.vesting_state.partnership_wallet_nonce
+impl<'info> VestedWalletContext<'info> for CreateOtcDealContext<'info> {
+    fn vesting_state(&self) -> &AccountLoader<'info, VestingState> {
+        &self.vesting_state
     }
 
-    fn vested_account_seed(&self) -> &'info str {
-        PARTNERSHIP_ACCOUNT_SEED
+    fn vested_account(&self) -> AccountInfo<'info> {
+        self.partnership_account.to_account_info()
     }
 
     fn deposit_wallet(&self) -> Box<Account<'info, TokenAccount>> {
-        self.deposit_wallet.to_owned()
+        self.otc_vault.to_owned()
+    }
+
+    // the escrow vault is owned by the program, the partner is fixed by the deal instead
+    fn beneficiary_owner(&self, _wallet_kind: WalletKind) -> Result<Pubkey> {
+        Ok(Pubkey::default())
     }
 
     fn token_program(&self) -> Program<'info, Token> {
@@ -495,46 +3023,105 @@ impl<'info> VestedWalletContext<'info> for WithdrawTokensFromPartnershipWalletCo
     }
 }
 
-impl<'info> VestedWalletContext<'info> for WithdrawTokensFromMarketingWalletContext<'info> {
-    fn vested_account(&self) -> Box<Account<'info, TokenAccount>> {
-        self.marketing_account.to_owned()
-    }
+/// Generic burn context which is a trait to be implemented by all contexts burning tokens from the burning account where:
+/// - `contract_state` refers to the contract state which records the total burned amount,
+/// - `mint` refers to the mint account used to mint tokens that should be burned,
+/// - `burning_account` refers to the account that holds tokens to be burned,
+/// - `burn_history` refers to the account that keeps the latest burns,
+/// - `token_program` refers to native Solana token program account.
+pub trait BurningContext<'info> {
+    fn contract_state(&mut self) -> &mut ContractState;
+    fn mint(&mut self) -> &mut Account<'info, Mint>;
+    fn burning_account(&mut self) -> &mut Account<'info, TokenAccount>;
+    fn burn_history(&mut self) -> &mut BurnHistory;
+    fn token_program(&self) -> Program<'info, Token>;
+}
 
-    fn vested_account_nonce(&self) -> u8 {
-        self.vesting_state.marketing_wallet_nonce
-    }
+/// Implements `BurningContext` for a burn context.
+macro_rules! burning_context {
+    ($($context:ident),+ $(,)?) => {
+        $(
+            impl<'info> BurningContext<'info> for $context<'info> {
+                fn contract_state(&mut self) -> &mut ContractState {
+                    &mut self.contract_state
+                }
 
-    fn vested_account_seed(&self) -> &'info str {
-        MARKETING_ACCOUNT_SEED
-    }
+                fn mint(&mut self) -> &mut Account<'info, Mint> {
+                    &mut self.mint
+                }
 
-    fn deposit_wallet(&self) -> Box<Account<'info, TokenAccount>> {
-        self.deposit_wallet.to_owned()
-    }
+                fn burning_account(&mut self) -> &mut Account<'info, TokenAccount> {
+                    &mut self.burning_account
+                }
 
-    fn token_program(&self) -> Program<'info, Token> {
-        self.token_program.to_owned()
-    }
+                fn burn_history(&mut self) -> &mut BurnHistory {
+                    &mut self.burn_history
+                }
+
+                fn token_program(&self) -> Program<'info, Token> {
+                    self.token_program.to_owned()
+                }
+            }
+        )+
+    };
+}
+
+burning_context! {
+    BurnContext,
+    CatchUpBurnContext,
+    BurnExactContext,
 }
 
-impl<'info> VestedWalletContext<'info> for WithdrawTokensFromLiquidityWalletContext<'info> {
-    fn vested_account(&self) -> Box<Account<'info, TokenAccount>> {
-        self.liquidity_account.to_owned()
+impl<'info> WithdrawAllUnlockedContext<'info> {
+    /// Returns the account of the given vested wallet.
+    pub fn wallet_account(&self, wallet_kind: WalletKind) -> AccountInfo<'info> {
+        match wallet_kind {
+            WalletKind::Community => self.community_account.to_account_info(),
+            WalletKind::Partnership => self.partnership_account.to_account_info(),
+            WalletKind::Marketing => self.marketing_account.to_account_info(),
+            WalletKind::Liquidity => self.liquidity_account.to_account_info(),
+        }
     }
 
-    fn vested_account_nonce(&self) -> u8 {
-        self.vesting_state.liquidity_wallet_nonce
+    /// Returns the deposit wallet receiving the tokens withdrawn from the given vested wallet.
+    pub fn deposit_wallet(&self, wallet_kind: WalletKind) -> &Account<'info, TokenAccount> {
+        match wallet_kind {
+            WalletKind::Community => &self.community_deposit_wallet,
+            WalletKind::Partnership => &self.partnership_deposit_wallet,
+            WalletKind::Marketing => &self.marketing_deposit_wallet,
+            WalletKind::Liquidity => &self.liquidity_deposit_wallet,
+        }
     }
 
-    fn vested_account_seed(&self) -> &'info str {
-        LIQUIDITY_ACCOUNT_SEED
+    /// Returns the account recording the withdrawal from the given vested wallet.
+    pub fn withdrawal_receipt(&mut self, wallet_kind: WalletKind) -> &mut WithdrawalReceipt {
+        match wallet_kind {
+            WalletKind::Community => &mut self.community_withdrawal_receipt,
+            WalletKind::Partnership => &mut self.partnership_withdrawal_receipt,
+            WalletKind::Marketing => &mut self.marketing_withdrawal_receipt,
+            WalletKind::Liquidity => &mut self.liquidity_withdrawal_receipt,
+        }
     }
 
-    fn deposit_wallet(&self) -> Box<Account<'info, TokenAccount>> {
-        self.deposit_wallet.to_owned()
+    /// Returns the name of the bump of the account recording the withdrawal from the given vested wallet.
+    pub fn withdrawal_receipt_bump(wallet_kind: WalletKind) -> &'static str {
+        match wallet_kind {
+            WalletKind::Community => "community_withdrawal_receipt",
+            WalletKind::Partnership => "partnership_withdrawal_receipt",
+            WalletKind::Marketing => "marketing_withdrawal_receipt",
+            WalletKind::Liquidity => "liquidity_withdrawal_receipt",
+        }
     }
+}
 
-    fn token_program(&self) -> Program<'info, Token> {
-        self.token_program.to_owned()
+impl<'info> RebalanceContext<'info> {
+    /// Returns the account of the given vested wallet.
+    pub fn wallet_account(&self, wallet_kind: WalletKind) -> AccountInfo<'info> {
+        match wallet_kind {
+            WalletKind::Community => self.community_account.to_account_info(),
+            WalletKind::Partnership => self.partnership_account.to_account_info(),
+            WalletKind::Marketing => self.marketing_account.to_account_info(),
+            WalletKind::Liquidity => self.liquidity_account.to_account_info(),
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,284 @@
+//! Off-chain decoder of the raw data of the contract accounts.
+//! The data is deserialized with the same account types which are used by the contract, so the decoded layout cannot drift.
+//! The module is available only for host builds, it is not part of the deployed program.
+
+use std::str::FromStr;
+
+use anchor_lang::{
+    error::ErrorCode,
+    prelude::{require, Result},
+    solana_program::pubkey::Pubkey,
+    AccountDeserialize,
+};
+use serde::{Serialize, Serializer};
+
+use crate::account::{ContractState, VestingState};
+use crate::context::DISCRIMINATOR_LEN;
+use crate::utils::calculate_month_difference;
+
+/// The kind of the account to decode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodedAccountKind {
+    ContractState,
+    VestingState,
+}
+
+impl FromStr for DecodedAccountKind {
+    type Err = String;
+
+    fn from_str(kind: &str) -> std::result::Result<Self, Self::Err> {
+        match kind {
+            "contract_state" => Ok(DecodedAccountKind::ContractState),
+            "vesting_state" => Ok(DecodedAccountKind::VestingState),
+            _ => Err(format!(
+                "Unknown account {}, expected contract_state or vesting_state",
+                kind
+            )),
+        }
+    }
+}
+
+/// The decoded account together with the values derived from it.
+#[derive(Serialize)]
+#[serde(tag = "account", rename_all = "snake_case")]
+pub enum DecodedAccount {
+    ContractState {
+        state: ContractState,
+    },
+    VestingState {
+        state: VestingState,
+        /// number of full months between the vesting start and the given timestamp, see `calculate_month_difference`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        months_since_start: Option<u64>,
+    },
+}
+
+/// Decodes the raw data of the account, including its discriminator.
+///
+/// ### Arguments
+///
+/// * `kind` - the kind of the account
+/// * `data` - the raw data of the account
+/// * `timestamp` - the timestamp used to calculate the derived values, if any
+///
+/// ### Returns
+/// The decoded account or an error if the data does not belong to the account of the given kind.
+pub fn decode_account(
+    kind: DecodedAccountKind,
+    data: &[u8],
+    timestamp: Option<i64>,
+) -> Result<DecodedAccount> {
+    match kind {
+        DecodedAccountKind::ContractState => Ok(DecodedAccount::ContractState {
+            state: ContractState::try_deserialize(&mut &data[..])?,
+        }),
+        DecodedAccountKind::VestingState => {
+            // the zero-copy layout is reinterpreted from exactly its size, trailing bytes are ignored
+            let vesting_state_len = DISCRIMINATOR_LEN + std::mem::size_of::<VestingState>();
+            require!(
+                data.len() >= vesting_state_len,
+                ErrorCode::AccountDidNotDeserialize
+            );
+            let state = VestingState::try_deserialize(&mut &data[..vesting_state_len])?;
+
+            let months_since_start = match timestamp {
+                Some(timestamp) if state.start_timestamp != 0 => Some(calculate_month_difference(
+                    state.start_timestamp,
+                    timestamp,
+                )?),
+                _ => None,
+            };
+
+            Ok(DecodedAccount::VestingState {
+                state,
+                months_since_start,
+            })
+        }
+    }
+}
+
+/// Serializes the public key as a base58 string instead of an array of bytes.
+pub fn serialize_pubkey<S: Serializer>(
+    pubkey: &Pubkey,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_str(pubkey)
+}
+
+/// Serializes the public keys as an array of base58 strings, see `serialize_pubkey`.
+pub fn serialize_pubkeys<S: Serializer>(
+    pubkeys: &[Pubkey],
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_seq(pubkeys.iter().map(Pubkey::to_string))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::account::{LegacyVestingState, CONTRACT_STATE_VERSION};
+    use crate::{
+        DEFAULT_BURN_RATE_BPS, DEFAULT_BURN_WINDOW_DAYS, DEFAULT_LATE_BURN_WINDOW_FIRST_DAY,
+        DEFAULT_LATE_BURN_WINDOW_LAST_DAY, MAX_KEEPERS, MIGRATION_SOURCES_COUNT,
+    };
+    use anchor_lang::{AccountSerialize, Discriminator};
+    use test_case::test_case;
+
+    // Sunday, 5 March 2023 01:01:01
+    const START_TIMESTAMP: i64 = 1677978061;
+
+    fn contract_state_fixture(authority: Pubkey) -> Vec<u8> {
+        let contract_state = ContractState {
+            version: CONTRACT_STATE_VERSION,
+            authority,
+            performed_imports: 1,
+            wallets_initialized: true,
+            cpi_allowed: false,
+            staking_enabled: false,
+            configuration_locked: true,
+            contract_state_nonce: 255,
+            mint_nonce: 254,
+            program_account_nonce: 253,
+            burning_account_nonce: 252,
+            sequence: 7,
+            max_supply: 10000000000000000000,
+            total_donated: 400,
+            imported_minted: [10000000000000000000; MIGRATION_SOURCES_COUNT],
+            imported_burned: [1470000000000000000; MIGRATION_SOURCES_COUNT],
+            last_burning_year: 2023,
+            last_burning_month: 3,
+            metadata_program_id: Pubkey::default(),
+            burn_remainder_numerator: 0,
+            withdrawal_grace_period_seconds: 0,
+            keepers: [Pubkey::default(); MAX_KEEPERS],
+            late_burn_window_first_day: DEFAULT_LATE_BURN_WINDOW_FIRST_DAY,
+            late_burn_window_last_day: DEFAULT_LATE_BURN_WINDOW_LAST_DAY,
+            paused: false,
+            renounced: false,
+            burn_authority: Pubkey::default(),
+            withdraw_authority: Pubkey::default(),
+            metadata_authority: Pubkey::default(),
+            mint_authority_revoked: false,
+            decimals: 9,
+            mint: Pubkey::default(),
+            burn_rate_bps: DEFAULT_BURN_RATE_BPS,
+            burn_window_days: DEFAULT_BURN_WINDOW_DAYS,
+            burn_caller_reward_bps: 0,
+            total_donated_for_burning: 0,
+            total_burned: 0,
+        };
+
+        let mut data = vec![];
+        contract_state.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn vesting_state_fixture(beneficiary_owner: Pubkey) -> Vec<u8> {
+        let vesting_state = VestingState::from(LegacyVestingState {
+            vesting_state_nonce: 255,
+            community_wallet_nonce: 254,
+            initial_community_wallet_balance: 1000000000000000000,
+            already_withdrawn_community_wallet_amount: 25000000000000000,
+            community_beneficiary_owner: beneficiary_owner,
+            partnership_wallet_nonce: 253,
+            initial_partnership_wallet_balance: 2000000000000000000,
+            already_withdrawn_partnership_wallet_amount: 0,
+            partnership_beneficiary_owner: Pubkey::default(),
+            marketing_wallet_nonce: 252,
+            initial_marketing_wallet_balance: 1500000000000000000,
+            already_withdrawn_marketing_wallet_amount: 0,
+            marketing_beneficiary_owner: Pubkey::default(),
+            liquidity_wallet_nonce: 251,
+            initial_liquidity_wallet_balance: 1000000000000000000,
+            already_withdrawn_liquidity_wallet_amount: 1,
+            liquidity_beneficiary_owner: Pubkey::default(),
+            liquidity_drip_recipient: Pubkey::default(),
+            liquidity_drip_amount_per_month: 0,
+            last_liquidity_drip_month: 0,
+            last_liquidity_drip_year: 0,
+            finalized_wallets: 0,
+            start_timestamp: START_TIMESTAMP,
+        });
+
+        let mut data = VestingState::discriminator().to_vec();
+        data.extend_from_slice(anchor_lang::__private::bytemuck::bytes_of(&vesting_state));
+        data
+    }
+
+    #[test]
+    fn test_decode_contract_state() {
+        let authority = Pubkey::new_unique();
+        let data = contract_state_fixture(authority);
+
+        let decoded = decode_account(DecodedAccountKind::ContractState, &data, None).unwrap();
+        let json = serde_json::to_value(&decoded).unwrap();
+
+        assert_eq!(json["account"], "contract_state");
+        assert_eq!(json["state"]["version"], CONTRACT_STATE_VERSION);
+        assert_eq!(json["state"]["authority"], authority.to_string());
+        assert_eq!(json["state"]["max_supply"], 10000000000000000000u64);
+        assert_eq!(json["state"]["sequence"], 7);
+        assert_eq!(json["state"]["imported_burned"][0], 1470000000000000000u64);
+    }
+
+    #[test_case(None, None ; "without timestamp")]
+    #[test_case(Some(START_TIMESTAMP), Some(0) ; "at vesting start")]
+    // Tuesday, 11 April 2023 01:01:01
+    #[test_case(Some(1681174861), Some(1) ; "one month after vesting start")]
+    fn test_decode_vesting_state(timestamp: Option<i64>, months_since_start: Option<u64>) {
+        let beneficiary_owner = Pubkey::new_unique();
+        let data = vesting_state_fixture(beneficiary_owner);
+
+        let decoded = decode_account(DecodedAccountKind::VestingState, &data, timestamp).unwrap();
+        let json = serde_json::to_value(&decoded).unwrap();
+
+        assert_eq!(json["account"], "vesting_state");
+        assert_eq!(
+            json["state"]["wallets"][0]["beneficiary_owner"],
+            beneficiary_owner.to_string()
+        );
+        assert_eq!(
+            json["state"]["wallets"][1]["initial_balance"],
+            2000000000000000000u64
+        );
+        assert_eq!(json["state"]["wallets"][3]["withdrawn"], 1);
+        assert!(json["state"]["wallets"][0].get("padding").is_none());
+        assert_eq!(json["state"]["start_timestamp"], START_TIMESTAMP);
+        assert!(json["state"].get("padding").is_none());
+        assert_eq!(
+            json.get("months_since_start")
+                .and_then(|months| months.as_u64()),
+            months_since_start
+        );
+    }
+
+    #[test]
+    fn test_fail_decode_account_of_other_kind() {
+        let data = contract_state_fixture(Pubkey::new_unique());
+
+        assert!(decode_account(DecodedAccountKind::VestingState, &data, None).is_err());
+    }
+
+    #[test]
+    fn test_fail_decode_truncated_vesting_state() {
+        let data = vesting_state_fixture(Pubkey::new_unique());
+
+        assert!(decode_account(
+            DecodedAccountKind::VestingState,
+            &data[..data.len() - 1],
+            None
+        )
+        .is_err());
+    }
+
+    #[test_case("contract_state", DecodedAccountKind::ContractState ; "contract state")]
+    #[test_case("vesting_state", DecodedAccountKind::VestingState ; "vesting state")]
+    fn test_parse_decoded_account_kind(kind: &str, expected_kind: DecodedAccountKind) {
+        assert_eq!(kind.parse::<DecodedAccountKind>().unwrap(), expected_kind);
+    }
+
+    #[test]
+    fn test_fail_parse_unknown_decoded_account_kind() {
+        assert!("burn_history".parse::<DecodedAccountKind>().is_err());
+    }
+}
@@ -1,6 +1,16 @@
 use anchor_lang::prelude::error_code;
+use leancoin_core::CoreError;
+
+use crate::WalletKind;
 
 /// The enum defining all errors used by the contract.
+///
+/// The wallet-specific errors use the codes from a range aligned to the number of vested wallets,
+/// so the wallet is encoded in the lowest bits of the code: `code & WALLET_ERROR_MASK` is the index of `WalletKind`
+/// and `code & !WALLET_ERROR_MASK` identifies the error regardless of the wallet.
+/// - 6064..=6067 (64..=67 before Anchor's offset) - not enough tokens to withdraw from the vested wallet.
+///
+/// The codes 54..=63 are used by the remaining errors before any new wallet-specific range, the following errors use the codes from 68.
 #[error_code]
 pub enum LeancoinError {
     #[msg("You are not an owner")]
@@ -11,7 +21,7 @@ pub enum LeancoinError {
     EthereumTokenStateMappingAlreadyPerformed = 2,
     #[msg("Account from remaining accounts not found in user info")]
     MismatchBetweenRemainingAccountsAndUserInfo = 3,
-    #[msg("Tokens can be burned only between the 1st and the 5th day of the month")]
+    #[msg("Tokens can be burned only in the primary or the secondary burn window of the month")]
     TooLateToBurnTokens = 4,
     #[msg("Tokens already burned this month.")]
     TokensAlreadyBurned = 5,
@@ -33,4 +43,418 @@ pub enum LeancoinError {
     LiquidityWalletBalanceIsZero = 13,
     #[msg("Invalid timestamp")]
     InvalidTimestamp = 14,
+    #[msg("Scaling the amount to mint decimals would lose precision")]
+    PrecisionLoss = 15,
+    #[msg("Scaled amount does not fit into the token amount")]
+    ScaledAmountOverflow = 16,
+    #[msg("Vested wallets not initialized yet")]
+    WalletsNotInitialized = 17,
+    #[msg("Vested wallets already initialized")]
+    WalletsAlreadyInitialized = 18,
+    #[msg("Reserves can be attested only once per day")]
+    AttestationTooFrequent = 19,
+    #[msg("Deposit wallet owner does not match the configured beneficiary owner")]
+    DepositOwnerMismatch = 20,
+    #[msg("The instruction cannot be invoked via CPI")]
+    CpiNotAllowed = 21,
+    #[msg("Metadata PDA is not derived from the mint and the metadata program")]
+    InvalidMetadataPda = 22,
+    #[msg("Vesting has not started yet")]
+    VestingNotStarted = 23,
+    #[msg("Deposit wallet is frozen")]
+    DepositWalletFrozen = 24,
+    #[msg("Reward percentage cannot exceed the burn percentage")]
+    InvalidRewardPercentage = 25,
+    #[msg("Not enough staked tokens")]
+    NotEnoughStakedTokens = 26,
+    #[msg("Unbonding period has not elapsed yet")]
+    UnbondingPeriodNotElapsed = 27,
+    #[msg("Staking rewards calculation overflow")]
+    RewardsOverflow = 28,
+    #[msg("Staking state and reward vault are required once staking is enabled")]
+    StakingAccountsMissing = 29,
+    #[msg("Liquidity drip is not configured")]
+    LiquidityDripNotConfigured = 30,
+    #[msg("Liquidity drip already executed this month")]
+    LiquidityDripAlreadyExecuted = 31,
+    #[msg("There is already an open OTC deal with the partner")]
+    OtcDealAlreadyOpen = 32,
+    #[msg("There is no open OTC deal with the partner")]
+    OtcDealNotOpen = 33,
+    #[msg("OTC deal is still locked")]
+    OtcDealLocked = 34,
+    #[msg("Token state from the migration source already imported")]
+    ImportAlreadyPerformed = 35,
+    #[msg("Initial balances of vested wallets can be set only by the first import")]
+    VestingStateAlreadyImported = 36,
+    #[msg("Configuration already locked")]
+    ConfigurationLocked = 37,
+    #[msg("Source and destination wallets must be different")]
+    RebalanceToSameWallet = 38,
+    #[msg("Already withdrawn amount exceeds the new initial wallet balance")]
+    WithdrawnAmountExceedsInitialBalance = 39,
+    #[msg("Timestamp is too far in the future")]
+    TimestampTooFarInFuture = 40,
+    #[msg("Vested wallet already finalized")]
+    WalletFinalized = 41,
+    #[msg("Vested wallet is not fully vested yet")]
+    WalletNotFullyVested = 42,
+    #[msg("Vested wallet balance is not zero")]
+    WalletBalanceIsNotZero = 43,
+    #[msg("Label is longer than 32 bytes")]
+    LabelTooLong = 44,
+    #[msg("Label is not valid UTF-8")]
+    InvalidLabel = 45,
+    #[msg("Vesting state already migrated to the zero-copy layout")]
+    VestingStateAlreadyMigrated = 46,
+    #[msg("Account is already rent-exempt")]
+    AlreadyRentExempt = 47,
+    #[msg("Instruction sequence number overflow")]
+    SequenceOverflow = 48,
+    #[msg("Withdrawal with the same idempotency key already executed")]
+    DuplicateWithdrawal = 49,
+    #[msg("Withdrawal intent account must be passed together with the idempotency key")]
+    WithdrawalIntentMismatch = 50,
+    #[msg("Withdrawal intent retention period has not elapsed yet")]
+    WithdrawalIntentNotExpired = 51,
+    #[msg("Minting would exceed the maximal supply")]
+    MaxSupplyExceeded = 52,
+    #[msg("Contract state already migrated to the current layout")]
+    ContractStateAlreadyMigrated = 53,
+    #[msg("Mint supply did not decrease by the burned amount")]
+    BurnSupplyMismatch = 54,
+    #[msg("Supply can be projected for at most 60 months")]
+    ProjectionTooLong = 55,
+    #[msg("Withdrawals are not allowed during the grace period after the import")]
+    GracePeriodActive = 56,
+    #[msg("Withdrawal grace period must be between 0 and 90 days")]
+    InvalidGracePeriod = 57,
+    #[msg("Keeper is already registered")]
+    KeeperAlreadyRegistered = 58,
+    #[msg("Keeper is not registered")]
+    KeeperNotRegistered = 59,
+    #[msg("At most 8 keepers can be registered")]
+    TooManyKeepers = 60,
+    #[msg("Secondary burn window must start after the primary one and end at the latest on the 28th day of the month")]
+    InvalidLateBurnWindow = 61,
+    #[msg("Import entry is out of the range of the staged import")]
+    ImportEntryOutOfRange = 62,
+    #[msg("Not all entries of the staged import have been transferred")]
+    ImportNotComplete = 63,
+    #[msg("Not enough tokens to withdraw from the community wallet")]
+    NotEnoughTokensCommunity = 64,
+    #[msg("Not enough tokens to withdraw from the partnership wallet")]
+    NotEnoughTokensPartnership = 65,
+    #[msg("Not enough tokens to withdraw from the marketing wallet")]
+    NotEnoughTokensMarketing = 66,
+    #[msg("Not enough tokens to withdraw from the liquidity wallet")]
+    NotEnoughTokensLiquidity = 67,
+    #[msg("A staged import can have at most 256 entries")]
+    TooManyImportEntries = 68,
+    #[msg("More tokens have been withdrawn from the wallet than are unlocked")]
+    WithdrawnMoreThanUnlocked = 69,
+    #[msg("The withdrawn amount of the wallet would overflow")]
+    WithdrawnAmountOverflow = 70,
+    #[msg("Ethereum token state import has not been performed yet")]
+    ImportNotPerformedYet = 71,
+    #[msg("Wallet account does not match the vested wallet")]
+    VestedWalletAccountMismatch = 72,
+    #[msg("Deposit wallet is not a token account of the program's mint")]
+    WrongDepositWalletMint = 73,
+    #[msg("Deposit wallet must not be a token account of the program")]
+    DepositWalletIsProgramAccount = 74,
+    #[msg("Vesting schedule unlocks more than the whole balance")]
+    InvalidVestingSchedule = 75,
+    #[msg("Withdrawals and burning are paused")]
+    ContractPaused = 76,
+    #[msg("Tokens of the program's mint cannot be recovered as foreign tokens")]
+    NativeMintRecoveryNotAllowed = 77,
+    #[msg("Linear daily unlock is supported by the community and marketing wallets only")]
+    LinearDailyUnlockNotSupported = 78,
+    #[msg("Unvested tokens of the wallet have already been revoked")]
+    WalletAlreadyRevoked = 79,
+    #[msg("The initial balance of the wallet would overflow")]
+    InitialBalanceOverflow = 80,
+    #[msg("The authority of the contract has been renounced")]
+    AuthorityRenounced = 81,
+    #[msg("The token name is longer than allowed by the token metadata program")]
+    TokenNameTooLong = 82,
+    #[msg("The token symbol is longer than allowed by the token metadata program")]
+    TokenSymbolTooLong = 83,
+    #[msg("The token URI is longer than allowed by the token metadata program")]
+    TokenUriTooLong = 84,
+    #[msg("The token metadata has not been created yet")]
+    TokenMetadataNotCreated = 85,
+    #[msg("The mint authority has been revoked, no tokens can be minted")]
+    MintAuthorityRevoked = 86,
+    #[msg("The number of decimals of the mint is greater than allowed")]
+    InvalidDecimals = 87,
+    #[msg("The mint is not the mint of the contract")]
+    WrongMint = 88,
+    #[msg("The burn rate must be positive and must not exceed 10000 basis points")]
+    InvalidBurnRate = 89,
+    #[msg("Primary burn window must last from 1 to 28 days")]
+    InvalidBurnWindow = 90,
+    #[msg("The reward of the burn caller must not exceed 50 basis points")]
+    InvalidBurnCallerReward = 91,
+    #[msg("Burning account balance did not decrease by the amount leaving it in the burn")]
+    BurningAccountBalanceMismatch = 92,
+    #[msg("The monthly burn amount of the burning account balance rounds down to zero")]
+    NothingToBurn = 93,
+    #[msg("The catch-up burn requires a previous monthly burn")]
+    NoPreviousBurn = 94,
+    #[msg("The catch-up burn requires a month whose burn was missed entirely")]
+    NoMissedBurn = 95,
+    #[msg("The donated amount would overflow")]
+    DonatedAmountOverflow = 96,
+    #[msg("The staked amount would overflow")]
+    StakedAmountOverflow = 97,
+    #[msg("The total burned amount would overflow")]
+    BurnedAmountOverflow = 98,
+    #[msg("The index of the next withdrawal receipt would overflow")]
+    ReceiptIndexOverflow = 99,
+}
+
+/// The mask of the bits of the wallet-specific error codes which encode the wallet, see `LeancoinError`.
+pub const WALLET_ERROR_MASK: u32 = 0b11;
+
+/// Generates the functions mapping the wallet to its variant of a wallet-specific error and back,
+/// so the order of the variants cannot drift from the order of `WalletKind`.
+macro_rules! wallet_errors {
+    ($($(#[$doc:meta])* $function:ident => [$($wallet_kind:ident => $error:ident),+ $(,)?];)+) => {
+        impl LeancoinError {
+            $(
+                $(#[$doc])*
+                pub fn $function(wallet_kind: WalletKind) -> LeancoinError {
+                    match wallet_kind {
+                        $(WalletKind::$wallet_kind => LeancoinError::$error,)+
+                    }
+                }
+            )+
+
+            /// Returns the wallet of the wallet-specific error.
+            ///
+            /// ### Returns
+            /// The wallet encoded in the error or `None` if the error is not wallet-specific.
+            pub fn wallet_kind(&self) -> Option<WalletKind> {
+                match self {
+                    $($(LeancoinError::$error => Some(WalletKind::$wallet_kind),)+)+
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+wallet_errors! {
+    /// Returns the error raised when there are not enough tokens to withdraw from the wallet.
+    not_enough_tokens => [
+        Community => NotEnoughTokensCommunity,
+        Partnership => NotEnoughTokensPartnership,
+        Marketing => NotEnoughTokensMarketing,
+        Liquidity => NotEnoughTokensLiquidity,
+    ];
+}
+
+impl LeancoinError {
+    /// All errors ordered by their codes, a new error must be added here as well.
+    pub const ALL: [LeancoinError; 100] = [
+        LeancoinError::Unauthorized,
+        LeancoinError::EndTimeMustBeLaterThanStartTime,
+        LeancoinError::EthereumTokenStateMappingAlreadyPerformed,
+        LeancoinError::MismatchBetweenRemainingAccountsAndUserInfo,
+        LeancoinError::TooLateToBurnTokens,
+        LeancoinError::TokensAlreadyBurned,
+        LeancoinError::NotEnoughTokens,
+        LeancoinError::NonUniqueAccountInfo,
+        LeancoinError::DuplicatedWalletName,
+        LeancoinError::ProgramAccountBalanceIsNotZero,
+        LeancoinError::CommunityWalletBalanceIsZero,
+        LeancoinError::PartnershipWalletBalanceIsZero,
+        LeancoinError::MarketingWalletBalanceIsZero,
+        LeancoinError::LiquidityWalletBalanceIsZero,
+        LeancoinError::InvalidTimestamp,
+        LeancoinError::PrecisionLoss,
+        LeancoinError::ScaledAmountOverflow,
+        LeancoinError::WalletsNotInitialized,
+        LeancoinError::WalletsAlreadyInitialized,
+        LeancoinError::AttestationTooFrequent,
+        LeancoinError::DepositOwnerMismatch,
+        LeancoinError::CpiNotAllowed,
+        LeancoinError::InvalidMetadataPda,
+        LeancoinError::VestingNotStarted,
+        LeancoinError::DepositWalletFrozen,
+        LeancoinError::InvalidRewardPercentage,
+        LeancoinError::NotEnoughStakedTokens,
+        LeancoinError::UnbondingPeriodNotElapsed,
+        LeancoinError::RewardsOverflow,
+        LeancoinError::StakingAccountsMissing,
+        LeancoinError::LiquidityDripNotConfigured,
+        LeancoinError::LiquidityDripAlreadyExecuted,
+        LeancoinError::OtcDealAlreadyOpen,
+        LeancoinError::OtcDealNotOpen,
+        LeancoinError::OtcDealLocked,
+        LeancoinError::ImportAlreadyPerformed,
+        LeancoinError::VestingStateAlreadyImported,
+        LeancoinError::ConfigurationLocked,
+        LeancoinError::RebalanceToSameWallet,
+        LeancoinError::WithdrawnAmountExceedsInitialBalance,
+        LeancoinError::TimestampTooFarInFuture,
+        LeancoinError::WalletFinalized,
+        LeancoinError::WalletNotFullyVested,
+        LeancoinError::WalletBalanceIsNotZero,
+        LeancoinError::LabelTooLong,
+        LeancoinError::InvalidLabel,
+        LeancoinError::VestingStateAlreadyMigrated,
+        LeancoinError::AlreadyRentExempt,
+        LeancoinError::SequenceOverflow,
+        LeancoinError::DuplicateWithdrawal,
+        LeancoinError::WithdrawalIntentMismatch,
+        LeancoinError::WithdrawalIntentNotExpired,
+        LeancoinError::MaxSupplyExceeded,
+        LeancoinError::ContractStateAlreadyMigrated,
+        LeancoinError::BurnSupplyMismatch,
+        LeancoinError::ProjectionTooLong,
+        LeancoinError::GracePeriodActive,
+        LeancoinError::InvalidGracePeriod,
+        LeancoinError::KeeperAlreadyRegistered,
+        LeancoinError::KeeperNotRegistered,
+        LeancoinError::TooManyKeepers,
+        LeancoinError::InvalidLateBurnWindow,
+        LeancoinError::ImportEntryOutOfRange,
+        LeancoinError::ImportNotComplete,
+        LeancoinError::NotEnoughTokensCommunity,
+        LeancoinError::NotEnoughTokensPartnership,
+        LeancoinError::NotEnoughTokensMarketing,
+        LeancoinError::NotEnoughTokensLiquidity,
+        LeancoinError::TooManyImportEntries,
+        LeancoinError::WithdrawnMoreThanUnlocked,
+        LeancoinError::WithdrawnAmountOverflow,
+        LeancoinError::ImportNotPerformedYet,
+        LeancoinError::VestedWalletAccountMismatch,
+        LeancoinError::WrongDepositWalletMint,
+        LeancoinError::DepositWalletIsProgramAccount,
+        LeancoinError::InvalidVestingSchedule,
+        LeancoinError::ContractPaused,
+        LeancoinError::NativeMintRecoveryNotAllowed,
+        LeancoinError::LinearDailyUnlockNotSupported,
+        LeancoinError::WalletAlreadyRevoked,
+        LeancoinError::InitialBalanceOverflow,
+        LeancoinError::AuthorityRenounced,
+        LeancoinError::TokenNameTooLong,
+        LeancoinError::TokenSymbolTooLong,
+        LeancoinError::TokenUriTooLong,
+        LeancoinError::TokenMetadataNotCreated,
+        LeancoinError::MintAuthorityRevoked,
+        LeancoinError::InvalidDecimals,
+        LeancoinError::WrongMint,
+        LeancoinError::InvalidBurnRate,
+        LeancoinError::InvalidBurnWindow,
+        LeancoinError::InvalidBurnCallerReward,
+        LeancoinError::BurningAccountBalanceMismatch,
+        LeancoinError::NothingToBurn,
+        LeancoinError::NoPreviousBurn,
+        LeancoinError::NoMissedBurn,
+        LeancoinError::DonatedAmountOverflow,
+        LeancoinError::StakedAmountOverflow,
+        LeancoinError::BurnedAmountOverflow,
+        LeancoinError::ReceiptIndexOverflow,
+    ];
+
+    /// Maps the custom error code returned by the program back to the error.
+    ///
+    /// ### Arguments
+    ///
+    /// * `code` - the custom error code, including Anchor's offset of the user-defined errors
+    ///
+    /// ### Returns
+    /// The error with the given code or `None` if the code does not belong to any of the contract errors.
+    pub fn from_code(code: u32) -> Option<LeancoinError> {
+        LeancoinError::ALL
+            .iter()
+            .copied()
+            .find(|error| u32::from(*error) == code)
+    }
+}
+
+impl From<CoreError> for LeancoinError {
+    fn from(error: CoreError) -> Self {
+        match error {
+            CoreError::InvalidTimestamp => LeancoinError::InvalidTimestamp,
+            CoreError::EndTimeMustBeLaterThanStartTime => {
+                LeancoinError::EndTimeMustBeLaterThanStartTime
+            }
+            CoreError::TimestampTooFarInFuture => LeancoinError::TimestampTooFarInFuture,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anchor_lang::error::ERROR_CODE_OFFSET;
+    use test_case::test_case;
+
+    #[test]
+    fn test_all_errors_are_ordered_by_code() {
+        // the codes are consecutive up to the first wallet-specific range
+        let first_wallet_error_index = LeancoinError::ALL
+            .iter()
+            .position(|error| error.wallet_kind().is_some())
+            .unwrap();
+        for (index, error) in LeancoinError::ALL[..first_wallet_error_index]
+            .iter()
+            .enumerate()
+        {
+            assert_eq!(u32::from(*error), ERROR_CODE_OFFSET + index as u32);
+        }
+        assert!(LeancoinError::ALL
+            .windows(2)
+            .all(|pair| u32::from(pair[0]) < u32::from(pair[1])));
+    }
+
+    #[test_case(WalletKind::Community ; "community")]
+    #[test_case(WalletKind::Partnership ; "partnership")]
+    #[test_case(WalletKind::Marketing ; "marketing")]
+    #[test_case(WalletKind::Liquidity ; "liquidity")]
+    fn test_not_enough_tokens_wallet_error(wallet_kind: WalletKind) {
+        let error = LeancoinError::not_enough_tokens(wallet_kind);
+        let code = u32::from(error);
+
+        assert_eq!(error.wallet_kind(), Some(wallet_kind));
+        assert_eq!(code & WALLET_ERROR_MASK, wallet_kind as u32);
+        assert_eq!(
+            code & !WALLET_ERROR_MASK,
+            u32::from(LeancoinError::NotEnoughTokensCommunity)
+        );
+        assert_eq!(LeancoinError::from_code(code).map(u32::from), Some(code));
+    }
+
+    #[test]
+    fn test_error_without_wallet() {
+        assert!(LeancoinError::NotEnoughTokens.wallet_kind().is_none());
+    }
+
+    #[test]
+    fn test_error_from_code() {
+        assert_eq!(
+            LeancoinError::from_code(u32::from(LeancoinError::NotEnoughTokens)).map(u32::from),
+            Some(u32::from(LeancoinError::NotEnoughTokens))
+        );
+        assert!(LeancoinError::from_code(ERROR_CODE_OFFSET + 100).is_none());
+        assert!(LeancoinError::from_code(0).is_none());
+    }
+
+    #[test_case(CoreError::InvalidTimestamp, LeancoinError::InvalidTimestamp)]
+    #[test_case(
+        CoreError::EndTimeMustBeLaterThanStartTime,
+        LeancoinError::EndTimeMustBeLaterThanStartTime
+    )]
+    #[test_case(
+        CoreError::TimestampTooFarInFuture,
+        LeancoinError::TimestampTooFarInFuture
+    )]
+    fn test_error_from_core_error(error: CoreError, expected: LeancoinError) {
+        assert_eq!(u32::from(LeancoinError::from(error)), u32::from(expected));
+    }
 }
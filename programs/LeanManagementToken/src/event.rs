@@ -0,0 +1,269 @@
+//! Every event contains the sequence number of the emitting instruction, see `ContractState::sequence`.
+//! The sequence numbers give a total order of the events which does not depend on the order of transactions returned by RPC nodes.
+
+use anchor_lang::prelude::{borsh, event, AnchorDeserialize, AnchorSerialize, Pubkey};
+
+use crate::{MigrationSource, Role, WalletKind, VESTED_WALLETS_COUNT};
+
+/// The event emitted by the attest_reserves instruction.
+/// It contains the full breakdown of the values used to compute the attestation hash.
+#[event]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub struct ReservesAttested {
+    pub slot: u64,
+    pub timestamp: i64,
+    pub supply: u64,
+    pub program_account_balance: u64,
+    pub burning_account_balance: u64,
+    pub community_account_balance: u64,
+    pub partnership_account_balance: u64,
+    pub marketing_account_balance: u64,
+    pub liquidity_account_balance: u64,
+    pub hash: [u8; 32],
+    pub sequence: u64,
+}
+
+/// The event emitted at the end of the import_external_token_state and import_ethereum_token_state instructions.
+/// It is a summary of the migration from the source, the amounts are expressed with mint decimals.
+/// The balances of vested wallets are set only by the first import, the balance of the swap wallet is summed over the imports.
+#[event]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub struct ImportCompleted {
+    pub source: MigrationSource,
+    pub total_minted: u64,
+    pub total_burned: u64,
+    pub accounts_funded: u32,
+    pub community: u64,
+    pub partnership: u64,
+    pub marketing: u64,
+    pub liquidity: u64,
+    pub timestamp: i64,
+    pub sequence: u64,
+    pub swap: u64,
+}
+
+/// The event emitted by the execute_liquidity_drip instruction.
+#[event]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub struct LiquidityDripExecuted {
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkey")
+    )]
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub month: u8,
+    pub year: i64,
+    pub sequence: u64,
+}
+
+/// The event emitted by the fund_burning_account instruction.
+#[event]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub struct BurningAccountFunded {
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkey")
+    )]
+    pub donor: Pubkey,
+    pub amount: u64,
+    pub sequence: u64,
+}
+
+/// The event emitted by the donate_to_burn instruction.
+#[event]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub struct BurnDonation {
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkey")
+    )]
+    pub donor: Pubkey,
+    pub amount: u64,
+    pub sequence: u64,
+}
+
+/// The event emitted by the rebalance instruction.
+/// It contains the initial balances of both wallets after the rebalance.
+#[event]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub struct WalletsRebalanced {
+    pub from: WalletKind,
+    pub to: WalletKind,
+    pub amount: u64,
+    pub initial_from_wallet_balance: u64,
+    pub initial_to_wallet_balance: u64,
+    pub sequence: u64,
+}
+
+/// The event emitted by the burn and burn_exact instructions.
+/// The amount does not include the tokens redirected to the staking reward vault,
+/// it is the difference between the supplies of the mint before and after the burn.
+/// The burn is late if it has been executed in the secondary burn window because the primary one was missed.
+/// The burning account balance is the balance left in the burning account after the burn and the transfer of the staking rewards.
+/// The burn rate is the rate in basis points of the burning account balance the amount has been calculated with.
+/// The caller reward is the part transferred to the token account of the caller instead of being burned, zero if none was passed.
+/// The burn is manual if it has been executed by the burn_exact instruction, then its burn rate and rewards are zero.
+#[event]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub struct BurnExecuted {
+    pub amount: u64,
+    pub rewards: u64,
+    pub month: u8,
+    pub year: i64,
+    pub sequence: u64,
+    pub supply_before: u64,
+    pub supply_after: u64,
+    pub late: bool,
+    pub burning_account_balance: u64,
+    pub burn_rate_bps: u16,
+    pub caller_reward: u64,
+    pub manual: bool,
+}
+
+/// The event emitted by the instructions withdrawing tokens from the vested wallets.
+/// The unlocked total is calculated for the given number of months since the vesting start
+/// and the withdrawn total already includes the amount of this withdrawal.
+#[event]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub struct VestedTokensWithdrawn {
+    pub wallet_kind: WalletKind,
+    pub amount: u64,
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkey")
+    )]
+    pub destination: Pubkey,
+    pub sequence: u64,
+    pub unlocked_total: u64,
+    pub already_withdrawn_total: u64,
+    pub months_since_vesting_start: u64,
+    pub timestamp: i64,
+}
+
+/// The event emitted by the withdraw_all_unlocked instruction instead of one `VestedTokensWithdrawn` event per wallet.
+/// The fields are indexed by `WalletKind`, the amount of the wallets skipped because nothing was available is zero.
+#[event]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub struct AllUnlockedTokensWithdrawn {
+    pub amounts: [u64; VESTED_WALLETS_COUNT],
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkeys")
+    )]
+    pub destinations: [Pubkey; VESTED_WALLETS_COUNT],
+    pub already_withdrawn_totals: [u64; VESTED_WALLETS_COUNT],
+    pub sequence: u64,
+    pub months_since_vesting_start: u64,
+    pub timestamp: i64,
+}
+
+/// The event emitted by the add_keeper instruction.
+#[event]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub struct KeeperAdded {
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkey")
+    )]
+    pub keeper: Pubkey,
+    pub sequence: u64,
+}
+
+/// The event emitted by the remove_keeper instruction.
+#[event]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub struct KeeperRemoved {
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkey")
+    )]
+    pub keeper: Pubkey,
+    pub sequence: u64,
+}
+
+/// The event emitted by the set_paused instruction.
+#[event]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub struct PauseStateChanged {
+    pub paused: bool,
+    pub sequence: u64,
+}
+
+/// The event emitted by the revoke_unvested instruction.
+/// The unlocked amount of the wallet is frozen at `unlocked_total`, the revoked tokens are transferred to the destination.
+#[event]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub struct VestingRevoked {
+    pub wallet_kind: WalletKind,
+    pub amount: u64,
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkey")
+    )]
+    pub destination: Pubkey,
+    pub unlocked_total: u64,
+    pub sequence: u64,
+}
+
+/// The event emitted by the add_to_vesting instruction.
+/// `initial_balance` is the initial balance of the wallet after the top-up, the vesting schedule is applied to it.
+#[event]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub struct VestingToppedUp {
+    pub wallet_kind: WalletKind,
+    pub amount: u64,
+    pub initial_balance: u64,
+    pub sequence: u64,
+}
+
+/// The event emitted by the set_role instruction.
+/// `authority` is the default public key if the role has been unassigned.
+#[event]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub struct RoleChanged {
+    pub role: Role,
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkey")
+    )]
+    pub authority: Pubkey,
+    pub sequence: u64,
+}
+
+/// The event emitted by the initialize_state instruction.
+/// `authority` is the contract's owner and `mint` the created mint with its number of decimals.
+#[event]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub struct Initialized {
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkey")
+    )]
+    pub authority: Pubkey,
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkey")
+    )]
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub sequence: u64,
+}
+
+/// The event emitted by the change_authority and renounce_authority instructions.
+/// `new_authority` is the default public key if the authority has been renounced.
+#[event]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub struct AuthorityChanged {
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkey")
+    )]
+    pub old_authority: Pubkey,
+    #[cfg_attr(
+        not(target_os = "solana"),
+        serde(serialize_with = "crate::decode::serialize_pubkey")
+    )]
+    pub new_authority: Pubkey,
+    pub sequence: u64,
+}
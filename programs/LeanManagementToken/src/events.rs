@@ -0,0 +1,406 @@
+//! Off-chain parser of the events emitted by the contract.
+//! Anchor emits an event as a `Program data: ` log line with the base64 encoded discriminator and serialized event,
+//! so the events can be decoded from the transaction logs without the TypeScript Anchor client.
+//! The module is available only for host builds, it is not part of the deployed program.
+
+use anchor_lang::{solana_program::pubkey::Pubkey, AnchorDeserialize, Discriminator};
+use serde::Serialize;
+
+pub use crate::event::{
+    AllUnlockedTokensWithdrawn, AuthorityChanged, BurnDonation, BurnExecuted, BurningAccountFunded,
+    ImportCompleted, Initialized, KeeperAdded, KeeperRemoved, LiquidityDripExecuted,
+    PauseStateChanged, ReservesAttested, RoleChanged, VestedTokensWithdrawn, VestingRevoked,
+    VestingToppedUp, WalletsRebalanced,
+};
+
+/// The prefix of the log line containing the data emitted by the program.
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// The names of the events emitted by the contract, see `LeancoinEvent::name`.
+pub const EVENT_NAMES: [&str; 17] = [
+    "ReservesAttested",
+    "ImportCompleted",
+    "LiquidityDripExecuted",
+    "BurnDonation",
+    "WalletsRebalanced",
+    "BurnExecuted",
+    "VestedTokensWithdrawn",
+    "KeeperAdded",
+    "KeeperRemoved",
+    "AllUnlockedTokensWithdrawn",
+    "PauseStateChanged",
+    "VestingRevoked",
+    "VestingToppedUp",
+    "RoleChanged",
+    "Initialized",
+    "AuthorityChanged",
+    "BurningAccountFunded",
+];
+
+/// The event emitted by the contract.
+/// It is serialized as an object with the name of the event in the `event` field followed by the fields of the event.
+#[derive(Serialize)]
+#[serde(tag = "event")]
+pub enum LeancoinEvent {
+    ReservesAttested(ReservesAttested),
+    ImportCompleted(ImportCompleted),
+    LiquidityDripExecuted(LiquidityDripExecuted),
+    BurnDonation(BurnDonation),
+    WalletsRebalanced(WalletsRebalanced),
+    BurnExecuted(BurnExecuted),
+    VestedTokensWithdrawn(VestedTokensWithdrawn),
+    KeeperAdded(KeeperAdded),
+    KeeperRemoved(KeeperRemoved),
+    AllUnlockedTokensWithdrawn(AllUnlockedTokensWithdrawn),
+    PauseStateChanged(PauseStateChanged),
+    VestingRevoked(VestingRevoked),
+    VestingToppedUp(VestingToppedUp),
+    RoleChanged(RoleChanged),
+    Initialized(Initialized),
+    AuthorityChanged(AuthorityChanged),
+    BurningAccountFunded(BurningAccountFunded),
+}
+
+impl LeancoinEvent {
+    /// Returns the name of the event, i.e. the name of its structure.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LeancoinEvent::ReservesAttested(_) => "ReservesAttested",
+            LeancoinEvent::ImportCompleted(_) => "ImportCompleted",
+            LeancoinEvent::LiquidityDripExecuted(_) => "LiquidityDripExecuted",
+            LeancoinEvent::BurnDonation(_) => "BurnDonation",
+            LeancoinEvent::WalletsRebalanced(_) => "WalletsRebalanced",
+            LeancoinEvent::BurnExecuted(_) => "BurnExecuted",
+            LeancoinEvent::VestedTokensWithdrawn(_) => "VestedTokensWithdrawn",
+            LeancoinEvent::KeeperAdded(_) => "KeeperAdded",
+            LeancoinEvent::KeeperRemoved(_) => "KeeperRemoved",
+            LeancoinEvent::AllUnlockedTokensWithdrawn(_) => "AllUnlockedTokensWithdrawn",
+            LeancoinEvent::PauseStateChanged(_) => "PauseStateChanged",
+            LeancoinEvent::VestingRevoked(_) => "VestingRevoked",
+            LeancoinEvent::VestingToppedUp(_) => "VestingToppedUp",
+            LeancoinEvent::RoleChanged(_) => "RoleChanged",
+            LeancoinEvent::Initialized(_) => "Initialized",
+            LeancoinEvent::AuthorityChanged(_) => "AuthorityChanged",
+            LeancoinEvent::BurningAccountFunded(_) => "BurningAccountFunded",
+        }
+    }
+
+    /// Decodes the event from the data emitted by the contract.
+    ///
+    /// ### Arguments
+    ///
+    /// * `data` - the discriminator of the event followed by the serialized event
+    ///
+    /// ### Returns
+    /// The decoded event or None if the data does not contain any of the contract events.
+    pub fn decode(data: &[u8]) -> Option<LeancoinEvent> {
+        if data.len() < 8 {
+            return None;
+        }
+        let (discriminator, mut event_data) = data.split_at(8);
+
+        fn deserialize<T: AnchorDeserialize>(
+            event_data: &mut &[u8],
+            event: fn(T) -> LeancoinEvent,
+        ) -> Option<LeancoinEvent> {
+            T::deserialize(event_data).ok().map(event)
+        }
+
+        match discriminator {
+            d if d == ReservesAttested::discriminator() => {
+                deserialize(&mut event_data, LeancoinEvent::ReservesAttested)
+            }
+            d if d == ImportCompleted::discriminator() => {
+                deserialize(&mut event_data, LeancoinEvent::ImportCompleted)
+            }
+            d if d == LiquidityDripExecuted::discriminator() => {
+                deserialize(&mut event_data, LeancoinEvent::LiquidityDripExecuted)
+            }
+            d if d == BurnDonation::discriminator() => {
+                deserialize(&mut event_data, LeancoinEvent::BurnDonation)
+            }
+            d if d == WalletsRebalanced::discriminator() => {
+                deserialize(&mut event_data, LeancoinEvent::WalletsRebalanced)
+            }
+            d if d == BurnExecuted::discriminator() => {
+                deserialize(&mut event_data, LeancoinEvent::BurnExecuted)
+            }
+            d if d == VestedTokensWithdrawn::discriminator() => {
+                deserialize(&mut event_data, LeancoinEvent::VestedTokensWithdrawn)
+            }
+            d if d == KeeperAdded::discriminator() => {
+                deserialize(&mut event_data, LeancoinEvent::KeeperAdded)
+            }
+            d if d == KeeperRemoved::discriminator() => {
+                deserialize(&mut event_data, LeancoinEvent::KeeperRemoved)
+            }
+            d if d == AllUnlockedTokensWithdrawn::discriminator() => {
+                deserialize(&mut event_data, LeancoinEvent::AllUnlockedTokensWithdrawn)
+            }
+            d if d == PauseStateChanged::discriminator() => {
+                deserialize(&mut event_data, LeancoinEvent::PauseStateChanged)
+            }
+            d if d == VestingRevoked::discriminator() => {
+                deserialize(&mut event_data, LeancoinEvent::VestingRevoked)
+            }
+            d if d == VestingToppedUp::discriminator() => {
+                deserialize(&mut event_data, LeancoinEvent::VestingToppedUp)
+            }
+            d if d == RoleChanged::discriminator() => {
+                deserialize(&mut event_data, LeancoinEvent::RoleChanged)
+            }
+            d if d == Initialized::discriminator() => {
+                deserialize(&mut event_data, LeancoinEvent::Initialized)
+            }
+            d if d == AuthorityChanged::discriminator() => {
+                deserialize(&mut event_data, LeancoinEvent::AuthorityChanged)
+            }
+            d if d == BurningAccountFunded::discriminator() => {
+                deserialize(&mut event_data, LeancoinEvent::BurningAccountFunded)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parses the events emitted by the contract from the logs of a transaction.
+/// The invoked programs are tracked with the `invoke` and `success`/`failed` log lines,
+/// so the data logged by other programs, e.g. invoked via CPI by the contract or invoking the contract, is skipped.
+///
+/// ### Arguments
+///
+/// * `logs` - the log messages of the transaction
+///
+/// ### Returns
+/// The events emitted by the contract in the order of emission.
+pub fn parse_events(logs: &[String]) -> Vec<LeancoinEvent> {
+    let program_id = crate::ID.to_string();
+    let mut invoked_programs: Vec<&str> = vec![];
+    let mut events = vec![];
+
+    for log in logs {
+        if let Some(data) = log.strip_prefix(PROGRAM_DATA_PREFIX) {
+            if invoked_programs.last() != Some(&program_id.as_str()) {
+                continue;
+            }
+            if let Some(event) = base64::decode(data)
+                .ok()
+                .and_then(|data| LeancoinEvent::decode(&data))
+            {
+                events.push(event);
+            }
+            continue;
+        }
+
+        let words: Vec<&str> = log.splitn(4, ' ').collect();
+        match words[..] {
+            ["Program", program, "invoke", ..] if program.parse::<Pubkey>().is_ok() => {
+                invoked_programs.push(program)
+            }
+            ["Program", program, "success"] | ["Program", program, "failed:", ..]
+                if program.parse::<Pubkey>().is_ok() =>
+            {
+                invoked_programs.pop();
+            }
+            _ => {}
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::WalletKind;
+    use anchor_lang::Event;
+
+    const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+    fn program_data<T: Event>(event: &T) -> String {
+        format!("{}{}", PROGRAM_DATA_PREFIX, base64::encode(event.data()))
+    }
+
+    fn withdrawal_event(amount: u64, sequence: u64) -> VestedTokensWithdrawn {
+        VestedTokensWithdrawn {
+            wallet_kind: WalletKind::Liquidity,
+            amount,
+            destination: Pubkey::new_from_array([7; 32]),
+            sequence,
+            unlocked_total: 1000,
+            already_withdrawn_total: amount,
+            months_since_vesting_start: 1,
+            timestamp: 1680652800,
+        }
+    }
+
+    /// The logs of the withdraw_tokens_from_liquidity_wallet instruction as returned by solana-program-test.
+    fn withdrawal_logs(event: &VestedTokensWithdrawn) -> Vec<String> {
+        let program_id = crate::ID.to_string();
+
+        vec![
+            format!("Program {} invoke [1]", program_id),
+            "Program log: Instruction: WithdrawTokensFromLiquidityWallet".to_string(),
+            format!("Program {} invoke [2]", TOKEN_PROGRAM_ID),
+            "Program log: Instruction: Transfer".to_string(),
+            format!(
+                "Program {} consumed 4645 of 171225 compute units",
+                TOKEN_PROGRAM_ID
+            ),
+            format!("Program {} success", TOKEN_PROGRAM_ID),
+            program_data(event),
+            format!(
+                "Program {} consumed 33420 of 200000 compute units",
+                program_id
+            ),
+            format!("Program {} success", program_id),
+        ]
+    }
+
+    #[test]
+    fn test_parse_events_with_interleaved_token_program_logs() {
+        let logs = withdrawal_logs(&withdrawal_event(100, 3));
+
+        let events = parse_events(&logs);
+
+        assert_eq!(events.len(), 1);
+        let event = match &events[0] {
+            LeancoinEvent::VestedTokensWithdrawn(event) => event,
+            _ => panic!("unexpected event"),
+        };
+        assert_eq!(event.wallet_kind, WalletKind::Liquidity);
+        assert_eq!(event.amount, 100);
+        assert_eq!(event.destination, Pubkey::new_from_array([7; 32]));
+        assert_eq!(event.sequence, 3);
+    }
+
+    #[test]
+    fn test_parse_events_of_multiple_instructions() {
+        let burn_event = BurnExecuted {
+            amount: 90000000000000000,
+            rewards: 0,
+            month: 3,
+            year: 2023,
+            sequence: 4,
+            supply_before: 8530000000000000000,
+            supply_after: 8440000000000000000,
+            late: false,
+            burning_account_balance: 1710000000000000000,
+            burn_rate_bps: 500,
+            caller_reward: 0,
+            manual: false,
+        };
+        let program_id = crate::ID.to_string();
+        let mut logs = vec![
+            format!("Program {} invoke [1]", program_id),
+            "Program log: Instruction: Burn".to_string(),
+            format!("Program {} invoke [2]", TOKEN_PROGRAM_ID),
+            "Program log: Instruction: Burn".to_string(),
+            format!("Program {} success", TOKEN_PROGRAM_ID),
+            program_data(&burn_event),
+            format!("Program {} success", program_id),
+        ];
+        logs.extend(withdrawal_logs(&withdrawal_event(1, 5)));
+
+        let events = parse_events(&logs);
+
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            &events[0],
+            LeancoinEvent::BurnExecuted(event) if event.amount == 90000000000000000 && event.sequence == 4
+        ));
+        assert!(matches!(
+            &events[1],
+            LeancoinEvent::VestedTokensWithdrawn(event) if event.amount == 1 && event.sequence == 5
+        ));
+    }
+
+    #[test]
+    fn test_parse_events_skips_data_of_other_programs() {
+        let other_program_id = Pubkey::new_unique().to_string();
+        let program_id = crate::ID.to_string();
+        let logs = vec![
+            format!("Program {} invoke [1]", other_program_id),
+            program_data(&withdrawal_event(1, 1)),
+            // the contract invoked via CPI by the other program
+            format!("Program {} invoke [2]", program_id),
+            program_data(&withdrawal_event(2, 2)),
+            format!("Program {} invoke [3]", other_program_id),
+            program_data(&withdrawal_event(3, 3)),
+            format!("Program {} success", other_program_id),
+            format!("Program {} success", program_id),
+            program_data(&withdrawal_event(4, 4)),
+            format!("Program {} success", other_program_id),
+        ];
+
+        let events = parse_events(&logs);
+
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0],
+            LeancoinEvent::VestedTokensWithdrawn(event) if event.amount == 2
+        ));
+    }
+
+    #[test]
+    fn test_parse_events_of_failed_instruction() {
+        let program_id = crate::ID.to_string();
+        let logs = vec![
+            format!("Program {} invoke [1]", program_id),
+            format!("Program {} invoke [2]", TOKEN_PROGRAM_ID),
+            format!(
+                "Program {} failed: custom program error: 0x1",
+                TOKEN_PROGRAM_ID
+            ),
+            program_data(&withdrawal_event(1, 1)),
+            format!("Program {} failed: custom program error: 0x1", program_id),
+            program_data(&withdrawal_event(2, 2)),
+        ];
+
+        let events = parse_events(&logs);
+
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0],
+            LeancoinEvent::VestedTokensWithdrawn(event) if event.amount == 1
+        ));
+    }
+
+    #[test]
+    fn test_serialize_event_with_name() {
+        let event = LeancoinEvent::VestedTokensWithdrawn(withdrawal_event(100, 3));
+
+        let json = serde_json::to_value(&event).unwrap();
+
+        assert!(EVENT_NAMES.contains(&event.name()));
+        assert_eq!(json["event"], "VestedTokensWithdrawn");
+        assert_eq!(json["wallet_kind"], "Liquidity");
+        assert_eq!(json["amount"], 100);
+        assert_eq!(
+            json["destination"],
+            Pubkey::new_from_array([7; 32]).to_string()
+        );
+        assert_eq!(json["sequence"], 3);
+        assert_eq!(json["unlocked_total"], 1000);
+        assert_eq!(json["months_since_vesting_start"], 1);
+    }
+
+    #[test]
+    fn test_parse_events_skips_invalid_data() {
+        let program_id = crate::ID.to_string();
+        let logs = vec![
+            format!("Program {} invoke [1]", program_id),
+            format!("{}not base64", PROGRAM_DATA_PREFIX),
+            format!("{}{}", PROGRAM_DATA_PREFIX, base64::encode([1, 2, 3])),
+            format!("{}{}", PROGRAM_DATA_PREFIX, base64::encode([0; 16])),
+            format!(
+                "{}{}",
+                PROGRAM_DATA_PREFIX,
+                base64::encode(VestedTokensWithdrawn::discriminator())
+            ),
+            format!("Program {} success", program_id),
+        ];
+
+        assert!(parse_events(&logs).is_empty());
+    }
+}
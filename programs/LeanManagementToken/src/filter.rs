@@ -0,0 +1,183 @@
+//! Off-chain builders of the `memcmp` filters of `getProgramAccounts` requests matching the contract state.
+//! The filters use the fixed field offsets of the versioned `ContractState` layout.
+//! The module is available only for host builds, it is not part of the deployed program.
+
+use anchor_lang::{solana_program::pubkey::Pubkey, Discriminator};
+
+use crate::account::{ContractState, CONTRACT_STATE_VERSION};
+
+/// The `memcmp` filter matching the account data which contains the given bytes at the given offset.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemcmpFilter {
+    pub offset: usize,
+    pub bytes: Vec<u8>,
+}
+
+impl MemcmpFilter {
+    /// Checks if the account data is matched by the filter, the same way as the RPC node does.
+    pub fn matches(&self, data: &[u8]) -> bool {
+        data.get(self.offset..self.offset + self.bytes.len()) == Some(self.bytes.as_slice())
+    }
+}
+
+/// Builds the filters matching the contract state accounts in the current layout.
+/// Further filters, e.g. `authority_filter`, can be appended to narrow down the result.
+///
+/// ### Returns
+/// The filters matching the discriminator and the layout version of the contract state
+pub fn contract_state_filters() -> Vec<MemcmpFilter> {
+    vec![
+        MemcmpFilter {
+            offset: 0,
+            bytes: ContractState::discriminator().to_vec(),
+        },
+        MemcmpFilter {
+            offset: ContractState::VERSION_OFFSET,
+            bytes: vec![CONTRACT_STATE_VERSION],
+        },
+    ]
+}
+
+/// Builds the filter matching the contract state with the given authority.
+///
+/// ### Arguments
+///
+/// * `authority` - the authority of the contract
+///
+/// ### Returns
+/// The filter matching the authority field of the contract state
+pub fn authority_filter(authority: &Pubkey) -> MemcmpFilter {
+    MemcmpFilter {
+        offset: ContractState::AUTHORITY_OFFSET,
+        bytes: authority.to_bytes().to_vec(),
+    }
+}
+
+/// Builds the filter matching the contract state with exactly the given imports performed,
+/// e.g. `MigrationSource::Ethereum.mask()` matches the contract state after the Ethereum token state import only.
+///
+/// ### Arguments
+///
+/// * `performed_imports` - the bitmask of migration sources whose token state has been imported
+///
+/// ### Returns
+/// The filter matching the performed imports field of the contract state
+pub fn performed_imports_filter(performed_imports: u8) -> MemcmpFilter {
+    MemcmpFilter {
+        offset: ContractState::PERFORMED_IMPORTS_OFFSET,
+        bytes: vec![performed_imports],
+    }
+}
+
+/// Builds the filter matching the contract state whose vested wallets have or have not been initialized.
+///
+/// ### Arguments
+///
+/// * `wallets_initialized` - information if the vested wallets have been initialized
+///
+/// ### Returns
+/// The filter matching the wallets initialized field of the contract state
+pub fn wallets_initialized_filter(wallets_initialized: bool) -> MemcmpFilter {
+    MemcmpFilter {
+        offset: ContractState::WALLETS_INITIALIZED_OFFSET,
+        bytes: vec![wallets_initialized as u8],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        MigrationSource, DEFAULT_BURN_RATE_BPS, DEFAULT_BURN_WINDOW_DAYS,
+        DEFAULT_LATE_BURN_WINDOW_FIRST_DAY, DEFAULT_LATE_BURN_WINDOW_LAST_DAY, MAX_KEEPERS,
+        MIGRATION_SOURCES_COUNT,
+    };
+    use anchor_lang::AccountSerialize;
+
+    fn contract_state_data(
+        authority: Pubkey,
+        performed_imports: u8,
+        wallets_initialized: bool,
+    ) -> Vec<u8> {
+        let contract_state = ContractState {
+            version: CONTRACT_STATE_VERSION,
+            authority,
+            performed_imports,
+            wallets_initialized,
+            cpi_allowed: false,
+            staking_enabled: false,
+            configuration_locked: false,
+            contract_state_nonce: 255,
+            mint_nonce: 254,
+            program_account_nonce: 253,
+            burning_account_nonce: 252,
+            sequence: 3,
+            max_supply: 0,
+            total_donated: 0,
+            imported_minted: [0; MIGRATION_SOURCES_COUNT],
+            imported_burned: [0; MIGRATION_SOURCES_COUNT],
+            last_burning_year: 0,
+            last_burning_month: 0,
+            metadata_program_id: Pubkey::default(),
+            burn_remainder_numerator: 0,
+            withdrawal_grace_period_seconds: 0,
+            keepers: [Pubkey::default(); MAX_KEEPERS],
+            late_burn_window_first_day: DEFAULT_LATE_BURN_WINDOW_FIRST_DAY,
+            late_burn_window_last_day: DEFAULT_LATE_BURN_WINDOW_LAST_DAY,
+            paused: false,
+            renounced: false,
+            burn_authority: Pubkey::default(),
+            withdraw_authority: Pubkey::default(),
+            metadata_authority: Pubkey::default(),
+            mint_authority_revoked: false,
+            decimals: 9,
+            mint: Pubkey::default(),
+            burn_rate_bps: DEFAULT_BURN_RATE_BPS,
+            burn_window_days: DEFAULT_BURN_WINDOW_DAYS,
+            burn_caller_reward_bps: 0,
+            total_donated_for_burning: 0,
+            total_burned: 0,
+        };
+
+        let mut data = vec![];
+        contract_state.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_contract_state_filters() {
+        let authority = Pubkey::new_unique();
+        let data = contract_state_data(authority, MigrationSource::Ethereum.mask(), true);
+        let other_data = contract_state_data(Pubkey::new_unique(), 0, false);
+
+        assert!(contract_state_filters()
+            .iter()
+            .all(|filter| filter.matches(&data) && filter.matches(&other_data)));
+
+        assert!(authority_filter(&authority).matches(&data));
+        assert!(!authority_filter(&authority).matches(&other_data));
+
+        assert!(performed_imports_filter(MigrationSource::Ethereum.mask()).matches(&data));
+        assert!(!performed_imports_filter(MigrationSource::Ethereum.mask()).matches(&other_data));
+
+        assert!(wallets_initialized_filter(true).matches(&data));
+        assert!(wallets_initialized_filter(false).matches(&other_data));
+    }
+
+    #[test]
+    fn test_contract_state_filters_do_not_match_legacy_layout() {
+        let mut data = contract_state_data(Pubkey::new_unique(), 0, false);
+        data[ContractState::VERSION_OFFSET] = 0;
+
+        assert!(!contract_state_filters()
+            .iter()
+            .all(|filter| filter.matches(&data)));
+    }
+
+    #[test]
+    fn test_filter_out_of_bounds_does_not_match() {
+        let filter = authority_filter(&Pubkey::new_unique());
+
+        assert!(!filter.matches(&[0; 16]));
+    }
+}
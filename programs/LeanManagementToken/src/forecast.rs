@@ -0,0 +1,198 @@
+//! Off-chain vesting forecast built on the same calculations which are used by the contract.
+//! The module is available only for host builds, it is not part of the deployed program.
+
+use anchor_lang::prelude::Result;
+use serde::Serialize;
+
+use crate::utils::{
+    calculate_unlocked_amount_community_wallet, calculate_unlocked_amount_liquidity_wallet,
+    calculate_unlocked_amount_marketing_wallet, calculate_unlocked_amount_partnership_wallet,
+    parse_timestamp,
+};
+
+/// Initial balances of the wallets affected by vesting mechanism, i.e. balances after Ethereum token state import.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WalletBalances {
+    pub community: u64,
+    pub partnership: u64,
+    pub marketing: u64,
+    pub liquidity: u64,
+}
+
+/// Amounts of unlocked tokens in the given month of vesting.
+/// The amounts are cumulative, i.e. they include tokens unlocked in the previous months.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MonthlyForecast {
+    pub months_since_vesting_start: u16,
+    pub year: i64,
+    pub month: u8,
+    pub community_unlocked: u64,
+    pub partnership_unlocked: u64,
+    pub marketing_unlocked: u64,
+    pub liquidity_unlocked: u64,
+    pub total_unlocked: u64,
+}
+
+impl MonthlyForecast {
+    /// The header of the CSV representation of the forecast.
+    pub const CSV_HEADER: &'static str = "months_since_vesting_start,year,month,community_unlocked,partnership_unlocked,marketing_unlocked,liquidity_unlocked,total_unlocked";
+
+    /// Returns the forecast as a CSV row matching `CSV_HEADER`.
+    pub fn to_csv_row(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{}",
+            self.months_since_vesting_start,
+            self.year,
+            self.month,
+            self.community_unlocked,
+            self.partnership_unlocked,
+            self.marketing_unlocked,
+            self.liquidity_unlocked,
+            self.total_unlocked
+        )
+    }
+}
+
+/// Forecasts the amounts of unlocked tokens for every month of vesting.
+///
+/// ### Arguments
+///
+/// * `initial_balances` - the initial balances of the wallets affected by vesting mechanism
+/// * `start_timestamp` - the vesting start timestamp, i.e. the timestamp of Ethereum token state import
+/// * `months` - number of months to forecast, the first forecasted month is the month of vesting start
+/// * `community_unlock_delay_months` - number of months before the first unlock of the community wallet
+///
+/// ### Returns
+/// The forecast for each month
+pub fn forecast(
+    initial_balances: WalletBalances,
+    start_timestamp: i64,
+    months: u16,
+    community_unlock_delay_months: u8,
+) -> Result<Vec<MonthlyForecast>> {
+    let start = parse_timestamp(start_timestamp)?;
+
+    (0..months)
+        .map(|months_since_vesting_start| {
+            let months_since_start = u64::from(months_since_vesting_start);
+            let month_index = i64::from(start.month) - 1 + i64::from(months_since_vesting_start);
+
+            let community_unlocked = calculate_unlocked_amount_community_wallet(
+                initial_balances.community,
+                months_since_start,
+                community_unlock_delay_months,
+            );
+            let partnership_unlocked = calculate_unlocked_amount_partnership_wallet(
+                initial_balances.partnership,
+                months_since_start,
+            );
+            let marketing_unlocked = calculate_unlocked_amount_marketing_wallet(
+                initial_balances.marketing,
+                months_since_start,
+            );
+            let liquidity_unlocked = calculate_unlocked_amount_liquidity_wallet(
+                initial_balances.liquidity,
+                months_since_start,
+            );
+
+            Ok(MonthlyForecast {
+                months_since_vesting_start,
+                year: start.year + month_index / 12,
+                month: (month_index % 12 + 1).try_into().unwrap(),
+                community_unlocked,
+                partnership_unlocked,
+                marketing_unlocked,
+                liquidity_unlocked,
+                total_unlocked: community_unlocked
+                    + partnership_unlocked
+                    + marketing_unlocked
+                    + liquidity_unlocked,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_case::test_case;
+
+    const INITIAL_BALANCES: WalletBalances = WalletBalances {
+        community: 1000000000,
+        partnership: 1000000000,
+        marketing: 1000000000,
+        liquidity: 1000000000,
+    };
+
+    // 03/05/21
+    const START_TIMESTAMP: i64 = 1620000000;
+
+    #[test_case(1, 50000000, 500000000, 0, 500000000; "1 month")]
+    #[test_case(2, 75000000, 1000000000, 0, 500000000; "2 months")]
+    #[test_case(11, 300000000, 1000000000, 0, 500000000; "11 months")]
+    #[test_case(12, 325000000, 1000000000, 400000000, 1000000000; "12 months")]
+    #[test_case(13, 350000000, 1000000000, 450000000, 1000000000; "13 months")]
+    #[test_case(39, 1000000000, 1000000000, 1000000000, 1000000000; "39 months")]
+    fn test_forecast(
+        months_since_vesting_start: usize,
+        community_unlocked: u64,
+        partnership_unlocked: u64,
+        marketing_unlocked: u64,
+        liquidity_unlocked: u64,
+    ) {
+        let forecast = forecast(INITIAL_BALANCES, START_TIMESTAMP, 48, 0).unwrap();
+        let month = forecast[months_since_vesting_start];
+
+        assert_eq!(month.community_unlocked, community_unlocked);
+        assert_eq!(month.partnership_unlocked, partnership_unlocked);
+        assert_eq!(month.marketing_unlocked, marketing_unlocked);
+        assert_eq!(month.liquidity_unlocked, liquidity_unlocked);
+        assert_eq!(
+            month.total_unlocked,
+            community_unlocked + partnership_unlocked + marketing_unlocked + liquidity_unlocked
+        );
+    }
+
+    #[test_case(0, 0; "vesting start")]
+    #[test_case(2, 0; "last month of the delay")]
+    #[test_case(3, 25000000; "first unlock")]
+    #[test_case(42, 1000000000; "full vesting")]
+    fn test_forecast_with_community_unlock_delay(
+        months_since_vesting_start: usize,
+        community_unlocked: u64,
+    ) {
+        let forecast = forecast(INITIAL_BALANCES, START_TIMESTAMP, 48, 3).unwrap();
+        let month = forecast[months_since_vesting_start];
+
+        assert_eq!(month.community_unlocked, community_unlocked);
+        assert_eq!(
+            month.total_unlocked,
+            community_unlocked
+                + month.partnership_unlocked
+                + month.marketing_unlocked
+                + month.liquidity_unlocked
+        );
+    }
+
+    #[test_case(0, 2021, 5; "vesting start")]
+    #[test_case(7, 2021, 12; "december")]
+    #[test_case(8, 2022, 1; "next year")]
+    #[test_case(47, 2025, 4; "last month")]
+    fn test_forecast_dates(months_since_vesting_start: usize, year: i64, month: u8) {
+        let forecast = forecast(INITIAL_BALANCES, START_TIMESTAMP, 48, 0).unwrap();
+
+        assert_eq!(forecast.len(), 48);
+        assert_eq!(forecast[months_since_vesting_start].year, year);
+        assert_eq!(forecast[months_since_vesting_start].month, month);
+    }
+
+    #[test]
+    fn test_forecast_csv_row() {
+        let forecast = forecast(INITIAL_BALANCES, START_TIMESTAMP, 1, 0).unwrap();
+
+        assert_eq!(
+            forecast[0].to_csv_row(),
+            "0,2021,5,25000000,0,0,500000000,525000000"
+        );
+    }
+}
@@ -2,24 +2,32 @@
 
 pub mod account;
 pub mod context;
+#[cfg(not(target_os = "solana"))]
+pub mod decode;
 pub mod error_codes;
+pub mod event;
+#[cfg(not(target_os = "solana"))]
+pub mod events;
+#[cfg(not(target_os = "solana"))]
+pub mod filter;
+#[cfg(not(target_os = "solana"))]
+pub mod forecast;
 pub mod utils;
 
 use anchor_lang::{
     error,
     prelude::{
-        access_control, account, borsh, declare_id, require, require_eq, require_gte, Account,
-        AccountDeserialize, AccountInfo, AccountSerialize, Accounts, AccountsExit,
-        AnchorDeserialize, AnchorSerialize, Context, CpiContext, Key, Program, Rent, Result,
-        Signer, System, ToAccountInfo,
+        access_control, account, borsh, declare_id, emit, require, require_gte, Account,
+        AccountDeserialize, AccountInfo, AccountLoader, AccountSerialize, Accounts, AccountsExit,
+        AnchorDeserialize, AnchorSerialize, Context, CpiContext, InitSpace, Key, Program, Rent,
+        Result, Signer, System, ToAccountInfo,
     },
     program,
-    solana_program::{
-        clock, program::invoke_signed, pubkey::Pubkey, sysvar::Sysvar as SolanaSysvar,
-    },
+    solana_program::{clock, pubkey::Pubkey, sysvar::Sysvar as SolanaSysvar},
 };
-use anchor_spl::token::{self, Burn};
+use anchor_spl::token::{self, spl_token::instruction::AuthorityType, SetAuthority, Transfer};
 
+use account::VestingSchedule;
 use context::*;
 
 /// set seeds for pda accounts
@@ -27,88 +35,293 @@ pub const MINT_SEED: &str = "mint";
 pub const PROGRAM_ACCOUNT_SEED: &str = "program_account";
 pub const BURNING_ACCOUNT_SEED: &str = "burning_account";
 
-const CONTRACT_STATE_SEED: &str = "contract_state";
-const VESTING_STATE_SEED: &str = "vesting_state";
+pub const CONTRACT_STATE_SEED: &str = "contract_state";
+pub const VESTING_STATE_SEED: &str = "vesting_state";
+
+pub const COMMUNITY_ACCOUNT_SEED: &str = "community_account";
+pub const PARTNERSHIP_ACCOUNT_SEED: &str = "partnership_account";
+pub const MARKETING_ACCOUNT_SEED: &str = "marketing_account";
+pub const LIQUIDITY_ACCOUNT_SEED: &str = "liquidity_account";
+
+pub const ATTESTATION_SEED: &str = "attestation";
+
+pub const STAKING_STATE_SEED: &str = "staking_state";
+pub const STAKE_ACCOUNT_SEED: &str = "stake_account";
+pub const STAKE_VAULT_SEED: &str = "stake_vault";
+pub const REWARD_VAULT_SEED: &str = "reward_vault";
+
+pub const OTC_DEAL_SEED: &str = "otc_deal";
+pub const OTC_VAULT_SEED: &str = "otc_vault";
+
+pub const DONOR_ACCOUNT_SEED: &str = "donor_account";
+
+pub const LABELS_SEED: &str = "labels";
+
+pub const WITHDRAWAL_INTENT_SEED: &str = "withdrawal_intent";
+
+pub const WITHDRAWAL_RECEIPT_SEED: &str = "receipt";
+
+pub const WALLET_DELEGATION_SEED: &str = "wallet_delegation";
+
+pub const IMPORT_PROGRESS_SEED: &str = "import_progress";
+
+pub const BURN_HISTORY_SEED: &str = "burn_history";
+
+/// number of supported migration sources, see `MigrationSource`
+pub const MIGRATION_SOURCES_COUNT: usize = 2;
+
+/// maximal length in bytes of a label of a program token account
+pub const LABEL_LENGTH: usize = 32;
+
+/// number of program token accounts with a label, see `LabeledAccountKind`
+pub const LABELED_ACCOUNTS_COUNT: usize = 6;
+
+/// number of wallets affected by the vesting mechanism, see `WalletKind`
+pub const VESTED_WALLETS_COUNT: usize = 4;
+
+/// maximal number of keepers allowed to run the automation instructions, see `ContractState::keepers`
+pub const MAX_KEEPERS: usize = 8;
+
+/// maximal number of entries of a staged import, limited by the size of the bitmap of transferred entries, see `ImportProgress`
+pub const MAX_STAGED_IMPORT_ENTRIES: usize = 256;
+
+/// amount to withdraw from a vested wallet meaning all tokens currently available to withdraw, see `withdraw_tokens_from_community_wallet`
+pub const WITHDRAW_ALL_UNLOCKED: u64 = u64::MAX;
+
+/// minimal number of seconds between two proof-of-reserve attestations
+const ATTESTATION_INTERVAL: i64 = 60 * 60 * 24;
+
+/// number of seconds after which a withdrawal intent can be closed
+const WITHDRAWAL_INTENT_RETENTION_PERIOD: i64 = 30 * 60 * 60 * 24;
+
+/// maximal number of decimals of the mint, see `initialize_state`
+pub const MAX_MINT_DECIMALS: u8 = 12;
+
+/// number of decimals of the mint created before the decimals were configurable, it is set in the migrated contract states
+pub const LEGACY_MINT_DECIMALS: u8 = 9;
+
+/// maximal number of seconds after the vesting start during which the withdrawals can be blocked
+pub const MAX_WITHDRAWAL_GRACE_PERIOD: i64 = 90 * 60 * 60 * 24;
+
+/// percentage of the burning account balance burned every month
+const BURN_PERCENTAGE: u8 = 5;
+
+/// the default number of days of the primary window starting on the 1st day of the month in which the monthly burn can be executed
+pub const DEFAULT_BURN_WINDOW_DAYS: u8 = 5;
+
+/// the maximal number of days of the primary burn window, every month has at least 28 days
+pub const MAX_BURN_WINDOW_DAYS: u8 = 28;
+
+/// the maximal reward of the burn caller in basis points of the burned amount
+pub const MAX_BURN_CALLER_REWARD_BPS: u16 = 50;
+
+/// the default first day of the secondary window in which the monthly burn missed in the primary window can be executed
+pub const DEFAULT_LATE_BURN_WINDOW_FIRST_DAY: u8 = 15;
+
+/// the default last day of the secondary window in which the monthly burn missed in the primary window can be executed
+pub const DEFAULT_LATE_BURN_WINDOW_LAST_DAY: u8 = 20;
+
+/// the latest day of the month on which the secondary burn window can end, every month has at least 28 days
+pub const MAX_LATE_BURN_WINDOW_LAST_DAY: u8 = 28;
+
+/// the default burn rate in basis points set in new and migrated contract states, the burned amount is calculated in fixed-point with this precision
+pub const DEFAULT_BURN_RATE_BPS: u16 = BURN_PERCENTAGE as u16 * 100;
 
-const COMMUNITY_ACCOUNT_SEED: &str = "community_account";
-const PARTNERSHIP_ACCOUNT_SEED: &str = "partnership_account";
-const MARKETING_ACCOUNT_SEED: &str = "marketing_account";
-const LIQUIDITY_ACCOUNT_SEED: &str = "liquidity_account";
+/// denominator of the burn rate and of the burn remainder carried over between the burns
+pub const BURN_RATE_DENOMINATOR: u64 = 10_000;
+
+/// number of the latest burns kept by the burn history, the oldest record is overwritten by the next burn once it is full
+#[cfg(not(test))]
+pub const BURN_HISTORY_CAPACITY: usize = 48;
+
+/// number of the latest burns kept by the burn history, reduced in tests so the overwriting of the oldest record can be covered
+#[cfg(test)]
+pub const BURN_HISTORY_CAPACITY: usize = 2;
+
+/// maximal number of missed monthly burns applied by a single catch-up burn
+pub const MAX_CATCH_UP_BURN_MONTHS: u64 = 24;
+
+/// maximal number of monthly burns applied by the supply projection
+pub const MAX_PROJECTED_MONTHS: u8 = 60;
+
+/// scale of the staking reward per token accumulator
+pub const REWARD_PER_TOKEN_PRECISION: u128 = 1_000_000_000_000;
+
+/// version of the `TokenInfo` layout returned by the get_token_info instruction
+pub const TOKEN_INFO_VERSION: u8 = 1;
 
 declare_id!("CeFVa5iijJASnRmMCvrHep8wVYRZ3XxAmgXArNJhpjmx");
 
 /// This program is used to mint, burn and transfer tokens. It includes also a vesting mechanism.
 #[program]
 pub mod leancoin {
+    #[cfg(feature = "metadata")]
+    use anchor_lang::solana_program::program::invoke_signed;
+    #[cfg(feature = "metadata")]
     use mpl_token_metadata::{
         instruction::{create_metadata_accounts_v3, update_metadata_accounts_v2},
         state::DataV2,
     };
 
+    #[cfg(feature = "metadata")]
+    use crate::utils::{valid_metadata_pda, valid_token_metadata};
+
+    use anchor_lang::{prelude::require_keys_eq, Space};
+
+    use crate::account::{
+        ContractState, VestingSchedule, VestingState, WalletVesting, CONTRACT_STATE_VERSION,
+    };
     use crate::error_codes::LeancoinError;
+    use crate::event::{
+        AuthorityChanged, BurnDonation, BurningAccountFunded, ImportCompleted, Initialized,
+        KeeperAdded, KeeperRemoved, LiquidityDripExecuted, PauseStateChanged, ReservesAttested,
+        RoleChanged, VestingRevoked, VestingToppedUp, WalletsRebalanced,
+    };
     use crate::utils::{
-        burn_tokens, calculate_month_difference, calculate_unlocked_amount_community_wallet,
-        calculate_unlocked_amount_liquidity_wallet, calculate_unlocked_amount_marketing_wallet,
-        calculate_unlocked_amount_partnership_wallet,
-        ethereum_token_state_mapping_not_performed_yet, mint_tokens, parse_timestamp,
-        transfer_tokens, valid_owner, valid_signer, withdraw_vested_tokens,
+        approve_delegate, burn_tokens, calculate_burn_caller_reward,
+        calculate_catch_up_burn_amount, calculate_locked_amount, calculate_month_difference,
+        calculate_monthly_burn_amount, calculate_reserves_attestation_hash, calculate_reward_debt,
+        calculate_reward_per_token_increase, calculate_staking_rewards_amount,
+        calculate_supply_projection, calculate_wallet_unlocked_amount,
+        calculate_wallet_vesting_status, close_token_account, configuration_not_locked,
+        create_program_address, default_metadata_program_id, encode_label,
+        ethereum_token_state_mapping_already_performed,
+        ethereum_token_state_mapping_not_performed_yet, execute_burn, forecast_unlocked_amounts,
+        import_not_performed_yet, import_performed, late_burn, mint_authority_not_revoked,
+        mint_tokens, months_since_last_burn, months_since_vesting_start, next_sequence, not_paused,
+        parse_timestamp, previous_month, program_token_account_nonce, read_outdated_contract_state,
+        read_outdated_vesting_state, reconcile_wallet_delegation, record_imported_balance,
+        record_withdrawal, remaining_unlocked_amount, revoke_delegate,
+        scale_amount_to_mint_decimals, top_level_invocation, transfer_rent_shortfall,
+        transfer_tokens, transfer_tokens_from_otc_vault, update_stake_rewards,
+        valid_burn_caller_reward, valid_burn_rate, valid_burn_window, valid_burner, valid_decimals,
+        valid_keeper, valid_late_burn_window, valid_linear_daily_unlock, valid_owner, valid_role,
+        valid_signer, valid_vesting_schedules, valid_withdrawal_grace_period, valid_withdrawer,
+        vested_wallet_balance, vested_wallet_not_finalized, vested_wallet_not_revoked,
+        vested_wallets_funded, wallets_initialized, wallets_not_initialized_yet,
+        withdraw_all_unlocked_tokens, withdraw_from_vested_wallet, withdraw_vested_tokens,
+        withdrawal_grace_period_passed,
     };
 
     use super::*;
 
-    /// Initializes accounts and set states. It is the first function that must be called and it can be called only once.
+    /// Initializes the contract state and the vesting state together with the mint, program and burning accounts.
+    /// It is the first function that must be called and it can be called only once.
+    /// The initialization is completed by `initialize_wallets` function which must be called afterwards.
+    /// The nonces of the created accounts are the canonical bumps found by Anchor, they are not passed by the caller.
+    /// Breaking change in version 0.2.0: the instruction no longer takes the nonces as arguments and takes the number of decimals of the mint.
     ///
     /// ### Arguments
     ///
-    /// * `contract_state_nonce` - nonce for contract state account
-    /// * `vesting_state_nonce` - nonce for vesting state account
-    /// * `mint_nonce` - nonce for mint account
-    /// * `program_account_nonce` - nonce for program account
-    /// * `burning_account_nonce` - nonce for burning account
-    /// * `community_wallet_nonce` - nonce for community wallet account
-    /// * `liquidity_wallet_nonce` - nonce for liquidity wallet account
-    /// * `marketing_wallet_nonce` - nonce for marketing wallet account
-    /// * `partnership_wallet_nonce` - nonce for partnership wallet account
-    pub fn initialize(
-        ctx: Context<InitializeContext>,
-        contract_state_nonce: u8,
-        vesting_state_nonce: u8,
-        mint_nonce: u8,
-        program_account_nonce: u8,
-        burning_account_nonce: u8,
-        community_wallet_nonce: u8,
-        liquidity_wallet_nonce: u8,
-        marketing_wallet_nonce: u8,
-        partnership_wallet_nonce: u8,
-    ) -> Result<()> {
+    /// * `decimals` - the number of decimals of the mint, at most `MAX_MINT_DECIMALS`
+    #[access_control(valid_decimals(decimals))]
+    pub fn initialize_state(ctx: Context<InitializeStateContext>, decimals: u8) -> Result<()> {
+        let contract_state_nonce = *ctx.bumps.get("contract_state").unwrap();
+        let vesting_state_nonce = *ctx.bumps.get("vesting_state").unwrap();
+        let mint_nonce = *ctx.bumps.get("mint").unwrap();
+        let program_account_nonce = *ctx.bumps.get("program_account").unwrap();
+        let burning_account_nonce = *ctx.bumps.get("burning_account").unwrap();
+
         let contract_state = &mut ctx.accounts.contract_state;
-        let vesting_state = &mut ctx.accounts.vesting_state;
+        let sequence = next_sequence(contract_state)?;
+        let mut vesting_state = ctx.accounts.vesting_state.load_init()?;
 
+        contract_state.version = CONTRACT_STATE_VERSION;
         contract_state.authority = ctx.accounts.signer.key();
         contract_state.contract_state_nonce = contract_state_nonce;
         contract_state.mint_nonce = mint_nonce;
-        contract_state.import_ethereum_token_state_already_performed = false;
+        contract_state.performed_imports = 0;
+        contract_state.imported_minted = [0; MIGRATION_SOURCES_COUNT];
+        contract_state.imported_burned = [0; MIGRATION_SOURCES_COUNT];
+        contract_state.max_supply = 0;
+        contract_state.wallets_initialized = false;
         contract_state.program_account_nonce = program_account_nonce;
         contract_state.burning_account_nonce = burning_account_nonce;
         contract_state.last_burning_month = 0;
         contract_state.last_burning_year = 0;
+        contract_state.cpi_allowed = false;
+        contract_state.staking_enabled = false;
+        contract_state.total_donated = 0;
+        contract_state.metadata_program_id = default_metadata_program_id();
+        contract_state.configuration_locked = false;
+        contract_state.late_burn_window_first_day = DEFAULT_LATE_BURN_WINDOW_FIRST_DAY;
+        contract_state.late_burn_window_last_day = DEFAULT_LATE_BURN_WINDOW_LAST_DAY;
+        contract_state.decimals = decimals;
+        contract_state.mint = ctx.accounts.mint.key();
+        contract_state.burn_rate_bps = DEFAULT_BURN_RATE_BPS;
+        contract_state.burn_window_days = DEFAULT_BURN_WINDOW_DAYS;
+        contract_state.burn_caller_reward_bps = 0;
 
         vesting_state.start_timestamp = 0;
-        vesting_state.initial_community_wallet_balance = 0;
-        vesting_state.initial_partnership_wallet_balance = 0;
-        vesting_state.initial_marketing_wallet_balance = 0;
-        vesting_state.initial_liquidity_wallet_balance = 0;
+        vesting_state.wallets =
+            [WalletVesting::new(0, 0, 0, Pubkey::default()); VESTED_WALLETS_COUNT];
+
+        vesting_state.liquidity_drip_recipient = Pubkey::default();
+        vesting_state.liquidity_drip_amount_per_month = 0;
+        vesting_state.last_liquidity_drip_month = 0;
+        vesting_state.last_liquidity_drip_year = 0;
 
-        vesting_state.already_withdrawn_community_wallet_amount = 0;
-        vesting_state.already_withdrawn_partnership_wallet_amount = 0;
-        vesting_state.already_withdrawn_marketing_wallet_amount = 0;
-        vesting_state.already_withdrawn_liquidity_wallet_amount = 0;
+        vesting_state.finalized_wallets = 0;
+        vesting_state.schedules = VestingSchedule::defaults();
 
         vesting_state.vesting_state_nonce = vesting_state_nonce;
-        vesting_state.community_wallet_nonce = community_wallet_nonce;
-        vesting_state.liquidity_wallet_nonce = liquidity_wallet_nonce;
-        vesting_state.marketing_wallet_nonce = marketing_wallet_nonce;
-        vesting_state.partnership_wallet_nonce = partnership_wallet_nonce;
+
+        emit!(Initialized {
+            authority: contract_state.authority,
+            mint: contract_state.mint,
+            decimals,
+            sequence,
+        });
+
+        Ok(())
+    }
+
+    /// Initializes the wallets affected by vesting mechanism: community, partnership, marketing and liquidity wallet, together with their vesting schedules.
+    /// It also initializes the labels of the program token accounts with the default labels.
+    /// It is the second function that must be called, after `initialize_state`, and it can be called only once.
+    /// The nonces of the wallet accounts are the canonical bumps found by Anchor, they are not passed by the caller.
+    /// Breaking change in version 0.2.0: the instruction no longer takes the nonces as arguments.
+    ///
+    /// ### Arguments
+    ///
+    /// * `vesting_schedules` - the vesting schedules of the vested wallets ordered like `WalletKind`, the default ones are used if not given
+    /// * `linear_daily_unlock` - whether the monthly unlock of each vested wallet ordered like `WalletKind` is interpolated over the days of the month,
+    ///   only the community and marketing wallets support it, all wallets unlock in monthly steps if not given
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) wallets_not_initialized_yet(&ctx.accounts.contract_state) valid_vesting_schedules(&vesting_schedules) valid_linear_daily_unlock(&linear_daily_unlock))]
+    pub fn initialize_wallets(
+        ctx: Context<InitializeWalletsContext>,
+        vesting_schedules: Option<[VestingSchedule; VESTED_WALLETS_COUNT]>,
+        linear_daily_unlock: Option<[bool; VESTED_WALLETS_COUNT]>,
+    ) -> Result<()> {
+        let contract_state = &mut ctx.accounts.contract_state;
+        next_sequence(contract_state)?;
+        let mut vesting_state = ctx.accounts.vesting_state.load_mut()?;
+
+        vesting_state.wallet_mut(WalletKind::Community).nonce =
+            *ctx.bumps.get("community_account").unwrap();
+        vesting_state.wallet_mut(WalletKind::Liquidity).nonce =
+            *ctx.bumps.get("liquidity_account").unwrap();
+        vesting_state.wallet_mut(WalletKind::Marketing).nonce =
+            *ctx.bumps.get("marketing_account").unwrap();
+        vesting_state.wallet_mut(WalletKind::Partnership).nonce =
+            *ctx.bumps.get("partnership_account").unwrap();
+        vesting_state.schedules = vesting_schedules.unwrap_or_else(VestingSchedule::defaults);
+        if let Some(linear_daily_unlock) = linear_daily_unlock {
+            for wallet_kind in WalletKind::ALL {
+                if linear_daily_unlock[wallet_kind.index()] {
+                    vesting_state.linear_daily_unlock_wallets |= wallet_kind.mask();
+                }
+            }
+        }
+
+        contract_state.wallets_initialized = true;
+
+        let labels = &mut ctx.accounts.labels;
+        labels.labels_nonce = *ctx.bumps.get("labels").unwrap();
+        for account_kind in LabeledAccountKind::ALL {
+            labels.labels[account_kind.index()] =
+                encode_label(account_kind.default_label().as_bytes())?;
+        }
 
         Ok(())
     }
@@ -117,37 +330,111 @@ pub mod leancoin {
     /// Additionally, it sets initial data related to burning and vesting like date (year and month) of the initial burning or initial state of accounts participating in vesting.
     /// The data is used later by burning and vesting functions.
     ///
-    /// It is the second function that should be called and it can be called only once.
+    /// It should be called after both `initialize_state` and `initialize_wallets` and it can be called only once.
+    /// It is equivalent to `import_external_token_state` with `MigrationSource::Ethereum`, see it for the heap requirements of large batches.
     ///
     /// ### Arguments
     ///
     /// * `account_info_from_ethereum` - a set of accounts reflecting those used on Ethereum; Leancoin tokens are transferred to these accounts
     /// * `amount_token_to_mint` - amount of tokens to mint to Program Account
     /// * `amount_token_to_burn` - amount of tokens to burn (also applied to Program Account)
-    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) ethereum_token_state_mapping_not_performed_yet(&ctx.accounts.contract_state))]
+    /// * `source_decimals` - number of decimals used by all the amounts above (18 for the Ethereum token); the amounts are scaled to mint decimals
+    /// * `community_unlock_delay_months` - number of months before the first unlock of the community wallet, 0 unlocks the first tranche immediately
+    /// * `withdrawal_grace_period_seconds` - number of seconds after the import during which no vested wallet can withdraw tokens, at most 90 days
     pub fn import_ethereum_token_state<'info>(
-        ctx: Context<'_, '_, '_, 'info, ImportEthereumTokenStateContext<'info>>,
+        ctx: Context<'_, '_, '_, 'info, ImportTokenStateContext<'info>>,
         account_info_from_ethereum: Vec<AccountInfoFromEthereum>,
-        amount_token_to_mint: u64,
-        amount_token_to_burn: u64,
+        amount_token_to_mint: u128,
+        amount_token_to_burn: u128,
+        source_decimals: u8,
+        community_unlock_delay_months: u8,
+        withdrawal_grace_period_seconds: i64,
+    ) -> Result<()> {
+        import_external_token_state(
+            ctx,
+            MigrationSource::Ethereum,
+            account_info_from_ethereum,
+            amount_token_to_mint,
+            amount_token_to_burn,
+            source_decimals,
+            community_unlock_delay_months,
+            withdrawal_grace_period_seconds,
+        )
+    }
+
+    /// Imports token state from an external migration source. It mints, burns and transfer tokens based on the passed parameters that should specify the current token state on the source chain.
+    /// The amounts minted and burned are recorded per source and each source can be imported only once.
+    ///
+    /// Only the first import sets the initial data related to vesting: the vesting start, the community unlock delay,
+    /// the withdrawal grace period and the initial balances of the vested wallets.
+    /// The following imports cannot transfer tokens to the vested wallets.
+    ///
+    /// It should be called after both `initialize_state` and `initialize_wallets`.
+    ///
+    /// ### Arguments
+    ///
+    /// * `source` - the migration source of the token state
+    /// * `entries` - a set of accounts reflecting those used on the source chain; Leancoin tokens are transferred to these accounts
+    /// * `amount_token_to_mint` - amount of tokens to mint to Program Account
+    /// * `amount_token_to_burn` - amount of tokens to burn (also applied to Program Account)
+    /// * `source_decimals` - number of decimals used by all the amounts above; the amounts are scaled to mint decimals
+    /// * `community_unlock_delay_months` - number of months before the first unlock of the community wallet, ignored by the following imports
+    /// * `withdrawal_grace_period_seconds` - number of seconds after the vesting start during which no vested wallet can withdraw tokens,
+    ///   at most 90 days, ignored by the following imports
+    ///
+    /// ### Heap
+    ///
+    /// Processing the entries does not allocate, but the deserialized entries themselves live on the heap.
+    /// Clients importing large batches (around 25 entries) should add `ComputeBudgetInstruction::request_heap_frame` to the transaction
+    /// so the batch does not hit the default 32KB heap.
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) wallets_initialized(&ctx.accounts.contract_state) import_not_performed_yet(&ctx.accounts.contract_state, source) mint_authority_not_revoked(&ctx.accounts.contract_state) valid_withdrawal_grace_period(withdrawal_grace_period_seconds))]
+    pub fn import_external_token_state<'info>(
+        ctx: Context<'_, '_, '_, 'info, ImportTokenStateContext<'info>>,
+        source: MigrationSource,
+        entries: Vec<AccountInfoFromEthereum>,
+        amount_token_to_mint: u128,
+        amount_token_to_burn: u128,
+        source_decimals: u8,
+        community_unlock_delay_months: u8,
+        withdrawal_grace_period_seconds: i64,
     ) -> Result<()> {
         let contract_state = &mut ctx.accounts.contract_state;
-        let vesting_state = &mut ctx.accounts.vesting_state;
+        let sequence = next_sequence(contract_state)?;
+        let mut vesting_state = ctx.accounts.vesting_state.load_mut()?;
 
+        let first_import = contract_state.performed_imports == 0;
         let mint_nonce = contract_state.mint_nonce;
         let program_account_nonce = contract_state.program_account_nonce;
+        let mint_decimals = ctx.accounts.mint.decimals;
         let timestamp = clock::Clock::get()?.unix_timestamp;
 
-        vesting_state.start_timestamp = timestamp;
+        let amount_token_to_mint =
+            scale_amount_to_mint_decimals(amount_token_to_mint, source_decimals, mint_decimals)?;
+        let amount_token_to_burn =
+            scale_amount_to_mint_decimals(amount_token_to_burn, source_decimals, mint_decimals)?;
+
+        // the import is the only instruction that raises the maximal supply, by the amount it mints
+        let max_supply = contract_state
+            .max_supply
+            .checked_add(amount_token_to_mint)
+            .ok_or(LeancoinError::MaxSupplyExceeded)?;
+
+        if first_import {
+            vesting_state.start_timestamp = timestamp;
+            vesting_state.community_unlock_delay_months = community_unlock_delay_months;
+            contract_state.withdrawal_grace_period_seconds = withdrawal_grace_period_seconds;
+        }
 
         mint_tokens(
-            ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.mint,
             ctx.accounts.program_account.to_account_info(),
             ctx.accounts.mint.to_account_info(),
             ctx.accounts.token_program.to_account_info(),
             mint_nonce,
+            max_supply,
             amount_token_to_mint,
         )?;
+        contract_state.max_supply = max_supply;
 
         burn_tokens(
             ctx.accounts.mint.to_account_info(),
@@ -158,27 +445,47 @@ pub mod leancoin {
             amount_token_to_burn,
         )?;
 
-        let mut wallet_names = vec![];
+        let mut accounts_funded: u32 = 0;
 
-        for account in ctx.remaining_accounts.iter() {
-            let matching_accounts = account_info_from_ethereum
+        // the loop does not allocate: entries are matched by iterating over them and wallet names are compared as byte slices,
+        // a wallet name is duplicated if an entry with the same name belongs to one of the already funded remaining accounts
+        for (index, account) in ctx.remaining_accounts.iter().enumerate() {
+            let mut matching_accounts = entries
                 .iter()
-                .filter(|account_info| account_info.account_public_key == account.key())
-                .collect::<Vec<&AccountInfoFromEthereum>>();
+                .filter(|account_info| account_info.account_public_key == *account.key);
+
+            let account_info = matching_accounts
+                .next()
+                .ok_or(LeancoinError::MismatchBetweenRemainingAccountsAndUserInfo)?;
 
             require!(
-                matching_accounts.len() <= 1,
+                matching_accounts.next().is_none(),
                 LeancoinError::NonUniqueAccountInfo
             );
 
-            let account_info = matching_accounts
-                .first()
-                .ok_or(LeancoinError::MismatchBetweenRemainingAccountsAndUserInfo)?;
+            let wallet_name = account_info.wallet_name.as_bytes();
+            let duplicated_wallet_name = entries.iter().any(|other_account_info| {
+                other_account_info.wallet_name.as_bytes() == wallet_name
+                    && ctx.remaining_accounts[..index]
+                        .iter()
+                        .any(|funded_account| {
+                            *funded_account.key == other_account_info.account_public_key
+                        })
+            });
+            require!(!duplicated_wallet_name, LeancoinError::DuplicatedWalletName);
 
-            if wallet_names.contains(&account_info.wallet_name) {
-                return Err(LeancoinError::DuplicatedWalletName.into());
-            }
-            wallet_names.push(account_info.wallet_name.clone());
+            let account_balance = scale_amount_to_mint_decimals(
+                account_info.account_balance,
+                source_decimals,
+                mint_decimals,
+            )?;
+
+            record_imported_balance(
+                &mut vesting_state,
+                &account_info.wallet_name,
+                account_balance,
+                first_import,
+            )?;
 
             transfer_tokens(
                 ctx.accounts.program_account.to_account_info(),
@@ -186,731 +493,15504 @@ pub mod leancoin {
                 ctx.accounts.token_program.to_account_info(),
                 PROGRAM_ACCOUNT_SEED,
                 program_account_nonce,
-                account_info.account_balance,
+                account_balance,
             )?;
-
-            match account_info.wallet_name.as_str() {
-                "community" => {
-                    vesting_state.initial_community_wallet_balance = account_info.account_balance
-                }
-                "partnership" => {
-                    vesting_state.initial_partnership_wallet_balance = account_info.account_balance
-                }
-                "marketing" => {
-                    vesting_state.initial_marketing_wallet_balance = account_info.account_balance
-                }
-                "liquidity" => {
-                    vesting_state.initial_liquidity_wallet_balance = account_info.account_balance
-                }
-                _ => {}
-            }
+            accounts_funded += 1;
         }
 
         require!(
             ctx.accounts.program_account.amount == 0,
             LeancoinError::ProgramAccountBalanceIsNotZero
         );
-        require!(
-            vesting_state.initial_community_wallet_balance != 0,
-            LeancoinError::CommunityWalletBalanceIsZero
-        );
-        require!(
-            vesting_state.initial_partnership_wallet_balance != 0,
-            LeancoinError::PartnershipWalletBalanceIsZero
-        );
-        require!(
-            vesting_state.initial_marketing_wallet_balance != 0,
-            LeancoinError::MarketingWalletBalanceIsZero
-        );
-        require!(
-            vesting_state.initial_liquidity_wallet_balance != 0,
-            LeancoinError::LiquidityWalletBalanceIsZero
-        );
+        if first_import {
+            vested_wallets_funded(&vesting_state)?;
+        }
 
-        contract_state.import_ethereum_token_state_already_performed = true;
+        contract_state.performed_imports |= source.mask();
+        contract_state.imported_minted[source.index()] = amount_token_to_mint;
+        contract_state.imported_burned[source.index()] = amount_token_to_burn;
+
+        emit!(ImportCompleted {
+            source,
+            total_minted: amount_token_to_mint,
+            total_burned: amount_token_to_burn,
+            accounts_funded,
+            community: vesting_state.wallet(WalletKind::Community).initial_balance,
+            partnership: vesting_state
+                .wallet(WalletKind::Partnership)
+                .initial_balance,
+            marketing: vesting_state.wallet(WalletKind::Marketing).initial_balance,
+            liquidity: vesting_state.wallet(WalletKind::Liquidity).initial_balance,
+            timestamp,
+            sequence,
+            swap: vesting_state.initial_swap_wallet_balance,
+        });
 
         Ok(())
     }
 
-    /// Burns 5% of all the tokens currently held by the burning account.
-    /// This function can be called only once per month and only between the 1st and the 5th day of the month.
-    pub fn burn(ctx: Context<BurnContext>) -> Result<()> {
-        let contract_state = &mut ctx.accounts.contract_state;
-        let timestamp = clock::Clock::get()?.unix_timestamp;
-        let now = parse_timestamp(timestamp)?;
-
-        require!(now.days <= 5, LeancoinError::TooLateToBurnTokens);
+    /// Starts a staged import of the token state from an external migration source.
+    /// It is the counterpart of `import_external_token_state` for the token states with too many entries to be transferred in a single transaction:
+    /// it mints and burns the tokens and sets the initial data related to vesting, the tokens are then transferred to the entries
+    /// by `import_batch` and the import is completed by `finalize_import`.
+    ///
+    /// The import from the source is recorded as performed immediately, so it cannot be started twice.
+    /// Only one staged import can be in progress at a time.
+    ///
+    /// ### Arguments
+    ///
+    /// * `source` - the migration source of the token state
+    /// * `entries_count` - number of entries of the import, at most `MAX_STAGED_IMPORT_ENTRIES`
+    /// * `amount_token_to_mint` - amount of tokens to mint to Program Account
+    /// * `amount_token_to_burn` - amount of tokens to burn (also applied to Program Account)
+    /// * `source_decimals` - number of decimals used by all the amounts of the import; the amounts are scaled to mint decimals
+    /// * `community_unlock_delay_months` - number of months before the first unlock of the community wallet, ignored by the following imports
+    /// * `withdrawal_grace_period_seconds` - number of seconds after the vesting start during which no vested wallet can withdraw tokens,
+    ///   at most 90 days, ignored by the following imports
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) wallets_initialized(&ctx.accounts.contract_state) import_not_performed_yet(&ctx.accounts.contract_state, source) mint_authority_not_revoked(&ctx.accounts.contract_state) valid_withdrawal_grace_period(withdrawal_grace_period_seconds))]
+    pub fn begin_import(
+        ctx: Context<BeginImportContext>,
+        source: MigrationSource,
+        entries_count: u16,
+        amount_token_to_mint: u128,
+        amount_token_to_burn: u128,
+        source_decimals: u8,
+        community_unlock_delay_months: u8,
+        withdrawal_grace_period_seconds: i64,
+    ) -> Result<()> {
         require!(
-            contract_state.last_burning_month != now.month
-                || contract_state.last_burning_year != now.year,
-            LeancoinError::TokensAlreadyBurned
+            usize::from(entries_count) <= MAX_STAGED_IMPORT_ENTRIES,
+            LeancoinError::TooManyImportEntries
         );
 
-        let seeds = &[
-            BURNING_ACCOUNT_SEED.as_bytes(),
-            &[contract_state.burning_account_nonce],
-        ];
-        let signer_seeds = &[&seeds[..]];
+        let contract_state = &mut ctx.accounts.contract_state;
+        next_sequence(contract_state)?;
+        let mut vesting_state = ctx.accounts.vesting_state.load_mut()?;
 
-        let cpi_accounts = Burn {
-            mint: ctx.accounts.mint.to_account_info(),
-            from: ctx.accounts.burning_account.to_account_info(),
-            authority: ctx.accounts.burning_account.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer_seeds);
+        let first_import = contract_state.performed_imports == 0;
+        let program_account_nonce = contract_state.program_account_nonce;
+        let mint_decimals = ctx.accounts.mint.decimals;
 
-        let amount = token::accessor::amount(&ctx.accounts.burning_account.to_account_info())? / 20;
+        let amount_token_to_mint =
+            scale_amount_to_mint_decimals(amount_token_to_mint, source_decimals, mint_decimals)?;
+        let amount_token_to_burn =
+            scale_amount_to_mint_decimals(amount_token_to_burn, source_decimals, mint_decimals)?;
 
-        token::burn(cpi_ctx, amount)?;
+        let max_supply = contract_state
+            .max_supply
+            .checked_add(amount_token_to_mint)
+            .ok_or(LeancoinError::MaxSupplyExceeded)?;
 
-        contract_state.last_burning_month = now.month;
-        contract_state.last_burning_year = now.year;
+        if first_import {
+            vesting_state.start_timestamp = clock::Clock::get()?.unix_timestamp;
+            vesting_state.community_unlock_delay_months = community_unlock_delay_months;
+            contract_state.withdrawal_grace_period_seconds = withdrawal_grace_period_seconds;
+        }
+
+        mint_tokens(
+            &ctx.accounts.mint,
+            ctx.accounts.program_account.to_account_info(),
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            contract_state.mint_nonce,
+            max_supply,
+            amount_token_to_mint,
+        )?;
+        contract_state.max_supply = max_supply;
+
+        burn_tokens(
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.program_account.to_account_info(),
+            ctx.accounts.program_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            program_account_nonce,
+            amount_token_to_burn,
+        )?;
+
+        contract_state.performed_imports |= source.mask();
+        contract_state.imported_minted[source.index()] = amount_token_to_mint;
+        contract_state.imported_burned[source.index()] = amount_token_to_burn;
+
+        let import_progress = &mut ctx.accounts.import_progress;
+        import_progress.import_progress_nonce = *ctx.bumps.get("import_progress").unwrap();
+        import_progress.source = source;
+        import_progress.source_decimals = source_decimals;
+        import_progress.first_import = first_import;
+        import_progress.entries_count = entries_count;
 
         Ok(())
     }
 
-    /// Withdraws vested tokens from community wallet, if available.
-    /// 2.5% of the initial wallet's balance is unlocked every month.
+    /// Transfers the tokens of a batch of entries of the staged import started by `begin_import`.
+    /// The entries are numbered across the whole import, the batch contains the consecutive entries starting at `first_index`.
+    /// The accounts of the entries are passed as the remaining accounts.
+    ///
+    /// Every transferred entry is marked in the import progress account, so the batch can be safely retried after a failure:
+    /// the entries transferred by an earlier attempt are skipped instead of being funded twice.
+    /// The entries whose accounts are not passed are left for a later batch.
     ///
     /// ### Arguments
     ///
-    /// * `amount_to_withdraw` - amount of tokens to withdraw
+    /// * `first_index` - the index of the first entry of the batch within the import
+    /// * `entries` - the entries of the batch, their balances are expressed with the source decimals passed to `begin_import`
     #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer))]
-    pub fn withdraw_tokens_from_community_wallet<'info>(
-        ctx: Context<'_, '_, '_, 'info, WithdrawTokensFromCommunityWalletContext<'info>>,
-        amount_to_withdraw: u64,
+    pub fn import_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, ImportBatchContext<'info>>,
+        first_index: u16,
+        entries: Vec<AccountInfoFromEthereum>,
     ) -> Result<()> {
-        let vesting_state = &mut ctx.accounts.vesting_state;
-        let months_since_first_vesting = calculate_month_difference(
-            vesting_state.start_timestamp,
-            clock::Clock::get()?.unix_timestamp,
-        )?;
+        let import_progress = &mut ctx.accounts.import_progress;
+        let mut vesting_state = ctx.accounts.vesting_state.load_mut()?;
+        let program_account_nonce = ctx.accounts.contract_state.program_account_nonce;
+        let mint_decimals = ctx.accounts.mint.decimals;
 
-        let unlocked_amount = calculate_unlocked_amount_community_wallet(
-            vesting_state.initial_community_wallet_balance,
-            months_since_first_vesting,
+        require!(
+            usize::from(first_index) + entries.len() <= usize::from(import_progress.entries_count),
+            LeancoinError::ImportEntryOutOfRange
         );
 
-        let amount_available_to_withdraw = ctx
-            .accounts
-            .community_account
-            .amount
-            .min(unlocked_amount - vesting_state.already_withdrawn_community_wallet_amount);
+        for (index, account) in ctx.remaining_accounts.iter().enumerate() {
+            let mut matching_entries = entries
+                .iter()
+                .enumerate()
+                .filter(|(_, entry)| entry.account_public_key == *account.key);
+
+            let (position, entry) = matching_entries
+                .next()
+                .ok_or(LeancoinError::MismatchBetweenRemainingAccountsAndUserInfo)?;
+
+            require!(
+                matching_entries.next().is_none(),
+                LeancoinError::NonUniqueAccountInfo
+            );
+
+            let wallet_name = entry.wallet_name.as_bytes();
+            let duplicated_wallet_name = entries.iter().any(|other_entry| {
+                other_entry.wallet_name.as_bytes() == wallet_name
+                    && ctx.remaining_accounts[..index]
+                        .iter()
+                        .any(|funded_account| *funded_account.key == other_entry.account_public_key)
+            });
+            require!(!duplicated_wallet_name, LeancoinError::DuplicatedWalletName);
+
+            // the position is within the range checked above, so the index fits in u16
+            let entry_index = first_index + position as u16;
+            if import_progress.is_transferred(entry_index) {
+                continue;
+            }
+
+            let account_balance = scale_amount_to_mint_decimals(
+                entry.account_balance,
+                import_progress.source_decimals,
+                mint_decimals,
+            )?;
+            record_imported_balance(
+                &mut vesting_state,
+                &entry.wallet_name,
+                account_balance,
+                import_progress.first_import,
+            )?;
 
-        vesting_state.already_withdrawn_community_wallet_amount += amount_to_withdraw;
-        withdraw_vested_tokens(ctx, amount_to_withdraw, amount_available_to_withdraw)?;
+            transfer_tokens(
+                ctx.accounts.program_account.to_account_info(),
+                account.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                PROGRAM_ACCOUNT_SEED,
+                program_account_nonce,
+                account_balance,
+            )?;
+            import_progress.mark_transferred(entry_index);
+        }
 
         Ok(())
     }
 
-    /// Withdraws vested tokens from partnership wallet, if available.
-    /// 50% of the initial wallet's balance is unlocked after 1 month.
-    /// The remaining part is unlocked after 2 months.
-    ///
-    /// ### Arguments
-    ///
-    /// * `amount_to_withdraw` - amount of tokens to withdraw
+    /// Completes the staged import once the tokens of all its entries have been transferred by `import_batch`.
+    /// Like `import_external_token_state`, it requires the program account to be empty and all vested wallets to be funded by the first import.
+    /// The import progress account is closed and its rent is returned to the signer.
     #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer))]
-    pub fn withdraw_tokens_from_partnership_wallet<'info>(
-        ctx: Context<'_, '_, '_, 'info, WithdrawTokensFromPartnershipWalletContext<'info>>,
-        amount_to_withdraw: u64,
-    ) -> Result<()> {
-        let vesting_state = &mut ctx.accounts.vesting_state;
-        let months_since_first_vesting = calculate_month_difference(
-            vesting_state.start_timestamp,
-            clock::Clock::get()?.unix_timestamp,
-        )?;
+    pub fn finalize_import(ctx: Context<FinalizeImportContext>) -> Result<()> {
+        let contract_state = &mut ctx.accounts.contract_state;
+        let sequence = next_sequence(contract_state)?;
+        let vesting_state = ctx.accounts.vesting_state.load()?;
+        let import_progress = &ctx.accounts.import_progress;
 
-        let unlocked_amount = calculate_unlocked_amount_partnership_wallet(
-            vesting_state.initial_partnership_wallet_balance,
-            months_since_first_vesting,
+        require!(
+            import_progress.all_transferred(),
+            LeancoinError::ImportNotComplete
         );
+        require!(
+            ctx.accounts.program_account.amount == 0,
+            LeancoinError::ProgramAccountBalanceIsNotZero
+        );
+        if import_progress.first_import {
+            vested_wallets_funded(&vesting_state)?;
+        }
 
-        let amount_available_to_withdraw = ctx
-            .accounts
-            .partnership_account
-            .amount
-            .min(unlocked_amount - vesting_state.already_withdrawn_partnership_wallet_amount);
-
-        vesting_state.already_withdrawn_partnership_wallet_amount += amount_to_withdraw;
-        withdraw_vested_tokens(ctx, amount_to_withdraw, amount_available_to_withdraw)?;
+        let source = import_progress.source;
+        emit!(ImportCompleted {
+            source,
+            total_minted: contract_state.imported_minted[source.index()],
+            total_burned: contract_state.imported_burned[source.index()],
+            accounts_funded: u32::from(import_progress.entries_count),
+            community: vesting_state.wallet(WalletKind::Community).initial_balance,
+            partnership: vesting_state
+                .wallet(WalletKind::Partnership)
+                .initial_balance,
+            marketing: vesting_state.wallet(WalletKind::Marketing).initial_balance,
+            liquidity: vesting_state.wallet(WalletKind::Liquidity).initial_balance,
+            timestamp: clock::Clock::get()?.unix_timestamp,
+            sequence,
+            swap: vesting_state.initial_swap_wallet_balance,
+        });
 
         Ok(())
     }
 
-    /// Withdraws vested tokens from marketing wallet, if available.
-    /// 40% of the initial wallet's balance is unlocked after 1 year.
-    /// Starting from the 13th month, 5% of the initial wallet's balance is unlocked every month.
+    /// Transfers the tokens sent to the program account after the import, which would be stuck there otherwise.
+    /// This function can be called only after the import has been performed and not during a staged import.
     ///
     /// ### Arguments
     ///
-    /// * `amount_to_withdraw` - amount of tokens to withdraw
-    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer))]
-    pub fn withdraw_tokens_from_marketing_wallet<'info>(
-        ctx: Context<'_, '_, '_, 'info, WithdrawTokensFromMarketingWalletContext<'info>>,
-        amount_to_withdraw: u64,
+    /// * `amount` - amount of tokens to recover, `WHOLE_BALANCE` recovers the whole balance of the program account
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) ethereum_token_state_mapping_already_performed(&ctx.accounts.contract_state))]
+    pub fn recover_program_account_tokens(
+        ctx: Context<RecoverProgramAccountTokensContext>,
+        amount: u64,
     ) -> Result<()> {
-        let vesting_state = &mut ctx.accounts.vesting_state;
-        let months_since_first_vesting = calculate_month_difference(
-            vesting_state.start_timestamp,
-            clock::Clock::get()?.unix_timestamp,
-        )?;
+        next_sequence(&mut ctx.accounts.contract_state)?;
 
-        let unlocked_amount = calculate_unlocked_amount_marketing_wallet(
-            vesting_state.initial_marketing_wallet_balance,
-            months_since_first_vesting,
+        transfer_tokens(
+            ctx.accounts.program_account.to_account_info(),
+            ctx.accounts.destination.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            PROGRAM_ACCOUNT_SEED,
+            ctx.accounts.contract_state.program_account_nonce,
+            amount,
         )?;
 
-        let amount_available_to_withdraw = ctx
-            .accounts
-            .marketing_account
-            .amount
-            .min(unlocked_amount - vesting_state.already_withdrawn_marketing_wallet_amount);
-
-        vesting_state.already_withdrawn_marketing_wallet_amount += amount_to_withdraw;
-        withdraw_vested_tokens(ctx, amount_to_withdraw, amount_available_to_withdraw)?;
-
         Ok(())
     }
 
-    /// Withdraws vested tokens from liquidity wallet, if available.
-    /// 50% of the initial wallet's balance is unlocked immediately.
-    /// The remaining part is unlocked after 1 year.
+    /// Transfers the whole balance of a token account of a foreign mint owned by one of the program's PDAs,
+    /// e.g. tokens accidentally sent to the burning account address, to the given destination.
+    /// The tokens of the program's mint cannot be recovered, so the vesting and burning accounting cannot be bypassed.
     ///
     /// ### Arguments
     ///
-    /// * `amount_to_withdraw` - amount of tokens to withdraw
+    /// * `account_kind` - the program token account whose PDA owns the foreign token account
     #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer))]
-    pub fn withdraw_tokens_from_liquidity_wallet<'info>(
-        ctx: Context<'_, '_, '_, 'info, WithdrawTokensFromLiquidityWalletContext<'info>>,
-        amount_to_withdraw: u64,
+    pub fn recover_foreign_tokens(
+        ctx: Context<RecoverForeignTokensContext>,
+        account_kind: LabeledAccountKind,
     ) -> Result<()> {
-        let vesting_state = &mut ctx.accounts.vesting_state;
-        let months_since_first_vesting = calculate_month_difference(
-            vesting_state.start_timestamp,
-            clock::Clock::get()?.unix_timestamp,
-        )?;
-
-        let unlocked_amount = calculate_unlocked_amount_liquidity_wallet(
-            vesting_state.initial_liquidity_wallet_balance,
-            months_since_first_vesting,
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        let nonce = program_token_account_nonce(
+            &ctx.accounts.contract_state,
+            &*ctx.accounts.vesting_state.load()?,
+            account_kind,
         );
 
-        let amount_available_to_withdraw = ctx
-            .accounts
-            .liquidity_account
-            .amount
-            .min(unlocked_amount - vesting_state.already_withdrawn_liquidity_wallet_amount);
+        let seeds = &[account_kind.seed().as_bytes(), &[nonce]];
+        let signer_seeds = &[&seeds[..]];
 
-        vesting_state.already_withdrawn_liquidity_wallet_amount += amount_to_withdraw;
-        withdraw_vested_tokens(ctx, amount_to_withdraw, amount_available_to_withdraw)?;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.foreign_token_account.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+            authority: ctx.accounts.source_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, ctx.accounts.foreign_token_account.amount)?;
 
         Ok(())
     }
 
-    /// Sets new authority
-    ///
-    /// ### Arguments
-    ///
-    /// * `new_authority` - new authority
-    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer))]
-    pub fn change_authority<'info>(
-        ctx: Context<'_, '_, '_, 'info, ChangeAuthorityContext<'info>>,
-        new_authority: Pubkey,
-    ) -> Result<()> {
-        let contract_state = &mut ctx.accounts.contract_state;
-        contract_state.authority = new_authority;
+    /// Initializes the burn history which keeps the latest burns, see `BurnHistory`.
+    /// The burns cannot be executed before it is initialized, contracts deployed before the burn history call it after the upgrade.
+    /// It can be called by anyone and only once, the signer pays the rent of the account.
+    pub fn initialize_burn_history(ctx: Context<InitializeBurnHistoryContext>) -> Result<()> {
+        let burn_history = &mut ctx.accounts.burn_history;
+        burn_history.burn_history_nonce = *ctx.bumps.get("burn_history").unwrap();
+        burn_history.cursor = 0;
+        burn_history.burns_count = 0;
 
         Ok(())
     }
 
-    /// Sets new token metadata
-    ///
-    /// ### Arguments
-    ///
-    /// * `name` - new token name
-    /// * `symbol` - new token symbol
-    /// * `uri` - new token uri
-    /// * `token_metadata_action` - enum that specifies which token metadata instruction to use
-    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer))]
-    pub fn set_token_metadata(
-        ctx: Context<SetTokenMetadataContext>,
-        name: String,
-        symbol: String,
-        uri: String,
-        token_metadata_action: TokenMetadataAction,
-    ) -> Result<()> {
-        let program_id = ctx.accounts.metadata_program.to_account_info();
-        let metadata_pda = ctx.accounts.metadata_pda.to_account_info();
-        let mint = ctx.accounts.mint.to_account_info();
-        let mint_authority = ctx.accounts.mint.to_account_info();
-        let payer = ctx.accounts.signer.to_account_info();
-        let update_authority = ctx.accounts.mint.to_account_info();
-        let system_program = ctx.accounts.system_program.to_account_info();
-
-        let seeds = &[
-            MINT_SEED.as_bytes(),
-            &[ctx.accounts.contract_state.mint_nonce],
-        ];
+    /// Burns the configured rate, 5% by default, of all the tokens currently held by the burning account.
+    /// Once staking is enabled, the configured part of the burned amount is transferred to the reward vault instead of being burned, as long as anything is staked.
+    /// This function can be called only once per month and only in the primary burn window, between the 1st and the 5th day of the month by default.
+    /// The burn missed in this window can be executed late in the secondary burn window configured in the contract state.
+    /// If the caller passes a token account of the mint, the configured reward is transferred to it from the burned amount.
+    /// It can be called by the contract's owner, a registered keeper or the burn authority, by any signer once the authority is renounced.
+    /// It cannot be invoked via CPI unless it is allowed in the contract state.
+    #[access_control(valid_burner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) not_paused(&ctx.accounts.contract_state) ethereum_token_state_mapping_already_performed(&ctx.accounts.contract_state) top_level_invocation(&ctx.accounts.contract_state, &ctx.accounts.instructions_sysvar))]
+    pub fn burn(ctx: Context<BurnContext>) -> Result<()> {
+        let contract_state = &mut ctx.accounts.contract_state;
+        let sequence = next_sequence(contract_state)?;
+        let timestamp = clock::Clock::get()?.unix_timestamp;
+        let now = parse_timestamp(timestamp)?;
 
-        let account_infos = &[
-            program_id.clone(),
-            metadata_pda.clone(),
-            mint.clone(),
-            mint_authority.clone(),
-            payer.clone(),
-            update_authority.clone(),
-            system_program.clone(),
-        ];
+        let late = late_burn(contract_state, now.days)?;
+        require!(
+            contract_state.last_burning_month != now.month
+                || contract_state.last_burning_year != now.year,
+            LeancoinError::TokensAlreadyBurned
+        );
 
-        let create_metadata_accounts_instruction = create_metadata_accounts_v3(
-            *program_id.key,
-            *metadata_pda.key,
-            *mint.key,
-            *mint_authority.key,
-            *payer.key,
-            *update_authority.key,
-            name.clone(),
-            symbol.clone(),
-            uri.clone(),
-            None,
-            0u16,
-            false,
-            true,
-            None,
-            None,
-            None,
+        let burning_account_balance = ctx.accounts.burning_account.amount;
+        let burn_rate_bps = contract_state.burn_rate_bps;
+        let (mut amount, burn_remainder_numerator) = calculate_monthly_burn_amount(
+            burning_account_balance,
+            contract_state.burn_remainder_numerator,
+            burn_rate_bps,
         );
+        // a zero amount fails before any state is changed, so the burn of the month can be retried after funding
+        require!(amount > 0, LeancoinError::NothingToBurn);
+        let mut rewards = 0;
 
-        let data = DataV2 {
-            name,
-            symbol,
-            uri,
-            seller_fee_basis_points: 0u16,
-            creators: None,
-            collection: None,
-            uses: None,
-        };
+        if contract_state.staking_enabled {
+            let staking_state = ctx
+                .accounts
+                .staking_state
+                .as_mut()
+                .ok_or(LeancoinError::StakingAccountsMissing)?;
+            let reward_vault = ctx
+                .accounts
+                .reward_vault
+                .as_ref()
+                .ok_or(LeancoinError::StakingAccountsMissing)?;
 
-        let update_metadata_accounts_instruction = update_metadata_accounts_v2(
-            *program_id.key,
-            *metadata_pda.key,
-            *update_authority.key,
-            Some(*update_authority.key),
-            Some(data),
-            None,
-            Some(true),
-        );
+            if staking_state.total_staked > 0 {
+                // the rewards cannot exceed the burned amount once the burn rate is lowered below the reward percentage
+                rewards = calculate_staking_rewards_amount(
+                    burning_account_balance,
+                    staking_state.reward_percentage,
+                )
+                .min(amount);
 
-        match token_metadata_action {
-            TokenMetadataAction::Create => {
-                invoke_signed(
-                    &create_metadata_accounts_instruction,
-                    account_infos,
-                    &[seeds],
+                transfer_tokens(
+                    ctx.accounts.burning_account.to_account_info(),
+                    reward_vault.to_account_info(),
+                    ctx.accounts.token_program.to_account_info(),
+                    BURNING_ACCOUNT_SEED,
+                    contract_state.burning_account_nonce,
+                    rewards,
                 )?;
+
+                staking_state.reward_per_token +=
+                    calculate_reward_per_token_increase(rewards, staking_state.total_staked);
+                amount -= rewards;
             }
-            TokenMetadataAction::Update => {
-                invoke_signed(
-                    &update_metadata_accounts_instruction,
-                    account_infos,
-                    &[seeds],
+        }
+
+        let mut caller_reward = 0;
+        if let Some(caller_token_account) = &ctx.accounts.caller_token_account {
+            caller_reward =
+                calculate_burn_caller_reward(amount, contract_state.burn_caller_reward_bps);
+
+            // the reward is skipped when it rounds down to zero, so no empty transfer is made
+            if caller_reward > 0 {
+                transfer_tokens(
+                    ctx.accounts.burning_account.to_account_info(),
+                    caller_token_account.to_account_info(),
+                    ctx.accounts.token_program.to_account_info(),
+                    BURNING_ACCOUNT_SEED,
+                    contract_state.burning_account_nonce,
+                    caller_reward,
                 )?;
+                amount -= caller_reward;
             }
         }
 
-        Ok(())
+        contract_state.last_burning_month = now.month;
+        contract_state.last_burning_year = now.year;
+        contract_state.burn_remainder_numerator = burn_remainder_numerator;
+
+        execute_burn(
+            ctx,
+            amount,
+            BurnDetails {
+                sequence,
+                timestamp,
+                month: now.month,
+                year: now.year,
+                rewards,
+                caller_reward,
+                late,
+                burn_rate_bps,
+                manual: false,
+            },
+        )
     }
-}
 
-/// structure for storing information about the account
-#[derive(AnchorSerialize, AnchorDeserialize)]
-pub struct AccountInfoFromEthereum {
-    pub wallet_name: String,
-    pub account_public_key: Pubkey,
-    pub account_balance: u64,
-}
+    /// Burns the tokens of the monthly burns missed entirely since the last one in a single burn.
+    /// The current month is not caught up, its burn is still executed by the monthly burn in the burn windows.
+    /// The configured rate is compounded once per missed month, at most for `MAX_CATCH_UP_BURN_MONTHS` months,
+    /// and the burn is recorded as the burn of the previous month.
+    /// Once staking is enabled, the staking rewards of every month are transferred to the reward vault instead of being burned, as long as anything is staked.
+    /// Unlike the monthly burn, it can be executed on any day of the month, but only after the first monthly burn.
+    /// It can be called by the contract's owner, a registered keeper or the burn authority, by any signer once the authority is renounced.
+    /// It cannot be invoked via CPI unless it is allowed in the contract state.
+    #[access_control(valid_burner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) not_paused(&ctx.accounts.contract_state) ethereum_token_state_mapping_already_performed(&ctx.accounts.contract_state) top_level_invocation(&ctx.accounts.contract_state, &ctx.accounts.instructions_sysvar))]
+    pub fn catch_up_burn(ctx: Context<CatchUpBurnContext>) -> Result<()> {
+        let contract_state = &mut ctx.accounts.contract_state;
+        let sequence = next_sequence(contract_state)?;
+        let timestamp = clock::Clock::get()?.unix_timestamp;
+        let now = parse_timestamp(timestamp)?;
+        let months = months_since_last_burn(contract_state, &now)?;
+        let (burning_month, burning_year) = previous_month(&now);
 
-/// The `TokenMetadataAction` enum is used to indicate whether the `set_token_metadata` function should create new metadata for a token, or update the existing metadata.
-///
-/// * `Create` - Indicates that new metadata should be created. This should be used when the token does not have any existing metadata.
-/// * `Update` - Indicates that the existing metadata should be updated. This should be used when the token already has metadata, and it needs to be modified.
-///
-#[derive(AnchorSerialize, AnchorDeserialize)]
-pub enum TokenMetadataAction {
-    Create,
-    Update,
-}
+        let burning_account_balance = ctx.accounts.burning_account.amount;
+        let burn_rate_bps = contract_state.burn_rate_bps;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::account::ContractState;
+        let mut reward_percentage = 0;
+        if contract_state.staking_enabled {
+            let staking_state = ctx
+                .accounts
+                .staking_state
+                .as_ref()
+                .ok_or(LeancoinError::StakingAccountsMissing)?;
+            if staking_state.total_staked > 0 {
+                reward_percentage = staking_state.reward_percentage;
+            }
+        }
 
-    use anchor_lang::{prelude::Clock, system_program, InstructionData, ToAccountMetas};
-    use anchor_spl::token::spl_token;
-    use solana_program::instruction::AccountMeta;
-    use spl_token::state::Account;
+        let (mut amount, rewards, burn_remainder_numerator) = calculate_catch_up_burn_amount(
+            burning_account_balance,
+            contract_state.burn_remainder_numerator,
+            burn_rate_bps,
+            reward_percentage,
+            months,
+        );
+        require!(amount > 0, LeancoinError::NothingToBurn);
 
-    use crate::context::__client_accounts_change_authority_context::ChangeAuthorityContext;
+        if rewards > 0 {
+            let staking_state = ctx
+                .accounts
+                .staking_state
+                .as_mut()
+                .ok_or(LeancoinError::StakingAccountsMissing)?;
+            let reward_vault = ctx
+                .accounts
+                .reward_vault
+                .as_ref()
+                .ok_or(LeancoinError::StakingAccountsMissing)?;
 
-    use crate::context::__client_accounts_import_ethereum_token_state_context::ImportEthereumTokenStateContext;
-    use crate::context::__client_accounts_initialize_context::InitializeContext;
-    use crate::context::__client_accounts_set_token_metadata_context::SetTokenMetadataContext;
-    use crate::context::__client_accounts_withdraw_tokens_from_community_wallet_context::WithdrawTokensFromCommunityWalletContext;
-    use crate::context::__client_accounts_withdraw_tokens_from_liquidity_wallet_context::WithdrawTokensFromLiquidityWalletContext;
-    use crate::context::__client_accounts_withdraw_tokens_from_marketing_wallet_context::WithdrawTokensFromMarketingWalletContext;
-    use crate::context::__client_accounts_withdraw_tokens_from_partnership_wallet_context::WithdrawTokensFromPartnershipWalletContext;
+            transfer_tokens(
+                ctx.accounts.burning_account.to_account_info(),
+                reward_vault.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                BURNING_ACCOUNT_SEED,
+                contract_state.burning_account_nonce,
+                rewards,
+            )?;
 
-    use crate::context::__client_accounts_burn_context::BurnContext;
+            staking_state.reward_per_token +=
+                calculate_reward_per_token_increase(rewards, staking_state.total_staked);
+            amount -= rewards;
+        }
 
-    use solana_program::{
-        hash::Hash, instruction::Instruction, program_pack::Pack, system_instruction,
-    };
-    use solana_program_test::*;
+        contract_state.last_burning_month = burning_month;
+        contract_state.last_burning_year = burning_year;
+        contract_state.burn_remainder_numerator = burn_remainder_numerator;
 
-    use solana_sdk::{
-        commitment_config::CommitmentLevel, signature::Keypair, signer::Signer,
-        transaction::Transaction,
-    };
+        execute_burn(
+            ctx,
+            amount,
+            BurnDetails {
+                sequence,
+                timestamp,
+                month: burning_month,
+                year: burning_year,
+                rewards,
+                caller_reward: 0,
+                late: false,
+                burn_rate_bps,
+                manual: false,
+            },
+        )
+    }
 
-    async fn initialize_instruction(
-        banks_client: &mut BanksClient,
-        payer: &Keypair,
-        recent_blockhash: Hash,
-    ) -> Result<()> {
-        let program_id = id();
-        let (
-            contract_state,
-            contract_state_nonce,
-            vesting_state,
-            vesting_state_nonce,
-            mint,
-            mint_nonce,
-            program_account,
-            program_account_nonce,
-            burning_account,
-            burning_account_nonce,
-            community_account,
-            community_wallet_nonce,
-            partnership_account,
-            partnership_wallet_nonce,
-            marketing_account,
-            marketing_wallet_nonce,
-            liquidity_account,
-            liquidity_wallet_nonce,
-        ) = get_pda_accounts();
+    /// Burns exactly the given amount of tokens from the burning account outside of the monthly schedule.
+    /// The burn is recorded in the total burned amount and in a manual burn event, but not as the burn of the month,
+    /// so the scheduled monthly burn can still be executed in the same month.
+    /// It can be called by the contract's owner or the burn authority.
+    ///
+    /// ### Arguments
+    ///
+    /// * `amount` - amount of tokens to burn
+    #[access_control(valid_role(&ctx.accounts.contract_state, Role::Burn, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) not_paused(&ctx.accounts.contract_state) ethereum_token_state_mapping_already_performed(&ctx.accounts.contract_state))]
+    pub fn burn_exact(ctx: Context<BurnExactContext>, amount: u64) -> Result<()> {
+        let sequence = next_sequence(&mut ctx.accounts.contract_state)?;
+        let timestamp = clock::Clock::get()?.unix_timestamp;
+        let now = parse_timestamp(timestamp)?;
 
-        let token_program = spl_token::id();
-        let signer = payer.pubkey();
+        require!(
+            ctx.accounts.burning_account.amount >= amount,
+            LeancoinError::NotEnoughTokens
+        );
 
-        let data = instruction::Initialize {
-            contract_state_nonce,
-            vesting_state_nonce,
-            mint_nonce,
-            program_account_nonce,
-            burning_account_nonce,
-            community_wallet_nonce,
-            liquidity_wallet_nonce,
-            marketing_wallet_nonce,
-            partnership_wallet_nonce,
+        // the month of the last burn is left untouched, so the manual burn does not replace the scheduled one
+        execute_burn(
+            ctx,
+            amount,
+            BurnDetails {
+                sequence,
+                timestamp,
+                month: now.month,
+                year: now.year,
+                rewards: 0,
+                caller_reward: 0,
+                late: false,
+                burn_rate_bps: 0,
+                manual: true,
+            },
+        )
+    }
+
+    /// Transfers tokens of the signer to the burning account, so they are burned by the following monthly burns.
+    /// The donation is recorded in the total donated amount, in the cumulative amount of the donor and in an event.
+    ///
+    /// ### Arguments
+    ///
+    /// * `amount` - amount of tokens to donate
+    pub fn donate_to_burn(ctx: Context<DonateToBurnContext>, amount: u64) -> Result<()> {
+        let contract_state = &mut ctx.accounts.contract_state;
+        let sequence = next_sequence(contract_state)?;
+        contract_state.total_donated = contract_state
+            .total_donated
+            .checked_add(amount)
+            .ok_or(LeancoinError::DonatedAmountOverflow)?;
+
+        let donor_account = &mut ctx.accounts.donor_account;
+        if donor_account.donor == Pubkey::default() {
+            donor_account.donor_account_nonce = *ctx.bumps.get("donor_account").unwrap();
+            donor_account.donor = ctx.accounts.signer.key();
         }
-        .data();
+        donor_account.total_donated = donor_account
+            .total_donated
+            .checked_add(amount)
+            .ok_or(LeancoinError::DonatedAmountOverflow)?;
 
-        let accs = InitializeContext {
-            contract_state,
-            vesting_state,
-            community_account,
-            liquidity_account,
-            marketing_account,
-            partnership_account,
-            mint,
-            program_account,
-            burning_account,
-            token_program,
-            signer,
-            system_program: system_program::ID,
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.donor_token_account.to_account_info(),
+            to: ctx.accounts.burning_account.to_account_info(),
+            authority: ctx.accounts.signer.to_account_info(),
         };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
 
-        let mut transaction = Transaction::new_with_payer(
-            &[Instruction::new_with_bytes(
-                program_id,
-                &data,
-                accs.to_account_metas(Some(false)),
-            )],
-            Some(&payer.pubkey()),
-        );
-
-        transaction.sign(&[payer], recent_blockhash);
-        banks_client
-            .process_transaction_with_commitment(transaction.clone(), CommitmentLevel::Finalized)
-            .await
-            .unwrap();
+        emit!(BurnDonation {
+            donor: ctx.accounts.signer.key(),
+            amount,
+            sequence,
+        });
 
         Ok(())
     }
 
-    async fn import_ethereum_token_state_instruction(
-        banks_client: &mut BanksClient,
-        payer: &Keypair,
-        recent_blockhash: Hash,
+    /// Transfers tokens of the signer to the burning account, so they are burned by the following monthly burns.
+    /// Unlike `donate_to_burn` it creates no donor account, the transfer is recorded only in the total amount funded for burning and in an event.
+    ///
+    /// ### Arguments
+    ///
+    /// * `amount` - amount of tokens to transfer
+    pub fn fund_burning_account(
+        ctx: Context<FundBurningAccountContext>,
+        amount: u64,
     ) -> Result<()> {
-        let program_id = id();
+        let contract_state = &mut ctx.accounts.contract_state;
+        let sequence = next_sequence(contract_state)?;
+        contract_state.total_donated_for_burning = contract_state
+            .total_donated_for_burning
+            .checked_add(amount)
+            .ok_or(LeancoinError::DonatedAmountOverflow)?;
 
-        let (
-            contract_state,
-            _,
-            vesting_state,
-            _,
-            mint,
-            mint_nonce,
-            program_account,
-            program_account_nonce,
-            burning_account,
-            _,
-            community_account,
-            _,
-            partnership_account,
-            _,
-            marketing_account,
-            _,
-            liquidity_account,
-            _,
-        ) = get_pda_accounts();
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.donor_token_account.to_account_info(),
+            to: ctx.accounts.burning_account.to_account_info(),
+            authority: ctx.accounts.signer.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
 
-        let token_program = spl_token::id();
-        let signer = payer.pubkey();
+        emit!(BurningAccountFunded {
+            donor: ctx.accounts.signer.key(),
+            amount,
+            sequence,
+        });
 
-        let account_info_from_ethereum = get_accounts_to_mapping();
-        let amount_token_to_mint = 10000000000000000000;
-        let amount_token_to_burn = 1470000000000000000;
+        Ok(())
+    }
 
-        let data = instruction::ImportEthereumTokenState {
-            account_info_from_ethereum,
-            amount_token_to_mint,
-            amount_token_to_burn,
-        }
-        .data();
+    /// Attests the mint supply and balances of all program-custodied token accounts.
+    /// The hash of the attested values is stored in the attestation account and the full breakdown is emitted in an event.
+    /// This function can be called by the contract's owner or a registered keeper but only once per day.
+    #[access_control(valid_keeper(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer))]
+    pub fn attest_reserves(ctx: Context<AttestReservesContext>) -> Result<()> {
+        let sequence = next_sequence(&mut ctx.accounts.contract_state)?;
+        let clock = clock::Clock::get()?;
+        let attestation = &mut ctx.accounts.attestation;
 
-        let accs = ImportEthereumTokenStateContext {
-            contract_state,
-            vesting_state,
-            mint,
-            program_account,
-            token_program,
-            signer,
-        };
+        require!(
+            clock.unix_timestamp - attestation.last_attestation_timestamp >= ATTESTATION_INTERVAL,
+            LeancoinError::AttestationTooFrequent
+        );
 
-        let mut accounts = accs.to_account_metas(Some(false));
-        accounts.push(AccountMeta::new(burning_account, false));
-        accounts.push(AccountMeta::new(community_account, false));
+        let vesting_state = ctx.accounts.vesting_state.load()?;
+        let supply = ctx.accounts.mint.supply;
+        let balances = [
+            ctx.accounts.program_account.amount,
+            ctx.accounts.burning_account.amount,
+            vested_wallet_balance(
+                &vesting_state,
+                WalletKind::Community,
+                &ctx.accounts.community_account,
+            )?,
+            vested_wallet_balance(
+                &vesting_state,
+                WalletKind::Partnership,
+                &ctx.accounts.partnership_account,
+            )?,
+            vested_wallet_balance(
+                &vesting_state,
+                WalletKind::Marketing,
+                &ctx.accounts.marketing_account,
+            )?,
+            vested_wallet_balance(
+                &vesting_state,
+                WalletKind::Liquidity,
+                &ctx.accounts.liquidity_account,
+            )?,
+        ];
+        let hash = calculate_reserves_attestation_hash(clock.slot, supply, &balances);
+
+        attestation.attestation_nonce = *ctx.bumps.get("attestation").unwrap();
+        attestation.last_attestation_slot = clock.slot;
+        attestation.last_attestation_timestamp = clock.unix_timestamp;
+        attestation.last_attestation_hash = hash;
+
+        emit!(ReservesAttested {
+            slot: clock.slot,
+            timestamp: clock.unix_timestamp,
+            supply,
+            program_account_balance: balances[0],
+            burning_account_balance: balances[1],
+            community_account_balance: balances[2],
+            partnership_account_balance: balances[3],
+            marketing_account_balance: balances[4],
+            liquidity_account_balance: balances[5],
+            hash,
+            sequence,
+        });
+
+        Ok(())
+    }
+
+    /// Configures the monthly liquidity incentive drip from the liquidity wallet.
+    /// The drip can be configured only before Ethereum token state import.
+    ///
+    /// ### Arguments
+    ///
+    /// * `recipient` - the token account receiving the liquidity incentive
+    /// * `amount_per_month` - amount of tokens transferred every month
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) ethereum_token_state_mapping_not_performed_yet(&ctx.accounts.contract_state))]
+    pub fn configure_liquidity_drip(
+        ctx: Context<ConfigureLiquidityDripContext>,
+        recipient: Pubkey,
+        amount_per_month: u64,
+    ) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        let mut vesting_state = ctx.accounts.vesting_state.load_mut()?;
+        vesting_state.liquidity_drip_recipient = recipient;
+        vesting_state.liquidity_drip_amount_per_month = amount_per_month;
+
+        Ok(())
+    }
+
+    /// Transfers the configured monthly liquidity incentive to the configured recipient.
+    /// The amount is capped by the unlocked but not yet withdrawn balance of the liquidity wallet, calculated like in its withdrawals,
+    /// see `calculate_wallet_unlocked_amount`, and the drip is refused whenever the liquidity withdrawal would be, i.e. before the import,
+    /// during the withdrawal grace period and once the wallet is finalized or revoked.
+    /// This function can be called by the contract's owner or a registered keeper but only once per calendar month.
+    /// Although the drip needs no decision of the owner, it is not permissionless: like the other automation instructions
+    /// it is restricted to the keepers, so no third party can choose the moment of the monthly transfer, see `add_keeper`.
+    #[access_control(valid_keeper(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) not_paused(&ctx.accounts.contract_state) ethereum_token_state_mapping_already_performed(&ctx.accounts.contract_state) vested_wallet_not_finalized(&ctx.accounts.vesting_state, WalletKind::Liquidity) vested_wallet_not_revoked(&ctx.accounts.vesting_state, WalletKind::Liquidity) withdrawal_grace_period_passed(&ctx.accounts.contract_state, &ctx.accounts.vesting_state))]
+    pub fn execute_liquidity_drip(ctx: Context<ExecuteLiquidityDripContext>) -> Result<()> {
+        let sequence = next_sequence(&mut ctx.accounts.contract_state)?;
+        let mut vesting_state = ctx.accounts.vesting_state.load_mut()?;
+
+        require!(
+            vesting_state.liquidity_drip_recipient != Pubkey::default(),
+            LeancoinError::LiquidityDripNotConfigured
+        );
+        require!(
+            vesting_state.start_timestamp != 0,
+            LeancoinError::VestingNotStarted
+        );
+
+        let timestamp = clock::Clock::get()?.unix_timestamp;
+        let now = parse_timestamp(timestamp)?;
+
+        require!(
+            vesting_state.last_liquidity_drip_month != now.month
+                || vesting_state.last_liquidity_drip_year != now.year,
+            LeancoinError::LiquidityDripAlreadyExecuted
+        );
+
+        let unlocked_amount =
+            calculate_wallet_unlocked_amount(&vesting_state, WalletKind::Liquidity, timestamp)?;
+
+        let amount = vesting_state
+            .liquidity_drip_amount_per_month
+            .min(remaining_unlocked_amount(
+                &vesting_state,
+                WalletKind::Liquidity,
+                unlocked_amount,
+            )?)
+            .min(ctx.accounts.liquidity_account.amount);
+        require!(amount > 0, LeancoinError::NotEnoughTokens);
+
+        record_withdrawal(&mut vesting_state, WalletKind::Liquidity, amount)?;
+        vesting_state.last_liquidity_drip_month = now.month;
+        vesting_state.last_liquidity_drip_year = now.year;
+
+        transfer_tokens(
+            ctx.accounts.liquidity_account.to_account_info(),
+            ctx.accounts.recipient.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            LIQUIDITY_ACCOUNT_SEED,
+            vesting_state.wallet(WalletKind::Liquidity).nonce,
+            amount,
+        )?;
+
+        emit!(LiquidityDripExecuted {
+            recipient: vesting_state.liquidity_drip_recipient,
+            amount,
+            month: now.month,
+            year: now.year,
+            sequence,
+        });
+
+        Ok(())
+    }
+
+    /// Creates an OTC deal with a partner by escrowing tokens from the partnership wallet in the partner's OTC vault.
+    /// The escrowed tokens count as withdrawn from the partnership wallet, so the same unlocked limits apply as to its withdrawals,
+    /// see `calculate_wallet_unlocked_amount`. No deal can be created from a finalized or revoked partnership wallet.
+    /// There can be at most one open deal per partner.
+    ///
+    /// ### Arguments
+    ///
+    /// * `partner` - the partner who can settle the deal
+    /// * `lean_amount` - amount of tokens to escrow, `WITHDRAW_ALL_UNLOCKED` escrows all tokens currently available
+    /// * `unlock_ts` - timestamp after which the partner can settle the deal
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) not_paused(&ctx.accounts.contract_state) ethereum_token_state_mapping_already_performed(&ctx.accounts.contract_state) vested_wallet_not_finalized(&ctx.accounts.vesting_state, WalletKind::Partnership) vested_wallet_not_revoked(&ctx.accounts.vesting_state, WalletKind::Partnership) withdrawal_grace_period_passed(&ctx.accounts.contract_state, &ctx.accounts.vesting_state))]
+    pub fn create_otc_deal<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateOtcDealContext<'info>>,
+        partner: Pubkey,
+        lean_amount: u64,
+        unlock_ts: i64,
+    ) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        let mut vesting_state = ctx.accounts.vesting_state.load_mut()?;
+        let otc_deal = &mut ctx.accounts.otc_deal;
+
+        require!(otc_deal.lean_amount == 0, LeancoinError::OtcDealAlreadyOpen);
+        require!(lean_amount > 0, LeancoinError::NotEnoughTokens);
+        let unlocked_amount = calculate_wallet_unlocked_amount(
+            &vesting_state,
+            WalletKind::Partnership,
+            clock::Clock::get()?.unix_timestamp,
+        )?;
+
+        let amount_available_to_withdraw =
+            ctx.accounts
+                .partnership_account
+                .amount
+                .min(remaining_unlocked_amount(
+                    &vesting_state,
+                    WalletKind::Partnership,
+                    unlocked_amount,
+                )?);
+
+        otc_deal.otc_deal_nonce = *ctx.bumps.get("otc_deal").unwrap();
+        otc_deal.otc_vault_nonce = *ctx.bumps.get("otc_vault").unwrap();
+        otc_deal.partner = partner;
+        otc_deal.unlock_timestamp = unlock_ts;
+
+        drop(vesting_state);
+        let withdrawn_amount = withdraw_vested_tokens(
+            &ctx,
+            WalletKind::Partnership,
+            lean_amount,
+            amount_available_to_withdraw,
+        )?;
+        require!(withdrawn_amount > 0, LeancoinError::NotEnoughTokens);
+        ctx.accounts.otc_deal.lean_amount = withdrawn_amount;
+        record_withdrawal(
+            &mut ctx.accounts.vesting_state.load_mut()?,
+            WalletKind::Partnership,
+            withdrawn_amount,
+        )?;
+
+        Ok(())
+    }
+
+    /// Transfers the tokens escrowed by the OTC deal to the partner.
+    /// This function can be called only by the partner and only after the unlock timestamp of the deal.
+    pub fn settle_otc_deal(ctx: Context<SettleOtcDealContext>) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        let otc_deal = &mut ctx.accounts.otc_deal;
+
+        require!(otc_deal.lean_amount > 0, LeancoinError::OtcDealNotOpen);
+        require!(
+            clock::Clock::get()?.unix_timestamp >= otc_deal.unlock_timestamp,
+            LeancoinError::OtcDealLocked
+        );
+
+        let amount = otc_deal.lean_amount;
+        otc_deal.lean_amount = 0;
+        otc_deal.unlock_timestamp = 0;
+
+        transfer_tokens_from_otc_vault(
+            ctx.accounts.otc_vault.to_account_info(),
+            ctx.accounts.partner_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            &otc_deal.partner,
+            otc_deal.otc_vault_nonce,
+            amount,
+        )
+    }
+
+    /// Cancels the OTC deal with a partner before it is settled.
+    /// The escrowed tokens are returned to the partnership wallet and no longer count as withdrawn.
+    ///
+    /// ### Arguments
+    ///
+    /// * `partner` - the partner of the deal
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer))]
+    pub fn cancel_otc_deal(ctx: Context<CancelOtcDealContext>, partner: Pubkey) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        let mut vesting_state = ctx.accounts.vesting_state.load_mut()?;
+        let otc_deal = &mut ctx.accounts.otc_deal;
+
+        require!(otc_deal.lean_amount > 0, LeancoinError::OtcDealNotOpen);
+
+        let amount = otc_deal.lean_amount;
+        otc_deal.lean_amount = 0;
+        otc_deal.unlock_timestamp = 0;
+        let partnership_wallet = vesting_state.wallet_mut(WalletKind::Partnership);
+        partnership_wallet.withdrawn = partnership_wallet
+            .withdrawn
+            .checked_sub(amount)
+            .ok_or(LeancoinError::WithdrawnAmountOverflow)?;
+
+        transfer_tokens_from_otc_vault(
+            ctx.accounts.otc_vault.to_account_info(),
+            ctx.accounts.partnership_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            &partner,
+            otc_deal.otc_vault_nonce,
+            amount,
+        )
+    }
+
+    /// Initializes staking: the staking state together with the stake and reward vaults.
+    /// From now on, the burn instruction redirects part of every monthly burn to the stakers.
+    ///
+    /// ### Arguments
+    ///
+    /// * `reward_percentage` - percentage points of the burning account balance redirected to the stakers, at most 5
+    /// * `unbonding_period` - number of seconds unstaked tokens stay locked before they can be withdrawn
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer))]
+    pub fn initialize_staking(
+        ctx: Context<InitializeStakingContext>,
+        reward_percentage: u8,
+        unbonding_period: i64,
+    ) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        require!(
+            reward_percentage <= BURN_PERCENTAGE,
+            LeancoinError::InvalidRewardPercentage
+        );
+        require_gte!(unbonding_period, 0);
+
+        let staking_state = &mut ctx.accounts.staking_state;
+        staking_state.staking_state_nonce = *ctx.bumps.get("staking_state").unwrap();
+        staking_state.stake_vault_nonce = *ctx.bumps.get("stake_vault").unwrap();
+        staking_state.reward_vault_nonce = *ctx.bumps.get("reward_vault").unwrap();
+        staking_state.reward_percentage = reward_percentage;
+        staking_state.unbonding_period = unbonding_period;
+        staking_state.total_staked = 0;
+        staking_state.reward_per_token = 0;
+
+        ctx.accounts.contract_state.staking_enabled = true;
+
+        Ok(())
+    }
+
+    /// Sets the part of the monthly burn redirected to the stakers.
+    ///
+    /// ### Arguments
+    ///
+    /// * `reward_percentage` - percentage points of the burning account balance redirected to the stakers, at most 5
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer))]
+    pub fn set_staking_reward_percentage(
+        ctx: Context<SetStakingRewardPercentageContext>,
+        reward_percentage: u8,
+    ) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        require!(
+            reward_percentage <= BURN_PERCENTAGE,
+            LeancoinError::InvalidRewardPercentage
+        );
+
+        ctx.accounts.staking_state.reward_percentage = reward_percentage;
+
+        Ok(())
+    }
+
+    /// Locks tokens of the signer in the stake vault.
+    /// The rewards earned so far are preserved as pending rewards.
+    ///
+    /// ### Arguments
+    ///
+    /// * `amount` - amount of tokens to stake
+    pub fn stake(ctx: Context<StakeContext>, amount: u64) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        let staking_state = &mut ctx.accounts.staking_state;
+        let stake_account = &mut ctx.accounts.stake_account;
+
+        if stake_account.owner == Pubkey::default() {
+            stake_account.stake_account_nonce = *ctx.bumps.get("stake_account").unwrap();
+            stake_account.owner = ctx.accounts.signer.key();
+        }
+
+        update_stake_rewards(stake_account, staking_state.reward_per_token)?;
+        stake_account.staked_amount = stake_account
+            .staked_amount
+            .checked_add(amount)
+            .ok_or(LeancoinError::StakedAmountOverflow)?;
+        stake_account.reward_debt =
+            calculate_reward_debt(stake_account.staked_amount, staking_state.reward_per_token)?;
+        staking_state.total_staked = staking_state
+            .total_staked
+            .checked_add(amount)
+            .ok_or(LeancoinError::StakedAmountOverflow)?;
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.staker_token_account.to_account_info(),
+            to: ctx.accounts.stake_vault.to_account_info(),
+            authority: ctx.accounts.signer.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+
+        token::transfer(cpi_ctx, amount)
+    }
+
+    /// Stops earning rewards for the given amount of staked tokens and starts their unbonding period.
+    /// Unstaking again restarts the unbonding period of all unbonding tokens.
+    ///
+    /// ### Arguments
+    ///
+    /// * `amount` - amount of tokens to unstake
+    pub fn unstake(ctx: Context<UnstakeContext>, amount: u64) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        let staking_state = &mut ctx.accounts.staking_state;
+        let stake_account = &mut ctx.accounts.stake_account;
+
+        require!(
+            amount <= stake_account.staked_amount,
+            LeancoinError::NotEnoughStakedTokens
+        );
+
+        update_stake_rewards(stake_account, staking_state.reward_per_token)?;
+        stake_account.staked_amount = stake_account
+            .staked_amount
+            .checked_sub(amount)
+            .ok_or(LeancoinError::NotEnoughStakedTokens)?;
+        stake_account.reward_debt =
+            calculate_reward_debt(stake_account.staked_amount, staking_state.reward_per_token)?;
+        staking_state.total_staked = staking_state
+            .total_staked
+            .checked_sub(amount)
+            .ok_or(LeancoinError::NotEnoughStakedTokens)?;
+
+        stake_account.unbonding_amount = stake_account
+            .unbonding_amount
+            .checked_add(amount)
+            .ok_or(LeancoinError::StakedAmountOverflow)?;
+        stake_account.unbonding_end_timestamp = clock::Clock::get()?
+            .unix_timestamp
+            .checked_add(staking_state.unbonding_period)
+            .ok_or(LeancoinError::InvalidTimestamp)?;
+
+        Ok(())
+    }
+
+    /// Transfers unstaked tokens back to the staker once their unbonding period has elapsed.
+    pub fn withdraw_unstaked(ctx: Context<WithdrawUnstakedContext>) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        let stake_account = &mut ctx.accounts.stake_account;
+
+        require!(
+            clock::Clock::get()?.unix_timestamp >= stake_account.unbonding_end_timestamp,
+            LeancoinError::UnbondingPeriodNotElapsed
+        );
+        require!(
+            stake_account.unbonding_amount > 0,
+            LeancoinError::NotEnoughTokens
+        );
+
+        let amount = stake_account.unbonding_amount;
+        stake_account.unbonding_amount = 0;
+
+        transfer_tokens(
+            ctx.accounts.stake_vault.to_account_info(),
+            ctx.accounts.staker_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            STAKE_VAULT_SEED,
+            ctx.accounts.staking_state.stake_vault_nonce,
+            amount,
+        )?;
+
+        Ok(())
+    }
+
+    /// Transfers all staking rewards earned by the signer from the reward vault.
+    pub fn claim_rewards(ctx: Context<ClaimRewardsContext>) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        let stake_account = &mut ctx.accounts.stake_account;
+
+        update_stake_rewards(stake_account, ctx.accounts.staking_state.reward_per_token)?;
+        require!(
+            stake_account.pending_rewards > 0,
+            LeancoinError::NotEnoughTokens
+        );
+
+        let amount = stake_account.pending_rewards;
+        stake_account.pending_rewards = 0;
+
+        transfer_tokens(
+            ctx.accounts.reward_vault.to_account_info(),
+            ctx.accounts.staker_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            REWARD_VAULT_SEED,
+            ctx.accounts.staking_state.reward_vault_nonce,
+            amount,
+        )?;
+
+        Ok(())
+    }
+
+    /// Returns the number of full vesting months since the Ethereum token state import.
+    /// The value is calculated the same way as in the withdraw instructions and it is returned via return data.
+    ///
+    /// ### Returns
+    /// Number of months since the vesting start, ignoring days.
+    pub fn get_months_since_start(ctx: Context<GetMonthsSinceStartContext>) -> Result<u64> {
+        let start_timestamp = ctx.accounts.vesting_state.load()?.start_timestamp;
+        require!(start_timestamp != 0, LeancoinError::VestingNotStarted);
+
+        calculate_month_difference(start_timestamp, clock::Clock::get()?.unix_timestamp)
+    }
+
+    /// Projects the mint supply after the given number of future monthly burns and returns it via return data.
+    /// The projection starts from the current mint supply and burning account balance,
+    /// the staking rewards are taken into account if staking is enabled and there are stakers.
+    ///
+    /// ### Arguments
+    ///
+    /// * `months` - the number of future monthly burns, at most `MAX_PROJECTED_MONTHS`
+    ///
+    /// ### Returns
+    /// `SupplyProjection` struct with the projected supply and the month in which the burns stop.
+    pub fn project_supply(
+        ctx: Context<ProjectSupplyContext>,
+        months: u8,
+    ) -> Result<SupplyProjection> {
+        let reward_percentage = match &ctx.accounts.staking_state {
+            Some(staking_state)
+                if ctx.accounts.contract_state.staking_enabled
+                    && staking_state.total_staked > 0 =>
+            {
+                staking_state.reward_percentage
+            }
+            _ => 0,
+        };
+
+        calculate_supply_projection(
+            ctx.accounts.mint.supply,
+            ctx.accounts.burning_account.amount,
+            ctx.accounts.contract_state.burn_remainder_numerator,
+            ctx.accounts.contract_state.burn_rate_bps,
+            reward_percentage,
+            months,
+        )
+    }
+
+    /// Returns the addresses of all program derived accounts together with their nonces via return data.
+    /// The nonces are read from the state accounts and the addresses are created from them instead of being searched for.
+    /// The labels of the program token accounts are returned as well.
+    ///
+    /// ### Returns
+    /// `ProgramAddresses` struct with the addresses, nonces and labels.
+    #[access_control(wallets_initialized(&ctx.accounts.contract_state))]
+    pub fn get_program_addresses(
+        ctx: Context<GetProgramAddressesContext>,
+    ) -> Result<ProgramAddresses> {
+        let contract_state = &ctx.accounts.contract_state;
+        let vesting_state = ctx.accounts.vesting_state.load()?;
+
+        Ok(ProgramAddresses {
+            contract_state: ctx.accounts.contract_state.key(),
+            contract_state_nonce: contract_state.contract_state_nonce,
+            vesting_state: ctx.accounts.vesting_state.key(),
+            vesting_state_nonce: vesting_state.vesting_state_nonce,
+            mint: create_program_address(MINT_SEED, contract_state.mint_nonce)?,
+            mint_nonce: contract_state.mint_nonce,
+            program_account: create_program_address(
+                PROGRAM_ACCOUNT_SEED,
+                contract_state.program_account_nonce,
+            )?,
+            program_account_nonce: contract_state.program_account_nonce,
+            burning_account: create_program_address(
+                BURNING_ACCOUNT_SEED,
+                contract_state.burning_account_nonce,
+            )?,
+            burning_account_nonce: contract_state.burning_account_nonce,
+            community_account: create_program_address(
+                COMMUNITY_ACCOUNT_SEED,
+                vesting_state.wallet(WalletKind::Community).nonce,
+            )?,
+            community_wallet_nonce: vesting_state.wallet(WalletKind::Community).nonce,
+            partnership_account: create_program_address(
+                PARTNERSHIP_ACCOUNT_SEED,
+                vesting_state.wallet(WalletKind::Partnership).nonce,
+            )?,
+            partnership_wallet_nonce: vesting_state.wallet(WalletKind::Partnership).nonce,
+            marketing_account: create_program_address(
+                MARKETING_ACCOUNT_SEED,
+                vesting_state.wallet(WalletKind::Marketing).nonce,
+            )?,
+            marketing_wallet_nonce: vesting_state.wallet(WalletKind::Marketing).nonce,
+            liquidity_account: create_program_address(
+                LIQUIDITY_ACCOUNT_SEED,
+                vesting_state.wallet(WalletKind::Liquidity).nonce,
+            )?,
+            liquidity_wallet_nonce: vesting_state.wallet(WalletKind::Liquidity).nonce,
+            labels: ctx.accounts.labels.labels,
+        })
+    }
+
+    /// Returns the token information consolidated for exchange integrations via return data.
+    /// The supply and the balances are read from the mint and the program token accounts,
+    /// the locked amounts are calculated from the vesting schedules at the current time.
+    ///
+    /// ### Returns
+    /// `TokenInfo` struct in the layout of `TOKEN_INFO_VERSION`.
+    #[access_control(wallets_initialized(&ctx.accounts.contract_state))]
+    pub fn get_token_info(ctx: Context<GetTokenInfoContext>) -> Result<TokenInfo> {
+        let contract_state = &ctx.accounts.contract_state;
+        let vesting_state = ctx.accounts.vesting_state.load()?;
+        let mint = &ctx.accounts.mint;
+        let timestamp = clock::Clock::get()?.unix_timestamp;
+
+        let wallet_accounts = [
+            &ctx.accounts.community_account,
+            &ctx.accounts.partnership_account,
+            &ctx.accounts.marketing_account,
+            &ctx.accounts.liquidity_account,
+        ];
+        let mut locked = [0; VESTED_WALLETS_COUNT];
+        let mut held_by_program = ctx
+            .accounts
+            .program_account
+            .amount
+            .saturating_add(ctx.accounts.burning_account.amount);
+        for wallet_kind in WalletKind::ALL {
+            locked[wallet_kind.index()] =
+                calculate_locked_amount(&vesting_state, wallet_kind, timestamp)?;
+            held_by_program = held_by_program.saturating_add(vested_wallet_balance(
+                &vesting_state,
+                wallet_kind,
+                wallet_accounts[wallet_kind.index()],
+            )?);
+        }
+
+        Ok(TokenInfo {
+            version: TOKEN_INFO_VERSION,
+            mint: mint.key(),
+            decimals: mint.decimals,
+            supply: mint.supply,
+            total_burned: contract_state.max_supply.saturating_sub(mint.supply),
+            circulating_supply: mint.supply.saturating_sub(held_by_program),
+            locked,
+            authority: contract_state.authority,
+            mint_authority_revoked: mint.mint_authority.is_none(),
+        })
+    }
+
+    /// Returns the unlocked, withdrawn and currently withdrawable amounts of the vested wallets via return data.
+    /// The amounts are calculated the same way as in the withdraw instructions at the current time, no account is modified.
+    ///
+    /// ### Returns
+    /// `VestingStatus` struct with the number of months since the vesting start and the status of every vested wallet.
+    #[access_control(ethereum_token_state_mapping_already_performed(&ctx.accounts.contract_state))]
+    pub fn get_vesting_status(ctx: Context<GetVestingStatusContext>) -> Result<VestingStatus> {
+        let vesting_state = ctx.accounts.vesting_state.load()?;
+        let timestamp = clock::Clock::get()?.unix_timestamp;
+        let months_since_vesting_start = months_since_vesting_start(&vesting_state, timestamp)?;
+
+        let wallet_accounts = [
+            &ctx.accounts.community_account,
+            &ctx.accounts.partnership_account,
+            &ctx.accounts.marketing_account,
+            &ctx.accounts.liquidity_account,
+        ];
+        let mut wallets = [WalletVestingStatus::default(); VESTED_WALLETS_COUNT];
+        for wallet_kind in WalletKind::ALL {
+            wallets[wallet_kind.index()] = calculate_wallet_vesting_status(
+                &vesting_state,
+                wallet_kind,
+                wallet_accounts[wallet_kind.index()],
+                timestamp,
+            )?;
+        }
+
+        Ok(VestingStatus {
+            months_since_vesting_start,
+            decimals: ctx.accounts.contract_state.decimals,
+            wallets,
+        })
+    }
+
+    /// Forecasts the amounts unlocked by the vesting curves of the vested wallets in the given month and returns them via return data.
+    /// The stored vesting schedules and initial balances are used and the clock is not read, no account is modified.
+    ///
+    /// ### Arguments
+    ///
+    /// * `months_since_start` - number of full months since the vesting start
+    ///
+    /// ### Returns
+    /// `UnlockForecast` struct with the unlocked amount of every vested wallet.
+    #[access_control(ethereum_token_state_mapping_already_performed(&ctx.accounts.contract_state))]
+    pub fn forecast_unlocked(
+        ctx: Context<ForecastUnlockedContext>,
+        months_since_start: u64,
+    ) -> Result<UnlockForecast> {
+        let vesting_state = ctx.accounts.vesting_state.load()?;
+
+        Ok(UnlockForecast {
+            months_since_vesting_start: months_since_start,
+            unlocked: forecast_unlocked_amounts(&vesting_state, months_since_start),
+        })
+    }
+
+    /// Withdraws vested tokens from the given vested wallet, if available.
+    /// The unlocked amount is calculated with the vesting curve of the wallet.
+    /// The passed wallet account must be the token account of the given wallet.
+    ///
+    /// ### Arguments
+    ///
+    /// * `wallet_kind` - the vested wallet to withdraw from
+    /// * `amount_to_withdraw` - amount of tokens to withdraw, `WITHDRAW_ALL_UNLOCKED` withdraws all tokens currently available,
+    ///   which succeeds without moving any tokens if nothing is available
+    /// * `idempotency_key` - optional key supplied by the client, the withdrawal fails if the key has already been used
+    #[access_control(valid_withdrawer(&ctx.accounts.contract_state, &ctx.accounts.vesting_state, wallet_kind, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) not_paused(&ctx.accounts.contract_state) ethereum_token_state_mapping_already_performed(&ctx.accounts.contract_state) top_level_invocation(&ctx.accounts.contract_state, &ctx.accounts.instructions_sysvar) vested_wallet_not_finalized(&ctx.accounts.vesting_state, wallet_kind) withdrawal_grace_period_passed(&ctx.accounts.contract_state, &ctx.accounts.vesting_state))]
+    pub fn withdraw_vested<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawVestedContext<'info>>,
+        wallet_kind: WalletKind,
+        amount_to_withdraw: u64,
+        idempotency_key: Option<[u8; 16]>,
+    ) -> Result<()> {
+        withdraw_from_vested_wallet(ctx, wallet_kind, amount_to_withdraw, idempotency_key)
+    }
+
+    /// Withdraws vested tokens from community wallet, if available.
+    /// 2.5% of the initial wallet's balance is unlocked every month.
+    ///
+    /// ### Arguments
+    ///
+    /// * `amount_to_withdraw` - amount of tokens to withdraw, `WITHDRAW_ALL_UNLOCKED` withdraws all tokens currently available,
+    ///   which succeeds without moving any tokens if nothing is available
+    /// * `idempotency_key` - optional key supplied by the client, the withdrawal fails if the key has already been used
+    #[access_control(valid_withdrawer(&ctx.accounts.contract_state, &ctx.accounts.vesting_state, WalletKind::Community, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) not_paused(&ctx.accounts.contract_state) ethereum_token_state_mapping_already_performed(&ctx.accounts.contract_state) top_level_invocation(&ctx.accounts.contract_state, &ctx.accounts.instructions_sysvar) vested_wallet_not_finalized(&ctx.accounts.vesting_state, WalletKind::Community) withdrawal_grace_period_passed(&ctx.accounts.contract_state, &ctx.accounts.vesting_state))]
+    pub fn withdraw_tokens_from_community_wallet<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawTokensFromCommunityWalletContext<'info>>,
+        amount_to_withdraw: u64,
+        idempotency_key: Option<[u8; 16]>,
+    ) -> Result<()> {
+        withdraw_from_vested_wallet(
+            ctx,
+            WalletKind::Community,
+            amount_to_withdraw,
+            idempotency_key,
+        )
+    }
+
+    /// Withdraws vested tokens from partnership wallet, if available.
+    /// 50% of the initial wallet's balance is unlocked after 1 month.
+    /// The remaining part is unlocked after 2 months.
+    ///
+    /// ### Arguments
+    ///
+    /// * `amount_to_withdraw` - amount of tokens to withdraw, `WITHDRAW_ALL_UNLOCKED` withdraws all tokens currently available,
+    ///   which succeeds without moving any tokens if nothing is available
+    /// * `idempotency_key` - optional key supplied by the client, the withdrawal fails if the key has already been used
+    #[access_control(valid_withdrawer(&ctx.accounts.contract_state, &ctx.accounts.vesting_state, WalletKind::Partnership, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) not_paused(&ctx.accounts.contract_state) ethereum_token_state_mapping_already_performed(&ctx.accounts.contract_state) top_level_invocation(&ctx.accounts.contract_state, &ctx.accounts.instructions_sysvar) vested_wallet_not_finalized(&ctx.accounts.vesting_state, WalletKind::Partnership) withdrawal_grace_period_passed(&ctx.accounts.contract_state, &ctx.accounts.vesting_state))]
+    pub fn withdraw_tokens_from_partnership_wallet<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawTokensFromPartnershipWalletContext<'info>>,
+        amount_to_withdraw: u64,
+        idempotency_key: Option<[u8; 16]>,
+    ) -> Result<()> {
+        withdraw_from_vested_wallet(
+            ctx,
+            WalletKind::Partnership,
+            amount_to_withdraw,
+            idempotency_key,
+        )
+    }
+
+    /// Withdraws vested tokens from marketing wallet, if available.
+    /// 40% of the initial wallet's balance is unlocked after 1 year.
+    /// Starting from the 13th month, 5% of the initial wallet's balance is unlocked every month.
+    ///
+    /// ### Arguments
+    ///
+    /// * `amount_to_withdraw` - amount of tokens to withdraw, `WITHDRAW_ALL_UNLOCKED` withdraws all tokens currently available,
+    ///   which succeeds without moving any tokens if nothing is available
+    /// * `idempotency_key` - optional key supplied by the client, the withdrawal fails if the key has already been used
+    #[access_control(valid_withdrawer(&ctx.accounts.contract_state, &ctx.accounts.vesting_state, WalletKind::Marketing, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) not_paused(&ctx.accounts.contract_state) ethereum_token_state_mapping_already_performed(&ctx.accounts.contract_state) top_level_invocation(&ctx.accounts.contract_state, &ctx.accounts.instructions_sysvar) vested_wallet_not_finalized(&ctx.accounts.vesting_state, WalletKind::Marketing) withdrawal_grace_period_passed(&ctx.accounts.contract_state, &ctx.accounts.vesting_state))]
+    pub fn withdraw_tokens_from_marketing_wallet<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawTokensFromMarketingWalletContext<'info>>,
+        amount_to_withdraw: u64,
+        idempotency_key: Option<[u8; 16]>,
+    ) -> Result<()> {
+        withdraw_from_vested_wallet(
+            ctx,
+            WalletKind::Marketing,
+            amount_to_withdraw,
+            idempotency_key,
+        )
+    }
+
+    /// Withdraws vested tokens from liquidity wallet, if available.
+    /// 50% of the initial wallet's balance is unlocked immediately.
+    /// The remaining part is unlocked after 1 year.
+    ///
+    /// ### Arguments
+    ///
+    /// * `amount_to_withdraw` - amount of tokens to withdraw, `WITHDRAW_ALL_UNLOCKED` withdraws all tokens currently available,
+    ///   which succeeds without moving any tokens if nothing is available
+    /// * `idempotency_key` - optional key supplied by the client, the withdrawal fails if the key has already been used
+    #[access_control(valid_withdrawer(&ctx.accounts.contract_state, &ctx.accounts.vesting_state, WalletKind::Liquidity, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) not_paused(&ctx.accounts.contract_state) ethereum_token_state_mapping_already_performed(&ctx.accounts.contract_state) top_level_invocation(&ctx.accounts.contract_state, &ctx.accounts.instructions_sysvar) vested_wallet_not_finalized(&ctx.accounts.vesting_state, WalletKind::Liquidity) withdrawal_grace_period_passed(&ctx.accounts.contract_state, &ctx.accounts.vesting_state))]
+    pub fn withdraw_tokens_from_liquidity_wallet<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawTokensFromLiquidityWalletContext<'info>>,
+        amount_to_withdraw: u64,
+        idempotency_key: Option<[u8; 16]>,
+    ) -> Result<()> {
+        withdraw_from_vested_wallet(
+            ctx,
+            WalletKind::Liquidity,
+            amount_to_withdraw,
+            idempotency_key,
+        )
+    }
+
+    /// Withdraws all unlocked tokens from every vested wallet in a single instruction, so the monthly withdrawals need one transaction.
+    /// The available amounts are calculated the same way as in the per-wallet withdraw instructions,
+    /// the wallets with nothing available are skipped. A single `AllUnlockedTokensWithdrawn` event is emitted
+    /// and every wallet gets a withdrawal receipt, like in the per-wallet withdraw instructions.
+    /// It can be called by the contract's owner or the withdraw authority.
+    #[access_control(valid_role(&ctx.accounts.contract_state, Role::Withdraw, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) not_paused(&ctx.accounts.contract_state) ethereum_token_state_mapping_already_performed(&ctx.accounts.contract_state) top_level_invocation(&ctx.accounts.contract_state, &ctx.accounts.instructions_sysvar) withdrawal_grace_period_passed(&ctx.accounts.contract_state, &ctx.accounts.vesting_state))]
+    pub fn withdraw_all_unlocked(ctx: Context<WithdrawAllUnlockedContext>) -> Result<()> {
+        withdraw_all_unlocked_tokens(ctx)
+    }
+
+    /// Closes the withdrawal intent account of a withdrawal with an idempotency key and returns its rent to the payer.
+    /// The account can be closed only after the retention period, the key can be reused afterwards.
+    ///
+    /// ### Arguments
+    ///
+    /// * `idempotency_key` - the idempotency key of the withdrawal
+    pub fn close_withdrawal_intent(
+        ctx: Context<CloseWithdrawalIntentContext>,
+        _idempotency_key: [u8; 16],
+    ) -> Result<()> {
+        require!(
+            clock::Clock::get()?.unix_timestamp
+                >= ctx.accounts.withdrawal_intent.created_timestamp
+                    + WITHDRAWAL_INTENT_RETENTION_PERIOD,
+            LeancoinError::WithdrawalIntentNotExpired
+        );
+
+        Ok(())
+    }
+
+    /// Sets new authority
+    ///
+    /// ### Arguments
+    ///
+    /// * `new_authority` - new authority
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer))]
+    pub fn change_authority<'info>(
+        ctx: Context<'_, '_, '_, 'info, ChangeAuthorityContext<'info>>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let contract_state = &mut ctx.accounts.contract_state;
+        let sequence = next_sequence(contract_state)?;
+        let old_authority = contract_state.authority;
+        contract_state.authority = new_authority;
+
+        emit!(AuthorityChanged {
+            old_authority,
+            new_authority,
+            sequence,
+        });
+
+        Ok(())
+    }
+
+    /// Allows or forbids invoking the burn and withdraw instructions via CPI.
+    /// By default the instructions can be invoked only directly by the transaction.
+    ///
+    /// ### Arguments
+    ///
+    /// * `cpi_allowed` - true if the instructions can be invoked via CPI, false otherwise
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer))]
+    pub fn set_cpi_allowed(ctx: Context<SetCpiAllowedContext>, cpi_allowed: bool) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        ctx.accounts.contract_state.cpi_allowed = cpi_allowed;
+
+        Ok(())
+    }
+
+    /// Pauses or unpauses the withdrawals from the vested wallets and the burning.
+    /// While paused, the burn, withdraw, liquidity drip and OTC deal creation instructions fail,
+    /// the administrative instructions like change_authority are still allowed.
+    ///
+    /// ### Arguments
+    ///
+    /// * `paused` - true to pause the withdrawals and burning, false to resume them
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer))]
+    pub fn set_paused(ctx: Context<SetPausedContext>, paused: bool) -> Result<()> {
+        let contract_state = &mut ctx.accounts.contract_state;
+        let sequence = next_sequence(contract_state)?;
+        contract_state.paused = paused;
+
+        emit!(PauseStateChanged { paused, sequence });
+
+        Ok(())
+    }
+
+    /// Assigns the role to the authority which can then run the instructions of the role besides the owner:
+    /// the burn role can burn and configure the secondary burn window, the withdraw role can withdraw from the vested wallets
+    /// and the metadata role can create and update the token metadata. change_authority and the other instructions stay restricted to the owner.
+    ///
+    /// ### Arguments
+    ///
+    /// * `role` - the role to assign
+    /// * `authority` - the authority of the role, the default public key unassigns the role
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer))]
+    pub fn set_role(ctx: Context<SetRoleContext>, role: Role, authority: Pubkey) -> Result<()> {
+        let contract_state = &mut ctx.accounts.contract_state;
+        let sequence = next_sequence(contract_state)?;
+        *contract_state.role_authority_mut(role) = authority;
+
+        emit!(RoleChanged {
+            role,
+            authority,
+            sequence
+        });
+
+        Ok(())
+    }
+
+    /// Permanently renounces the authority over the contract, the contract then runs autonomously.
+    /// The authority is set to the default public key and every instruction restricted to the owner fails afterwards,
+    /// including change_authority and renounce_authority itself. The role authorities are unassigned as well.
+    /// This cannot be undone.
+    /// The automation instructions like burn can then be run by any signer because the keepers can no longer be managed.
+    /// The contract must not be paused, otherwise the withdrawals and burning would stay paused forever.
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) not_paused(&ctx.accounts.contract_state))]
+    pub fn renounce_authority(ctx: Context<RenounceAuthorityContext>) -> Result<()> {
+        let contract_state = &mut ctx.accounts.contract_state;
+        let sequence = next_sequence(contract_state)?;
+        let old_authority = contract_state.authority;
+        contract_state.authority = Pubkey::default();
+        contract_state.renounced = true;
+        for role in Role::ALL {
+            *contract_state.role_authority_mut(role) = Pubkey::default();
+        }
+
+        emit!(AuthorityChanged {
+            old_authority,
+            new_authority: Pubkey::default(),
+            sequence,
+        });
+
+        Ok(())
+    }
+
+    /// Revokes the mint authority of the token, so no tokens can ever be minted beyond the imported supply.
+    /// It can be called only after the Ethereum token state import. The imports from the other migration sources,
+    /// which mint their tokens, fail afterwards. This cannot be undone, a repeated call fails with `MintAuthorityRevoked`.
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) import_performed(&ctx.accounts.contract_state, MigrationSource::Ethereum) mint_authority_not_revoked(&ctx.accounts.contract_state))]
+    pub fn revoke_mint_authority(ctx: Context<RevokeMintAuthorityContext>) -> Result<()> {
+        let contract_state = &mut ctx.accounts.contract_state;
+        next_sequence(contract_state)?;
+
+        let seeds = &[MINT_SEED.as_bytes(), &[contract_state.mint_nonce]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = SetAuthority {
+            current_authority: ctx.accounts.mint.to_account_info(),
+            account_or_mint: ctx.accounts.mint.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::set_authority(cpi_ctx, AuthorityType::MintTokens, None)?;
+
+        contract_state.mint_authority_revoked = true;
+
+        Ok(())
+    }
+
+    /// Registers a keeper allowed to run the automation instructions: burn, execute_liquidity_drip and attest_reserves.
+    /// The contract's owner can always run them, at most `MAX_KEEPERS` other keepers can be registered.
+    ///
+    /// ### Arguments
+    ///
+    /// * `keeper` - the keeper to register
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer))]
+    pub fn add_keeper(ctx: Context<AddKeeperContext>, keeper: Pubkey) -> Result<()> {
+        let contract_state = &mut ctx.accounts.contract_state;
+        let sequence = next_sequence(contract_state)?;
+
+        require!(
+            keeper != Pubkey::default() && !contract_state.keepers.contains(&keeper),
+            LeancoinError::KeeperAlreadyRegistered
+        );
+        let slot = contract_state
+            .keepers
+            .iter_mut()
+            .find(|slot| **slot == Pubkey::default())
+            .ok_or(LeancoinError::TooManyKeepers)?;
+        *slot = keeper;
+
+        emit!(KeeperAdded { keeper, sequence });
+
+        Ok(())
+    }
+
+    /// Unregisters a keeper, so it cannot run the automation instructions anymore.
+    ///
+    /// ### Arguments
+    ///
+    /// * `keeper` - the keeper to unregister
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer))]
+    pub fn remove_keeper(ctx: Context<RemoveKeeperContext>, keeper: Pubkey) -> Result<()> {
+        let contract_state = &mut ctx.accounts.contract_state;
+        let sequence = next_sequence(contract_state)?;
+
+        let slot = contract_state
+            .keepers
+            .iter_mut()
+            .find(|slot| keeper != Pubkey::default() && **slot == keeper)
+            .ok_or(LeancoinError::KeeperNotRegistered)?;
+        *slot = Pubkey::default();
+
+        emit!(KeeperRemoved { keeper, sequence });
+
+        Ok(())
+    }
+
+    /// Configures the reward of the burn caller in basis points of the burned amount.
+    /// The reward is transferred to the token account passed by the caller of the burn and the rest is burned, zero disables it.
+    /// It can be called only by the contract's owner.
+    ///
+    /// ### Arguments
+    ///
+    /// * `burn_caller_reward_bps` - the reward in basis points, at most `MAX_BURN_CALLER_REWARD_BPS`
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) valid_burn_caller_reward(burn_caller_reward_bps))]
+    pub fn set_burn_caller_reward(
+        ctx: Context<SetBurnCallerRewardContext>,
+        burn_caller_reward_bps: u16,
+    ) -> Result<()> {
+        let contract_state = &mut ctx.accounts.contract_state;
+        next_sequence(contract_state)?;
+        contract_state.burn_caller_reward_bps = burn_caller_reward_bps;
+
+        Ok(())
+    }
+
+    /// Configures the number of days of the primary burn window starting on the 1st day of the month.
+    /// The days of the secondary burn window falling into the extended primary window are no longer treated as late.
+    /// It can be called only by the contract's owner.
+    ///
+    /// ### Arguments
+    ///
+    /// * `burn_window_days` - the number of days of the primary burn window, at least one and at most `MAX_BURN_WINDOW_DAYS`
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) valid_burn_window(burn_window_days))]
+    pub fn set_burn_window(ctx: Context<SetBurnWindowContext>, burn_window_days: u8) -> Result<()> {
+        let contract_state = &mut ctx.accounts.contract_state;
+        next_sequence(contract_state)?;
+        contract_state.burn_window_days = burn_window_days;
+
+        Ok(())
+    }
+
+    /// Configures the secondary window in which the monthly burn missed in the primary window can be executed.
+    /// The window must start after the primary window and end at the latest on the 28th day of the month.
+    /// It can be called only by the contract's owner and only before the configuration is locked.
+    ///
+    /// ### Arguments
+    ///
+    /// * `first_day` - the first day of the secondary burn window
+    /// * `last_day` - the last day of the secondary burn window
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) configuration_not_locked(&ctx.accounts.contract_state) valid_late_burn_window(&ctx.accounts.contract_state, first_day, last_day))]
+    pub fn set_late_burn_window(
+        ctx: Context<SetLateBurnWindowContext>,
+        first_day: u8,
+        last_day: u8,
+    ) -> Result<()> {
+        let contract_state = &mut ctx.accounts.contract_state;
+        next_sequence(contract_state)?;
+        contract_state.late_burn_window_first_day = first_day;
+        contract_state.late_burn_window_last_day = last_day;
+
+        Ok(())
+    }
+
+    /// Configures the rate of the monthly burn in basis points of the burning account balance.
+    /// The rate applies from the next burn, the fraction carried over from the previous burns is kept.
+    /// It can be called only by the contract's owner.
+    ///
+    /// ### Arguments
+    ///
+    /// * `burn_rate_bps` - the burn rate in basis points, greater than zero and at most `BURN_RATE_DENOMINATOR`
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) valid_burn_rate(burn_rate_bps))]
+    pub fn set_burn_rate(ctx: Context<SetBurnRateContext>, burn_rate_bps: u16) -> Result<()> {
+        let contract_state = &mut ctx.accounts.contract_state;
+        next_sequence(contract_state)?;
+        contract_state.burn_rate_bps = burn_rate_bps;
+
+        Ok(())
+    }
+
+    /// Sets the beneficiary owner of one of the vested wallets.
+    /// When configured, tokens withdrawn from the wallet can be transferred only to token accounts owned by the beneficiary owner.
+    /// Setting the default public key disables the check.
+    ///
+    /// ### Arguments
+    ///
+    /// * `wallet_kind` - the vested wallet to configure
+    /// * `beneficiary_owner` - the required owner of deposit wallets
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer))]
+    pub fn set_beneficiary_owner(
+        ctx: Context<SetBeneficiaryOwnerContext>,
+        wallet_kind: WalletKind,
+        beneficiary_owner: Pubkey,
+    ) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        let mut vesting_state = ctx.accounts.vesting_state.load_mut()?;
+
+        vesting_state.wallet_mut(wallet_kind).beneficiary_owner = beneficiary_owner;
+
+        Ok(())
+    }
+
+    /// Sets the beneficiary of one of the vested wallets.
+    /// The beneficiary can withdraw vested tokens from the wallet besides the contract's owner.
+    /// Setting the default public key leaves the withdrawals to the contract's owner only, which is the default.
+    ///
+    /// ### Arguments
+    ///
+    /// * `wallet_kind` - the vested wallet to configure
+    /// * `beneficiary` - the account allowed to withdraw from the wallet
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer))]
+    pub fn set_wallet_beneficiary(
+        ctx: Context<SetWalletBeneficiaryContext>,
+        wallet_kind: WalletKind,
+        beneficiary: Pubkey,
+    ) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        let mut vesting_state = ctx.accounts.vesting_state.load_mut()?;
+
+        vesting_state.beneficiaries[wallet_kind.index()] = beneficiary;
+
+        Ok(())
+    }
+
+    /// Locks the configuration of the contract.
+    /// After the lock, the allocation of the vested wallets cannot be rebalanced anymore and the secondary burn window cannot be changed.
+    /// The lock is irreversible.
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) configuration_not_locked(&ctx.accounts.contract_state))]
+    pub fn lock_configuration(ctx: Context<LockConfigurationContext>) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        ctx.accounts.contract_state.configuration_locked = true;
+
+        Ok(())
+    }
+
+    /// Transfers tokens directly between two vested wallets together with their vesting allocation.
+    /// The initial balance of the source wallet is decreased and the initial balance of the destination wallet is increased by the amount,
+    /// so the unlocked amounts of both wallets are calculated from the new initial balances.
+    /// The amount already withdrawn from the source wallet must not exceed its new initial balance.
+    /// The wallets can be rebalanced only after Ethereum token state import and before the configuration is locked.
+    ///
+    /// ### Arguments
+    ///
+    /// * `from` - the source vested wallet
+    /// * `to` - the destination vested wallet
+    /// * `amount` - amount of tokens to move
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) configuration_not_locked(&ctx.accounts.contract_state))]
+    pub fn rebalance(
+        ctx: Context<RebalanceContext>,
+        from: WalletKind,
+        to: WalletKind,
+        amount: u64,
+    ) -> Result<()> {
+        let sequence = next_sequence(&mut ctx.accounts.contract_state)?;
+        require!(from != to, LeancoinError::RebalanceToSameWallet);
+        require!(amount > 0, LeancoinError::NotEnoughTokens);
+
+        vested_wallet_not_finalized(&ctx.accounts.vesting_state, from)?;
+        vested_wallet_not_finalized(&ctx.accounts.vesting_state, to)?;
+
+        let mut vesting_state = ctx.accounts.vesting_state.load_mut()?;
+        require!(
+            vesting_state.start_timestamp != 0,
+            LeancoinError::VestingNotStarted
+        );
+
+        let initial_from_wallet_balance = vesting_state
+            .wallet(from)
+            .initial_balance
+            .checked_sub(amount)
+            .ok_or(LeancoinError::NotEnoughTokens)?;
+        require!(
+            vesting_state.wallet(from).withdrawn <= initial_from_wallet_balance,
+            LeancoinError::WithdrawnAmountExceedsInitialBalance
+        );
+        let initial_to_wallet_balance = vesting_state.wallet(to).initial_balance + amount;
+
+        vesting_state.wallet_mut(from).initial_balance = initial_from_wallet_balance;
+        vesting_state.wallet_mut(to).initial_balance = initial_to_wallet_balance;
+        let from_wallet_nonce = vesting_state.wallet(from).nonce;
+
+        transfer_tokens(
+            ctx.accounts.wallet_account(from),
+            ctx.accounts.wallet_account(to),
+            ctx.accounts.token_program.to_account_info(),
+            from.seed(),
+            from_wallet_nonce,
+            amount,
+        )?;
+
+        emit!(WalletsRebalanced {
+            from,
+            to,
+            amount,
+            initial_from_wallet_balance,
+            initial_to_wallet_balance,
+            sequence,
+        });
+
+        Ok(())
+    }
+
+    /// Sets the human-readable label of one of the program token accounts, e.g. to be displayed by explorers.
+    /// The label can be changed only before the configuration is locked.
+    ///
+    /// ### Arguments
+    ///
+    /// * `account_kind` - the labeled program token account
+    /// * `label` - UTF-8 encoded label, at most 32 bytes long
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) configuration_not_locked(&ctx.accounts.contract_state))]
+    pub fn set_label(
+        ctx: Context<SetLabelContext>,
+        account_kind: LabeledAccountKind,
+        label: Vec<u8>,
+    ) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        ctx.accounts.labels.labels[account_kind.index()] = encode_label(&label)?;
+
+        Ok(())
+    }
+
+    /// Finalizes a vested wallet whose schedule is complete, or whose unvested tokens have been revoked, and whose tokens have all been withdrawn.
+    /// The token account of the wallet is closed and its rent is transferred to the destination account.
+    /// Withdrawals from a finalized wallet fail.
+    ///
+    /// ### Arguments
+    ///
+    /// * `wallet_kind` - the vested wallet to finalize
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) vested_wallet_not_finalized(&ctx.accounts.vesting_state, wallet_kind))]
+    pub fn finalize_vested_wallet(
+        ctx: Context<FinalizeVestedWalletContext>,
+        wallet_kind: WalletKind,
+    ) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        let mut vesting_state = ctx.accounts.vesting_state.load_mut()?;
+        let initial_wallet_balance = vesting_state.wallet(wallet_kind).initial_balance;
+        let unlocked_amount = calculate_wallet_unlocked_amount(
+            &vesting_state,
+            wallet_kind,
+            clock::Clock::get()?.unix_timestamp,
+        )?;
+
+        require!(
+            unlocked_amount == initial_wallet_balance || vesting_state.revoked(wallet_kind),
+            LeancoinError::WalletNotFullyVested
+        );
+        require!(
+            ctx.accounts.wallet_account.amount == 0,
+            LeancoinError::WalletBalanceIsNotZero
+        );
+
+        vesting_state.finalized_wallets |= wallet_kind.mask();
+
+        close_token_account(
+            ctx.accounts.wallet_account.to_account_info(),
+            ctx.accounts.destination.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            wallet_kind.seed(),
+            vesting_state.wallet(wallet_kind).nonce,
+        )
+    }
+
+    /// Revokes the unvested tokens of a vested wallet, e.g. when a partnership falls through.
+    /// The tokens which have not been unlocked yet are transferred to the destination, limited by the balance of the wallet,
+    /// and the unlocked amount of the wallet is frozen at its current value, so the wallet can be withdrawn from only up to it.
+    /// A wallet can be revoked only once.
+    ///
+    /// ### Arguments
+    ///
+    /// * `wallet_kind` - the vested wallet to revoke
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) vested_wallet_not_finalized(&ctx.accounts.vesting_state, wallet_kind) vested_wallet_not_revoked(&ctx.accounts.vesting_state, wallet_kind))]
+    pub fn revoke_unvested(
+        ctx: Context<RevokeUnvestedContext>,
+        wallet_kind: WalletKind,
+    ) -> Result<()> {
+        let sequence = next_sequence(&mut ctx.accounts.contract_state)?;
+        let mut vesting_state = ctx.accounts.vesting_state.load_mut()?;
+        let unlocked_amount = calculate_wallet_unlocked_amount(
+            &vesting_state,
+            wallet_kind,
+            clock::Clock::get()?.unix_timestamp,
+        )?;
+        let locked_amount = vesting_state
+            .wallet(wallet_kind)
+            .initial_balance
+            .saturating_sub(unlocked_amount);
+
+        vesting_state.revoked_wallets |= wallet_kind.mask();
+        vesting_state.revoked_unlocked[wallet_kind.index()] = unlocked_amount;
+
+        let amount = transfer_tokens(
+            ctx.accounts.wallet_account.to_account_info(),
+            ctx.accounts.destination.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            wallet_kind.seed(),
+            vesting_state.wallet(wallet_kind).nonce,
+            locked_amount.min(ctx.accounts.wallet_account.amount),
+        )?;
+
+        emit!(VestingRevoked {
+            wallet_kind,
+            amount,
+            destination: ctx.accounts.destination.key(),
+            unlocked_total: unlocked_amount,
+            sequence,
+        });
+
+        Ok(())
+    }
+
+    /// Tops up a vested wallet: transfers tokens of the signer to the wallet and adds them to its initial balance,
+    /// so the vesting schedule of the wallet is applied to the new total. The share of the added tokens which corresponds
+    /// to the already unlocked percentage is withdrawable immediately.
+    ///
+    /// ### Arguments
+    ///
+    /// * `wallet_kind` - the vested wallet to top up
+    /// * `amount` - amount of tokens to add
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) ethereum_token_state_mapping_already_performed(&ctx.accounts.contract_state) vested_wallet_not_finalized(&ctx.accounts.vesting_state, wallet_kind) vested_wallet_not_revoked(&ctx.accounts.vesting_state, wallet_kind))]
+    pub fn add_to_vesting(
+        ctx: Context<AddToVestingContext>,
+        wallet_kind: WalletKind,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, LeancoinError::NotEnoughTokens);
+        let sequence = next_sequence(&mut ctx.accounts.contract_state)?;
+        let mut vesting_state = ctx.accounts.vesting_state.load_mut()?;
+        let wallet = vesting_state.wallet_mut(wallet_kind);
+        wallet.initial_balance = wallet
+            .initial_balance
+            .checked_add(amount)
+            .ok_or(LeancoinError::InitialBalanceOverflow)?;
+        let initial_balance = wallet.initial_balance;
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.source.to_account_info(),
+            to: ctx.accounts.wallet_account.to_account_info(),
+            authority: ctx.accounts.signer.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        emit!(VestingToppedUp {
+            wallet_kind,
+            amount,
+            initial_balance,
+            sequence,
+        });
+
+        Ok(())
+    }
+
+    /// Approves the delegate allowed to transfer tokens out of a vested wallet, e.g. a market maker or an escrow program.
+    /// The allowance is bounded by the unlocked amount which has not been withdrawn yet and it is counted as withdrawn
+    /// when it is approved, so the withdrawals cannot spend the delegated tokens.
+    /// Approving a new delegate replaces the previous one, the unused allowance of the previous delegate is released.
+    /// Like the withdrawals, the delegate cannot be approved during the withdrawal grace period.
+    ///
+    /// ### Arguments
+    ///
+    /// * `wallet_kind` - the vested wallet
+    /// * `delegate` - the account approved as the delegate
+    /// * `allowance` - the amount of tokens the delegate is allowed to transfer
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) vested_wallet_not_finalized(&ctx.accounts.vesting_state, wallet_kind) withdrawal_grace_period_passed(&ctx.accounts.contract_state, &ctx.accounts.vesting_state))]
+    pub fn approve_wallet_delegate(
+        ctx: Context<ApproveWalletDelegateContext>,
+        wallet_kind: WalletKind,
+        delegate: Pubkey,
+        allowance: u64,
+    ) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        let mut vesting_state = ctx.accounts.vesting_state.load_mut()?;
+        require!(
+            vesting_state.start_timestamp != 0,
+            LeancoinError::VestingNotStarted
+        );
+
+        let wallet_account = &ctx.accounts.wallet_account;
+        let wallet_delegation = &mut ctx.accounts.wallet_delegation;
+        wallet_delegation.wallet_delegation_nonce = *ctx.bumps.get("wallet_delegation").unwrap();
+        reconcile_wallet_delegation(
+            wallet_delegation,
+            wallet_account.delegate,
+            wallet_account.delegated_amount,
+        );
+
+        let wallet = vesting_state.wallet_mut(wallet_kind);
+        wallet.withdrawn = wallet
+            .withdrawn
+            .checked_sub(wallet_delegation.allowance)
+            .ok_or(LeancoinError::NotEnoughTokens)?;
+
+        let unlocked_amount = calculate_wallet_unlocked_amount(
+            &vesting_state,
+            wallet_kind,
+            clock::Clock::get()?.unix_timestamp,
+        )?;
+        let amount_available_to_delegate = wallet_account.amount.min(remaining_unlocked_amount(
+            &vesting_state,
+            wallet_kind,
+            unlocked_amount,
+        )?);
+        require!(
+            allowance <= amount_available_to_delegate,
+            LeancoinError::not_enough_tokens(wallet_kind)
+        );
+
+        record_withdrawal(&mut vesting_state, wallet_kind, allowance)?;
+        wallet_delegation.delegate = delegate;
+        wallet_delegation.allowance = allowance;
+
+        approve_delegate(
+            ctx.accounts.wallet_account.to_account_info(),
+            ctx.accounts.delegate.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            wallet_kind.seed(),
+            vesting_state.wallet(wallet_kind).nonce,
+            allowance,
+        )
+    }
+
+    /// Accounts the tokens transferred by the delegate of a vested wallet since the last reconciliation.
+    /// The transferred tokens are already counted as withdrawn, the reconciliation records them in the wallet delegation
+    /// account so the remaining allowance can be released when the delegate is revoked. Anyone can reconcile the delegation.
+    ///
+    /// ### Arguments
+    ///
+    /// * `wallet_kind` - the vested wallet
+    pub fn reconcile_wallet_delegate(
+        ctx: Context<WalletDelegationContext>,
+        _wallet_kind: WalletKind,
+    ) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        reconcile_wallet_delegation(
+            &mut ctx.accounts.wallet_delegation,
+            ctx.accounts.wallet_account.delegate,
+            ctx.accounts.wallet_account.delegated_amount,
+        );
+
+        Ok(())
+    }
+
+    /// Revokes the delegate of a vested wallet.
+    /// The tokens transferred by the delegate are reconciled and the unused allowance is released,
+    /// so it can be withdrawn or delegated again.
+    ///
+    /// ### Arguments
+    ///
+    /// * `wallet_kind` - the vested wallet
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer))]
+    pub fn revoke_wallet_delegate(
+        ctx: Context<WalletDelegationContext>,
+        wallet_kind: WalletKind,
+    ) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        let mut vesting_state = ctx.accounts.vesting_state.load_mut()?;
+
+        let wallet_account = &ctx.accounts.wallet_account;
+        let wallet_delegation = &mut ctx.accounts.wallet_delegation;
+        reconcile_wallet_delegation(
+            wallet_delegation,
+            wallet_account.delegate,
+            wallet_account.delegated_amount,
+        );
+
+        let wallet = vesting_state.wallet_mut(wallet_kind);
+        wallet.withdrawn = wallet
+            .withdrawn
+            .checked_sub(wallet_delegation.allowance)
+            .ok_or(LeancoinError::NotEnoughTokens)?;
+        wallet_delegation.delegate = Pubkey::default();
+        wallet_delegation.allowance = 0;
+
+        revoke_delegate(
+            ctx.accounts.wallet_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            wallet_kind.seed(),
+            vesting_state.wallet(wallet_kind).nonce,
+        )
+    }
+
+    /// Converts the vesting state account created in one of the previous layouts to the current layout:
+    /// the borsh layout used before `VestingState` became zero-copy, the zero-copy layout with separate fields for every wallet
+    /// or the layouts with per-wallet states without the swap wallet balance, the vesting schedules, the beneficiaries,
+    /// the withdrawal history, the receipt indexes or the revoked amounts.
+    /// The account is resized to the new layout and the signer pays for the additional rent.
+    /// The migration can be performed only once, the accounts created by `initialize_state` are already in the current layout.
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer))]
+    pub fn migrate_vesting_state(ctx: Context<MigrateVestingStateContext>) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        let vesting_state_info = ctx.accounts.vesting_state.to_account_info();
+        let outdated_vesting_state = read_outdated_vesting_state(&vesting_state_info)?;
+
+        vesting_state_info.realloc(
+            DISCRIMINATOR_LEN + std::mem::size_of::<VestingState>(),
+            true,
+        )?;
+        transfer_rent_shortfall(
+            vesting_state_info.clone(),
+            ctx.accounts.signer.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        let vesting_state = AccountLoader::<VestingState>::try_from(&vesting_state_info)?;
+        *vesting_state.load_mut()? = outdated_vesting_state;
+
+        Ok(())
+    }
+
+    /// Converts the contract state account created in the deployed borsh layout to the current layout with fixed field offsets.
+    /// The fields missing in the deployed layout are set to their defaults, see `read_outdated_contract_state`.
+    /// The account is resized to the new layout and the signer pays for the additional rent.
+    /// It must be called before any other instruction since the deployed layout cannot be loaded as `ContractState`.
+    /// The migration can be performed only once, the accounts created by `initialize_state` are already in the current layout.
+    #[access_control(valid_signer(&ctx.accounts.signer))]
+    pub fn migrate_contract_state(ctx: Context<MigrateContractStateContext>) -> Result<()> {
+        let contract_state_info = ctx.accounts.contract_state.to_account_info();
+        let mut contract_state = read_outdated_contract_state(&contract_state_info)?;
+        require_keys_eq!(
+            contract_state.authority,
+            ctx.accounts.signer.key(),
+            LeancoinError::Unauthorized
+        );
+
+        next_sequence(&mut contract_state)?;
+
+        contract_state_info.realloc(DISCRIMINATOR_LEN + ContractState::INIT_SPACE, false)?;
+        transfer_rent_shortfall(
+            contract_state_info.clone(),
+            ctx.accounts.signer.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        contract_state.try_serialize(&mut &mut contract_state_info.try_borrow_mut_data()?[..])
+    }
+
+    /// Transfers the lamports missing to make a state account rent-exempt from the signer to the account.
+    /// It repairs state accounts left under the rent-exempt minimum, e.g. by an underfunded reallocation.
+    /// `migrate_vesting_state` tops up the vesting state the same way automatically.
+    /// This function can be called by anyone.
+    ///
+    /// ### Arguments
+    ///
+    /// * `account_kind` - the state account to top up
+    pub fn top_up_rent(
+        ctx: Context<TopUpRentContext>,
+        _account_kind: StateAccountKind,
+    ) -> Result<()> {
+        let shortfall = transfer_rent_shortfall(
+            ctx.accounts.state_account.to_account_info(),
+            ctx.accounts.signer.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        )?;
+        require!(shortfall > 0, LeancoinError::AlreadyRentExempt);
+
+        Ok(())
+    }
+
+    /// Sets the token metadata program id used to set the token metadata.
+    /// It can be used on clusters where the metadata program is deployed under a different address.
+    /// The program id can be changed only before Ethereum token state import.
+    ///
+    /// ### Arguments
+    ///
+    /// * `metadata_program_id` - new token metadata program id
+    #[cfg(feature = "metadata")]
+    #[access_control(valid_owner(&ctx.accounts.contract_state, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) ethereum_token_state_mapping_not_performed_yet(&ctx.accounts.contract_state))]
+    pub fn set_metadata_program_id(
+        ctx: Context<SetMetadataProgramIdContext>,
+        metadata_program_id: Pubkey,
+    ) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        ctx.accounts.contract_state.metadata_program_id = metadata_program_id;
+
+        Ok(())
+    }
+
+    /// Creates the token metadata with the mint PDA as the mint and update authority.
+    /// It can be called by the contract's owner or the metadata authority.
+    /// The name, the symbol and the uri must fit in the limits of the token metadata program.
+    /// Breaking change in version 0.2.0: the existing metadata is updated by update_token_metadata instead of a `TokenMetadataAction` argument.
+    ///
+    /// ### Arguments
+    ///
+    /// * `name` - new token name
+    /// * `symbol` - new token symbol
+    /// * `uri` - new token uri
+    #[cfg(feature = "metadata")]
+    #[access_control(valid_role(&ctx.accounts.contract_state, Role::Metadata, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) valid_token_metadata(&name, &symbol, &uri) valid_metadata_pda(ctx.accounts.metadata_pda.key, ctx.accounts.metadata_program.key, &ctx.accounts.mint.key()))]
+    pub fn set_token_metadata(
+        ctx: Context<SetTokenMetadataContext>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        let program_id = ctx.accounts.metadata_program.to_account_info();
+        let metadata_pda = ctx.accounts.metadata_pda.to_account_info();
+        let mint = ctx.accounts.mint.to_account_info();
+        let mint_authority = ctx.accounts.mint.to_account_info();
+        let payer = ctx.accounts.signer.to_account_info();
+        let update_authority = ctx.accounts.mint.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+
+        let seeds = &[
+            MINT_SEED.as_bytes(),
+            &[ctx.accounts.contract_state.mint_nonce],
+        ];
+
+        let account_infos = &[
+            program_id.clone(),
+            metadata_pda.clone(),
+            mint.clone(),
+            mint_authority.clone(),
+            payer.clone(),
+            update_authority.clone(),
+            system_program.clone(),
+        ];
+
+        let create_metadata_accounts_instruction = create_metadata_accounts_v3(
+            *program_id.key,
+            *metadata_pda.key,
+            *mint.key,
+            *mint_authority.key,
+            *payer.key,
+            *update_authority.key,
+            name,
+            symbol,
+            uri,
+            None,
+            0u16,
+            false,
+            true,
+            None,
+            None,
+            None,
+        );
+
+        invoke_signed(
+            &create_metadata_accounts_instruction,
+            account_infos,
+            &[seeds],
+        )?;
+
+        Ok(())
+    }
+
+    /// Updates the name, the symbol and the uri of the token metadata created by set_token_metadata,
+    /// e.g. when the host of the logo changes. The mint PDA signs as the update authority.
+    /// It can be called by the contract's owner or the metadata authority.
+    /// It fails with `TokenMetadataNotCreated` if the metadata has not been created yet.
+    ///
+    /// ### Arguments
+    ///
+    /// * `name` - new token name
+    /// * `symbol` - new token symbol
+    /// * `uri` - new token uri
+    #[cfg(feature = "metadata")]
+    #[access_control(valid_role(&ctx.accounts.contract_state, Role::Metadata, &ctx.accounts.signer) valid_signer(&ctx.accounts.signer) valid_token_metadata(&name, &symbol, &uri) valid_metadata_pda(ctx.accounts.metadata_pda.key, ctx.accounts.metadata_program.key, &ctx.accounts.mint.key()))]
+    pub fn update_token_metadata(
+        ctx: Context<UpdateTokenMetadataContext>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        next_sequence(&mut ctx.accounts.contract_state)?;
+        let program_id = ctx.accounts.metadata_program.to_account_info();
+        let metadata_pda = ctx.accounts.metadata_pda.to_account_info();
+        let update_authority = ctx.accounts.mint.to_account_info();
+
+        let seeds = &[
+            MINT_SEED.as_bytes(),
+            &[ctx.accounts.contract_state.mint_nonce],
+        ];
+
+        let data = DataV2 {
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points: 0u16,
+            creators: None,
+            collection: None,
+            uses: None,
+        };
+
+        let update_metadata_accounts_instruction = update_metadata_accounts_v2(
+            *program_id.key,
+            *metadata_pda.key,
+            *update_authority.key,
+            Some(*update_authority.key),
+            Some(data),
+            None,
+            Some(true),
+        );
+
+        invoke_signed(
+            &update_metadata_accounts_instruction,
+            &[program_id, metadata_pda, update_authority],
+            &[seeds],
+        )?;
+
+        Ok(())
+    }
+}
+
+/// structure for storing information about the account, the balance is expressed with decimals of the source token
+/// it is used by imports from all migration sources, not only from Ethereum
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AccountInfoFromEthereum {
+    pub wallet_name: String,
+    pub account_public_key: Pubkey,
+    pub account_balance: u128,
+}
+
+/// structure returned by the get_program_addresses instruction
+/// it contains the addresses of all program derived accounts together with their nonces stored in the state accounts
+/// and the labels of the program token accounts ordered like `LabeledAccountKind`, padded with zeros
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ProgramAddresses {
+    pub contract_state: Pubkey,
+    pub contract_state_nonce: u8,
+    pub vesting_state: Pubkey,
+    pub vesting_state_nonce: u8,
+    pub mint: Pubkey,
+    pub mint_nonce: u8,
+    pub program_account: Pubkey,
+    pub program_account_nonce: u8,
+    pub burning_account: Pubkey,
+    pub burning_account_nonce: u8,
+    pub community_account: Pubkey,
+    pub community_wallet_nonce: u8,
+    pub partnership_account: Pubkey,
+    pub partnership_wallet_nonce: u8,
+    pub marketing_account: Pubkey,
+    pub marketing_wallet_nonce: u8,
+    pub liquidity_account: Pubkey,
+    pub liquidity_wallet_nonce: u8,
+    pub labels: [[u8; LABEL_LENGTH]; LABELED_ACCOUNTS_COUNT],
+}
+
+/// structure passed to `execute_burn` with the values of the `BurnExecuted` event which differ between the burn instructions
+/// the month and the year are the ones the burn is recorded for, the rewards have already been transferred by the caller
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BurnDetails {
+    pub sequence: u64,
+    pub timestamp: i64,
+    pub month: u8,
+    pub year: i64,
+    pub rewards: u64,
+    pub caller_reward: u64,
+    pub late: bool,
+    pub burn_rate_bps: u16,
+    pub manual: bool,
+}
+
+/// structure returned by the project_supply instruction
+/// the floor month is the first projected month in which the burning account is empty, `None` if the burns continue in all projected months
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct SupplyProjection {
+    pub supply: u64,
+    pub burned: u64,
+    pub burning_account_balance: u64,
+    pub floor_month: Option<u8>,
+}
+
+/// structure returned by the get_token_info instruction, its layout is identified by the version (see `TOKEN_INFO_VERSION`)
+/// the fields of the version 1 layout in the order of serialization:
+/// - the address and the decimals of the mint,
+/// - the current supply of the mint,
+/// - the total amount of burned tokens, i.e. all imported tokens minus the current supply,
+/// - the circulating supply, i.e. the supply minus the balances of the program, burning and vested wallet accounts,
+/// - the amounts of the vested wallets which have not been unlocked yet, ordered like `WalletKind`,
+/// - the contract's authority,
+/// - information whether the mint authority has been revoked, so no more tokens can be minted
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct TokenInfo {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub supply: u64,
+    pub total_burned: u64,
+    pub circulating_supply: u64,
+    pub locked: [u64; VESTED_WALLETS_COUNT],
+    pub authority: Pubkey,
+    pub mint_authority_revoked: bool,
+}
+
+/// structure returned by the get_vesting_status instruction
+/// - the number of full months since the vesting start used to calculate the unlocked amounts,
+/// - the number of decimals of the mint the amounts are expressed with,
+/// - the vesting status of the vested wallets, ordered like `WalletKind`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct VestingStatus {
+    pub months_since_vesting_start: u64,
+    pub decimals: u8,
+    pub wallets: [WalletVestingStatus; VESTED_WALLETS_COUNT],
+}
+
+/// vesting status of a single vested wallet
+/// - the amount unlocked by the vesting curve of the wallet,
+/// - the amount already withdrawn from the wallet,
+/// - the amount which can be withdrawn now, i.e. the unlocked amount not withdrawn yet, limited by the balance of the wallet
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct WalletVestingStatus {
+    pub unlocked: u64,
+    pub withdrawn: u64,
+    pub withdrawable: u64,
+}
+
+/// structure returned by the forecast_unlocked instruction
+/// the amounts unlocked by the vesting curves of the vested wallets in the given month, ordered like `WalletKind`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct UnlockForecast {
+    pub months_since_vesting_start: u64,
+    pub unlocked: [u64; VESTED_WALLETS_COUNT],
+}
+
+/// a row of the vesting table built by `utils::vesting_table`
+/// the amounts unlocked by the vesting curves of the vested wallets in the month, ordered like `WalletKind`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct MonthRow {
+    pub month: u64,
+    pub unlocked: [u64; VESTED_WALLETS_COUNT],
+}
+
+/// The `MigrationSource` enum is used to indicate the chain whose token state is imported.
+///
+/// * `Ethereum` - The original Ethereum token, its import sets the initial balances of the vested wallets.
+/// * `Bsc` - The BSC-based community token absorbed after the Ethereum migration.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub enum MigrationSource {
+    Ethereum,
+    Bsc,
+}
+
+impl MigrationSource {
+    /// Returns the index of the source in the per-source arrays of the contract state.
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// Returns the bit of the source in the bitmask of performed imports.
+    pub fn mask(&self) -> u8 {
+        1 << self.index()
+    }
+}
+
+/// The `WalletKind` enum is used to indicate which of the wallets affected by vesting mechanism is concerned.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub enum WalletKind {
+    Community,
+    Partnership,
+    Marketing,
+    Liquidity,
+}
+
+/// The `Role` enum is used to indicate which of the delegated powers of the owner is concerned, see `set_role`.
+///
+/// * `Burn` - The monthly and the manual burns.
+/// * `Withdraw` - The withdrawals from the vested wallets.
+/// * `Metadata` - The token metadata.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(not(target_os = "solana"), derive(serde::Serialize))]
+pub enum Role {
+    Burn,
+    Withdraw,
+    Metadata,
+}
+
+impl Role {
+    /// All roles.
+    pub const ALL: [Role; 3] = [Role::Burn, Role::Withdraw, Role::Metadata];
+}
+
+/// The `LabeledAccountKind` enum is used to indicate which of the program token accounts is labeled.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LabeledAccountKind {
+    ProgramAccount,
+    BurningAccount,
+    Community,
+    Partnership,
+    Marketing,
+    Liquidity,
+}
+
+impl LabeledAccountKind {
+    /// All labeled program token accounts.
+    pub const ALL: [LabeledAccountKind; LABELED_ACCOUNTS_COUNT] = [
+        LabeledAccountKind::ProgramAccount,
+        LabeledAccountKind::BurningAccount,
+        LabeledAccountKind::Community,
+        LabeledAccountKind::Partnership,
+        LabeledAccountKind::Marketing,
+        LabeledAccountKind::Liquidity,
+    ];
+
+    /// Returns the index of the account in the labels array.
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// Returns the label set during initialization.
+    pub fn default_label(&self) -> &'static str {
+        match self {
+            LabeledAccountKind::ProgramAccount => "Leancoin: Program Account",
+            LabeledAccountKind::BurningAccount => "Leancoin: Burning Account",
+            LabeledAccountKind::Community => "Leancoin: Community Vesting",
+            LabeledAccountKind::Partnership => "Leancoin: Partnership Vesting",
+            LabeledAccountKind::Marketing => "Leancoin: Marketing Vesting",
+            LabeledAccountKind::Liquidity => "Leancoin: Liquidity Vesting",
+        }
+    }
+
+    /// Returns the seed of the account's PDA.
+    pub fn seed(&self) -> &'static str {
+        match self {
+            LabeledAccountKind::ProgramAccount => PROGRAM_ACCOUNT_SEED,
+            LabeledAccountKind::BurningAccount => BURNING_ACCOUNT_SEED,
+            LabeledAccountKind::Community => COMMUNITY_ACCOUNT_SEED,
+            LabeledAccountKind::Partnership => PARTNERSHIP_ACCOUNT_SEED,
+            LabeledAccountKind::Marketing => MARKETING_ACCOUNT_SEED,
+            LabeledAccountKind::Liquidity => LIQUIDITY_ACCOUNT_SEED,
+        }
+    }
+}
+
+impl WalletKind {
+    /// All vested wallets.
+    pub const ALL: [WalletKind; VESTED_WALLETS_COUNT] = [
+        WalletKind::Community,
+        WalletKind::Partnership,
+        WalletKind::Marketing,
+        WalletKind::Liquidity,
+    ];
+
+    /// Returns the index of the wallet in the array of per-wallet vesting states.
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    /// Returns the bit of the wallet in the bitmasks of finalized wallets and wallets with linear daily unlock.
+    pub fn mask(&self) -> u8 {
+        1 << (*self as u8)
+    }
+
+    /// Returns the seed of the wallet's PDA.
+    pub fn seed(&self) -> &'static str {
+        match self {
+            WalletKind::Community => COMMUNITY_ACCOUNT_SEED,
+            WalletKind::Partnership => PARTNERSHIP_ACCOUNT_SEED,
+            WalletKind::Marketing => MARKETING_ACCOUNT_SEED,
+            WalletKind::Liquidity => LIQUIDITY_ACCOUNT_SEED,
+        }
+    }
+}
+
+/// The `StateAccountKind` enum is used to indicate which of the program state accounts is concerned.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StateAccountKind {
+    ContractState,
+    VestingState,
+}
+
+impl StateAccountKind {
+    /// Returns the seed of the state account's PDA.
+    pub fn seed(&self) -> &'static str {
+        match self {
+            StateAccountKind::ContractState => CONTRACT_STATE_SEED,
+            StateAccountKind::VestingState => VESTING_STATE_SEED,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::account::{
+        Attestation, BurnHistory, BurnRecord, ContractState, DonorAccount, LegacyContractState,
+        LegacyVestingState, OtcDeal, VestingSchedule, VestingState, VestingStateV1,
+        WalletDelegation, WithdrawalHistory, WithdrawalIntent, WithdrawalReceipt,
+        CONTRACT_STATE_VERSION, VESTING_STATE_V2_SPACE, VESTING_STATE_V3_SPACE,
+        VESTING_STATE_V4_SPACE, VESTING_STATE_V5_SPACE, VESTING_STATE_V6_SPACE,
+        VESTING_STATE_V7_SPACE,
+    };
+    use crate::error_codes::LeancoinError;
+    use crate::event::{
+        AllUnlockedTokensWithdrawn, AuthorityChanged, BurnDonation, BurnExecuted,
+        BurningAccountFunded, ImportCompleted, Initialized, KeeperAdded, KeeperRemoved,
+        PauseStateChanged, RoleChanged, VestedTokensWithdrawn, VestingRevoked, VestingToppedUp,
+    };
+    use crate::events::{parse_events, LeancoinEvent};
+    use crate::utils::{
+        calculate_month_difference, calculate_supply_projection, calculate_unlocked_amount,
+        encode_label, vesting_schedule, WHOLE_BALANCE,
+    };
+
+    use anchor_lang::{
+        prelude::Clock, system_program, Discriminator, Event, InstructionData, Space,
+        ToAccountMetas,
+    };
+    use anchor_spl::token::spl_token;
+    use solana_program::instruction::AccountMeta;
+    use spl_token::state::{Account, AccountState};
+
+    use crate::context::__client_accounts_change_authority_context::ChangeAuthorityContext;
+    use crate::context::__client_accounts_close_withdrawal_intent_context::CloseWithdrawalIntentContext;
+    use crate::context::__client_accounts_get_months_since_start_context::GetMonthsSinceStartContext;
+    use crate::context::__client_accounts_get_program_addresses_context::GetProgramAddressesContext;
+    use crate::context::__client_accounts_get_token_info_context::GetTokenInfoContext;
+    use crate::context::__client_accounts_get_vesting_status_context::GetVestingStatusContext;
+
+    use crate::context::__client_accounts_begin_import_context::BeginImportContext;
+    use crate::context::__client_accounts_finalize_import_context::FinalizeImportContext;
+    use crate::context::__client_accounts_forecast_unlocked_context::ForecastUnlockedContext;
+    use crate::context::__client_accounts_import_batch_context::ImportBatchContext;
+    use crate::context::__client_accounts_import_token_state_context::ImportTokenStateContext;
+    use crate::context::__client_accounts_initialize_burn_history_context::InitializeBurnHistoryContext;
+    use crate::context::__client_accounts_initialize_state_context::InitializeStateContext;
+    use crate::context::__client_accounts_initialize_wallets_context::InitializeWalletsContext;
+    use crate::context::__client_accounts_set_beneficiary_owner_context::SetBeneficiaryOwnerContext;
+    use crate::context::__client_accounts_set_burn_caller_reward_context::SetBurnCallerRewardContext;
+    use crate::context::__client_accounts_set_burn_rate_context::SetBurnRateContext;
+    use crate::context::__client_accounts_set_burn_window_context::SetBurnWindowContext;
+    use crate::context::__client_accounts_set_label_context::SetLabelContext;
+    use crate::context::__client_accounts_set_late_burn_window_context::SetLateBurnWindowContext;
+    #[cfg(feature = "metadata")]
+    use crate::context::__client_accounts_set_metadata_program_id_context::SetMetadataProgramIdContext;
+    #[cfg(feature = "metadata")]
+    use crate::context::__client_accounts_set_token_metadata_context::SetTokenMetadataContext;
+    use crate::context::__client_accounts_set_wallet_beneficiary_context::SetWalletBeneficiaryContext;
+    #[cfg(feature = "metadata")]
+    use crate::context::__client_accounts_update_token_metadata_context::UpdateTokenMetadataContext;
+    use crate::context::__client_accounts_withdraw_all_unlocked_context::WithdrawAllUnlockedContext;
+    use crate::context::__client_accounts_withdraw_tokens_from_community_wallet_context::WithdrawTokensFromCommunityWalletContext;
+    use crate::context::__client_accounts_withdraw_tokens_from_liquidity_wallet_context::WithdrawTokensFromLiquidityWalletContext;
+    use crate::context::__client_accounts_withdraw_tokens_from_marketing_wallet_context::WithdrawTokensFromMarketingWalletContext;
+    use crate::context::__client_accounts_withdraw_tokens_from_partnership_wallet_context::WithdrawTokensFromPartnershipWalletContext;
+    use crate::context::__client_accounts_withdraw_vested_context::WithdrawVestedContext;
+    #[cfg(feature = "metadata")]
+    use mpl_token_metadata::state::{Metadata, TokenMetadataAccount};
+
+    use crate::context::__client_accounts_add_keeper_context::AddKeeperContext;
+    use crate::context::__client_accounts_add_to_vesting_context::AddToVestingContext;
+    use crate::context::__client_accounts_approve_wallet_delegate_context::ApproveWalletDelegateContext;
+    use crate::context::__client_accounts_attest_reserves_context::AttestReservesContext;
+    use crate::context::__client_accounts_burn_context::BurnContext;
+    use crate::context::__client_accounts_burn_exact_context::BurnExactContext;
+    use crate::context::__client_accounts_cancel_otc_deal_context::CancelOtcDealContext;
+    use crate::context::__client_accounts_catch_up_burn_context::CatchUpBurnContext;
+    use crate::context::__client_accounts_claim_rewards_context::ClaimRewardsContext;
+    use crate::context::__client_accounts_configure_liquidity_drip_context::ConfigureLiquidityDripContext;
+    use crate::context::__client_accounts_create_otc_deal_context::CreateOtcDealContext;
+    use crate::context::__client_accounts_donate_to_burn_context::DonateToBurnContext;
+    use crate::context::__client_accounts_execute_liquidity_drip_context::ExecuteLiquidityDripContext;
+    use crate::context::__client_accounts_finalize_vested_wallet_context::FinalizeVestedWalletContext;
+    use crate::context::__client_accounts_fund_burning_account_context::FundBurningAccountContext;
+    use crate::context::__client_accounts_initialize_staking_context::InitializeStakingContext;
+    use crate::context::__client_accounts_lock_configuration_context::LockConfigurationContext;
+    use crate::context::__client_accounts_migrate_contract_state_context::MigrateContractStateContext;
+    use crate::context::__client_accounts_migrate_vesting_state_context::MigrateVestingStateContext;
+    use crate::context::__client_accounts_project_supply_context::ProjectSupplyContext;
+    use crate::context::__client_accounts_rebalance_context::RebalanceContext;
+    use crate::context::__client_accounts_recover_foreign_tokens_context::RecoverForeignTokensContext;
+    use crate::context::__client_accounts_recover_program_account_tokens_context::RecoverProgramAccountTokensContext;
+    use crate::context::__client_accounts_remove_keeper_context::RemoveKeeperContext;
+    use crate::context::__client_accounts_renounce_authority_context::RenounceAuthorityContext;
+    use crate::context::__client_accounts_revoke_mint_authority_context::RevokeMintAuthorityContext;
+    use crate::context::__client_accounts_revoke_unvested_context::RevokeUnvestedContext;
+    use crate::context::__client_accounts_set_paused_context::SetPausedContext;
+    use crate::context::__client_accounts_set_role_context::SetRoleContext;
+    use crate::context::__client_accounts_settle_otc_deal_context::SettleOtcDealContext;
+    use crate::context::__client_accounts_stake_context::StakeContext;
+    use crate::context::__client_accounts_top_up_rent_context::TopUpRentContext;
+    use crate::context::__client_accounts_unstake_context::UnstakeContext;
+    use crate::context::__client_accounts_wallet_delegation_context::WalletDelegationContext;
+    use crate::context::__client_accounts_withdraw_unstaked_context::WithdrawUnstakedContext;
+
+    use solana_program::{
+        account_info::AccountInfo,
+        entrypoint::ProgramResult,
+        hash::{hashv, Hash},
+        instruction::{Instruction, InstructionError},
+        program::invoke,
+        program_option::COption,
+        program_pack::Pack,
+        system_instruction, sysvar,
+    };
+    use solana_program_test::*;
+
+    use solana_sdk::{
+        commitment_config::CommitmentLevel,
+        compute_budget::ComputeBudgetInstruction,
+        signature::Keypair,
+        signer::Signer,
+        transaction::{Transaction, TransactionError},
+    };
+
+    const IMPORT_HEAP_FRAME_SIZE: u32 = 256 * 1024;
+    const IMPORT_BATCH_SIZE: usize = 25;
+    const IMPORT_BATCH_COMPUTE_UNITS: u64 =
+        IMPORT_BATCH_SIZE as u64 * IMPORT_PER_ENTRY_COMPUTE_BUDGET;
+
+    /// compute unit budgets of the instructions, see `process_transaction_within_compute_budget`
+    #[cfg(feature = "test-bpf")]
+    const BURN_COMPUTE_BUDGET: u64 = 40_000;
+    #[cfg(feature = "test-bpf")]
+    const WITHDRAW_COMPUTE_BUDGET: u64 = 60_000;
+    #[cfg(feature = "test-bpf")]
+    const WITHDRAW_ALL_COMPUTE_BUDGET: u64 = 150_000;
+    const IMPORT_PER_ENTRY_COMPUTE_BUDGET: u64 = 8_000;
+    /// percentage by which an instruction can exceed its compute unit budget
+    #[cfg(feature = "test-bpf")]
+    const COMPUTE_BUDGET_TOLERANCE_PERCENTAGE: u64 = 10;
+
+    async fn initialize_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+    ) -> Result<()> {
+        initialize_state_instruction(banks_client, payer, recent_blockhash).await?;
+        initialize_wallets_instruction(banks_client, payer, recent_blockhash).await?;
+        banks_client
+            .process_transaction(initialize_burn_history_transaction(payer, recent_blockhash))
+            .await
+            .unwrap();
+
+        Ok(())
+    }
+
+    async fn initialize_state_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+    ) -> Result<()> {
+        banks_client
+            .process_transaction_with_commitment(
+                initialize_state_transaction(payer, payer, recent_blockhash, LEGACY_MINT_DECIMALS),
+                CommitmentLevel::Finalized,
+            )
+            .await
+            .unwrap();
+
+        Ok(())
+    }
+
+    fn initialize_state_transaction(
+        payer: &Keypair,
+        signer: &Keypair,
+        recent_blockhash: Hash,
+        decimals: u8,
+    ) -> Transaction {
+        let program_id = id();
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            mint,
+            _,
+            program_account,
+            _,
+            burning_account,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+        ) = get_pda_accounts();
+
+        let token_program = spl_token::id();
+
+        let data = instruction::InitializeState { decimals }.data();
+
+        let accs = InitializeStateContext {
+            contract_state,
+            vesting_state,
+            mint,
+            program_account,
+            burning_account,
+            token_program,
+            signer: signer.pubkey(),
+            payer: payer.pubkey(),
+            system_program: system_program::ID,
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        if signer.pubkey() == payer.pubkey() {
+            transaction.sign(&[payer], recent_blockhash);
+        } else {
+            transaction.sign(&[payer, signer], recent_blockhash);
+        }
+        transaction
+    }
+
+    async fn initialize_wallets_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+    ) -> Result<()> {
+        banks_client
+            .process_transaction_with_commitment(
+                initialize_wallets_transaction(payer, recent_blockhash, None, None),
+                CommitmentLevel::Finalized,
+            )
+            .await
+            .unwrap();
+
+        Ok(())
+    }
+
+    fn initialize_wallets_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        vesting_schedules: Option<[VestingSchedule; VESTED_WALLETS_COUNT]>,
+        linear_daily_unlock: Option<[bool; VESTED_WALLETS_COUNT]>,
+    ) -> Transaction {
+        initialize_wallets_by_transaction(
+            payer,
+            payer,
+            recent_blockhash,
+            vesting_schedules,
+            linear_daily_unlock,
+        )
+    }
+
+    fn initialize_wallets_by_transaction(
+        payer: &Keypair,
+        signer: &Keypair,
+        recent_blockhash: Hash,
+        vesting_schedules: Option<[VestingSchedule; VESTED_WALLETS_COUNT]>,
+        linear_daily_unlock: Option<[bool; VESTED_WALLETS_COUNT]>,
+    ) -> Transaction {
+        let program_id = id();
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            mint,
+            _,
+            _,
+            _,
+            _,
+            _,
+            community_account,
+            _,
+            partnership_account,
+            _,
+            marketing_account,
+            _,
+            liquidity_account,
+            _,
+        ) = get_pda_accounts();
+
+        let token_program = spl_token::id();
+
+        let data = instruction::InitializeWallets {
+            vesting_schedules,
+            linear_daily_unlock,
+        }
+        .data();
+
+        let accs = InitializeWalletsContext {
+            contract_state,
+            vesting_state,
+            mint,
+            community_account,
+            partnership_account,
+            marketing_account,
+            liquidity_account,
+            labels: get_labels_account(),
+            token_program,
+            signer: signer.pubkey(),
+            payer: payer.pubkey(),
+            system_program: system_program::ID,
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        if signer.pubkey() == payer.pubkey() {
+            transaction.sign(&[payer], recent_blockhash);
+        } else {
+            transaction.sign(&[payer, signer], recent_blockhash);
+        }
+        transaction
+    }
+
+    fn import_ethereum_token_state_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+    ) -> Transaction {
+        import_ethereum_token_state_with_vesting_parameters_transaction(
+            payer,
+            recent_blockhash,
+            0,
+            0,
+        )
+    }
+
+    fn import_ethereum_token_state_with_vesting_parameters_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        community_unlock_delay_months: u8,
+        withdrawal_grace_period_seconds: i64,
+    ) -> Transaction {
+        let program_id = id();
+
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            mint,
+            _,
+            program_account,
+            _,
+            burning_account,
+            _,
+            community_account,
+            _,
+            partnership_account,
+            _,
+            marketing_account,
+            _,
+            liquidity_account,
+            _,
+        ) = get_pda_accounts();
+
+        let token_program = spl_token::id();
+        let signer = payer.pubkey();
+
+        let account_info_from_ethereum = get_accounts_to_mapping();
+        let amount_token_to_mint = 10000000000000000000000000000;
+        let amount_token_to_burn = 1470000000000000000000000000;
+
+        let data = instruction::ImportEthereumTokenState {
+            account_info_from_ethereum,
+            amount_token_to_mint,
+            amount_token_to_burn,
+            source_decimals: 18,
+            community_unlock_delay_months,
+            withdrawal_grace_period_seconds,
+        }
+        .data();
+
+        let accs = ImportTokenStateContext {
+            contract_state,
+            vesting_state,
+            mint,
+            program_account,
+            token_program,
+            signer,
+        };
+
+        let mut accounts = accs.to_account_metas(Some(false));
+        accounts.push(AccountMeta::new(burning_account, false));
+        accounts.push(AccountMeta::new(community_account, false));
         accounts.push(AccountMeta::new(partnership_account, false));
         accounts.push(AccountMeta::new(marketing_account, false));
         accounts.push(AccountMeta::new(liquidity_account, false));
 
-        let mut transaction = Transaction::new_with_payer(
-            &[Instruction::new_with_bytes(program_id, &data, accounts)],
-            Some(&payer.pubkey()),
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                ComputeBudgetInstruction::request_heap_frame(IMPORT_HEAP_FRAME_SIZE),
+                Instruction::new_with_bytes(program_id, &data, accounts),
+            ],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+
+        transaction
+    }
+
+    fn import_external_token_state_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        source: MigrationSource,
+        entries: Vec<AccountInfoFromEthereum>,
+        amount_token_to_mint: u128,
+        amount_token_to_burn: u128,
+    ) -> Transaction {
+        let program_id = id();
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            mint,
+            _,
+            program_account,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+        ) = get_pda_accounts();
+
+        let remaining_accounts = entries
+            .iter()
+            .map(|entry| AccountMeta::new(entry.account_public_key, false))
+            .collect::<Vec<AccountMeta>>();
+
+        let data = instruction::ImportExternalTokenState {
+            source,
+            entries,
+            amount_token_to_mint,
+            amount_token_to_burn,
+            source_decimals: 18,
+            community_unlock_delay_months: 0,
+            withdrawal_grace_period_seconds: 0,
+        }
+        .data();
+
+        let accs = ImportTokenStateContext {
+            contract_state,
+            vesting_state,
+            mint,
+            program_account,
+            token_program: spl_token::id(),
+            signer: payer.pubkey(),
+        };
+
+        let mut accounts = accs.to_account_metas(Some(false));
+        accounts.extend(remaining_accounts);
+
+        let mut transaction = Transaction::new_with_payer(
+            &[
+                ComputeBudgetInstruction::request_heap_frame(IMPORT_HEAP_FRAME_SIZE),
+                Instruction::new_with_bytes(program_id, &data, accounts),
+            ],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+
+        transaction
+    }
+
+    async fn import_external_token_state_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        source: MigrationSource,
+        entries: Vec<AccountInfoFromEthereum>,
+        amount_token_to_mint: u128,
+        amount_token_to_burn: u128,
+    ) -> Result<()> {
+        let transaction = import_external_token_state_transaction(
+            payer,
+            recent_blockhash,
+            source,
+            entries,
+            amount_token_to_mint,
+            amount_token_to_burn,
+        );
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        Ok(())
+    }
+
+    async fn import_ethereum_token_state_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+    ) -> Result<()> {
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            _,
+            mint_nonce,
+            _,
+            program_account_nonce,
+            _,
+            _,
+            community_account,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+        ) = get_pda_accounts();
+
+        let signer = payer.pubkey();
+        let transaction = import_ethereum_token_state_transaction(payer, recent_blockhash);
+
+        banks_client
+            .process_transaction_with_commitment(transaction.clone(), CommitmentLevel::Finalized)
+            .await
+            .unwrap();
+
+        let contract_state_info = banks_client
+            .get_account_with_commitment(contract_state, CommitmentLevel::Finalized)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let contract_state: ContractState =
+            ContractState::try_deserialize_unchecked(&mut contract_state_info.data.as_slice())
+                .unwrap();
+
+        assert_eq!(
+            contract_state.performed_imports,
+            MigrationSource::Ethereum.mask()
+        );
+        assert_eq!(
+            contract_state.imported_minted[MigrationSource::Ethereum.index()],
+            10000000000000000000
+        );
+        assert_eq!(
+            contract_state.imported_burned[MigrationSource::Ethereum.index()],
+            1470000000000000000
+        );
+        assert_eq!(contract_state.authority, signer);
+        assert_eq!(contract_state.last_burning_month, 0);
+        assert_eq!(contract_state.last_burning_year, 0);
+        assert_eq!(contract_state.mint_nonce, mint_nonce);
+        assert_eq!(contract_state.program_account_nonce, program_account_nonce);
+
+        let vesting_state_info = banks_client
+            .get_account_with_commitment(vesting_state, CommitmentLevel::Finalized)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let vesting_state: VestingState =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                .unwrap();
+
+        assert_eq!(
+            vesting_state.wallet(WalletKind::Community).initial_balance,
+            1000000000000000000
+        );
+        assert_eq!(
+            vesting_state
+                .wallet(WalletKind::Partnership)
+                .initial_balance,
+            2000000000000000000
+        );
+        assert_eq!(
+            vesting_state.wallet(WalletKind::Marketing).initial_balance,
+            1500000000000000000
+        );
+        assert_eq!(
+            vesting_state.wallet(WalletKind::Liquidity).initial_balance,
+            1000000000000000000
+        );
+        assert_eq!(
+            get_token_balance(banks_client, &community_account).await,
+            1000000000000000000
+        );
+
+        Ok(())
+    }
+
+    fn begin_import_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        entries_count: u16,
+    ) -> Transaction {
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            mint,
+            _,
+            program_account,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+        ) = get_pda_accounts();
+        let (import_progress, _) =
+            Pubkey::find_program_address(&[IMPORT_PROGRESS_SEED.as_bytes()], &id());
+
+        let data = instruction::BeginImport {
+            source: MigrationSource::Ethereum,
+            entries_count,
+            amount_token_to_mint: 10000000000000000000000000000,
+            amount_token_to_burn: 1470000000000000000000000000,
+            source_decimals: 18,
+            community_unlock_delay_months: 0,
+            withdrawal_grace_period_seconds: 0,
+        }
+        .data();
+
+        let accounts = BeginImportContext {
+            contract_state,
+            vesting_state,
+            mint,
+            program_account,
+            import_progress,
+            token_program: spl_token::id(),
+            signer: payer.pubkey(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(Some(false));
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(id(), &data, accounts)],
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[payer], recent_blockhash);
+
+        transaction
+    }
+
+    /// Builds the import_batch transaction passing only the given accounts of the batch entries as the remaining accounts.
+    fn import_batch_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        first_index: u16,
+        entries: &[AccountInfoFromEthereum],
+        funded_accounts: &[Pubkey],
+    ) -> Transaction {
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            mint,
+            _,
+            program_account,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+        ) = get_pda_accounts();
+        let (import_progress, _) =
+            Pubkey::find_program_address(&[IMPORT_PROGRESS_SEED.as_bytes()], &id());
+
+        let data = instruction::ImportBatch {
+            first_index,
+            entries: entries
+                .iter()
+                .map(|entry| AccountInfoFromEthereum {
+                    wallet_name: entry.wallet_name.clone(),
+                    account_public_key: entry.account_public_key,
+                    account_balance: entry.account_balance,
+                })
+                .collect(),
+        }
+        .data();
+
+        let mut accounts = ImportBatchContext {
+            contract_state,
+            vesting_state,
+            mint,
+            program_account,
+            import_progress,
+            token_program: spl_token::id(),
+            signer: payer.pubkey(),
+        }
+        .to_account_metas(Some(false));
+        accounts.extend(
+            funded_accounts
+                .iter()
+                .map(|account| AccountMeta::new(*account, false)),
+        );
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(id(), &data, accounts)],
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[payer], recent_blockhash);
+
+        transaction
+    }
+
+    fn finalize_import_transaction(payer: &Keypair, recent_blockhash: Hash) -> Transaction {
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            _,
+            _,
+            program_account,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+        ) = get_pda_accounts();
+        let (import_progress, _) =
+            Pubkey::find_program_address(&[IMPORT_PROGRESS_SEED.as_bytes()], &id());
+
+        let accounts = FinalizeImportContext {
+            contract_state,
+            vesting_state,
+            program_account,
+            import_progress,
+            signer: payer.pubkey(),
+        }
+        .to_account_metas(Some(false));
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &instruction::FinalizeImport {}.data(),
+                accounts,
+            )],
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[payer], recent_blockhash);
+
+        transaction
+    }
+
+    fn burn_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        staking_enabled: bool,
+    ) -> Transaction {
+        burn_by_keeper_transaction(payer, payer, recent_blockhash, staking_enabled)
+    }
+
+    fn burn_by_keeper_transaction(
+        payer: &Keypair,
+        keeper: &Keypair,
+        recent_blockhash: Hash,
+        staking_enabled: bool,
+    ) -> Transaction {
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        burn_with_mint_transaction(payer, keeper, recent_blockhash, staking_enabled, mint, None)
+    }
+
+    fn burn_with_mint_transaction(
+        payer: &Keypair,
+        keeper: &Keypair,
+        recent_blockhash: Hash,
+        staking_enabled: bool,
+        mint: Pubkey,
+        caller_token_account: Option<Pubkey>,
+    ) -> Transaction {
+        let program_id = id();
+        let (staking_state, _, reward_vault) = get_staking_pda_accounts();
+
+        let (contract_state, _, _, _, _, _, _, _, burning_account, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let token_program = spl_token::id();
+
+        let data = instruction::Burn {}.data();
+
+        let accs = BurnContext {
+            contract_state,
+            mint,
+            burning_account,
+            token_program,
+            instructions_sysvar: sysvar::instructions::ID,
+            staking_state: staking_enabled.then_some(staking_state),
+            reward_vault: staking_enabled.then_some(reward_vault),
+            signer: keeper.pubkey(),
+            caller_token_account,
+            burn_history: get_burn_history_account(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        if keeper.pubkey() == payer.pubkey() {
+            transaction.sign(&[payer], recent_blockhash);
+        } else {
+            transaction.sign(&[payer, keeper], recent_blockhash);
+        }
+        transaction
+    }
+
+    fn catch_up_burn_transaction(payer: &Keypair, recent_blockhash: Hash) -> Transaction {
+        let (contract_state, _, _, _, mint, _, _, _, burning_account, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::CatchUpBurn {}.data();
+
+        let accs = CatchUpBurnContext {
+            mint,
+            contract_state,
+            burning_account,
+            token_program: spl_token::id(),
+            instructions_sysvar: sysvar::instructions::ID,
+            staking_state: None,
+            reward_vault: None,
+            signer: payer.pubkey(),
+            burn_history: get_burn_history_account(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    fn burn_exact_transaction(payer: &Keypair, recent_blockhash: Hash, amount: u64) -> Transaction {
+        let (contract_state, _, _, _, mint, _, _, _, burning_account, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::BurnExact { amount }.data();
+
+        let accs = BurnExactContext {
+            mint,
+            contract_state,
+            burning_account,
+            token_program: spl_token::id(),
+            signer: payer.pubkey(),
+            burn_history: get_burn_history_account(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    fn initialize_burn_history_transaction(payer: &Keypair, recent_blockhash: Hash) -> Transaction {
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::InitializeBurnHistory {}.data();
+
+        let accs = InitializeBurnHistoryContext {
+            contract_state,
+            burn_history: get_burn_history_account(),
+            payer: payer.pubkey(),
+            system_program: system_program::ID,
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    fn add_keeper_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        keeper: Pubkey,
+    ) -> Transaction {
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::AddKeeper { keeper }.data();
+
+        let accs = AddKeeperContext {
+            contract_state,
+            signer: payer.pubkey(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    fn remove_keeper_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        keeper: Pubkey,
+    ) -> Transaction {
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::RemoveKeeper { keeper }.data();
+
+        let accs = RemoveKeeperContext {
+            contract_state,
+            signer: payer.pubkey(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    fn set_paused_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        paused: bool,
+    ) -> Transaction {
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::SetPaused { paused }.data();
+
+        let accs = SetPausedContext {
+            contract_state,
+            signer: payer.pubkey(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    fn set_burn_rate_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        burn_rate_bps: u16,
+    ) -> Transaction {
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::SetBurnRate { burn_rate_bps }.data();
+
+        let accs = SetBurnRateContext {
+            contract_state,
+            signer: payer.pubkey(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    fn set_burn_caller_reward_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        burn_caller_reward_bps: u16,
+    ) -> Transaction {
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::SetBurnCallerReward {
+            burn_caller_reward_bps,
+        }
+        .data();
+
+        let accs = SetBurnCallerRewardContext {
+            contract_state,
+            signer: payer.pubkey(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    fn set_burn_window_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        burn_window_days: u8,
+    ) -> Transaction {
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::SetBurnWindow { burn_window_days }.data();
+
+        let accs = SetBurnWindowContext {
+            contract_state,
+            signer: payer.pubkey(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    fn set_late_burn_window_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        first_day: u8,
+        last_day: u8,
+    ) -> Transaction {
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::SetLateBurnWindow {
+            first_day,
+            last_day,
+        }
+        .data();
+
+        let accs = SetLateBurnWindowContext {
+            contract_state,
+            signer: payer.pubkey(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    fn change_authority_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        new_authority: Pubkey,
+    ) -> Transaction {
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::ChangeAuthority { new_authority }.data();
+
+        let accs = ChangeAuthorityContext {
+            contract_state,
+            signer: payer.pubkey(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    fn set_role_transaction(
+        payer: &Keypair,
+        signer: &Keypair,
+        recent_blockhash: Hash,
+        role: Role,
+        authority: Pubkey,
+    ) -> Transaction {
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::SetRole { role, authority }.data();
+
+        let accs = SetRoleContext {
+            contract_state,
+            signer: signer.pubkey(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        if signer.pubkey() == payer.pubkey() {
+            transaction.sign(&[payer], recent_blockhash);
+        } else {
+            transaction.sign(&[payer, signer], recent_blockhash);
+        }
+        transaction
+    }
+
+    fn renounce_authority_transaction(payer: &Keypair, recent_blockhash: Hash) -> Transaction {
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::RenounceAuthority {}.data();
+
+        let accs = RenounceAuthorityContext {
+            contract_state,
+            signer: payer.pubkey(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    fn revoke_mint_authority_transaction(payer: &Keypair, recent_blockhash: Hash) -> Transaction {
+        let (contract_state, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::RevokeMintAuthority {}.data();
+
+        let accs = RevokeMintAuthorityContext {
+            contract_state,
+            mint,
+            signer: payer.pubkey(),
+            token_program: spl_token::id(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    fn recover_program_account_tokens_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        destination: Pubkey,
+        amount: u64,
+    ) -> Transaction {
+        let (contract_state, _, _, _, _, _, program_account, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+        let (import_progress, _) =
+            Pubkey::find_program_address(&[IMPORT_PROGRESS_SEED.as_bytes()], &id());
+
+        let data = instruction::RecoverProgramAccountTokens { amount }.data();
+
+        let accs = RecoverProgramAccountTokensContext {
+            contract_state,
+            program_account,
+            import_progress,
+            destination,
+            signer: payer.pubkey(),
+            token_program: spl_token::id(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    fn recover_foreign_tokens_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        account_kind: LabeledAccountKind,
+        source_authority: Pubkey,
+        foreign_token_account: Pubkey,
+        destination: Pubkey,
+    ) -> Transaction {
+        let (contract_state, _, vesting_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::RecoverForeignTokens { account_kind }.data();
+
+        let accs = RecoverForeignTokensContext {
+            contract_state,
+            vesting_state,
+            source_authority,
+            foreign_token_account,
+            destination,
+            signer: payer.pubkey(),
+            token_program: spl_token::id(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    async fn burn_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        staking_enabled: bool,
+    ) -> Result<()> {
+        let transaction = burn_transaction(payer, recent_blockhash, staking_enabled);
+        banks_client
+            .process_transaction_with_commitment(transaction.clone(), CommitmentLevel::Finalized)
+            .await
+            .unwrap();
+
+        Ok(())
+    }
+
+    #[cfg(feature = "metadata")]
+    async fn set_the_token_metadata_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        metadata_pda: Pubkey,
+        metadata_program: Pubkey,
+    ) -> Result<()> {
+        let transaction = set_token_metadata_transaction(
+            payer,
+            recent_blockhash,
+            ("Test", "TST", "https://test.com"),
+            metadata_pda,
+            metadata_program,
+        );
+        banks_client
+            .process_transaction_with_commitment(transaction, CommitmentLevel::Finalized)
+            .await
+            .unwrap();
+
+        Ok(())
+    }
+
+    #[cfg(feature = "metadata")]
+    fn set_token_metadata_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        (name, symbol, uri): (&str, &str, &str),
+        metadata_pda: Pubkey,
+        metadata_program: Pubkey,
+    ) -> Transaction {
+        let program_id = id();
+
+        let (contract_state, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let token_program = spl_token::id();
+
+        let data = instruction::SetTokenMetadata {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            uri: uri.to_string(),
+        }
+        .data();
+
+        let accs = SetTokenMetadataContext {
+            contract_state,
+            mint,
+            metadata_pda,
+            metadata_program,
+            signer: payer.pubkey(),
+            system_program: system_program::ID,
+            token_program,
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    #[cfg(feature = "metadata")]
+    fn update_token_metadata_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        (name, symbol, uri): (&str, &str, &str),
+        metadata_pda: Pubkey,
+        metadata_program: Pubkey,
+    ) -> Transaction {
+        let (contract_state, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::UpdateTokenMetadata {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            uri: uri.to_string(),
+        }
+        .data();
+
+        let accs = UpdateTokenMetadataContext {
+            contract_state,
+            mint,
+            metadata_pda,
+            metadata_program,
+            signer: payer.pubkey(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    async fn withdraw_tokens_from_partnership_wallet_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        deposit_wallet: Pubkey,
+    ) -> Result<()> {
+        let program_id = id();
+        let signer = payer.pubkey();
+
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            partnership_account,
+            _,
+            _,
+            _,
+            _,
+            _,
+        ) = get_pda_accounts();
+
+        let token_program = spl_token::id();
+
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(banks_client, WalletKind::Partnership).await;
+
+        let data = instruction::WithdrawTokensFromPartnershipWallet {
+            amount_to_withdraw: 1000000000000000000,
+            idempotency_key: None,
+        }
+        .data();
+
+        let accs = WithdrawTokensFromPartnershipWalletContext {
+            contract_state,
+            vesting_state,
+            deposit_wallet,
+            partnership_account,
+            token_program,
+            signer,
+            instructions_sysvar: sysvar::instructions::ID,
+            withdrawal_intent: None,
+            withdrawal_receipt,
+            system_program: system_program::ID,
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        banks_client
+            .process_transaction_with_commitment(transaction.clone(), CommitmentLevel::Finalized)
+            .await
+            .unwrap();
+
+        Ok(())
+    }
+
+    async fn withdraw_tokens_from_marketing_wallet_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        deposit_wallet: Pubkey,
+    ) -> Result<()> {
+        let program_id = id();
+        let token_program = spl_token::id();
+        let signer = payer.pubkey();
+
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            marketing_account,
+            _,
+            _,
+            _,
+        ) = get_pda_accounts();
+
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(banks_client, WalletKind::Marketing).await;
+
+        let data = instruction::WithdrawTokensFromMarketingWallet {
+            amount_to_withdraw: 1,
+            idempotency_key: None,
+        }
+        .data();
+
+        let accs = WithdrawTokensFromMarketingWalletContext {
+            vesting_state,
+            deposit_wallet,
+            signer,
+            contract_state,
+            marketing_account,
+            token_program,
+            instructions_sysvar: sysvar::instructions::ID,
+            withdrawal_intent: None,
+            withdrawal_receipt,
+            system_program: system_program::ID,
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        Ok(())
+    }
+
+    fn withdraw_tokens_from_community_wallet_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        deposit_wallet: Pubkey,
+        withdrawal_receipt: Pubkey,
+    ) -> Transaction {
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            community_account,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+        ) = get_pda_accounts();
+
+        let data = instruction::WithdrawTokensFromCommunityWallet {
+            amount_to_withdraw: 1,
+            idempotency_key: None,
+        }
+        .data();
+
+        let accs = WithdrawTokensFromCommunityWalletContext {
+            vesting_state,
+            deposit_wallet,
+            signer: payer.pubkey(),
+            contract_state,
+            community_account,
+            token_program: spl_token::id(),
+            instructions_sysvar: sysvar::instructions::ID,
+            withdrawal_intent: None,
+            withdrawal_receipt,
+            system_program: system_program::ID,
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    fn withdraw_tokens_from_liquidity_wallet_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        deposit_wallet: Pubkey,
+        idempotency_key: Option<[u8; 16]>,
+        withdrawal_receipt: Pubkey,
+    ) -> Transaction {
+        withdraw_amount_from_liquidity_wallet_transaction(
+            payer,
+            recent_blockhash,
+            deposit_wallet,
+            1,
+            idempotency_key,
+            withdrawal_receipt,
+        )
+    }
+
+    fn withdraw_vested_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        wallet_kind: WalletKind,
+        wallet_account: Pubkey,
+        deposit_wallet: Pubkey,
+        amount_to_withdraw: u64,
+        withdrawal_receipt: Pubkey,
+    ) -> Transaction {
+        let (contract_state, _, vesting_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::WithdrawVested {
+            wallet_kind,
+            amount_to_withdraw,
+            idempotency_key: None,
+        }
+        .data();
+
+        let accs = WithdrawVestedContext {
+            contract_state,
+            vesting_state,
+            wallet_account,
+            deposit_wallet,
+            withdrawal_intent: None,
+            withdrawal_receipt,
+            signer: payer.pubkey(),
+            token_program: spl_token::id(),
+            instructions_sysvar: sysvar::instructions::ID,
+            system_program: system_program::ID,
+        };
+
+        Transaction::new_signed_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+            &[payer],
+            recent_blockhash,
+        )
+    }
+
+    fn withdraw_all_unlocked_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        deposit_wallets: [Pubkey; VESTED_WALLETS_COUNT],
+        withdrawal_receipts: [Pubkey; VESTED_WALLETS_COUNT],
+    ) -> Transaction {
+        withdraw_all_unlocked_by_transaction(
+            payer,
+            payer,
+            recent_blockhash,
+            deposit_wallets,
+            withdrawal_receipts,
+        )
+    }
+
+    fn withdraw_all_unlocked_by_transaction(
+        payer: &Keypair,
+        signer: &Keypair,
+        recent_blockhash: Hash,
+        deposit_wallets: [Pubkey; VESTED_WALLETS_COUNT],
+        withdrawal_receipts: [Pubkey; VESTED_WALLETS_COUNT],
+    ) -> Transaction {
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            community_account,
+            _,
+            partnership_account,
+            _,
+            marketing_account,
+            _,
+            liquidity_account,
+            _,
+        ) = get_pda_accounts();
+
+        let data = instruction::WithdrawAllUnlocked {}.data();
+
+        let accs = WithdrawAllUnlockedContext {
+            contract_state,
+            vesting_state,
+            community_account,
+            partnership_account,
+            marketing_account,
+            liquidity_account,
+            community_deposit_wallet: deposit_wallets[WalletKind::Community.index()],
+            partnership_deposit_wallet: deposit_wallets[WalletKind::Partnership.index()],
+            marketing_deposit_wallet: deposit_wallets[WalletKind::Marketing.index()],
+            liquidity_deposit_wallet: deposit_wallets[WalletKind::Liquidity.index()],
+            community_withdrawal_receipt: withdrawal_receipts[WalletKind::Community.index()],
+            partnership_withdrawal_receipt: withdrawal_receipts[WalletKind::Partnership.index()],
+            marketing_withdrawal_receipt: withdrawal_receipts[WalletKind::Marketing.index()],
+            liquidity_withdrawal_receipt: withdrawal_receipts[WalletKind::Liquidity.index()],
+            signer: signer.pubkey(),
+            token_program: spl_token::id(),
+            instructions_sysvar: sysvar::instructions::ID,
+            system_program: system_program::ID,
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        if signer.pubkey() == payer.pubkey() {
+            transaction.sign(&[payer], recent_blockhash);
+        } else {
+            transaction.sign(&[payer, signer], recent_blockhash);
+        }
+        transaction
+    }
+
+    fn withdraw_amount_from_liquidity_wallet_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        deposit_wallet: Pubkey,
+        amount_to_withdraw: u64,
+        idempotency_key: Option<[u8; 16]>,
+        withdrawal_receipt: Pubkey,
+    ) -> Transaction {
+        let program_id = id();
+        let token_program = spl_token::id();
+        let signer = payer.pubkey();
+
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            liquidity_account,
+            _,
+        ) = get_pda_accounts();
+
+        let data = instruction::WithdrawTokensFromLiquidityWallet {
+            amount_to_withdraw,
+            idempotency_key,
+        }
+        .data();
+
+        let accs = WithdrawTokensFromLiquidityWalletContext {
+            vesting_state,
+            deposit_wallet,
+            signer,
+            contract_state,
+            liquidity_account,
+            token_program,
+            instructions_sysvar: sysvar::instructions::ID,
+            withdrawal_intent: idempotency_key.map(|key| get_withdrawal_intent_account(&key)),
+            withdrawal_receipt,
+            system_program: system_program::ID,
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    fn close_withdrawal_intent_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        idempotency_key: [u8; 16],
+    ) -> Transaction {
+        let data = instruction::CloseWithdrawalIntent { idempotency_key }.data();
+
+        let accs = CloseWithdrawalIntentContext {
+            withdrawal_intent: get_withdrawal_intent_account(&idempotency_key),
+            payer: payer.pubkey(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    async fn withdraw_tokens_from_liquidity_wallet_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        deposit_wallet: Pubkey,
+    ) -> Result<()> {
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(banks_client, WalletKind::Liquidity).await;
+        let transaction = withdraw_tokens_from_liquidity_wallet_transaction(
+            payer,
+            recent_blockhash,
+            deposit_wallet,
+            None,
+            withdrawal_receipt,
+        );
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        Ok(())
+    }
+
+    async fn configure_liquidity_drip_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        recipient: Pubkey,
+        amount_per_month: u64,
+    ) -> Result<()> {
+        let program_id = id();
+        let (contract_state, _, vesting_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::ConfigureLiquidityDrip {
+            recipient,
+            amount_per_month,
+        }
+        .data();
+
+        let accs = ConfigureLiquidityDripContext {
+            contract_state,
+            vesting_state,
+            signer: payer.pubkey(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        Ok(())
+    }
+
+    async fn execute_liquidity_drip_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        recipient: Pubkey,
+    ) -> Result<()> {
+        banks_client
+            .process_transaction(execute_liquidity_drip_transaction(
+                payer,
+                recent_blockhash,
+                recipient,
+            ))
+            .await
+            .unwrap();
+
+        Ok(())
+    }
+
+    fn execute_liquidity_drip_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        recipient: Pubkey,
+    ) -> Transaction {
+        let program_id = id();
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            liquidity_account,
+            _,
+        ) = get_pda_accounts();
+
+        let data = instruction::ExecuteLiquidityDrip {}.data();
+
+        let accs = ExecuteLiquidityDripContext {
+            contract_state,
+            vesting_state,
+            liquidity_account,
+            recipient,
+            token_program: spl_token::id(),
+            signer: payer.pubkey(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[payer], recent_blockhash);
+
+        transaction
+    }
+
+    async fn create_otc_deal_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        partner: Pubkey,
+        lean_amount: u64,
+        unlock_ts: i64,
+    ) -> Result<()> {
+        banks_client
+            .process_transaction(create_otc_deal_transaction(
+                payer,
+                recent_blockhash,
+                partner,
+                lean_amount,
+                unlock_ts,
+            ))
+            .await
+            .unwrap();
+
+        Ok(())
+    }
+
+    fn create_otc_deal_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        partner: Pubkey,
+        lean_amount: u64,
+        unlock_ts: i64,
+    ) -> Transaction {
+        let program_id = id();
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            mint,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            partnership_account,
+            _,
+            _,
+            _,
+            _,
+            _,
+        ) = get_pda_accounts();
+        let (otc_deal, otc_vault) = get_otc_deal_accounts(&partner);
+
+        let data = instruction::CreateOtcDeal {
+            partner,
+            lean_amount,
+            unlock_ts,
+        }
+        .data();
+
+        let accs = CreateOtcDealContext {
+            contract_state,
+            vesting_state,
+            mint,
+            partnership_account,
+            otc_deal,
+            otc_vault,
+            signer: payer.pubkey(),
+            token_program: spl_token::id(),
+            system_program: system_program::ID,
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[payer], recent_blockhash);
+
+        transaction
+    }
+
+    async fn settle_otc_deal_instruction(
+        banks_client: &mut BanksClient,
+        partner: &Keypair,
+        recent_blockhash: Hash,
+        partner_token_account: Pubkey,
+    ) -> Result<()> {
+        let program_id = id();
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+        let (otc_deal, otc_vault) = get_otc_deal_accounts(&partner.pubkey());
+
+        let data = instruction::SettleOtcDeal {}.data();
+
+        let accs = SettleOtcDealContext {
+            contract_state,
+            otc_deal,
+            otc_vault,
+            partner_token_account,
+            signer: partner.pubkey(),
+            token_program: spl_token::id(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&partner.pubkey()),
+        );
+
+        transaction.sign(&[partner], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        Ok(())
+    }
+
+    async fn cancel_otc_deal_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        partner: Pubkey,
+    ) -> Result<()> {
+        let program_id = id();
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            partnership_account,
+            _,
+            _,
+            _,
+            _,
+            _,
+        ) = get_pda_accounts();
+        let (otc_deal, otc_vault) = get_otc_deal_accounts(&partner);
+
+        let data = instruction::CancelOtcDeal { partner }.data();
+
+        let accs = CancelOtcDealContext {
+            contract_state,
+            vesting_state,
+            partnership_account,
+            otc_deal,
+            otc_vault,
+            signer: payer.pubkey(),
+            token_program: spl_token::id(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        Ok(())
+    }
+
+    fn donate_to_burn_transaction(
+        donor: &Keypair,
+        recent_blockhash: Hash,
+        donor_token_account: Pubkey,
+        amount: u64,
+    ) -> Transaction {
+        let program_id = id();
+        let (contract_state, _, _, _, _, _, _, _, burning_account, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::DonateToBurn { amount }.data();
+
+        let accs = DonateToBurnContext {
+            contract_state,
+            burning_account,
+            donor_token_account,
+            donor_account: get_donor_account(&donor.pubkey()),
+            signer: donor.pubkey(),
+            token_program: spl_token::id(),
+            system_program: system_program::ID,
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&donor.pubkey()),
+        );
+        transaction.sign(&[donor], recent_blockhash);
+
+        transaction
+    }
+
+    fn fund_burning_account_transaction(
+        donor: &Keypair,
+        recent_blockhash: Hash,
+        donor_token_account: Pubkey,
+        amount: u64,
+    ) -> Transaction {
+        let program_id = id();
+        let (contract_state, _, _, _, _, _, _, _, burning_account, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::FundBurningAccount { amount }.data();
+
+        let accs = FundBurningAccountContext {
+            contract_state,
+            burning_account,
+            donor_token_account,
+            signer: donor.pubkey(),
+            token_program: spl_token::id(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&donor.pubkey()),
+        );
+        transaction.sign(&[donor], recent_blockhash);
+
+        transaction
+    }
+
+    async fn initialize_staking_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        reward_percentage: u8,
+        unbonding_period: i64,
+    ) -> Result<()> {
+        let program_id = id();
+        let (contract_state, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+        let (staking_state, stake_vault, reward_vault) = get_staking_pda_accounts();
+
+        let data = instruction::InitializeStaking {
+            reward_percentage,
+            unbonding_period,
+        }
+        .data();
+
+        let accs = InitializeStakingContext {
+            contract_state,
+            staking_state,
+            mint,
+            stake_vault,
+            reward_vault,
+            signer: payer.pubkey(),
+            token_program: spl_token::id(),
+            system_program: system_program::ID,
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        Ok(())
+    }
+
+    async fn stake_instruction(
+        banks_client: &mut BanksClient,
+        staker: &Keypair,
+        recent_blockhash: Hash,
+        staker_token_account: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        let program_id = id();
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+        let (staking_state, stake_vault, _) = get_staking_pda_accounts();
+
+        let data = instruction::Stake { amount }.data();
+
+        let accs = StakeContext {
+            contract_state,
+            staking_state,
+            stake_account: get_stake_account(&staker.pubkey()),
+            stake_vault,
+            staker_token_account,
+            signer: staker.pubkey(),
+            token_program: spl_token::id(),
+            system_program: system_program::ID,
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&staker.pubkey()),
+        );
+
+        transaction.sign(&[staker], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        Ok(())
+    }
+
+    async fn unstake_instruction(
+        banks_client: &mut BanksClient,
+        staker: &Keypair,
+        recent_blockhash: Hash,
+        amount: u64,
+    ) -> Result<()> {
+        let program_id = id();
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+        let (staking_state, _, _) = get_staking_pda_accounts();
+
+        let data = instruction::Unstake { amount }.data();
+
+        let accs = UnstakeContext {
+            contract_state,
+            staking_state,
+            stake_account: get_stake_account(&staker.pubkey()),
+            signer: staker.pubkey(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&staker.pubkey()),
+        );
+
+        transaction.sign(&[staker], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        Ok(())
+    }
+
+    async fn withdraw_unstaked_instruction(
+        banks_client: &mut BanksClient,
+        staker: &Keypair,
+        recent_blockhash: Hash,
+        staker_token_account: Pubkey,
+    ) -> Result<()> {
+        let program_id = id();
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+        let (staking_state, stake_vault, _) = get_staking_pda_accounts();
+
+        let data = instruction::WithdrawUnstaked {}.data();
+
+        let accs = WithdrawUnstakedContext {
+            contract_state,
+            staking_state,
+            stake_account: get_stake_account(&staker.pubkey()),
+            stake_vault,
+            staker_token_account,
+            signer: staker.pubkey(),
+            token_program: spl_token::id(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&staker.pubkey()),
+        );
+
+        transaction.sign(&[staker], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        Ok(())
+    }
+
+    async fn claim_rewards_instruction(
+        banks_client: &mut BanksClient,
+        staker: &Keypair,
+        recent_blockhash: Hash,
+        staker_token_account: Pubkey,
+    ) -> Result<()> {
+        let program_id = id();
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+        let (staking_state, _, reward_vault) = get_staking_pda_accounts();
+
+        let data = instruction::ClaimRewards {}.data();
+
+        let accs = ClaimRewardsContext {
+            contract_state,
+            staking_state,
+            stake_account: get_stake_account(&staker.pubkey()),
+            reward_vault,
+            staker_token_account,
+            signer: staker.pubkey(),
+            token_program: spl_token::id(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&staker.pubkey()),
+        );
+
+        transaction.sign(&[staker], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        Ok(())
+    }
+
+    async fn get_months_since_start_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+    ) -> Result<u64, TransactionError> {
+        let program_id = id();
+        let (_, _, vesting_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        let data = instruction::GetMonthsSinceStart {}.data();
+        let accs = GetMonthsSinceStartContext { vesting_state };
+
+        let instruction = Instruction {
+            program_id,
+            data,
+            accounts: accs.to_account_metas(None),
+        };
+
+        let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+        transaction.sign(&[payer], recent_blockhash);
+
+        let result = banks_client
+            .simulate_transaction(transaction)
+            .await
+            .unwrap();
+        result.result.unwrap()?;
+        let return_data = result.simulation_details.unwrap().return_data.unwrap();
+
+        Ok(u64::try_from_slice(&return_data.data).unwrap())
+    }
+
+    async fn project_supply_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        months: u8,
+    ) -> Result<SupplyProjection, TransactionError> {
+        let program_id = id();
+        let (contract_state, _, _, _, mint, _, _, _, burning_account, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::ProjectSupply { months }.data();
+        let accs = ProjectSupplyContext {
+            contract_state,
+            mint,
+            burning_account,
+            staking_state: None,
+        };
+
+        let instruction = Instruction {
+            program_id,
+            data,
+            accounts: accs.to_account_metas(None),
+        };
+
+        let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+        transaction.sign(&[payer], recent_blockhash);
+
+        let result = banks_client
+            .simulate_transaction(transaction)
+            .await
+            .unwrap();
+        result.result.unwrap()?;
+        let return_data = result.simulation_details.unwrap().return_data.unwrap();
+
+        Ok(SupplyProjection::try_from_slice(&return_data.data).unwrap())
+    }
+
+    async fn get_program_addresses_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+    ) -> Result<ProgramAddresses, TransactionError> {
+        let program_id = id();
+        let (contract_state, _, vesting_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::GetProgramAddresses {}.data();
+        let accs = GetProgramAddressesContext {
+            contract_state,
+            vesting_state,
+            labels: get_labels_account(),
+        };
+
+        let instruction = Instruction {
+            program_id,
+            data,
+            accounts: accs.to_account_metas(None),
+        };
+
+        let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+        transaction.sign(&[payer], recent_blockhash);
+
+        let result = banks_client
+            .simulate_transaction(transaction)
+            .await
+            .unwrap();
+        result.result.unwrap()?;
+        let return_data = result.simulation_details.unwrap().return_data.unwrap();
+
+        Ok(ProgramAddresses::try_from_slice(&return_data.data).unwrap())
+    }
+
+    async fn get_token_info_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+    ) -> Result<TokenInfo, TransactionError> {
+        let program_id = id();
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            mint,
+            _,
+            program_account,
+            _,
+            burning_account,
+            _,
+            community_account,
+            _,
+            partnership_account,
+            _,
+            marketing_account,
+            _,
+            liquidity_account,
+            _,
+        ) = get_pda_accounts();
+
+        let data = instruction::GetTokenInfo {}.data();
+        let accs = GetTokenInfoContext {
+            contract_state,
+            vesting_state,
+            mint,
+            program_account,
+            burning_account,
+            community_account,
+            partnership_account,
+            marketing_account,
+            liquidity_account,
+        };
+
+        let instruction = Instruction {
+            program_id,
+            data,
+            accounts: accs.to_account_metas(None),
+        };
+
+        let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+        transaction.sign(&[payer], recent_blockhash);
+
+        let result = banks_client
+            .simulate_transaction(transaction)
+            .await
+            .unwrap();
+        result.result.unwrap()?;
+        let return_data = result.simulation_details.unwrap().return_data.unwrap();
+
+        Ok(TokenInfo::try_from_slice(&return_data.data).unwrap())
+    }
+
+    async fn get_vesting_status_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+    ) -> Result<VestingStatus, TransactionError> {
+        let program_id = id();
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            community_account,
+            _,
+            partnership_account,
+            _,
+            marketing_account,
+            _,
+            liquidity_account,
+            _,
+        ) = get_pda_accounts();
+
+        let data = instruction::GetVestingStatus {}.data();
+        let accs = GetVestingStatusContext {
+            contract_state,
+            vesting_state,
+            community_account,
+            partnership_account,
+            marketing_account,
+            liquidity_account,
+        };
+
+        let instruction = Instruction {
+            program_id,
+            data,
+            accounts: accs.to_account_metas(None),
+        };
+
+        let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+        transaction.sign(&[payer], recent_blockhash);
+
+        let result = banks_client
+            .simulate_transaction(transaction)
+            .await
+            .unwrap();
+        result.result.unwrap()?;
+        let return_data = result.simulation_details.unwrap().return_data.unwrap();
+
+        Ok(VestingStatus::try_from_slice(&return_data.data).unwrap())
+    }
+
+    async fn forecast_unlocked_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        months_since_start: u64,
+    ) -> Result<UnlockForecast, TransactionError> {
+        let (contract_state, _, vesting_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::ForecastUnlocked { months_since_start }.data();
+        let accs = ForecastUnlockedContext {
+            contract_state,
+            vesting_state,
+        };
+
+        let instruction = Instruction {
+            program_id: id(),
+            data,
+            accounts: accs.to_account_metas(None),
+        };
+
+        let mut transaction = Transaction::new_with_payer(&[instruction], Some(&payer.pubkey()));
+        transaction.sign(&[payer], recent_blockhash);
+
+        let result = banks_client
+            .simulate_transaction(transaction)
+            .await
+            .unwrap();
+        result.result.unwrap()?;
+        let return_data = result.simulation_details.unwrap().return_data.unwrap();
+
+        Ok(UnlockForecast::try_from_slice(&return_data.data).unwrap())
+    }
+
+    async fn set_label_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        account_kind: LabeledAccountKind,
+        label: &str,
+    ) -> Result<()> {
+        let transaction = set_label_transaction(payer, recent_blockhash, account_kind, label);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        Ok(())
+    }
+
+    fn set_label_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        account_kind: LabeledAccountKind,
+        label: &str,
+    ) -> Transaction {
+        let program_id = id();
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::SetLabel {
+            account_kind,
+            label: label.as_bytes().to_vec(),
+        }
+        .data();
+
+        let accs = SetLabelContext {
+            contract_state,
+            labels: get_labels_account(),
+            signer: payer.pubkey(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    async fn set_wallet_beneficiary_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        wallet_kind: WalletKind,
+        beneficiary: Pubkey,
+    ) -> Result<()> {
+        let (contract_state, _, vesting_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::SetWalletBeneficiary {
+            wallet_kind,
+            beneficiary,
+        }
+        .data();
+
+        let accs = SetWalletBeneficiaryContext {
+            contract_state,
+            vesting_state,
+            signer: payer.pubkey(),
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+            &[payer],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        Ok(())
+    }
+
+    async fn set_beneficiary_owner_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        wallet_kind: WalletKind,
+        beneficiary_owner: Pubkey,
+    ) -> Result<()> {
+        let program_id = id();
+
+        let (contract_state, _, vesting_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::SetBeneficiaryOwner {
+            wallet_kind,
+            beneficiary_owner,
+        }
+        .data();
+
+        let accs = SetBeneficiaryOwnerContext {
+            contract_state,
+            vesting_state,
+            signer: payer.pubkey(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        Ok(())
+    }
+
+    async fn lock_configuration_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+    ) -> Result<()> {
+        let program_id = id();
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::LockConfiguration {}.data();
+
+        let accs = LockConfigurationContext {
+            contract_state,
+            signer: payer.pubkey(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        Ok(())
+    }
+
+    fn migrate_contract_state_transaction(payer: &Keypair, recent_blockhash: Hash) -> Transaction {
+        let program_id = id();
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::MigrateContractState {}.data();
+
+        let accs = MigrateContractStateContext {
+            contract_state,
+            system_program: system_program::ID,
+            signer: payer.pubkey(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+
+        transaction
+    }
+
+    fn legacy_contract_state_data(contract_state: &ContractState, authority: Pubkey) -> Vec<u8> {
+        let legacy_contract_state = LegacyContractState {
+            performed_imports: contract_state.performed_imports,
+            imported_minted: contract_state.imported_minted,
+            imported_burned: contract_state.imported_burned,
+            max_supply: contract_state.max_supply,
+            wallets_initialized: contract_state.wallets_initialized,
+            contract_state_nonce: contract_state.contract_state_nonce,
+            mint_nonce: contract_state.mint_nonce,
+            program_account_nonce: contract_state.program_account_nonce,
+            burning_account_nonce: contract_state.burning_account_nonce,
+            last_burning_month: contract_state.last_burning_month,
+            last_burning_year: contract_state.last_burning_year,
+            cpi_allowed: contract_state.cpi_allowed,
+            staking_enabled: contract_state.staking_enabled,
+            total_donated: contract_state.total_donated,
+            metadata_program_id: contract_state.metadata_program_id,
+            configuration_locked: contract_state.configuration_locked,
+            sequence: contract_state.sequence,
+            authority,
+        };
+
+        let mut legacy_data = ContractState::discriminator().to_vec();
+        legacy_contract_state.serialize(&mut legacy_data).unwrap();
+        assert_eq!(legacy_data.len(), 8 + LegacyContractState::INIT_SPACE);
+
+        legacy_data
+    }
+
+    fn migrate_vesting_state_transaction(payer: &Keypair, recent_blockhash: Hash) -> Transaction {
+        let program_id = id();
+        let (contract_state, _, vesting_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::MigrateVestingState {}.data();
+
+        let accs = MigrateVestingStateContext {
+            contract_state,
+            vesting_state,
+            system_program: system_program::ID,
+            signer: payer.pubkey(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+
+        transaction
+    }
+
+    fn top_up_rent_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        account_kind: StateAccountKind,
+    ) -> Transaction {
+        let program_id = id();
+        let (state_account, _) =
+            Pubkey::find_program_address(&[account_kind.seed().as_bytes()], &program_id);
+
+        let data = instruction::TopUpRent { account_kind }.data();
+
+        let accs = TopUpRentContext {
+            state_account,
+            system_program: system_program::ID,
+            signer: payer.pubkey(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+
+        transaction
+    }
+
+    async fn rebalance_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        from: WalletKind,
+        to: WalletKind,
+        amount: u64,
+    ) -> Result<()> {
+        let transaction = rebalance_transaction(payer, recent_blockhash, from, to, amount);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        Ok(())
+    }
+
+    fn rebalance_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        from: WalletKind,
+        to: WalletKind,
+        amount: u64,
+    ) -> Transaction {
+        let program_id = id();
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            community_account,
+            _,
+            partnership_account,
+            _,
+            marketing_account,
+            _,
+            liquidity_account,
+            _,
+        ) = get_pda_accounts();
+
+        let data = instruction::Rebalance { from, to, amount }.data();
+
+        let accs = RebalanceContext {
+            contract_state,
+            vesting_state,
+            community_account,
+            partnership_account,
+            marketing_account,
+            liquidity_account,
+            signer: payer.pubkey(),
+            token_program: spl_token::id(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    async fn finalize_vested_wallet_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        wallet_kind: WalletKind,
+        destination: Pubkey,
+    ) -> Result<()> {
+        let program_id = id();
+        let (contract_state, _, vesting_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+        let (wallet_account, _) =
+            Pubkey::find_program_address(&[wallet_kind.seed().as_bytes()], &program_id);
+
+        let data = instruction::FinalizeVestedWallet { wallet_kind }.data();
+
+        let accs = FinalizeVestedWalletContext {
+            contract_state,
+            vesting_state,
+            wallet_account,
+            destination,
+            signer: payer.pubkey(),
+            token_program: spl_token::id(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        Ok(())
+    }
+
+    fn revoke_unvested_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        wallet_kind: WalletKind,
+        destination: Pubkey,
+    ) -> Transaction {
+        let (contract_state, _, vesting_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+        let (wallet_account, _) =
+            Pubkey::find_program_address(&[wallet_kind.seed().as_bytes()], &id());
+
+        let data = instruction::RevokeUnvested { wallet_kind }.data();
+
+        let accs = RevokeUnvestedContext {
+            contract_state,
+            vesting_state,
+            wallet_account,
+            destination,
+            signer: payer.pubkey(),
+            token_program: spl_token::id(),
+        };
+
+        Transaction::new_signed_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+            &[payer],
+            recent_blockhash,
+        )
+    }
+
+    fn add_to_vesting_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        wallet_kind: WalletKind,
+        source: Pubkey,
+        amount: u64,
+    ) -> Transaction {
+        let (contract_state, _, vesting_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+        let (wallet_account, _) =
+            Pubkey::find_program_address(&[wallet_kind.seed().as_bytes()], &id());
+
+        let data = instruction::AddToVesting {
+            wallet_kind,
+            amount,
+        }
+        .data();
+
+        let accs = AddToVestingContext {
+            contract_state,
+            vesting_state,
+            wallet_account,
+            source,
+            signer: payer.pubkey(),
+            token_program: spl_token::id(),
+        };
+
+        Transaction::new_signed_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+            &[payer],
+            recent_blockhash,
+        )
+    }
+
+    fn approve_wallet_delegate_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        wallet_kind: WalletKind,
+        delegate: Pubkey,
+        allowance: u64,
+    ) -> Transaction {
+        let (contract_state, _, vesting_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+        let (wallet_account, _) =
+            Pubkey::find_program_address(&[wallet_kind.seed().as_bytes()], &id());
+
+        let data = instruction::ApproveWalletDelegate {
+            wallet_kind,
+            delegate,
+            allowance,
+        }
+        .data();
+
+        let accs = ApproveWalletDelegateContext {
+            contract_state,
+            vesting_state,
+            wallet_account,
+            wallet_delegation: get_wallet_delegation_account(wallet_kind),
+            delegate,
+            signer: payer.pubkey(),
+            token_program: spl_token::id(),
+            system_program: system_program::ID,
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    fn wallet_delegation_transaction(
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        wallet_kind: WalletKind,
+        revoke: bool,
+    ) -> Transaction {
+        let (contract_state, _, vesting_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+        let (wallet_account, _) =
+            Pubkey::find_program_address(&[wallet_kind.seed().as_bytes()], &id());
+
+        let data = if revoke {
+            instruction::RevokeWalletDelegate { wallet_kind }.data()
+        } else {
+            instruction::ReconcileWalletDelegate {
+                _wallet_kind: wallet_kind,
+            }
+            .data()
+        };
+
+        let accs = WalletDelegationContext {
+            contract_state,
+            vesting_state,
+            wallet_account,
+            wallet_delegation: get_wallet_delegation_account(wallet_kind),
+            signer: payer.pubkey(),
+            token_program: spl_token::id(),
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                id(),
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    async fn get_wallet_delegation(
+        banks_client: &mut BanksClient,
+        wallet_kind: WalletKind,
+    ) -> WalletDelegation {
+        let wallet_delegation_info = banks_client
+            .get_account(get_wallet_delegation_account(wallet_kind))
+            .await
+            .unwrap()
+            .unwrap();
+
+        WalletDelegation::try_deserialize(&mut wallet_delegation_info.data.as_slice()).unwrap()
+    }
+
+    async fn get_already_withdrawn_wallet_amount(
+        banks_client: &mut BanksClient,
+        wallet_kind: WalletKind,
+    ) -> u64 {
+        let (_, _, vesting_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+        let vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+
+        VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+            .unwrap()
+            .wallet(wallet_kind)
+            .withdrawn
+    }
+
+    #[tokio::test]
+    async fn test_initialize() {
+        let program_id = id();
+        let program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_initialize_with_custom_decimals() {
+        let program_id = id();
+        let program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let (contract_state, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let error = banks_client
+            .process_transaction(initialize_state_transaction(
+                &payer,
+                &payer,
+                recent_blockhash,
+                MAX_MINT_DECIMALS + 1,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::InvalidDecimals.into())
+            )
+        );
+
+        banks_client
+            .process_transaction(initialize_state_transaction(
+                &payer,
+                &payer,
+                recent_blockhash,
+                6,
+            ))
+            .await
+            .unwrap();
+
+        let mint_info = banks_client.get_account(mint).await.unwrap().unwrap();
+        let mint_account =
+            spl_token::state::Mint::unpack_from_slice(mint_info.data.as_slice()).unwrap();
+        assert_eq!(mint_account.decimals, 6);
+
+        let contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let state =
+            ContractState::try_deserialize(&mut contract_state_info.data.as_slice()).unwrap();
+        assert_eq!(state.decimals, 6);
+    }
+
+    #[tokio::test]
+    async fn test_initialize_with_separate_payer() {
+        let program_id = id();
+        let program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        // the authority holds no lamports, the rent of all created accounts is paid by the payer
+        let authority = Keypair::new();
+
+        banks_client
+            .process_transaction(initialize_state_transaction(
+                &payer,
+                &authority,
+                recent_blockhash,
+                LEGACY_MINT_DECIMALS,
+            ))
+            .await
+            .unwrap();
+        banks_client
+            .process_transaction(initialize_wallets_by_transaction(
+                &payer,
+                &authority,
+                recent_blockhash,
+                None,
+                None,
+            ))
+            .await
+            .unwrap();
+
+        let contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let state =
+            ContractState::try_deserialize(&mut contract_state_info.data.as_slice()).unwrap();
+        assert_eq!(state.authority, authority.pubkey());
+        assert_ne!(state.authority, payer.pubkey());
+        assert!(state.wallets_initialized);
+        assert_eq!(
+            banks_client.get_balance(authority.pubkey()).await.unwrap(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_initialize_emits_event() {
+        let program_id = id();
+        let program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        let result = banks_client
+            .process_transaction_with_metadata(initialize_state_transaction(
+                &payer,
+                &payer,
+                recent_blockhash,
+                LEGACY_MINT_DECIMALS,
+            ))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let event: Initialized = get_event(&result.metadata.unwrap().log_messages).unwrap();
+
+        assert_eq!(event.authority, payer.pubkey());
+        assert_eq!(event.mint, mint);
+        assert_eq!(event.decimals, LEGACY_MINT_DECIMALS);
+        assert_eq!(event.sequence, 1);
+    }
+
+    #[tokio::test]
+    async fn test_change_authority_emits_event() {
+        let program_id = id();
+        let program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let new_authority = Keypair::new().pubkey();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let result = banks_client
+            .process_transaction_with_metadata(change_authority_transaction(
+                &payer,
+                recent_blockhash,
+                new_authority,
+            ))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let event: AuthorityChanged = get_event(&result.metadata.unwrap().log_messages).unwrap();
+
+        assert_eq!(event.old_authority, payer.pubkey());
+        assert_eq!(event.new_authority, new_authority);
+        // the initialization takes the sequence numbers 1 and 2
+        assert_eq!(event.sequence, 3);
+    }
+
+    #[tokio::test]
+    async fn test_initialize_stores_canonical_nonces() {
+        let program_id = id();
+        let program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let (
+            contract_state,
+            contract_state_nonce,
+            vesting_state,
+            vesting_state_nonce,
+            mint,
+            mint_nonce,
+            _,
+            program_account_nonce,
+            _,
+            burning_account_nonce,
+            _,
+            community_wallet_nonce,
+            _,
+            partnership_wallet_nonce,
+            _,
+            marketing_wallet_nonce,
+            _,
+            liquidity_wallet_nonce,
+        ) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let contract_state =
+            ContractState::try_deserialize(&mut contract_state_info.data.as_slice()).unwrap();
+        assert_eq!(contract_state.contract_state_nonce, contract_state_nonce);
+        assert_eq!(contract_state.mint, mint);
+        assert_eq!(contract_state.mint_nonce, mint_nonce);
+        assert_eq!(contract_state.program_account_nonce, program_account_nonce);
+        assert_eq!(contract_state.burning_account_nonce, burning_account_nonce);
+
+        let vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let vesting_state =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                .unwrap();
+        assert_eq!(vesting_state.vesting_state_nonce, vesting_state_nonce);
+        assert_eq!(
+            vesting_state.wallet(WalletKind::Community).nonce,
+            community_wallet_nonce
+        );
+        assert_eq!(
+            vesting_state.wallet(WalletKind::Partnership).nonce,
+            partnership_wallet_nonce
+        );
+        assert_eq!(
+            vesting_state.wallet(WalletKind::Marketing).nonce,
+            marketing_wallet_nonce
+        );
+        assert_eq!(
+            vesting_state.wallet(WalletKind::Liquidity).nonce,
+            liquidity_wallet_nonce
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_ethereum_token_state() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_import_ethereum_token_state_emits_import_completed_event() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let transaction = import_ethereum_token_state_transaction(&payer, recent_blockhash);
+        let result = banks_client
+            .process_transaction_with_metadata(transaction)
+            .await
+            .unwrap();
+        result.result.unwrap();
+
+        let event: ImportCompleted = get_event(&result.metadata.unwrap().log_messages).unwrap();
+
+        // the fixture snapshot expressed with mint decimals
+        assert_eq!(event.source, MigrationSource::Ethereum);
+        assert_eq!(event.total_minted, 10000000000000000000);
+        assert_eq!(event.total_burned, 1470000000000000000);
+        assert_eq!(event.accounts_funded, 5);
+        assert_eq!(event.community, 1000000000000000000);
+        assert_eq!(event.partnership, 2000000000000000000);
+        assert_eq!(event.marketing, 1500000000000000000);
+        assert_eq!(event.liquidity, 1000000000000000000);
+    }
+
+    #[tokio::test]
+    async fn test_import_records_swap_wallet_balance() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let (_, _, vesting_state, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let swap_account = create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+            .await
+            .unwrap();
+        let entries = get_accounts_to_mapping()
+            .into_iter()
+            .map(|entry| match entry.wallet_name.as_str() {
+                "swap" => AccountInfoFromEthereum {
+                    account_public_key: swap_account,
+                    ..entry
+                },
+                _ => entry,
+            })
+            .collect::<Vec<AccountInfoFromEthereum>>();
+
+        let transaction = import_external_token_state_transaction(
+            &payer,
+            recent_blockhash,
+            MigrationSource::Ethereum,
+            entries,
+            10000000000000000000000000000,
+            1470000000000000000000000000,
+        );
+        let result = banks_client
+            .process_transaction_with_metadata(transaction)
+            .await
+            .unwrap();
+        result.result.unwrap();
+
+        let event: ImportCompleted = get_event(&result.metadata.unwrap().log_messages).unwrap();
+        assert_eq!(event.accounts_funded, 6);
+        assert_eq!(event.swap, 1230000000000000000);
+        assert_eq!(
+            get_token_balance(&mut banks_client, &swap_account).await,
+            1230000000000000000
+        );
+
+        // the swap wallet of the following source is added to the recorded balance
+        let bsc_swap_account =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+        import_external_token_state_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            MigrationSource::Bsc,
+            vec![AccountInfoFromEthereum {
+                wallet_name: String::from("swap"),
+                account_public_key: bsc_swap_account,
+                account_balance: 900000000000000000000,
+            }],
+            900000000000000000000,
+            0,
+        )
+        .await
+        .unwrap();
+
+        let vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let vesting_state =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                .unwrap();
+        assert_eq!(
+            vesting_state.initial_swap_wallet_balance,
+            1230000000000000000 + 900000000000
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_external_token_state_after_ethereum_import() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let (contract_state, _, vesting_state, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let vesting_state_after_ethereum_import =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                .unwrap();
+
+        let holder = create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+            .await
+            .unwrap();
+        let entries = vec![AccountInfoFromEthereum {
+            wallet_name: String::from("bsc_holders"),
+            account_public_key: holder,
+            account_balance: 900000000000000000000,
+        }];
+
+        import_external_token_state_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            MigrationSource::Bsc,
+            entries,
+            1000000000000000000000,
+            100000000000000000000,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            get_token_balance(&mut banks_client, &holder).await,
+            900000000000
+        );
+
+        let contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let contract_state =
+            ContractState::try_deserialize_unchecked(&mut contract_state_info.data.as_slice())
+                .unwrap();
+        assert_eq!(
+            contract_state.performed_imports,
+            MigrationSource::Ethereum.mask() | MigrationSource::Bsc.mask()
+        );
+        assert_eq!(
+            contract_state.imported_minted[MigrationSource::Bsc.index()],
+            1000000000000
+        );
+        assert_eq!(
+            contract_state.imported_burned[MigrationSource::Bsc.index()],
+            100000000000
+        );
+        assert_eq!(
+            contract_state.imported_minted[MigrationSource::Ethereum.index()],
+            10000000000000000000
+        );
+        // both imports raise the maximal supply by the amount they mint
+        assert_eq!(
+            contract_state.max_supply,
+            10000000000000000000 + 1000000000000
+        );
+
+        let vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let vesting_state =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                .unwrap();
+        assert_eq!(
+            vesting_state.start_timestamp,
+            vesting_state_after_ethereum_import.start_timestamp
+        );
+        assert_eq!(
+            vesting_state.wallet(WalletKind::Community).initial_balance,
+            vesting_state_after_ethereum_import
+                .wallet(WalletKind::Community)
+                .initial_balance
+        );
+    }
+
+    #[tokio::test]
+    async fn test_staged_import_retried_batch_does_not_transfer_twice() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let (
+            _,
+            _,
+            vesting_state,
+            _,
+            mint,
+            _,
+            program_account,
+            _,
+            burning_account,
+            _,
+            community_account,
+            _,
+            partnership_account,
+            _,
+            marketing_account,
+            _,
+            liquidity_account,
+            _,
+        ) = get_pda_accounts();
+        let (import_progress, _) =
+            Pubkey::find_program_address(&[IMPORT_PROGRESS_SEED.as_bytes()], &program_id);
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        // the swap entry needs an existing token account, so the whole minted amount is distributed
+        let swap_account = create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+            .await
+            .unwrap();
+        let mut entries = get_accounts_to_mapping();
+        entries[5].account_public_key = swap_account;
+
+        banks_client
+            .process_transaction(begin_import_transaction(&payer, recent_blockhash, 6))
+            .await
+            .unwrap();
+
+        // the first attempt of the batch funds only the first two entries, as if the transaction ran out of compute units
+        banks_client
+            .process_transaction(import_batch_transaction(
+                &payer,
+                recent_blockhash,
+                0,
+                &entries[..4],
+                &[burning_account, community_account],
+            ))
+            .await
+            .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &community_account).await,
+            1000000000000000000
+        );
+
+        let error = banks_client
+            .process_transaction(finalize_import_transaction(&payer, recent_blockhash))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::ImportNotComplete.into())
+            )
+        );
+
+        // the retry passes the whole batch, the already funded entries are skipped
+        banks_client
+            .process_transaction(import_batch_transaction(
+                &payer,
+                recent_blockhash,
+                0,
+                &entries[..4],
+                &[
+                    burning_account,
+                    community_account,
+                    partnership_account,
+                    marketing_account,
+                ],
+            ))
+            .await
+            .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &burning_account).await,
+            1800000000000000000
+        );
+        assert_eq!(
+            get_token_balance(&mut banks_client, &community_account).await,
+            1000000000000000000
+        );
+        assert_eq!(
+            get_token_balance(&mut banks_client, &marketing_account).await,
+            1500000000000000000
+        );
+
+        banks_client
+            .process_transaction(import_batch_transaction(
+                &payer,
+                recent_blockhash,
+                4,
+                &entries[4..],
+                &[liquidity_account, swap_account],
+            ))
+            .await
+            .unwrap();
+
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+        banks_client
+            .process_transaction(finalize_import_transaction(&payer, recent_blockhash))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            get_token_balance(&mut banks_client, &program_account).await,
+            0
+        );
+        assert!(banks_client
+            .get_account(import_progress)
+            .await
+            .unwrap()
+            .is_none());
+
+        let vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let vesting_state =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                .unwrap();
+        assert_eq!(
+            vesting_state.wallet(WalletKind::Community).initial_balance,
+            1000000000000000000
+        );
+        assert_eq!(
+            vesting_state.initial_swap_wallet_balance,
+            1230000000000000000
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_batch_out_of_range_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let (_, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, liquidity_account, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        banks_client
+            .process_transaction(begin_import_transaction(&payer, recent_blockhash, 6))
+            .await
+            .unwrap();
+
+        let entries = get_accounts_to_mapping();
+        let error = banks_client
+            .process_transaction(import_batch_transaction(
+                &payer,
+                recent_blockhash,
+                5,
+                &entries[4..],
+                &[liquidity_account],
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::ImportEntryOutOfRange.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mint_above_max_supply_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (contract_state, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let mut contract_state_data =
+            ContractState::try_deserialize_unchecked(&mut contract_state_info.data.as_slice())
+                .unwrap();
+        assert_eq!(contract_state_data.max_supply, 10000000000000000000);
+
+        // simulate a second mint that is not accompanied by raising the maximal supply:
+        // the following import raises the maximal supply only by the amount it mints
+        // while the supply is already above the lowered maximal supply
+        contract_state_data.max_supply = 0;
+        let mut data = Vec::with_capacity(contract_state_info.data.len());
+        contract_state_data.try_serialize(&mut data).unwrap();
+        data.resize(contract_state_info.data.len(), 0);
+        program_test_context.set_account(
+            &contract_state,
+            &solana_sdk::account::Account {
+                data,
+                ..contract_state_info
+            }
+            .into(),
+        );
+
+        let holder = create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+            .await
+            .unwrap();
+        let entries = vec![AccountInfoFromEthereum {
+            wallet_name: String::from("bsc_holders"),
+            account_public_key: holder,
+            account_balance: 900000000000000000000,
+        }];
+
+        let error = banks_client
+            .process_transaction(import_external_token_state_transaction(
+                &payer,
+                recent_blockhash,
+                MigrationSource::Bsc,
+                entries,
+                1000000000000000000000,
+                100000000000000000000,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(LeancoinError::MaxSupplyExceeded.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "test-bpf")]
+    async fn test_burn_within_compute_budget() {
+        let program_test = bpf_program_test();
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        process_transaction_within_compute_budget(
+            &mut banks_client,
+            burn_transaction(&payer, recent_blockhash, false),
+            &[&payer],
+            BURN_COMPUTE_BUDGET,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "test-bpf")]
+    async fn test_withdrawals_within_compute_budget() {
+        let program_test = bpf_program_test();
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        let transactions = [
+            withdraw_tokens_from_community_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                get_withdrawal_receipt_account(WalletKind::Community, 0),
+            ),
+            withdraw_tokens_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                None,
+                get_withdrawal_receipt_account(WalletKind::Liquidity, 0),
+            ),
+            // the withdrawal intent account is initialized as well
+            withdraw_tokens_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                Some([1; 16]),
+                get_withdrawal_receipt_account(WalletKind::Liquidity, 1),
+            ),
+        ];
+        for transaction in transactions {
+            process_transaction_within_compute_budget(
+                &mut banks_client,
+                transaction,
+                &[&payer],
+                WITHDRAW_COMPUTE_BUDGET,
+            )
+            .await;
+        }
+
+        assert_eq!(
+            get_token_balance(&mut banks_client, &deposit_wallet).await,
+            3
+        );
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "test-bpf")]
+    async fn test_import_ethereum_token_state_within_compute_budget() {
+        let program_test = bpf_program_test();
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        process_transaction_within_compute_budget(
+            &mut banks_client,
+            import_ethereum_token_state_transaction(&payer, recent_blockhash),
+            &[&payer],
+            get_accounts_to_mapping().len() as u64 * IMPORT_PER_ENTRY_COMPUTE_BUDGET,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "test-bpf")]
+    async fn test_withdraw_all_unlocked_within_compute_budget() {
+        let program_test = bpf_program_test();
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        set_time(&mut program_test_context, 1677978061).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let mut deposit_wallets = [Pubkey::default(); VESTED_WALLETS_COUNT];
+        for deposit_wallet in deposit_wallets.iter_mut() {
+            *deposit_wallet =
+                create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                    .await
+                    .unwrap();
+        }
+
+        // every wallet has unlocked tokens after 13 months, so all four transfers are made
+        //  Friday, 5 April 2024 01:01:01
+        set_time(&mut program_test_context, 1712278861).await;
+
+        let withdrawal_receipts = get_next_withdrawal_receipt_accounts(&mut banks_client).await;
+        process_transaction_within_compute_budget(
+            &mut banks_client,
+            withdraw_all_unlocked_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallets,
+                withdrawal_receipts,
+            ),
+            &[&payer],
+            WITHDRAW_ALL_COMPUTE_BUDGET,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_import_external_token_state_batch_within_compute_budget() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let mut entries = vec![];
+        for index in 0..IMPORT_BATCH_SIZE {
+            let holder = create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+            entries.push(AccountInfoFromEthereum {
+                wallet_name: format!("bsc{}", index),
+                account_public_key: holder,
+                account_balance: 1000000000000000000,
+            });
+        }
+        let holders = entries
+            .iter()
+            .map(|entry| entry.account_public_key)
+            .collect::<Vec<Pubkey>>();
+
+        let transaction = import_external_token_state_transaction(
+            &payer,
+            recent_blockhash,
+            MigrationSource::Bsc,
+            entries,
+            26000000000000000000,
+            1000000000000000000,
+        );
+
+        let simulation = banks_client
+            .simulate_transaction(transaction.clone())
+            .await
+            .unwrap();
+        simulation.result.unwrap().unwrap();
+        assert!(
+            simulation.simulation_details.unwrap().units_consumed <= IMPORT_BATCH_COMPUTE_UNITS
+        );
+
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        for holder in holders {
+            assert_eq!(
+                get_token_balance(&mut banks_client, &holder).await,
+                1000000000
+            );
+        }
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn test_import_external_token_state_to_vested_wallet_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let (_, _, _, _, _, _, _, _, _, _, community_account, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let entries = vec![AccountInfoFromEthereum {
+            wallet_name: String::from("community"),
+            account_public_key: community_account,
+            account_balance: 900000000000000000000,
+        }];
+
+        import_external_token_state_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            MigrationSource::Bsc,
+            entries,
+            1000000000000000000000,
+            100000000000000000000,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn test_import_ethereum_token_state_before_wallets_initialized_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn test_burn_after_5th_day_of_month_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Monday, 6 March 2023 01:01:01
+        let time_in_timestamp = 1678064461;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        burn_instruction(&mut banks_client, &payer, recent_blockhash, false)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_burn_on_last_day_of_configured_window_succeeds() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Friday, 10 March 2023 01:01:01
+        let time_in_timestamp = 1678410061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        banks_client
+            .process_transaction(set_burn_window_transaction(&payer, recent_blockhash, 10))
+            .await
+            .unwrap();
+
+        let result = banks_client
+            .process_transaction_with_metadata(burn_transaction(&payer, recent_blockhash, false))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let burn_event: BurnExecuted = get_event(&result.metadata.unwrap().log_messages).unwrap();
+
+        assert_eq!(burn_event.amount, 1800000000000000000 / 20);
+        assert!(!burn_event.late);
+    }
+
+    #[tokio::test]
+    async fn test_burn_after_configured_window_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Saturday, 11 March 2023 01:01:01
+        let time_in_timestamp = 1678496461;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        banks_client
+            .process_transaction(set_burn_window_transaction(&payer, recent_blockhash, 10))
+            .await
+            .unwrap();
+
+        let error = banks_client
+            .process_transaction(burn_transaction(&payer, recent_blockhash, false))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::TooLateToBurnTokens.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_burn_window_out_of_range_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        for burn_window_days in [0, MAX_BURN_WINDOW_DAYS + 1] {
+            let error = banks_client
+                .process_transaction(set_burn_window_transaction(
+                    &payer,
+                    recent_blockhash,
+                    burn_window_days,
+                ))
+                .await
+                .unwrap_err()
+                .unwrap();
+            assert_eq!(
+                error,
+                TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(LeancoinError::InvalidBurnWindow.into())
+                )
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_late_burn_window_by_owner_before_lock() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let burn_authority = add_funded_keypair(&mut program_test);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        banks_client
+            .process_transaction(set_role_transaction(
+                &payer,
+                &payer,
+                recent_blockhash,
+                Role::Burn,
+                burn_authority.pubkey(),
+            ))
+            .await
+            .unwrap();
+
+        // the burn authority cannot widen the window in which it may burn
+        let error = banks_client
+            .process_transaction(set_late_burn_window_transaction(
+                &burn_authority,
+                recent_blockhash,
+                10,
+                28,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::Unauthorized.into())
+            )
+        );
+
+        banks_client
+            .process_transaction(set_late_burn_window_transaction(
+                &payer,
+                recent_blockhash,
+                10,
+                14,
+            ))
+            .await
+            .unwrap();
+        let contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let state =
+            ContractState::try_deserialize(&mut contract_state_info.data.as_slice()).unwrap();
+        assert_eq!(state.late_burn_window_first_day, 10);
+        assert_eq!(state.late_burn_window_last_day, 14);
+
+        lock_configuration_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        let error = banks_client
+            .process_transaction(set_late_burn_window_transaction(
+                &payer,
+                recent_blockhash,
+                16,
+                21,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::ConfigurationLocked.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_burn_on_5th_day_of_month_succeeds() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let (_, _, _, _, _, _, _, _, burning_account, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let burning_account_mint_balance =
+            get_token_balance(&mut banks_client, &burning_account).await;
+        let expected_burning_account_mint_balance = 1800000000000000000;
+        assert_eq!(
+            burning_account_mint_balance,
+            expected_burning_account_mint_balance
+        );
+
+        burn_instruction(&mut banks_client, &payer, recent_blockhash, false)
+            .await
+            .unwrap();
+
+        let burning_account_mint_balance =
+            get_token_balance(&mut banks_client, &burning_account).await;
+        let expected_burning_account_mint_balance = 1800000000000000000 - 1800000000000000000 / 20;
+        assert_eq!(
+            burning_account_mint_balance,
+            expected_burning_account_mint_balance
+        );
+    }
+
+    #[tokio::test]
+    async fn test_burn_with_forged_mint_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        // a mint with the same decimals as the contract's one, owned by the token program
+        let forged_mint = Pubkey::new_unique();
+        let mut forged_mint_data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint {
+            mint_authority: COption::Some(forged_mint),
+            supply: 8530000000000000000,
+            decimals: LEGACY_MINT_DECIMALS,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        }
+        .pack_into_slice(&mut forged_mint_data);
+        program_test.add_account(
+            forged_mint,
+            solana_sdk::account::Account {
+                lamports: Rent::default().minimum_balance(forged_mint_data.len()),
+                data: forged_mint_data,
+                owner: spl_token::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let error = banks_client
+            .process_transaction(burn_with_mint_transaction(
+                &payer,
+                &payer,
+                recent_blockhash,
+                false,
+                forged_mint,
+                None,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::WrongMint.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_burn_by_registered_keeper() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+        let keeper = Keypair::new();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let result = banks_client
+            .process_transaction_with_metadata(add_keeper_transaction(
+                &payer,
+                recent_blockhash,
+                keeper.pubkey(),
+            ))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let event: KeeperAdded = get_event(&result.metadata.unwrap().log_messages).unwrap();
+        assert_eq!(event.keeper, keeper.pubkey());
+
+        banks_client
+            .process_transaction(burn_by_keeper_transaction(
+                &payer,
+                &keeper,
+                recent_blockhash,
+                false,
+            ))
+            .await
+            .unwrap();
+
+        let (contract_state, _, _, _, _, _, _, _, burning_account, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &burning_account).await,
+            1800000000000000000 - 1800000000000000000 / 20
+        );
+
+        let contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let contract_state =
+            ContractState::try_deserialize(&mut contract_state_info.data.as_slice()).unwrap();
+        assert_eq!(contract_state.keepers[0], keeper.pubkey());
+    }
+
+    #[tokio::test]
+    async fn test_burn_by_removed_keeper_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+        let keeper = Keypair::new();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        banks_client
+            .process_transaction(add_keeper_transaction(
+                &payer,
+                recent_blockhash,
+                keeper.pubkey(),
+            ))
+            .await
+            .unwrap();
+        let result = banks_client
+            .process_transaction_with_metadata(remove_keeper_transaction(
+                &payer,
+                recent_blockhash,
+                keeper.pubkey(),
+            ))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let event: KeeperRemoved = get_event(&result.metadata.unwrap().log_messages).unwrap();
+        assert_eq!(event.keeper, keeper.pubkey());
+
+        let error = banks_client
+            .process_transaction(burn_by_keeper_transaction(
+                &payer,
+                &keeper,
+                recent_blockhash,
+                false,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::Unauthorized.into())
+            )
+        );
+
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+        let error = banks_client
+            .process_transaction(remove_keeper_transaction(
+                &payer,
+                recent_blockhash,
+                keeper.pubkey(),
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::KeeperNotRegistered.into())
+            )
+        );
+
+        // the authority can always run the automation instructions
+        burn_instruction(&mut banks_client, &payer, recent_blockhash, false)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_add_keeper_over_limit_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let keepers = (0..MAX_KEEPERS)
+            .map(|_| Pubkey::new_unique())
+            .collect::<Vec<Pubkey>>();
+        for keeper in &keepers {
+            banks_client
+                .process_transaction(add_keeper_transaction(&payer, recent_blockhash, *keeper))
+                .await
+                .unwrap();
+        }
+
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+        let error = banks_client
+            .process_transaction(add_keeper_transaction(&payer, recent_blockhash, keepers[0]))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::KeeperAlreadyRegistered.into())
+            )
+        );
+
+        let error = banks_client
+            .process_transaction(add_keeper_transaction(
+                &payer,
+                recent_blockhash,
+                Pubkey::new_unique(),
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::TooManyKeepers.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_late_burn_in_secondary_window() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        // the primary window is missed, Friday, 17 March 2023 01:01:01
+        set_time(&mut program_test_context, 1679014861).await;
+
+        let result = banks_client
+            .process_transaction_with_metadata(burn_transaction(&payer, recent_blockhash, false))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let burn_event: BurnExecuted = get_event(&result.metadata.unwrap().log_messages).unwrap();
+        assert!(burn_event.late);
+        assert_eq!(burn_event.month, 3);
+        assert_eq!(burn_event.amount, 1800000000000000000 / 20);
+
+        // Saturday, 18 March 2023 01:01:01
+        set_time(&mut program_test_context, 1679101261).await;
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+
+        let error = banks_client
+            .process_transaction(burn_transaction(&payer, recent_blockhash, false))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::TokensAlreadyBurned.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_burn_history_overwrites_oldest_record() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let mut recent_blockhash = program_test_context.last_blockhash;
+        let burn_history = get_burn_history_account();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        // 5 March, 5 April and 5 May 2023 01:01:01
+        let timestamps = [
+            time_in_timestamp,
+            time_in_timestamp + 31 * 86400,
+            time_in_timestamp + 61 * 86400,
+        ];
+        let mut records = vec![];
+        for timestamp in timestamps {
+            set_time(&mut program_test_context, timestamp).await;
+            recent_blockhash = banks_client
+                .get_new_latest_blockhash(&recent_blockhash)
+                .await
+                .unwrap();
+
+            let result = banks_client
+                .process_transaction_with_metadata(burn_transaction(
+                    &payer,
+                    recent_blockhash,
+                    false,
+                ))
+                .await
+                .unwrap();
+            result.result.unwrap();
+            let burn_event: BurnExecuted =
+                get_event(&result.metadata.unwrap().log_messages).unwrap();
+            records.push(BurnRecord {
+                timestamp,
+                amount: burn_event.amount,
+                burning_account_balance_after: burn_event.burning_account_balance,
+            });
+        }
+
+        let burn_history_info = banks_client
+            .get_account(burn_history)
+            .await
+            .unwrap()
+            .unwrap();
+        let history = BurnHistory::try_deserialize(&mut burn_history_info.data.as_slice()).unwrap();
+
+        // the capacity is reduced to 2 in tests, so the third burn overwrites the first one
+        assert_eq!(BURN_HISTORY_CAPACITY, 2);
+        assert_eq!(history.burns_count, 3);
+        assert_eq!(history.cursor, 1);
+        assert_eq!(history.records, [records[2], records[1]]);
+        assert_eq!(history.latest_burns(), records[1..].to_vec());
+        assert_eq!(records[1].amount, 1710000000000000000 / 20);
+        assert_eq!(
+            records[2].burning_account_balance_after,
+            1624500000000000000 - 1624500000000000000 / 20
+        );
+    }
+
+    #[tokio::test]
+    async fn test_catch_up_burn_after_missed_months() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (contract_state, _, _, _, _, _, _, _, burning_account, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        banks_client
+            .process_transaction(burn_transaction(&payer, recent_blockhash, false))
+            .await
+            .unwrap();
+        let burning_account_balance = 1800000000000000000 - 1800000000000000000 / 20;
+
+        // the burns of April and May are missed, Saturday, 3 June 2023 01:01:01
+        set_time(&mut program_test_context, time_in_timestamp + 90 * 86400).await;
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+
+        let result = banks_client
+            .process_transaction_with_metadata(catch_up_burn_transaction(&payer, recent_blockhash))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let burn_event: BurnExecuted = get_event(&result.metadata.unwrap().log_messages).unwrap();
+
+        // 5% of the balance compounded for April and May, the burn of June is left to the monthly burn
+        let expected_balance: u128 = burning_account_balance as u128 * 19 * 19 / (20 * 20);
+        let expected_amount = burning_account_balance - expected_balance as u64;
+        assert_eq!(burn_event.amount, expected_amount);
+        assert_eq!(burn_event.month, 5);
+        assert_eq!(
+            burn_event.supply_before - burn_event.supply_after,
+            expected_amount
+        );
+        assert_eq!(
+            get_token_balance(&mut banks_client, &burning_account).await,
+            expected_balance as u64
+        );
+
+        let contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let state =
+            ContractState::try_deserialize(&mut contract_state_info.data.as_slice()).unwrap();
+        assert_eq!(state.last_burning_month, 5);
+        assert_eq!(state.last_burning_year, 2023);
+        assert_eq!(
+            state.total_burned,
+            1800000000000000000 / 20 + expected_amount
+        );
+
+        // nothing is left to catch up, the burn of June is executed by the monthly burn in the window
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+        let error = banks_client
+            .process_transaction(catch_up_burn_transaction(&payer, recent_blockhash))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::NoMissedBurn.into())
+            )
+        );
+
+        let result = banks_client
+            .process_transaction_with_metadata(burn_transaction(&payer, recent_blockhash, false))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let burn_event: BurnExecuted = get_event(&result.metadata.unwrap().log_messages).unwrap();
+        assert_eq!(burn_event.month, 6);
+        assert_eq!(burn_event.amount, expected_balance as u64 / 20);
+    }
+
+    #[tokio::test]
+    async fn test_catch_up_burn_outside_of_burn_window_leaves_current_month() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        banks_client
+            .process_transaction(burn_transaction(&payer, recent_blockhash, false))
+            .await
+            .unwrap();
+
+        // the burn of April is missed, Wednesday, 10 May 2023 01:01:01 is outside of both burn windows
+        set_time(&mut program_test_context, time_in_timestamp + 66 * 86400).await;
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+
+        let result = banks_client
+            .process_transaction_with_metadata(catch_up_burn_transaction(&payer, recent_blockhash))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let burn_event: BurnExecuted = get_event(&result.metadata.unwrap().log_messages).unwrap();
+        assert_eq!(burn_event.month, 4);
+        assert_eq!(
+            burn_event.amount,
+            (1800000000000000000 - 1800000000000000000 / 20) / 20
+        );
+
+        // the burn of May is still subject to the burn windows
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+        let error = banks_client
+            .process_transaction(burn_transaction(&payer, recent_blockhash, false))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::TooLateToBurnTokens.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_catch_up_burn_without_previous_burn_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let error = banks_client
+            .process_transaction(catch_up_burn_transaction(&payer, recent_blockhash))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::NoPreviousBurn.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pause_withdrawals_and_burning() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (contract_state, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        let result = banks_client
+            .process_transaction_with_metadata(set_paused_transaction(
+                &payer,
+                recent_blockhash,
+                true,
+            ))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let event: PauseStateChanged = get_event(&result.metadata.unwrap().log_messages).unwrap();
+        assert!(event.paused);
+
+        let contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let state =
+            ContractState::try_deserialize(&mut contract_state_info.data.as_slice()).unwrap();
+        assert!(state.paused);
+
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        for transaction in [
+            burn_transaction(&payer, recent_blockhash, false),
+            withdraw_tokens_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                None,
+                withdrawal_receipt,
+            ),
+        ] {
+            let error = banks_client
+                .process_transaction(transaction)
+                .await
+                .unwrap_err()
+                .unwrap();
+            assert_eq!(
+                error,
+                TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(LeancoinError::ContractPaused.into())
+                )
+            );
+        }
+
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+
+        let result = banks_client
+            .process_transaction_with_metadata(set_paused_transaction(
+                &payer,
+                recent_blockhash,
+                false,
+            ))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let event: PauseStateChanged = get_event(&result.metadata.unwrap().log_messages).unwrap();
+        assert!(!event.paused);
+
+        banks_client
+            .process_transaction(burn_transaction(&payer, recent_blockhash, false))
+            .await
+            .unwrap();
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        banks_client
+            .process_transaction(withdraw_tokens_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                None,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap();
+
+        let deposit_wallet_info = banks_client
+            .get_account(deposit_wallet)
+            .await
+            .unwrap()
+            .unwrap();
+        let deposit_wallet_account =
+            spl_token::state::Account::unpack(&deposit_wallet_info.data).unwrap();
+        assert!(deposit_wallet_account.amount > 0);
+    }
+
+    #[tokio::test]
+    async fn test_renounce_authority() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (contract_state, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let destination = create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+            .await
+            .unwrap();
+        let source = create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+            .await
+            .unwrap();
+
+        let result = banks_client
+            .process_transaction_with_metadata(renounce_authority_transaction(
+                &payer,
+                recent_blockhash,
+            ))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let event: AuthorityChanged = get_event(&result.metadata.unwrap().log_messages).unwrap();
+        assert_eq!(event.old_authority, payer.pubkey());
+        assert_eq!(event.new_authority, Pubkey::default());
+
+        let contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let state =
+            ContractState::try_deserialize(&mut contract_state_info.data.as_slice()).unwrap();
+        assert!(state.renounced);
+        assert_eq!(state.authority, Pubkey::default());
+
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+
+        for transaction in [
+            renounce_authority_transaction(&payer, recent_blockhash),
+            change_authority_transaction(&payer, recent_blockhash, payer.pubkey()),
+            set_paused_transaction(&payer, recent_blockhash, true),
+            add_keeper_transaction(&payer, recent_blockhash, Keypair::new().pubkey()),
+            recover_program_account_tokens_transaction(&payer, recent_blockhash, destination, 1),
+            revoke_unvested_transaction(
+                &payer,
+                recent_blockhash,
+                WalletKind::Community,
+                destination,
+            ),
+            set_role_transaction(&payer, &payer, recent_blockhash, Role::Burn, payer.pubkey()),
+            set_burn_rate_transaction(&payer, recent_blockhash, 250),
+            set_burn_window_transaction(&payer, recent_blockhash, 10),
+            set_late_burn_window_transaction(&payer, recent_blockhash, 16, 21),
+            rebalance_transaction(
+                &payer,
+                recent_blockhash,
+                WalletKind::Community,
+                WalletKind::Liquidity,
+                1,
+            ),
+            set_label_transaction(
+                &payer,
+                recent_blockhash,
+                LabeledAccountKind::BurningAccount,
+                "burning",
+            ),
+            add_to_vesting_transaction(&payer, recent_blockhash, WalletKind::Community, source, 0),
+            migrate_vesting_state_transaction(&payer, recent_blockhash),
+        ] {
+            let error = banks_client
+                .process_transaction(transaction)
+                .await
+                .unwrap_err()
+                .unwrap();
+            assert_eq!(
+                error,
+                TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(LeancoinError::AuthorityRenounced.into())
+                )
+            );
+        }
+
+        // the metadata role falls back to the owner only before the authority is renounced
+        #[cfg(feature = "metadata")]
+        {
+            let (metadata_pda, _) = Pubkey::find_program_address(
+                &[
+                    b"metadata",
+                    &mpl_token_metadata::id().to_bytes(),
+                    &mint.to_bytes(),
+                ],
+                &mpl_token_metadata::id(),
+            );
+            let error = banks_client
+                .process_transaction(set_token_metadata_transaction(
+                    &payer,
+                    recent_blockhash,
+                    ("Test", "TST", "https://test.com"),
+                    metadata_pda,
+                    mpl_token_metadata::id(),
+                ))
+                .await
+                .unwrap_err()
+                .unwrap();
+            assert_eq!(
+                error,
+                TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(LeancoinError::Unauthorized.into())
+                )
+            );
+        }
+
+        // the contract state is in the current layout, so there is nothing left to migrate
+        let error = banks_client
+            .process_transaction(migrate_contract_state_transaction(&payer, recent_blockhash))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::ContractStateAlreadyMigrated.into())
+            )
+        );
+
+        // the burn can be run by any signer once nobody can register keepers
+        banks_client
+            .process_transaction(burn_by_keeper_transaction(
+                &payer,
+                &Keypair::new(),
+                recent_blockhash,
+                false,
+            ))
+            .await
+            .unwrap();
+
+        let contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let state =
+            ContractState::try_deserialize(&mut contract_state_info.data.as_slice()).unwrap();
+        assert_eq!(state.last_burning_month, 3);
+    }
+
+    #[tokio::test]
+    async fn test_revoke_mint_authority() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (contract_state, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        // the mint authority can be revoked only after the Ethereum import
+        let error = banks_client
+            .process_transaction(revoke_mint_authority_transaction(&payer, recent_blockhash))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::ImportNotPerformedYet.into())
+            )
+        );
+
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        banks_client
+            .process_transaction(revoke_mint_authority_transaction(&payer, recent_blockhash))
+            .await
+            .unwrap();
+
+        let mint_info = banks_client.get_account(mint).await.unwrap().unwrap();
+        let mint_account =
+            spl_token::state::Mint::unpack_from_slice(mint_info.data.as_slice()).unwrap();
+        assert_eq!(mint_account.mint_authority, COption::None);
+
+        let contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let state =
+            ContractState::try_deserialize(&mut contract_state_info.data.as_slice()).unwrap();
+        assert!(state.mint_authority_revoked);
+
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+
+        for transaction in [
+            revoke_mint_authority_transaction(&payer, recent_blockhash),
+            import_external_token_state_transaction(
+                &payer,
+                recent_blockhash,
+                MigrationSource::Bsc,
+                vec![],
+                0,
+                0,
+            ),
+        ] {
+            let error = banks_client
+                .process_transaction(transaction)
+                .await
+                .unwrap_err()
+                .unwrap();
+            assert_eq!(
+                error,
+                TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(LeancoinError::MintAuthorityRevoked.into())
+                )
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_roles() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        // the signer of withdraw_all_unlocked pays for the withdrawal receipts
+        let burner = add_funded_keypair(&mut program_test);
+        let withdrawer = add_funded_keypair(&mut program_test);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let mut deposit_wallets = [Pubkey::default(); VESTED_WALLETS_COUNT];
+        for deposit_wallet in deposit_wallets.iter_mut() {
+            *deposit_wallet =
+                create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                    .await
+                    .unwrap();
+        }
+
+        // only the owner can assign the roles
+        let error = banks_client
+            .process_transaction(set_role_transaction(
+                &payer,
+                &burner,
+                recent_blockhash,
+                Role::Burn,
+                burner.pubkey(),
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::Unauthorized.into())
+            )
+        );
+
+        for (role, authority) in [
+            (Role::Burn, burner.pubkey()),
+            (Role::Withdraw, withdrawer.pubkey()),
+        ] {
+            let result = banks_client
+                .process_transaction_with_metadata(set_role_transaction(
+                    &payer,
+                    &payer,
+                    recent_blockhash,
+                    role,
+                    authority,
+                ))
+                .await
+                .unwrap();
+            result.result.unwrap();
+            let event: RoleChanged = get_event(&result.metadata.unwrap().log_messages).unwrap();
+            assert_eq!(event.role, role);
+            assert_eq!(event.authority, authority);
+        }
+
+        // a role authority can neither assign the roles nor run the instructions of another role
+        for transaction in [
+            set_role_transaction(
+                &payer,
+                &withdrawer,
+                recent_blockhash,
+                Role::Burn,
+                withdrawer.pubkey(),
+            ),
+            burn_by_keeper_transaction(&payer, &withdrawer, recent_blockhash, false),
+        ] {
+            let error = banks_client
+                .process_transaction(transaction)
+                .await
+                .unwrap_err()
+                .unwrap();
+            assert_eq!(
+                error,
+                TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(LeancoinError::Unauthorized.into())
+                )
+            );
+        }
+
+        banks_client
+            .process_transaction(burn_by_keeper_transaction(
+                &payer,
+                &burner,
+                recent_blockhash,
+                false,
+            ))
+            .await
+            .unwrap();
+
+        //  Friday, 5 April 2024 01:01:01
+        let timestamp = 1712278861;
+        set_time(&mut program_test_context, timestamp).await;
+
+        let withdrawal_receipts = get_next_withdrawal_receipt_accounts(&mut banks_client).await;
+        let error = banks_client
+            .process_transaction(withdraw_all_unlocked_by_transaction(
+                &payer,
+                &burner,
+                recent_blockhash,
+                deposit_wallets,
+                withdrawal_receipts,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::Unauthorized.into())
+            )
+        );
+
+        banks_client
+            .process_transaction(withdraw_all_unlocked_by_transaction(
+                &payer,
+                &withdrawer,
+                recent_blockhash,
+                deposit_wallets,
+                withdrawal_receipts,
+            ))
+            .await
+            .unwrap();
+        for deposit_wallet in deposit_wallets {
+            assert!(get_token_balance(&mut banks_client, &deposit_wallet).await > 0);
+        }
+
+        // unassigning the role revokes the access
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+        banks_client
+            .process_transaction(set_role_transaction(
+                &payer,
+                &payer,
+                recent_blockhash,
+                Role::Withdraw,
+                Pubkey::default(),
+            ))
+            .await
+            .unwrap();
+
+        let withdrawal_receipts = get_next_withdrawal_receipt_accounts(&mut banks_client).await;
+        let error = banks_client
+            .process_transaction(withdraw_all_unlocked_by_transaction(
+                &payer,
+                &withdrawer,
+                recent_blockhash,
+                deposit_wallets,
+                withdrawal_receipts,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::Unauthorized.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_renounce_authority_while_paused_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        banks_client
+            .process_transaction(set_paused_transaction(&payer, recent_blockhash, true))
+            .await
+            .unwrap();
+
+        let error = banks_client
+            .process_transaction(renounce_authority_transaction(&payer, recent_blockhash))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::ContractPaused.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_events_from_program_logs() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        let mut log_messages = vec![];
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        for transaction in [
+            burn_transaction(&payer, recent_blockhash, false),
+            withdraw_tokens_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                None,
+                withdrawal_receipt,
+            ),
+        ] {
+            let result = banks_client
+                .process_transaction_with_metadata(transaction)
+                .await
+                .unwrap();
+            result.result.unwrap();
+            log_messages.extend(result.metadata.unwrap().log_messages);
+        }
+
+        // the logs of both instructions are interleaved with the logs of the token program
+        let events = parse_events(&log_messages);
+        assert_eq!(events.len(), 2);
+        let burn_event = match &events[0] {
+            LeancoinEvent::BurnExecuted(event) => event,
+            _ => panic!("unexpected event"),
+        };
+        assert_eq!(burn_event.amount, 1800000000000000000 / 20);
+        let withdrawal_event = match &events[1] {
+            LeancoinEvent::VestedTokensWithdrawn(event) => event,
+            _ => panic!("unexpected event"),
+        };
+        assert_eq!(withdrawal_event.wallet_kind, WalletKind::Liquidity);
+        assert_eq!(withdrawal_event.amount, 1);
+        assert_eq!(withdrawal_event.destination, deposit_wallet);
+        assert!(burn_event.sequence < withdrawal_event.sequence);
+    }
+
+    #[tokio::test]
+    async fn test_forecast_unlocked() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        set_time(&mut program_test_context, 1677978061).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, vesting_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        assert_eq!(
+            forecast_unlocked_instruction(&mut banks_client, &payer, recent_blockhash, 12)
+                .await
+                .unwrap_err(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::ImportNotPerformedYet.into())
+            )
+        );
+
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        let vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let state =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                .unwrap();
+
+        // the forecast does not depend on the current time
+        for months in [0, 12, 13, 39] {
+            let forecast =
+                forecast_unlocked_instruction(&mut banks_client, &payer, recent_blockhash, months)
+                    .await
+                    .unwrap();
+            assert_eq!(forecast.months_since_vesting_start, months);
+            for wallet_kind in WalletKind::ALL {
+                assert_eq!(
+                    forecast.unlocked[wallet_kind.index()],
+                    calculate_unlocked_amount(
+                        &vesting_schedule(&state, wallet_kind),
+                        state.wallet(wallet_kind).initial_balance,
+                        months,
+                    )
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_vesting_status() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        set_time(&mut program_test_context, 1677978061).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (
+            _,
+            _,
+            vesting_state,
+            _,
+            mint,
+            _,
+            _,
+            _,
+            _,
+            _,
+            community_account,
+            _,
+            partnership_account,
+            _,
+            marketing_account,
+            _,
+            liquidity_account,
+            _,
+        ) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        // the vesting has not started before the import
+        assert_eq!(
+            get_vesting_status_instruction(&mut banks_client, &payer, recent_blockhash)
+                .await
+                .unwrap_err(),
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::ImportNotPerformedYet.into())
+            )
+        );
+
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        //  Thursday, 11 May 2023 01:01:01
+        let timestamp = 1683766861;
+        set_time(&mut program_test_context, timestamp).await;
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        banks_client
+            .process_transaction(withdraw_tokens_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                None,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap();
+
+        let vesting_state_data = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap()
+            .data;
+        let vesting_status =
+            get_vesting_status_instruction(&mut banks_client, &payer, recent_blockhash)
+                .await
+                .unwrap();
+
+        let state =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_data.as_slice()).unwrap();
+        let months_since_vesting_start =
+            calculate_month_difference(state.start_timestamp, timestamp).unwrap();
+        assert_eq!(vesting_status.months_since_vesting_start, 2);
+        assert_eq!(vesting_status.decimals, LEGACY_MINT_DECIMALS);
+        assert_eq!(
+            vesting_status.months_since_vesting_start,
+            months_since_vesting_start
+        );
+
+        let wallet_accounts = [
+            community_account,
+            partnership_account,
+            marketing_account,
+            liquidity_account,
+        ];
+        for wallet_kind in WalletKind::ALL {
+            let unlocked = calculate_unlocked_amount(
+                &vesting_schedule(&state, wallet_kind),
+                state.wallet(wallet_kind).initial_balance,
+                months_since_vesting_start,
+            );
+            let withdrawn = state.wallet(wallet_kind).withdrawn;
+            let balance =
+                get_token_balance(&mut banks_client, &wallet_accounts[wallet_kind.index()]).await;
+            assert_eq!(
+                vesting_status.wallets[wallet_kind.index()],
+                WalletVestingStatus {
+                    unlocked,
+                    withdrawn,
+                    withdrawable: balance.min(unlocked - withdrawn),
+                },
+                "{:?}",
+                wallet_kind
+            );
+        }
+
+        // half of the liquidity wallet is unlocked in the first year and 1 token has been withdrawn
+        assert_eq!(
+            vesting_status.wallets[WalletKind::Liquidity.index()],
+            WalletVestingStatus {
+                unlocked: 500000000000000000,
+                withdrawn: 1,
+                withdrawable: 499999999999999999,
+            }
+        );
+
+        // reading the status does not modify the vesting state
+        assert_eq!(
+            banks_client
+                .get_account(vesting_state)
+                .await
+                .unwrap()
+                .unwrap()
+                .data,
+            vesting_state_data
+        );
+    }
+
+    #[tokio::test]
+    async fn test_initialize_wallets_with_custom_vesting_schedules() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let (_, _, vesting_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        // the liquidity wallet is unlocked at once and the partnership wallet 10% every month
+        let mut vesting_schedules = VestingSchedule::defaults();
+        vesting_schedules[WalletKind::Liquidity.index()] = VestingSchedule {
+            initial_unlock_bps: 10000,
+            monthly_unlock_bps: 0,
+            cliff_months: 0,
+            full_unlock_months: 0,
+            minimum_unlock: 0,
+        };
+        vesting_schedules[WalletKind::Partnership.index()] = VestingSchedule {
+            initial_unlock_bps: 1000,
+            monthly_unlock_bps: 1000,
+            cliff_months: 0,
+            full_unlock_months: 9,
+            minimum_unlock: 0,
+        };
+
+        // the unlocks of the marketing wallet would sum to more than the whole balance
+        let mut invalid_vesting_schedules = vesting_schedules;
+        invalid_vesting_schedules[WalletKind::Marketing.index()].initial_unlock_bps = 4001;
+        let error = banks_client
+            .process_transaction(initialize_wallets_transaction(
+                &payer,
+                recent_blockhash,
+                Some(invalid_vesting_schedules),
+                None,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::InvalidVestingSchedule.into())
+            )
+        );
+
+        banks_client
+            .process_transaction(initialize_wallets_transaction(
+                &payer,
+                recent_blockhash,
+                Some(vesting_schedules),
+                None,
+            ))
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let state =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                .unwrap();
+        assert_eq!(state.schedules, vesting_schedules);
+
+        let vesting_status =
+            get_vesting_status_instruction(&mut banks_client, &payer, recent_blockhash)
+                .await
+                .unwrap();
+        assert_eq!(
+            vesting_status.wallets.map(|wallet| wallet.unlocked),
+            [
+                25000000000000000,
+                200000000000000000,
+                0,
+                1000000000000000000
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_linear_daily_unlock() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        set_time(&mut program_test_context, 1677978061).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, vesting_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        // the cliff of the liquidity wallet stays step-based
+        let error = banks_client
+            .process_transaction(initialize_wallets_transaction(
+                &payer,
+                recent_blockhash,
+                None,
+                Some([true, false, true, true]),
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::LinearDailyUnlockNotSupported.into())
+            )
+        );
+
+        banks_client
+            .process_transaction(initialize_wallets_transaction(
+                &payer,
+                recent_blockhash,
+                None,
+                Some([true, false, true, false]),
+            ))
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let state =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                .unwrap();
+        assert!(state.linear_daily_unlock(WalletKind::Community));
+        assert!(!state.linear_daily_unlock(WalletKind::Partnership));
+        assert!(state.linear_daily_unlock(WalletKind::Marketing));
+        assert!(!state.linear_daily_unlock(WalletKind::Liquidity));
+
+        // 4 of 31 days of March have elapsed, so 4/31 of the next 2.5% of the community wallet are unlocked,
+        // the marketing wallet is before its cliff and the other wallets keep their monthly steps
+        let vesting_status =
+            get_vesting_status_instruction(&mut banks_client, &payer, recent_blockhash)
+                .await
+                .unwrap();
+        assert_eq!(
+            vesting_status.wallets.map(|wallet| wallet.unlocked),
+            [28225806451612903, 0, 0, 500000000000000000]
+        );
+
+        //  Saturday, 15 April 2023 01:01:01, Sunday, 30 April 2023 23:59:59 and Monday, 1 May 2023 00:00:00
+        let mut previous_unlocked = 0;
+        for (timestamp, expected_unlocked) in [
+            (1681520461, 61666666666666666),
+            (1682899199, 74166666666666666),
+            (1682899200, 75000000000000000),
+        ] {
+            set_time(&mut program_test_context, timestamp).await;
+            let recent_blockhash = banks_client
+                .get_new_latest_blockhash(&recent_blockhash)
+                .await
+                .unwrap();
+            let vesting_status =
+                get_vesting_status_instruction(&mut banks_client, &payer, recent_blockhash)
+                    .await
+                    .unwrap();
+
+            let unlocked = vesting_status.wallets[WalletKind::Community.index()].unlocked;
+            assert_eq!(unlocked, expected_unlocked);
+            assert!(unlocked > previous_unlocked);
+            previous_unlocked = unlocked;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_token_info() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let token_info = get_token_info_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        // all imported tokens are held by the program accounts, 1/40 of the community wallet
+        // and half of the liquidity wallet are unlocked in the first month
+        let expected_token_info = TokenInfo {
+            version: TOKEN_INFO_VERSION,
+            mint,
+            decimals: 9,
+            supply: 8530000000000000000,
+            total_burned: 1470000000000000000,
+            circulating_supply: 0,
+            locked: [
+                975000000000000000,
+                2000000000000000000,
+                1500000000000000000,
+                500000000000000000,
+            ],
+            authority: payer.pubkey(),
+            mint_authority_revoked: false,
+        };
+        assert_eq!(token_info, expected_token_info);
+
+        banks_client
+            .process_transaction(burn_transaction(&payer, recent_blockhash, false))
+            .await
+            .unwrap();
+
+        let token_info = get_token_info_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            token_info,
+            TokenInfo {
+                supply: 8440000000000000000,
+                total_burned: 1560000000000000000,
+                ..expected_token_info.clone()
+            }
+        );
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        banks_client
+            .process_transaction(withdraw_tokens_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                None,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap();
+
+        let token_info = get_token_info_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        // the withdrawn token is the only one held outside of the program accounts
+        assert_eq!(
+            token_info,
+            TokenInfo {
+                supply: 8440000000000000000,
+                total_burned: 1560000000000000000,
+                circulating_supply: 1,
+                ..expected_token_info
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_burn_event_reports_mint_supply() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, _, _, mint, _, _, _, burning_account, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let mint_info = banks_client.get_account(mint).await.unwrap().unwrap();
+        let supply_before = spl_token::state::Mint::unpack_from_slice(mint_info.data.as_slice())
+            .unwrap()
+            .supply;
+
+        let result = banks_client
+            .process_transaction_with_metadata(burn_transaction(&payer, recent_blockhash, false))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let burn_event: BurnExecuted = get_event(&result.metadata.unwrap().log_messages).unwrap();
+
+        let mint_info = banks_client.get_account(mint).await.unwrap().unwrap();
+        let supply_after = spl_token::state::Mint::unpack_from_slice(mint_info.data.as_slice())
+            .unwrap()
+            .supply;
+
+        assert_eq!(burn_event.supply_before, supply_before);
+        assert_eq!(burn_event.supply_after, supply_after);
+        assert_eq!(
+            burn_event.supply_before - burn_event.supply_after,
+            burn_event.amount
+        );
+        assert_eq!(burn_event.amount, 1800000000000000000 / 20);
+        assert_eq!(burn_event.month, 3);
+        assert_eq!(burn_event.year, 2023);
+        assert_eq!(
+            burn_event.burning_account_balance,
+            get_token_balance(&mut banks_client, &burning_account).await
+        );
+        assert_eq!(
+            burn_event.burning_account_balance,
+            1800000000000000000 - burn_event.amount
+        );
+        assert_eq!(burn_event.burn_rate_bps, DEFAULT_BURN_RATE_BPS);
+    }
+
+    #[tokio::test]
+    async fn test_burn_includes_tokens_transferred_earlier_in_transaction() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (contract_state, _, _, _, mint, _, _, _, burning_account, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let donor = add_funded_keypair(&mut program_test);
+        let donor_token_account = add_token_account(
+            &mut program_test,
+            mint,
+            donor.pubkey(),
+            200000000000000000,
+            AccountState::Initialized,
+        );
+
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let mint_info = banks_client.get_account(mint).await.unwrap().unwrap();
+        let supply_before = spl_token::state::Mint::unpack_from_slice(mint_info.data.as_slice())
+            .unwrap()
+            .supply;
+
+        let accs = BurnContext {
+            contract_state,
+            mint,
+            burning_account,
+            token_program: spl_token::id(),
+            instructions_sysvar: sysvar::instructions::ID,
+            staking_state: None,
+            reward_vault: None,
+            signer: payer.pubkey(),
+            caller_token_account: None,
+            burn_history: get_burn_history_account(),
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                spl_token::instruction::transfer(
+                    &spl_token::id(),
+                    &donor_token_account,
+                    &burning_account,
+                    &donor.pubkey(),
+                    &[],
+                    200000000000000000,
+                )
+                .unwrap(),
+                Instruction::new_with_bytes(
+                    program_id,
+                    &instruction::Burn {}.data(),
+                    accs.to_account_metas(Some(false)),
+                ),
+            ],
+            Some(&payer.pubkey()),
+            &[&payer, &donor],
+            recent_blockhash,
+        );
+        let result = banks_client
+            .process_transaction_with_metadata(transaction)
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let burn_event: BurnExecuted = get_event(&result.metadata.unwrap().log_messages).unwrap();
+
+        let mint_info = banks_client.get_account(mint).await.unwrap().unwrap();
+        let supply_after = spl_token::state::Mint::unpack_from_slice(mint_info.data.as_slice())
+            .unwrap()
+            .supply;
+
+        // 5% of the balance including the tokens transferred by the first instruction
+        assert_eq!(burn_event.amount, 2000000000000000000 / 20);
+        assert_eq!(supply_before - supply_after, burn_event.amount);
+        // the reported balance is read after the burn, not from the accounts deserialized before it
+        assert_eq!(
+            burn_event.burning_account_balance,
+            2000000000000000000 - burn_event.amount
+        );
+        assert_eq!(
+            get_token_balance(&mut banks_client, &burning_account).await,
+            burn_event.burning_account_balance
+        );
+    }
+
+    #[tokio::test]
+    async fn test_burn_rewards_caller() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, _, _, mint, _, _, _, burning_account, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        banks_client
+            .process_transaction(set_burn_caller_reward_transaction(
+                &payer,
+                recent_blockhash,
+                MAX_BURN_CALLER_REWARD_BPS,
+            ))
+            .await
+            .unwrap();
+
+        let caller_token_account =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        let mint_info = banks_client.get_account(mint).await.unwrap().unwrap();
+        let supply_before = spl_token::state::Mint::unpack_from_slice(mint_info.data.as_slice())
+            .unwrap()
+            .supply;
+
+        let result = banks_client
+            .process_transaction_with_metadata(burn_with_mint_transaction(
+                &payer,
+                &payer,
+                recent_blockhash,
+                false,
+                mint,
+                Some(caller_token_account),
+            ))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let burn_event: BurnExecuted = get_event(&result.metadata.unwrap().log_messages).unwrap();
+
+        let mint_info = banks_client.get_account(mint).await.unwrap().unwrap();
+        let supply_after = spl_token::state::Mint::unpack_from_slice(mint_info.data.as_slice())
+            .unwrap()
+            .supply;
+
+        // 0.5% of the 5% leaving the burning account is paid to the caller
+        let caller_reward = 1800000000000000000 / 20 / 200;
+        assert_eq!(burn_event.caller_reward, caller_reward);
+        assert_eq!(
+            get_token_balance(&mut banks_client, &caller_token_account).await,
+            caller_reward
+        );
+        assert_eq!(burn_event.amount, 1800000000000000000 / 20 - caller_reward);
+        assert_eq!(supply_before - supply_after, burn_event.amount);
+        assert_eq!(
+            get_token_balance(&mut banks_client, &burning_account).await,
+            1800000000000000000 - burn_event.amount - caller_reward
+        );
+        assert_eq!(
+            burn_event.burning_account_balance,
+            1800000000000000000 - burn_event.amount - caller_reward
+        );
+    }
+
+    #[tokio::test]
+    async fn test_burn_without_caller_reward_configured() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let caller_token_account =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        // the reward is zero by default, so the transfer is skipped and the whole amount is burned
+        let result = banks_client
+            .process_transaction_with_metadata(burn_with_mint_transaction(
+                &payer,
+                &payer,
+                recent_blockhash,
+                false,
+                mint,
+                Some(caller_token_account),
+            ))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let burn_event: BurnExecuted = get_event(&result.metadata.unwrap().log_messages).unwrap();
+
+        assert_eq!(burn_event.caller_reward, 0);
+        assert_eq!(burn_event.amount, 1800000000000000000 / 20);
+        assert_eq!(
+            get_token_balance(&mut banks_client, &caller_token_account).await,
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_burn_caller_reward_above_maximum_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let error = banks_client
+            .process_transaction(set_burn_caller_reward_transaction(
+                &payer,
+                recent_blockhash,
+                MAX_BURN_CALLER_REWARD_BPS + 1,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::InvalidBurnCallerReward.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_default_burn_rate_matches_five_percent() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (contract_state, _, _, _, _, _, _, _, burning_account, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let state =
+            ContractState::try_deserialize(&mut contract_state_info.data.as_slice()).unwrap();
+        assert_eq!(state.burn_rate_bps, 500);
+
+        let result = banks_client
+            .process_transaction_with_metadata(burn_transaction(&payer, recent_blockhash, false))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let burn_event: BurnExecuted = get_event(&result.metadata.unwrap().log_messages).unwrap();
+
+        assert_eq!(burn_event.amount, 1800000000000000000 / 20);
+        assert_eq!(burn_event.burn_rate_bps, 500);
+        assert_eq!(
+            get_token_balance(&mut banks_client, &burning_account).await,
+            1800000000000000000 - 1800000000000000000 / 20
+        );
+    }
+
+    #[tokio::test]
+    async fn test_burn_nothing_to_burn_below_threshold() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (contract_state, _, _, _, _, _, _, _, burning_account, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let burning_account_info = banks_client
+            .get_account(burning_account)
+            .await
+            .unwrap()
+            .unwrap();
+        let mut burning_token_account =
+            spl_token::state::Account::unpack(&burning_account_info.data).unwrap();
+
+        // 5% of 19 base units rounds down to zero
+        burning_token_account.amount = 19;
+        let mut data = vec![0; Account::LEN];
+        burning_token_account.pack_into_slice(&mut data);
+        program_test_context.set_account(
+            &burning_account,
+            &solana_sdk::account::Account {
+                data,
+                ..burning_account_info.clone()
+            }
+            .into(),
+        );
+
+        let error = banks_client
+            .process_transaction(burn_transaction(&payer, recent_blockhash, false))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::NothingToBurn.into())
+            )
+        );
+
+        let contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let state =
+            ContractState::try_deserialize(&mut contract_state_info.data.as_slice()).unwrap();
+        assert_ne!(state.last_burning_month, 3);
+        assert_eq!(
+            get_token_balance(&mut banks_client, &burning_account).await,
+            19
+        );
+
+        // the burn of the month can be retried once the burning account holds 20 base units
+        burning_token_account.amount = 20;
+        let mut data = vec![0; Account::LEN];
+        burning_token_account.pack_into_slice(&mut data);
+        program_test_context.set_account(
+            &burning_account,
+            &solana_sdk::account::Account {
+                data,
+                ..burning_account_info
+            }
+            .into(),
+        );
+
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+        let result = banks_client
+            .process_transaction_with_metadata(burn_transaction(&payer, recent_blockhash, false))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let burn_event: BurnExecuted = get_event(&result.metadata.unwrap().log_messages).unwrap();
+
+        assert_eq!(burn_event.amount, 1);
+        assert_eq!(burn_event.burning_account_balance, 19);
+    }
+
+    #[tokio::test]
+    async fn test_burn_with_custom_burn_rate() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (contract_state, _, _, _, _, _, _, _, burning_account, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        banks_client
+            .process_transaction(set_burn_rate_transaction(&payer, recent_blockhash, 250))
+            .await
+            .unwrap();
+
+        let contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let state =
+            ContractState::try_deserialize(&mut contract_state_info.data.as_slice()).unwrap();
+        assert_eq!(state.burn_rate_bps, 250);
+
+        let result = banks_client
+            .process_transaction_with_metadata(burn_transaction(&payer, recent_blockhash, false))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let burn_event: BurnExecuted = get_event(&result.metadata.unwrap().log_messages).unwrap();
+
+        // 2.5% of the burning account balance
+        assert_eq!(burn_event.amount, 45000000000000000);
+        assert_eq!(burn_event.burn_rate_bps, 250);
+        assert_eq!(
+            get_token_balance(&mut banks_client, &burning_account).await,
+            1800000000000000000 - 45000000000000000
+        );
+    }
+
+    #[tokio::test]
+    async fn test_burn_exact_and_monthly_burn_in_same_month() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (contract_state, _, _, _, _, _, _, _, burning_account, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let result = banks_client
+            .process_transaction_with_metadata(burn_exact_transaction(
+                &payer,
+                recent_blockhash,
+                100000000000000000,
+            ))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let manual_burn_event: BurnExecuted =
+            get_event(&result.metadata.unwrap().log_messages).unwrap();
+
+        assert!(manual_burn_event.manual);
+        assert_eq!(manual_burn_event.amount, 100000000000000000);
+        assert_eq!(manual_burn_event.month, 3);
+        assert_eq!(
+            manual_burn_event.supply_before - manual_burn_event.supply_after,
+            100000000000000000
+        );
+        assert_eq!(
+            manual_burn_event.burning_account_balance,
+            1700000000000000000
+        );
+
+        let contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let state =
+            ContractState::try_deserialize(&mut contract_state_info.data.as_slice()).unwrap();
+        assert_eq!(state.total_burned, 100000000000000000);
+        assert_ne!(state.last_burning_month, 3);
+
+        // the scheduled burn of the month is still possible after the manual one
+        let result = banks_client
+            .process_transaction_with_metadata(burn_transaction(&payer, recent_blockhash, false))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let burn_event: BurnExecuted = get_event(&result.metadata.unwrap().log_messages).unwrap();
+
+        assert!(!burn_event.manual);
+        assert_eq!(burn_event.amount, 1700000000000000000 / 20);
+        assert_eq!(
+            get_token_balance(&mut banks_client, &burning_account).await,
+            1700000000000000000 - 1700000000000000000 / 20
+        );
+
+        let contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let state =
+            ContractState::try_deserialize(&mut contract_state_info.data.as_slice()).unwrap();
+        assert_eq!(
+            state.total_burned,
+            100000000000000000 + 1700000000000000000 / 20
+        );
+        assert_eq!(state.last_burning_month, 3);
+    }
+
+    #[tokio::test]
+    async fn test_burn_exact_more_than_balance_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let error = banks_client
+            .process_transaction(burn_exact_transaction(
+                &payer,
+                recent_blockhash,
+                1800000000000000001,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::NotEnoughTokens.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_burn_rate_out_of_range_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        for burn_rate_bps in [0, BURN_RATE_DENOMINATOR as u16 + 1] {
+            let error = banks_client
+                .process_transaction(set_burn_rate_transaction(
+                    &payer,
+                    recent_blockhash,
+                    burn_rate_bps,
+                ))
+                .await
+                .unwrap_err()
+                .unwrap();
+            assert_eq!(
+                error,
+                TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(LeancoinError::InvalidBurnRate.into())
+                )
+            );
+        }
+
+        banks_client
+            .process_transaction(set_burn_rate_transaction(
+                &payer,
+                recent_blockhash,
+                BURN_RATE_DENOMINATOR as u16,
+            ))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sequence_numbers_strictly_increase_across_burn_and_withdrawals() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (contract_state, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        let transaction = burn_transaction(&payer, recent_blockhash, false);
+        let result = banks_client
+            .process_transaction_with_metadata(transaction)
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let burn_event: BurnExecuted = get_event(&result.metadata.unwrap().log_messages).unwrap();
+        assert_eq!(burn_event.amount, 1800000000000000000 / 20);
+        assert_eq!(burn_event.rewards, 0);
+
+        let mut sequences = vec![burn_event.sequence];
+        let mut blockhash = recent_blockhash;
+        for _ in 0..2 {
+            blockhash = banks_client
+                .get_new_latest_blockhash(&blockhash)
+                .await
+                .unwrap();
+            let withdrawal_receipt =
+                get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+            let transaction = withdraw_tokens_from_liquidity_wallet_transaction(
+                &payer,
+                blockhash,
+                deposit_wallet,
+                None,
+                withdrawal_receipt,
+            );
+            let result = banks_client
+                .process_transaction_with_metadata(transaction)
+                .await
+                .unwrap();
+            result.result.unwrap();
+
+            let event: VestedTokensWithdrawn =
+                get_event(&result.metadata.unwrap().log_messages).unwrap();
+            assert_eq!(event.wallet_kind, WalletKind::Liquidity);
+            assert_eq!(event.amount, 1);
+            assert_eq!(event.destination, deposit_wallet);
+            sequences.push(event.sequence);
+        }
+
+        assert!(sequences.windows(2).all(|pair| pair[0] < pair[1]));
+
+        // read-only instructions do not consume a sequence number
+        get_months_since_start_instruction(&mut banks_client, &payer, blockhash)
+            .await
+            .unwrap();
+
+        let contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let contract_state =
+            ContractState::try_deserialize_unchecked(&mut contract_state_info.data.as_slice())
+                .unwrap();
+        assert_eq!(contract_state.sequence, *sequences.last().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_liquidity_drip_over_months() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        let recipient = create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+            .await
+            .unwrap();
+        configure_liquidity_drip_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            recipient,
+            200000000000000000,
+        )
+        .await
+        .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        execute_liquidity_drip_instruction(&mut banks_client, &payer, recent_blockhash, recipient)
+            .await
+            .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &recipient).await,
+            200000000000000000
+        );
+
+        //  Tuesday, 11 April 2023 01:01:01
+        let recent_blockhash = program_test_context
+            .get_new_latest_blockhash()
+            .await
+            .unwrap();
+        let time_in_timestamp = 1681174861;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        execute_liquidity_drip_instruction(&mut banks_client, &payer, recent_blockhash, recipient)
+            .await
+            .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &recipient).await,
+            400000000000000000
+        );
+
+        // only 50% of the liquidity wallet is unlocked during the first year
+        //  Thursday, 11 May 2023 01:01:01
+        let recent_blockhash = program_test_context
+            .get_new_latest_blockhash()
+            .await
+            .unwrap();
+        let time_in_timestamp = 1683766861;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        execute_liquidity_drip_instruction(&mut banks_client, &payer, recent_blockhash, recipient)
+            .await
+            .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &recipient).await,
+            500000000000000000
+        );
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn test_liquidity_drip_twice_in_one_month_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        let recipient = create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+            .await
+            .unwrap();
+        configure_liquidity_drip_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            recipient,
+            200000000000000000,
+        )
+        .await
+        .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        execute_liquidity_drip_instruction(&mut banks_client, &payer, recent_blockhash, recipient)
+            .await
+            .unwrap();
+
+        //  Friday, 31 March 2023 23:59:59
+        let recent_blockhash = program_test_context
+            .get_new_latest_blockhash()
+            .await
+            .unwrap();
+        let time_in_timestamp = 1680307199;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        execute_liquidity_drip_instruction(&mut banks_client, &payer, recent_blockhash, recipient)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_liquidity_drip_from_revoked_wallet_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        set_time(&mut program_test_context, 1677978061).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        let recipient = create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+            .await
+            .unwrap();
+        configure_liquidity_drip_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            recipient,
+            200000000000000000,
+        )
+        .await
+        .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let destination = create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+            .await
+            .unwrap();
+        banks_client
+            .process_transaction(revoke_unvested_transaction(
+                &payer,
+                recent_blockhash,
+                WalletKind::Liquidity,
+                destination,
+            ))
+            .await
+            .unwrap();
+
+        let error = banks_client
+            .process_transaction(execute_liquidity_drip_transaction(
+                &payer,
+                recent_blockhash,
+                recipient,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::WalletAlreadyRevoked.into())
+            )
+        );
+        assert_eq!(get_token_balance(&mut banks_client, &recipient).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_otc_deal_settled_by_partner() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        let partner = add_funded_keypair(&mut program_test);
+        let partner_token_account = add_token_account(
+            &mut program_test,
+            mint,
+            partner.pubkey(),
+            0,
+            AccountState::Initialized,
+        );
+        let (_, otc_vault) = get_otc_deal_accounts(&partner.pubkey());
+
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        //  Tuesday, 11 April 2023 01:01:01
+        let time_in_timestamp = 1681174861;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        //  Thursday, 11 May 2023 01:01:01
+        let unlock_ts = 1683766861;
+        create_otc_deal_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            partner.pubkey(),
+            500000000000000000,
+            unlock_ts,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &otc_vault).await,
+            500000000000000000
+        );
+
+        set_time(&mut program_test_context, unlock_ts).await;
+
+        settle_otc_deal_instruction(
+            &mut banks_client,
+            &partner,
+            recent_blockhash,
+            partner_token_account,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &partner_token_account).await,
+            500000000000000000
+        );
+        assert_eq!(get_token_balance(&mut banks_client, &otc_vault).await, 0);
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn test_otc_deal_settled_before_unlock_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        let partner = add_funded_keypair(&mut program_test);
+        let partner_token_account = add_token_account(
+            &mut program_test,
+            mint,
+            partner.pubkey(),
+            0,
+            AccountState::Initialized,
+        );
+
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        //  Tuesday, 11 April 2023 01:01:01
+        let time_in_timestamp = 1681174861;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        //  Thursday, 11 May 2023 01:01:01
+        create_otc_deal_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            partner.pubkey(),
+            500000000000000000,
+            1683766861,
+        )
+        .await
+        .unwrap();
+
+        settle_otc_deal_instruction(
+            &mut banks_client,
+            &partner,
+            recent_blockhash,
+            partner_token_account,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cancel_otc_deal() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, vesting_state, _, _, _, _, _, _, _, _, _, partnership_account, _, _, _, _, _) =
+            get_pda_accounts();
+        let partner = Pubkey::new_unique();
+        let (_, otc_vault) = get_otc_deal_accounts(&partner);
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        //  Tuesday, 11 April 2023 01:01:01
+        let time_in_timestamp = 1681174861;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        //  Thursday, 11 May 2023 01:01:01
+        create_otc_deal_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            partner,
+            500000000000000000,
+            1683766861,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &partnership_account).await,
+            1500000000000000000
+        );
+
+        cancel_otc_deal_instruction(&mut banks_client, &payer, recent_blockhash, partner)
+            .await
+            .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &partnership_account).await,
+            2000000000000000000
+        );
+        assert_eq!(get_token_balance(&mut banks_client, &otc_vault).await, 0);
+
+        let vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let vesting_state =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                .unwrap();
+        assert_eq!(vesting_state.wallet(WalletKind::Partnership).withdrawn, 0);
+    }
+
+    #[tokio::test]
+    async fn test_otc_deal_with_all_unlocked_tokens() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (_, _, vesting_state, _, mint, _, _, _, _, _, _, _, partnership_account, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let partner = add_funded_keypair(&mut program_test);
+        let partner_token_account = add_token_account(
+            &mut program_test,
+            mint,
+            partner.pubkey(),
+            0,
+            AccountState::Initialized,
+        );
+        let (otc_deal, otc_vault) = get_otc_deal_accounts(&partner.pubkey());
+
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        //  Tuesday, 11 April 2023 01:01:01
+        let time_in_timestamp = 1681174861;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        //  Thursday, 11 May 2023 01:01:01
+        let unlock_ts = 1683766861;
+        create_otc_deal_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            partner.pubkey(),
+            WITHDRAW_ALL_UNLOCKED,
+            unlock_ts,
+        )
+        .await
+        .unwrap();
+
+        let escrowed_amount = get_token_balance(&mut banks_client, &otc_vault).await;
+        assert!(escrowed_amount > 0);
+        assert_eq!(
+            get_token_balance(&mut banks_client, &partnership_account).await,
+            2000000000000000000 - escrowed_amount
+        );
+
+        let otc_deal_info = banks_client.get_account(otc_deal).await.unwrap().unwrap();
+        let otc_deal_data =
+            OtcDeal::try_deserialize_unchecked(&mut otc_deal_info.data.as_slice()).unwrap();
+        assert_eq!(otc_deal_data.lean_amount, escrowed_amount);
+
+        let vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let vesting_state_data =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                .unwrap();
+        assert_eq!(
+            vesting_state_data.wallet(WalletKind::Partnership).withdrawn,
+            escrowed_amount
+        );
+
+        set_time(&mut program_test_context, unlock_ts).await;
+
+        settle_otc_deal_instruction(
+            &mut banks_client,
+            &partner,
+            recent_blockhash,
+            partner_token_account,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &partner_token_account).await,
+            escrowed_amount
+        );
+        assert_eq!(get_token_balance(&mut banks_client, &otc_vault).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_otc_deal_limited_by_custom_vesting_schedule() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        set_time(&mut program_test_context, 1677978061).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let partner = Pubkey::new_unique();
+        let (_, otc_vault) = get_otc_deal_accounts(&partner);
+
+        initialize_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        // the partnership wallet is unlocked 10% at once and 10% every month instead of 50% after 1 month
+        let mut vesting_schedules = VestingSchedule::defaults();
+        vesting_schedules[WalletKind::Partnership.index()] = VestingSchedule {
+            initial_unlock_bps: 1000,
+            monthly_unlock_bps: 1000,
+            cliff_months: 0,
+            full_unlock_months: 9,
+            minimum_unlock: 0,
+        };
+        banks_client
+            .process_transaction(initialize_wallets_transaction(
+                &payer,
+                recent_blockhash,
+                Some(vesting_schedules),
+                None,
+            ))
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        //  Tuesday, 11 April 2023 01:01:01, 20% of the partnership wallet is unlocked
+        let unlock_ts = 1681174861;
+        set_time(&mut program_test_context, unlock_ts).await;
+
+        let error = banks_client
+            .process_transaction(create_otc_deal_transaction(
+                &payer,
+                recent_blockhash,
+                partner,
+                500000000000000000,
+                unlock_ts,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::NotEnoughTokensPartnership.into())
+            )
+        );
+
+        create_otc_deal_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            partner,
+            WITHDRAW_ALL_UNLOCKED,
+            unlock_ts,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &otc_vault).await,
+            400000000000000000
+        );
+    }
+
+    #[tokio::test]
+    async fn test_otc_deal_from_revoked_partnership_wallet_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        set_time(&mut program_test_context, 1677978061).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let destination = create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+            .await
+            .unwrap();
+
+        //  Tuesday, 11 April 2023 01:01:01, 50% of the partnership wallet is unlocked and frozen by the revocation
+        let unlock_ts = 1681174861;
+        set_time(&mut program_test_context, unlock_ts).await;
+
+        banks_client
+            .process_transaction(revoke_unvested_transaction(
+                &payer,
+                recent_blockhash,
+                WalletKind::Partnership,
+                destination,
+            ))
+            .await
+            .unwrap();
+
+        let error = banks_client
+            .process_transaction(create_otc_deal_transaction(
+                &payer,
+                recent_blockhash,
+                Pubkey::new_unique(),
+                500000000000000000,
+                unlock_ts,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::WalletAlreadyRevoked.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rebalance_between_wallets() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (
+            _,
+            _,
+            vesting_state,
+            _,
+            mint,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            partnership_account,
+            _,
+            _,
+            _,
+            liquidity_account,
+            _,
+        ) = get_pda_accounts();
+        let partner = Keypair::new();
+        let (_, otc_vault) = get_otc_deal_accounts(&partner.pubkey());
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        let recipient = create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+            .await
+            .unwrap();
+        configure_liquidity_drip_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            recipient,
+            1000000000000000000,
+        )
+        .await
+        .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        rebalance_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            WalletKind::Partnership,
+            WalletKind::Liquidity,
+            200000000000000000,
+        )
+        .await
+        .unwrap();
+
+        let vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let vesting_state =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                .unwrap();
+        assert_eq!(
+            vesting_state
+                .wallet(WalletKind::Partnership)
+                .initial_balance,
+            1800000000000000000
+        );
+        assert_eq!(
+            vesting_state.wallet(WalletKind::Liquidity).initial_balance,
+            1200000000000000000
+        );
+        assert_eq!(
+            get_token_balance(&mut banks_client, &partnership_account).await,
+            1800000000000000000
+        );
+        assert_eq!(
+            get_token_balance(&mut banks_client, &liquidity_account).await,
+            1200000000000000000
+        );
+
+        // 50% of the new initial balance of the liquidity wallet is unlocked immediately
+        execute_liquidity_drip_instruction(&mut banks_client, &payer, recent_blockhash, recipient)
+            .await
+            .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &recipient).await,
+            600000000000000000
+        );
+
+        // 50% of the new initial balance of the partnership wallet is unlocked after 1 month
+        //  Tuesday, 11 April 2023 01:01:01
+        let time_in_timestamp = 1681174861;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        create_otc_deal_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            partner.pubkey(),
+            900000000000000000,
+            time_in_timestamp,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &otc_vault).await,
+            900000000000000000
+        );
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn test_withdraw_more_than_unlocked_after_rebalance_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        rebalance_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            WalletKind::Partnership,
+            WalletKind::Liquidity,
+            200000000000000000,
+        )
+        .await
+        .unwrap();
+
+        //  Tuesday, 11 April 2023 01:01:01
+        let time_in_timestamp = 1681174861;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        // only 50% of 1.8 * 10^18 is unlocked
+        withdraw_tokens_from_partnership_wallet_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            deposit_wallet,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn test_rebalance_below_already_withdrawn_amount_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+        withdraw_tokens_from_liquidity_wallet_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            deposit_wallet,
+        )
+        .await
+        .unwrap();
+
+        rebalance_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            WalletKind::Liquidity,
+            WalletKind::Community,
+            1000000000000000000,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn test_rebalance_after_configuration_lock_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        lock_configuration_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        rebalance_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            WalletKind::Partnership,
+            WalletKind::Liquidity,
+            200000000000000000,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_staking_rewards_distributed_between_two_stakers() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (_, _, _, _, mint, _, _, _, burning_account, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+        let (_, _, reward_vault) = get_staking_pda_accounts();
+
+        let first_staker = add_funded_keypair(&mut program_test);
+        let first_staker_token_account = add_token_account(
+            &mut program_test,
+            mint,
+            first_staker.pubkey(),
+            300,
+            AccountState::Initialized,
+        );
+        let second_staker = add_funded_keypair(&mut program_test);
+        let second_staker_token_account = add_token_account(
+            &mut program_test,
+            mint,
+            second_staker.pubkey(),
+            100,
+            AccountState::Initialized,
+        );
+
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        initialize_staking_instruction(&mut banks_client, &payer, recent_blockhash, 1, 0)
+            .await
+            .unwrap();
+
+        stake_instruction(
+            &mut banks_client,
+            &first_staker,
+            recent_blockhash,
+            first_staker_token_account,
+            300,
+        )
+        .await
+        .unwrap();
+        stake_instruction(
+            &mut banks_client,
+            &second_staker,
+            recent_blockhash,
+            second_staker_token_account,
+            100,
+        )
+        .await
+        .unwrap();
+
+        burn_instruction(&mut banks_client, &payer, recent_blockhash, true)
+            .await
+            .unwrap();
+
+        // 5% of the burning account balance leaves it, 1% of it goes to the stakers
+        assert_eq!(
+            get_token_balance(&mut banks_client, &burning_account).await,
+            1800000000000000000 - 1800000000000000000 / 20
+        );
+        assert_eq!(
+            get_token_balance(&mut banks_client, &reward_vault).await,
+            1800000000000000000 / 100
+        );
+
+        claim_rewards_instruction(
+            &mut banks_client,
+            &first_staker,
+            recent_blockhash,
+            first_staker_token_account,
+        )
+        .await
+        .unwrap();
+        claim_rewards_instruction(
+            &mut banks_client,
+            &second_staker,
+            recent_blockhash,
+            second_staker_token_account,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            get_token_balance(&mut banks_client, &first_staker_token_account).await,
+            13500000000000000
+        );
+        assert_eq!(
+            get_token_balance(&mut banks_client, &second_staker_token_account).await,
+            4500000000000000
+        );
+        assert_eq!(get_token_balance(&mut banks_client, &reward_vault).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_unstaked_after_unbonding_period() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        let staker = add_funded_keypair(&mut program_test);
+        let staker_token_account = add_token_account(
+            &mut program_test,
+            mint,
+            staker.pubkey(),
+            100,
+            AccountState::Initialized,
+        );
+
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        initialize_staking_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            1,
+            60 * 60 * 24 * 7,
+        )
+        .await
+        .unwrap();
+
+        stake_instruction(
+            &mut banks_client,
+            &staker,
+            recent_blockhash,
+            staker_token_account,
+            100,
+        )
+        .await
+        .unwrap();
+        unstake_instruction(&mut banks_client, &staker, recent_blockhash, 100)
+            .await
+            .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &staker_token_account).await,
+            0
+        );
+
+        //  Sunday, 12 March 2023 01:01:01
+        let time_in_timestamp = 1677978061 + 60 * 60 * 24 * 7;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        withdraw_unstaked_instruction(
+            &mut banks_client,
+            &staker,
+            recent_blockhash,
+            staker_token_account,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &staker_token_account).await,
+            100
+        );
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn test_withdraw_unstaked_before_unbonding_period_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        let staker = add_funded_keypair(&mut program_test);
+        let staker_token_account = add_token_account(
+            &mut program_test,
+            mint,
+            staker.pubkey(),
+            100,
+            AccountState::Initialized,
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        initialize_staking_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            1,
+            60 * 60 * 24 * 7,
+        )
+        .await
+        .unwrap();
+
+        stake_instruction(
+            &mut banks_client,
+            &staker,
+            recent_blockhash,
+            staker_token_account,
+            100,
+        )
+        .await
+        .unwrap();
+        unstake_instruction(&mut banks_client, &staker, recent_blockhash, 100)
+            .await
+            .unwrap();
+
+        withdraw_unstaked_instruction(
+            &mut banks_client,
+            &staker,
+            recent_blockhash,
+            staker_token_account,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_donate_to_burn_from_two_wallets() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (contract_state, _, _, _, mint, _, _, _, burning_account, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let first_donor = add_funded_keypair(&mut program_test);
+        let first_donor_token_account = add_token_account(
+            &mut program_test,
+            mint,
+            first_donor.pubkey(),
+            300,
+            AccountState::Initialized,
+        );
+        let second_donor = add_funded_keypair(&mut program_test);
+        let second_donor_token_account = add_token_account(
+            &mut program_test,
+            mint,
+            second_donor.pubkey(),
+            100,
+            AccountState::Initialized,
+        );
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let donations = [
+            (&first_donor, first_donor_token_account, 100),
+            (&second_donor, second_donor_token_account, 100),
+            (&first_donor, first_donor_token_account, 200),
+        ];
+        for (donor, donor_token_account, amount) in donations {
+            let transaction =
+                donate_to_burn_transaction(donor, recent_blockhash, donor_token_account, amount);
+            let result = banks_client
+                .process_transaction_with_metadata(transaction)
+                .await
+                .unwrap();
+            result.result.unwrap();
+
+            let event: BurnDonation = get_event(&result.metadata.unwrap().log_messages).unwrap();
+            assert_eq!(event.donor, donor.pubkey());
+            assert_eq!(event.amount, amount);
+        }
+
+        assert_eq!(
+            get_token_balance(&mut banks_client, &burning_account).await,
+            400
+        );
+
+        let contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let contract_state =
+            ContractState::try_deserialize_unchecked(&mut contract_state_info.data.as_slice())
+                .unwrap();
+        assert_eq!(contract_state.total_donated, 400);
+
+        for (donor, expected_total_donated) in [(&first_donor, 300), (&second_donor, 100)] {
+            let donor_account_info = banks_client
+                .get_account(get_donor_account(&donor.pubkey()))
+                .await
+                .unwrap()
+                .unwrap();
+            let donor_account =
+                DonorAccount::try_deserialize_unchecked(&mut donor_account_info.data.as_slice())
+                    .unwrap();
+            assert_eq!(donor_account.donor, donor.pubkey());
+            assert_eq!(donor_account.total_donated, expected_total_donated);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fund_burning_account_from_community_withdrawal() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let (contract_state, _, _, _, mint, _, _, _, burning_account, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let user_token_account =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+        banks_client
+            .process_transaction(withdraw_tokens_from_community_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                user_token_account,
+                get_withdrawal_receipt_account(WalletKind::Community, 0),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &user_token_account).await,
+            1
+        );
+
+        let burning_account_balance = get_token_balance(&mut banks_client, &burning_account).await;
+
+        let result = banks_client
+            .process_transaction_with_metadata(fund_burning_account_transaction(
+                &payer,
+                recent_blockhash,
+                user_token_account,
+                1,
+            ))
+            .await
+            .unwrap();
+        result.result.unwrap();
+
+        let event: BurningAccountFunded =
+            get_event(&result.metadata.unwrap().log_messages).unwrap();
+        assert_eq!(event.donor, payer.pubkey());
+        assert_eq!(event.amount, 1);
+
+        assert_eq!(
+            get_token_balance(&mut banks_client, &user_token_account).await,
+            0
+        );
+        assert_eq!(
+            get_token_balance(&mut banks_client, &burning_account).await,
+            burning_account_balance + 1
+        );
+
+        let contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let contract_state =
+            ContractState::try_deserialize(&mut contract_state_info.data.as_slice()).unwrap();
+        assert_eq!(contract_state.total_donated_for_burning, 1);
+        assert_eq!(contract_state.total_donated, 0);
+    }
+
+    #[tokio::test]
+    async fn test_fund_burning_account_with_foreign_mint_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let foreign_mint = create_foreign_mint(&mut banks_client, &payer, recent_blockhash).await;
+        let foreign_token_account =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, foreign_mint)
+                .await
+                .unwrap();
+
+        let error = banks_client
+            .process_transaction(fund_burning_account_transaction(
+                &payer,
+                recent_blockhash,
+                foreign_token_account,
+                0,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::WrongMint.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_donate_to_burn_with_foreign_mint_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let foreign_mint = create_foreign_mint(&mut banks_client, &payer, recent_blockhash).await;
+        let foreign_token_account =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, foreign_mint)
+                .await
+                .unwrap();
+
+        let error = banks_client
+            .process_transaction(donate_to_burn_transaction(
+                &payer,
+                recent_blockhash,
+                foreign_token_account,
+                0,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::WrongMint.into())
+            )
+        );
+    }
+
+    fn attest_reserves_transaction(payer: &Keypair, recent_blockhash: Hash) -> Transaction {
+        let program_id = id();
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            mint,
+            _,
+            program_account,
+            _,
+            burning_account,
+            _,
+            community_account,
+            _,
+            partnership_account,
+            _,
+            marketing_account,
+            _,
+            liquidity_account,
+            _,
+        ) = get_pda_accounts();
+        let (attestation, _) = Pubkey::find_program_address(&[b"attestation"], &program_id);
+
+        let data = instruction::AttestReserves {}.data();
+
+        let accs = AttestReservesContext {
+            contract_state,
+            vesting_state,
+            attestation,
+            mint,
+            program_account,
+            burning_account,
+            community_account,
+            partnership_account,
+            marketing_account,
+            liquidity_account,
+            signer: payer.pubkey(),
+            system_program: system_program::ID,
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[payer], recent_blockhash);
+        transaction
+    }
+
+    #[tokio::test]
+    async fn test_attest_reserves() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let (
+            _,
+            _,
+            _,
+            _,
+            mint,
+            _,
+            program_account,
+            _,
+            burning_account,
+            _,
+            community_account,
+            _,
+            partnership_account,
+            _,
+            marketing_account,
+            _,
+            liquidity_account,
+            _,
+        ) = get_pda_accounts();
+        let (attestation, _) = Pubkey::find_program_address(&[b"attestation"], &program_id);
+
+        banks_client
+            .process_transaction(attest_reserves_transaction(&payer, recent_blockhash))
+            .await
+            .unwrap();
+
+        let attestation_info = banks_client
+            .get_account(attestation)
+            .await
+            .unwrap()
+            .unwrap();
+        let attestation =
+            Attestation::try_deserialize_unchecked(&mut attestation_info.data.as_slice()).unwrap();
+
+        let mint_info = banks_client.get_account(mint).await.unwrap().unwrap();
+        let supply = spl_token::state::Mint::unpack_from_slice(mint_info.data.as_slice())
+            .unwrap()
+            .supply;
+
+        let mut balances = vec![];
+        for account in [
+            program_account,
+            burning_account,
+            community_account,
+            partnership_account,
+            marketing_account,
+            liquidity_account,
+        ] {
+            balances.push(
+                get_token_balance(&mut banks_client, &account)
+                    .await
+                    .to_le_bytes(),
+            );
+        }
+
+        // the digest is recomputed from the raw little-endian bytes instead of the program's own hashing
+        let slot = attestation.last_attestation_slot.to_le_bytes();
+        let supply = supply.to_le_bytes();
+        let mut hashed_data: Vec<&[u8]> = vec![&slot, &supply];
+        hashed_data.extend(balances.iter().map(|balance| balance.as_slice()));
+        assert_eq!(
+            attestation.last_attestation_hash,
+            hashv(&hashed_data).to_bytes()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_attest_reserves_once_per_day() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        banks_client
+            .process_transaction(attest_reserves_transaction(&payer, recent_blockhash))
+            .await
+            .unwrap();
+
+        // a second before a day has passed since the attestation
+        set_time(
+            &mut program_test_context,
+            time_in_timestamp + ATTESTATION_INTERVAL - 1,
+        )
+        .await;
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+        let error = banks_client
+            .process_transaction(attest_reserves_transaction(&payer, recent_blockhash))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::AttestationTooFrequent.into())
+            )
+        );
+
+        set_time(
+            &mut program_test_context,
+            time_in_timestamp + ATTESTATION_INTERVAL,
+        )
+        .await;
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+        banks_client
+            .process_transaction(attest_reserves_transaction(&payer, recent_blockhash))
+            .await
+            .unwrap();
+    }
+
+    /// Processor of the program wrapping leancoin instructions in CPI.
+    /// All the accounts are passed to the wrapped instruction, the first one must be leancoin program account.
+    fn cpi_wrapper_process_instruction(
+        _program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        data: &[u8],
+    ) -> ProgramResult {
+        let account_metas = accounts[1..]
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: *account.key,
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect();
+
+        invoke(
+            &Instruction::new_with_bytes(*accounts[0].key, data, account_metas),
+            accounts,
+        )
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn test_burn_via_cpi_fails() {
+        let program_id = id();
+        let cpi_wrapper_program_id = Pubkey::new_unique();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.add_program(
+            "cpi_wrapper",
+            cpi_wrapper_program_id,
+            processor!(cpi_wrapper_process_instruction),
+        );
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let (contract_state, _, _, _, mint, _, _, _, burning_account, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        let data = instruction::Burn {}.data();
+
+        let accs = BurnContext {
+            contract_state,
+            mint,
+            burning_account,
+            token_program: spl_token::id(),
+            instructions_sysvar: sysvar::instructions::ID,
+            staking_state: None,
+            reward_vault: None,
+            signer: payer.pubkey(),
+            caller_token_account: None,
+            burn_history: get_burn_history_account(),
+        };
+
+        let mut accounts = vec![AccountMeta::new_readonly(program_id, false)];
+        accounts.extend(accs.to_account_metas(Some(false)));
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                cpi_wrapper_program_id,
+                &data,
+                accounts,
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+    }
+
+    /// Returns the program test running the BPF build of the contract instead of the native processor,
+    /// since compute units are metered only for BPF programs.
+    /// The BPF build is looked up in `BPF_OUT_DIR`, which is set by `cargo test-bpf`.
+    #[cfg(feature = "test-bpf")]
+    fn bpf_program_test() -> ProgramTest {
+        let mut program_test = ProgramTest::new("leancoin", id(), None);
+        program_test.prefer_bpf(true);
+        program_test.set_compute_max_units(500000);
+        program_test
+    }
+
+    /// Processes the instructions of the transaction with the compute unit limit set to the budget increased by the tolerance
+    /// and asserts that they consumed at most that many compute units.
+    /// The transaction is signed again by the signers since the compute unit limit instruction is prepended to it.
+    #[cfg(feature = "test-bpf")]
+    async fn process_transaction_within_compute_budget(
+        banks_client: &mut BanksClient,
+        transaction: Transaction,
+        signers: &[&Keypair],
+        budget: u64,
+    ) -> u64 {
+        let compute_unit_limit = budget * (100 + COMPUTE_BUDGET_TOLERANCE_PERCENTAGE) / 100;
+        let message = &transaction.message;
+
+        let mut instructions = vec![ComputeBudgetInstruction::set_compute_unit_limit(
+            compute_unit_limit as u32,
+        )];
+        instructions.extend(message.instructions.iter().map(|instruction| {
+            Instruction {
+                program_id: message.account_keys[instruction.program_id_index as usize],
+                accounts: instruction
+                    .accounts
+                    .iter()
+                    .map(|&index| AccountMeta {
+                        pubkey: message.account_keys[index as usize],
+                        is_signer: message.is_signer(index as usize),
+                        is_writable: message.is_writable(index as usize),
+                    })
+                    .collect(),
+                data: instruction.data.clone(),
+            }
+        }));
+
+        let mut limited_transaction =
+            Transaction::new_with_payer(&instructions, Some(&message.account_keys[0]));
+        limited_transaction.sign(signers, message.recent_blockhash);
+
+        let result = banks_client
+            .process_transaction_with_metadata(limited_transaction)
+            .await
+            .unwrap();
+        result.result.unwrap();
+
+        let units_consumed = result.metadata.unwrap().compute_units_consumed;
+        assert!(
+            units_consumed <= compute_unit_limit,
+            "consumed {} compute units, the budget is {}",
+            units_consumed,
+            budget
+        );
+
+        units_consumed
+    }
+
+    fn get_event<T: Event>(log_messages: &[String]) -> Option<T> {
+        log_messages.iter().find_map(|log| {
+            let data = base64::decode(log.strip_prefix("Program data: ")?).ok()?;
+            if data.len() < 8 || data[..8] != T::discriminator() {
+                return None;
+            }
+
+            T::try_from_slice(&data[8..]).ok()
+        })
+    }
+
+    async fn get_token_balance(banks_client: &mut BanksClient, burning_account: &Pubkey) -> u64 {
+        let burning_account_mint_account = banks_client
+            .get_account(burning_account.clone())
+            .await
+            .unwrap()
+            .unwrap();
+        let burning_account_mint_state = spl_token::state::Account::unpack_from_slice(
+            burning_account_mint_account.data.as_slice(),
+        )
+        .unwrap();
+
+        burning_account_mint_state.amount
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn test_burn_change_clock_two_times_in_one_day() {
+        let program_id = id();
+        let program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut sub_clock = Clock::default();
+        sub_clock.unix_timestamp += 2_160_000;
+        let recent_blockhash = program_test_context
+            .banks_client
+            .get_latest_blockhash()
+            .await
+            .unwrap();
+
+        initialize_instruction(
+            &mut program_test_context.banks_client,
+            &program_test_context.payer,
+            recent_blockhash,
+        )
+        .await
+        .unwrap();
+        import_ethereum_token_state_instruction(
+            &mut program_test_context.banks_client,
+            &program_test_context.payer,
+            recent_blockhash,
+        )
+        .await
+        .unwrap();
+
+        burn_instruction(
+            &mut program_test_context.banks_client,
+            &program_test_context.payer,
+            recent_blockhash,
+            false,
+        )
+        .await
+        .unwrap();
+
+        //  Sunday, 5 March 2023 05:01:01
+        let time_in_timestamp = 1677992461;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let recent_blockhash = program_test_context
+            .banks_client
+            .get_latest_blockhash()
+            .await
+            .unwrap();
+        burn_instruction(
+            &mut program_test_context.banks_client,
+            &program_test_context.payer,
+            recent_blockhash,
+            false,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_tokens_from_community_wallet() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let token_program = spl_token::id();
+        let signer = payer.pubkey();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            mint,
+            _,
+            _,
+            _,
+            _,
+            _,
+            community_account,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+        ) = get_pda_accounts();
+
+        let data = instruction::WithdrawTokensFromCommunityWallet {
+            amount_to_withdraw: 25_000_000_000_000_000,
+            idempotency_key: None,
+        }
+        .data();
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        let deposit_wallet_balance_before_withdraw_tokens_from_community_wallet_context =
+            get_token_balance(&mut banks_client, &deposit_wallet).await;
+        assert_eq!(
+            deposit_wallet_balance_before_withdraw_tokens_from_community_wallet_context,
+            0
+        );
+
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Community).await;
+        let accs = WithdrawTokensFromCommunityWalletContext {
+            vesting_state,
+            deposit_wallet,
+            signer,
+            contract_state,
+            community_account,
+            token_program,
+            instructions_sysvar: sysvar::instructions::ID,
+            withdrawal_intent: None,
+            withdrawal_receipt,
+            system_program: system_program::ID,
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let deposit_wallet_balance_after_withdraw_tokens_from_community_wallet_context =
+            get_token_balance(&mut banks_client, &deposit_wallet).await;
+        assert_eq!(
+            deposit_wallet_balance_after_withdraw_tokens_from_community_wallet_context,
+            25_000_000_000_000_000
+        );
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_tokens_from_community_wallet_after_unlock_delay() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        // Wednesday, 1 March 2023 01:00:00
+        set_time(&mut program_test_context, 1677632400).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, vesting_state, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        banks_client
+            .process_transaction(
+                import_ethereum_token_state_with_vesting_parameters_transaction(
+                    &payer,
+                    recent_blockhash,
+                    3,
+                    0,
+                ),
+            )
+            .await
+            .unwrap();
+
+        let vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let vesting_state_data =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                .unwrap();
+        assert_eq!(vesting_state_data.community_unlock_delay_months, 3);
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        // Monday, 1 May 2023 01:00:00, 2 months after the import
+        set_time(&mut program_test_context, 1682902800).await;
+
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Community).await;
+        let error = banks_client
+            .process_transaction(withdraw_tokens_from_community_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::NotEnoughTokensCommunity.into())
+            )
+        );
+
+        // Thursday, 1 June 2023 01:00:00, 3 months after the import
+        set_time(&mut program_test_context, 1685581200).await;
+
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Community).await;
+        banks_client
+            .process_transaction(withdraw_tokens_from_community_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            get_token_balance(&mut banks_client, &deposit_wallet).await,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_tokens_from_liquidity_wallet_after_grace_period() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        // Wednesday, 1 March 2023 01:00:00
+        let import_timestamp = 1677632400;
+        let withdrawal_grace_period_seconds = 7 * 24 * 60 * 60;
+        set_time(&mut program_test_context, import_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        banks_client
+            .process_transaction(
+                import_ethereum_token_state_with_vesting_parameters_transaction(
+                    &payer,
+                    recent_blockhash,
+                    0,
+                    withdrawal_grace_period_seconds,
+                ),
+            )
+            .await
+            .unwrap();
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        set_time(
+            &mut program_test_context,
+            import_timestamp + withdrawal_grace_period_seconds - 1,
+        )
+        .await;
+
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        let error = banks_client
+            .process_transaction(withdraw_tokens_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                None,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::GracePeriodActive.into())
+            )
+        );
+
+        set_time(
+            &mut program_test_context,
+            import_timestamp + withdrawal_grace_period_seconds,
+        )
+        .await;
+
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        banks_client
+            .process_transaction(withdraw_tokens_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                None,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_import_with_too_long_grace_period_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let error = banks_client
+            .process_transaction(
+                import_ethereum_token_state_with_vesting_parameters_transaction(
+                    &payer,
+                    recent_blockhash,
+                    0,
+                    MAX_WITHDRAWAL_GRACE_PERIOD + 1,
+                ),
+            )
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                1,
+                InstructionError::Custom(LeancoinError::InvalidGracePeriod.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn test_withdraw_tokens_from_partnership_wallet() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        withdraw_tokens_from_partnership_wallet_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            deposit_wallet,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_finalize_vested_wallet() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            mint,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            partnership_account,
+            _,
+            _,
+            _,
+            _,
+            _,
+        ) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        //  Thursday, 11 May 2023 01:01:01
+        let time_in_timestamp = 1683766861;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+        withdraw_tokens_from_partnership_wallet_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            deposit_wallet,
+        )
+        .await
+        .unwrap();
+        let recent_blockhash = program_test_context
+            .get_new_latest_blockhash()
+            .await
+            .unwrap();
+        withdraw_tokens_from_partnership_wallet_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            deposit_wallet,
+        )
+        .await
+        .unwrap();
+
+        let destination = Pubkey::new_unique();
+        finalize_vested_wallet_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            WalletKind::Partnership,
+            destination,
+        )
+        .await
+        .unwrap();
+
+        assert!(banks_client
+            .get_account(partnership_account)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(banks_client.get_balance(destination).await.unwrap() > 0);
+
+        let vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let vesting_state_after_finalization =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                .unwrap();
+        assert_eq!(
+            vesting_state_after_finalization.finalized_wallets,
+            WalletKind::Partnership.mask()
+        );
+
+        // withdrawals from the finalized wallet fail with a dedicated error
+        let data = instruction::WithdrawTokensFromPartnershipWallet {
+            amount_to_withdraw: 1,
+            idempotency_key: None,
+        }
+        .data();
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Partnership).await;
+        let accs = WithdrawTokensFromPartnershipWalletContext {
+            contract_state,
+            vesting_state,
+            deposit_wallet,
+            partnership_account,
+            token_program: spl_token::id(),
+            signer: payer.pubkey(),
+            instructions_sysvar: sysvar::instructions::ID,
+            withdrawal_intent: None,
+            withdrawal_receipt,
+            system_program: system_program::ID,
+        };
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer], recent_blockhash);
+
+        let error = banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::WalletFinalized.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_add_to_vesting() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        set_time(&mut program_test_context, 1677978061).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (
+            _,
+            _,
+            vesting_state,
+            _,
+            mint,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            marketing_account,
+            _,
+            liquidity_account,
+            _,
+        ) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        // the tokens added to the marketing wallet are withdrawn from the liquidity wallet first
+        let top_up = 100000000000000000;
+        let source = create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+            .await
+            .unwrap();
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        banks_client
+            .process_transaction(withdraw_vested_transaction(
+                &payer,
+                recent_blockhash,
+                WalletKind::Liquidity,
+                liquidity_account,
+                source,
+                top_up,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap();
+
+        //  Sunday, 5 May 2024 01:01:01, 14 months after the vesting start
+        set_time(&mut program_test_context, 1714870861).await;
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+
+        let vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let state =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                .unwrap();
+        let initial_balance = state.wallet(WalletKind::Marketing).initial_balance;
+        let marketing_balance = get_token_balance(&mut banks_client, &marketing_account).await;
+
+        let result = banks_client
+            .process_transaction_with_metadata(add_to_vesting_transaction(
+                &payer,
+                recent_blockhash,
+                WalletKind::Marketing,
+                source,
+                top_up,
+            ))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let event: VestingToppedUp = get_event(&result.metadata.unwrap().log_messages).unwrap();
+        assert_eq!(event.wallet_kind, WalletKind::Marketing);
+        assert_eq!(event.amount, top_up);
+        assert_eq!(event.initial_balance, initial_balance + top_up);
+
+        assert_eq!(get_token_balance(&mut banks_client, &source).await, 0);
+        assert_eq!(
+            get_token_balance(&mut banks_client, &marketing_account).await,
+            marketing_balance + top_up
+        );
+
+        let vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let state =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                .unwrap();
+        assert_eq!(
+            state.wallet(WalletKind::Marketing).initial_balance,
+            initial_balance + top_up
+        );
+
+        // the schedule is applied to the new total
+        let vesting_status =
+            get_vesting_status_instruction(&mut banks_client, &payer, recent_blockhash)
+                .await
+                .unwrap();
+        assert_eq!(vesting_status.months_since_vesting_start, 14);
+        assert_eq!(
+            vesting_status.wallets[WalletKind::Marketing.index()].unlocked,
+            calculate_unlocked_amount(
+                &vesting_schedule(&state, WalletKind::Marketing),
+                initial_balance + top_up,
+                14,
+            )
+        );
+        assert!(
+            vesting_status.wallets[WalletKind::Marketing.index()].unlocked
+                > calculate_unlocked_amount(
+                    &vesting_schedule(&state, WalletKind::Marketing),
+                    initial_balance,
+                    14,
+                )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_revoke_unvested_before_cliff() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        set_time(&mut program_test_context, 1677978061).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, marketing_account, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let destination = create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+            .await
+            .unwrap();
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+        let marketing_balance = get_token_balance(&mut banks_client, &marketing_account).await;
+
+        //  Tuesday, 5 September 2023 01:01:01, nothing is unlocked before the cliff of the marketing wallet
+        set_time(&mut program_test_context, 1693875661).await;
+
+        let result = banks_client
+            .process_transaction_with_metadata(revoke_unvested_transaction(
+                &payer,
+                recent_blockhash,
+                WalletKind::Marketing,
+                destination,
+            ))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let event: VestingRevoked = get_event(&result.metadata.unwrap().log_messages).unwrap();
+        assert_eq!(event.wallet_kind, WalletKind::Marketing);
+        assert_eq!(event.amount, marketing_balance);
+        assert_eq!(event.destination, destination);
+        assert_eq!(event.unlocked_total, 0);
+
+        assert_eq!(
+            get_token_balance(&mut banks_client, &destination).await,
+            marketing_balance
+        );
+        assert_eq!(
+            get_token_balance(&mut banks_client, &marketing_account).await,
+            0
+        );
+
+        //  Friday, 5 July 2024 01:01:01, the unlocked amount stays frozen after the cliff
+        set_time(&mut program_test_context, 1720141261).await;
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Marketing).await;
+        let error = banks_client
+            .process_transaction(withdraw_vested_transaction(
+                &payer,
+                recent_blockhash,
+                WalletKind::Marketing,
+                marketing_account,
+                deposit_wallet,
+                1,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::NotEnoughTokensMarketing.into())
+            )
+        );
+
+        let error = banks_client
+            .process_transaction(revoke_unvested_transaction(
+                &payer,
+                recent_blockhash,
+                WalletKind::Marketing,
+                destination,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::WalletAlreadyRevoked.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_revoke_unvested_partially_unlocked() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        set_time(&mut program_test_context, 1677978061).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, vesting_state, _, mint, _, _, _, _, _, _, _, _, _, marketing_account, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let destination = create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+            .await
+            .unwrap();
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+        let marketing_balance = get_token_balance(&mut banks_client, &marketing_account).await;
+
+        //  Sunday, 5 May 2024 01:01:01, 14 months after the vesting start
+        set_time(&mut program_test_context, 1714870861).await;
+
+        let vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let state =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                .unwrap();
+        let unlocked_amount = calculate_unlocked_amount(
+            &vesting_schedule(&state, WalletKind::Marketing),
+            state.wallet(WalletKind::Marketing).initial_balance,
+            14,
+        );
+        assert!(unlocked_amount > 0 && unlocked_amount < marketing_balance);
+
+        let result = banks_client
+            .process_transaction_with_metadata(revoke_unvested_transaction(
+                &payer,
+                recent_blockhash,
+                WalletKind::Marketing,
+                destination,
+            ))
+            .await
+            .unwrap();
+        result.result.unwrap();
+        let event: VestingRevoked = get_event(&result.metadata.unwrap().log_messages).unwrap();
+        assert_eq!(event.amount, marketing_balance - unlocked_amount);
+        assert_eq!(event.unlocked_total, unlocked_amount);
+
+        assert_eq!(
+            get_token_balance(&mut banks_client, &destination).await,
+            marketing_balance - unlocked_amount
+        );
+        assert_eq!(
+            get_token_balance(&mut banks_client, &marketing_account).await,
+            unlocked_amount
+        );
+
+        let vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let state =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                .unwrap();
+        assert!(state.revoked(WalletKind::Marketing));
+        assert_eq!(
+            state.revoked_unlocked[WalletKind::Marketing.index()],
+            unlocked_amount
+        );
+
+        //  Friday, 5 July 2024 01:01:01, the frozen unlocked amount can still be withdrawn, but nothing more
+        set_time(&mut program_test_context, 1720141261).await;
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Marketing).await;
+        banks_client
+            .process_transaction(withdraw_vested_transaction(
+                &payer,
+                recent_blockhash,
+                WalletKind::Marketing,
+                marketing_account,
+                deposit_wallet,
+                unlocked_amount,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &deposit_wallet).await,
+            unlocked_amount
+        );
+
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Marketing).await;
+        let error = banks_client
+            .process_transaction(withdraw_vested_transaction(
+                &payer,
+                recent_blockhash,
+                WalletKind::Marketing,
+                marketing_account,
+                deposit_wallet,
+                1,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::NotEnoughTokensMarketing.into())
+            )
+        );
+
+        // the revoked and emptied wallet can be finalized before its schedule is complete
+        finalize_vested_wallet_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            WalletKind::Marketing,
+            payer.pubkey(),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn test_finalize_not_fully_vested_wallet_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        //  Tuesday, 11 April 2023 01:01:01
+        let time_in_timestamp = 1681174861;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+        withdraw_tokens_from_partnership_wallet_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            deposit_wallet,
+        )
+        .await
+        .unwrap();
+
+        // only 50% of the partnership wallet is unlocked after 1 month
+        finalize_vested_wallet_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            WalletKind::Partnership,
+            payer.pubkey(),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn test_finalize_vested_wallet_with_balance_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        //  Thursday, 11 May 2023 01:01:01
+        let time_in_timestamp = 1683766861;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        finalize_vested_wallet_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            WalletKind::Partnership,
+            payer.pubkey(),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_approve_and_revoke_wallet_delegate() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, liquidity_account, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        // 50% of the liquidity wallet is unlocked immediately
+        let delegate = Keypair::new();
+        banks_client
+            .process_transaction(approve_wallet_delegate_transaction(
+                &payer,
+                recent_blockhash,
+                WalletKind::Liquidity,
+                delegate.pubkey(),
+                200_000_000_000_000_000,
+            ))
+            .await
+            .unwrap();
+
+        let wallet_delegation =
+            get_wallet_delegation(&mut banks_client, WalletKind::Liquidity).await;
+        assert_eq!(wallet_delegation.delegate, delegate.pubkey());
+        assert_eq!(wallet_delegation.allowance, 200_000_000_000_000_000);
+        assert_eq!(wallet_delegation.transferred, 0);
+        assert_eq!(
+            get_already_withdrawn_wallet_amount(&mut banks_client, WalletKind::Liquidity).await,
+            200_000_000_000_000_000
+        );
+
+        let liquidity_account_info = banks_client
+            .get_account(liquidity_account)
+            .await
+            .unwrap()
+            .unwrap();
+        let liquidity_token_account =
+            Account::unpack_from_slice(liquidity_account_info.data.as_slice()).unwrap();
+        assert_eq!(
+            liquidity_token_account.delegate,
+            COption::Some(delegate.pubkey())
+        );
+        assert_eq!(
+            liquidity_token_account.delegated_amount,
+            200_000_000_000_000_000
+        );
+
+        // the delegate transfers the tokens directly with the token program
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+        let mut transaction = Transaction::new_with_payer(
+            &[spl_token::instruction::transfer(
+                &spl_token::id(),
+                &liquidity_account,
+                &deposit_wallet,
+                &delegate.pubkey(),
+                &[],
+                50_000_000_000_000_000,
+            )
+            .unwrap()],
+            Some(&payer.pubkey()),
+        );
+        transaction.sign(&[&payer, &delegate], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &deposit_wallet).await,
+            50_000_000_000_000_000
+        );
+
+        banks_client
+            .process_transaction(wallet_delegation_transaction(
+                &payer,
+                recent_blockhash,
+                WalletKind::Liquidity,
+                false,
+            ))
+            .await
+            .unwrap();
+
+        let wallet_delegation =
+            get_wallet_delegation(&mut banks_client, WalletKind::Liquidity).await;
+        assert_eq!(wallet_delegation.allowance, 150_000_000_000_000_000);
+        assert_eq!(wallet_delegation.transferred, 50_000_000_000_000_000);
+        assert_eq!(
+            get_already_withdrawn_wallet_amount(&mut banks_client, WalletKind::Liquidity).await,
+            200_000_000_000_000_000
+        );
+
+        banks_client
+            .process_transaction(wallet_delegation_transaction(
+                &payer,
+                recent_blockhash,
+                WalletKind::Liquidity,
+                true,
+            ))
+            .await
+            .unwrap();
+
+        // the unused allowance is released, only the transferred tokens remain withdrawn
+        let wallet_delegation =
+            get_wallet_delegation(&mut banks_client, WalletKind::Liquidity).await;
+        assert_eq!(wallet_delegation.delegate, Pubkey::default());
+        assert_eq!(wallet_delegation.allowance, 0);
+        assert_eq!(wallet_delegation.transferred, 50_000_000_000_000_000);
+        assert_eq!(
+            get_already_withdrawn_wallet_amount(&mut banks_client, WalletKind::Liquidity).await,
+            50_000_000_000_000_000
+        );
+
+        let liquidity_account_info = banks_client
+            .get_account(liquidity_account)
+            .await
+            .unwrap()
+            .unwrap();
+        let liquidity_token_account =
+            Account::unpack_from_slice(liquidity_account_info.data.as_slice()).unwrap();
+        assert_eq!(liquidity_token_account.delegate, COption::None);
+        assert_eq!(liquidity_token_account.delegated_amount, 0);
+    }
+
+    #[tokio::test]
+    async fn test_approve_wallet_delegate_above_unlocked_amount_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let error = banks_client
+            .process_transaction(approve_wallet_delegate_transaction(
+                &payer,
+                recent_blockhash,
+                WalletKind::Liquidity,
+                Pubkey::new_unique(),
+                500_000_000_000_000_001,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::NotEnoughTokensLiquidity.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_delegated_tokens_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        // the whole unlocked amount is delegated, so nothing is left to withdraw
+        banks_client
+            .process_transaction(approve_wallet_delegate_transaction(
+                &payer,
+                recent_blockhash,
+                WalletKind::Liquidity,
+                Pubkey::new_unique(),
+                500_000_000_000_000_000,
+            ))
+            .await
+            .unwrap();
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        let error = banks_client
+            .process_transaction(withdraw_tokens_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                None,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::NotEnoughTokensLiquidity.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migrate_legacy_vesting_state() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let mut program_test_context = program_test.start_with_context().await;
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, vesting_state, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let zero_copy_vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let zero_copy_vesting_state = VestingState::try_deserialize_unchecked(
+            &mut zero_copy_vesting_state_info.data.as_slice(),
+        )
+        .unwrap();
+
+        // replace the account by the same state stored in the layout used before the zero-copy one
+        let legacy_vesting_state = LegacyVestingState {
+            vesting_state_nonce: zero_copy_vesting_state.vesting_state_nonce,
+            community_wallet_nonce: zero_copy_vesting_state.wallet(WalletKind::Community).nonce,
+            initial_community_wallet_balance: zero_copy_vesting_state
+                .wallet(WalletKind::Community)
+                .initial_balance,
+            already_withdrawn_community_wallet_amount: 0,
+            community_beneficiary_owner: Pubkey::default(),
+            partnership_wallet_nonce: zero_copy_vesting_state
+                .wallet(WalletKind::Partnership)
+                .nonce,
+            initial_partnership_wallet_balance: zero_copy_vesting_state
+                .wallet(WalletKind::Partnership)
+                .initial_balance,
+            already_withdrawn_partnership_wallet_amount: 0,
+            partnership_beneficiary_owner: Pubkey::default(),
+            marketing_wallet_nonce: zero_copy_vesting_state.wallet(WalletKind::Marketing).nonce,
+            initial_marketing_wallet_balance: zero_copy_vesting_state
+                .wallet(WalletKind::Marketing)
+                .initial_balance,
+            already_withdrawn_marketing_wallet_amount: 0,
+            marketing_beneficiary_owner: Pubkey::default(),
+            liquidity_wallet_nonce: zero_copy_vesting_state.wallet(WalletKind::Liquidity).nonce,
+            initial_liquidity_wallet_balance: zero_copy_vesting_state
+                .wallet(WalletKind::Liquidity)
+                .initial_balance,
+            already_withdrawn_liquidity_wallet_amount: 0,
+            liquidity_beneficiary_owner: Pubkey::default(),
+            liquidity_drip_recipient: Pubkey::default(),
+            liquidity_drip_amount_per_month: 0,
+            last_liquidity_drip_month: 0,
+            last_liquidity_drip_year: 0,
+            finalized_wallets: 0,
+            start_timestamp: zero_copy_vesting_state.start_timestamp,
+        };
+        let mut legacy_data = VestingState::discriminator().to_vec();
+        legacy_vesting_state.serialize(&mut legacy_data).unwrap();
+        assert_eq!(legacy_data.len(), 8 + LegacyVestingState::INIT_SPACE);
+
+        program_test_context.set_account(
+            &vesting_state,
+            &solana_sdk::account::Account {
+                lamports: Rent::default().minimum_balance(legacy_data.len()),
+                data: legacy_data,
+                owner: program_id,
+                executable: false,
+                rent_epoch: 0,
+            }
+            .into(),
+        );
+
+        banks_client
+            .process_transaction(migrate_vesting_state_transaction(&payer, recent_blockhash))
+            .await
+            .unwrap();
+
+        let migrated_vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            migrated_vesting_state_info.data,
+            zero_copy_vesting_state_info.data
+        );
+        assert!(
+            migrated_vesting_state_info.lamports
+                >= Rent::default().minimum_balance(migrated_vesting_state_info.data.len())
+        );
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+        withdraw_tokens_from_liquidity_wallet_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            deposit_wallet,
+        )
+        .await
+        .unwrap();
+
+        let vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let vesting_state =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                .unwrap();
+        assert_eq!(vesting_state.wallet(WalletKind::Liquidity).withdrawn, 1);
+    }
+
+    #[tokio::test]
+    async fn test_migrate_vesting_state_v1() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let mut program_test_context = program_test.start_with_context().await;
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, vesting_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let current_vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let current_vesting_state = VestingState::try_deserialize_unchecked(
+            &mut current_vesting_state_info.data.as_slice(),
+        )
+        .unwrap();
+        let beneficiary_owner = Pubkey::new_unique();
+
+        // replace the account by the same state stored in the zero-copy layout with separate fields for every wallet
+        let wallet = |wallet_kind: WalletKind| *current_vesting_state.wallet(wallet_kind);
+        let vesting_state_v1 = VestingStateV1 {
+            initial_community_wallet_balance: wallet(WalletKind::Community).initial_balance,
+            initial_partnership_wallet_balance: wallet(WalletKind::Partnership).initial_balance,
+            initial_marketing_wallet_balance: wallet(WalletKind::Marketing).initial_balance,
+            initial_liquidity_wallet_balance: wallet(WalletKind::Liquidity).initial_balance,
+            already_withdrawn_community_wallet_amount: 0,
+            already_withdrawn_partnership_wallet_amount: 0,
+            already_withdrawn_marketing_wallet_amount: 0,
+            already_withdrawn_liquidity_wallet_amount: 7,
+            liquidity_drip_amount_per_month: 0,
+            last_liquidity_drip_year: 0,
+            start_timestamp: current_vesting_state.start_timestamp,
+            community_beneficiary_owner: Pubkey::default(),
+            partnership_beneficiary_owner: beneficiary_owner,
+            marketing_beneficiary_owner: Pubkey::default(),
+            liquidity_beneficiary_owner: Pubkey::default(),
+            liquidity_drip_recipient: Pubkey::default(),
+            vesting_state_nonce: current_vesting_state.vesting_state_nonce,
+            community_wallet_nonce: wallet(WalletKind::Community).nonce,
+            partnership_wallet_nonce: wallet(WalletKind::Partnership).nonce,
+            marketing_wallet_nonce: wallet(WalletKind::Marketing).nonce,
+            liquidity_wallet_nonce: wallet(WalletKind::Liquidity).nonce,
+            last_liquidity_drip_month: 0,
+            finalized_wallets: 0,
+            padding: [0; 1],
+        };
+        let mut v1_data = VestingState::discriminator().to_vec();
+        v1_data.extend_from_slice(anchor_lang::__private::bytemuck::bytes_of(
+            &vesting_state_v1,
+        ));
+        assert_eq!(v1_data.len(), 8 + 256);
+
+        program_test_context.set_account(
+            &vesting_state,
+            &solana_sdk::account::Account {
+                lamports: Rent::default().minimum_balance(v1_data.len()),
+                data: v1_data,
+                owner: program_id,
+                executable: false,
+                rent_epoch: 0,
+            }
+            .into(),
+        );
+
+        banks_client
+            .process_transaction(migrate_vesting_state_transaction(&payer, recent_blockhash))
+            .await
+            .unwrap();
+
+        let migrated_vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            migrated_vesting_state_info.data.len(),
+            current_vesting_state_info.data.len()
+        );
+        let migrated_vesting_state = VestingState::try_deserialize_unchecked(
+            &mut migrated_vesting_state_info.data.as_slice(),
+        )
+        .unwrap();
+        for wallet_kind in WalletKind::ALL {
+            let migrated_wallet = migrated_vesting_state.wallet(wallet_kind);
+            assert_eq!(migrated_wallet.nonce, wallet(wallet_kind).nonce);
+            assert_eq!(
+                migrated_wallet.initial_balance,
+                wallet(wallet_kind).initial_balance
+            );
+        }
+        assert_eq!(
+            migrated_vesting_state
+                .wallet(WalletKind::Liquidity)
+                .withdrawn,
+            7
+        );
+        assert_eq!(
+            migrated_vesting_state
+                .wallet(WalletKind::Partnership)
+                .beneficiary_owner,
+            beneficiary_owner
+        );
+        assert_eq!(
+            migrated_vesting_state.start_timestamp,
+            current_vesting_state.start_timestamp
+        );
+
+        // the migrated account cannot be migrated again
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+        let error = banks_client
+            .process_transaction(migrate_vesting_state_transaction(&payer, recent_blockhash))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::VestingStateAlreadyMigrated.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migrate_zero_copy_vesting_state_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let error = banks_client
+            .process_transaction(migrate_vesting_state_transaction(&payer, recent_blockhash))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::VestingStateAlreadyMigrated.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migrate_legacy_contract_state() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let mut program_test_context = program_test.start_with_context().await;
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (contract_state, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let current_contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let current_contract_state =
+            ContractState::try_deserialize(&mut current_contract_state_info.data.as_slice())
+                .unwrap();
+
+        // replace the account by the same state stored in the layout used before the versioned one
+        let legacy_data = legacy_contract_state_data(&current_contract_state, payer.pubkey());
+        program_test_context.set_account(
+            &contract_state,
+            &solana_sdk::account::Account {
+                lamports: Rent::default().minimum_balance(legacy_data.len()),
+                data: legacy_data,
+                owner: program_id,
+                executable: false,
+                rent_epoch: 0,
+            }
+            .into(),
+        );
+
+        banks_client
+            .process_transaction(migrate_contract_state_transaction(&payer, recent_blockhash))
+            .await
+            .unwrap();
+
+        let migrated_contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            migrated_contract_state_info.data.len(),
+            current_contract_state_info.data.len()
+        );
+        assert!(
+            migrated_contract_state_info.lamports
+                >= Rent::default().minimum_balance(migrated_contract_state_info.data.len())
+        );
+        assert_eq!(
+            migrated_contract_state_info.data[ContractState::VERSION_OFFSET],
+            CONTRACT_STATE_VERSION
+        );
+        assert_eq!(
+            migrated_contract_state_info.data
+                [ContractState::AUTHORITY_OFFSET..ContractState::AUTHORITY_OFFSET + 32],
+            payer.pubkey().to_bytes()
+        );
+
+        let migrated_contract_state =
+            ContractState::try_deserialize(&mut migrated_contract_state_info.data.as_slice())
+                .unwrap();
+        assert_eq!(migrated_contract_state.authority, payer.pubkey());
+        assert_eq!(
+            migrated_contract_state.performed_imports,
+            current_contract_state.performed_imports
+        );
+        assert_eq!(
+            migrated_contract_state.imported_minted,
+            current_contract_state.imported_minted
+        );
+        assert_eq!(
+            migrated_contract_state.imported_burned,
+            current_contract_state.imported_burned
+        );
+        assert_eq!(
+            migrated_contract_state.max_supply,
+            current_contract_state.max_supply
+        );
+        assert_eq!(
+            migrated_contract_state.mint_nonce,
+            current_contract_state.mint_nonce
+        );
+        assert_eq!(
+            migrated_contract_state.metadata_program_id,
+            current_contract_state.metadata_program_id
+        );
+        assert_eq!(
+            migrated_contract_state.sequence,
+            current_contract_state.sequence + 1
+        );
+
+        // the fields appended by the versioned layout are set to their defaults
+        assert_eq!(migrated_contract_state.burn_remainder_numerator, 0);
+        assert_eq!(migrated_contract_state.withdrawal_grace_period_seconds, 0);
+        assert_eq!(
+            migrated_contract_state.keepers,
+            [Pubkey::default(); MAX_KEEPERS]
+        );
+        assert_eq!(
+            migrated_contract_state.late_burn_window_first_day,
+            DEFAULT_LATE_BURN_WINDOW_FIRST_DAY
+        );
+        assert_eq!(
+            migrated_contract_state.late_burn_window_last_day,
+            DEFAULT_LATE_BURN_WINDOW_LAST_DAY
+        );
+        assert!(!migrated_contract_state.paused);
+        assert!(!migrated_contract_state.renounced);
+        for role in Role::ALL {
+            assert_eq!(
+                migrated_contract_state.role_authority(role),
+                Pubkey::default()
+            );
+        }
+        assert!(!migrated_contract_state.mint_authority_revoked);
+        assert_eq!(migrated_contract_state.decimals, LEGACY_MINT_DECIMALS);
+        assert_eq!(migrated_contract_state.mint, mint);
+        assert_eq!(migrated_contract_state.burn_rate_bps, DEFAULT_BURN_RATE_BPS);
+        assert_eq!(
+            migrated_contract_state.burn_window_days,
+            DEFAULT_BURN_WINDOW_DAYS
+        );
+        assert_eq!(migrated_contract_state.burn_caller_reward_bps, 0);
+        assert_eq!(migrated_contract_state.total_donated_for_burning, 0);
+        assert_eq!(migrated_contract_state.total_burned, 0);
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+        withdraw_tokens_from_liquidity_wallet_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            deposit_wallet,
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_migrate_vesting_state_v2() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let mut program_test_context = program_test.start_with_context().await;
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, vesting_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let current_vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // the layout with per-wallet states is the current one without the swap wallet balance and the vesting schedules,
+        // the version 3 layout is the current one without the vesting schedules, which are set to the default ones,
+        // the version 4 layout is the current one without the beneficiaries,
+        // the version 5 layout is the current one without the withdrawal history,
+        // the version 6 layout is the current one without the receipt indexes,
+        // the version 7 layout is the current one without the revoked unlocked amounts
+        for space in [
+            VESTING_STATE_V2_SPACE,
+            VESTING_STATE_V3_SPACE,
+            VESTING_STATE_V4_SPACE,
+            VESTING_STATE_V5_SPACE,
+            VESTING_STATE_V6_SPACE,
+            VESTING_STATE_V7_SPACE,
+        ] {
+            let outdated_data = current_vesting_state_info.data[..8 + space].to_vec();
+            program_test_context.set_account(
+                &vesting_state,
+                &solana_sdk::account::Account {
+                    lamports: Rent::default().minimum_balance(outdated_data.len()),
+                    data: outdated_data,
+                    owner: program_id,
+                    executable: false,
+                    rent_epoch: 0,
+                }
+                .into(),
+            );
+
+            let recent_blockhash = banks_client
+                .get_new_latest_blockhash(&recent_blockhash)
+                .await
+                .unwrap();
+            banks_client
+                .process_transaction(migrate_vesting_state_transaction(&payer, recent_blockhash))
+                .await
+                .unwrap();
+
+            let migrated_vesting_state_info = banks_client
+                .get_account(vesting_state)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                migrated_vesting_state_info.data,
+                current_vesting_state_info.data
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migrate_vesting_state_without_withdrawal_history() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let mut program_test_context = program_test.start_with_context().await;
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, vesting_state, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        banks_client
+            .process_transaction(withdraw_tokens_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                None,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap();
+
+        let current_vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // the version 5 layout is the current one without the withdrawal history
+        let outdated_data = current_vesting_state_info.data[..8 + VESTING_STATE_V5_SPACE].to_vec();
+        program_test_context.set_account(
+            &vesting_state,
+            &solana_sdk::account::Account {
+                lamports: Rent::default().minimum_balance(outdated_data.len()),
+                data: outdated_data.clone(),
+                owner: program_id,
+                executable: false,
+                rent_epoch: 0,
+            }
+            .into(),
+        );
+
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+        banks_client
+            .process_transaction(migrate_vesting_state_transaction(&payer, recent_blockhash))
+            .await
+            .unwrap();
+
+        let migrated_vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            migrated_vesting_state_info.data.len(),
+            current_vesting_state_info.data.len()
+        );
+        assert_eq!(
+            migrated_vesting_state_info.data[..outdated_data.len()],
+            outdated_data[..]
+        );
+
+        // the withdrawal made before the migration is not in the history, which is zero-initialized
+        let migrated_vesting_state = VestingState::try_deserialize_unchecked(
+            &mut migrated_vesting_state_info.data.as_slice(),
+        )
+        .unwrap();
+        assert_eq!(
+            migrated_vesting_state
+                .wallet(WalletKind::Liquidity)
+                .withdrawn,
+            1
+        );
+        assert_eq!(
+            migrated_vesting_state.withdrawal_history,
+            [WithdrawalHistory::default(); VESTED_WALLETS_COUNT]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migrate_legacy_contract_state_by_not_owner_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let mut program_test_context = program_test.start_with_context().await;
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let contract_state_info = banks_client
+            .get_account(contract_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let current_contract_state =
+            ContractState::try_deserialize(&mut contract_state_info.data.as_slice()).unwrap();
+        let legacy_data = legacy_contract_state_data(&current_contract_state, Pubkey::new_unique());
+        program_test_context.set_account(
+            &contract_state,
+            &solana_sdk::account::Account {
+                lamports: Rent::default().minimum_balance(legacy_data.len()),
+                data: legacy_data,
+                owner: program_id,
+                executable: false,
+                rent_epoch: 0,
+            }
+            .into(),
+        );
+
+        let error = banks_client
+            .process_transaction(migrate_contract_state_transaction(&payer, recent_blockhash))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::Unauthorized.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migrate_current_contract_state_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let error = banks_client
+            .process_transaction(migrate_contract_state_transaction(&payer, recent_blockhash))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::ContractStateAlreadyMigrated.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_top_up_rent_of_underfunded_state_accounts() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let mut program_test_context = program_test.start_with_context().await;
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (contract_state, _, vesting_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        for (account_kind, state_account) in [
+            (StateAccountKind::ContractState, contract_state),
+            (StateAccountKind::VestingState, vesting_state),
+        ] {
+            let mut state_account_info = banks_client
+                .get_account(state_account)
+                .await
+                .unwrap()
+                .unwrap();
+            let minimum_balance = Rent::default().minimum_balance(state_account_info.data.len());
+
+            // simulate an underfunded reallocation
+            state_account_info.lamports = minimum_balance - 1000;
+            program_test_context.set_account(&state_account, &state_account_info.into());
+
+            banks_client
+                .process_transaction(top_up_rent_transaction(
+                    &payer,
+                    recent_blockhash,
+                    account_kind,
+                ))
+                .await
+                .unwrap();
+
+            let state_account_info = banks_client
+                .get_account(state_account)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(state_account_info.lamports, minimum_balance);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_top_up_rent_of_rent_exempt_account_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let error = banks_client
+            .process_transaction(top_up_rent_transaction(
+                &payer,
+                recent_blockhash,
+                StateAccountKind::ContractState,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::AlreadyRentExempt.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_with_reused_idempotency_key_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        banks_client
+            .process_transaction(withdraw_tokens_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                Some([1; 16]),
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap();
+
+        // the retry of the same withdrawal is a different transaction with the same idempotency key
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        let error = banks_client
+            .process_transaction(withdraw_tokens_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                Some([1; 16]),
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::DuplicateWithdrawal.into())
+            )
+        );
+
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        banks_client
+            .process_transaction(withdraw_tokens_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                Some([2; 16]),
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            get_token_balance(&mut banks_client, &deposit_wallet).await,
+            2
+        );
+
+        let withdrawal_intent_info = banks_client
+            .get_account(get_withdrawal_intent_account(&[1; 16]))
+            .await
+            .unwrap()
+            .unwrap();
+        let withdrawal_intent = WithdrawalIntent::try_deserialize_unchecked(
+            &mut withdrawal_intent_info.data.as_slice(),
+        )
+        .unwrap();
+        assert_eq!(withdrawal_intent.payer, payer.pubkey());
+        assert_eq!(withdrawal_intent.amount, 1);
+
+        // the intent of a withdraw-all records the transferred amount instead of the sentinel
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        banks_client
+            .process_transaction(withdraw_amount_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                WITHDRAW_ALL_UNLOCKED,
+                Some([3; 16]),
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap();
+        let withdrawn_amount = get_token_balance(&mut banks_client, &deposit_wallet).await - 2;
+        assert!(withdrawn_amount > 0);
+
+        let withdrawal_intent_info = banks_client
+            .get_account(get_withdrawal_intent_account(&[3; 16]))
+            .await
+            .unwrap()
+            .unwrap();
+        let withdrawal_intent = WithdrawalIntent::try_deserialize_unchecked(
+            &mut withdrawal_intent_info.data.as_slice(),
+        )
+        .unwrap();
+        assert_eq!(withdrawal_intent.amount, withdrawn_amount);
+    }
+
+    #[tokio::test]
+    async fn test_close_withdrawal_intent_after_retention_period() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        banks_client
+            .process_transaction(withdraw_tokens_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                Some([1; 16]),
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap();
+
+        let error = banks_client
+            .process_transaction(close_withdrawal_intent_transaction(
+                &payer,
+                recent_blockhash,
+                [1; 16],
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::WithdrawalIntentNotExpired.into())
+            )
+        );
+
+        //  Tuesday, 4 April 2023 01:01:01
+        let recent_blockhash = program_test_context
+            .get_new_latest_blockhash()
+            .await
+            .unwrap();
+        let time_in_timestamp = 1680570061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        banks_client
+            .process_transaction(close_withdrawal_intent_transaction(
+                &payer,
+                recent_blockhash,
+                [1; 16],
+            ))
+            .await
+            .unwrap();
+
+        assert!(banks_client
+            .get_account(get_withdrawal_intent_account(&[1; 16]))
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_tokens_from_partnership_wallet_after_one_month() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        //  Thursday, 11 May 2023 01:01:01
+        let time_in_timestamp = 1683766861;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        let deposit_wallet_balance_before_withdraw_tokens_from_partnership_wallet_context =
+            get_token_balance(&mut banks_client, &deposit_wallet).await;
+        assert_eq!(
+            deposit_wallet_balance_before_withdraw_tokens_from_partnership_wallet_context,
+            0
+        );
+        withdraw_tokens_from_partnership_wallet_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            deposit_wallet,
+        )
+        .await
+        .unwrap();
+
+        let deposit_wallet_balance_after_withdraw_tokens_from_partnership_wallet_context =
+            get_token_balance(&mut banks_client, &deposit_wallet).await;
+        assert_eq!(
+            deposit_wallet_balance_after_withdraw_tokens_from_partnership_wallet_context,
+            1000000000000000000
+        );
+    }
+
+    #[tokio::test]
+    async fn test_withdrawal_event_contains_vesting_schedule() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        set_time(&mut program_test_context, 1677978061).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, partnership_account, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        //  Thursday, 11 May 2023 01:01:01
+        set_time(&mut program_test_context, 1683766861).await;
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Partnership).await;
+        let transaction_result = banks_client
+            .process_transaction_with_metadata(withdraw_vested_transaction(
+                &payer,
+                recent_blockhash,
+                WalletKind::Partnership,
+                partnership_account,
+                deposit_wallet,
+                1000000000000000000,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap();
+        transaction_result.result.unwrap();
+
+        let event: VestedTokensWithdrawn =
+            get_event(&transaction_result.metadata.unwrap().log_messages).unwrap();
+        assert_eq!(event.wallet_kind, WalletKind::Partnership);
+        assert_eq!(event.amount, 1000000000000000000);
+        assert_eq!(event.destination, deposit_wallet);
+        // the whole balance of the partnership wallet is unlocked 2 months after the vesting start
+        assert_eq!(event.months_since_vesting_start, 2);
+        assert_eq!(event.unlocked_total, 2000000000000000000);
+        assert_eq!(event.already_withdrawn_total, 1000000000000000000);
+        assert_eq!(event.timestamp, 1683766861);
+    }
+
+    #[tokio::test]
+    async fn test_project_supply() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client;
+        let payer = program_test_context.payer;
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let mint_info = banks_client.get_account(mint).await.unwrap().unwrap();
+        let supply = spl_token::state::Mint::unpack_from_slice(mint_info.data.as_slice())
+            .unwrap()
+            .supply;
+
+        let projection =
+            project_supply_instruction(&mut banks_client, &payer, recent_blockhash, 12)
+                .await
+                .unwrap();
+        assert_eq!(
+            projection,
+            calculate_supply_projection(
+                supply,
+                1800000000000000000,
+                0,
+                DEFAULT_BURN_RATE_BPS,
+                0,
+                12
+            )
+            .unwrap()
+        );
+        assert_eq!(projection.supply, supply - projection.burned);
+        assert_eq!(projection.floor_month, None);
+
+        // the projection of a single month matches the supply after the burn
+        let projection = project_supply_instruction(&mut banks_client, &payer, recent_blockhash, 1)
+            .await
+            .unwrap();
+        burn_instruction(&mut banks_client, &payer, recent_blockhash, false)
+            .await
+            .unwrap();
+        let mint_info = banks_client.get_account(mint).await.unwrap().unwrap();
+        let supply_after_burn =
+            spl_token::state::Mint::unpack_from_slice(mint_info.data.as_slice())
+                .unwrap()
+                .supply;
+        assert_eq!(projection.supply, supply_after_burn);
+
+        let error = project_supply_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            MAX_PROJECTED_MONTHS + 1,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::ProjectionTooLong.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_months_since_start() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let months =
+            get_months_since_start_instruction(&mut banks_client, &payer, recent_blockhash)
+                .await
+                .unwrap();
+        assert_eq!(months, 0);
+
+        //  Friday, 31 March 2023 23:59:59
+        let time_in_timestamp = 1680307199;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let months =
+            get_months_since_start_instruction(&mut banks_client, &payer, recent_blockhash)
+                .await
+                .unwrap();
+        assert_eq!(months, 0);
+
+        //  Saturday, 1 April 2023 00:00:00
+        let time_in_timestamp = 1680307200;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let months =
+            get_months_since_start_instruction(&mut banks_client, &payer, recent_blockhash)
+                .await
+                .unwrap();
+        assert_eq!(months, 1);
+
+        //  Thursday, 11 May 2023 01:01:01
+        let time_in_timestamp = 1683766861;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let months =
+            get_months_since_start_instruction(&mut banks_client, &payer, recent_blockhash)
+                .await
+                .unwrap();
+        assert_eq!(months, 2);
+
+        //  Tuesday, 5 March 2024 01:01:01
+        let time_in_timestamp = 1709600461;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let months =
+            get_months_since_start_instruction(&mut banks_client, &payer, recent_blockhash)
+                .await
+                .unwrap();
+        assert_eq!(months, 12);
+    }
+
+    #[tokio::test]
+    async fn test_get_program_addresses() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let program_addresses =
+            get_program_addresses_instruction(&mut banks_client, &payer, recent_blockhash)
+                .await
+                .unwrap();
+
+        // the addresses created from the stored nonces must match the re-derived ones
+        let (
+            contract_state,
+            contract_state_nonce,
+            vesting_state,
+            vesting_state_nonce,
+            mint,
+            mint_nonce,
+            program_account,
+            program_account_nonce,
+            burning_account,
+            burning_account_nonce,
+            community_account,
+            community_wallet_nonce,
+            partnership_account,
+            partnership_wallet_nonce,
+            marketing_account,
+            marketing_wallet_nonce,
+            liquidity_account,
+            liquidity_wallet_nonce,
+        ) = get_pda_accounts();
+
+        assert_eq!(
+            program_addresses,
+            ProgramAddresses {
+                contract_state,
+                contract_state_nonce,
+                vesting_state,
+                vesting_state_nonce,
+                mint,
+                mint_nonce,
+                program_account,
+                program_account_nonce,
+                burning_account,
+                burning_account_nonce,
+                community_account,
+                community_wallet_nonce,
+                partnership_account,
+                partnership_wallet_nonce,
+                marketing_account,
+                marketing_wallet_nonce,
+                liquidity_account,
+                liquidity_wallet_nonce,
+                labels: LabeledAccountKind::ALL.map(|account_kind| encode_label(
+                    account_kind.default_label().as_bytes()
+                )
+                .unwrap()),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_label() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        set_label_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            LabeledAccountKind::Marketing,
+            "Leancoin: Marketing Treasury",
+        )
+        .await
+        .unwrap();
+
+        let program_addresses =
+            get_program_addresses_instruction(&mut banks_client, &payer, recent_blockhash)
+                .await
+                .unwrap();
+
+        let label = program_addresses.labels[LabeledAccountKind::Marketing.index()];
+        let label_length = label.iter().position(|byte| *byte == 0).unwrap();
+        assert_eq!(
+            std::str::from_utf8(&label[..label_length]).unwrap(),
+            "Leancoin: Marketing Treasury"
+        );
+        assert_eq!(
+            program_addresses.labels[LabeledAccountKind::Liquidity.index()],
+            encode_label(b"Leancoin: Liquidity Vesting").unwrap()
+        );
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn test_set_label_after_configuration_lock_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        lock_configuration_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        set_label_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            LabeledAccountKind::Marketing,
+            "Leancoin: Marketing Treasury",
+        )
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn test_get_program_addresses_before_wallets_initialized_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        get_program_addresses_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn test_get_months_since_start_before_import_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        get_months_since_start_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_burn_before_import_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let error = banks_client
+            .process_transaction(burn_transaction(&payer, recent_blockhash, false))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::ImportNotPerformedYet.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_withdrawals_before_import_fail() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let signer = payer.pubkey();
+        let token_program = spl_token::id();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            mint,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            partnership_account,
+            _,
+            marketing_account,
+            _,
+            _,
+            _,
+        ) = get_pda_accounts();
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        let partnership_instruction = Instruction::new_with_bytes(
+            program_id,
+            &instruction::WithdrawTokensFromPartnershipWallet {
+                amount_to_withdraw: 1,
+                idempotency_key: None,
+            }
+            .data(),
+            WithdrawTokensFromPartnershipWalletContext {
+                contract_state,
+                vesting_state,
+                deposit_wallet,
+                partnership_account,
+                token_program,
+                signer,
+                instructions_sysvar: sysvar::instructions::ID,
+                withdrawal_intent: None,
+                withdrawal_receipt: get_withdrawal_receipt_account(WalletKind::Partnership, 0),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(Some(false)),
+        );
+        let marketing_instruction = Instruction::new_with_bytes(
+            program_id,
+            &instruction::WithdrawTokensFromMarketingWallet {
+                amount_to_withdraw: 1,
+                idempotency_key: None,
+            }
+            .data(),
+            WithdrawTokensFromMarketingWalletContext {
+                contract_state,
+                vesting_state,
+                marketing_account,
+                deposit_wallet,
+                withdrawal_intent: None,
+                withdrawal_receipt: get_withdrawal_receipt_account(WalletKind::Marketing, 0),
+                signer,
+                token_program,
+                instructions_sysvar: sysvar::instructions::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(Some(false)),
+        );
+
+        let transactions = [
+            withdraw_tokens_from_community_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                get_withdrawal_receipt_account(WalletKind::Community, 0),
+            ),
+            Transaction::new_signed_with_payer(
+                &[partnership_instruction],
+                Some(&signer),
+                &[&payer],
+                recent_blockhash,
+            ),
+            Transaction::new_signed_with_payer(
+                &[marketing_instruction],
+                Some(&signer),
+                &[&payer],
+                recent_blockhash,
+            ),
+            withdraw_tokens_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                None,
+                get_withdrawal_receipt_account(WalletKind::Liquidity, 0),
+            ),
+        ];
+        for transaction in transactions {
+            let error = banks_client
+                .process_transaction(transaction)
+                .await
+                .unwrap_err()
+                .unwrap();
+            assert_eq!(
+                error,
+                TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(LeancoinError::ImportNotPerformedYet.into())
+                )
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_when_withdrawn_exceeds_unlocked_amount_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let mut program_test_context = program_test.start_with_context().await;
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, vesting_state, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        // simulate the withdrawals made before the clock of the cluster went back,
+        // so more tokens have been withdrawn than are unlocked now
+        let mut vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let mut current_vesting_state =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                .unwrap();
+        for wallet_kind in [WalletKind::Community, WalletKind::Liquidity] {
+            let wallet = current_vesting_state.wallet_mut(wallet_kind);
+            wallet.withdrawn = wallet.initial_balance;
+        }
+        let mut data = VestingState::discriminator().to_vec();
+        data.extend_from_slice(anchor_lang::__private::bytemuck::bytes_of(
+            &current_vesting_state,
+        ));
+        vesting_state_info.data = data;
+        program_test_context.set_account(&vesting_state, &vesting_state_info.into());
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Community).await;
+        let error = banks_client
+            .process_transaction(withdraw_tokens_from_community_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::WithdrawnMoreThanUnlocked.into())
+            )
+        );
+
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        let error = banks_client
+            .process_transaction(withdraw_tokens_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                None,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::WithdrawnMoreThanUnlocked.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_more_than_unlocked_does_not_change_withdrawn_amount() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let (
+            contract_state,
+            _,
+            vesting_state,
+            _,
+            mint,
+            _,
+            _,
+            _,
+            _,
+            _,
+            community_account,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+            _,
+        ) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Community).await;
+        banks_client
+            .process_transaction(withdraw_tokens_from_community_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(
+            get_already_withdrawn_wallet_amount(&mut banks_client, WalletKind::Community).await,
+            1
+        );
+
+        // only 2.5% of the community wallet is unlocked at the vesting start
+        let data = instruction::WithdrawTokensFromCommunityWallet {
+            amount_to_withdraw: 1_000_000_000_000_000_000,
+            idempotency_key: None,
+        }
+        .data();
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Community).await;
+        let accs = WithdrawTokensFromCommunityWalletContext {
+            vesting_state,
+            deposit_wallet,
+            signer: payer.pubkey(),
+            contract_state,
+            community_account,
+            token_program: spl_token::id(),
+            instructions_sysvar: sysvar::instructions::ID,
+            withdrawal_intent: None,
+            withdrawal_receipt,
+            system_program: system_program::ID,
+        };
+        let transaction = Transaction::new_signed_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+
+        let error = banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::NotEnoughTokensCommunity.into())
+            )
+        );
+        assert_eq!(
+            get_already_withdrawn_wallet_amount(&mut banks_client, WalletKind::Community).await,
+            1
+        );
+        assert_eq!(
+            get_token_balance(&mut banks_client, &deposit_wallet).await,
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_withdrawal_history() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        set_time(&mut program_test_context, 1677978061).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, vesting_state, _, mint, _, _, _, _, _, _, _, _, _, _, _, liquidity_account, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let read_vesting_state =
+            |data: Vec<u8>| VestingState::try_deserialize_unchecked(&mut data.as_slice()).unwrap();
+        let state = read_vesting_state(
+            banks_client
+                .get_account(vesting_state)
+                .await
+                .unwrap()
+                .unwrap()
+                .data,
+        );
+        assert_eq!(
+            state.withdrawal_history,
+            [WithdrawalHistory::default(); VESTED_WALLETS_COUNT]
+        );
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        //  Thursday, 11 May 2023 01:01:01 and Friday, 12 May 2023 01:01:01
+        let timestamps = [1683766861, 1683853261];
+        for timestamp in timestamps {
+            set_time(&mut program_test_context, timestamp).await;
+            let recent_blockhash = banks_client
+                .get_new_latest_blockhash(&recent_blockhash)
+                .await
+                .unwrap();
+            let withdrawal_receipt =
+                get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+            banks_client
+                .process_transaction(withdraw_vested_transaction(
+                    &payer,
+                    recent_blockhash,
+                    WalletKind::Liquidity,
+                    liquidity_account,
+                    deposit_wallet,
+                    1,
+                    withdrawal_receipt,
+                ))
+                .await
+                .unwrap();
+        }
+
+        let state = read_vesting_state(
+            banks_client
+                .get_account(vesting_state)
+                .await
+                .unwrap()
+                .unwrap()
+                .data,
+        );
+        assert_eq!(
+            *state.history(WalletKind::Liquidity),
+            WithdrawalHistory {
+                last_withdrawal_timestamp: timestamps[1],
+                withdrawal_count: 2,
+                padding: [0; 4],
+            }
+        );
+        assert_eq!(
+            *state.history(WalletKind::Community),
+            WithdrawalHistory::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_withdrawal_receipts() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        set_time(&mut program_test_context, 1677978061).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, vesting_state, _, mint, _, _, _, _, _, _, _, _, _, _, _, liquidity_account, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        //  Thursday, 11 May 2023 01:01:01, Friday, 12 May 2023 01:01:01 and Saturday, 13 May 2023 01:01:01
+        let withdrawals = [(1683766861, 1), (1683853261, 2), (1683939661, 3)];
+        let mut events = vec![];
+        for (index, (timestamp, amount)) in withdrawals.into_iter().enumerate() {
+            set_time(&mut program_test_context, timestamp).await;
+            let recent_blockhash = banks_client
+                .get_new_latest_blockhash(&recent_blockhash)
+                .await
+                .unwrap();
+            let withdrawal_receipt =
+                get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+            assert_eq!(
+                withdrawal_receipt,
+                get_withdrawal_receipt_account(WalletKind::Liquidity, index as u64)
+            );
+
+            let result = banks_client
+                .process_transaction_with_metadata(withdraw_vested_transaction(
+                    &payer,
+                    recent_blockhash,
+                    WalletKind::Liquidity,
+                    liquidity_account,
+                    deposit_wallet,
+                    amount,
+                    withdrawal_receipt,
+                ))
+                .await
+                .unwrap();
+            result.result.unwrap();
+            let event: VestedTokensWithdrawn =
+                get_event(&result.metadata.unwrap().log_messages).unwrap();
+            events.push(event);
+        }
+
+        let mut already_withdrawn_total = 0;
+        for (index, ((timestamp, amount), event)) in withdrawals.into_iter().zip(events).enumerate()
+        {
+            already_withdrawn_total += amount;
+            let receipt_info = banks_client
+                .get_account(get_withdrawal_receipt_account(
+                    WalletKind::Liquidity,
+                    index as u64,
+                ))
+                .await
+                .unwrap()
+                .unwrap();
+            let receipt =
+                WithdrawalReceipt::try_deserialize(&mut receipt_info.data.as_slice()).unwrap();
+
+            assert_eq!(receipt.wallet_kind, WalletKind::Liquidity);
+            assert_eq!(receipt.index, index as u64);
+            assert_eq!(receipt.amount, amount);
+            assert_eq!(receipt.destination, deposit_wallet);
+            assert_eq!(receipt.timestamp, timestamp);
+            assert_eq!(receipt.unlocked_total, event.unlocked_total);
+            assert_eq!(receipt.already_withdrawn_total, already_withdrawn_total);
+            assert_eq!(
+                receipt.already_withdrawn_total,
+                event.already_withdrawn_total
+            );
+        }
+
+        let vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let state =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                .unwrap();
+        assert_eq!(state.next_receipt_index, [0, 0, 0, 3]);
+        assert!(banks_client
+            .get_account(get_withdrawal_receipt_account(WalletKind::Liquidity, 3))
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_all_unlocked() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2023 01:01:01
+        set_time(&mut program_test_context, 1677978061).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (
+            _,
+            _,
+            _,
+            _,
+            mint,
+            _,
+            _,
+            _,
+            _,
+            _,
+            community_account,
+            _,
+            partnership_account,
+            _,
+            marketing_account,
+            _,
+            liquidity_account,
+            _,
+        ) = get_pda_accounts();
+        let wallet_accounts = [
+            community_account,
+            partnership_account,
+            marketing_account,
+            liquidity_account,
+        ];
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let mut deposit_wallets = [Pubkey::default(); VESTED_WALLETS_COUNT];
+        for deposit_wallet in deposit_wallets.iter_mut() {
+            *deposit_wallet =
+                create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                    .await
+                    .unwrap();
+        }
+
+        //  Friday, 5 April 2024 01:01:01
+        let timestamp = 1712278861;
+        set_time(&mut program_test_context, timestamp).await;
+
+        let vesting_status =
+            get_vesting_status_instruction(&mut banks_client, &payer, recent_blockhash)
+                .await
+                .unwrap();
+        assert_eq!(vesting_status.months_since_vesting_start, 13);
+
+        let mut wallet_balances = [0; VESTED_WALLETS_COUNT];
+        for wallet_kind in WalletKind::ALL {
+            wallet_balances[wallet_kind.index()] =
+                get_token_balance(&mut banks_client, &wallet_accounts[wallet_kind.index()]).await;
+            // every wallet has unlocked tokens after 13 months
+            assert!(vesting_status.wallets[wallet_kind.index()].withdrawable > 0);
+        }
+
+        let withdrawal_receipts = get_next_withdrawal_receipt_accounts(&mut banks_client).await;
+        let transaction_result = banks_client
+            .process_transaction_with_metadata(withdraw_all_unlocked_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallets,
+                withdrawal_receipts,
+            ))
+            .await
+            .unwrap();
+        transaction_result.result.unwrap();
+        let metadata = transaction_result.metadata.unwrap();
+
+        let withdrawn_amounts = vesting_status
+            .wallets
+            .map(|wallet_status| wallet_status.withdrawable);
+        for wallet_kind in WalletKind::ALL {
+            let index = wallet_kind.index();
+            assert_eq!(
+                get_token_balance(&mut banks_client, &deposit_wallets[index]).await,
+                withdrawn_amounts[index],
+                "{:?}",
+                wallet_kind
+            );
+            assert_eq!(
+                get_token_balance(&mut banks_client, &wallet_accounts[index]).await,
+                wallet_balances[index] - withdrawn_amounts[index],
+                "{:?}",
+                wallet_kind
+            );
+            assert_eq!(
+                get_already_withdrawn_wallet_amount(&mut banks_client, wallet_kind).await,
+                withdrawn_amounts[index],
+                "{:?}",
+                wallet_kind
+            );
+        }
+
+        let event: AllUnlockedTokensWithdrawn = get_event(&metadata.log_messages).unwrap();
+        assert_eq!(event.amounts, withdrawn_amounts);
+        assert_eq!(event.destinations, deposit_wallets);
+        assert_eq!(event.already_withdrawn_totals, withdrawn_amounts);
+        assert_eq!(event.months_since_vesting_start, 13);
+        assert_eq!(event.timestamp, timestamp);
+
+        for wallet_kind in WalletKind::ALL {
+            let index = wallet_kind.index();
+            let receipt_info = banks_client
+                .get_account(get_withdrawal_receipt_account(wallet_kind, 0))
+                .await
+                .unwrap()
+                .unwrap();
+            let receipt =
+                WithdrawalReceipt::try_deserialize(&mut receipt_info.data.as_slice()).unwrap();
+
+            assert_eq!(receipt.wallet_kind, wallet_kind);
+            assert_eq!(receipt.index, 0);
+            assert_eq!(receipt.amount, withdrawn_amounts[index]);
+            assert_eq!(receipt.destination, deposit_wallets[index]);
+            assert_eq!(receipt.timestamp, timestamp);
+            assert_eq!(
+                receipt.unlocked_total,
+                vesting_status.wallets[index].unlocked
+            );
+            assert_eq!(receipt.already_withdrawn_total, withdrawn_amounts[index]);
+        }
+
+        // nothing is available anymore, so all wallets are skipped
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+        let withdrawal_receipts = get_next_withdrawal_receipt_accounts(&mut banks_client).await;
+        assert_eq!(
+            withdrawal_receipts,
+            WalletKind::ALL.map(|wallet_kind| get_withdrawal_receipt_account(wallet_kind, 1))
         );
+        let transaction_result = banks_client
+            .process_transaction_with_metadata(withdraw_all_unlocked_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallets,
+                withdrawal_receipts,
+            ))
+            .await
+            .unwrap();
+        transaction_result.result.unwrap();
 
-        transaction.sign(&[payer], recent_blockhash);
-        banks_client
-            .process_transaction_with_commitment(transaction.clone(), CommitmentLevel::Finalized)
+        let event: AllUnlockedTokensWithdrawn =
+            get_event(&transaction_result.metadata.unwrap().log_messages).unwrap();
+        assert_eq!(event.amounts, [0; VESTED_WALLETS_COUNT]);
+        assert_eq!(event.already_withdrawn_totals, withdrawn_amounts);
+
+        // the skipped wallets get receipts without any tokens
+        for wallet_kind in WalletKind::ALL {
+            let receipt_info = banks_client
+                .get_account(withdrawal_receipts[wallet_kind.index()])
+                .await
+                .unwrap()
+                .unwrap();
+            let receipt =
+                WithdrawalReceipt::try_deserialize(&mut receipt_info.data.as_slice()).unwrap();
+
+            assert_eq!(receipt.index, 1);
+            assert_eq!(receipt.amount, 0);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_all_unlocked_into_vested_account_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
             .await
             .unwrap();
 
-        let contract_state_info = banks_client
-            .get_account_with_commitment(contract_state, CommitmentLevel::Finalized)
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, liquidity_account, _) =
+            get_pda_accounts();
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        // the tokens of the community wallet would stay in the program
+        let mut deposit_wallets = [deposit_wallet; VESTED_WALLETS_COUNT];
+        deposit_wallets[WalletKind::Community.index()] = liquidity_account;
+
+        let withdrawal_receipts = get_next_withdrawal_receipt_accounts(&mut banks_client).await;
+        let error = banks_client
+            .process_transaction(withdraw_all_unlocked_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallets,
+                withdrawal_receipts,
+            ))
             .await
-            .unwrap()
+            .unwrap_err()
             .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::DepositWalletIsProgramAccount.into())
+            )
+        );
+    }
 
-        let contract_state: ContractState =
-            ContractState::try_deserialize_unchecked(&mut contract_state_info.data.as_slice())
+    #[tokio::test]
+    async fn test_withdraw_all_unlocked_tokens_from_liquidity_wallet() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
                 .unwrap();
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        banks_client
+            .process_transaction(withdraw_tokens_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                None,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap();
 
+        // 50% of 10^18 is unlocked at the vesting start and 1 token has already been withdrawn
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        let transaction_result = banks_client
+            .process_transaction_with_metadata(withdraw_amount_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                WITHDRAW_ALL_UNLOCKED,
+                None,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap();
+        transaction_result.result.unwrap();
+        let events = parse_events(&transaction_result.metadata.unwrap().log_messages);
+        let withdrawal_event = match events.as_slice() {
+            [LeancoinEvent::VestedTokensWithdrawn(event)] => event,
+            _ => panic!("unexpected events"),
+        };
+        assert_eq!(withdrawal_event.wallet_kind, WalletKind::Liquidity);
+        assert_eq!(withdrawal_event.amount, 499_999_999_999_999_999);
         assert_eq!(
-            contract_state.import_ethereum_token_state_already_performed,
-            true
+            get_token_balance(&mut banks_client, &deposit_wallet).await,
+            500_000_000_000_000_000
+        );
+        assert_eq!(
+            get_already_withdrawn_wallet_amount(&mut banks_client, WalletKind::Liquidity).await,
+            500_000_000_000_000_000
         );
-        assert_eq!(contract_state.authority, signer);
-        assert_eq!(contract_state.last_burning_month, 0);
-        assert_eq!(contract_state.last_burning_year, 0);
-        assert_eq!(contract_state.mint_nonce, mint_nonce);
-        assert_eq!(contract_state.program_account_nonce, program_account_nonce);
 
-        Ok(())
+        // nothing is available anymore, so withdrawing everything succeeds without moving any tokens
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        banks_client
+            .process_transaction(withdraw_amount_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                WITHDRAW_ALL_UNLOCKED,
+                None,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &deposit_wallet).await,
+            500_000_000_000_000_000
+        );
+        assert_eq!(
+            get_already_withdrawn_wallet_amount(&mut banks_client, WalletKind::Liquidity).await,
+            500_000_000_000_000_000
+        );
     }
 
-    async fn burn_instruction(
-        banks_client: &mut BanksClient,
-        payer: &Keypair,
-        recent_blockhash: Hash,
-    ) -> Result<()> {
+    #[tokio::test]
+    async fn test_withdraw_vested_from_every_wallet() {
         let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
 
-        let (contract_state, _, _, _, mint, _, _, _, burning_account, _, _, _, _, _, _, _, _, _) =
+        //  Sunday, 5 March 2023 01:01:01
+        set_time(&mut program_test_context, 1677978061).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        //  Wednesday, 5 March 2025 01:01:01, the tokens of every wallet are at least partially unlocked
+        set_time(&mut program_test_context, 1741136461).await;
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        for (index, wallet_kind) in WalletKind::ALL.into_iter().enumerate() {
+            let (wallet_account, _) =
+                Pubkey::find_program_address(&[wallet_kind.seed().as_bytes()], &program_id);
+            let withdrawal_receipt =
+                get_next_withdrawal_receipt_account(&mut banks_client, wallet_kind).await;
+            banks_client
+                .process_transaction(withdraw_vested_transaction(
+                    &payer,
+                    recent_blockhash,
+                    wallet_kind,
+                    wallet_account,
+                    deposit_wallet,
+                    1,
+                    withdrawal_receipt,
+                ))
+                .await
+                .unwrap();
+
+            assert_eq!(
+                get_already_withdrawn_wallet_amount(&mut banks_client, wallet_kind).await,
+                1
+            );
+            assert_eq!(
+                get_token_balance(&mut banks_client, &deposit_wallet).await,
+                index as u64 + 1
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_vested_with_account_of_other_wallet_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, partnership_account, _, _, _, _, _) =
             get_pda_accounts();
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
 
-        let token_program = spl_token::id();
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Community).await;
+        let error = banks_client
+            .process_transaction(withdraw_vested_transaction(
+                &payer,
+                recent_blockhash,
+                WalletKind::Community,
+                partnership_account,
+                deposit_wallet,
+                1,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::VestedWalletAccountMismatch.into())
+            )
+        );
+        assert_eq!(
+            get_already_withdrawn_wallet_amount(&mut banks_client, WalletKind::Community).await,
+            0
+        );
+    }
 
-        let data = instruction::Burn {}.data();
+    #[tokio::test]
+    async fn test_withdraw_to_token_account_of_foreign_mint_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
 
-        let accs = BurnContext {
-            contract_state,
-            mint,
-            burning_account,
-            token_program,
-        };
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
 
-        let mut transaction = Transaction::new_with_payer(
-            &[Instruction::new_with_bytes(
-                program_id,
-                &data,
-                accs.to_account_metas(Some(false)),
-            )],
+        let foreign_mint = create_foreign_mint(&mut banks_client, &payer, recent_blockhash).await;
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, foreign_mint)
+                .await
+                .unwrap();
+
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        let error = banks_client
+            .process_transaction(withdraw_tokens_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                None,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::WrongDepositWalletMint.into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recover_program_account_tokens() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let (_, _, _, _, mint, _, program_account, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+        let destination = create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+            .await
+            .unwrap();
+
+        // the tokens of the program account are distributed by the import
+        let error = banks_client
+            .process_transaction(recover_program_account_tokens_transaction(
+                &payer,
+                recent_blockhash,
+                destination,
+                1,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::ImportNotPerformedYet.into())
+            )
+        );
+
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &program_account).await,
+            0
+        );
+
+        // tokens accidentally sent to the program account after the import
+        withdraw_tokens_from_liquidity_wallet_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            deposit_wallet,
+        )
+        .await
+        .unwrap();
+        let transaction = Transaction::new_signed_with_payer(
+            &[spl_token::instruction::transfer(
+                &spl_token::id(),
+                &deposit_wallet,
+                &program_account,
+                &payer.pubkey(),
+                &[],
+                1_000,
+            )
+            .unwrap()],
             Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(transaction).await.unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &program_account).await,
+            1_000
+        );
+
+        let foreign_mint = create_foreign_mint(&mut banks_client, &payer, recent_blockhash).await;
+        let foreign_destination =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, foreign_mint)
+                .await
+                .unwrap();
+        let error = banks_client
+            .process_transaction(recover_program_account_tokens_transaction(
+                &payer,
+                recent_blockhash,
+                foreign_destination,
+                1_000,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::WrongDepositWalletMint.into())
+            )
         );
 
-        transaction.sign(&[payer], recent_blockhash);
         banks_client
-            .process_transaction_with_commitment(transaction.clone(), CommitmentLevel::Finalized)
+            .process_transaction(recover_program_account_tokens_transaction(
+                &payer,
+                recent_blockhash,
+                destination,
+                400,
+            ))
             .await
             .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &program_account).await,
+            600
+        );
+        assert_eq!(
+            get_token_balance(&mut banks_client, &destination).await,
+            400
+        );
 
-        Ok(())
+        banks_client
+            .process_transaction(recover_program_account_tokens_transaction(
+                &payer,
+                recent_blockhash,
+                destination,
+                WHOLE_BALANCE,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &program_account).await,
+            0
+        );
+        assert_eq!(
+            get_token_balance(&mut banks_client, &destination).await,
+            1_000
+        );
     }
 
-    async fn set_the_token_metadata_instruction(
-        banks_client: &mut BanksClient,
-        payer: &Keypair,
-        recent_blockhash: Hash,
-        token_metadata_action: TokenMetadataAction,
-        metadata_pda: Pubkey,
-    ) -> Result<()> {
+    #[tokio::test]
+    async fn test_recover_foreign_tokens() {
         let program_id = id();
-
-        let (contract_state, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let (_, _, _, _, mint, _, _, _, burning_account, _, community_account, _, _, _, _, _, _, _) =
             get_pda_accounts();
 
-        let token_program = spl_token::id();
-
-        let data = instruction::SetTokenMetadata {
-            name: "Test".to_string(),
-            symbol: "TST".to_string(),
-            uri: "https://test.com".to_string(),
-            token_metadata_action,
-        }
-        .data();
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
 
-        let accs = SetTokenMetadataContext {
-            contract_state,
-            mint,
-            metadata_pda,
-            metadata_program: mpl_token_metadata::id(),
-            signer: payer.pubkey(),
-            system_program: system_program::ID,
-            token_program,
-        };
+        let foreign_mint = create_foreign_mint(&mut banks_client, &payer, recent_blockhash).await;
+        let foreign_token_account = create_token_account_with_owner(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            foreign_mint,
+            community_account,
+        )
+        .await
+        .unwrap();
+        let destination =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, foreign_mint)
+                .await
+                .unwrap();
 
-        let mut transaction = Transaction::new_with_payer(
-            &[Instruction::new_with_bytes(
-                program_id,
-                &data,
-                accs.to_account_metas(Some(false)),
-            )],
+        let transaction = Transaction::new_signed_with_payer(
+            &[spl_token::instruction::mint_to(
+                &spl_token::id(),
+                &foreign_mint,
+                &foreign_token_account,
+                &payer.pubkey(),
+                &[],
+                1_000,
+            )
+            .unwrap()],
             Some(&payer.pubkey()),
+            &[&payer],
+            recent_blockhash,
         );
+        banks_client.process_transaction(transaction).await.unwrap();
 
-        transaction.sign(&[payer], recent_blockhash);
         banks_client
-            .process_transaction_with_commitment(transaction.clone(), CommitmentLevel::Finalized)
+            .process_transaction(recover_foreign_tokens_transaction(
+                &payer,
+                recent_blockhash,
+                LabeledAccountKind::Community,
+                community_account,
+                foreign_token_account,
+                destination,
+            ))
             .await
             .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &foreign_token_account).await,
+            0
+        );
+        assert_eq!(
+            get_token_balance(&mut banks_client, &destination).await,
+            1_000
+        );
 
-        Ok(())
+        // the tokens of the program's mint held by the burning account cannot be moved
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+        let error = banks_client
+            .process_transaction(recover_foreign_tokens_transaction(
+                &payer,
+                recent_blockhash,
+                LabeledAccountKind::BurningAccount,
+                burning_account,
+                burning_account,
+                deposit_wallet,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::NativeMintRecoveryNotAllowed.into())
+            )
+        );
     }
 
-    async fn withdraw_tokens_from_partnership_wallet_instruction(
-        banks_client: &mut BanksClient,
-        payer: &Keypair,
-        recent_blockhash: Hash,
-        deposit_wallet: Pubkey,
-    ) -> Result<()> {
+    #[tokio::test]
+    async fn test_withdraw_into_program_token_account_fails() {
         let program_id = id();
-        let signer = payer.pubkey();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
 
         let (
-            contract_state,
             _,
-            vesting_state,
             _,
             _,
             _,
             _,
             _,
+            program_account,
             _,
+            burning_account,
             _,
             _,
             _,
-            partnership_account,
             _,
             _,
             _,
             _,
+            liquidity_account,
             _,
         ) = get_pda_accounts();
 
-        let token_program = spl_token::id();
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
 
-        let data = instruction::WithdrawTokensFromPartnershipWallet {
-            amount_to_withdraw: 1000000000000000000,
+        for (index, deposit_wallet) in [liquidity_account, program_account, burning_account]
+            .into_iter()
+            .enumerate()
+        {
+            let recent_blockhash = banks_client
+                .get_new_latest_blockhash(&recent_blockhash)
+                .await
+                .unwrap();
+            let withdrawal_receipt =
+                get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+            let error = banks_client
+                .process_transaction(withdraw_tokens_from_liquidity_wallet_transaction(
+                    &payer,
+                    recent_blockhash,
+                    deposit_wallet,
+                    None,
+                    withdrawal_receipt,
+                ))
+                .await
+                .unwrap_err()
+                .unwrap();
+            assert_eq!(
+                error,
+                TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(LeancoinError::DepositWalletIsProgramAccount.into())
+                ),
+                "deposit wallet {}",
+                index
+            );
         }
-        .data();
 
-        let accs = WithdrawTokensFromPartnershipWalletContext {
-            contract_state,
-            vesting_state,
+        assert_eq!(
+            get_already_withdrawn_wallet_amount(&mut banks_client, WalletKind::Liquidity).await,
+            0
+        );
+    }
+
+    #[tokio::test]
+    #[should_panic]
+    async fn test_withdraw_tokens_from_marketing_wallet() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        withdraw_tokens_from_marketing_wallet_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
             deposit_wallet,
-            partnership_account,
-            token_program,
-            signer,
-        };
+        )
+        .await
+        .unwrap();
+    }
 
-        let mut transaction = Transaction::new_with_payer(
-            &[Instruction::new_with_bytes(
-                program_id,
-                &data,
-                accs.to_account_metas(Some(false)),
-            )],
-            Some(&payer.pubkey()),
+    #[tokio::test]
+    async fn test_withdraw_tokens_from_marketing_wallet_after_one_year() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+        let mut program_test_context = program_test.start_with_context().await;
+
+        //  Sunday, 5 March 2024 01:01:01
+        let time_in_timestamp = 1677978061;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let mut banks_client = program_test_context.banks_client.clone();
+        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
+        let recent_blockhash = program_test_context.last_blockhash;
+
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        //  Thursday, 11 May 2023 01:01:01
+        let time_in_timestamp = 1709600470;
+        set_time(&mut program_test_context, time_in_timestamp).await;
+
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        let deposit_wallet_balance_before_withdraw_tokens_from_marketing_wallet_context =
+            get_token_balance(&mut banks_client, &deposit_wallet).await;
+        assert_eq!(
+            deposit_wallet_balance_before_withdraw_tokens_from_marketing_wallet_context,
+            0
         );
 
-        transaction.sign(&[payer], recent_blockhash);
-        banks_client
-            .process_transaction_with_commitment(transaction.clone(), CommitmentLevel::Finalized)
+        let recent_blockhash = program_test_context
+            .banks_client
+            .get_latest_blockhash()
             .await
             .unwrap();
+        withdraw_tokens_from_marketing_wallet_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            deposit_wallet,
+        )
+        .await
+        .unwrap();
 
-        Ok(())
+        let deposit_wallet_balance_after_withdraw_tokens_from_marketing_wallet_context =
+            get_token_balance(&mut banks_client, &deposit_wallet).await;
+        assert_eq!(
+            deposit_wallet_balance_after_withdraw_tokens_from_marketing_wallet_context,
+            1
+        );
     }
 
-    async fn withdraw_tokens_from_marketing_wallet_instruction(
-        banks_client: &mut BanksClient,
-        payer: &Keypair,
-        recent_blockhash: Hash,
-        deposit_wallet: Pubkey,
-    ) -> Result<()> {
+    #[tokio::test]
+    async fn test_withdraw_tokens_from_liquidity_wallet() {
         let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
         let token_program = spl_token::id();
         let signer = payer.pubkey();
 
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
         let (
             contract_state,
             _,
             vesting_state,
             _,
+            mint,
             _,
             _,
             _,
@@ -921,24 +16001,42 @@ mod tests {
             _,
             _,
             _,
-            marketing_account,
-            _,
             _,
+            liquidity_account,
             _,
         ) = get_pda_accounts();
 
-        let data = instruction::WithdrawTokensFromMarketingWallet {
+        let data = instruction::WithdrawTokensFromLiquidityWallet {
             amount_to_withdraw: 1,
+            idempotency_key: None,
         }
         .data();
 
-        let accs = WithdrawTokensFromMarketingWalletContext {
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
+
+        let deposit_wallet_balance_before_withdraw_tokens_from_liquidity_wallet_context =
+            get_token_balance(&mut banks_client, &deposit_wallet).await;
+        assert_eq!(
+            deposit_wallet_balance_before_withdraw_tokens_from_liquidity_wallet_context,
+            0
+        );
+
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        let accs = WithdrawTokensFromLiquidityWalletContext {
             vesting_state,
             deposit_wallet,
             signer,
             contract_state,
-            marketing_account,
+            liquidity_account,
             token_program,
+            instructions_sysvar: sysvar::instructions::ID,
+            withdrawal_intent: None,
+            withdrawal_receipt,
+            system_program: system_program::ID,
         };
 
         let mut transaction = Transaction::new_with_payer(
@@ -950,83 +16048,25 @@ mod tests {
             Some(&payer.pubkey()),
         );
 
-        transaction.sign(&[payer], recent_blockhash);
+        transaction.sign(&[&payer], recent_blockhash);
         banks_client.process_transaction(transaction).await.unwrap();
 
-        Ok(())
-    }
-
-    #[tokio::test]
-    async fn test_initialize() {
-        let program_id = id();
-        let program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
-        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
-
-        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
-            .await
-            .unwrap();
+        let deposit_wallet_balance_after_withdraw_tokens_from_liquidity_wallet_context =
+            get_token_balance(&mut banks_client, &deposit_wallet).await;
+        assert_eq!(
+            deposit_wallet_balance_after_withdraw_tokens_from_liquidity_wallet_context,
+            1
+        );
     }
 
     #[tokio::test]
-    async fn test_import_ethereum_token_state() {
+    async fn test_withdraw_tokens_to_wallet_of_beneficiary_owner() {
         let program_id = id();
         let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
         program_test.set_compute_max_units(500000);
 
         let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
 
-        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
-            .await
-            .unwrap();
-
-        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
-            .await
-            .unwrap();
-    }
-
-    #[tokio::test]
-    #[should_panic]
-    async fn test_burn_after_5th_day_of_month_fails() {
-        let program_id = id();
-        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
-        program_test.set_compute_max_units(500000);
-        let mut program_test_context = program_test.start_with_context().await;
-
-        //  Monday, 6 March 2023 01:01:01
-        let time_in_timestamp = 1678064461;
-        set_time(&mut program_test_context, time_in_timestamp).await;
-
-        let mut banks_client = program_test_context.banks_client;
-        let payer = program_test_context.payer;
-        let recent_blockhash = program_test_context.last_blockhash;
-
-        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
-            .await
-            .unwrap();
-        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
-            .await
-            .unwrap();
-
-        burn_instruction(&mut banks_client, &payer, recent_blockhash)
-            .await
-            .unwrap();
-    }
-
-    #[tokio::test]
-    async fn test_burn_on_5th_day_of_month_succeeds() {
-        let program_id = id();
-        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
-        program_test.set_compute_max_units(500000);
-        let mut program_test_context = program_test.start_with_context().await;
-
-        //  Sunday, 5 March 2023 01:01:01
-        let time_in_timestamp = 1677978061;
-        set_time(&mut program_test_context, time_in_timestamp).await;
-
-        let mut banks_client = program_test_context.banks_client;
-        let payer = program_test_context.payer;
-        let recent_blockhash = program_test_context.last_blockhash;
-
         initialize_instruction(&mut banks_client, &payer, recent_blockhash)
             .await
             .unwrap();
@@ -1034,113 +16074,47 @@ mod tests {
             .await
             .unwrap();
 
-        let (_, _, _, _, _, _, _, _, burning_account, _, _, _, _, _, _, _, _, _) =
-            get_pda_accounts();
-
-        let burning_account_mint_balance =
-            get_token_balance(&mut banks_client, &burning_account).await;
-        let expected_burning_account_mint_balance = 1800000000000000000;
-        assert_eq!(
-            burning_account_mint_balance,
-            expected_burning_account_mint_balance
-        );
-
-        burn_instruction(&mut banks_client, &payer, recent_blockhash)
-            .await
-            .unwrap();
-
-        let burning_account_mint_balance =
-            get_token_balance(&mut banks_client, &burning_account).await;
-        let expected_burning_account_mint_balance = 1800000000000000000 - 1800000000000000000 / 20;
-        assert_eq!(
-            burning_account_mint_balance,
-            expected_burning_account_mint_balance
-        );
-    }
-
-    async fn get_token_balance(banks_client: &mut BanksClient, burning_account: &Pubkey) -> u64 {
-        let burning_account_mint_account = banks_client
-            .get_account(burning_account.clone())
-            .await
-            .unwrap()
-            .unwrap();
-        let burning_account_mint_state = spl_token::state::Account::unpack_from_slice(
-            burning_account_mint_account.data.as_slice(),
-        )
-        .unwrap();
-
-        burning_account_mint_state.amount
-    }
-
-    #[tokio::test]
-    #[should_panic]
-    async fn test_burn_change_clock_two_times_in_one_day() {
-        let program_id = id();
-        let program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
-        let mut program_test_context = program_test.start_with_context().await;
-
-        //  Sunday, 5 March 2023 01:01:01
-        let time_in_timestamp = 1677978061;
-        set_time(&mut program_test_context, time_in_timestamp).await;
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
 
-        let mut sub_clock = Clock::default();
-        sub_clock.unix_timestamp += 2_160_000;
-        let recent_blockhash = program_test_context
-            .banks_client
-            .get_latest_blockhash()
-            .await
-            .unwrap();
+        // the deposit wallet is owned by the payer
+        let deposit_wallet =
+            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
+                .await
+                .unwrap();
 
-        initialize_instruction(
-            &mut program_test_context.banks_client,
-            &program_test_context.payer,
-            recent_blockhash,
-        )
-        .await
-        .unwrap();
-        import_ethereum_token_state_instruction(
-            &mut program_test_context.banks_client,
-            &program_test_context.payer,
+        set_beneficiary_owner_instruction(
+            &mut banks_client,
+            &payer,
             recent_blockhash,
+            WalletKind::Liquidity,
+            payer.pubkey(),
         )
         .await
         .unwrap();
 
-        burn_instruction(
-            &mut program_test_context.banks_client,
-            &program_test_context.payer,
+        withdraw_tokens_from_liquidity_wallet_instruction(
+            &mut banks_client,
+            &payer,
             recent_blockhash,
+            deposit_wallet,
         )
         .await
         .unwrap();
 
-        //  Sunday, 5 March 2023 05:01:01
-        let time_in_timestamp = 1677992461;
-        set_time(&mut program_test_context, time_in_timestamp).await;
-
-        let recent_blockhash = program_test_context
-            .banks_client
-            .get_latest_blockhash()
-            .await
-            .unwrap();
-        burn_instruction(
-            &mut program_test_context.banks_client,
-            &program_test_context.payer,
-            recent_blockhash,
-        )
-        .await
-        .unwrap();
+        assert_eq!(
+            get_token_balance(&mut banks_client, &deposit_wallet).await,
+            1
+        );
     }
 
     #[tokio::test]
-    async fn test_withdraw_tokens_from_community_wallet() {
+    async fn test_withdraw_tokens_by_wallet_beneficiary() {
         let program_id = id();
         let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
         program_test.set_compute_max_units(500000);
+        let beneficiary = add_funded_keypair(&mut program_test);
 
         let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
-        let token_program = spl_token::id();
-        let signer = payer.pubkey();
 
         initialize_instruction(&mut banks_client, &payer, recent_blockhash)
             .await
@@ -1150,7 +16124,7 @@ mod tests {
             .unwrap();
 
         let (
-            contract_state,
+            _,
             _,
             vesting_state,
             _,
@@ -1166,59 +16140,181 @@ mod tests {
             _,
             _,
             _,
-            _,
+            liquidity_account,
             _,
         ) = get_pda_accounts();
 
-        let data = instruction::WithdrawTokensFromCommunityWallet {
-            amount_to_withdraw: 25_000_000_000_000_000,
-        }
-        .data();
+        let vesting_state_info = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .unwrap();
+        let state =
+            VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                .unwrap();
+        assert_eq!(
+            state.beneficiaries,
+            [Pubkey::default(); VESTED_WALLETS_COUNT]
+        );
 
         let deposit_wallet =
             create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
                 .await
                 .unwrap();
 
-        let deposit_wallet_balance_before_withdraw_tokens_from_community_wallet_context =
-            get_token_balance(&mut banks_client, &deposit_wallet).await;
+        // only the contract's owner can withdraw before the beneficiary is set
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        let error = banks_client
+            .process_transaction(withdraw_vested_transaction(
+                &beneficiary,
+                recent_blockhash,
+                WalletKind::Liquidity,
+                liquidity_account,
+                deposit_wallet,
+                1,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
         assert_eq!(
-            deposit_wallet_balance_before_withdraw_tokens_from_community_wallet_context,
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::Unauthorized.into())
+            )
+        );
+
+        set_wallet_beneficiary_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            WalletKind::Liquidity,
+            beneficiary.pubkey(),
+        )
+        .await
+        .unwrap();
+
+        let recent_blockhash = banks_client
+            .get_new_latest_blockhash(&recent_blockhash)
+            .await
+            .unwrap();
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        banks_client
+            .process_transaction(withdraw_vested_transaction(
+                &beneficiary,
+                recent_blockhash,
+                WalletKind::Liquidity,
+                liquidity_account,
+                deposit_wallet,
+                1,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap();
+
+        // the owner can still withdraw from the wallet
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        banks_client
+            .process_transaction(withdraw_vested_transaction(
+                &payer,
+                recent_blockhash,
+                WalletKind::Liquidity,
+                liquidity_account,
+                deposit_wallet,
+                1,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            get_token_balance(&mut banks_client, &deposit_wallet).await,
+            2
+        );
+
+        // the beneficiary of the liquidity wallet cannot withdraw from another wallet
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Community).await;
+        let error = banks_client
+            .process_transaction(withdraw_vested_transaction(
+                &beneficiary,
+                recent_blockhash,
+                WalletKind::Community,
+                community_account,
+                deposit_wallet,
+                1,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::Unauthorized.into())
+            )
+        );
+        assert_eq!(
+            get_already_withdrawn_wallet_amount(&mut banks_client, WalletKind::Community).await,
             0
         );
+    }
 
-        let accs = WithdrawTokensFromCommunityWalletContext {
-            vesting_state,
-            deposit_wallet,
-            signer,
-            contract_state,
-            community_account,
-            token_program,
-        };
+    #[tokio::test]
+    async fn test_withdraw_tokens_to_frozen_wallet_fails() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
+
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
 
-        let mut transaction = Transaction::new_with_payer(
-            &[Instruction::new_with_bytes(
-                program_id,
-                &data,
-                accs.to_account_metas(Some(false)),
-            )],
-            Some(&payer.pubkey()),
+        let deposit_wallet = add_token_account(
+            &mut program_test,
+            mint,
+            Pubkey::new_unique(),
+            0,
+            AccountState::Frozen,
         );
 
-        transaction.sign(&[&payer], recent_blockhash);
-        banks_client.process_transaction(transaction).await.unwrap();
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
 
-        let deposit_wallet_balance_after_withdraw_tokens_from_community_wallet_context =
-            get_token_balance(&mut banks_client, &deposit_wallet).await;
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let withdrawal_receipt =
+            get_next_withdrawal_receipt_account(&mut banks_client, WalletKind::Liquidity).await;
+        let error = banks_client
+            .process_transaction(withdraw_tokens_from_liquidity_wallet_transaction(
+                &payer,
+                recent_blockhash,
+                deposit_wallet,
+                None,
+                withdrawal_receipt,
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
         assert_eq!(
-            deposit_wallet_balance_after_withdraw_tokens_from_community_wallet_context,
-            25_000_000_000_000_000
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::DepositWalletFrozen.into())
+            )
         );
     }
 
     #[tokio::test]
     #[should_panic]
-    async fn test_withdraw_tokens_from_partnership_wallet() {
+    async fn test_withdraw_tokens_to_wallet_of_third_party_fails() {
         let program_id = id();
         let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
         program_test.set_compute_max_units(500000);
@@ -1239,7 +16335,17 @@ mod tests {
                 .await
                 .unwrap();
 
-        withdraw_tokens_from_partnership_wallet_instruction(
+        set_beneficiary_owner_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            WalletKind::Liquidity,
+            Pubkey::new_unique(),
+        )
+        .await
+        .unwrap();
+
+        withdraw_tokens_from_liquidity_wallet_instruction(
             &mut banks_client,
             &payer,
             recent_blockhash,
@@ -1250,303 +16356,403 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_withdraw_tokens_from_partnership_wallet_after_one_month() {
+    async fn test_new_authority() {
         let program_id = id();
         let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
         program_test.set_compute_max_units(500000);
-        let mut program_test_context = program_test.start_with_context().await;
-
-        //  Sunday, 5 March 2023 01:01:01
-        let time_in_timestamp = 1677978061;
-        set_time(&mut program_test_context, time_in_timestamp).await;
 
-        let mut banks_client = program_test_context.banks_client.clone();
-        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
-        let recent_blockhash = program_test_context.last_blockhash;
-        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let signer = payer.pubkey();
 
         initialize_instruction(&mut banks_client, &payer, recent_blockhash)
             .await
             .unwrap();
-        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
-            .await
-            .unwrap();
 
-        //  Thursday, 11 May 2023 01:01:01
-        let time_in_timestamp = 1683766861;
-        set_time(&mut program_test_context, time_in_timestamp).await;
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
 
-        let deposit_wallet =
-            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
-                .await
-                .unwrap();
+        let data = instruction::ChangeAuthority {
+            new_authority: signer,
+        }
+        .data();
 
-        let deposit_wallet_balance_before_withdraw_tokens_from_partnership_wallet_context =
-            get_token_balance(&mut banks_client, &deposit_wallet).await;
-        assert_eq!(
-            deposit_wallet_balance_before_withdraw_tokens_from_partnership_wallet_context,
-            0
-        );
-        withdraw_tokens_from_partnership_wallet_instruction(
-            &mut banks_client,
-            &payer,
-            recent_blockhash,
-            deposit_wallet,
-        )
-        .await
-        .unwrap();
+        let accs = ChangeAuthorityContext {
+            contract_state,
+            signer,
+        };
 
-        let deposit_wallet_balance_after_withdraw_tokens_from_partnership_wallet_context =
-            get_token_balance(&mut banks_client, &deposit_wallet).await;
-        assert_eq!(
-            deposit_wallet_balance_after_withdraw_tokens_from_partnership_wallet_context,
-            1000000000000000000
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
         );
+
+        transaction.sign(&[&payer], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
     }
 
     #[tokio::test]
     #[should_panic]
-    async fn test_withdraw_tokens_from_marketing_wallet() {
+    async fn test_new_authority_with_wrong_signer() {
         let program_id = id();
         let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
         program_test.set_compute_max_units(500000);
-        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
 
-        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+        let signer = payer.pubkey();
 
         initialize_instruction(&mut banks_client, &payer, recent_blockhash)
             .await
             .unwrap();
-        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
-            .await
-            .unwrap();
 
-        let deposit_wallet =
-            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
-                .await
-                .unwrap();
+        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
+            get_pda_accounts();
 
-        withdraw_tokens_from_marketing_wallet_instruction(
-            &mut banks_client,
-            &payer,
-            recent_blockhash,
-            deposit_wallet,
-        )
-        .await
-        .unwrap();
+        let data = instruction::ChangeAuthority {
+            new_authority: signer,
+        }
+        .data();
+
+        let sub_signer = Keypair::new().pubkey();
+        let accs = ChangeAuthorityContext {
+            contract_state,
+            signer: sub_signer,
+        };
+
+        let mut transaction = Transaction::new_with_payer(
+            &[Instruction::new_with_bytes(
+                program_id,
+                &data,
+                accs.to_account_metas(Some(false)),
+            )],
+            Some(&payer.pubkey()),
+        );
+
+        transaction.sign(&[&payer], recent_blockhash);
+        banks_client.process_transaction(transaction).await.unwrap();
     }
 
+    #[cfg(feature = "metadata")]
     #[tokio::test]
-    async fn test_withdraw_tokens_from_marketing_wallet_after_one_year() {
+    #[should_panic]
+    async fn test_fail_set_the_token_metadata_wrong_signer() {
         let program_id = id();
         let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
         program_test.set_compute_max_units(500000);
-        let mut program_test_context = program_test.start_with_context().await;
-
-        //  Sunday, 5 March 2024 01:01:01
-        let time_in_timestamp = 1677978061;
-        set_time(&mut program_test_context, time_in_timestamp).await;
-
-        let mut banks_client = program_test_context.banks_client.clone();
-        let payer = Keypair::from_base58_string(&program_test_context.payer.to_base58_string());
-        let recent_blockhash = program_test_context.last_blockhash;
 
-        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
 
         initialize_instruction(&mut banks_client, &payer, recent_blockhash)
             .await
             .unwrap();
-        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
-            .await
-            .unwrap();
-
-        //  Thursday, 11 May 2023 01:01:01
-        let time_in_timestamp = 1709600470;
-        set_time(&mut program_test_context, time_in_timestamp).await;
 
-        let deposit_wallet =
-            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
-                .await
-                .unwrap();
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
 
-        let deposit_wallet_balance_before_withdraw_tokens_from_marketing_wallet_context =
-            get_token_balance(&mut banks_client, &deposit_wallet).await;
-        assert_eq!(
-            deposit_wallet_balance_before_withdraw_tokens_from_marketing_wallet_context,
-            0
-        );
+        let sub_signer = Keypair::new();
+        let seed1 = "metadata".as_bytes();
+        let seed2 = &mpl_token_metadata::id().to_bytes();
+        let seed3 = &mint.to_bytes();
+        let (metadata_pda, _) =
+            Pubkey::find_program_address(&[seed1, seed2, seed3], &mpl_token_metadata::id());
 
-        let recent_blockhash = program_test_context
-            .banks_client
-            .get_latest_blockhash()
-            .await
-            .unwrap();
-        withdraw_tokens_from_marketing_wallet_instruction(
+        set_the_token_metadata_instruction(
             &mut banks_client,
-            &payer,
+            &sub_signer,
             recent_blockhash,
-            deposit_wallet,
+            metadata_pda,
+            mpl_token_metadata::id(),
         )
         .await
         .unwrap();
-
-        let deposit_wallet_balance_after_withdraw_tokens_from_marketing_wallet_context =
-            get_token_balance(&mut banks_client, &deposit_wallet).await;
-        assert_eq!(
-            deposit_wallet_balance_after_withdraw_tokens_from_marketing_wallet_context,
-            1
-        );
     }
 
+    #[cfg(feature = "metadata")]
     #[tokio::test]
-    async fn test_withdraw_tokens_from_liquidity_wallet() {
+    async fn test_set_the_token_metadata() {
         let program_id = id();
         let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.add_account(
+            mpl_token_metadata::id(),
+            solana_sdk::account::Account {
+                lamports: Rent::default().minimum_balance(0).max(1),
+                data: std::fs::read("../../mpl_metadata.so").unwrap(),
+                owner: solana_sdk::bpf_loader::id(),
+                executable: true,
+                rent_epoch: 0,
+            },
+        );
         program_test.set_compute_max_units(500000);
 
         let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
-        let token_program = spl_token::id();
-        let signer = payer.pubkey();
 
         initialize_instruction(&mut banks_client, &payer, recent_blockhash)
             .await
             .unwrap();
-        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
-            .await
-            .unwrap();
 
-        let (
-            contract_state,
-            _,
-            vesting_state,
-            _,
-            mint,
-            _,
-            _,
-            _,
-            _,
-            _,
-            _,
-            _,
-            _,
-            _,
-            _,
-            _,
-            liquidity_account,
-            _,
-        ) = get_pda_accounts();
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+        let (metadata_pda, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::id().to_bytes(),
+                &mint.to_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
 
-        let data = instruction::WithdrawTokensFromLiquidityWallet {
-            amount_to_withdraw: 1,
-        }
-        .data();
+        set_the_token_metadata_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            metadata_pda,
+            mpl_token_metadata::id(),
+        )
+        .await
+        .unwrap();
 
-        let deposit_wallet =
-            create_token_account(&mut banks_client, &payer, recent_blockhash, mint)
-                .await
-                .unwrap();
+        let metadata_account = banks_client
+            .get_account(metadata_pda)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(metadata_account.owner, mpl_token_metadata::id());
+        let metadata = Metadata::safe_deserialize(&metadata_account.data).unwrap();
+        assert_eq!(metadata.mint, mint);
+        assert_eq!(metadata.update_authority, mint);
+        // the token metadata program pads the strings with zeros to their maximal lengths
+        assert_eq!(metadata.data.name.trim_end_matches('\0'), "Test");
+        assert_eq!(metadata.data.symbol.trim_end_matches('\0'), "TST");
+        assert_eq!(metadata.data.uri.trim_end_matches('\0'), "https://test.com");
+        assert_eq!(metadata.data.seller_fee_basis_points, 0);
+        assert!(metadata.is_mutable);
+    }
 
-        let deposit_wallet_balance_before_withdraw_tokens_from_liquidity_wallet_context =
-            get_token_balance(&mut banks_client, &deposit_wallet).await;
-        assert_eq!(
-            deposit_wallet_balance_before_withdraw_tokens_from_liquidity_wallet_context,
-            0
+    #[cfg(feature = "metadata")]
+    #[tokio::test]
+    async fn test_update_the_token_metadata() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.add_account(
+            mpl_token_metadata::id(),
+            solana_sdk::account::Account {
+                lamports: Rent::default().minimum_balance(0).max(1),
+                data: std::fs::read("../../mpl_metadata.so").unwrap(),
+                owner: solana_sdk::bpf_loader::id(),
+                executable: true,
+                rent_epoch: 0,
+            },
         );
+        program_test.set_compute_max_units(500000);
 
-        let accs = WithdrawTokensFromLiquidityWalletContext {
-            vesting_state,
-            deposit_wallet,
-            signer,
-            contract_state,
-            liquidity_account,
-            token_program,
-        };
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
 
-        let mut transaction = Transaction::new_with_payer(
-            &[Instruction::new_with_bytes(
-                program_id,
-                &data,
-                accs.to_account_metas(Some(false)),
-            )],
-            Some(&payer.pubkey()),
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+        let (metadata_pda, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::id().to_bytes(),
+                &mint.to_bytes(),
+            ],
+            &mpl_token_metadata::id(),
         );
 
-        transaction.sign(&[&payer], recent_blockhash);
-        banks_client.process_transaction(transaction).await.unwrap();
+        set_the_token_metadata_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            metadata_pda,
+            mpl_token_metadata::id(),
+        )
+        .await
+        .unwrap();
 
-        let deposit_wallet_balance_after_withdraw_tokens_from_liquidity_wallet_context =
-            get_token_balance(&mut banks_client, &deposit_wallet).await;
+        banks_client
+            .process_transaction(update_token_metadata_transaction(
+                &payer,
+                recent_blockhash,
+                ("Leancoin", "LEAN", "https://example.com/leancoin.json"),
+                metadata_pda,
+                mpl_token_metadata::id(),
+            ))
+            .await
+            .unwrap();
+
+        let metadata_account = banks_client
+            .get_account(metadata_pda)
+            .await
+            .unwrap()
+            .unwrap();
+        let metadata = Metadata::safe_deserialize(&metadata_account.data).unwrap();
+        assert_eq!(metadata.update_authority, mint);
+        assert_eq!(metadata.data.name.trim_end_matches('\0'), "Leancoin");
+        assert_eq!(metadata.data.symbol.trim_end_matches('\0'), "LEAN");
         assert_eq!(
-            deposit_wallet_balance_after_withdraw_tokens_from_liquidity_wallet_context,
-            1
+            metadata.data.uri.trim_end_matches('\0'),
+            "https://example.com/leancoin.json"
         );
     }
 
+    #[cfg(feature = "metadata")]
     #[tokio::test]
-    async fn test_new_authority() {
+    async fn test_fail_update_the_token_metadata_before_create() {
         let program_id = id();
         let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
         program_test.set_compute_max_units(500000);
 
         let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
-        let signer = payer.pubkey();
 
         initialize_instruction(&mut banks_client, &payer, recent_blockhash)
             .await
             .unwrap();
 
-        let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
-            get_pda_accounts();
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+        let (metadata_pda, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::id().to_bytes(),
+                &mint.to_bytes(),
+            ],
+            &mpl_token_metadata::id(),
+        );
 
-        let data = instruction::ChangeAuthority {
-            new_authority: signer,
-        }
-        .data();
+        let error = banks_client
+            .process_transaction(update_token_metadata_transaction(
+                &payer,
+                recent_blockhash,
+                ("Leancoin", "LEAN", "https://example.com/leancoin.json"),
+                metadata_pda,
+                mpl_token_metadata::id(),
+            ))
+            .await
+            .unwrap_err()
+            .unwrap();
+        assert_eq!(
+            error,
+            TransactionError::InstructionError(
+                0,
+                InstructionError::Custom(LeancoinError::TokenMetadataNotCreated.into())
+            )
+        );
+    }
 
-        let accs = ChangeAuthorityContext {
-            contract_state,
-            signer,
-        };
+    #[cfg(feature = "metadata")]
+    #[tokio::test]
+    async fn test_fail_set_the_token_metadata_too_long() {
+        let program_id = id();
+        let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.set_compute_max_units(500000);
 
-        let mut transaction = Transaction::new_with_payer(
-            &[Instruction::new_with_bytes(
-                program_id,
-                &data,
-                accs.to_account_metas(Some(false)),
-            )],
-            Some(&payer.pubkey()),
+        let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+        initialize_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
+
+        let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+        let (metadata_pda, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &mpl_token_metadata::id().to_bytes(),
+                &mint.to_bytes(),
+            ],
+            &mpl_token_metadata::id(),
         );
 
-        transaction.sign(&[&payer], recent_blockhash);
-        banks_client.process_transaction(transaction).await.unwrap();
+        let name = "n".repeat(mpl_token_metadata::state::MAX_NAME_LENGTH + 1);
+        let symbol = "s".repeat(mpl_token_metadata::state::MAX_SYMBOL_LENGTH + 1);
+        let uri = "u".repeat(mpl_token_metadata::state::MAX_URI_LENGTH + 1);
+        for (metadata, expected_error) in [
+            (
+                (name.as_str(), "TST", "https://test.com"),
+                LeancoinError::TokenNameTooLong,
+            ),
+            (
+                ("Test", symbol.as_str(), "https://test.com"),
+                LeancoinError::TokenSymbolTooLong,
+            ),
+            (
+                ("Test", "TST", uri.as_str()),
+                LeancoinError::TokenUriTooLong,
+            ),
+        ] {
+            let error = banks_client
+                .process_transaction(set_token_metadata_transaction(
+                    &payer,
+                    recent_blockhash,
+                    metadata,
+                    metadata_pda,
+                    mpl_token_metadata::id(),
+                ))
+                .await
+                .unwrap_err()
+                .unwrap();
+            assert_eq!(
+                error,
+                TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(expected_error.into())
+                )
+            );
+        }
     }
 
+    #[cfg(feature = "metadata")]
     #[tokio::test]
     #[should_panic]
-    async fn test_new_authority_with_wrong_signer() {
+    async fn tes_fail_set_the_token_metadata_wrong_metadata_pda() {
         let program_id = id();
         let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
         program_test.set_compute_max_units(500000);
 
         let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
-        let signer = payer.pubkey();
 
         initialize_instruction(&mut banks_client, &payer, recent_blockhash)
             .await
             .unwrap();
 
+        let seed1 = "metadata".as_bytes();
+        let seed2 = &mpl_token_metadata::id().to_bytes();
+        let seed3 = &Keypair::new().pubkey().to_bytes();
+        let (metadata_pda, _) =
+            Pubkey::find_program_address(&[seed1, seed2, seed3], &mpl_token_metadata::id());
+
+        set_the_token_metadata_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            metadata_pda,
+            mpl_token_metadata::id(),
+        )
+        .await
+        .unwrap();
+    }
+
+    #[cfg(feature = "metadata")]
+    async fn set_metadata_program_id_instruction(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        metadata_program_id: Pubkey,
+    ) -> Result<()> {
+        let program_id = id();
+
         let (contract_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) =
             get_pda_accounts();
 
-        let data = instruction::ChangeAuthority {
-            new_authority: signer,
+        let data = instruction::SetMetadataProgramId {
+            metadata_program_id,
         }
         .data();
 
-        let sub_signer = Keypair::new().pubkey();
-        let accs = ChangeAuthorityContext {
+        let accs = SetMetadataProgramIdContext {
             contract_state,
-            signer: sub_signer,
+            signer: payer.pubkey(),
         };
 
         let mut transaction = Transaction::new_with_payer(
@@ -1558,15 +16764,28 @@ mod tests {
             Some(&payer.pubkey()),
         );
 
-        transaction.sign(&[&payer], recent_blockhash);
+        transaction.sign(&[payer], recent_blockhash);
         banks_client.process_transaction(transaction).await.unwrap();
+
+        Ok(())
     }
 
+    #[cfg(feature = "metadata")]
     #[tokio::test]
-    #[should_panic]
-    async fn test_fail_set_the_token_metadata_wrong_signer() {
+    async fn test_set_the_token_metadata_with_relocated_metadata_program() {
         let program_id = id();
+        let relocated_metadata_program_id = Pubkey::new_unique();
         let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
+        program_test.add_account(
+            relocated_metadata_program_id,
+            solana_sdk::account::Account {
+                lamports: Rent::default().minimum_balance(0).max(1),
+                data: std::fs::read("../../mpl_metadata.so").unwrap(),
+                owner: solana_sdk::bpf_loader::id(),
+                executable: true,
+                rent_epoch: 0,
+            },
+        );
         program_test.set_compute_max_units(500000);
 
         let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
@@ -1574,30 +16793,48 @@ mod tests {
         initialize_instruction(&mut banks_client, &payer, recent_blockhash)
             .await
             .unwrap();
+        set_metadata_program_id_instruction(
+            &mut banks_client,
+            &payer,
+            recent_blockhash,
+            relocated_metadata_program_id,
+        )
+        .await
+        .unwrap();
 
         let (_, _, _, _, mint, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
 
-        let sub_signer = Keypair::new();
-        let seed1 = "metadata".as_bytes();
-        let seed2 = &mpl_token_metadata::id().to_bytes();
-        let seed3 = &mint.to_bytes();
-        let (metadata_pda, _) =
-            Pubkey::find_program_address(&[seed1, seed2, seed3], &mpl_token_metadata::id());
+        let (metadata_pda, _) = Pubkey::find_program_address(
+            &[
+                b"metadata",
+                &relocated_metadata_program_id.to_bytes(),
+                &mint.to_bytes(),
+            ],
+            &relocated_metadata_program_id,
+        );
 
         set_the_token_metadata_instruction(
             &mut banks_client,
-            &sub_signer,
+            &payer,
             recent_blockhash,
-            TokenMetadataAction::Create,
             metadata_pda,
+            relocated_metadata_program_id,
         )
         .await
         .unwrap();
+
+        let metadata_account = banks_client
+            .get_account(metadata_pda)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(metadata_account.owner, relocated_metadata_program_id);
     }
 
+    #[cfg(feature = "metadata")]
     #[tokio::test]
     #[should_panic]
-    async fn tes_fail_set_the_token_metadata_wrong_metadata_pda() {
+    async fn test_fail_set_metadata_program_id_after_import() {
         let program_id = id();
         let mut program_test = ProgramTest::new("leancoin", program_id, processor!(entry));
         program_test.set_compute_max_units(500000);
@@ -1607,19 +16844,15 @@ mod tests {
         initialize_instruction(&mut banks_client, &payer, recent_blockhash)
             .await
             .unwrap();
+        import_ethereum_token_state_instruction(&mut banks_client, &payer, recent_blockhash)
+            .await
+            .unwrap();
 
-        let seed1 = "metadata".as_bytes();
-        let seed2 = &mpl_token_metadata::id().to_bytes();
-        let seed3 = &Keypair::new().pubkey().to_bytes();
-        let (metadata_pda, _) =
-            Pubkey::find_program_address(&[seed1, seed2, seed3], &mpl_token_metadata::id());
-
-        set_the_token_metadata_instruction(
+        set_metadata_program_id_instruction(
             &mut banks_client,
             &payer,
             recent_blockhash,
-            TokenMetadataAction::Create,
-            metadata_pda,
+            Pubkey::new_unique(),
         )
         .await
         .unwrap();
@@ -1630,6 +16863,17 @@ mod tests {
         payer: &Keypair,
         recent_blockhash: Hash,
         mint: Pubkey,
+    ) -> Result<Pubkey> {
+        create_token_account_with_owner(banks_client, payer, recent_blockhash, mint, payer.pubkey())
+            .await
+    }
+
+    async fn create_token_account_with_owner(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+        mint: Pubkey,
+        owner: Pubkey,
     ) -> Result<Pubkey> {
         let rent = Rent::default();
         let new_keypair = Keypair::new();
@@ -1646,7 +16890,7 @@ mod tests {
                     &spl_token::id(),
                     &new_keypair.pubkey(),
                     &mint,
-                    &payer.pubkey(),
+                    &owner,
                 )
                 .unwrap(),
             ],
@@ -1659,6 +16903,39 @@ mod tests {
         Ok(new_keypair.pubkey())
     }
 
+    async fn create_foreign_mint(
+        banks_client: &mut BanksClient,
+        payer: &Keypair,
+        recent_blockhash: Hash,
+    ) -> Pubkey {
+        let mint = Keypair::new();
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                system_instruction::create_account(
+                    &payer.pubkey(),
+                    &mint.pubkey(),
+                    Rent::default().minimum_balance(spl_token::state::Mint::LEN),
+                    spl_token::state::Mint::LEN.try_into().unwrap(),
+                    &spl_token::id(),
+                ),
+                spl_token::instruction::initialize_mint(
+                    &spl_token::id(),
+                    &mint.pubkey(),
+                    &payer.pubkey(),
+                    None,
+                    9,
+                )
+                .unwrap(),
+            ],
+            Some(&payer.pubkey()),
+            &[payer, &mint],
+            recent_blockhash,
+        );
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        mint.pubkey()
+    }
+
     fn get_accounts_to_mapping() -> Vec<AccountInfoFromEthereum> {
         let (
             _,
@@ -1681,12 +16958,13 @@ mod tests {
             _,
         ) = get_pda_accounts();
 
-        let burn_balance = 1800000000000000000; // 18% of total supply
-        let community_balance = 1000000000000000000; // 10% of total supply
-        let partnership_balance = 2000000000000000000; // 20% of total supply
-        let marketing_balance = 1500000000000000000; // 15% of total supply
-        let liquidity_balance = 1000000000000000000; // 10% of total supply
-        let swap_balance = 1230000000000000000; // 12.3% of total supply
+        // balances are expressed with 18 decimals like on Ethereum
+        let burn_balance = 1800000000000000000000000000; // 18% of total supply
+        let community_balance = 1000000000000000000000000000; // 10% of total supply
+        let partnership_balance = 2000000000000000000000000000; // 20% of total supply
+        let marketing_balance = 1500000000000000000000000000; // 15% of total supply
+        let liquidity_balance = 1000000000000000000000000000; // 10% of total supply
+        let swap_balance = 1230000000000000000000000000; // 12.3% of total supply
 
         vec![
             AccountInfoFromEthereum {
@@ -1784,6 +17062,160 @@ mod tests {
         )
     }
 
+    fn get_staking_pda_accounts() -> (Pubkey, Pubkey, Pubkey) {
+        let program_id = id();
+
+        let (staking_state, _) = Pubkey::find_program_address(&[b"staking_state"], &program_id);
+        let (stake_vault, _) = Pubkey::find_program_address(&[b"stake_vault"], &program_id);
+        let (reward_vault, _) = Pubkey::find_program_address(&[b"reward_vault"], &program_id);
+
+        (staking_state, stake_vault, reward_vault)
+    }
+
+    fn get_stake_account(staker: &Pubkey) -> Pubkey {
+        let (stake_account, _) =
+            Pubkey::find_program_address(&[b"stake_account", staker.as_ref()], &id());
+
+        stake_account
+    }
+
+    fn get_otc_deal_accounts(partner: &Pubkey) -> (Pubkey, Pubkey) {
+        let program_id = id();
+
+        let (otc_deal, _) =
+            Pubkey::find_program_address(&[b"otc_deal", partner.as_ref()], &program_id);
+        let (otc_vault, _) =
+            Pubkey::find_program_address(&[b"otc_vault", partner.as_ref()], &program_id);
+
+        (otc_deal, otc_vault)
+    }
+
+    fn get_donor_account(donor: &Pubkey) -> Pubkey {
+        let (donor_account, _) =
+            Pubkey::find_program_address(&[b"donor_account", donor.as_ref()], &id());
+
+        donor_account
+    }
+
+    fn get_burn_history_account() -> Pubkey {
+        let (burn_history, _) = Pubkey::find_program_address(&[b"burn_history"], &id());
+
+        burn_history
+    }
+
+    fn get_labels_account() -> Pubkey {
+        let (labels, _) = Pubkey::find_program_address(&[b"labels"], &id());
+
+        labels
+    }
+
+    fn get_withdrawal_receipt_account(wallet_kind: WalletKind, index: u64) -> Pubkey {
+        let (withdrawal_receipt, _) = Pubkey::find_program_address(
+            &[
+                WITHDRAWAL_RECEIPT_SEED.as_bytes(),
+                &[wallet_kind as u8],
+                &index.to_le_bytes(),
+            ],
+            &id(),
+        );
+
+        withdrawal_receipt
+    }
+
+    async fn get_next_withdrawal_receipt_account(
+        banks_client: &mut BanksClient,
+        wallet_kind: WalletKind,
+    ) -> Pubkey {
+        let (_, _, vesting_state, _, _, _, _, _, _, _, _, _, _, _, _, _, _, _) = get_pda_accounts();
+        let index = banks_client
+            .get_account(vesting_state)
+            .await
+            .unwrap()
+            .map(|vesting_state_info| {
+                VestingState::try_deserialize_unchecked(&mut vesting_state_info.data.as_slice())
+                    .unwrap()
+                    .next_receipt_index[wallet_kind.index()]
+            })
+            .unwrap_or_default();
+
+        get_withdrawal_receipt_account(wallet_kind, index)
+    }
+
+    async fn get_next_withdrawal_receipt_accounts(
+        banks_client: &mut BanksClient,
+    ) -> [Pubkey; VESTED_WALLETS_COUNT] {
+        let mut withdrawal_receipts = [Pubkey::default(); VESTED_WALLETS_COUNT];
+        for wallet_kind in WalletKind::ALL {
+            withdrawal_receipts[wallet_kind.index()] =
+                get_next_withdrawal_receipt_account(banks_client, wallet_kind).await;
+        }
+
+        withdrawal_receipts
+    }
+
+    fn get_withdrawal_intent_account(idempotency_key: &[u8; 16]) -> Pubkey {
+        let (withdrawal_intent, _) =
+            Pubkey::find_program_address(&[b"withdrawal_intent", idempotency_key], &id());
+
+        withdrawal_intent
+    }
+
+    fn get_wallet_delegation_account(wallet_kind: WalletKind) -> Pubkey {
+        let (wallet_delegation, _) = Pubkey::find_program_address(
+            &[b"wallet_delegation", wallet_kind.seed().as_bytes()],
+            &id(),
+        );
+
+        wallet_delegation
+    }
+
+    fn add_funded_keypair(program_test: &mut ProgramTest) -> Keypair {
+        let keypair = Keypair::new();
+        program_test.add_account(
+            keypair.pubkey(),
+            solana_sdk::account::Account {
+                lamports: 1_000_000_000,
+                data: vec![],
+                owner: system_program::ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
+        keypair
+    }
+
+    fn add_token_account(
+        program_test: &mut ProgramTest,
+        mint: Pubkey,
+        owner: Pubkey,
+        amount: u64,
+        state: AccountState,
+    ) -> Pubkey {
+        let token_account = Pubkey::new_unique();
+        let mut data = vec![0; Account::LEN];
+        Account {
+            mint,
+            owner,
+            amount,
+            state,
+            ..Account::default()
+        }
+        .pack_into_slice(&mut data);
+        program_test.add_account(
+            token_account,
+            solana_sdk::account::Account {
+                lamports: Rent::default().minimum_balance(Account::LEN),
+                data,
+                owner: spl_token::id(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
+        token_account
+    }
+
     async fn set_time(ctx: &mut ProgramTestContext, time: i64) {
         let clock_sysvar: Clock = ctx.banks_client.get_sysvar().await.unwrap();
         let mut new_clock = clock_sysvar.clone();
@@ -1,14 +1,47 @@
-use anchor_lang::prelude::{require, AccountInfo, Context, CpiContext, Result, ToAccountInfo};
-use anchor_spl::token::{self, Burn, MintTo, Transfer};
-
-use crate::account::ContractState;
-use crate::context::VestedWalletContext;
+use anchor_lang::prelude::{
+    emit, require, require_keys_eq, Account, AccountDeserialize, AccountInfo, AccountLoader,
+    AnchorDeserialize, Clock, Context, CpiContext, Key, Pubkey, Rent, Result, SolanaSysvar,
+    ToAccountInfo,
+};
+use anchor_lang::solana_program::{
+    hash::hash, program_error::ProgramError, program_option::COption,
+    sysvar::instructions::get_instruction_relative,
+};
+use anchor_lang::{__private::bytemuck, error::ErrorCode, system_program, Discriminator, Space};
+use anchor_spl::token::{
+    self, spl_token::state::AccountState, Approve, Burn, CloseAccount, Mint, MintTo, Revoke,
+    TokenAccount, Transfer,
+};
+
+use crate::account::{
+    Attestation, BurnRecord, ContractState, LegacyContractState, LegacyVestingState, StakeAccount,
+    VestingSchedule, VestingState, VestingStateV1, WalletDelegation, WithdrawalIntent,
+    WithdrawalReceipt, VESTING_STATE_V2_SPACE, VESTING_STATE_V3_SPACE, VESTING_STATE_V4_SPACE,
+    VESTING_STATE_V5_SPACE, VESTING_STATE_V6_SPACE, VESTING_STATE_V7_SPACE,
+};
+use crate::context::{
+    BurningContext, VestedWalletContext, VestedWithdrawalContext, WithdrawAllUnlockedContext,
+};
 use crate::error_codes::LeancoinError;
-
-use crate::{MINT_SEED, PROGRAM_ACCOUNT_SEED};
-
-/// DAYS_PER_MONTH is an array of integers that contains the number of days for each month, excluding December
-const DAYS_PER_MONTH: [i64; 11] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30];
+use crate::event::{AllUnlockedTokensWithdrawn, BurnExecuted, VestedTokensWithdrawn};
+
+use crate::{
+    BurnDetails, LabeledAccountKind, MigrationSource, MonthRow, Role, SupplyProjection, WalletKind,
+    WalletVestingStatus, BURNING_ACCOUNT_SEED, BURN_RATE_DENOMINATOR, LABEL_LENGTH,
+    MAX_BURN_CALLER_REWARD_BPS, MAX_BURN_WINDOW_DAYS, MAX_CATCH_UP_BURN_MONTHS,
+    MAX_LATE_BURN_WINDOW_LAST_DAY, MAX_MINT_DECIMALS, MAX_PROJECTED_MONTHS,
+    MAX_WITHDRAWAL_GRACE_PERIOD, MINT_SEED, OTC_VAULT_SEED, PROGRAM_ACCOUNT_SEED,
+    REWARD_PER_TOKEN_PRECISION, VESTED_WALLETS_COUNT, WITHDRAW_ALL_UNLOCKED,
+};
+
+pub use leancoin_core::{
+    calculate_unlocked_amount_community_wallet, calculate_unlocked_amount_liquidity_wallet,
+    calculate_unlocked_amount_marketing_wallet, calculate_unlocked_amount_partnership_wallet,
+    days_in_month, DateTime, UnlockSchedule, VestingCurve, MAX_MONTH_DIFFERENCE,
+};
+
+/// WHOLE_BALANCE is the amount passed to `transfer_tokens` to transfer the whole current balance of the source account
+pub const WHOLE_BALANCE: u64 = u64::MAX;
 
 /// Transfers tokens between two accounts.
 ///
@@ -19,10 +52,10 @@ const DAYS_PER_MONTH: [i64; 11] = [31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30];
 /// * `program_account` - the program account
 /// * `program_account_seed` - the seed of the program account
 /// * `program_account_nonce` - the nonce of the program account
-/// * `amount` - the amount of tokens to transfer
+/// * `amount` - the amount of tokens to transfer, `WHOLE_BALANCE` transfers the whole current balance of the source account
 ///
 /// ### Returns
-/// The result of the transfer
+/// The amount of transferred tokens. Transferring zero tokens, e.g. the whole balance of an empty account, is a no-op returning 0.
 pub fn transfer_tokens<'a>(
     authority: AccountInfo<'a>,
     to: AccountInfo<'a>,
@@ -30,7 +63,12 @@ pub fn transfer_tokens<'a>(
     program_account_seed: &str,
     program_account_nonce: u8,
     amount: u64,
-) -> Result<()> {
+) -> Result<u64> {
+    let amount = resolve_transfer_amount(&authority, amount)?;
+    if amount == 0 {
+        return Ok(0);
+    }
+
     let seeds = &[program_account_seed.as_bytes(), &[program_account_nonce]];
     let signer_seeds = &[&seeds[..]];
 
@@ -49,10 +87,72 @@ pub fn transfer_tokens<'a>(
         signer_seeds,
     );
 
+    token::transfer(cpi_ctx, amount)?;
+
+    Ok(amount)
+}
+
+/// Resolves the amount of tokens transferred by `transfer_tokens`.
+/// `WHOLE_BALANCE` is replaced by the balance read from the data of the source account,
+/// so it includes the tokens moved by the preceding CPIs of the same instruction.
+///
+/// ### Arguments
+///
+/// * `source` - the source token account
+/// * `amount` - the requested amount of tokens or `WHOLE_BALANCE`
+///
+/// ### Returns
+/// The amount of tokens to transfer or an error if the whole balance is requested and the source is not a token account.
+pub fn resolve_transfer_amount(source: &AccountInfo, amount: u64) -> Result<u64> {
+    if amount != WHOLE_BALANCE {
+        return Ok(amount);
+    }
+
+    let data = source.try_borrow_data()?;
+    Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+}
+
+/// Transfers tokens escrowed in the OTC vault of the given partner.
+///
+/// ### Arguments
+///
+/// * `otc_vault` - the OTC vault of the partner, it is also the authority of the transfer
+/// * `to` - the destination account
+/// * `token_program` - the Solana token program account
+/// * `partner` - the partner of the OTC deal used to derive the vault
+/// * `otc_vault_nonce` - the nonce of the OTC vault
+/// * `amount` - the amount of tokens to transfer
+///
+/// ### Returns
+/// The result of the transfer
+pub fn transfer_tokens_from_otc_vault<'a>(
+    otc_vault: AccountInfo<'a>,
+    to: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    partner: &Pubkey,
+    otc_vault_nonce: u8,
+    amount: u64,
+) -> Result<()> {
+    let seeds = &[
+        OTC_VAULT_SEED.as_bytes(),
+        partner.as_ref(),
+        &[otc_vault_nonce],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: otc_vault.to_account_info(),
+        to,
+        authority: otc_vault,
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer_seeds);
+
     token::transfer(cpi_ctx, amount)
 }
 
 /// Mints tokens to given account.
+/// The minting fails if the supply of the mint would exceed the maximal supply.
 ///
 /// ### Arguments
 ///
@@ -61,23 +161,27 @@ pub fn transfer_tokens<'a>(
 /// * `authority` - the authority that is used to mint the tokens
 /// * `program_account` - the program account
 /// * `mint_nonce` - the nonce of the mint account
+/// * `max_supply` - the maximal supply of the mint
 /// * `amount` - the amount of tokens to transfer
 ///
 /// ### Returns
 /// The result of the minting
 pub fn mint_tokens<'a>(
-    mint: AccountInfo<'a>,
+    mint: &Account<'a, Mint>,
     to: AccountInfo<'a>,
     authority: AccountInfo<'a>,
     program_account: AccountInfo<'a>,
     mint_nonce: u8,
+    max_supply: u64,
     amount: u64,
 ) -> Result<()> {
+    within_max_supply(mint.supply, amount, max_supply)?;
+
     let seeds = &[MINT_SEED.as_bytes(), &[mint_nonce]];
     let signer_seeds = &[&seeds[..]];
 
     let cpi_accounts = MintTo {
-        mint,
+        mint: mint.to_account_info(),
         to,
         authority,
     };
@@ -87,6 +191,28 @@ pub fn mint_tokens<'a>(
     token::mint_to(cpi_ctx, amount)
 }
 
+/// Checks if minting the amount of tokens keeps the supply within the maximal supply.
+///
+/// ### Arguments
+///
+/// * `supply` - the current supply of the mint
+/// * `amount` - the amount of tokens to mint
+/// * `max_supply` - the maximal supply of the mint
+///
+/// ### Returns
+/// Ok if the supply after minting does not exceed the maximal supply, otherwise an error.
+pub fn within_max_supply(supply: u64, amount: u64, max_supply: u64) -> Result<()> {
+    let supply_after_minting = supply
+        .checked_add(amount)
+        .ok_or(LeancoinError::MaxSupplyExceeded)?;
+    require!(
+        supply_after_minting <= max_supply,
+        LeancoinError::MaxSupplyExceeded
+    );
+
+    Ok(())
+}
+
 /// Removes tokens from given account by burning them.
 ///
 /// ### Arguments
@@ -122,527 +248,2938 @@ pub fn burn_tokens<'a>(
     token::burn(cpi_ctx, amount)
 }
 
-/// Asserts that the signer is authorized to perform the action, i.e. if the signer is contract's owner.
+/// Closes the token account of a vested wallet and transfers its rent to the destination.
 ///
 /// ### Arguments
 ///
-/// * `state` - the current state of the contract
-/// * `signer` - the account which is the signer of the current transaction
+/// * `account` - the token account to close, it is also the authority of the account
+/// * `destination` - the account receiving the rent of the closed account
+/// * `token_program` - the Solana token program account
+/// * `account_seed` - the seed of the token account
+/// * `account_nonce` - the nonce of the token account
 ///
 /// ### Returns
-/// An error if the signer is not an owner of the contract, otherwise a successful result.
-pub fn valid_owner(state: &ContractState, signer: &AccountInfo) -> Result<()> {
-    require!(signer.key.eq(&state.authority), LeancoinError::Unauthorized);
+/// The result of the closing
+pub fn close_token_account<'a>(
+    account: AccountInfo<'a>,
+    destination: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    account_seed: &str,
+    account_nonce: u8,
+) -> Result<()> {
+    let seeds = &[account_seed.as_bytes(), &[account_nonce]];
+    let signer_seeds = &[&seeds[..]];
 
-    Ok(())
+    let cpi_accounts = CloseAccount {
+        account: account.clone(),
+        destination,
+        authority: account,
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer_seeds);
+
+    token::close_account(cpi_ctx)
 }
 
-/// Asserts that the given account is a signer.
+/// Approves the delegate allowed to transfer tokens out of the token account of a vested wallet.
 ///
 /// ### Arguments
 ///
-/// * `signer` - the account which is supposed to be a signer
+/// * `account` - the token account of the vested wallet, it is also the authority of the account
+/// * `delegate` - the account approved as the delegate
+/// * `token_program` - the Solana token program account
+/// * `account_seed` - the seed of the token account
+/// * `account_nonce` - the nonce of the token account
+/// * `allowance` - the amount of tokens the delegate is allowed to transfer
 ///
 /// ### Returns
-/// An error if the account is not a signer, otherwise a successful result.
-pub fn valid_signer(signer: &AccountInfo) -> Result<()> {
-    require!(signer.is_signer, LeancoinError::Unauthorized);
+/// The result of the approval
+pub fn approve_delegate<'a>(
+    account: AccountInfo<'a>,
+    delegate: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    account_seed: &str,
+    account_nonce: u8,
+    allowance: u64,
+) -> Result<()> {
+    let seeds = &[account_seed.as_bytes(), &[account_nonce]];
+    let signer_seeds = &[&seeds[..]];
 
-    Ok(())
+    let cpi_accounts = Approve {
+        to: account.clone(),
+        delegate,
+        authority: account,
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer_seeds);
+
+    token::approve(cpi_ctx, allowance)
 }
 
-/// Asserts that the import of Ethereum token state has not yet been performed.
+/// Revokes the delegate of the token account of a vested wallet.
 ///
 /// ### Arguments
 ///
-/// * `state` - the current state of the contract
+/// * `account` - the token account of the vested wallet, it is also the authority of the account
+/// * `token_program` - the Solana token program account
+/// * `account_seed` - the seed of the token account
+/// * `account_nonce` - the nonce of the token account
 ///
 /// ### Returns
-/// An error if the import has already been performed, otherwise a successful result.
-pub fn ethereum_token_state_mapping_not_performed_yet(state: &ContractState) -> Result<()> {
-    require!(
-        !state.import_ethereum_token_state_already_performed,
-        LeancoinError::EthereumTokenStateMappingAlreadyPerformed
-    );
+/// The result of the revocation
+pub fn revoke_delegate<'a>(
+    account: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    account_seed: &str,
+    account_nonce: u8,
+) -> Result<()> {
+    let seeds = &[account_seed.as_bytes(), &[account_nonce]];
+    let signer_seeds = &[&seeds[..]];
 
-    Ok(())
-}
+    let cpi_accounts = Revoke {
+        source: account.clone(),
+        authority: account,
+    };
 
-/// Date time struct for the timestamp parsing
-pub struct DateTime {
-    pub year: i64,
-    pub month: u8,
-    pub days: u8,
+    let cpi_ctx = CpiContext::new_with_signer(token_program, cpi_accounts, signer_seeds);
+
+    token::revoke(cpi_ctx)
 }
 
-/// Accepts the timestamp as an integer (i64) and returns DateTime struct
+/// Transfers lamports from the payer to the account so the account becomes rent-exempt.
 ///
 /// ### Arguments
 ///
-/// * `timestamp` - the timestamp as a signed integer
+/// * `account` - the account which must be rent-exempt
+/// * `payer` - the signer paying the shortfall
+/// * `system_program` - the Solana system program account
 ///
 /// ### Returns
-/// DateTime struct created from the timestamp
-pub fn parse_timestamp(timestamp: i64) -> Result<DateTime> {
-    require!(timestamp >= 0, LeancoinError::InvalidTimestamp);
-
-    let mut remaining_days = timestamp / (60 * 60 * 24);
-    let mut year = 1970;
-    let mut month = 1;
-
-    while remaining_days >= 365 {
-        if is_leap_year(year) {
-            if remaining_days >= 366 {
-                remaining_days -= 366;
-                year += 1;
-            } else {
-                break;
-            }
-        } else {
-            remaining_days -= 365;
-            year += 1;
-        }
-    }
-
-    let leap_year = is_leap_year(year);
-    while month < 12 {
-        let month_length = if month == 2 && leap_year {
-            29
-        } else {
-            DAYS_PER_MONTH[month - 1]
+/// The transferred shortfall, zero if the account was already rent-exempt.
+pub fn transfer_rent_shortfall<'a>(
+    account: AccountInfo<'a>,
+    payer: AccountInfo<'a>,
+    system_program: AccountInfo<'a>,
+) -> Result<u64> {
+    let shortfall = Rent::get()?
+        .minimum_balance(account.data_len())
+        .saturating_sub(account.lamports());
+
+    if shortfall > 0 {
+        let cpi_accounts = system_program::Transfer {
+            from: payer,
+            to: account,
         };
-
-        if remaining_days < month_length {
-            break;
-        }
-        remaining_days -= month_length;
-        month += 1;
+        system_program::transfer(CpiContext::new(system_program, cpi_accounts), shortfall)?;
     }
-    remaining_days += 1;
 
-    let month: u8 = month.try_into().unwrap();
-    let days: u8 = remaining_days.try_into().unwrap();
-
-    Ok(DateTime { year, month, days })
+    Ok(shortfall)
 }
 
-/// Checks whether the given year is a leap year.
-///
-/// A year is a leap year if it is divisible by 4 and not by 100, or if it is
-/// divisible by 400. For example, 1992 and 1996 are leap years, but 1900 is not.
+/// Reads the vesting state stored in one of the previous layouts and converts it to the current layout.
+/// The previous layouts are the borsh layout used before `VestingState` became zero-copy,
+/// the zero-copy layout with separate fields for every wallet, the layout with per-wallet states without the swap wallet balance
+/// and the layouts without the vesting schedules, the beneficiaries, the withdrawal history, the receipt indexes or the revoked amounts,
+/// they are distinguished by the length of the account.
+/// The swap wallet balance, the beneficiaries, the withdrawal history, the receipt indexes and the revoked amounts missing in the layouts are zeroed
+/// and the vesting schedules are set to the default ones.
 ///
 /// ### Arguments
 ///
-/// * `year` - the year to be checked
+/// * `vesting_state` - the vesting state account
 ///
 /// ### Returns
-/// True if the provided year is a leap year, false otherwise.
-fn is_leap_year(year: i64) -> bool {
-    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+/// The converted vesting state or an error if the account is not in any of the previous layouts, e.g. because it has already been migrated.
+pub fn read_outdated_vesting_state(vesting_state: &AccountInfo) -> Result<VestingState> {
+    let data = vesting_state.try_borrow_data()?;
+    let discriminator_len = VestingState::discriminator().len();
+
+    require!(
+        data.len() >= discriminator_len
+            && data[..discriminator_len] == VestingState::discriminator(),
+        LeancoinError::VestingStateAlreadyMigrated
+    );
+
+    let state_data = &data[discriminator_len..];
+    if state_data.len() == LegacyVestingState::INIT_SPACE {
+        LegacyVestingState::deserialize(&mut &state_data[..])
+            .map(VestingState::from)
+            .map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+    } else if state_data.len() == std::mem::size_of::<VestingStateV1>() {
+        Ok(VestingState::from(bytemuck::pod_read_unaligned::<
+            VestingStateV1,
+        >(state_data)))
+    } else if state_data.len() == VESTING_STATE_V2_SPACE
+        || state_data.len() == VESTING_STATE_V3_SPACE
+        || state_data.len() == VESTING_STATE_V4_SPACE
+        || state_data.len() == VESTING_STATE_V5_SPACE
+        || state_data.len() == VESTING_STATE_V6_SPACE
+        || state_data.len() == VESTING_STATE_V7_SPACE
+    {
+        let mut current_data = state_data.to_vec();
+        current_data.resize(std::mem::size_of::<VestingState>(), 0);
+
+        let mut current_vesting_state = bytemuck::pod_read_unaligned::<VestingState>(&current_data);
+        if state_data.len() < VESTING_STATE_V4_SPACE {
+            current_vesting_state.schedules = VestingSchedule::defaults();
+        }
+        Ok(current_vesting_state)
+    } else {
+        Err(LeancoinError::VestingStateAlreadyMigrated.into())
+    }
 }
 
-/// Calculates the number of months between two timestamps.
-/// Only month numbers are compared, days are ignored.
-///
-/// Examples:
-/// - when start date is 01/04/2023 and end date is 01/05/2023, then the result is 1
-/// - when start date is 27/04/2023 and end date is 01/05/2023, then the result is 1
-/// - when start date is 01/04/2023 and end date is 01/06/2023, then the result is 2
-/// - when start date is 27/04/2023 and end date is 01/06/2023, then the result is 2
-/// - when start date is 27/04/2023 and end date is 01/12/2023, then the result is 8
-/// - when start date is 27/04/2023 and end date is 01/04/2024, then the result is 12
+/// Reads the contract state stored in the deployed borsh layout used before the versioned layout with fixed offsets
+/// and converts it to the current layout. The layout is detected by the length of the account.
+/// The fields missing in the deployed layout are set to their defaults, see `From<LegacyContractState>`,
+/// and the mint address is derived from the stored mint nonce.
 ///
 /// ### Arguments
 ///
-/// * `start` - the earlier timestamp
-/// * `end` - the later timestamp
+/// * `contract_state` - the contract state account
 ///
 /// ### Returns
-/// Number of months between two timestamps, ignoring days.
-pub fn calculate_month_difference(start: i64, end: i64) -> Result<u64> {
-    require!(end >= start, LeancoinError::EndTimeMustBeLaterThanStartTime);
-    let start = parse_timestamp(start)?;
-    let end = parse_timestamp(end)?;
+/// The converted contract state or an error if the account is not in the deployed layout, e.g. because it has already been migrated.
+pub fn read_outdated_contract_state(contract_state: &AccountInfo) -> Result<ContractState> {
+    let data = contract_state.try_borrow_data()?;
+    let discriminator_len = ContractState::discriminator().len();
 
-    let end_month: i64 = end.month.try_into().unwrap();
-    let start_month: i64 = start.month.try_into().unwrap();
+    require!(
+        data.len() >= discriminator_len
+            && data[..discriminator_len] == ContractState::discriminator(),
+        LeancoinError::ContractStateAlreadyMigrated
+    );
+    let state_data = &data[discriminator_len..];
 
-    let month_difference = end_month - start_month;
-    let months = (end.year - start.year) * 12 + month_difference;
-    let months = months.try_into().unwrap();
+    require!(
+        state_data.len() == LegacyContractState::INIT_SPACE,
+        LeancoinError::ContractStateAlreadyMigrated
+    );
+    let mut contract_state = LegacyContractState::deserialize(&mut &state_data[..])
+        .map(ContractState::from)
+        .map_err(|_| ErrorCode::AccountDidNotDeserialize)?;
+    contract_state.mint = create_program_address(MINT_SEED, contract_state.mint_nonce)?;
 
-    Ok(months)
+    Ok(contract_state)
 }
 
-/// Calculates the amount of unlocked tokens for the partnership wallet.
-/// 50% of the initial wallet's balance is unlocked after 1 month.
-/// The remaining part is unlocked after 2 months.
+/// Asserts that the signer is authorized to perform the action, i.e. if the signer is contract's owner.
+/// No signer is the owner once the authority has been renounced.
 ///
 /// ### Arguments
 ///
-/// * `vesting_start_account_balance` - the initial balance of the partnership wallet after Ethereum token state import
-/// * `months_since_vesting_start` - number of full months since the Ethereum token state import
+/// * `state` - the current state of the contract
+/// * `signer` - the account which is the signer of the current transaction
 ///
 /// ### Returns
-/// The amount of unlocked tokens for partnership wallet
-pub fn calculate_unlocked_amount_partnership_wallet(
-    vesting_start_account_balance: u64,
-    months_since_vesting_start: u64,
-) -> u64 {
-    match months_since_vesting_start {
-        0 => 0,
-        1 => vesting_start_account_balance / 2,
-        _ => vesting_start_account_balance,
-    }
+/// An error if the authority has been renounced or the signer is not an owner of the contract, otherwise a successful result.
+pub fn valid_owner(state: &ContractState, signer: &AccountInfo) -> Result<()> {
+    require!(!state.renounced, LeancoinError::AuthorityRenounced);
+    require!(signer.key.eq(&state.authority), LeancoinError::Unauthorized);
+
+    Ok(())
 }
 
-/// Calculates the amount of unlocked tokens for the marketing wallet.
-/// 40% of the initial wallet's balance is unlocked after 1 year.
-/// Starting from the 13th month, 5% of the initial wallet's balance is unlocked every month.
+/// Asserts that the signer is allowed to run the instructions of the role, i.e. if the signer is contract's owner or the authority assigned to the role.
+///
+/// ### Arguments
+///
+/// * `state` - the current state of the contract
+/// * `role` - the role of the instruction
+/// * `signer` - the account which is the signer of the current transaction
 ///
-/// If the calculated amount is below 1, then 1 is returned as the calculated amount.
+/// ### Returns
+/// An error if the signer is neither an owner of the contract nor the authority of the role, otherwise a successful result.
+pub fn valid_role(state: &ContractState, role: Role, signer: &AccountInfo) -> Result<()> {
+    require!(
+        (!state.renounced && signer.key.eq(&state.authority))
+            || (*signer.key != Pubkey::default() && signer.key.eq(&state.role_authority(role))),
+        LeancoinError::Unauthorized
+    );
+
+    Ok(())
+}
+
+/// Asserts that the signer is allowed to withdraw from the vested wallet,
+/// i.e. if the signer is contract's owner, the withdraw authority or the beneficiary of the wallet.
 ///
 /// ### Arguments
 ///
-/// * `vesting_start_account_balance` - the initial balance of the marketing wallet after Ethereum token state import
-/// * `months_since_vesting_start` - number of full months since the Ethereum token state import
+/// * `state` - the current state of the contract
+/// * `vesting_state` - the vesting state account, it must not be mutably borrowed
+/// * `wallet_kind` - the vested wallet to withdraw from
+/// * `signer` - the account which is the signer of the current transaction
 ///
 /// ### Returns
-/// The amount of unlocked tokens for marketing wallet but not less than 1
-pub fn calculate_unlocked_amount_marketing_wallet(
-    vesting_start_account_balance: u64,
-    months_since_vesting_start: u64,
-) -> Result<u64> {
-    if months_since_vesting_start < 12 {
-        return Ok(0);
+/// An error if the signer is neither an owner of the contract, the withdraw authority nor the beneficiary of the wallet,
+/// otherwise a successful result.
+pub fn valid_withdrawer(
+    state: &ContractState,
+    vesting_state: &AccountLoader<VestingState>,
+    wallet_kind: WalletKind,
+    signer: &AccountInfo,
+) -> Result<()> {
+    if valid_role(state, Role::Withdraw, signer).is_ok() {
+        return Ok(());
     }
 
-    let (vesting_start_account_balance, months_since_vesting_start) = (
-        u128::from(vesting_start_account_balance),
-        u128::from(months_since_vesting_start),
+    let beneficiary = vesting_state.load()?.beneficiaries[wallet_kind.index()];
+    require!(
+        *signer.key != Pubkey::default() && signer.key.eq(&beneficiary),
+        LeancoinError::Unauthorized
     );
 
-    let amount_unlocked = (vesting_start_account_balance * 40
-        + (months_since_vesting_start - 12) * (vesting_start_account_balance * 5))
-        / 100;
-
-    Ok(u64::try_from(amount_unlocked.max(1).min(vesting_start_account_balance)).unwrap())
+    Ok(())
 }
 
-/// Calculates the amount of unlocked tokens for the community wallet.
-/// 2.5% of the initial wallet's balance is unlocked immediately.
-/// Additional 2.5% of the initial wallet's balance is unlocked every month.
-/// So after 2 months: 7.5% of the initial balance is unlocked, after 3 months: 10%, after 4 months: 12.5% etc.
-///
-/// If the calculated amount is below 1, then 1 is returned as the calculated amount.
+/// Asserts that the signer is allowed to run the automation instructions, i.e. if the signer is contract's owner or a registered keeper.
+/// Once the authority has been renounced the keepers can no longer be managed, so any signer can run them.
 ///
 /// ### Arguments
 ///
-/// * `vesting_start_account_balance` - the initial balance of the community wallet after Ethereum token state import
-/// * `months_since_vesting_start` - number of full months since the Ethereum token state import
+/// * `state` - the current state of the contract
+/// * `signer` - the account which is the signer of the current transaction
 ///
 /// ### Returns
-/// The amount of unlocked tokens for community wallet but not less than 1
-pub fn calculate_unlocked_amount_community_wallet(
-    vesting_start_account_balance: u64,
-    months_since_vesting_start: u64,
-) -> u64 {
-    let (vesting_start_account_balance, months_since_vesting_start) = (
-        u128::from(vesting_start_account_balance),
-        u128::from(months_since_vesting_start),
+/// An error if the authority has not been renounced and the signer is neither an owner of the contract nor a registered keeper,
+/// otherwise a successful result.
+pub fn valid_keeper(state: &ContractState, signer: &AccountInfo) -> Result<()> {
+    require!(
+        state.renounced
+            || signer.key.eq(&state.authority)
+            || (*signer.key != Pubkey::default() && state.keepers.contains(signer.key)),
+        LeancoinError::Unauthorized
     );
-    let amount_unlocked = vesting_start_account_balance * (months_since_vesting_start + 1) / 40;
 
-    u64::try_from(amount_unlocked.max(1).min(vesting_start_account_balance)).unwrap()
+    Ok(())
 }
 
-/// Calculates the amount of unlocked tokens for the liquidity wallet.
-/// 50% of the initial wallet's balance is unlocked immediately.
-/// The remaining part is unlocked after 1 year.
+/// Asserts that the signer is allowed to run the burn, i.e. if the signer is allowed to run the automation instructions
+/// (see `valid_keeper`) or is the burn authority.
 ///
 /// ### Arguments
 ///
-/// * `vesting_start_account_balance` - the initial balance of the liquidity wallet after Ethereum token state import
-/// * `months_since_vesting_start` - number of full months since the Ethereum token state import
+/// * `state` - the current state of the contract
+/// * `signer` - the account which is the signer of the current transaction
 ///
 /// ### Returns
-/// The amount of unlocked tokens for liquidity wallet
-pub fn calculate_unlocked_amount_liquidity_wallet(
-    vesting_start_account_balance: u64,
-    months_since_vesting_start: u64,
-) -> u64 {
-    match months_since_vesting_start {
-        months if months >= 12 => vesting_start_account_balance,
-        _ => vesting_start_account_balance / 2,
+/// An error if the signer is neither allowed to run the automation instructions nor the burn authority, otherwise a successful result.
+pub fn valid_burner(state: &ContractState, signer: &AccountInfo) -> Result<()> {
+    if valid_keeper(state, signer).is_ok() {
+        return Ok(());
     }
+
+    valid_role(state, Role::Burn, signer)
 }
 
-/// Transfers tokens from one of the wallets affected by vesting mechanism: community, partnership, marketing or liquidity wallet.
-/// The destination for the transfer is deposit wallet which is not managed by this contract.
-///
-/// The function also validates if the amount of tokens to withdraw is not greater than amount of already unlocked tokens.
-/// It does not calculate the amount of unlocked tokens but instead it accepts the amount as an input parameter.
-/// Hence, the amount of unlocked tokens should be calculated and validated before this function is invoked.
+/// Asserts that the given account is a signer.
 ///
 /// ### Arguments
 ///
-/// * `ctx` - the program's context
-/// * `amount_to_withdraw` - the amount of tokens to withdraw
-/// * `amount_available_to_withdraw` - the amount of tokens available to withdraw from the source wallet
+/// * `signer` - the account which is supposed to be a signer
 ///
 /// ### Returns
-/// Tokens transfer result
-pub fn withdraw_vested_tokens<'a, 'b, 'c, 'info, T>(
-    ctx: Context<'a, 'b, 'c, 'info, T>,
-    amount_to_withdraw: u64,
-    amount_available_to_withdraw: u64,
-) -> Result<()>
-where
-    T: VestedWalletContext<'info>,
-{
-    require!(
-        amount_to_withdraw <= amount_available_to_withdraw,
-        LeancoinError::NotEnoughTokens
-    );
-
-    transfer_tokens(
-        ctx.accounts.vested_account().to_account_info(),
-        ctx.accounts.deposit_wallet().to_account_info(),
-        ctx.accounts.token_program().to_account_info(),
-        ctx.accounts.vested_account_seed(),
-        ctx.accounts.vested_account_nonce(),
-        amount_to_withdraw,
-    )?;
+/// An error if the account is not a signer, otherwise a successful result.
+pub fn valid_signer(signer: &AccountInfo) -> Result<()> {
+    require!(signer.is_signer, LeancoinError::Unauthorized);
 
     Ok(())
 }
 
-#[cfg(test)]
-mod test {
-
-    use super::*;
-    use anchor_lang::prelude::Pubkey;
-    use std::cell::RefCell;
-    use std::rc::Rc;
-    use test_case::test_case;
-
-    impl PartialEq for DateTime {
-        fn eq(&self, other: &Self) -> bool {
-            self.year == other.year && self.month == other.month && self.days == other.days
-        }
+/// Asserts that the deposit wallet is owned by the configured beneficiary owner.
+/// The check is skipped if the beneficiary owner is not configured, i.e. it equals the default public key.
+///
+/// ### Arguments
+///
+/// * `deposit_wallet_owner` - the owner of the deposit wallet
+/// * `beneficiary_owner` - the configured beneficiary owner
+///
+/// ### Returns
+/// An error if the beneficiary owner is configured and it is not the owner of the deposit wallet, otherwise a successful result.
+pub fn valid_deposit_wallet_owner(
+    deposit_wallet_owner: &Pubkey,
+    beneficiary_owner: &Pubkey,
+) -> Result<()> {
+    if *beneficiary_owner != Pubkey::default() {
+        require_keys_eq!(
+            *deposit_wallet_owner,
+            *beneficiary_owner,
+            LeancoinError::DepositOwnerMismatch
+        );
     }
 
-    impl std::fmt::Debug for DateTime {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            f.debug_struct("DateTime")
-                .field("year", &self.year)
-                .field("month", &self.month)
-                .field("days", &self.days)
-                .finish()
-        }
-    }
+    Ok(())
+}
 
-    impl std::fmt::Debug for ContractState {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            f.debug_struct("ContractState")
-                .field("mint_nonce", &self.mint_nonce)
-                .field(
-                    "import_ethereum_token_state_already_performed",
-                    &self.import_ethereum_token_state_already_performed,
-                )
-                .field("program_account_nonce", &self.program_account_nonce)
-                .field("burning_account_nonce", &self.burning_account_nonce)
-                .field("last_burning_month", &self.last_burning_month)
-                .field("last_burning_year", &self.last_burning_year)
-                .field("authority", &self.authority)
-                .finish()
-        }
+/// Asserts that the current instruction is invoked directly by the transaction, not via CPI from another program.
+/// The check is skipped if invoking via CPI is allowed in the contract state.
+///
+/// ### Arguments
+///
+/// * `state` - the current state of the contract
+/// * `instructions_sysvar` - the instructions sysvar account
+///
+/// ### Returns
+/// An error if the instruction is invoked via CPI and it is not allowed, otherwise a successful result.
+pub fn top_level_invocation(
+    state: &ContractState,
+    instructions_sysvar: &AccountInfo,
+) -> Result<()> {
+    if state.cpi_allowed {
+        return Ok(());
     }
 
-    impl ContractState {
-        pub fn default() -> Self {
-            Self {
-                contract_state_nonce: 0,
-                mint_nonce: 0,
-                import_ethereum_token_state_already_performed: false,
-                program_account_nonce: 0,
-                burning_account_nonce: 0,
-                last_burning_month: 0,
-                last_burning_year: 0,
-                authority: Pubkey::new_unique(),
-            }
-        }
-    }
+    let current_instruction = get_instruction_relative(0, instructions_sysvar)?;
+    require!(
+        current_instruction.program_id == crate::ID,
+        LeancoinError::CpiNotAllowed
+    );
 
-    #[test_case( 0, DateTime { year: 1970, month: 1, days: 1 }; "timestamp 0")]
-    #[test_case( 162000, DateTime { year: 1970, month: 1, days: 2 }; "timestamp 162000")]
-    #[test_case( 94694400, DateTime { year: 1973, month: 1, days: 1 }; "timestamp 94694400")]
-    #[test_case( 2678400, DateTime { year: 1970, month: 2, days: 1 }; "timestamp 2678400")]
-    #[test_case( 5097600, DateTime { year: 1970, month: 3, days: 1 }; "timestamp 5097600")]
-    #[test_case( 68256000, DateTime { year: 1972, month: 3, days: 1 }; "timestamp 68256000")]
-    #[test_case( 31449600, DateTime { year: 1970, month: 12, days: 31 }; "timestamp 31449600")]
-    #[test_case( 220838400, DateTime { year: 1976, month: 12, days: 31 }; "timestamp 220838400")]
-    #[test_case( 978220800, DateTime { year: 2000, month: 12, days: 31 }; "timestamp 978220800")]
-    #[test_case( 1609335304, DateTime { year: 2020, month: 12, days: 30 }; "timestamp 1609335304" )]
-    #[test_case( 1620000000, DateTime { year: 2021, month: 5, days: 3 }; "timestamp 1620000000")]
-    #[test_case( 1620002137, DateTime { year: 2021, month: 5, days: 3 }; "timestamp 1620002137")]
-    #[test_case( 1378183924, DateTime { year: 2013, month: 9, days: 3 }; "timestamp 1378183924")]
-    #[test_case( 959249016, DateTime { year: 2000, month: 5, days: 25 }; "timestamp 959249016")]
-    #[test_case( 1336937134, DateTime { year: 2012, month: 5, days: 13 }; "timestamp 1336937134")]
-    #[test_case( 1836183646,  DateTime { year: 2028, month: 3, days: 9 }; "timestamp 1836183646")]
-    #[test_case( 1641052800,  DateTime { year: 2022, month: 1, days: 1 }; "timestamp 1641052800")]
-    fn test_parse_timestamp(timestamp: i64, expected: DateTime) {
-        let parsed_timestamp = parse_timestamp(timestamp).unwrap();
-        assert_eq!(parsed_timestamp, expected);
-    }
+    Ok(())
+}
+
+/// Asserts that withdrawals and burning have not been paused by the contract's owner.
+///
+/// ### Arguments
+///
+/// * `state` - the current state of the contract
+///
+/// ### Returns
+/// An error if the contract is paused, otherwise a successful result.
+pub fn not_paused(state: &ContractState) -> Result<()> {
+    require!(!state.paused, LeancoinError::ContractPaused);
+
+    Ok(())
+}
+
+/// Asserts that the first import has been performed, so the vesting has started and the wallets have been funded.
+/// Before the import the start timestamp of the vesting is 0, so the whole schedule would appear unlocked.
+///
+/// ### Arguments
+///
+/// * `state` - the current state of the contract
+///
+/// ### Returns
+/// An error if no import has been performed yet, otherwise a successful result.
+pub fn ethereum_token_state_mapping_already_performed(state: &ContractState) -> Result<()> {
+    require!(
+        state.performed_imports != 0,
+        LeancoinError::ImportNotPerformedYet
+    );
+
+    Ok(())
+}
+
+/// Asserts that the import of Ethereum token state has not yet been performed.
+///
+/// ### Arguments
+///
+/// * `state` - the current state of the contract
+///
+/// ### Returns
+/// An error if the import has already been performed, otherwise a successful result.
+pub fn ethereum_token_state_mapping_not_performed_yet(state: &ContractState) -> Result<()> {
+    require!(
+        state.performed_imports & MigrationSource::Ethereum.mask() == 0,
+        LeancoinError::EthereumTokenStateMappingAlreadyPerformed
+    );
+
+    Ok(())
+}
+
+/// Asserts that the token state from the given migration source has not yet been imported.
+///
+/// ### Arguments
+///
+/// * `state` - the current state of the contract
+/// * `source` - the migration source of the import
+///
+/// ### Returns
+/// An error if the import from the source has already been performed, otherwise a successful result.
+pub fn import_not_performed_yet(state: &ContractState, source: MigrationSource) -> Result<()> {
+    require!(
+        state.performed_imports & source.mask() == 0,
+        LeancoinError::ImportAlreadyPerformed
+    );
+
+    Ok(())
+}
+
+/// Asserts that the token state from the given migration source has been imported.
+///
+/// ### Arguments
+///
+/// * `state` - the current state of the contract
+/// * `source` - the migration source of the import
+///
+/// ### Returns
+/// An error if the import from the source has not been performed yet, otherwise a successful result.
+pub fn import_performed(state: &ContractState, source: MigrationSource) -> Result<()> {
+    require!(
+        state.performed_imports & source.mask() != 0,
+        LeancoinError::ImportNotPerformedYet
+    );
+
+    Ok(())
+}
+
+/// Asserts that the mint authority has not been revoked, so the tokens can still be minted.
+///
+/// ### Arguments
+///
+/// * `state` - the current state of the contract
+///
+/// ### Returns
+/// An error if the mint authority has been revoked, otherwise a successful result.
+pub fn mint_authority_not_revoked(state: &ContractState) -> Result<()> {
+    require!(
+        !state.mint_authority_revoked,
+        LeancoinError::MintAuthorityRevoked
+    );
+
+    Ok(())
+}
+
+/// Records the balance of an imported entry in the vesting state.
+/// The balance of a vested wallet becomes its initial balance and the balance of the swap wallet is summed over the migration sources,
+/// the balances of other entries are not recorded.
+///
+/// ### Arguments
+///
+/// * `vesting_state` - the vesting state
+/// * `wallet_name` - the name of the wallet of the entry
+/// * `account_balance` - the balance of the entry expressed with mint decimals
+/// * `first_import` - whether the entry belongs to the first import, only the first import can fund the vested wallets
+///
+/// ### Returns
+/// An error if a following import funds a vested wallet, the vested wallet has already been funded or the swap balance overflows.
+pub fn record_imported_balance(
+    vesting_state: &mut VestingState,
+    wallet_name: &str,
+    account_balance: u64,
+    first_import: bool,
+) -> Result<()> {
+    let vested_wallet_kind = match wallet_name {
+        "community" => Some(WalletKind::Community),
+        "partnership" => Some(WalletKind::Partnership),
+        "marketing" => Some(WalletKind::Marketing),
+        "liquidity" => Some(WalletKind::Liquidity),
+        _ => None,
+    };
+    if let Some(vested_wallet_kind) = vested_wallet_kind {
+        require!(first_import, LeancoinError::VestingStateAlreadyImported);
+        let wallet = vesting_state.wallet_mut(vested_wallet_kind);
+        require!(
+            wallet.initial_balance == 0,
+            LeancoinError::DuplicatedWalletName
+        );
+        wallet.initial_balance = account_balance;
+    }
+    // the swap wallet is not vested, its balance is only recorded and summed over the migration sources
+    if wallet_name == "swap" {
+        vesting_state.initial_swap_wallet_balance = vesting_state
+            .initial_swap_wallet_balance
+            .checked_add(account_balance)
+            .ok_or(LeancoinError::MaxSupplyExceeded)?;
+    }
+
+    Ok(())
+}
+
+/// Asserts that all vested wallets have been funded by the first import.
+///
+/// ### Arguments
+///
+/// * `vesting_state` - the vesting state
+///
+/// ### Returns
+/// An error naming the first vested wallet with zero initial balance, otherwise a successful result.
+pub fn vested_wallets_funded(vesting_state: &VestingState) -> Result<()> {
+    require!(
+        vesting_state.wallet(WalletKind::Community).initial_balance != 0,
+        LeancoinError::CommunityWalletBalanceIsZero
+    );
+    require!(
+        vesting_state
+            .wallet(WalletKind::Partnership)
+            .initial_balance
+            != 0,
+        LeancoinError::PartnershipWalletBalanceIsZero
+    );
+    require!(
+        vesting_state.wallet(WalletKind::Marketing).initial_balance != 0,
+        LeancoinError::MarketingWalletBalanceIsZero
+    );
+    require!(
+        vesting_state.wallet(WalletKind::Liquidity).initial_balance != 0,
+        LeancoinError::LiquidityWalletBalanceIsZero
+    );
+
+    Ok(())
+}
+
+/// Asserts that the vested wallets have not yet been initialized.
+///
+/// ### Arguments
+///
+/// * `state` - the current state of the contract
+///
+/// ### Returns
+/// An error if the vested wallets have already been initialized, otherwise a successful result.
+pub fn wallets_not_initialized_yet(state: &ContractState) -> Result<()> {
+    require!(
+        !state.wallets_initialized,
+        LeancoinError::WalletsAlreadyInitialized
+    );
+
+    Ok(())
+}
+
+/// Asserts that the vested wallets have already been initialized, i.e. both phases of the initialization have been completed.
+///
+/// ### Arguments
+///
+/// * `state` - the current state of the contract
+///
+/// ### Returns
+/// An error if the vested wallets have not been initialized yet, otherwise a successful result.
+pub fn wallets_initialized(state: &ContractState) -> Result<()> {
+    require!(
+        state.wallets_initialized,
+        LeancoinError::WalletsNotInitialized
+    );
+
+    Ok(())
+}
+
+/// Creates the address of a program derived account from its seed and the nonce stored in the state accounts.
+/// Unlike `Pubkey::find_program_address`, it does not search for the nonce, so it is cheap enough to be used on-chain.
+///
+/// ### Arguments
+///
+/// * `seed` - the seed of the account
+/// * `nonce` - the stored nonce of the account
+///
+/// ### Returns
+/// The address of the account or an error if the seed and nonce do not produce a valid program address.
+pub fn create_program_address(seed: &str, nonce: u8) -> Result<Pubkey> {
+    Pubkey::create_program_address(&[seed.as_bytes(), &[nonce]], &crate::ID)
+        .map_err(|_| ProgramError::InvalidSeeds.into())
+}
+
+/// Returns the nonce of the PDA of the given program token account.
+///
+/// ### Arguments
+///
+/// * `contract_state` - the contract state holding the nonces of the program and burning accounts
+/// * `vesting_state` - the vesting state holding the nonces of the vested wallets
+/// * `account_kind` - the program token account
+///
+/// ### Returns
+/// The nonce of the account's PDA.
+pub fn program_token_account_nonce(
+    contract_state: &ContractState,
+    vesting_state: &VestingState,
+    account_kind: LabeledAccountKind,
+) -> u8 {
+    match account_kind {
+        LabeledAccountKind::ProgramAccount => contract_state.program_account_nonce,
+        LabeledAccountKind::BurningAccount => contract_state.burning_account_nonce,
+        LabeledAccountKind::Community => vesting_state.wallet(WalletKind::Community).nonce,
+        LabeledAccountKind::Partnership => vesting_state.wallet(WalletKind::Partnership).nonce,
+        LabeledAccountKind::Marketing => vesting_state.wallet(WalletKind::Marketing).nonce,
+        LabeledAccountKind::Liquidity => vesting_state.wallet(WalletKind::Liquidity).nonce,
+    }
+}
+
+/// Checks whether the deposit wallet of a withdrawal is one of the program's own token accounts,
+/// i.e. the vested account the tokens are withdrawn from, the program account or the burning account.
+/// Withdrawing into them would increase the withdrawn amount while the tokens stay in the program.
+///
+/// ### Arguments
+///
+/// * `contract_state` - the contract state holding the nonces of the program and burning accounts
+/// * `vested_account` - the address of the vested account the tokens are withdrawn from
+/// * `deposit_wallet` - the address of the deposit wallet
+///
+/// ### Returns
+/// True if the deposit wallet is a token account of the program, false otherwise.
+pub fn is_program_token_account(
+    contract_state: &ContractState,
+    vested_account: Pubkey,
+    deposit_wallet: Pubkey,
+) -> Result<bool> {
+    Ok(deposit_wallet == vested_account
+        || deposit_wallet
+            == create_program_address(PROGRAM_ACCOUNT_SEED, contract_state.program_account_nonce)?
+        || deposit_wallet
+            == create_program_address(BURNING_ACCOUNT_SEED, contract_state.burning_account_nonce)?)
+}
+
+/// Asserts that the number of decimals of the mint is not greater than `MAX_MINT_DECIMALS`.
+///
+/// ### Arguments
+///
+/// * `decimals` - the number of decimals of the mint
+///
+/// ### Returns
+/// An error if the number of decimals is too big, otherwise a successful result.
+pub fn valid_decimals(decimals: u8) -> Result<()> {
+    require!(
+        decimals <= MAX_MINT_DECIMALS,
+        LeancoinError::InvalidDecimals
+    );
+
+    Ok(())
+}
+
+/// Asserts that the burn rate is positive and does not exceed the whole burning account balance.
+///
+/// ### Arguments
+///
+/// * `burn_rate_bps` - the rate of the monthly burn in basis points of the burning account balance
+///
+/// ### Returns
+/// An error if the burn rate is zero or greater than `BURN_RATE_DENOMINATOR`, otherwise a successful result.
+pub fn valid_burn_rate(burn_rate_bps: u16) -> Result<()> {
+    require!(
+        burn_rate_bps > 0 && burn_rate_bps as u64 <= BURN_RATE_DENOMINATOR,
+        LeancoinError::InvalidBurnRate
+    );
+
+    Ok(())
+}
+
+/// Asserts that the reward of the burn caller does not exceed `MAX_BURN_CALLER_REWARD_BPS`.
+///
+/// ### Arguments
+///
+/// * `burn_caller_reward_bps` - the reward of the burn caller in basis points of the burned amount
+///
+/// ### Returns
+/// An error if the reward is too high, otherwise a successful result.
+pub fn valid_burn_caller_reward(burn_caller_reward_bps: u16) -> Result<()> {
+    require!(
+        burn_caller_reward_bps <= MAX_BURN_CALLER_REWARD_BPS,
+        LeancoinError::InvalidBurnCallerReward
+    );
+
+    Ok(())
+}
+
+/// Asserts that the withdrawal grace period is not longer than `MAX_WITHDRAWAL_GRACE_PERIOD`.
+///
+/// ### Arguments
+///
+/// * `withdrawal_grace_period_seconds` - the number of seconds after the vesting start during which the withdrawals are blocked
+///
+/// ### Returns
+/// An error if the grace period is negative or too long, otherwise a successful result.
+pub fn valid_withdrawal_grace_period(withdrawal_grace_period_seconds: i64) -> Result<()> {
+    require!(
+        (0..=MAX_WITHDRAWAL_GRACE_PERIOD).contains(&withdrawal_grace_period_seconds),
+        LeancoinError::InvalidGracePeriod
+    );
+
+    Ok(())
+}
+
+/// Asserts that the withdrawal grace period after the vesting start has elapsed at the given time.
+///
+/// ### Arguments
+///
+/// * `start_timestamp` - the vesting start timestamp
+/// * `withdrawal_grace_period_seconds` - the number of seconds after the vesting start during which the withdrawals are blocked
+/// * `timestamp` - the current timestamp
+///
+/// ### Returns
+/// An error if the grace period is still active, otherwise a successful result.
+pub fn withdrawal_grace_period_elapsed(
+    start_timestamp: i64,
+    withdrawal_grace_period_seconds: i64,
+    timestamp: i64,
+) -> Result<()> {
+    require!(
+        timestamp.saturating_sub(start_timestamp) >= withdrawal_grace_period_seconds,
+        LeancoinError::GracePeriodActive
+    );
+
+    Ok(())
+}
+
+/// Asserts that the withdrawal grace period after the vesting start has elapsed, see `withdrawal_grace_period_elapsed`.
+///
+/// ### Arguments
+///
+/// * `state` - the current state of the contract
+/// * `vesting_state` - the vesting state account, it must not be mutably borrowed
+///
+/// ### Returns
+/// An error if the grace period is still active, otherwise a successful result.
+pub fn withdrawal_grace_period_passed(
+    state: &ContractState,
+    vesting_state: &AccountLoader<VestingState>,
+) -> Result<()> {
+    withdrawal_grace_period_elapsed(
+        vesting_state.load()?.start_timestamp,
+        state.withdrawal_grace_period_seconds,
+        Clock::get()?.unix_timestamp,
+    )
+}
+
+/// Asserts that the vested wallet has not been finalized, i.e. its token account has not been closed.
+///
+/// ### Arguments
+///
+/// * `vesting_state` - the vesting state account, it must not be mutably borrowed
+/// * `wallet_kind` - the vested wallet to check
+///
+/// ### Returns
+/// An error if the wallet has already been finalized, otherwise a successful result.
+pub fn vested_wallet_not_finalized(
+    vesting_state: &AccountLoader<VestingState>,
+    wallet_kind: WalletKind,
+) -> Result<()> {
+    require!(
+        vesting_state.load()?.finalized_wallets & wallet_kind.mask() == 0,
+        LeancoinError::WalletFinalized
+    );
+
+    Ok(())
+}
+
+/// Asserts that the unvested tokens of the vested wallet have not been revoked yet.
+///
+/// ### Arguments
+///
+/// * `vesting_state` - the vesting state account, it must not be mutably borrowed
+/// * `wallet_kind` - the vested wallet to check
+///
+/// ### Returns
+/// An error if the wallet has already been revoked, otherwise a successful result.
+pub fn vested_wallet_not_revoked(
+    vesting_state: &AccountLoader<VestingState>,
+    wallet_kind: WalletKind,
+) -> Result<()> {
+    require!(
+        !vesting_state.load()?.revoked(wallet_kind),
+        LeancoinError::WalletAlreadyRevoked
+    );
+
+    Ok(())
+}
+
+/// Returns the token balance of the vested wallet.
+/// The token account of a finalized wallet is closed, so its balance is zero.
+///
+/// ### Arguments
+///
+/// * `vesting_state` - the current state of the vesting
+/// * `wallet_kind` - the vested wallet
+/// * `wallet_account` - the token account of the vested wallet
+///
+/// ### Returns
+/// The balance of the wallet or an error if the token account of a not finalized wallet cannot be deserialized.
+pub fn vested_wallet_balance(
+    vesting_state: &VestingState,
+    wallet_kind: WalletKind,
+    wallet_account: &AccountInfo,
+) -> Result<u64> {
+    if vesting_state.finalized_wallets & wallet_kind.mask() != 0 {
+        return Ok(0);
+    }
+
+    Ok(Account::<TokenAccount>::try_from(wallet_account)?.amount)
+}
+
+/// Returns the part of the unlocked amount of the vested wallet which has not been withdrawn yet.
+///
+/// ### Arguments
+///
+/// * `vesting_state` - the current state of the vesting
+/// * `wallet_kind` - the vested wallet
+/// * `unlocked_amount` - the amount of tokens currently unlocked in the wallet
+///
+/// ### Returns
+/// The not withdrawn unlocked amount or an error if more than the unlocked amount has already been withdrawn,
+/// e.g. after the clock of the cluster went back.
+pub fn remaining_unlocked_amount(
+    vesting_state: &VestingState,
+    wallet_kind: WalletKind,
+    unlocked_amount: u64,
+) -> Result<u64> {
+    unlocked_amount
+        .checked_sub(vesting_state.wallet(wallet_kind).withdrawn)
+        .ok_or_else(|| LeancoinError::WithdrawnMoreThanUnlocked.into())
+}
+
+/// Calculates the vesting status of the vested wallet, i.e. its unlocked, withdrawn and currently withdrawable amounts.
+/// The withdrawable amount is the part of the unlocked amount which has not been withdrawn yet, limited by the balance of the wallet.
+///
+/// ### Arguments
+///
+/// * `vesting_state` - the current state of the vesting
+/// * `wallet_kind` - the vested wallet
+/// * `wallet_account` - the token account of the vested wallet
+/// * `timestamp` - the timestamp at which the status is calculated
+///
+/// ### Returns
+/// The vesting status of the wallet or an error if more than the unlocked amount has already been withdrawn,
+/// the vesting has not started yet or the token account cannot be deserialized.
+pub fn calculate_wallet_vesting_status(
+    vesting_state: &VestingState,
+    wallet_kind: WalletKind,
+    wallet_account: &AccountInfo,
+    timestamp: i64,
+) -> Result<WalletVestingStatus> {
+    let unlocked = calculate_wallet_unlocked_amount(vesting_state, wallet_kind, timestamp)?;
+    let wallet_balance = vested_wallet_balance(vesting_state, wallet_kind, wallet_account)?;
+
+    Ok(WalletVestingStatus {
+        unlocked,
+        withdrawn: vesting_state.wallet(wallet_kind).withdrawn,
+        withdrawable: wallet_balance.min(remaining_unlocked_amount(
+            vesting_state,
+            wallet_kind,
+            unlocked,
+        )?),
+    })
+}
+
+/// Encodes the label of a program token account, padding it with zeros.
+///
+/// ### Arguments
+///
+/// * `label` - the UTF-8 encoded label
+///
+/// ### Returns
+/// The padded label or an error if the label is longer than `LABEL_LENGTH` bytes or it is not valid UTF-8.
+pub fn encode_label(label: &[u8]) -> Result<[u8; LABEL_LENGTH]> {
+    require!(label.len() <= LABEL_LENGTH, LeancoinError::LabelTooLong);
+    require!(
+        std::str::from_utf8(label).is_ok(),
+        LeancoinError::InvalidLabel
+    );
+
+    let mut encoded_label = [0u8; LABEL_LENGTH];
+    encoded_label[..label.len()].copy_from_slice(label);
+
+    Ok(encoded_label)
+}
+
+/// Increments the sequence number of state-mutating instructions.
+/// It must be called at the start of every state-mutating instruction, read-only instructions must not call it.
+///
+/// ### Arguments
+///
+/// * `state` - the current state of the contract
+///
+/// ### Returns
+/// The sequence number of the current instruction or an error if the sequence number overflows.
+pub fn next_sequence(state: &mut ContractState) -> Result<u64> {
+    state.sequence = state
+        .sequence
+        .checked_add(1)
+        .ok_or(LeancoinError::SequenceOverflow)?;
+
+    Ok(state.sequence)
+}
+
+/// Asserts that the configuration has not been locked yet.
+///
+/// ### Arguments
+///
+/// * `state` - the current state of the contract
+///
+/// ### Returns
+/// An error if the configuration has already been locked, otherwise a successful result.
+pub fn configuration_not_locked(state: &ContractState) -> Result<()> {
+    require!(
+        !state.configuration_locked,
+        LeancoinError::ConfigurationLocked
+    );
+
+    Ok(())
+}
+
+/// Asserts that the primary burn window is not empty and ends on a day which every month has.
+///
+/// ### Arguments
+///
+/// * `burn_window_days` - the number of days of the primary burn window
+///
+/// ### Returns
+/// An error if the window is empty or ends after the 28th day, otherwise a successful result.
+pub fn valid_burn_window(burn_window_days: u8) -> Result<()> {
+    require!(
+        burn_window_days > 0 && burn_window_days <= MAX_BURN_WINDOW_DAYS,
+        LeancoinError::InvalidBurnWindow
+    );
+
+    Ok(())
+}
+
+/// Asserts that the secondary burn window starts after the primary one and ends on a day which every month has.
+///
+/// ### Arguments
+///
+/// * `state` - the current state of the contract
+/// * `first_day` - the first day of the secondary burn window
+/// * `last_day` - the last day of the secondary burn window
+///
+/// ### Returns
+/// An error if the window is empty, overlaps the primary window or ends after the 28th day, otherwise a successful result.
+pub fn valid_late_burn_window(state: &ContractState, first_day: u8, last_day: u8) -> Result<()> {
+    require!(
+        first_day > state.burn_window_days
+            && first_day <= last_day
+            && last_day <= MAX_LATE_BURN_WINDOW_LAST_DAY,
+        LeancoinError::InvalidLateBurnWindow
+    );
+
+    Ok(())
+}
+
+/// Burns tokens from the burning account and records the burn.
+/// The burn is checked against the decrease of the mint supply and of the burning account balance,
+/// added to the total burned amount and to the burn history, and reported by the `BurnExecuted` event.
+/// The month of the last burn is not changed, so it has to be updated by the caller if the burn replaces a monthly burn.
+///
+/// ### Arguments
+///
+/// * `ctx` - the program's context
+/// * `amount` - the amount of tokens to burn
+/// * `details` - the values reported in the event which differ between the burn instructions
+///
+/// ### Returns
+/// The result of the burn, an error if the supply or the burning account balance did not decrease by the amount
+pub fn execute_burn<'a, 'b, 'c, 'info, T>(
+    ctx: Context<'a, 'b, 'c, 'info, T>,
+    amount: u64,
+    details: BurnDetails,
+) -> Result<()>
+where
+    T: BurningContext<'info>,
+{
+    let accounts = ctx.accounts;
+    let burning_account_nonce = accounts.contract_state().burning_account_nonce;
+    let seeds = &[BURNING_ACCOUNT_SEED.as_bytes(), &[burning_account_nonce]];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = Burn {
+        mint: accounts.mint().to_account_info(),
+        from: accounts.burning_account().to_account_info(),
+        authority: accounts.burning_account().to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        accounts.token_program().to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+
+    // the balance is read from the reloaded account, so the transfers made before the burn are reflected
+    accounts.burning_account().reload()?;
+    let burning_account_balance = accounts.burning_account().amount;
+    let supply_before = accounts.mint().supply;
+    token::burn(cpi_ctx, amount)?;
+
+    // the supply is read again after the CPI, so the event reports the supply actually burned
+    accounts.mint().reload()?;
+    let supply_after = accounts.mint().supply;
+    require!(
+        supply_before.checked_sub(supply_after) == Some(amount),
+        LeancoinError::BurnSupplyMismatch
+    );
+
+    accounts.burning_account().reload()?;
+    let burning_account_balance_after = accounts.burning_account().amount;
+    require!(
+        burning_account_balance.checked_sub(amount) == Some(burning_account_balance_after),
+        LeancoinError::BurningAccountBalanceMismatch
+    );
+
+    let contract_state = accounts.contract_state();
+    contract_state.total_burned = contract_state
+        .total_burned
+        .checked_add(amount)
+        .ok_or(LeancoinError::BurnedAmountOverflow)?;
+    accounts.burn_history().record(BurnRecord {
+        timestamp: details.timestamp,
+        amount,
+        burning_account_balance_after,
+    });
+
+    emit!(BurnExecuted {
+        amount,
+        rewards: details.rewards,
+        month: details.month,
+        year: details.year,
+        sequence: details.sequence,
+        supply_before,
+        supply_after,
+        late: details.late,
+        burning_account_balance: burning_account_balance_after,
+        burn_rate_bps: details.burn_rate_bps,
+        caller_reward: details.caller_reward,
+        manual: details.manual,
+    });
+
+    Ok(())
+}
+
+/// Checks in which burn window the given day of the month is.
+/// The primary window starts on the 1st day of the month and lasts for the number of days configured in the contract state,
+/// the secondary window is configured in the contract state as well.
+///
+/// ### Arguments
+///
+/// * `state` - the current state of the contract
+/// * `day` - the day of the month
+///
+/// ### Returns
+/// False if the day is in the primary window, true if it is in the secondary window, otherwise an error.
+pub fn late_burn(state: &ContractState, day: u8) -> Result<bool> {
+    if day <= state.burn_window_days {
+        return Ok(false);
+    }
+    require!(
+        (state.late_burn_window_first_day..=state.late_burn_window_last_day).contains(&day),
+        LeancoinError::TooLateToBurnTokens
+    );
+
+    Ok(true)
+}
+
+/// Calculates the number of monthly burns missed entirely since the last one, ignoring days like `calculate_month_difference`.
+/// The current month is not counted, since its burn can still be executed by the monthly burn in the burn windows.
+/// The number is capped at `MAX_CATCH_UP_BURN_MONTHS`.
+///
+/// ### Arguments
+///
+/// * `state` - the contract state holding the month and the year of the last burn
+/// * `now` - the current date
+///
+/// ### Returns
+/// The number of months to burn for or an error if no monthly burn has been executed yet,
+/// the burn of the current month has already been executed or the last burn was executed in the previous month.
+pub fn months_since_last_burn(state: &ContractState, now: &DateTime) -> Result<u64> {
+    require!(state.last_burning_year != 0, LeancoinError::NoPreviousBurn);
+
+    let months = (now.year - state.last_burning_year) * 12 + i64::from(now.month)
+        - i64::from(state.last_burning_month);
+    require!(months > 0, LeancoinError::TokensAlreadyBurned);
+    require!(months >= 2, LeancoinError::NoMissedBurn);
+
+    Ok((months as u64 - 1).min(MAX_CATCH_UP_BURN_MONTHS))
+}
+
+/// Calculates the month preceding the given date.
+///
+/// ### Arguments
+///
+/// * `now` - the current date
+///
+/// ### Returns
+/// The month and the year of the previous month.
+pub fn previous_month(now: &DateTime) -> (u8, i64) {
+    if now.month == 1 {
+        (12, now.year - 1)
+    } else {
+        (now.month - 1, now.year)
+    }
+}
+
+/// Scales the amount of tokens expressed with source decimals (e.g. decimals of the Ethereum token) to the amount expressed with mint decimals.
+///
+/// When source decimals exceed mint decimals, the amount is divided by 10^(source_decimals - mint_decimals).
+/// The division must be exact, otherwise the scaling fails so no token fraction is silently lost.
+/// When source decimals are lower than mint decimals, the amount is multiplied by 10^(mint_decimals - source_decimals).
+///
+/// ### Arguments
+///
+/// * `amount` - the amount of tokens expressed with source decimals
+/// * `source_decimals` - the number of decimals used by the source of the amount
+/// * `mint_decimals` - the number of decimals used by the mint
+///
+/// ### Returns
+/// The amount of tokens expressed with mint decimals or an error if precision would be lost or the scaled amount does not fit into u64.
+pub fn scale_amount_to_mint_decimals(
+    amount: u128,
+    source_decimals: u8,
+    mint_decimals: u8,
+) -> Result<u64> {
+    let scaled_amount = if source_decimals >= mint_decimals {
+        let divisor = 10u128
+            .checked_pow(u32::from(source_decimals - mint_decimals))
+            .ok_or(LeancoinError::PrecisionLoss)?;
+        require!(amount % divisor == 0, LeancoinError::PrecisionLoss);
+
+        amount / divisor
+    } else {
+        let multiplier = 10u128
+            .checked_pow(u32::from(mint_decimals - source_decimals))
+            .ok_or(LeancoinError::ScaledAmountOverflow)?;
+
+        amount
+            .checked_mul(multiplier)
+            .ok_or(LeancoinError::ScaledAmountOverflow)?
+    };
+
+    u64::try_from(scaled_amount).map_err(|_| LeancoinError::ScaledAmountOverflow.into())
+}
+
+/// Calculates the deterministic hash of the proof-of-reserve attestation.
+/// The hash is the SHA256 of little-endian bytes of the slot, the mint supply and the balances, in the given order.
+///
+/// The function is used by the attest_reserves instruction but it can be also used off-chain to recompute the hash from historical account data.
+///
+/// ### Arguments
+///
+/// * `slot` - the slot in which the attestation is performed
+/// * `supply` - the mint supply
+/// * `balances` - the balances of program-custodied token accounts: program, burning, community, partnership, marketing and liquidity account
+///
+/// ### Returns
+/// The attestation hash
+pub fn calculate_reserves_attestation_hash(slot: u64, supply: u64, balances: &[u64]) -> [u8; 32] {
+    let mut data = Vec::with_capacity(8 * (balances.len() + 2));
+    data.extend_from_slice(&slot.to_le_bytes());
+    data.extend_from_slice(&supply.to_le_bytes());
+    for balance in balances {
+        data.extend_from_slice(&balance.to_le_bytes());
+    }
+
+    hash(&data).to_bytes()
+}
+
+/// Verifies the proof-of-reserve attestation against the mint supply and balances read from historical account data.
+///
+/// ### Arguments
+///
+/// * `attestation` - the attestation account
+/// * `supply` - the mint supply in the attested slot
+/// * `balances` - the balances of program-custodied token accounts in the attested slot, in the same order as in `calculate_reserves_attestation_hash`
+///
+/// ### Returns
+/// True if the recomputed hash matches the attested one, false otherwise.
+pub fn verify_reserves_attestation(
+    attestation: &Attestation,
+    supply: u64,
+    balances: &[u64],
+) -> bool {
+    calculate_reserves_attestation_hash(attestation.last_attestation_slot, supply, balances)
+        == attestation.last_attestation_hash
+}
+
+/// Asserts that the token metadata fits in the limits of the Metaplex token metadata program.
+///
+/// ### Arguments
+///
+/// * `name` - the token name
+/// * `symbol` - the token symbol
+/// * `uri` - the token uri
+///
+/// ### Returns
+/// An error if the name, the symbol or the uri is too long, otherwise a successful result.
+#[cfg(feature = "metadata")]
+pub fn valid_token_metadata(name: &str, symbol: &str, uri: &str) -> Result<()> {
+    require!(
+        name.len() <= mpl_token_metadata::state::MAX_NAME_LENGTH,
+        LeancoinError::TokenNameTooLong
+    );
+    require!(
+        symbol.len() <= mpl_token_metadata::state::MAX_SYMBOL_LENGTH,
+        LeancoinError::TokenSymbolTooLong
+    );
+    require!(
+        uri.len() <= mpl_token_metadata::state::MAX_URI_LENGTH,
+        LeancoinError::TokenUriTooLong
+    );
+
+    Ok(())
+}
+
+/// Asserts that the metadata account is the metadata PDA of the mint derived by the token metadata program.
+///
+/// ### Arguments
+///
+/// * `metadata_pda` - the metadata account
+/// * `metadata_program_id` - the token metadata program id
+/// * `mint` - the mint of the token
+///
+/// ### Returns
+/// An error if the account is not the metadata PDA of the mint, otherwise a successful result.
+#[cfg(feature = "metadata")]
+pub fn valid_metadata_pda(
+    metadata_pda: &Pubkey,
+    metadata_program_id: &Pubkey,
+    mint: &Pubkey,
+) -> Result<()> {
+    let (expected_metadata_pda, _) = Pubkey::find_program_address(
+        &[b"metadata", metadata_program_id.as_ref(), mint.as_ref()],
+        metadata_program_id,
+    );
+    require_keys_eq!(
+        *metadata_pda,
+        expected_metadata_pda,
+        LeancoinError::InvalidMetadataPda
+    );
+
+    Ok(())
+}
+
+/// Returns the token metadata program id set during initialization.
+/// It is the Metaplex token metadata program id, or the default public key if the program is built without metadata support.
+#[cfg(feature = "metadata")]
+pub fn default_metadata_program_id() -> Pubkey {
+    mpl_token_metadata::id()
+}
+
+/// Returns the token metadata program id set during initialization.
+/// It is the Metaplex token metadata program id, or the default public key if the program is built without metadata support.
+#[cfg(not(feature = "metadata"))]
+pub fn default_metadata_program_id() -> Pubkey {
+    Pubkey::default()
+}
+
+/// Accepts the timestamp as an integer (i64) and returns DateTime struct, see `leancoin_core::parse_timestamp`.
+///
+/// ### Arguments
+///
+/// * `timestamp` - the timestamp as a signed integer
+///
+/// ### Returns
+/// DateTime struct created from the timestamp
+pub fn parse_timestamp(timestamp: i64) -> Result<DateTime> {
+    leancoin_core::parse_timestamp(timestamp).map_err(|error| LeancoinError::from(error).into())
+}
+
+/// Calculates the number of months between two timestamps, ignoring days, see `leancoin_core::calculate_month_difference`.
+///
+/// ### Arguments
+///
+/// * `start` - the earlier timestamp
+/// * `end` - the later timestamp
+///
+/// ### Returns
+/// Number of months between two timestamps, ignoring days.
+pub fn calculate_month_difference(start: i64, end: i64) -> Result<u64> {
+    leancoin_core::calculate_month_difference(start, end)
+        .map_err(|error| LeancoinError::from(error).into())
+}
+
+/// Calculates the number of months since the vesting start, see `calculate_month_difference`.
+/// Before the first import the vesting start is 0, the months since the Unix epoch would unlock the whole balance of every wallet.
+///
+/// ### Arguments
+///
+/// * `vesting_state` - the vesting state
+/// * `timestamp` - the timestamp until which the months are counted
+///
+/// ### Returns
+/// Number of months since the vesting start or an error if the vesting has not started yet.
+pub fn months_since_vesting_start(vesting_state: &VestingState, timestamp: i64) -> Result<u64> {
+    require!(
+        vesting_state.start_timestamp != 0,
+        LeancoinError::VestingNotStarted
+    );
+
+    calculate_month_difference(vesting_state.start_timestamp, timestamp)
+}
+
+/// Returns the vesting schedule of the given vested wallet.
+/// The unlock delay of the community wallet set during Ethereum token state import is added to the cliff of its schedule.
+///
+/// ### Arguments
+///
+/// * `vesting_state` - the current vesting state
+/// * `wallet_kind` - the vested wallet
+///
+/// ### Returns
+/// The vesting schedule of the wallet
+pub fn vesting_schedule(vesting_state: &VestingState, wallet_kind: WalletKind) -> VestingSchedule {
+    let schedule = UnlockSchedule::from(*vesting_state.schedule(wallet_kind));
+    match wallet_kind {
+        WalletKind::Community => schedule
+            .delayed(vesting_state.community_unlock_delay_months)
+            .into(),
+        _ => schedule.into(),
+    }
+}
+
+/// Calculates the amount of unlocked tokens of a vested wallet using its vesting schedule.
+///
+/// ### Arguments
+///
+/// * `schedule` - the vesting schedule of the wallet, see `vesting_schedule`
+/// * `vesting_start_account_balance` - the initial balance of the wallet after Ethereum token state import
+/// * `months_since_vesting_start` - number of full months since the Ethereum token state import
+///
+/// ### Returns
+/// The amount of unlocked tokens for the wallet
+pub fn calculate_unlocked_amount(
+    schedule: &VestingSchedule,
+    vesting_start_account_balance: u64,
+    months_since_vesting_start: u64,
+) -> u64 {
+    UnlockSchedule::from(*schedule)
+        .unlocked_amount(vesting_start_account_balance, months_since_vesting_start)
+}
+
+/// Forecasts the amounts unlocked by the vesting curves of the vested wallets after the given number of months,
+/// using the stored vesting schedules and initial balances. The clock is not read, so any past or future month can be forecast.
+/// The wallets with linear daily unlock are forecast at the beginning of the month and the unlocked amount of a revoked wallet
+/// stays frozen at its revocation.
+///
+/// ### Arguments
+///
+/// * `vesting_state` - the current vesting state
+/// * `months_since_vesting_start` - number of full months since the Ethereum token state import
+///
+/// ### Returns
+/// The amounts of unlocked tokens of the vested wallets, ordered like `WalletKind`
+pub fn forecast_unlocked_amounts(
+    vesting_state: &VestingState,
+    months_since_vesting_start: u64,
+) -> [u64; VESTED_WALLETS_COUNT] {
+    WalletKind::ALL.map(|wallet_kind| {
+        if vesting_state.revoked(wallet_kind) {
+            return vesting_state.revoked_unlocked[wallet_kind.index()];
+        }
+
+        calculate_unlocked_amount(
+            &vesting_schedule(vesting_state, wallet_kind),
+            vesting_state.wallet(wallet_kind).initial_balance,
+            months_since_vesting_start,
+        )
+    })
+}
+
+/// Builds the table of the amounts unlocked by the default vesting schedules in every month up to the given one,
+/// e.g. for the tokenomics documentation. The community wallet is calculated without an unlock delay.
+///
+/// ### Arguments
+///
+/// * `initial_balances` - the initial balances of the vested wallets, ordered like `WalletKind`
+/// * `max_months` - the last month of the table
+///
+/// ### Returns
+/// The rows of the months from 0 to `max_months` inclusive
+pub fn vesting_table(
+    initial_balances: [u64; VESTED_WALLETS_COUNT],
+    max_months: u64,
+) -> Vec<MonthRow> {
+    let [community, partnership, marketing, liquidity] = initial_balances;
+    (0..=max_months)
+        .map(|month| MonthRow {
+            month,
+            unlocked: [
+                calculate_unlocked_amount_community_wallet(community, month, 0),
+                calculate_unlocked_amount_partnership_wallet(partnership, month),
+                calculate_unlocked_amount_marketing_wallet(marketing, month),
+                calculate_unlocked_amount_liquidity_wallet(liquidity, month),
+            ],
+        })
+        .collect()
+}
+
+/// Calculates the amount of unlocked tokens of the vested wallet at the given time.
+/// The wallets with linear daily unlock interpolate the monthly unlock over the days of the current month,
+/// see `UnlockSchedule::linearly_unlocked_amount`, the other wallets unlock in monthly steps, see `calculate_unlocked_amount`.
+/// The unlocked amount of a revoked wallet is frozen at its revocation, see `VestingState::revoked_unlocked`.
+///
+/// ### Arguments
+///
+/// * `vesting_state` - the current vesting state
+/// * `wallet_kind` - the vested wallet
+/// * `timestamp` - the timestamp at which the unlocked amount is calculated
+///
+/// ### Returns
+/// The amount of unlocked tokens of the wallet or an error if the vesting has not started yet or the timestamp is invalid.
+pub fn calculate_wallet_unlocked_amount(
+    vesting_state: &VestingState,
+    wallet_kind: WalletKind,
+    timestamp: i64,
+) -> Result<u64> {
+    if vesting_state.revoked(wallet_kind) {
+        return Ok(vesting_state.revoked_unlocked[wallet_kind.index()]);
+    }
+
+    let months_since_vesting_start = months_since_vesting_start(vesting_state, timestamp)?;
+    let schedule = vesting_schedule(vesting_state, wallet_kind);
+    let initial_balance = vesting_state.wallet(wallet_kind).initial_balance;
+    if !vesting_state.linear_daily_unlock(wallet_kind) {
+        return Ok(calculate_unlocked_amount(
+            &schedule,
+            initial_balance,
+            months_since_vesting_start,
+        ));
+    }
+
+    let date = parse_timestamp(timestamp)?;
+    Ok(UnlockSchedule::from(schedule).linearly_unlocked_amount(
+        initial_balance,
+        months_since_vesting_start,
+        date.days - 1,
+        days_in_month(date.year, date.month),
+    ))
+}
+
+/// Asserts that the unlocks of every vesting schedule do not exceed the whole balance of the wallet, see `UnlockSchedule::is_valid`.
+///
+/// ### Arguments
+///
+/// * `vesting_schedules` - the vesting schedules of the vested wallets, the default ones are used if not given
+///
+/// ### Returns
+/// An error if any of the schedules is not valid, otherwise a successful result.
+pub fn valid_vesting_schedules(
+    vesting_schedules: &Option<[VestingSchedule; VESTED_WALLETS_COUNT]>,
+) -> Result<()> {
+    if let Some(vesting_schedules) = vesting_schedules {
+        require!(
+            vesting_schedules
+                .iter()
+                .all(|schedule| UnlockSchedule::from(*schedule).is_valid()),
+            LeancoinError::InvalidVestingSchedule
+        );
+    }
+
+    Ok(())
+}
+
+/// Asserts that the linear daily unlock is requested only for the community and marketing wallets,
+/// the cliffs of the partnership and liquidity wallets stay step-based.
+///
+/// ### Arguments
+///
+/// * `linear_daily_unlock` - whether each vested wallet ordered like `WalletKind` unlocks daily, none of them if not given
+///
+/// ### Returns
+/// An error if the linear daily unlock is requested for the partnership or liquidity wallet, otherwise a successful result.
+pub fn valid_linear_daily_unlock(
+    linear_daily_unlock: &Option<[bool; VESTED_WALLETS_COUNT]>,
+) -> Result<()> {
+    if let Some(linear_daily_unlock) = linear_daily_unlock {
+        require!(
+            !linear_daily_unlock[WalletKind::Partnership.index()]
+                && !linear_daily_unlock[WalletKind::Liquidity.index()],
+            LeancoinError::LinearDailyUnlockNotSupported
+        );
+    }
+
+    Ok(())
+}
+
+/// Calculates the amount of tokens of the vested wallet which have not been unlocked yet.
+/// The whole initial balance is locked before the vesting start, nothing is locked in a finalized or revoked wallet.
+///
+/// ### Arguments
+///
+/// * `vesting_state` - the current vesting state
+/// * `wallet_kind` - the vested wallet
+/// * `timestamp` - the timestamp at which the locked amount is calculated
+///
+/// ### Returns
+/// The locked amount or an error if the timestamp is invalid.
+pub fn calculate_locked_amount(
+    vesting_state: &VestingState,
+    wallet_kind: WalletKind,
+    timestamp: i64,
+) -> Result<u64> {
+    if vesting_state.finalized_wallets & wallet_kind.mask() != 0
+        || vesting_state.revoked(wallet_kind)
+    {
+        return Ok(0);
+    }
+
+    let initial_balance = vesting_state.wallet(wallet_kind).initial_balance;
+    if vesting_state.start_timestamp == 0 {
+        return Ok(initial_balance);
+    }
+
+    let unlocked_amount = calculate_wallet_unlocked_amount(vesting_state, wallet_kind, timestamp)?;
+
+    Ok(initial_balance.saturating_sub(unlocked_amount))
+}
+
+/// Resolves the amount of tokens requested by a withdrawal.
+///
+/// ### Arguments
+///
+/// * `amount_to_withdraw` - the amount of tokens to withdraw or `WITHDRAW_ALL_UNLOCKED`
+/// * `amount_available_to_withdraw` - the amount of tokens available to withdraw from the source wallet
+///
+/// ### Returns
+/// The available amount for `WITHDRAW_ALL_UNLOCKED`, otherwise the requested amount.
+pub fn resolve_amount_to_withdraw(
+    amount_to_withdraw: u64,
+    amount_available_to_withdraw: u64,
+) -> u64 {
+    match amount_to_withdraw {
+        WITHDRAW_ALL_UNLOCKED => amount_available_to_withdraw,
+        amount => amount,
+    }
+}
+
+/// Transfers tokens from one of the wallets affected by vesting mechanism: community, partnership, marketing or liquidity wallet.
+/// The destination for the transfer is deposit wallet which is not managed by this contract.
+///
+/// The function also validates if the amount of tokens to withdraw is not greater than amount of already unlocked tokens
+/// and if the deposit wallet is owned by the configured beneficiary owner.
+/// It does not calculate the amount of unlocked tokens but instead it accepts the amount as an input parameter.
+/// Hence, the amount of unlocked tokens should be calculated and validated before this function is invoked.
+/// `WITHDRAW_ALL_UNLOCKED` withdraws exactly the available amount, so nothing is transferred if it is zero.
+///
+/// ### Arguments
+///
+/// * `ctx` - the program's context
+/// * `wallet_kind` - the vested wallet to withdraw from
+/// * `amount_to_withdraw` - the amount of tokens to withdraw or `WITHDRAW_ALL_UNLOCKED`
+/// * `amount_available_to_withdraw` - the amount of tokens available to withdraw from the source wallet
+///
+/// ### Returns
+/// The amount of transferred tokens which should be recorded as withdrawn by the caller, see `record_withdrawal`
+pub fn withdraw_vested_tokens<'a, 'b, 'c, 'info, T>(
+    ctx: &Context<'a, 'b, 'c, 'info, T>,
+    wallet_kind: WalletKind,
+    amount_to_withdraw: u64,
+    amount_available_to_withdraw: u64,
+) -> Result<u64>
+where
+    T: VestedWalletContext<'info>,
+{
+    let amount_to_withdraw =
+        resolve_amount_to_withdraw(amount_to_withdraw, amount_available_to_withdraw);
+    require!(
+        amount_to_withdraw <= amount_available_to_withdraw,
+        LeancoinError::not_enough_tokens(wallet_kind)
+    );
+    require!(
+        ctx.accounts.deposit_wallet().state == AccountState::Initialized,
+        LeancoinError::DepositWalletFrozen
+    );
+    valid_deposit_wallet_owner(
+        &ctx.accounts.deposit_wallet().owner,
+        &ctx.accounts.beneficiary_owner(wallet_kind)?,
+    )?;
+
+    let vested_account_nonce = ctx
+        .accounts
+        .vesting_state()
+        .load()?
+        .wallet(wallet_kind)
+        .nonce;
+    transfer_tokens(
+        ctx.accounts.vested_account().to_account_info(),
+        ctx.accounts.deposit_wallet().to_account_info(),
+        ctx.accounts.token_program().to_account_info(),
+        wallet_kind.seed(),
+        vested_account_nonce,
+        amount_to_withdraw,
+    )
+}
+
+/// Withdraws the unlocked tokens from the vested wallet, it is the shared implementation of all withdraw instructions.
+/// The amount available to withdraw is the part of the unlocked amount which has not been withdrawn yet,
+/// limited by the balance of the wallet. The transferred amount is recorded as withdrawn, in the withdrawal history of the wallet
+/// and in the withdrawal receipt created by the instruction, `VestedTokensWithdrawn` is emitted with the unlocked and withdrawn totals, so the withdrawal can be audited against the schedule
+/// from the event alone.
+///
+/// ### Arguments
+///
+/// * `ctx` - the program's context
+/// * `wallet_kind` - the vested wallet to withdraw from
+/// * `amount_to_withdraw` - the amount of tokens to withdraw or `WITHDRAW_ALL_UNLOCKED`
+/// * `idempotency_key` - optional key supplied by the client, the withdrawal fails if the key has already been used
+///
+/// ### Returns
+/// An error if the withdrawal is not valid, otherwise a successful result.
+pub fn withdraw_from_vested_wallet<'a, 'b, 'c, 'info, T>(
+    mut ctx: Context<'a, 'b, 'c, 'info, T>,
+    wallet_kind: WalletKind,
+    amount_to_withdraw: u64,
+    idempotency_key: Option<[u8; 16]>,
+) -> Result<()>
+where
+    T: VestedWithdrawalContext<'info>,
+{
+    let sequence = next_sequence(ctx.accounts.contract_state())?;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let vesting_state = ctx.accounts.vesting_state().load()?;
+    let months_since_vesting_start = months_since_vesting_start(&vesting_state, timestamp)?;
+    let wallet_vesting_status = calculate_wallet_vesting_status(
+        &vesting_state,
+        wallet_kind,
+        &ctx.accounts.vested_account(),
+        timestamp,
+    )?;
+    drop(vesting_state);
+
+    // the intent records the amount actually transferred, not the `WITHDRAW_ALL_UNLOCKED` sentinel
+    let signer = ctx.accounts.signer();
+    record_withdrawal_intent(
+        ctx.accounts.withdrawal_intent(),
+        idempotency_key,
+        ctx.bumps
+            .get("withdrawal_intent")
+            .copied()
+            .unwrap_or_default(),
+        signer,
+        resolve_amount_to_withdraw(amount_to_withdraw, wallet_vesting_status.withdrawable),
+        timestamp,
+    )?;
+
+    let withdrawn_amount = withdraw_vested_tokens(
+        &ctx,
+        wallet_kind,
+        amount_to_withdraw,
+        wallet_vesting_status.withdrawable,
+    )?;
+    let mut vesting_state = ctx.accounts.vesting_state().load_mut()?;
+    record_withdrawal(&mut vesting_state, wallet_kind, withdrawn_amount)?;
+    record_withdrawal_history(&mut vesting_state, wallet_kind, withdrawn_amount, timestamp);
+    let receipt_index = next_receipt_index(&mut vesting_state, wallet_kind)?;
+    let already_withdrawn_total = vesting_state.wallet(wallet_kind).withdrawn;
+    drop(vesting_state);
+
+    let destination = ctx.accounts.deposit_wallet().key();
+    let withdrawal_receipt_nonce = *ctx.bumps.get("withdrawal_receipt").unwrap();
+    let withdrawal_receipt = ctx.accounts.withdrawal_receipt();
+    withdrawal_receipt.withdrawal_receipt_nonce = withdrawal_receipt_nonce;
+    withdrawal_receipt.wallet_kind = wallet_kind;
+    withdrawal_receipt.index = receipt_index;
+    withdrawal_receipt.amount = withdrawn_amount;
+    withdrawal_receipt.destination = destination;
+    withdrawal_receipt.timestamp = timestamp;
+    withdrawal_receipt.unlocked_total = wallet_vesting_status.unlocked;
+    withdrawal_receipt.already_withdrawn_total = already_withdrawn_total;
+
+    emit!(VestedTokensWithdrawn {
+        wallet_kind,
+        amount: withdrawn_amount,
+        destination,
+        sequence,
+        unlocked_total: wallet_vesting_status.unlocked,
+        already_withdrawn_total,
+        months_since_vesting_start,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// Withdraws all unlocked tokens from every vested wallet to the wallet's deposit wallet, it is the implementation of withdraw_all_unlocked.
+/// The amount available to withdraw from each wallet is calculated the same way as in `withdraw_from_vested_wallet`,
+/// the wallets with nothing available, including the finalized ones, are skipped without checking their deposit wallets.
+/// All transferred amounts are recorded as withdrawn together with the withdrawal history of the wallets,
+/// every wallet gets a withdrawal receipt, with zero amount if it was skipped, and a single `AllUnlockedTokensWithdrawn` event is emitted.
+///
+/// ### Arguments
+///
+/// * `ctx` - the program's context
+///
+/// ### Returns
+/// An error if any of the withdrawals is not valid, otherwise a successful result.
+pub fn withdraw_all_unlocked_tokens(ctx: Context<WithdrawAllUnlockedContext>) -> Result<()> {
+    let sequence = next_sequence(&mut ctx.accounts.contract_state)?;
+    let timestamp = Clock::get()?.unix_timestamp;
+    let wallet_accounts =
+        WalletKind::ALL.map(|wallet_kind| ctx.accounts.wallet_account(wallet_kind));
+
+    let mut vesting_state = ctx.accounts.vesting_state.load_mut()?;
+    let months_since_vesting_start = months_since_vesting_start(&vesting_state, timestamp)?;
+
+    let mut amounts = [0; VESTED_WALLETS_COUNT];
+    let mut unlocked_totals = [0; VESTED_WALLETS_COUNT];
+    let mut receipt_indexes = [0; VESTED_WALLETS_COUNT];
+    for wallet_kind in WalletKind::ALL {
+        let wallet_account = &wallet_accounts[wallet_kind.index()];
+        let deposit_wallet = ctx.accounts.deposit_wallet(wallet_kind);
+        require!(
+            !wallet_accounts
+                .iter()
+                .any(|account| account.key() == deposit_wallet.key())
+                && !is_program_token_account(
+                    &ctx.accounts.contract_state,
+                    wallet_account.key(),
+                    deposit_wallet.key(),
+                )?,
+            LeancoinError::DepositWalletIsProgramAccount
+        );
+
+        let wallet_vesting_status = calculate_wallet_vesting_status(
+            &vesting_state,
+            wallet_kind,
+            wallet_account,
+            timestamp,
+        )?;
+        unlocked_totals[wallet_kind.index()] = wallet_vesting_status.unlocked;
+        receipt_indexes[wallet_kind.index()] = next_receipt_index(&mut vesting_state, wallet_kind)?;
+
+        let withdrawable = wallet_vesting_status.withdrawable;
+        if withdrawable == 0 {
+            continue;
+        }
+
+        require!(
+            deposit_wallet.state == AccountState::Initialized,
+            LeancoinError::DepositWalletFrozen
+        );
+        valid_deposit_wallet_owner(
+            &deposit_wallet.owner,
+            &vesting_state.wallet(wallet_kind).beneficiary_owner,
+        )?;
+
+        let amount = transfer_tokens(
+            wallet_account.to_account_info(),
+            deposit_wallet.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            wallet_kind.seed(),
+            vesting_state.wallet(wallet_kind).nonce,
+            withdrawable,
+        )?;
+        record_withdrawal(&mut vesting_state, wallet_kind, amount)?;
+        record_withdrawal_history(&mut vesting_state, wallet_kind, amount, timestamp);
+        amounts[wallet_kind.index()] = amount;
+    }
+
+    let destinations =
+        WalletKind::ALL.map(|wallet_kind| ctx.accounts.deposit_wallet(wallet_kind).key());
+    let already_withdrawn_totals =
+        WalletKind::ALL.map(|wallet_kind| vesting_state.wallet(wallet_kind).withdrawn);
+    drop(vesting_state);
+
+    for wallet_kind in WalletKind::ALL {
+        let index = wallet_kind.index();
+        let withdrawal_receipt_bump =
+            WithdrawAllUnlockedContext::withdrawal_receipt_bump(wallet_kind);
+        let withdrawal_receipt_nonce = *ctx.bumps.get(withdrawal_receipt_bump).unwrap();
+        *ctx.accounts.withdrawal_receipt(wallet_kind) = WithdrawalReceipt {
+            withdrawal_receipt_nonce,
+            wallet_kind,
+            index: receipt_indexes[index],
+            amount: amounts[index],
+            destination: destinations[index],
+            timestamp,
+            unlocked_total: unlocked_totals[index],
+            already_withdrawn_total: already_withdrawn_totals[index],
+        };
+    }
+
+    emit!(AllUnlockedTokensWithdrawn {
+        amounts,
+        destinations,
+        already_withdrawn_totals,
+        sequence,
+        months_since_vesting_start,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// Adds the amount of tokens which left the vested wallet to its withdrawn amount.
+/// It should be called only after the withdrawal has been validated.
+///
+/// ### Arguments
+///
+/// * `vesting_state` - the current state of the vesting
+/// * `wallet_kind` - the vested wallet
+/// * `amount` - the amount of withdrawn tokens
+///
+/// ### Returns
+/// Error if the withdrawn amount would overflow
+pub fn record_withdrawal(
+    vesting_state: &mut VestingState,
+    wallet_kind: WalletKind,
+    amount: u64,
+) -> Result<()> {
+    let wallet = vesting_state.wallet_mut(wallet_kind);
+    wallet.withdrawn = wallet
+        .withdrawn
+        .checked_add(amount)
+        .ok_or(LeancoinError::WithdrawnAmountOverflow)?;
+    Ok(())
+}
+
+/// Reserves the index of the next withdrawal receipt of the vested wallet, see `VestingState::next_receipt_index`.
+///
+/// ### Arguments
+///
+/// * `vesting_state` - the current state of the vesting
+/// * `wallet_kind` - the vested wallet
+///
+/// ### Returns
+/// The index of the receipt created by the current withdrawal or an error if the index would overflow.
+pub fn next_receipt_index(
+    vesting_state: &mut VestingState,
+    wallet_kind: WalletKind,
+) -> Result<u64> {
+    let next_receipt_index = &mut vesting_state.next_receipt_index[wallet_kind.index()];
+    let receipt_index = *next_receipt_index;
+    *next_receipt_index = receipt_index
+        .checked_add(1)
+        .ok_or(LeancoinError::ReceiptIndexOverflow)?;
+
+    Ok(receipt_index)
+}
+
+/// Records the withdrawal in the withdrawal history of the vested wallet.
+/// The withdrawals which have not moved any tokens are not recorded.
+///
+/// ### Arguments
+///
+/// * `vesting_state` - the current state of the vesting
+/// * `wallet_kind` - the vested wallet
+/// * `amount` - the amount of withdrawn tokens
+/// * `timestamp` - the timestamp of the withdrawal
+pub fn record_withdrawal_history(
+    vesting_state: &mut VestingState,
+    wallet_kind: WalletKind,
+    amount: u64,
+    timestamp: i64,
+) {
+    if amount == 0 {
+        return;
+    }
+
+    let history = &mut vesting_state.withdrawal_history[wallet_kind.index()];
+    history.withdrawal_count = history.withdrawal_count.saturating_add(1);
+    history.last_withdrawal_timestamp = timestamp;
+}
+
+/// Records the withdrawal in the withdrawal intent account if an idempotency key is given.
+/// The account is initialized if needed before the instruction is executed, hence a withdrawal intent that already
+/// has a payer means that the withdrawal with the same idempotency key has already been executed.
+///
+/// ### Arguments
+///
+/// * `withdrawal_intent` - the withdrawal intent account derived from the idempotency key, if passed
+/// * `idempotency_key` - the idempotency key supplied by the client, if any
+/// * `withdrawal_intent_nonce` - the nonce of the withdrawal intent account
+/// * `payer` - the payer of the withdrawal intent account
+/// * `amount` - the amount of withdrawn tokens, resolved from `WITHDRAW_ALL_UNLOCKED`, see `resolve_amount_to_withdraw`
+/// * `timestamp` - the current timestamp
+///
+/// ### Returns
+/// Ok if the withdrawal is not a replay and the withdrawal intent account is passed exactly when the key is given.
+pub fn record_withdrawal_intent(
+    withdrawal_intent: Option<&mut WithdrawalIntent>,
+    idempotency_key: Option<[u8; 16]>,
+    withdrawal_intent_nonce: u8,
+    payer: Pubkey,
+    amount: u64,
+    timestamp: i64,
+) -> Result<()> {
+    match (withdrawal_intent, idempotency_key) {
+        (None, None) => Ok(()),
+        (Some(withdrawal_intent), Some(_)) => {
+            require_keys_eq!(
+                withdrawal_intent.payer,
+                Pubkey::default(),
+                LeancoinError::DuplicateWithdrawal
+            );
+
+            withdrawal_intent.withdrawal_intent_nonce = withdrawal_intent_nonce;
+            withdrawal_intent.payer = payer;
+            withdrawal_intent.amount = amount;
+            withdrawal_intent.created_timestamp = timestamp;
+
+            Ok(())
+        }
+        _ => Err(LeancoinError::WithdrawalIntentMismatch.into()),
+    }
+}
+
+/// Accounts the tokens transferred by the delegate of a vested wallet since the last reconciliation.
+/// The token program decreases the delegated amount of the wallet's token account with every transfer made by
+/// the delegate, so the difference between the recorded allowance and the remaining delegated amount is the amount
+/// transferred out of the wallet. The allowance is already counted as withdrawn when it is approved.
+///
+/// ### Arguments
+///
+/// * `wallet_delegation` - the account tracking the delegation of the wallet
+/// * `token_account_delegate` - the delegate of the wallet's token account
+/// * `delegated_amount` - the remaining delegated amount of the wallet's token account
+///
+/// ### Returns
+/// The amount of tokens transferred by the delegate since the last reconciliation.
+pub fn reconcile_wallet_delegation(
+    wallet_delegation: &mut WalletDelegation,
+    token_account_delegate: COption<Pubkey>,
+    delegated_amount: u64,
+) -> u64 {
+    let remaining_allowance = match token_account_delegate {
+        COption::Some(delegate) if delegate == wallet_delegation.delegate => delegated_amount,
+        _ => 0,
+    };
+    let transferred = wallet_delegation
+        .allowance
+        .saturating_sub(remaining_allowance);
+
+    wallet_delegation.allowance = remaining_allowance.min(wallet_delegation.allowance);
+    // the transferred tokens never exceed the wallet's balance, so the sum cannot overflow
+    wallet_delegation.transferred = wallet_delegation.transferred.saturating_add(transferred);
+
+    transferred
+}
+
+/// Calculates the amount of tokens redirected from the monthly burn to the staking reward vault.
+///
+/// ### Arguments
+///
+/// * `burning_account_balance` - the balance of the burning account before the burn
+/// * `reward_percentage` - the percentage points of the balance redirected to the stakers
+///
+/// ### Returns
+/// The amount of tokens to transfer to the reward vault instead of burning them.
+pub fn calculate_staking_rewards_amount(
+    burning_account_balance: u64,
+    reward_percentage: u8,
+) -> u64 {
+    let rewards = burning_account_balance as u128 * reward_percentage as u128 / 100;
+
+    rewards as u64
+}
+
+/// Calculates the increase of the reward per token accumulator after distributing rewards to the stakers.
+///
+/// ### Arguments
+///
+/// * `rewards` - the amount of distributed rewards
+/// * `total_staked` - the total amount of staked tokens
+///
+/// ### Returns
+/// The increase of the reward per token accumulator, scaled by `REWARD_PER_TOKEN_PRECISION`, or 0 if nothing is staked.
+pub fn calculate_reward_per_token_increase(rewards: u64, total_staked: u64) -> u128 {
+    if total_staked == 0 {
+        return 0;
+    }
+
+    rewards as u128 * REWARD_PER_TOKEN_PRECISION / total_staked as u128
+}
+
+/// Calculates the rewards accumulated by the staked amount since the accumulator was zero.
+///
+/// ### Arguments
+///
+/// * `staked_amount` - the amount of staked tokens
+/// * `reward_per_token` - the reward per token accumulator
+///
+/// ### Returns
+/// The accumulated rewards or an error if the calculation overflows.
+pub fn calculate_reward_debt(staked_amount: u64, reward_per_token: u128) -> Result<u128> {
+    let reward_debt = (staked_amount as u128)
+        .checked_mul(reward_per_token)
+        .ok_or(LeancoinError::RewardsOverflow)?;
+
+    Ok(reward_debt / REWARD_PER_TOKEN_PRECISION)
+}
+
+/// Moves the rewards earned by the stake since the last update into the pending rewards.
+/// It must be called before the staked amount changes, afterwards the reward debt must be recalculated.
+///
+/// ### Arguments
+///
+/// * `stake_account` - the stake to update
+/// * `reward_per_token` - the current reward per token accumulator
+///
+/// ### Returns
+/// An error if the calculation overflows, otherwise a successful result.
+pub fn update_stake_rewards(
+    stake_account: &mut StakeAccount,
+    reward_per_token: u128,
+) -> Result<()> {
+    let accumulated_rewards = calculate_reward_debt(stake_account.staked_amount, reward_per_token)?;
+    let earned_rewards: u64 = (accumulated_rewards - stake_account.reward_debt)
+        .try_into()
+        .map_err(|_| LeancoinError::RewardsOverflow)?;
+
+    stake_account.pending_rewards = stake_account
+        .pending_rewards
+        .checked_add(earned_rewards)
+        .ok_or(LeancoinError::RewardsOverflow)?;
+    stake_account.reward_debt = accumulated_rewards;
+
+    Ok(())
+}
+
+/// Calculates the amount of tokens leaving the burning account in the monthly burn, including the staking rewards.
+/// The amount is calculated in fixed-point: the fraction of a token lost by rounding down is carried over to the next burn,
+/// so the sum of the burns does not drift from the exact burn rate.
+///
+/// ### Arguments
+///
+/// * `burning_account_balance` - the balance of the burning account before the burn
+/// * `burn_remainder_numerator` - the numerator of the fraction carried over from the previous burn, see `BURN_RATE_DENOMINATOR`
+/// * `burn_rate_bps` - the rate of the burn in basis points of the burning account balance
+///
+/// ### Returns
+/// The amount of tokens burned together with the ones redirected to the staking reward vault and the numerator carried over to the next burn.
+pub fn calculate_monthly_burn_amount(
+    burning_account_balance: u64,
+    burn_remainder_numerator: u64,
+    burn_rate_bps: u16,
+) -> (u64, u64) {
+    let owed =
+        burning_account_balance as u128 * burn_rate_bps as u128 + burn_remainder_numerator as u128;
+    let amount = owed / BURN_RATE_DENOMINATOR as u128;
+    let remainder = owed % BURN_RATE_DENOMINATOR as u128;
+
+    (amount as u64, remainder as u64)
+}
+
+/// Calculates the amount of tokens leaving the burning account in the catch-up burn, including the staking rewards.
+/// The monthly burn is applied once per month to the balance left by the previous one, so the burn rate is compounded.
+///
+/// ### Arguments
+///
+/// * `burning_account_balance` - the balance of the burning account before the burn
+/// * `burn_remainder_numerator` - the numerator of the fraction carried over from the previous burn, see `BURN_RATE_DENOMINATOR`
+/// * `burn_rate_bps` - the rate of the burn in basis points of the burning account balance
+/// * `reward_percentage` - the percentage points of the balance redirected to the stakers every month, zero if nothing is staked
+/// * `months` - the number of monthly burns to apply
+///
+/// ### Returns
+/// The amount of tokens burned together with the ones redirected to the staking reward vault, the staking rewards and the numerator carried over to the next burn.
+pub fn calculate_catch_up_burn_amount(
+    burning_account_balance: u64,
+    burn_remainder_numerator: u64,
+    burn_rate_bps: u16,
+    reward_percentage: u8,
+    months: u64,
+) -> (u64, u64, u64) {
+    let mut balance = burning_account_balance;
+    let mut remainder = burn_remainder_numerator;
+    let mut amount = 0;
+    let mut rewards = 0;
+
+    for _ in 0..months {
+        let (monthly_amount, monthly_remainder) =
+            calculate_monthly_burn_amount(balance, remainder, burn_rate_bps);
+        rewards += calculate_staking_rewards_amount(balance, reward_percentage).min(monthly_amount);
+        amount += monthly_amount;
+        balance -= monthly_amount;
+        remainder = monthly_remainder;
+    }
+
+    (amount, rewards, remainder)
+}
+
+/// Calculates the reward of the burn caller transferred from the burning account instead of being burned.
+///
+/// ### Arguments
+///
+/// * `amount` - the amount of tokens to be burned, without the staking rewards
+/// * `burn_caller_reward_bps` - the reward of the burn caller in basis points of the burned amount
+///
+/// ### Returns
+/// The reward rounded down, zero if it is less than a token.
+pub fn calculate_burn_caller_reward(amount: u64, burn_caller_reward_bps: u16) -> u64 {
+    (amount as u128 * burn_caller_reward_bps as u128 / BURN_RATE_DENOMINATOR as u128) as u64
+}
+
+/// Projects the mint supply after the given number of future monthly burns.
+/// Every burn is applied the same way as in the burn instruction, the staking rewards leave the burning account without being burned.
+/// The contract has no configured supply floor, the supply stops decreasing once the burning account is emptied.
+///
+/// ### Arguments
+///
+/// * `supply` - the current supply of the mint
+/// * `burning_account_balance` - the current balance of the burning account
+/// * `burn_remainder_numerator` - the numerator of the fraction carried over from the last burn
+/// * `burn_rate_bps` - the rate of the monthly burn in basis points of the burning account balance
+/// * `reward_percentage` - the percentage points of the burning account balance redirected to the stakers, zero if there are no stakers
+/// * `months` - the number of monthly burns to apply, at most `MAX_PROJECTED_MONTHS`
+///
+/// ### Returns
+/// The projected supply or an error if the number of months is too high.
+pub fn calculate_supply_projection(
+    supply: u64,
+    burning_account_balance: u64,
+    burn_remainder_numerator: u64,
+    burn_rate_bps: u16,
+    reward_percentage: u8,
+    months: u8,
+) -> Result<SupplyProjection> {
+    require!(
+        months <= MAX_PROJECTED_MONTHS,
+        LeancoinError::ProjectionTooLong
+    );
+
+    let mut supply = supply as u128;
+    let mut burning_account_balance = burning_account_balance;
+    let mut burn_remainder_numerator = burn_remainder_numerator;
+    let mut burned = 0u128;
+    let mut floor_month = None;
+
+    for month in 1..=months {
+        if burning_account_balance == 0 {
+            floor_month = Some(month);
+            break;
+        }
+
+        let (amount, remainder) = calculate_monthly_burn_amount(
+            burning_account_balance,
+            burn_remainder_numerator,
+            burn_rate_bps,
+        );
+        let rewards = calculate_staking_rewards_amount(burning_account_balance, reward_percentage);
+        let burned_in_month = amount.saturating_sub(rewards) as u128;
+
+        burning_account_balance -= amount;
+        burn_remainder_numerator = remainder;
+        supply -= burned_in_month;
+        burned += burned_in_month;
+    }
+
+    Ok(SupplyProjection {
+        supply: supply as u64,
+        burned: burned as u64,
+        burning_account_balance,
+        floor_month,
+    })
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::account::{WithdrawalHistory, CONTRACT_STATE_VERSION};
+    use crate::{
+        DEFAULT_BURN_RATE_BPS, DEFAULT_BURN_WINDOW_DAYS, DEFAULT_LATE_BURN_WINDOW_FIRST_DAY,
+        DEFAULT_LATE_BURN_WINDOW_LAST_DAY, MAX_KEEPERS, MIGRATION_SOURCES_COUNT,
+    };
+    use anchor_lang::solana_program::hash::hashv;
+    use anchor_lang::solana_program::program_pack::Pack;
+    use anchor_spl::token::spl_token::state::Account as SplTokenAccount;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use test_case::test_case;
+
+    impl std::fmt::Debug for ContractState {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("ContractState")
+                .field("version", &self.version)
+                .field("mint_nonce", &self.mint_nonce)
+                .field("performed_imports", &self.performed_imports)
+                .field("imported_minted", &self.imported_minted)
+                .field("imported_burned", &self.imported_burned)
+                .field("max_supply", &self.max_supply)
+                .field("wallets_initialized", &self.wallets_initialized)
+                .field("program_account_nonce", &self.program_account_nonce)
+                .field("burning_account_nonce", &self.burning_account_nonce)
+                .field("last_burning_month", &self.last_burning_month)
+                .field("last_burning_year", &self.last_burning_year)
+                .field("cpi_allowed", &self.cpi_allowed)
+                .field("staking_enabled", &self.staking_enabled)
+                .field("total_donated", &self.total_donated)
+                .field("configuration_locked", &self.configuration_locked)
+                .field("sequence", &self.sequence)
+                .field("authority", &self.authority)
+                .field("keepers", &self.keepers)
+                .finish()
+        }
+    }
+
+    impl ContractState {
+        pub fn default() -> Self {
+            Self {
+                version: CONTRACT_STATE_VERSION,
+                contract_state_nonce: 0,
+                mint_nonce: 0,
+                performed_imports: 0,
+                imported_minted: [0; MIGRATION_SOURCES_COUNT],
+                imported_burned: [0; MIGRATION_SOURCES_COUNT],
+                max_supply: 0,
+                wallets_initialized: false,
+                program_account_nonce: 0,
+                burning_account_nonce: 0,
+                last_burning_month: 0,
+                last_burning_year: 0,
+                cpi_allowed: false,
+                staking_enabled: false,
+                total_donated: 0,
+                metadata_program_id: Pubkey::default(),
+                burn_remainder_numerator: 0,
+                withdrawal_grace_period_seconds: 0,
+                keepers: [Pubkey::default(); MAX_KEEPERS],
+                late_burn_window_first_day: DEFAULT_LATE_BURN_WINDOW_FIRST_DAY,
+                late_burn_window_last_day: DEFAULT_LATE_BURN_WINDOW_LAST_DAY,
+                paused: false,
+                renounced: false,
+                burn_authority: Pubkey::default(),
+                withdraw_authority: Pubkey::default(),
+                metadata_authority: Pubkey::default(),
+                mint_authority_revoked: false,
+                decimals: 9,
+                mint: Pubkey::default(),
+                burn_rate_bps: DEFAULT_BURN_RATE_BPS,
+                burn_window_days: DEFAULT_BURN_WINDOW_DAYS,
+                burn_caller_reward_bps: 0,
+                total_donated_for_burning: 0,
+                total_burned: 0,
+                configuration_locked: false,
+                sequence: 0,
+                authority: Pubkey::new_unique(),
+            }
+        }
+    }
 
     #[test]
-    fn test_parse_timestamp_error() {
-        let parsed_timestamp = parse_timestamp(-1);
-        assert!(parsed_timestamp.is_err());
+    fn test_months_since_vesting_start() {
+        let mut vesting_state: VestingState = bytemuck::Zeroable::zeroed();
+        assert_eq!(
+            months_since_vesting_start(&vesting_state, 1677978061).unwrap_err(),
+            LeancoinError::VestingNotStarted.into()
+        );
+
+        vesting_state.start_timestamp = 1677978061;
+        assert_eq!(
+            months_since_vesting_start(&vesting_state, 1677978061 + 31 * 24 * 60 * 60).unwrap(),
+            1
+        );
     }
 
     #[test]
-    #[should_panic]
-    fn test_parse_timestamp_negative() {
-        let timestamp: i64 = -1;
-        parse_timestamp(timestamp).unwrap();
-    }
-
-    #[test_case( 1620000000, 1620000000 + 60 * 60 * 24 * 15, 0; "start = 03/05/21, end = 18/05/21, same month")]
-    #[test_case( 1620000000, 1620000000, 0; "start = 03/05/21, end = 03/05/21, same month")]
-    #[test_case( 1620000000, 1620000000 + 60 * 60 * 24 * 31 - (2 * 24 * 60 * 60), 1; "start = 03/05/21, end = 01/06/21, 1 month")]
-    #[test_case( 1620000000, 1620000000 + 60 * 60 * 24 * 31, 1; "start = 03/05/21, end = 03/06/21, 1 month")]
-    #[test_case( 1620000000 + 60 * 60 * 24 * 15, 1620000000 + 60 * 60 * 24 * 30, 1; "start = 18/05/21, end = 02/06/21, 1 month")]
-    #[test_case( 1620000000, 1620000000 + 60 * 60 * 24 * 31 * 2, 2; "start = 03/05/21, end = 04/07/21, 2 months")]
-    #[test_case( 1620000000, 1620000000 + 60 * 60 * 24 * 31 * 3, 3; "start = 03/05/21, end = 04/08/21, 3 months")]
-    #[test_case( 1620000000, 1620000000 + 60 * 60 * 24 * 31 * 11, 11; "start = 03/05/21, end = 09/04/22, 11 months")]
-    #[test_case( 1620000000, 1620000000 + 60 * 60 * 24 * 31 * 12, 12; "start = 03/05/21, end = 10/05/22, 12 months")]
-    #[test_case( 1620000000, 1620000000 + 60 * 60 * 24 * 31 * 13, 13; "start = 03/05/21, end = 10/06/22, 13 months")]
-
-    fn test_calculate_month_difference(start: i64, end: i64, expected: u64) {
-        let months_since_vesting_start = calculate_month_difference(start, end).unwrap();
-        assert_eq!(months_since_vesting_start, expected);
-    }
-
-    #[test_case(1000000000, 0, 0; "0 months")]
-    #[test_case(1000000000, 1, 500000000; "1 month")]
-    #[test_case(1000000000, 2, 1000000000; "2 months")]
-    #[test_case(1000000000, 3, 1000000000; "3 months")]
-    fn test_calculate_unlocked_amount_partnership_wallet(
-        vesting_start_account_balance: u64,
+    fn test_remaining_unlocked_amount() {
+        let mut vesting_state: VestingState = bytemuck::Zeroable::zeroed();
+        vesting_state.wallet_mut(WalletKind::Marketing).withdrawn = 40;
+        assert_eq!(
+            remaining_unlocked_amount(&vesting_state, WalletKind::Marketing, 100).unwrap(),
+            60
+        );
+        assert_eq!(
+            remaining_unlocked_amount(&vesting_state, WalletKind::Marketing, 40).unwrap(),
+            0
+        );
+        assert_eq!(
+            remaining_unlocked_amount(&vesting_state, WalletKind::Marketing, 39).unwrap_err(),
+            LeancoinError::WithdrawnMoreThanUnlocked.into()
+        );
+    }
+
+    #[test]
+    fn test_record_withdrawal() {
+        let mut vesting_state: VestingState = bytemuck::Zeroable::zeroed();
+        record_withdrawal(&mut vesting_state, WalletKind::Liquidity, 7).unwrap();
+        record_withdrawal(&mut vesting_state, WalletKind::Liquidity, 3).unwrap();
+        assert_eq!(vesting_state.wallet(WalletKind::Liquidity).withdrawn, 10);
+
+        assert_eq!(
+            record_withdrawal(&mut vesting_state, WalletKind::Liquidity, u64::MAX).unwrap_err(),
+            LeancoinError::WithdrawnAmountOverflow.into()
+        );
+        assert_eq!(vesting_state.wallet(WalletKind::Liquidity).withdrawn, 10);
+    }
+
+    #[test]
+    fn test_next_receipt_index() {
+        let mut vesting_state: VestingState = bytemuck::Zeroable::zeroed();
+        assert_eq!(
+            next_receipt_index(&mut vesting_state, WalletKind::Partnership).unwrap(),
+            0
+        );
+        assert_eq!(
+            next_receipt_index(&mut vesting_state, WalletKind::Partnership).unwrap(),
+            1
+        );
+        assert_eq!(vesting_state.next_receipt_index, [0, 2, 0, 0]);
+
+        vesting_state.next_receipt_index[WalletKind::Community.index()] = u64::MAX;
+        assert_eq!(
+            next_receipt_index(&mut vesting_state, WalletKind::Community).unwrap_err(),
+            LeancoinError::ReceiptIndexOverflow.into()
+        );
+        assert_eq!(
+            vesting_state.next_receipt_index[WalletKind::Community.index()],
+            u64::MAX
+        );
+    }
+
+    #[test]
+    fn test_record_withdrawal_history() {
+        let mut vesting_state: VestingState = bytemuck::Zeroable::zeroed();
+        record_withdrawal_history(&mut vesting_state, WalletKind::Marketing, 7, 1680652800);
+        record_withdrawal_history(&mut vesting_state, WalletKind::Marketing, 3, 1683244800);
+        // nothing has been withdrawn
+        record_withdrawal_history(&mut vesting_state, WalletKind::Marketing, 0, 1685923200);
+
+        let history = vesting_state.history(WalletKind::Marketing);
+        assert_eq!(history.withdrawal_count, 2);
+        assert_eq!(history.last_withdrawal_timestamp, 1683244800);
+        assert_eq!(
+            *vesting_state.history(WalletKind::Community),
+            WithdrawalHistory::default()
+        );
+    }
+
+    #[test]
+    fn test_ethereum_token_state_mapping_already_performed() {
+        let mut state = ContractState::default();
+        assert_eq!(
+            ethereum_token_state_mapping_already_performed(&state).unwrap_err(),
+            LeancoinError::ImportNotPerformedYet.into()
+        );
+
+        state.performed_imports = MigrationSource::Bsc.mask();
+        ethereum_token_state_mapping_already_performed(&state).unwrap();
+    }
+
+    #[test]
+    fn test_core_errors_are_mapped_to_contract_errors() {
+        assert_eq!(
+            parse_timestamp(-1).unwrap_err(),
+            LeancoinError::InvalidTimestamp.into()
+        );
+        assert_eq!(
+            calculate_month_difference(1, 0).unwrap_err(),
+            LeancoinError::EndTimeMustBeLaterThanStartTime.into()
+        );
+        assert_eq!(
+            calculate_month_difference(0, i64::MAX).unwrap_err(),
+            LeancoinError::TimestampTooFarInFuture.into()
+        );
+    }
+
+    #[test_case(WalletKind::Community, 3, 4, 50000000; "community with delay")]
+    #[test_case(WalletKind::Marketing, 3, 13, 450000000; "marketing ignores the delay")]
+    fn test_calculate_unlocked_amount(
+        wallet_kind: WalletKind,
+        community_unlock_delay_months: u8,
         months_since_vesting_start: u64,
         expected: u64,
     ) {
-        let amount_unlocked = calculate_unlocked_amount_partnership_wallet(
-            vesting_start_account_balance,
-            months_since_vesting_start,
+        let mut vesting_state: VestingState = bytemuck::Zeroable::zeroed();
+        vesting_state.schedules = VestingSchedule::defaults();
+        vesting_state.community_unlock_delay_months = community_unlock_delay_months;
+
+        assert_eq!(
+            calculate_unlocked_amount(
+                &vesting_schedule(&vesting_state, wallet_kind),
+                1000000000,
+                months_since_vesting_start,
+            ),
+            expected
         );
-        assert_eq!(amount_unlocked, expected);
-    }
-
-    #[test_case(1000000000, 1, 0; "1 month")]
-    #[test_case(1000000000, 2, 0; "2 months")]
-    #[test_case(1000000000, 3, 0; "3 months")]
-    #[test_case(1000000000, 12, 400000000; "12 months")]
-    #[test_case(1000000000, 13, 450000000; "13 months")]
-    #[test_case(1000000000, 50, 1000000000; "50 months")]
-    #[test_case(1000000000, 100, 1000000000; "100 months")]
-    #[test_case(0, 11, 0; "11 months with 0 tokens - no unlocked tokens")]
-    #[test_case(0, 12, 0; "12 months with 0 tokens - no unlocked tokens")]
-    #[test_case(0, 13, 0; "13 months with 0 tokens - no unlocked tokens")]
-    #[test_case(0, 50, 0; "50 months with 0 tokens - no unlocked tokens")]
-    #[test_case(0, 100, 0; "100 months with 0 tokens - no unlocked tokens")]
-    #[test_case(1, 11, 0; "11 months with 1 token - no unlocked tokens")]
-    #[test_case(1, 12, 1; "12 months with 1 token - one token unlocked")]
-    #[test_case(1, 13, 1; "13 months with 1 token - one token unlocked")]
-    #[test_case(1, 50, 1; "50 months with 1 token - one token unlocked")]
-    #[test_case(1, 100, 1; "100 months with 1 token - one token unlocked")]
-    fn test_calculate_unlocked_amount_marketing_wallet(
-        vesting_start_account_balance: u64,
-        months_since_vesting_start: u64,
+    }
+
+    #[test]
+    fn test_calculate_unlocked_amount_with_custom_schedule() {
+        // 10% after half a year and 15% every following month
+        let schedule = VestingSchedule {
+            initial_unlock_bps: 1000,
+            monthly_unlock_bps: 1500,
+            cliff_months: 6,
+            full_unlock_months: 6,
+            minimum_unlock: 0,
+        };
+
+        assert_eq!(calculate_unlocked_amount(&schedule, 1000000000, 5), 0);
+        assert_eq!(
+            calculate_unlocked_amount(&schedule, 1000000000, 6),
+            100000000
+        );
+        assert_eq!(
+            calculate_unlocked_amount(&schedule, 1000000000, 8),
+            400000000
+        );
+        assert_eq!(
+            calculate_unlocked_amount(&schedule, 1000000000, 12),
+            1000000000
+        );
+    }
+
+    #[test]
+    fn test_valid_vesting_schedules() {
+        valid_vesting_schedules(&None).unwrap();
+        valid_vesting_schedules(&Some(VestingSchedule::defaults())).unwrap();
+
+        let mut vesting_schedules = VestingSchedule::defaults();
+        vesting_schedules[WalletKind::Marketing.index()].monthly_unlock_bps = 501;
+        assert_eq!(
+            valid_vesting_schedules(&Some(vesting_schedules)).unwrap_err(),
+            LeancoinError::InvalidVestingSchedule.into()
+        );
+    }
+
+    #[test_case(1000000000000000000000000000, 18, 9, 1000000000000000000; "18 to 9 decimals")]
+    #[test_case(1000000000000000000, 9, 9, 1000000000000000000; "same decimals")]
+    #[test_case(0, 18, 9, 0; "zero amount")]
+    #[test_case(1000000000, 0, 9, 1000000000000000000; "0 to 9 decimals")]
+    fn test_scale_amount_to_mint_decimals(
+        amount: u128,
+        source_decimals: u8,
+        mint_decimals: u8,
         expected: u64,
     ) {
-        let amount_unlocked = calculate_unlocked_amount_marketing_wallet(
-            vesting_start_account_balance,
-            months_since_vesting_start,
+        let scaled_amount =
+            scale_amount_to_mint_decimals(amount, source_decimals, mint_decimals).unwrap();
+        assert_eq!(scaled_amount, expected);
+    }
+
+    #[test_case(1000000000000000001, 18, 9; "non-zero remainder")]
+    #[test_case(1, 255, 9; "divisor out of range")]
+    fn test_fail_scale_amount_to_mint_decimals_with_precision_loss(
+        amount: u128,
+        source_decimals: u8,
+        mint_decimals: u8,
+    ) {
+        let scaled_amount = scale_amount_to_mint_decimals(amount, source_decimals, mint_decimals);
+        assert!(scaled_amount.is_err());
+    }
+
+    #[test_case(100000000000000000000000000000, 18, 9; "scaled amount exceeds u64")]
+    #[test_case(u128::MAX, 0, 9; "multiplication overflow")]
+    fn test_fail_scale_amount_to_mint_decimals_with_overflow(
+        amount: u128,
+        source_decimals: u8,
+        mint_decimals: u8,
+    ) {
+        let scaled_amount = scale_amount_to_mint_decimals(amount, source_decimals, mint_decimals);
+        assert!(scaled_amount.is_err());
+    }
+
+    #[test]
+    fn test_ethereum_token_state_mapping_not_performed_yet() {
+        let state = ContractState {
+            performed_imports: MigrationSource::Bsc.mask(),
+            ..ContractState::default()
+        };
+        ethereum_token_state_mapping_not_performed_yet(&state).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fail_ethereum_token_state_mapping_not_performed_yet() {
+        let state = ContractState {
+            performed_imports: MigrationSource::Ethereum.mask(),
+            ..ContractState::default()
+        };
+        ethereum_token_state_mapping_not_performed_yet(&state).unwrap();
+    }
+
+    #[test_case(0, MigrationSource::Ethereum, true; "nothing imported")]
+    #[test_case(1, MigrationSource::Bsc, true; "only Ethereum imported")]
+    #[test_case(1, MigrationSource::Ethereum, false; "Ethereum already imported")]
+    #[test_case(3, MigrationSource::Bsc, false; "both imported")]
+    fn test_import_not_performed_yet(
+        performed_imports: u8,
+        source: MigrationSource,
+        expected: bool,
+    ) {
+        let state = ContractState {
+            performed_imports,
+            ..ContractState::default()
+        };
+        assert_eq!(import_not_performed_yet(&state, source).is_ok(), expected);
+    }
+
+    #[test_case(0, MigrationSource::Ethereum, false; "nothing imported")]
+    #[test_case(2, MigrationSource::Ethereum, false; "only BSC imported")]
+    #[test_case(1, MigrationSource::Ethereum, true; "Ethereum imported")]
+    #[test_case(3, MigrationSource::Bsc, true; "both imported")]
+    fn test_import_performed(performed_imports: u8, source: MigrationSource, expected: bool) {
+        let state = ContractState {
+            performed_imports,
+            ..ContractState::default()
+        };
+        assert_eq!(import_performed(&state, source).is_ok(), expected);
+    }
+
+    #[test]
+    fn test_wallets_not_initialized_yet() {
+        let state = ContractState {
+            wallets_initialized: false,
+            ..ContractState::default()
+        };
+        wallets_not_initialized_yet(&state).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fail_wallets_not_initialized_yet() {
+        let state = ContractState {
+            wallets_initialized: true,
+            ..ContractState::default()
+        };
+        wallets_not_initialized_yet(&state).unwrap();
+    }
+
+    #[test]
+    fn test_wallets_initialized() {
+        let state = ContractState {
+            wallets_initialized: true,
+            ..ContractState::default()
+        };
+        wallets_initialized(&state).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fail_wallets_initialized() {
+        let state = ContractState {
+            wallets_initialized: false,
+            ..ContractState::default()
+        };
+        wallets_initialized(&state).unwrap();
+    }
+
+    #[test_case(crate::CONTRACT_STATE_SEED; "contract state")]
+    #[test_case(crate::MINT_SEED; "mint")]
+    #[test_case(crate::LIQUIDITY_ACCOUNT_SEED; "liquidity account")]
+    fn test_create_program_address_matches_find_program_address(seed: &str) {
+        let (expected, nonce) = Pubkey::find_program_address(&[seed.as_bytes()], &crate::ID);
+
+        assert_eq!(create_program_address(seed, nonce).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_encode_label() {
+        let label = encode_label("Leancoin: Marketing Vesting".as_bytes()).unwrap();
+
+        assert_eq!(&label[..27], "Leancoin: Marketing Vesting".as_bytes());
+        assert_eq!(label[27..], [0u8; 5]);
+    }
+
+    #[test_case(&[b'a'; 33]; "over-length label")]
+    #[test_case(&[0xff, 0xfe]; "non-UTF8 label")]
+    #[test_case(&[b'a', 0xc3]; "truncated multi-byte character")]
+    fn test_fail_encode_label(label: &[u8]) {
+        assert!(encode_label(label).is_err());
+    }
+
+    #[test]
+    fn test_encode_label_of_maximal_length() {
+        let label = "ż".repeat(LABEL_LENGTH / 2);
+
+        assert_eq!(
+            encode_label(label.as_bytes()).unwrap(),
+            label.as_bytes()[..]
+        );
+    }
+
+    #[test_case(0, 100, 100 ; "minting up to the maximal supply")]
+    #[test_case(40, 60, 100 ; "minting the rest of the maximal supply")]
+    #[test_case(100, 0, 100 ; "minting nothing at the maximal supply")]
+    fn test_within_max_supply(supply: u64, amount: u64, max_supply: u64) {
+        within_max_supply(supply, amount, max_supply).unwrap();
+    }
+
+    #[test_case(0, 101, 100 ; "minting above the maximal supply")]
+    #[test_case(100, 1, 100 ; "second mint at the maximal supply")]
+    #[test_case(u64::MAX, 1, u64::MAX ; "supply overflow")]
+    #[should_panic]
+    fn test_fail_within_max_supply(supply: u64, amount: u64, max_supply: u64) {
+        within_max_supply(supply, amount, max_supply).unwrap();
+    }
+
+    #[test]
+    fn test_next_sequence() {
+        let mut state = ContractState::default();
+
+        assert_eq!(next_sequence(&mut state).unwrap(), 1);
+        assert_eq!(next_sequence(&mut state).unwrap(), 2);
+        assert_eq!(state.sequence, 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fail_next_sequence_overflow() {
+        let mut state = ContractState {
+            sequence: u64::MAX,
+            ..ContractState::default()
+        };
+        next_sequence(&mut state).unwrap();
+    }
+
+    fn empty_withdrawal_intent() -> WithdrawalIntent {
+        WithdrawalIntent {
+            withdrawal_intent_nonce: 0,
+            payer: Pubkey::default(),
+            amount: 0,
+            created_timestamp: 0,
+        }
+    }
+
+    #[test_case(100, 250, 100 ; "requested amount")]
+    #[test_case(300, 250, 300 ; "requested amount above the available one")]
+    #[test_case(WITHDRAW_ALL_UNLOCKED, 250, 250 ; "whole available amount")]
+    #[test_case(WITHDRAW_ALL_UNLOCKED, 0, 0 ; "nothing available")]
+    fn test_resolve_amount_to_withdraw(
+        amount_to_withdraw: u64,
+        amount_available_to_withdraw: u64,
+        expected_amount: u64,
+    ) {
+        assert_eq!(
+            resolve_amount_to_withdraw(amount_to_withdraw, amount_available_to_withdraw),
+            expected_amount
+        );
+    }
+
+    #[test]
+    fn test_record_withdrawal_intent() {
+        let mut withdrawal_intent = empty_withdrawal_intent();
+        let payer = Pubkey::new_unique();
+
+        record_withdrawal_intent(
+            Some(&mut withdrawal_intent),
+            Some([1; 16]),
+            254,
+            payer,
+            100,
+            1677978061,
+        )
+        .unwrap();
+
+        assert_eq!(withdrawal_intent.withdrawal_intent_nonce, 254);
+        assert_eq!(withdrawal_intent.payer, payer);
+        assert_eq!(withdrawal_intent.amount, 100);
+        assert_eq!(withdrawal_intent.created_timestamp, 1677978061);
+    }
+
+    #[test]
+    fn test_record_withdrawal_without_idempotency_key() {
+        record_withdrawal_intent(None, None, 0, Pubkey::new_unique(), 100, 1677978061).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fail_record_withdrawal_intent_twice() {
+        let mut withdrawal_intent = WithdrawalIntent {
+            payer: Pubkey::new_unique(),
+            ..empty_withdrawal_intent()
+        };
+
+        record_withdrawal_intent(
+            Some(&mut withdrawal_intent),
+            Some([1; 16]),
+            254,
+            Pubkey::new_unique(),
+            100,
+            1677978061,
+        )
+        .unwrap();
+    }
+
+    #[test_case(true, None ; "intent account without idempotency key")]
+    #[test_case(false, Some([1; 16]) ; "idempotency key without intent account")]
+    #[should_panic]
+    fn test_fail_record_withdrawal_intent_mismatch(
+        with_account: bool,
+        idempotency_key: Option<[u8; 16]>,
+    ) {
+        let mut withdrawal_intent = empty_withdrawal_intent();
+
+        record_withdrawal_intent(
+            with_account.then_some(&mut withdrawal_intent),
+            idempotency_key,
+            254,
+            Pubkey::new_unique(),
+            100,
+            1677978061,
         )
         .unwrap();
-        assert_eq!(amount_unlocked, expected);
-    }
-
-    #[test_case(1000000000, 1, 50000000; "1 month")]
-    #[test_case(1000000000, 2, 75000000; "2 months")]
-    #[test_case(1000000000, 3, 100000000; "3 months")]
-    #[test_case(1000000000, 4, 125000000; "4 months")]
-    #[test_case(1000000000, 5, 150000000; "5 months")]
-    #[test_case(1000000000, 11, 300000000; "11 months")]
-    #[test_case(1000000000, 12, 325000000; "12 months")]
-    #[test_case(1000000000, 13, 350000000; "13 months")]
-    #[test_case(1000000000, 38, 975000000; "38 months")]
-    #[test_case(1000000000, 39, 1000000000; "39 months")]
-    #[test_case(1000000000, 40, 1000000000; "40 months")]
-    #[test_case(1000000000, 100, 1000000000; "100 months")]
-    #[test_case(0, 1, 0; "1 month with 0 tokens - no unlocked tokens")]
-    #[test_case(0, 38, 0; "38 months with 0 tokens - no unlocked tokens")]
-    #[test_case(0, 39, 0; "39 months with 0 tokens - no unlocked tokens")]
-    #[test_case(0, 100, 0; "100 months with 0 tokens - no unlocked tokens")]
-    #[test_case(1, 1, 1; "1 month with 1 token - one token unlocked")]
-    #[test_case(1, 38, 1; "38 months with 1 token - one token unlocked")]
-    #[test_case(1, 39, 1; "39 months with 1 token - one token unlocked")]
-    #[test_case(1, 100, 1; "100 months with 1 token - one token unlocked")]
-    #[test_case(1000000000000000000, 100, 1000000000000000000; "100 months with 1000000000000000000 token - 1000000000000000000 token unlocked")]
-    fn test_calculate_unlocked_amount_community_wallet(
-        vesting_start_account_balance: u64,
-        months_since_vesting_start: u64,
-        expected: u64,
+    }
+
+    #[test_case(COption::Some(0), 100, 0, 100, 0 ; "nothing transferred")]
+    #[test_case(COption::Some(0), 40, 60, 40, 60 ; "partially transferred")]
+    #[test_case(COption::Some(0), 0, 100, 0, 100 ; "fully transferred")]
+    #[test_case(COption::None, 0, 100, 0, 100 ; "delegate removed after full transfer")]
+    #[test_case(COption::Some(1), 100, 100, 0, 100 ; "other delegate")]
+    fn test_reconcile_wallet_delegation(
+        token_account_delegate_index: COption<usize>,
+        delegated_amount: u64,
+        expected_transferred: u64,
+        expected_allowance: u64,
+        expected_total_transferred: u64,
     ) {
-        let amount_unlocked = calculate_unlocked_amount_community_wallet(
-            vesting_start_account_balance,
-            months_since_vesting_start,
+        let delegates = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let mut wallet_delegation = WalletDelegation {
+            wallet_delegation_nonce: 254,
+            delegate: delegates[0],
+            allowance: 100,
+            transferred: 0,
+        };
+
+        let transferred = reconcile_wallet_delegation(
+            &mut wallet_delegation,
+            token_account_delegate_index.map(|index| delegates[index]),
+            delegated_amount,
         );
-        assert_eq!(amount_unlocked, expected);
+
+        assert_eq!(transferred, expected_transferred);
+        assert_eq!(wallet_delegation.allowance, expected_allowance);
+        assert_eq!(wallet_delegation.transferred, expected_total_transferred);
     }
 
-    #[test_case(1000000000, 1, 500000000; "1 month")]
-    #[test_case(1000000000, 2, 500000000; "2 months")]
-    #[test_case(1000000000, 3, 500000000; "3 months")]
-    #[test_case(1000000000, 4, 500000000; "4 months")]
-    #[test_case(1000000000, 5, 500000000; "5 months")]
-    #[test_case(1000000000, 11, 500000000; "11 months")]
-    #[test_case(1000000000, 12, 1000000000; "12 months")]
-    #[test_case(1000000000, 13, 1000000000; "13 months")]
-    #[test_case(1000000000, 100, 1000000000; "100 months")]
+    #[test]
+    fn test_reconcile_wallet_delegation_twice() {
+        let delegate = Pubkey::new_unique();
+        let mut wallet_delegation = WalletDelegation {
+            wallet_delegation_nonce: 254,
+            delegate,
+            allowance: 100,
+            transferred: 0,
+        };
 
-    fn test_calculate_unlocked_amount_liquidity_wallet(
-        vesting_start_account_balance: u64,
-        months_since_vesting_start: u64,
-        expected: u64,
-    ) {
-        let amount_unlocked = calculate_unlocked_amount_liquidity_wallet(
-            vesting_start_account_balance,
-            months_since_vesting_start,
+        assert_eq!(
+            reconcile_wallet_delegation(&mut wallet_delegation, COption::Some(delegate), 70),
+            30
+        );
+        assert_eq!(
+            reconcile_wallet_delegation(&mut wallet_delegation, COption::Some(delegate), 70),
+            0
         );
-        assert_eq!(amount_unlocked, expected);
+        assert_eq!(
+            reconcile_wallet_delegation(&mut wallet_delegation, COption::Some(delegate), 20),
+            50
+        );
+        assert_eq!(wallet_delegation.allowance, 20);
+        assert_eq!(wallet_delegation.transferred, 80);
     }
 
     #[test]
-    fn test_ethereum_token_state_mapping_not_performed_yet() {
+    fn test_configuration_not_locked() {
         let state = ContractState {
-            import_ethereum_token_state_already_performed: false,
+            configuration_locked: false,
             ..ContractState::default()
         };
-        ethereum_token_state_mapping_not_performed_yet(&state).unwrap();
+        configuration_not_locked(&state).unwrap();
     }
 
     #[test]
     #[should_panic]
-    fn test_fail_ethereum_token_state_mapping_not_performed_yet() {
+    fn test_fail_configuration_not_locked() {
         let state = ContractState {
-            import_ethereum_token_state_already_performed: true,
+            configuration_locked: true,
             ..ContractState::default()
         };
-        ethereum_token_state_mapping_not_performed_yet(&state).unwrap();
+        configuration_not_locked(&state).unwrap();
+    }
+
+    #[test]
+    fn test_calculate_reserves_attestation_hash_is_deterministic() {
+        let balances = [1, 2, 3, 4, 5, 6];
+
+        assert_eq!(
+            calculate_reserves_attestation_hash(100, 21, &balances),
+            calculate_reserves_attestation_hash(100, 21, &balances)
+        );
+        assert_ne!(
+            calculate_reserves_attestation_hash(100, 21, &balances),
+            calculate_reserves_attestation_hash(101, 21, &balances)
+        );
+        assert_ne!(
+            calculate_reserves_attestation_hash(100, 21, &balances),
+            calculate_reserves_attestation_hash(100, 21, &[2, 1, 3, 4, 5, 6])
+        );
+    }
+
+    #[test]
+    fn test_calculate_reserves_attestation_hash_layout() {
+        let balances = [1u64, 2, 3, 4, 5, 6];
+        let mut hashed_data = vec![100u64.to_le_bytes(), 21u64.to_le_bytes()];
+        hashed_data.extend(balances.map(u64::to_le_bytes));
+
+        assert_eq!(
+            calculate_reserves_attestation_hash(100, 21, &balances),
+            hashv(
+                &hashed_data
+                    .iter()
+                    .map(|data| data.as_slice())
+                    .collect::<Vec<_>>()
+            )
+            .to_bytes()
+        );
+    }
+
+    #[test]
+    fn test_verify_reserves_attestation() {
+        let balances = [1, 2, 3, 4, 5, 6];
+        let attestation = Attestation {
+            attestation_nonce: 0,
+            last_attestation_slot: 100,
+            last_attestation_timestamp: 0,
+            last_attestation_hash: calculate_reserves_attestation_hash(100, 21, &balances),
+        };
+
+        assert!(verify_reserves_attestation(&attestation, 21, &balances));
+        assert!(!verify_reserves_attestation(&attestation, 22, &balances));
+    }
+
+    #[test]
+    fn test_valid_deposit_wallet_owner() {
+        let owner = Pubkey::new_unique();
+
+        valid_deposit_wallet_owner(&owner, &owner).unwrap();
+    }
+
+    #[test]
+    fn test_valid_deposit_wallet_owner_not_configured() {
+        valid_deposit_wallet_owner(&Pubkey::new_unique(), &Pubkey::default()).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fail_valid_deposit_wallet_owner() {
+        valid_deposit_wallet_owner(&Pubkey::new_unique(), &Pubkey::new_unique()).unwrap();
+    }
+
+    fn token_account_data(amount: u64) -> Vec<u8> {
+        let mut data = vec![0; SplTokenAccount::LEN];
+        SplTokenAccount::pack(
+            SplTokenAccount {
+                mint: Pubkey::new_unique(),
+                owner: Pubkey::new_unique(),
+                amount,
+                state: AccountState::Initialized,
+                ..SplTokenAccount::default()
+            },
+            &mut data,
+        )
+        .unwrap();
+        data
+    }
+
+    #[test_case(42, 7, 7; "requested amount")]
+    #[test_case(42, WHOLE_BALANCE, 42; "whole balance")]
+    #[test_case(0, WHOLE_BALANCE, 0; "whole balance of empty account")]
+    fn test_resolve_transfer_amount(balance: u64, amount: u64, expected: u64) {
+        let mut data = token_account_data(balance);
+        let mut lamports = 0u64;
+        let source = AccountInfo {
+            key: &Pubkey::new_unique(),
+            is_signer: false,
+            is_writable: true,
+            lamports: Rc::new(RefCell::new(&mut lamports)),
+            data: Rc::new(RefCell::new(&mut data[..])),
+            owner: &anchor_spl::token::spl_token::ID,
+            executable: false,
+            rent_epoch: 0,
+        };
+
+        assert_eq!(resolve_transfer_amount(&source, amount).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_fail_resolve_whole_balance_of_non_token_account() {
+        let mut data = [0u8; 8];
+        let mut lamports = 0u64;
+        let source = AccountInfo {
+            key: &Pubkey::new_unique(),
+            is_signer: false,
+            is_writable: true,
+            lamports: Rc::new(RefCell::new(&mut lamports)),
+            data: Rc::new(RefCell::new(&mut data[..])),
+            owner: &Pubkey::new_unique(),
+            executable: false,
+            rent_epoch: 0,
+        };
+
+        assert!(resolve_transfer_amount(&source, WHOLE_BALANCE).is_err());
     }
 
     #[test]
@@ -730,4 +3267,598 @@ mod test {
 
         valid_owner(&state, &signer).unwrap()
     }
+
+    #[test]
+    fn test_fail_valid_owner_renounced() {
+        let data: Rc<RefCell<&mut [u8]>> = Rc::new(RefCell::new(&mut [0u8; 0]));
+        let authority = Pubkey::new_unique();
+        let mut binding = 0u64;
+
+        let signer = AccountInfo {
+            key: &authority,
+            is_signer: true,
+            is_writable: false,
+            lamports: Rc::new(RefCell::new(&mut binding)),
+            data,
+            owner: &Pubkey::new_unique(),
+            executable: false,
+            rent_epoch: 0,
+        };
+        let state = ContractState {
+            authority,
+            renounced: true,
+            ..ContractState::default()
+        };
+
+        assert_eq!(
+            valid_owner(&state, &signer).unwrap_err(),
+            LeancoinError::AuthorityRenounced.into()
+        );
+    }
+
+    #[test_case(true, false, false, true; "authority")]
+    #[test_case(false, true, false, true; "registered keeper")]
+    #[test_case(false, false, false, false; "not registered")]
+    #[test_case(false, false, true, true; "renounced")]
+    fn test_valid_keeper(is_authority: bool, is_registered: bool, renounced: bool, valid: bool) {
+        let data: Rc<RefCell<&mut [u8]>> = Rc::new(RefCell::new(&mut [0u8; 0]));
+        let key = Pubkey::new_unique();
+        let mut binding = 0u64;
+
+        let signer = AccountInfo {
+            key: &key,
+            is_signer: true,
+            is_writable: false,
+            lamports: Rc::new(RefCell::new(&mut binding)),
+            data,
+            owner: &Pubkey::new_unique(),
+            executable: false,
+            rent_epoch: 0,
+        };
+        let mut state = ContractState::default();
+        if is_authority {
+            state.authority = key;
+        }
+        state.keepers[0] = Pubkey::new_unique();
+        if is_registered {
+            state.keepers[MAX_KEEPERS - 1] = key;
+        }
+        state.renounced = renounced;
+
+        assert_eq!(valid_keeper(&state, &signer).is_ok(), valid);
+    }
+
+    #[test_case(Role::Burn, None, true, true; "owner")]
+    #[test_case(Role::Burn, Some(Role::Burn), false, true; "burn authority burns")]
+    #[test_case(Role::Burn, Some(Role::Withdraw), false, false; "withdraw authority cannot burn")]
+    #[test_case(Role::Withdraw, Some(Role::Withdraw), false, true; "withdraw authority withdraws")]
+    #[test_case(Role::Withdraw, Some(Role::Metadata), false, false; "metadata authority cannot withdraw")]
+    #[test_case(Role::Metadata, Some(Role::Metadata), false, true; "metadata authority sets metadata")]
+    #[test_case(Role::Metadata, Some(Role::Burn), false, false; "burn authority cannot set metadata")]
+    #[test_case(Role::Metadata, None, false, false; "not assigned")]
+    fn test_valid_role(role: Role, assigned_role: Option<Role>, is_authority: bool, valid: bool) {
+        let data: Rc<RefCell<&mut [u8]>> = Rc::new(RefCell::new(&mut [0u8; 0]));
+        let key = Pubkey::new_unique();
+        let mut binding = 0u64;
+
+        let signer = AccountInfo {
+            key: &key,
+            is_signer: true,
+            is_writable: false,
+            lamports: Rc::new(RefCell::new(&mut binding)),
+            data,
+            owner: &Pubkey::new_unique(),
+            executable: false,
+            rent_epoch: 0,
+        };
+        let mut state = ContractState {
+            authority: Pubkey::new_unique(),
+            ..ContractState::default()
+        };
+        if is_authority {
+            state.authority = key;
+        }
+        for other_role in Role::ALL {
+            *state.role_authority_mut(other_role) = Pubkey::new_unique();
+        }
+        if let Some(assigned_role) = assigned_role {
+            *state.role_authority_mut(assigned_role) = key;
+        }
+
+        assert_eq!(valid_role(&state, role, &signer).is_ok(), valid);
+    }
+
+    #[cfg(feature = "metadata")]
+    #[test_case(32, 10, 200, None; "maximal lengths")]
+    #[test_case(33, 10, 200, Some(LeancoinError::TokenNameTooLong); "name too long")]
+    #[test_case(32, 11, 200, Some(LeancoinError::TokenSymbolTooLong); "symbol too long")]
+    #[test_case(32, 10, 201, Some(LeancoinError::TokenUriTooLong); "uri too long")]
+    fn test_valid_token_metadata(
+        name_length: usize,
+        symbol_length: usize,
+        uri_length: usize,
+        error: Option<LeancoinError>,
+    ) {
+        let result = valid_token_metadata(
+            &"n".repeat(name_length),
+            &"s".repeat(symbol_length),
+            &"u".repeat(uri_length),
+        );
+
+        assert_eq!(result.err(), error.map(Into::into));
+    }
+
+    #[test]
+    fn test_calculate_locked_amount() {
+        // Sunday, 5 March 2023 01:01:01
+        let start_timestamp = 1677978061;
+        let mut vesting_state: VestingState = bytemuck::Zeroable::zeroed();
+        vesting_state.schedules = VestingSchedule::defaults();
+        vesting_state
+            .wallet_mut(WalletKind::Liquidity)
+            .initial_balance = 1000;
+        vesting_state
+            .wallet_mut(WalletKind::Partnership)
+            .initial_balance = 2000;
+
+        // the vesting has not started yet
+        assert_eq!(
+            calculate_locked_amount(&vesting_state, WalletKind::Liquidity, start_timestamp)
+                .unwrap(),
+            1000
+        );
+
+        vesting_state.start_timestamp = start_timestamp;
+        assert_eq!(
+            calculate_locked_amount(&vesting_state, WalletKind::Liquidity, start_timestamp)
+                .unwrap(),
+            500
+        );
+        assert_eq!(
+            calculate_locked_amount(&vesting_state, WalletKind::Partnership, start_timestamp)
+                .unwrap(),
+            2000
+        );
+        // Wednesday, 5 April 2023 01:01:01
+        assert_eq!(
+            calculate_locked_amount(&vesting_state, WalletKind::Partnership, 1680656461).unwrap(),
+            1000
+        );
+
+        vesting_state.finalized_wallets = WalletKind::Partnership.mask();
+        assert_eq!(
+            calculate_locked_amount(&vesting_state, WalletKind::Partnership, start_timestamp)
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_calculate_unlocked_amount_of_revoked_wallet() {
+        // Sunday, 5 March 2023 01:01:01
+        let start_timestamp = 1677978061;
+        let mut vesting_state: VestingState = bytemuck::Zeroable::zeroed();
+        vesting_state.schedules = VestingSchedule::defaults();
+        vesting_state.start_timestamp = start_timestamp;
+        vesting_state
+            .wallet_mut(WalletKind::Community)
+            .initial_balance = 1000;
+
+        // Friday, 5 May 2023 01:01:01
+        assert_eq!(
+            calculate_wallet_unlocked_amount(&vesting_state, WalletKind::Community, 1683248461)
+                .unwrap(),
+            75
+        );
+
+        vesting_state.revoked_wallets = WalletKind::Community.mask();
+        vesting_state.revoked_unlocked[WalletKind::Community.index()] = 50;
+        assert_eq!(
+            calculate_wallet_unlocked_amount(&vesting_state, WalletKind::Community, 1683248461)
+                .unwrap(),
+            50
+        );
+        assert_eq!(
+            calculate_locked_amount(&vesting_state, WalletKind::Community, 1683248461).unwrap(),
+            0
+        );
+    }
+
+    #[test_case(12 ; "end of the marketing cliff")]
+    #[test_case(13 ; "month after the marketing cliff")]
+    #[test_case(39 ; "after the full unlock")]
+    fn test_forecast_unlocked_amounts(months: u64) {
+        let initial_balances = [1000000, 2000000, 3000000, 4000000];
+        let mut vesting_state: VestingState = bytemuck::Zeroable::zeroed();
+        vesting_state.schedules = VestingSchedule::defaults();
+        for wallet_kind in WalletKind::ALL {
+            vesting_state.wallet_mut(wallet_kind).initial_balance =
+                initial_balances[wallet_kind.index()];
+        }
+
+        let expected = [
+            calculate_unlocked_amount_community_wallet(1000000, months, 0),
+            calculate_unlocked_amount_partnership_wallet(2000000, months),
+            calculate_unlocked_amount_marketing_wallet(3000000, months),
+            calculate_unlocked_amount_liquidity_wallet(4000000, months),
+        ];
+        assert_eq!(forecast_unlocked_amounts(&vesting_state, months), expected);
+
+        let table = vesting_table(initial_balances, months);
+        assert_eq!(table.len() as u64, months + 1);
+        assert_eq!(
+            table.last(),
+            Some(&MonthRow {
+                month: months,
+                unlocked: expected,
+            })
+        );
+
+        vesting_state.community_unlock_delay_months = 3;
+        assert_eq!(
+            forecast_unlocked_amounts(&vesting_state, months)[WalletKind::Community.index()],
+            calculate_unlocked_amount_community_wallet(1000000, months, 3)
+        );
+    }
+
+    #[test_case(6, 6, true; "single day")]
+    #[test_case(15, 20, true; "default")]
+    #[test_case(5, 20, false; "overlapping primary window")]
+    #[test_case(20, 15, false; "empty")]
+    #[test_case(15, 29, false; "after 28th day")]
+    fn test_valid_late_burn_window(first_day: u8, last_day: u8, valid: bool) {
+        let state = ContractState::default();
+
+        assert_eq!(
+            valid_late_burn_window(&state, first_day, last_day).is_ok(),
+            valid
+        );
+    }
+
+    #[test_case(0, false; "empty")]
+    #[test_case(1, true; "single day")]
+    #[test_case(DEFAULT_BURN_WINDOW_DAYS, true; "default")]
+    #[test_case(MAX_BURN_WINDOW_DAYS, true; "28 days")]
+    #[test_case(MAX_BURN_WINDOW_DAYS + 1, false; "after 28th day")]
+    fn test_valid_burn_window(burn_window_days: u8, valid: bool) {
+        assert_eq!(valid_burn_window(burn_window_days).is_ok(), valid);
+    }
+
+    #[test_case(1, Some(false); "first day of primary window")]
+    #[test_case(5, Some(false); "last day of primary window")]
+    #[test_case(6, None; "between windows")]
+    #[test_case(15, Some(true); "first day of secondary window")]
+    #[test_case(20, Some(true); "last day of secondary window")]
+    #[test_case(21, None; "after secondary window")]
+    fn test_late_burn(day: u8, late: Option<bool>) {
+        let state = ContractState::default();
+
+        assert_eq!(late_burn(&state, day).ok(), late);
+    }
+
+    #[test_case(10, Some(false); "last day of extended primary window")]
+    #[test_case(11, None; "day after extended primary window")]
+    #[test_case(15, Some(true); "first day of secondary window")]
+    fn test_late_burn_with_extended_window(day: u8, late: Option<bool>) {
+        let mut state = ContractState::default();
+        state.burn_window_days = 10;
+
+        assert_eq!(late_burn(&state, day).ok(), late);
+    }
+
+    #[test_case(1800000000000000000, 0, 0; "no rewards")]
+    #[test_case(1800000000000000000, 1, 18000000000000000; "1 percentage point")]
+    #[test_case(1800000000000000000, 5, 90000000000000000; "whole burn")]
+    #[test_case(99, 1, 0; "rounded down")]
+    fn test_calculate_staking_rewards_amount(balance: u64, reward_percentage: u8, expected: u64) {
+        assert_eq!(
+            calculate_staking_rewards_amount(balance, reward_percentage),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_calculate_reward_per_token_increase_without_stakers() {
+        assert_eq!(calculate_reward_per_token_increase(1000, 0), 0);
+    }
+
+    #[test]
+    fn test_calculate_reward_debt_overflow() {
+        assert!(calculate_reward_debt(u64::MAX, u128::MAX).is_err());
+    }
+
+    #[test]
+    fn test_update_stake_rewards_two_stakers() {
+        let mut first_stake = StakeAccount {
+            stake_account_nonce: 0,
+            owner: Pubkey::new_unique(),
+            staked_amount: 300,
+            reward_debt: 0,
+            pending_rewards: 0,
+            unbonding_amount: 0,
+            unbonding_end_timestamp: 0,
+        };
+        let mut second_stake = StakeAccount {
+            staked_amount: 100,
+            owner: Pubkey::new_unique(),
+            ..first_stake.clone()
+        };
+
+        let reward_per_token = calculate_reward_per_token_increase(1000, 400);
+        update_stake_rewards(&mut first_stake, reward_per_token).unwrap();
+        update_stake_rewards(&mut second_stake, reward_per_token).unwrap();
+
+        assert_eq!(first_stake.pending_rewards, 750);
+        assert_eq!(second_stake.pending_rewards, 250);
+
+        // already accounted rewards are not counted twice
+        update_stake_rewards(&mut first_stake, reward_per_token).unwrap();
+        assert_eq!(first_stake.pending_rewards, 750);
+    }
+
+    #[test]
+    fn test_calculate_supply_projection() {
+        // month 1: 5% of 1000000 is 50000, month 2: 5% of 950000 is 47500, month 3: 5% of 902500 is 45125
+        let projection =
+            calculate_supply_projection(10000000, 1000000, 0, DEFAULT_BURN_RATE_BPS, 0, 3).unwrap();
+
+        assert_eq!(projection.burned, 50000 + 47500 + 45125);
+        assert_eq!(projection.supply, 10000000 - 142625);
+        assert_eq!(projection.burning_account_balance, 1000000 - 142625);
+        assert_eq!(projection.floor_month, None);
+    }
+
+    #[test]
+    fn test_calculate_supply_projection_with_staking_rewards() {
+        // 2 of 5 percentage points are redirected to the stakers, the burning account decreases by 5% anyway
+        let projection =
+            calculate_supply_projection(10000000, 1000000, 0, DEFAULT_BURN_RATE_BPS, 2, 2).unwrap();
+
+        assert_eq!(projection.burned, 30000 + 28500);
+        assert_eq!(projection.supply, 10000000 - 58500);
+        assert_eq!(projection.burning_account_balance, 1000000 - 50000 - 47500);
+    }
+
+    #[test]
+    fn test_calculate_supply_projection_with_burn_remainder() {
+        // month 1: 5% of 30 is 1.5 so 1 is burned and 0.5 is carried over, month 2: 5% of 29 is 1.45 plus 0.5 is 1.95
+        let projection =
+            calculate_supply_projection(1000, 30, 0, DEFAULT_BURN_RATE_BPS, 0, 2).unwrap();
+
+        assert_eq!(projection.burned, 2);
+        assert_eq!(projection.burning_account_balance, 28);
+
+        // the same months with 0.6 carried over from the last burn: 2.1 and then 0.1 plus 5% of 28 is 1.5
+        let projection =
+            calculate_supply_projection(1000, 30, 6000, DEFAULT_BURN_RATE_BPS, 0, 2).unwrap();
+
+        assert_eq!(projection.burned, 3);
+        assert_eq!(projection.burning_account_balance, 27);
+    }
+
+    #[test_case(0, 10, 10 ; "no months")]
+    #[test_case(12, 0, 0 ; "empty burning account")]
+    fn test_calculate_supply_projection_without_burns(
+        months: u8,
+        burning_account_balance: u64,
+        expected_balance: u64,
+    ) {
+        let projection = calculate_supply_projection(
+            1000,
+            burning_account_balance,
+            0,
+            DEFAULT_BURN_RATE_BPS,
+            0,
+            months,
+        )
+        .unwrap();
+
+        assert_eq!(projection.supply, 1000);
+        assert_eq!(projection.burned, 0);
+        assert_eq!(projection.burning_account_balance, expected_balance);
+    }
+
+    #[test]
+    fn test_calculate_supply_projection_until_floor() {
+        // 5% of a single token is carried over until the 20th burn burns it and empties the burning account
+        let projection =
+            calculate_supply_projection(1000, 1, 0, DEFAULT_BURN_RATE_BPS, 0, 30).unwrap();
+
+        assert_eq!(projection.burned, 1);
+        assert_eq!(projection.supply, 999);
+        assert_eq!(projection.burning_account_balance, 0);
+        assert_eq!(projection.floor_month, Some(21));
+    }
+
+    #[test]
+    fn test_fail_calculate_supply_projection_too_long() {
+        assert!(calculate_supply_projection(
+            1000,
+            100,
+            0,
+            DEFAULT_BURN_RATE_BPS,
+            0,
+            MAX_PROJECTED_MONTHS + 1
+        )
+        .is_err());
+    }
+
+    #[test_case(1800000000000000000, 0, 90000000000000000, 0 ; "imported burning account")]
+    #[test_case(19, 0, 0, 9500 ; "fraction carried over")]
+    #[test_case(19, 9500, 1, 9000 ; "carried fraction completes a token")]
+    #[test_case(u64::MAX, 9999, u64::MAX / 20 + 1, 7499 ; "maximal balance")]
+    fn test_calculate_monthly_burn_amount(
+        burning_account_balance: u64,
+        burn_remainder_numerator: u64,
+        expected_amount: u64,
+        expected_remainder: u64,
+    ) {
+        assert_eq!(
+            calculate_monthly_burn_amount(
+                burning_account_balance,
+                burn_remainder_numerator,
+                DEFAULT_BURN_RATE_BPS
+            ),
+            (expected_amount, expected_remainder)
+        );
+    }
+
+    #[test_case(1800000000000000000, 250, 45000000000000000 ; "half of the default rate")]
+    #[test_case(1000, 1, 0 ; "minimal rate")]
+    #[test_case(1000, 10000, 1000 ; "whole balance")]
+    fn test_calculate_monthly_burn_amount_with_custom_rate(
+        burning_account_balance: u64,
+        burn_rate_bps: u16,
+        expected_amount: u64,
+    ) {
+        let (amount, _) = calculate_monthly_burn_amount(burning_account_balance, 0, burn_rate_bps);
+
+        assert_eq!(amount, expected_amount);
+    }
+
+    #[test_case(1800000000000000000, 0, 1, 90000000000000000, 0; "single month")]
+    #[test_case(1710000000000000000, 0, 3, 243888750000000000, 0; "three months compounded")]
+    #[test_case(1710000000000000000, 1, 3, 243888750000000000, 48777750000000000; "three months with rewards")]
+    #[test_case(1710000000000000000, 0, 0, 0, 0; "no months")]
+    #[test_case(19, 0, 1, 0, 0; "rounded down")]
+    fn test_calculate_catch_up_burn_amount(
+        burning_account_balance: u64,
+        reward_percentage: u8,
+        months: u64,
+        expected_amount: u64,
+        expected_rewards: u64,
+    ) {
+        let (amount, rewards, _) = calculate_catch_up_burn_amount(
+            burning_account_balance,
+            0,
+            DEFAULT_BURN_RATE_BPS,
+            reward_percentage,
+            months,
+        );
+
+        assert_eq!(amount, expected_amount);
+        assert_eq!(rewards, expected_rewards);
+    }
+
+    #[test_case(3, 2023, DateTime { year: 2023, month: 6, days: 3 }, Some(2); "two months")]
+    #[test_case(11, 2022, DateTime { year: 2023, month: 2, days: 1 }, Some(2); "over the year end")]
+    #[test_case(1, 2020, DateTime { year: 2023, month: 6, days: 3 }, Some(24); "capped")]
+    #[test_case(6, 2023, DateTime { year: 2023, month: 6, days: 3 }, None; "current month burned")]
+    #[test_case(5, 2023, DateTime { year: 2023, month: 6, days: 3 }, None; "previous month burned")]
+    #[test_case(0, 0, DateTime { year: 2023, month: 6, days: 3 }, None; "never burned")]
+    fn test_months_since_last_burn(
+        last_burning_month: u8,
+        last_burning_year: i64,
+        now: DateTime,
+        expected: Option<u64>,
+    ) {
+        let mut state = ContractState::default();
+        state.last_burning_month = last_burning_month;
+        state.last_burning_year = last_burning_year;
+
+        assert_eq!(months_since_last_burn(&state, &now).ok(), expected);
+    }
+
+    #[test_case(DateTime { year: 2023, month: 6, days: 3 }, (5, 2023); "within the year")]
+    #[test_case(DateTime { year: 2023, month: 1, days: 31 }, (12, 2022); "over the year start")]
+    fn test_previous_month(now: DateTime, expected: (u8, i64)) {
+        assert_eq!(previous_month(&now), expected);
+    }
+
+    #[test_case(1800000000000000000 ; "imported burning account")]
+    #[test_case(1234567 ; "odd balance")]
+    #[test_case(399 ; "balance below one token burned per month")]
+    fn test_monthly_burns_match_exact_burn_rate(initial_balance: u64) {
+        let mut balance = initial_balance;
+        let mut burn_remainder_numerator = 0;
+        let mut burned = 0u64;
+        for _ in 0..12 {
+            let (amount, remainder) = calculate_monthly_burn_amount(
+                balance,
+                burn_remainder_numerator,
+                DEFAULT_BURN_RATE_BPS,
+            );
+            balance -= amount;
+            burn_remainder_numerator = remainder;
+            burned += amount;
+        }
+
+        // the exact remaining balance after 12 burns of 5% is initial_balance * 19^12 / 20^12
+        let exact_remaining_numerator = initial_balance as i128 * 19i128.pow(12);
+        let denominator = 20i128.pow(12);
+        let exact_burned_numerator =
+            initial_balance as i128 * denominator - exact_remaining_numerator;
+
+        assert!((burned as i128 * denominator - exact_burned_numerator).abs() <= denominator);
+    }
+
+    #[test_case(0, true; "no decimals")]
+    #[test_case(9, true; "9 decimals")]
+    #[test_case(MAX_MINT_DECIMALS, true; "12 decimals")]
+    #[test_case(MAX_MINT_DECIMALS + 1, false; "13 decimals")]
+    fn test_valid_decimals(decimals: u8, valid: bool) {
+        assert_eq!(valid_decimals(decimals).is_ok(), valid);
+    }
+
+    #[test_case(0, false; "zero")]
+    #[test_case(1, true; "minimal rate")]
+    #[test_case(DEFAULT_BURN_RATE_BPS, true; "default rate")]
+    #[test_case(10000, true; "whole balance")]
+    #[test_case(10001, false; "above whole balance")]
+    fn test_valid_burn_rate(burn_rate_bps: u16, valid: bool) {
+        assert_eq!(valid_burn_rate(burn_rate_bps).is_ok(), valid);
+    }
+
+    #[test_case(0, true; "disabled")]
+    #[test_case(MAX_BURN_CALLER_REWARD_BPS, true; "50 basis points")]
+    #[test_case(MAX_BURN_CALLER_REWARD_BPS + 1, false; "above 50 basis points")]
+    fn test_valid_burn_caller_reward(burn_caller_reward_bps: u16, valid: bool) {
+        assert_eq!(
+            valid_burn_caller_reward(burn_caller_reward_bps).is_ok(),
+            valid
+        );
+    }
+
+    #[test_case(90000000000000000, 50, 450000000000000; "maximal reward")]
+    #[test_case(90000000000000000, 0, 0; "disabled")]
+    #[test_case(199, 50, 0; "rounded down to zero")]
+    #[test_case(u64::MAX, 50, u64::MAX / 200; "maximal amount")]
+    fn test_calculate_burn_caller_reward(amount: u64, burn_caller_reward_bps: u16, expected: u64) {
+        assert_eq!(
+            calculate_burn_caller_reward(amount, burn_caller_reward_bps),
+            expected
+        );
+    }
+
+    #[test_case(0, true; "no grace period")]
+    #[test_case(MAX_WITHDRAWAL_GRACE_PERIOD, true; "90 days")]
+    #[test_case(MAX_WITHDRAWAL_GRACE_PERIOD + 1, false; "longer than 90 days")]
+    #[test_case(-1, false; "negative")]
+    fn test_valid_withdrawal_grace_period(withdrawal_grace_period_seconds: i64, valid: bool) {
+        assert_eq!(
+            valid_withdrawal_grace_period(withdrawal_grace_period_seconds).is_ok(),
+            valid
+        );
+    }
+
+    #[test_case(0, 1677978061, true; "no grace period at vesting start")]
+    #[test_case(604800, 1677978061, false; "grace period at vesting start")]
+    #[test_case(604800, 1678582860, false; "one second before the end of the grace period")]
+    #[test_case(604800, 1678582861, true; "end of the grace period")]
+    #[test_case(604800, 1678582862, true; "after the grace period")]
+    fn test_withdrawal_grace_period_elapsed(
+        withdrawal_grace_period_seconds: i64,
+        timestamp: i64,
+        elapsed: bool,
+    ) {
+        let start_timestamp = 1677978061;
+
+        let result = withdrawal_grace_period_elapsed(
+            start_timestamp,
+            withdrawal_grace_period_seconds,
+            timestamp,
+        );
+
+        assert_eq!(result.is_ok(), elapsed);
+    }
 }
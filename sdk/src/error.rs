@@ -0,0 +1,139 @@
+//! Errors returned by the client.
+//! The errors of the contract are mapped back to `LeancoinError` so callers can match on them.
+
+use std::fmt;
+
+use anchor_client::{
+    solana_sdk::{instruction::InstructionError, transaction::TransactionError},
+    ClientError,
+};
+use anchor_lang::solana_program::program_error::ProgramError;
+use leancoin::error_codes::LeancoinError;
+
+/// The result type used by the client.
+pub type Result<T> = std::result::Result<T, LeancoinClientError>;
+
+/// The error returned by the client.
+#[derive(Debug)]
+pub enum LeancoinClientError {
+    /// the contract rejected the instruction
+    Contract(LeancoinError),
+    /// the transaction failed for another reason, e.g. a violated account constraint
+    Transaction(TransactionError),
+    /// the request did not reach the contract, e.g. because of an RPC failure
+    Client(ClientError),
+}
+
+/// Returns the contract error which failed the transaction.
+///
+/// ### Arguments
+///
+/// * `error` - the error of the transaction
+///
+/// ### Returns
+/// The contract error or `None` if the transaction failed for another reason.
+pub fn contract_error(error: &TransactionError) -> Option<LeancoinError> {
+    match error {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            LeancoinError::from_code(*code)
+        }
+        _ => None,
+    }
+}
+
+impl From<TransactionError> for LeancoinClientError {
+    fn from(error: TransactionError) -> Self {
+        match contract_error(&error) {
+            Some(contract_error) => LeancoinClientError::Contract(contract_error),
+            None => LeancoinClientError::Transaction(error),
+        }
+    }
+}
+
+impl From<ClientError> for LeancoinClientError {
+    fn from(error: ClientError) -> Self {
+        let error_code = match &error {
+            ClientError::SolanaClientError(client_error) => {
+                match client_error.get_transaction_error() {
+                    Some(transaction_error) => return transaction_error.into(),
+                    None => None,
+                }
+            }
+            ClientError::ProgramError(ProgramError::Custom(code)) => Some(*code),
+            ClientError::AnchorError(anchor_lang::error::Error::AnchorError(anchor_error)) => {
+                Some(anchor_error.error_code_number)
+            }
+            _ => None,
+        };
+
+        match error_code.and_then(LeancoinError::from_code) {
+            Some(contract_error) => LeancoinClientError::Contract(contract_error),
+            None => LeancoinClientError::Client(error),
+        }
+    }
+}
+
+impl From<anchor_lang::error::Error> for LeancoinClientError {
+    fn from(error: anchor_lang::error::Error) -> Self {
+        ClientError::AnchorError(error).into()
+    }
+}
+
+impl fmt::Display for LeancoinClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LeancoinClientError::Contract(error) => write!(f, "{}", error),
+            LeancoinClientError::Transaction(error) => write!(f, "{}", error),
+            LeancoinClientError::Client(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for LeancoinClientError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_map_contract_error() {
+        let error = LeancoinClientError::from(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(LeancoinError::NotEnoughTokens.into()),
+        ));
+
+        assert!(matches!(
+            error,
+            LeancoinClientError::Contract(LeancoinError::NotEnoughTokens)
+        ));
+    }
+
+    #[test]
+    fn test_map_anchor_error_of_contract() {
+        let error = LeancoinClientError::from(anchor_lang::error::Error::from(
+            LeancoinError::TooLateToBurnTokens,
+        ));
+
+        assert!(matches!(
+            error,
+            LeancoinClientError::Contract(LeancoinError::TooLateToBurnTokens)
+        ));
+    }
+
+    #[test]
+    fn test_keep_other_errors() {
+        let constraint_error = TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(anchor_lang::error::ErrorCode::ConstraintSeeds.into()),
+        );
+
+        assert!(matches!(
+            LeancoinClientError::from(constraint_error),
+            LeancoinClientError::Transaction(_)
+        ));
+        assert!(matches!(
+            LeancoinClientError::from(ClientError::AccountNotFound),
+            LeancoinClientError::Client(ClientError::AccountNotFound)
+        ));
+    }
+}
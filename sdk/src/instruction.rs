@@ -0,0 +1,370 @@
+//! Builders of the contract instructions.
+//! The program derived accounts are derived from their seeds, so only the arguments of the instructions have to be passed.
+
+use anchor_client::solana_sdk::compute_budget::ComputeBudgetInstruction;
+use anchor_lang::{
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        system_program, sysvar,
+    },
+    InstructionData, ToAccountMetas,
+};
+use leancoin::context::{
+    __client_accounts_add_keeper_context::AddKeeperContext,
+    __client_accounts_burn_context::BurnContext,
+    __client_accounts_import_token_state_context::ImportTokenStateContext,
+    __client_accounts_initialize_burn_history_context::InitializeBurnHistoryContext,
+    __client_accounts_initialize_state_context::InitializeStateContext,
+    __client_accounts_initialize_wallets_context::InitializeWalletsContext,
+    __client_accounts_remove_keeper_context::RemoveKeeperContext,
+    __client_accounts_withdraw_tokens_from_community_wallet_context::WithdrawTokensFromCommunityWalletContext,
+    __client_accounts_withdraw_tokens_from_liquidity_wallet_context::WithdrawTokensFromLiquidityWalletContext,
+    __client_accounts_withdraw_tokens_from_marketing_wallet_context::WithdrawTokensFromMarketingWalletContext,
+    __client_accounts_withdraw_tokens_from_partnership_wallet_context::WithdrawTokensFromPartnershipWalletContext,
+};
+use leancoin::{
+    AccountInfoFromEthereum, MigrationSource, WalletKind, BURNING_ACCOUNT_SEED, BURN_HISTORY_SEED,
+    CONTRACT_STATE_SEED, LABELS_SEED, MINT_SEED, PROGRAM_ACCOUNT_SEED, REWARD_VAULT_SEED,
+    STAKING_STATE_SEED, VESTING_STATE_SEED,
+};
+
+use crate::pda::{address, wallet_address, withdrawal_intent_address, withdrawal_receipt_address};
+
+/// Size of the heap frame requested by the import, the entries do not fit into the default heap.
+pub const IMPORT_HEAP_FRAME_SIZE: u32 = 256 * 1024;
+
+/// Builds the `initialize_state` instruction, the first phase of the contract initialization.
+///
+/// ### Arguments
+///
+/// * `signer` - the signer of the transaction who becomes contract's owner
+/// * `payer` - the signer paying the rent of the created accounts, it can be the same as `signer`
+/// * `decimals` - the number of decimals of the mint
+///
+/// ### Returns
+/// The instruction
+pub fn initialize_state(signer: &Pubkey, payer: &Pubkey, decimals: u8) -> Instruction {
+    let data = leancoin::instruction::InitializeState { decimals }.data();
+
+    let accounts = InitializeStateContext {
+        contract_state: address(CONTRACT_STATE_SEED),
+        vesting_state: address(VESTING_STATE_SEED),
+        mint: address(MINT_SEED),
+        program_account: address(PROGRAM_ACCOUNT_SEED),
+        burning_account: address(BURNING_ACCOUNT_SEED),
+        token_program: anchor_spl::token::ID,
+        signer: *signer,
+        payer: *payer,
+        system_program: system_program::ID,
+    };
+
+    Instruction::new_with_bytes(leancoin::ID, &data, accounts.to_account_metas(None))
+}
+
+/// Builds the `initialize_wallets` instruction, the second phase of the contract initialization.
+/// The vested wallets are initialized with the default vesting schedules.
+///
+/// ### Arguments
+///
+/// * `signer` - the signer of the transaction which must be the contract's owner
+/// * `payer` - the signer paying the rent of the created accounts, it can be the same as `signer`
+///
+/// ### Returns
+/// The instruction
+pub fn initialize_wallets(signer: &Pubkey, payer: &Pubkey) -> Instruction {
+    let data = leancoin::instruction::InitializeWallets {
+        vesting_schedules: None,
+        linear_daily_unlock: None,
+    }
+    .data();
+
+    let accounts = InitializeWalletsContext {
+        contract_state: address(CONTRACT_STATE_SEED),
+        vesting_state: address(VESTING_STATE_SEED),
+        mint: address(MINT_SEED),
+        community_account: wallet_address(WalletKind::Community),
+        partnership_account: wallet_address(WalletKind::Partnership),
+        marketing_account: wallet_address(WalletKind::Marketing),
+        liquidity_account: wallet_address(WalletKind::Liquidity),
+        labels: address(LABELS_SEED),
+        token_program: anchor_spl::token::ID,
+        signer: *signer,
+        payer: *payer,
+        system_program: system_program::ID,
+    };
+
+    Instruction::new_with_bytes(leancoin::ID, &data, accounts.to_account_metas(None))
+}
+
+/// Builds the `initialize_burn_history` instruction creating the account that keeps the latest burns.
+///
+/// ### Arguments
+///
+/// * `payer` - the signer paying the rent of the burn history
+///
+/// ### Returns
+/// The instruction
+pub fn initialize_burn_history(payer: &Pubkey) -> Instruction {
+    let data = leancoin::instruction::InitializeBurnHistory {}.data();
+
+    let accounts = InitializeBurnHistoryContext {
+        contract_state: address(CONTRACT_STATE_SEED),
+        burn_history: address(BURN_HISTORY_SEED),
+        payer: *payer,
+        system_program: system_program::ID,
+    };
+
+    Instruction::new_with_bytes(leancoin::ID, &data, accounts.to_account_metas(None))
+}
+
+/// Builds the instructions importing the token state of the migration source.
+/// The import is preceded by the request of a bigger heap frame, the funded accounts are passed as the remaining accounts.
+///
+/// ### Arguments
+///
+/// * `signer` - the signer of the transaction which must be the contract's owner
+/// * `source` - the migration source
+/// * `entries` - the accounts to fund together with their balances expressed with decimals of the source token
+/// * `amount_token_to_mint` - amount of tokens to mint, expressed with decimals of the source token
+/// * `amount_token_to_burn` - amount of tokens to burn, expressed with decimals of the source token
+/// * `source_decimals` - number of decimals of the source token
+/// * `community_unlock_delay_months` - number of months before the first unlock of the community wallet
+/// * `withdrawal_grace_period_seconds` - number of seconds after the import during which no vested wallet can withdraw tokens
+///
+/// ### Returns
+/// The instructions to send in a single transaction
+#[allow(clippy::too_many_arguments)]
+pub fn import_token_state(
+    signer: &Pubkey,
+    source: MigrationSource,
+    entries: Vec<AccountInfoFromEthereum>,
+    amount_token_to_mint: u128,
+    amount_token_to_burn: u128,
+    source_decimals: u8,
+    community_unlock_delay_months: u8,
+    withdrawal_grace_period_seconds: i64,
+) -> Vec<Instruction> {
+    let remaining_accounts = entries
+        .iter()
+        .map(|entry| AccountMeta::new(entry.account_public_key, false))
+        .collect::<Vec<AccountMeta>>();
+
+    let data = leancoin::instruction::ImportExternalTokenState {
+        source,
+        entries,
+        amount_token_to_mint,
+        amount_token_to_burn,
+        source_decimals,
+        community_unlock_delay_months,
+        withdrawal_grace_period_seconds,
+    }
+    .data();
+
+    let accounts = ImportTokenStateContext {
+        contract_state: address(CONTRACT_STATE_SEED),
+        vesting_state: address(VESTING_STATE_SEED),
+        mint: address(MINT_SEED),
+        program_account: address(PROGRAM_ACCOUNT_SEED),
+        token_program: anchor_spl::token::ID,
+        signer: *signer,
+    };
+
+    let mut account_metas = accounts.to_account_metas(None);
+    account_metas.extend(remaining_accounts);
+
+    vec![
+        ComputeBudgetInstruction::request_heap_frame(IMPORT_HEAP_FRAME_SIZE),
+        Instruction::new_with_bytes(leancoin::ID, &data, account_metas),
+    ]
+}
+
+/// Builds the monthly `burn` instruction.
+///
+/// ### Arguments
+///
+/// * `signer` - the signer of the transaction which must be the contract's owner or a registered keeper
+/// * `staking_enabled` - information if staking is enabled in the contract state, the staking accounts are passed only then
+/// * `caller_token_account` - the optional token account of the mint receiving the reward of the burn caller
+///
+/// ### Returns
+/// The instruction
+pub fn burn(
+    signer: &Pubkey,
+    staking_enabled: bool,
+    caller_token_account: Option<Pubkey>,
+) -> Instruction {
+    let data = leancoin::instruction::Burn {}.data();
+
+    let accounts = BurnContext {
+        contract_state: address(CONTRACT_STATE_SEED),
+        mint: address(MINT_SEED),
+        burning_account: address(BURNING_ACCOUNT_SEED),
+        token_program: anchor_spl::token::ID,
+        instructions_sysvar: sysvar::instructions::ID,
+        staking_state: staking_enabled.then(|| address(STAKING_STATE_SEED)),
+        reward_vault: staking_enabled.then(|| address(REWARD_VAULT_SEED)),
+        signer: *signer,
+        caller_token_account,
+        burn_history: address(BURN_HISTORY_SEED),
+    };
+
+    Instruction::new_with_bytes(leancoin::ID, &data, accounts.to_account_metas(None))
+}
+
+/// Builds the `add_keeper` instruction registering a keeper allowed to run the automation instructions.
+///
+/// ### Arguments
+///
+/// * `signer` - the signer of the transaction which must be the contract's owner
+/// * `keeper` - the keeper to register
+///
+/// ### Returns
+/// The instruction
+pub fn add_keeper(signer: &Pubkey, keeper: &Pubkey) -> Instruction {
+    let data = leancoin::instruction::AddKeeper { keeper: *keeper }.data();
+
+    let accounts = AddKeeperContext {
+        contract_state: address(CONTRACT_STATE_SEED),
+        signer: *signer,
+    };
+
+    Instruction::new_with_bytes(leancoin::ID, &data, accounts.to_account_metas(None))
+}
+
+/// Builds the `remove_keeper` instruction unregistering a keeper.
+///
+/// ### Arguments
+///
+/// * `signer` - the signer of the transaction which must be the contract's owner
+/// * `keeper` - the keeper to unregister
+///
+/// ### Returns
+/// The instruction
+pub fn remove_keeper(signer: &Pubkey, keeper: &Pubkey) -> Instruction {
+    let data = leancoin::instruction::RemoveKeeper { keeper: *keeper }.data();
+
+    let accounts = RemoveKeeperContext {
+        contract_state: address(CONTRACT_STATE_SEED),
+        signer: *signer,
+    };
+
+    Instruction::new_with_bytes(leancoin::ID, &data, accounts.to_account_metas(None))
+}
+
+/// Builds the instruction withdrawing unlocked tokens from the given vested wallet.
+///
+/// ### Arguments
+///
+/// * `signer` - the signer of the transaction which must be the contract's owner, it pays for the withdrawal intent and the receipt
+/// * `wallet_kind` - the vested wallet to withdraw from
+/// * `amount_to_withdraw` - amount of tokens to withdraw, `leancoin::WITHDRAW_ALL_UNLOCKED` withdraws all tokens currently available
+/// * `deposit_wallet` - the token account receiving the tokens
+/// * `idempotency_key` - the optional key rejecting any replay of the withdrawal
+/// * `receipt_index` - the index of the withdrawal's receipt, i.e. `VestingState::next_receipt_index` of the wallet
+///
+/// ### Returns
+/// The instruction
+pub fn withdraw(
+    signer: &Pubkey,
+    wallet_kind: WalletKind,
+    amount_to_withdraw: u64,
+    deposit_wallet: &Pubkey,
+    idempotency_key: Option<[u8; 16]>,
+    receipt_index: u64,
+) -> Instruction {
+    let contract_state = address(CONTRACT_STATE_SEED);
+    let vesting_state = address(VESTING_STATE_SEED);
+    let wallet_account = wallet_address(wallet_kind);
+    let withdrawal_intent = idempotency_key.as_ref().map(withdrawal_intent_address);
+    let withdrawal_receipt = withdrawal_receipt_address(wallet_kind, receipt_index);
+    let token_program = anchor_spl::token::ID;
+    let instructions_sysvar = sysvar::instructions::ID;
+    let system_program = system_program::ID;
+    let (signer, deposit_wallet) = (*signer, *deposit_wallet);
+
+    let (data, account_metas) = match wallet_kind {
+        WalletKind::Community => (
+            leancoin::instruction::WithdrawTokensFromCommunityWallet {
+                amount_to_withdraw,
+                idempotency_key,
+            }
+            .data(),
+            WithdrawTokensFromCommunityWalletContext {
+                contract_state,
+                vesting_state,
+                community_account: wallet_account,
+                deposit_wallet,
+                withdrawal_intent,
+                withdrawal_receipt,
+                signer,
+                token_program,
+                instructions_sysvar,
+                system_program,
+            }
+            .to_account_metas(None),
+        ),
+        WalletKind::Partnership => (
+            leancoin::instruction::WithdrawTokensFromPartnershipWallet {
+                amount_to_withdraw,
+                idempotency_key,
+            }
+            .data(),
+            WithdrawTokensFromPartnershipWalletContext {
+                contract_state,
+                vesting_state,
+                partnership_account: wallet_account,
+                deposit_wallet,
+                withdrawal_intent,
+                withdrawal_receipt,
+                signer,
+                token_program,
+                instructions_sysvar,
+                system_program,
+            }
+            .to_account_metas(None),
+        ),
+        WalletKind::Marketing => (
+            leancoin::instruction::WithdrawTokensFromMarketingWallet {
+                amount_to_withdraw,
+                idempotency_key,
+            }
+            .data(),
+            WithdrawTokensFromMarketingWalletContext {
+                contract_state,
+                vesting_state,
+                marketing_account: wallet_account,
+                deposit_wallet,
+                withdrawal_intent,
+                withdrawal_receipt,
+                signer,
+                token_program,
+                instructions_sysvar,
+                system_program,
+            }
+            .to_account_metas(None),
+        ),
+        WalletKind::Liquidity => (
+            leancoin::instruction::WithdrawTokensFromLiquidityWallet {
+                amount_to_withdraw,
+                idempotency_key,
+            }
+            .data(),
+            WithdrawTokensFromLiquidityWalletContext {
+                contract_state,
+                vesting_state,
+                liquidity_account: wallet_account,
+                deposit_wallet,
+                withdrawal_intent,
+                withdrawal_receipt,
+                signer,
+                token_program,
+                instructions_sysvar,
+                system_program,
+            }
+            .to_account_metas(None),
+        ),
+    };
+
+    Instruction::new_with_bytes(leancoin::ID, &data, account_metas)
+}
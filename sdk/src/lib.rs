@@ -0,0 +1,226 @@
+//! Typed Rust client of the Leancoin contract built on `anchor-client`.
+//! The instructions are built by the `instruction` module which derives all program derived accounts from their seeds,
+//! `LeancoinClient` sends them and maps the errors of the contract back to `LeancoinError`.
+//! The imports funding many accounts are sent as versioned transactions built by the `lookup_table` module.
+
+pub mod error;
+pub mod instruction;
+pub mod lookup_table;
+pub mod pda;
+pub mod vesting;
+
+use std::ops::Deref;
+
+use anchor_client::{
+    solana_sdk::{
+        account::from_account, clock::Clock, instruction::Instruction, pubkey::Pubkey,
+        signature::Signature, signer::Signer, sysvar,
+    },
+    ClientError, Program,
+};
+use leancoin::account::{BurnHistory, BurnRecord, ContractState, VestingState};
+use leancoin::{
+    AccountInfoFromEthereum, MigrationSource, WalletKind, BURN_HISTORY_SEED, CONTRACT_STATE_SEED,
+    VESTING_STATE_SEED,
+};
+
+pub use error::{LeancoinClientError, Result};
+pub use vesting::{vesting_status_at, VestingStatus, WalletStatus};
+
+/// The client of the contract, the payer of the wrapped program is the signer of all transactions.
+pub struct LeancoinClient<C> {
+    program: Program<C>,
+}
+
+impl<C: Deref<Target = impl Signer> + Clone> LeancoinClient<C> {
+    /// Creates the client of the contract.
+    ///
+    /// ### Arguments
+    ///
+    /// * `program` - the program created by `anchor_client::Client::program` with `leancoin::ID`
+    pub fn new(program: Program<C>) -> Self {
+        LeancoinClient { program }
+    }
+
+    /// Returns the wrapped program, e.g. to send instructions not covered by the client.
+    pub fn program(&self) -> &Program<C> {
+        &self.program
+    }
+
+    /// Initializes the contract state, the vested wallets and the burn history, in three transactions.
+    /// The payer becomes contract's owner.
+    ///
+    /// ### Arguments
+    ///
+    /// * `decimals` - the number of decimals of the mint
+    ///
+    /// ### Returns
+    /// The signatures of the transactions
+    pub fn initialize(&self, decimals: u8) -> Result<Vec<Signature>> {
+        let signer = self.program.payer();
+
+        [
+            instruction::initialize_state(&signer, &signer, decimals),
+            instruction::initialize_wallets(&signer, &signer),
+            instruction::initialize_burn_history(&signer),
+        ]
+        .into_iter()
+        .map(|instruction| self.send(vec![instruction]))
+        .collect()
+    }
+
+    /// Imports the token state of the migration source.
+    ///
+    /// ### Arguments
+    ///
+    /// * `source` - the migration source
+    /// * `entries` - the accounts to fund together with their balances expressed with decimals of the source token
+    /// * `amount_token_to_mint` - amount of tokens to mint, expressed with decimals of the source token
+    /// * `amount_token_to_burn` - amount of tokens to burn, expressed with decimals of the source token
+    /// * `source_decimals` - number of decimals of the source token
+    /// * `community_unlock_delay_months` - number of months before the first unlock of the community wallet
+    /// * `withdrawal_grace_period_seconds` - number of seconds after the import during which no vested wallet can withdraw tokens
+    ///
+    /// ### Returns
+    /// The signature of the transaction
+    #[allow(clippy::too_many_arguments)]
+    pub fn import(
+        &self,
+        source: MigrationSource,
+        entries: Vec<AccountInfoFromEthereum>,
+        amount_token_to_mint: u128,
+        amount_token_to_burn: u128,
+        source_decimals: u8,
+        community_unlock_delay_months: u8,
+        withdrawal_grace_period_seconds: i64,
+    ) -> Result<Signature> {
+        self.send(instruction::import_token_state(
+            &self.program.payer(),
+            source,
+            entries,
+            amount_token_to_mint,
+            amount_token_to_burn,
+            source_decimals,
+            community_unlock_delay_months,
+            withdrawal_grace_period_seconds,
+        ))
+    }
+
+    /// Executes the monthly burn, the staking accounts are passed if staking is enabled.
+    /// The payer must be the contract's owner or a registered keeper.
+    ///
+    /// ### Returns
+    /// The signature of the transaction
+    pub fn burn(&self) -> Result<Signature> {
+        let contract_state = self.contract_state()?;
+
+        self.send(vec![instruction::burn(
+            &self.program.payer(),
+            contract_state.staking_enabled,
+            None,
+        )])
+    }
+
+    /// Registers a keeper allowed to run the automation instructions, the payer must be the contract's owner.
+    ///
+    /// ### Arguments
+    ///
+    /// * `keeper` - the keeper to register
+    ///
+    /// ### Returns
+    /// The signature of the transaction
+    pub fn add_keeper(&self, keeper: &Pubkey) -> Result<Signature> {
+        self.send(vec![instruction::add_keeper(&self.program.payer(), keeper)])
+    }
+
+    /// Unregisters a keeper, the payer must be the contract's owner.
+    ///
+    /// ### Arguments
+    ///
+    /// * `keeper` - the keeper to unregister
+    ///
+    /// ### Returns
+    /// The signature of the transaction
+    pub fn remove_keeper(&self, keeper: &Pubkey) -> Result<Signature> {
+        self.send(vec![instruction::remove_keeper(
+            &self.program.payer(),
+            keeper,
+        )])
+    }
+
+    /// Withdraws unlocked tokens from the given vested wallet.
+    ///
+    /// ### Arguments
+    ///
+    /// * `wallet_kind` - the vested wallet to withdraw from
+    /// * `amount_to_withdraw` - amount of tokens to withdraw, `leancoin::WITHDRAW_ALL_UNLOCKED` withdraws all tokens currently available
+    /// * `deposit_wallet` - the token account receiving the tokens
+    /// * `idempotency_key` - the optional key rejecting any replay of the withdrawal
+    ///
+    /// ### Returns
+    /// The signature of the transaction
+    pub fn withdraw(
+        &self,
+        wallet_kind: WalletKind,
+        amount_to_withdraw: u64,
+        deposit_wallet: &Pubkey,
+        idempotency_key: Option<[u8; 16]>,
+    ) -> Result<Signature> {
+        let receipt_index = self.vesting_state()?.next_receipt_index[wallet_kind.index()];
+
+        self.send(vec![instruction::withdraw(
+            &self.program.payer(),
+            wallet_kind,
+            amount_to_withdraw,
+            deposit_wallet,
+            idempotency_key,
+            receipt_index,
+        )])
+    }
+
+    /// Fetches the contract state.
+    pub fn contract_state(&self) -> Result<ContractState> {
+        Ok(self.program.account(pda::address(CONTRACT_STATE_SEED))?)
+    }
+
+    /// Fetches the vesting state.
+    pub fn vesting_state(&self) -> Result<VestingState> {
+        Ok(self.program.account(pda::address(VESTING_STATE_SEED))?)
+    }
+
+    /// Fetches the latest burns kept by the burn history.
+    ///
+    /// ### Returns
+    /// The records ordered from the oldest to the latest burn, see `BurnHistory::latest_burns`
+    pub fn latest_burns(&self) -> Result<Vec<BurnRecord>> {
+        let burn_history: BurnHistory = self.program.account(pda::address(BURN_HISTORY_SEED))?;
+
+        Ok(burn_history.latest_burns())
+    }
+
+    /// Calculates the vesting status at the current time of the cluster.
+    ///
+    /// ### Returns
+    /// The vesting status, see `vesting_status_at`
+    pub fn vesting_status(&self) -> Result<VestingStatus> {
+        let clock_account = self
+            .program
+            .rpc()
+            .get_account(&sysvar::clock::ID)
+            .map_err(ClientError::from)?;
+        let clock: Clock = from_account(&clock_account).ok_or(ClientError::AccountNotFound)?;
+
+        vesting_status_at(&self.vesting_state()?, clock.unix_timestamp)
+    }
+
+    /// Sends the instructions in a single transaction signed by the payer.
+    fn send(&self, instructions: Vec<Instruction>) -> Result<Signature> {
+        let request = instructions
+            .into_iter()
+            .fold(self.program.request(), |request, instruction| {
+                request.instruction(instruction)
+            });
+
+        Ok(request.send()?)
+    }
+}
@@ -0,0 +1,282 @@
+//! Address lookup tables of the import transactions.
+//! An import passes every funded account twice, as an account meta and in the entries, so a legacy transaction
+//! runs out of space after a few entries. A versioned transaction loads the account metas from a lookup table
+//! and references each of them with a single byte instead.
+
+use anchor_client::solana_sdk::{
+    address_lookup_table_account::AddressLookupTableAccount,
+    clock::Slot,
+    hash::Hash,
+    instruction::{Instruction, InstructionError},
+    message::{v0, CompileError, VersionedMessage},
+    pubkey::Pubkey,
+};
+use leancoin::{
+    AccountInfoFromEthereum, MigrationSource, CONTRACT_STATE_SEED, MINT_SEED, PROGRAM_ACCOUNT_SEED,
+    VESTING_STATE_SEED,
+};
+use solana_address_lookup_table_program::{instruction, state::AddressLookupTable};
+
+use crate::pda::address;
+
+/// Maximal number of addresses added by a single extend instruction, so its transaction fits into a packet.
+pub const MAX_ADDRESSES_PER_EXTEND: usize = 20;
+
+/// Returns the addresses of the accounts passed to the import of the given entries.
+/// The signer and the invoked programs are not included, they have to be static keys of the transaction.
+///
+/// ### Arguments
+///
+/// * `entries` - the accounts to fund by the import
+///
+/// ### Returns
+/// The addresses without duplicates, the program derived accounts first
+pub fn import_addresses(entries: &[AccountInfoFromEthereum]) -> Vec<Pubkey> {
+    let mut addresses = vec![
+        address(CONTRACT_STATE_SEED),
+        address(VESTING_STATE_SEED),
+        address(MINT_SEED),
+        address(PROGRAM_ACCOUNT_SEED),
+        anchor_spl::token::ID,
+    ];
+
+    for entry in entries {
+        if !addresses.contains(&entry.account_public_key) {
+            addresses.push(entry.account_public_key);
+        }
+    }
+
+    addresses
+}
+
+/// Builds the instructions creating a lookup table with the given addresses.
+///
+/// ### Arguments
+///
+/// * `authority` - the authority of the lookup table, it signs all the instructions
+/// * `payer` - the payer of the lookup table's rent
+/// * `recent_slot` - a recent slot from which the address of the lookup table is derived
+/// * `addresses` - the addresses stored in the lookup table, e.g. the ones returned by `import_addresses`
+///
+/// ### Returns
+/// The address of the lookup table and the instructions, each of them must be sent in its own transaction in the given order
+pub fn create_lookup_table(
+    authority: &Pubkey,
+    payer: &Pubkey,
+    recent_slot: Slot,
+    addresses: &[Pubkey],
+) -> (Pubkey, Vec<Instruction>) {
+    let (create_instruction, lookup_table) =
+        instruction::create_lookup_table(*authority, *payer, recent_slot);
+
+    let mut instructions = vec![create_instruction];
+    instructions.extend(extend_lookup_table(
+        &lookup_table,
+        authority,
+        payer,
+        addresses,
+    ));
+
+    (lookup_table, instructions)
+}
+
+/// Builds the instructions adding the given addresses to the existing lookup table.
+/// The added addresses can be used by transactions sent in the slots following the extension.
+///
+/// ### Arguments
+///
+/// * `lookup_table` - the address of the lookup table
+/// * `authority` - the authority of the lookup table, it signs all the instructions
+/// * `payer` - the payer of the additional rent of the lookup table
+/// * `addresses` - the addresses to add
+///
+/// ### Returns
+/// The instructions, each of them must be sent in its own transaction
+pub fn extend_lookup_table(
+    lookup_table: &Pubkey,
+    authority: &Pubkey,
+    payer: &Pubkey,
+    addresses: &[Pubkey],
+) -> Vec<Instruction> {
+    addresses
+        .chunks(MAX_ADDRESSES_PER_EXTEND)
+        .map(|chunk| {
+            instruction::extend_lookup_table(
+                *lookup_table,
+                *authority,
+                Some(*payer),
+                chunk.to_vec(),
+            )
+        })
+        .collect()
+}
+
+/// Deserializes the fetched lookup table account, so it can be used to compile the messages.
+///
+/// ### Arguments
+///
+/// * `lookup_table` - the address of the lookup table
+/// * `data` - the data of the lookup table account
+///
+/// ### Returns
+/// The lookup table or an error if the data does not belong to a lookup table
+pub fn lookup_table_account(
+    lookup_table: &Pubkey,
+    data: &[u8],
+) -> std::result::Result<AddressLookupTableAccount, InstructionError> {
+    let table = AddressLookupTable::deserialize(data)?;
+
+    Ok(AddressLookupTableAccount {
+        key: *lookup_table,
+        addresses: table.addresses.to_vec(),
+    })
+}
+
+/// Builds the versioned message importing the token state of the migration source, see `instruction::import_token_state`.
+/// The accounts found in the lookup tables are loaded from them, all other accounts are static keys of the message.
+///
+/// ### Arguments
+///
+/// * `signer` - the signer and the fee payer of the transaction which must be the contract's owner
+/// * `source` - the migration source
+/// * `entries` - the accounts to fund together with their balances expressed with decimals of the source token
+/// * `amount_token_to_mint` - amount of tokens to mint, expressed with decimals of the source token
+/// * `amount_token_to_burn` - amount of tokens to burn, expressed with decimals of the source token
+/// * `source_decimals` - number of decimals of the source token
+/// * `community_unlock_delay_months` - number of months before the first unlock of the community wallet
+/// * `withdrawal_grace_period_seconds` - number of seconds after the import during which no vested wallet can withdraw tokens
+/// * `lookup_tables` - the lookup tables containing the accounts of the import
+/// * `recent_blockhash` - the recent blockhash
+///
+/// ### Returns
+/// The message to sign or an error if it cannot be compiled, e.g. because of too many accounts
+#[allow(clippy::too_many_arguments)]
+pub fn import_message(
+    signer: &Pubkey,
+    source: MigrationSource,
+    entries: Vec<AccountInfoFromEthereum>,
+    amount_token_to_mint: u128,
+    amount_token_to_burn: u128,
+    source_decimals: u8,
+    community_unlock_delay_months: u8,
+    withdrawal_grace_period_seconds: i64,
+    lookup_tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+) -> std::result::Result<VersionedMessage, CompileError> {
+    let instructions = crate::instruction::import_token_state(
+        signer,
+        source,
+        entries,
+        amount_token_to_mint,
+        amount_token_to_burn,
+        source_decimals,
+        community_unlock_delay_months,
+        withdrawal_grace_period_seconds,
+    );
+
+    Ok(VersionedMessage::V0(v0::Message::try_compile(
+        signer,
+        &instructions,
+        lookup_tables,
+        recent_blockhash,
+    )?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use leancoin::WalletKind;
+
+    fn entry(wallet_name: &str, account_public_key: Pubkey) -> AccountInfoFromEthereum {
+        AccountInfoFromEthereum {
+            wallet_name: wallet_name.to_string(),
+            account_public_key,
+            account_balance: 1,
+        }
+    }
+
+    #[test]
+    fn test_import_addresses_without_duplicates() {
+        let holder = Pubkey::new_unique();
+        let entries = vec![
+            entry("community", address(WalletKind::Community.seed())),
+            entry("holder", holder),
+            entry("other holder", holder),
+        ];
+
+        let addresses = import_addresses(&entries);
+
+        assert_eq!(addresses.len(), 7);
+        assert_eq!(addresses[0], address(CONTRACT_STATE_SEED));
+        assert_eq!(addresses[4], anchor_spl::token::ID);
+        assert_eq!(addresses[5], address(WalletKind::Community.seed()));
+        assert_eq!(addresses[6], holder);
+    }
+
+    #[test]
+    fn test_create_lookup_table_in_chunks() {
+        let authority = Pubkey::new_unique();
+        let addresses = (0..MAX_ADDRESSES_PER_EXTEND + 1)
+            .map(|_| Pubkey::new_unique())
+            .collect::<Vec<Pubkey>>();
+
+        let (lookup_table, instructions) =
+            create_lookup_table(&authority, &authority, 7, &addresses);
+
+        assert_eq!(
+            lookup_table,
+            instruction::derive_lookup_table_address(&authority, 7).0
+        );
+        assert_eq!(instructions.len(), 3);
+        assert!(
+            instructions
+                .iter()
+                .all(|instruction| instruction.program_id
+                    == solana_address_lookup_table_program::id())
+        );
+        assert!(instructions[1..]
+            .iter()
+            .all(|instruction| instruction.accounts[0].pubkey == lookup_table));
+    }
+
+    #[test]
+    fn test_import_message_loads_accounts_from_lookup_table() {
+        let signer = Pubkey::new_unique();
+        let entries = (0..30)
+            .map(|index| entry(&format!("holder{}", index), Pubkey::new_unique()))
+            .collect::<Vec<AccountInfoFromEthereum>>();
+        let lookup_table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: import_addresses(&entries),
+        };
+
+        let message = import_message(
+            &signer,
+            MigrationSource::Bsc,
+            entries,
+            30,
+            0,
+            9,
+            0,
+            0,
+            &[lookup_table],
+            Hash::new_unique(),
+        )
+        .unwrap();
+
+        let message = match message {
+            VersionedMessage::V0(message) => message,
+            VersionedMessage::Legacy(_) => panic!("unexpected legacy message"),
+        };
+        // the signer and the invoked programs: the contract and the compute budget program
+        assert_eq!(message.account_keys.len(), 3);
+        assert_eq!(message.account_keys[0], signer);
+        assert!(message.account_keys.contains(&leancoin::ID));
+        assert_eq!(message.address_table_lookups.len(), 1);
+        assert_eq!(
+            message.address_table_lookups[0].writable_indexes.len()
+                + message.address_table_lookups[0].readonly_indexes.len(),
+            35
+        );
+    }
+}
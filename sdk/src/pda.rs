@@ -0,0 +1,77 @@
+//! Addresses of the program derived accounts of the contract.
+
+use anchor_lang::solana_program::pubkey::Pubkey;
+use leancoin::{WalletKind, WITHDRAWAL_INTENT_SEED, WITHDRAWAL_RECEIPT_SEED};
+
+/// Finds the address and the nonce of the program derived account created with the given seed.
+///
+/// ### Arguments
+///
+/// * `seed` - the seed of the account, e.g. `leancoin::MINT_SEED`
+///
+/// ### Returns
+/// The address of the account together with its nonce
+pub fn find_program_address(seed: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[seed.as_bytes()], &leancoin::ID)
+}
+
+/// Finds the address of the program derived account created with the given seed.
+///
+/// ### Arguments
+///
+/// * `seed` - the seed of the account, e.g. `leancoin::MINT_SEED`
+///
+/// ### Returns
+/// The address of the account
+pub fn address(seed: &str) -> Pubkey {
+    find_program_address(seed).0
+}
+
+/// Finds the address of the token account of the given vested wallet.
+///
+/// ### Arguments
+///
+/// * `wallet_kind` - the vested wallet
+///
+/// ### Returns
+/// The address of the wallet's token account
+pub fn wallet_address(wallet_kind: WalletKind) -> Pubkey {
+    address(wallet_kind.seed())
+}
+
+/// Finds the address of the withdrawal intent recorded for the given idempotency key.
+///
+/// ### Arguments
+///
+/// * `idempotency_key` - the idempotency key of the withdrawal
+///
+/// ### Returns
+/// The address of the withdrawal intent account
+pub fn withdrawal_intent_address(idempotency_key: &[u8; 16]) -> Pubkey {
+    Pubkey::find_program_address(
+        &[WITHDRAWAL_INTENT_SEED.as_bytes(), idempotency_key],
+        &leancoin::ID,
+    )
+    .0
+}
+
+/// Finds the address of the receipt recorded for the given withdrawal from the vested wallet.
+///
+/// ### Arguments
+///
+/// * `wallet_kind` - the vested wallet the tokens are withdrawn from
+/// * `index` - the index of the withdrawal within the wallet, see `VestingState::next_receipt_index`
+///
+/// ### Returns
+/// The address of the withdrawal receipt account
+pub fn withdrawal_receipt_address(wallet_kind: WalletKind, index: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            WITHDRAWAL_RECEIPT_SEED.as_bytes(),
+            &[wallet_kind as u8],
+            &index.to_le_bytes(),
+        ],
+        &leancoin::ID,
+    )
+    .0
+}
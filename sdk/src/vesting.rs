@@ -0,0 +1,81 @@
+//! Vesting status of the vested wallets, calculated with the same functions which are used by the contract.
+
+use leancoin::account::VestingState;
+use leancoin::utils::{calculate_month_difference, calculate_wallet_unlocked_amount};
+use leancoin::{WalletKind, VESTED_WALLETS_COUNT};
+
+use crate::error::Result;
+
+/// All vested wallets in the order of `WalletKind`.
+pub const WALLET_KINDS: [WalletKind; VESTED_WALLETS_COUNT] = WalletKind::ALL;
+
+/// The status of a vested wallet at the given time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WalletStatus {
+    pub wallet_kind: WalletKind,
+    /// the initial balance of the wallet after Ethereum token state import
+    pub initial_balance: u64,
+    /// the amount of tokens unlocked so far, including the already withdrawn ones
+    pub unlocked: u64,
+    pub already_withdrawn: u64,
+    /// the amount of tokens which can be withdrawn now, zero once the wallet is finalized
+    pub available: u64,
+}
+
+/// The status of the vesting at the given time.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VestingStatus {
+    /// number of full months since the vesting start, `None` if the vesting has not started yet
+    pub months_since_start: Option<u64>,
+    /// the status of each vested wallet ordered like `WalletKind`
+    pub wallets: Vec<WalletStatus>,
+    /// the balance of the swap wallet set by the imports, the swap wallet is not vested
+    pub swap_balance: u64,
+}
+
+/// Calculates the vesting status at the given time.
+///
+/// ### Arguments
+///
+/// * `vesting_state` - the vesting state
+/// * `timestamp` - the unix timestamp at which the status is calculated
+///
+/// ### Returns
+/// The vesting status or an error if the timestamp is earlier than the vesting start.
+pub fn vesting_status_at(vesting_state: &VestingState, timestamp: i64) -> Result<VestingStatus> {
+    let months_since_start = match vesting_state.start_timestamp {
+        0 => None,
+        start_timestamp => Some(calculate_month_difference(start_timestamp, timestamp)?),
+    };
+
+    let wallets = WALLET_KINDS
+        .iter()
+        .map(|&wallet_kind| {
+            let wallet = vesting_state.wallet(wallet_kind);
+            let initial_balance = wallet.initial_balance;
+            let already_withdrawn = wallet.withdrawn;
+            let unlocked = match months_since_start {
+                Some(_) => calculate_wallet_unlocked_amount(vesting_state, wallet_kind, timestamp)?,
+                None => 0,
+            };
+            let available = match vesting_state.finalized_wallets & wallet_kind.mask() {
+                0 => unlocked.saturating_sub(already_withdrawn),
+                _ => 0,
+            };
+
+            Ok(WalletStatus {
+                wallet_kind,
+                initial_balance,
+                unlocked,
+                already_withdrawn,
+                available,
+            })
+        })
+        .collect::<Result<Vec<WalletStatus>>>()?;
+
+    Ok(VestingStatus {
+        months_since_start,
+        swap_balance: vesting_state.initial_swap_wallet_balance,
+        wallets,
+    })
+}
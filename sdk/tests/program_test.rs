@@ -0,0 +1,415 @@
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token::spl_token::{self, state::Account as TokenAccount};
+use leancoin::account::{BurnHistory, ContractState, VestingState};
+use leancoin::error_codes::LeancoinError;
+use leancoin::{
+    AccountInfoFromEthereum, MigrationSource, WalletKind, BURNING_ACCOUNT_SEED, BURN_HISTORY_SEED,
+    CONTRACT_STATE_SEED, LEGACY_MINT_DECIMALS, MINT_SEED, VESTING_STATE_SEED,
+};
+use leancoin_sdk::{instruction, lookup_table, pda, vesting_status_at, LeancoinClientError};
+use solana_program_test::*;
+use solana_sdk::{
+    clock::Clock,
+    hash::Hash,
+    instruction::Instruction,
+    message::Message,
+    packet::PACKET_DATA_SIZE,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    slot_hashes::SlotHashes,
+    system_instruction,
+    transaction::{Transaction, VersionedTransaction},
+};
+
+// Sunday, 5 March 2023 01:01:01
+const START_TIMESTAMP: i64 = 1677978061;
+
+async fn process(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), LeancoinClientError> {
+    let recent_blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        recent_blockhash,
+    );
+
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|error| error.unwrap().into())
+}
+
+async fn get_account<T: AccountDeserialize>(context: &mut ProgramTestContext, seed: &str) -> T {
+    let account = context
+        .banks_client
+        .get_account(pda::address(seed))
+        .await
+        .unwrap()
+        .unwrap();
+
+    T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+async fn get_token_balance(context: &mut ProgramTestContext, token_account: Pubkey) -> u64 {
+    let account = context
+        .banks_client
+        .get_account(token_account)
+        .await
+        .unwrap()
+        .unwrap();
+
+    TokenAccount::unpack(&account.data).unwrap().amount
+}
+
+async fn create_deposit_wallet(context: &mut ProgramTestContext) -> Pubkey {
+    let deposit_wallet = Keypair::new();
+    create_token_account(context, &deposit_wallet).await;
+
+    deposit_wallet.pubkey()
+}
+
+async fn create_token_account(context: &mut ProgramTestContext, deposit_wallet: &Keypair) {
+    let payer = context.payer.pubkey();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    process(
+        context,
+        &[
+            system_instruction::create_account(
+                &payer,
+                &deposit_wallet.pubkey(),
+                rent.minimum_balance(TokenAccount::LEN),
+                TokenAccount::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_account(
+                &spl_token::id(),
+                &deposit_wallet.pubkey(),
+                &pda::address(MINT_SEED),
+                &payer,
+            )
+            .unwrap(),
+        ],
+        &[deposit_wallet],
+    )
+    .await
+    .unwrap();
+}
+
+fn import_entries() -> Vec<AccountInfoFromEthereum> {
+    [
+        (
+            "community",
+            WalletKind::Community.seed(),
+            1000000000000000000,
+        ),
+        (
+            "partnership",
+            WalletKind::Partnership.seed(),
+            2000000000000000000,
+        ),
+        (
+            "marketing",
+            WalletKind::Marketing.seed(),
+            1500000000000000000,
+        ),
+        (
+            "liquidity",
+            WalletKind::Liquidity.seed(),
+            1000000000000000000,
+        ),
+        ("burning", BURNING_ACCOUNT_SEED, 1800000000000000000),
+    ]
+    .into_iter()
+    .map(
+        |(wallet_name, seed, account_balance)| AccountInfoFromEthereum {
+            wallet_name: wallet_name.to_string(),
+            account_public_key: pda::address(seed),
+            account_balance,
+        },
+    )
+    .collect()
+}
+
+fn import_entries_with_holders(holders: &[Keypair]) -> Vec<AccountInfoFromEthereum> {
+    let mut entries = import_entries();
+    entries.extend(
+        holders
+            .iter()
+            .enumerate()
+            .map(|(index, holder)| AccountInfoFromEthereum {
+                wallet_name: format!("holder{}", index),
+                account_public_key: holder.pubkey(),
+                account_balance: 100000000000000000,
+            }),
+    );
+
+    entries
+}
+
+#[tokio::test]
+async fn test_sdk_initialize_import_withdraw_and_burn() {
+    let mut program_test = ProgramTest::new("leancoin", leancoin::ID, processor!(leancoin::entry));
+    program_test.set_compute_max_units(500000);
+
+    let mut context = program_test.start_with_context().await;
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp = START_TIMESTAMP;
+    context.set_sysvar(&clock);
+
+    let payer = context.payer.pubkey();
+
+    process(
+        &mut context,
+        &[instruction::initialize_state(
+            &payer,
+            &payer,
+            LEGACY_MINT_DECIMALS,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    process(
+        &mut context,
+        &[instruction::initialize_wallets(&payer, &payer)],
+        &[],
+    )
+    .await
+    .unwrap();
+    process(
+        &mut context,
+        &[instruction::initialize_burn_history(&payer)],
+        &[],
+    )
+    .await
+    .unwrap();
+    process(
+        &mut context,
+        &instruction::import_token_state(
+            &payer,
+            MigrationSource::Ethereum,
+            import_entries(),
+            7300000000000000000,
+            0,
+            18,
+            0,
+            0,
+        ),
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let vesting_state: VestingState = get_account(&mut context, VESTING_STATE_SEED).await;
+    let status = vesting_status_at(&vesting_state, START_TIMESTAMP).unwrap();
+    assert_eq!(status.months_since_start, Some(0));
+    let liquidity = status.wallets[WalletKind::Liquidity as usize];
+    assert_eq!(liquidity.initial_balance, 1000000000);
+    assert_eq!(liquidity.unlocked, 500000000);
+    assert_eq!(liquidity.available, 500000000);
+    assert_eq!(status.wallets[WalletKind::Marketing as usize].available, 0);
+
+    let deposit_wallet = create_deposit_wallet(&mut context).await;
+    process(
+        &mut context,
+        &[instruction::withdraw(
+            &payer,
+            WalletKind::Liquidity,
+            100,
+            &deposit_wallet,
+            Some([7; 16]),
+            0,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    assert_eq!(get_token_balance(&mut context, deposit_wallet).await, 100);
+
+    let vesting_state: VestingState = get_account(&mut context, VESTING_STATE_SEED).await;
+    let liquidity = vesting_status_at(&vesting_state, START_TIMESTAMP)
+        .unwrap()
+        .wallets[WalletKind::Liquidity as usize];
+    assert_eq!(liquidity.already_withdrawn, 100);
+    assert_eq!(liquidity.available, 499999900);
+
+    let error = process(
+        &mut context,
+        &[instruction::withdraw(
+            &payer,
+            WalletKind::Marketing,
+            1,
+            &deposit_wallet,
+            None,
+            0,
+        )],
+        &[],
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(
+        error,
+        LeancoinClientError::Contract(LeancoinError::NotEnoughTokensMarketing)
+    ));
+
+    process(&mut context, &[instruction::burn(&payer, false, None)], &[])
+        .await
+        .unwrap();
+    let contract_state: ContractState = get_account(&mut context, CONTRACT_STATE_SEED).await;
+    assert_eq!(contract_state.last_burning_month, 3);
+    assert_eq!(contract_state.last_burning_year, 2023);
+    assert_eq!(
+        get_token_balance(&mut context, pda::address(BURNING_ACCOUNT_SEED)).await,
+        1800000000 - 1800000000 / 20
+    );
+    let burn_history: BurnHistory = get_account(&mut context, BURN_HISTORY_SEED).await;
+    assert_eq!(burn_history.latest_burns().len(), 1);
+    assert_eq!(burn_history.latest_burns()[0].amount, 1800000000 / 20);
+}
+
+#[tokio::test]
+async fn test_sdk_initialize_in_one_transaction() {
+    let mut program_test = ProgramTest::new("leancoin", leancoin::ID, processor!(leancoin::entry));
+    program_test.set_compute_max_units(500000);
+
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.pubkey();
+
+    // both phases of the initialization still fit into a single legacy transaction
+    let instructions = [
+        instruction::initialize_state(&payer, &payer, LEGACY_MINT_DECIMALS),
+        instruction::initialize_wallets(&payer, &payer),
+    ];
+    let transaction = Transaction::new_unsigned(Message::new(&instructions, Some(&payer)));
+    assert!(bincode::serialize(&transaction).unwrap().len() <= PACKET_DATA_SIZE);
+
+    process(&mut context, &instructions, &[]).await.unwrap();
+
+    let contract_state: ContractState = get_account(&mut context, CONTRACT_STATE_SEED).await;
+    assert!(contract_state.wallets_initialized);
+    assert_eq!(contract_state.mint, pda::address(MINT_SEED));
+}
+
+#[tokio::test]
+async fn test_sdk_import_with_lookup_table() {
+    let mut program_test = ProgramTest::new("leancoin", leancoin::ID, processor!(leancoin::entry));
+    program_test.set_compute_max_units(500000);
+
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.pubkey();
+
+    let holders = (0..8).map(|_| Keypair::new()).collect::<Vec<Keypair>>();
+    let entries = import_entries_with_holders(&holders);
+
+    // the lookup table is derived from a slot which has to be found in the slot hashes
+    let recent_slot = 1;
+    context.set_sysvar(&SlotHashes::new(&[(recent_slot, Hash::new_unique())]));
+    let (lookup_table, instructions) = lookup_table::create_lookup_table(
+        &payer,
+        &payer,
+        recent_slot,
+        &lookup_table::import_addresses(&entries),
+    );
+    for instruction in instructions {
+        process(&mut context, &[instruction], &[]).await.unwrap();
+    }
+    // the addresses are active in the slots following the extension
+    let slot = context.banks_client.get_root_slot().await.unwrap();
+    context.warp_to_slot(slot + 2).unwrap();
+
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp = START_TIMESTAMP;
+    context.set_sysvar(&clock);
+
+    process(
+        &mut context,
+        &[instruction::initialize_state(
+            &payer,
+            &payer,
+            LEGACY_MINT_DECIMALS,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+    process(
+        &mut context,
+        &[instruction::initialize_wallets(&payer, &payer)],
+        &[],
+    )
+    .await
+    .unwrap();
+    for holder in &holders {
+        create_token_account(&mut context, holder).await;
+    }
+
+    // the same import does not fit into a legacy transaction
+    let legacy_transaction = Transaction::new_unsigned(Message::new(
+        &instruction::import_token_state(
+            &payer,
+            MigrationSource::Ethereum,
+            import_entries_with_holders(&holders),
+            8100000000000000000,
+            0,
+            18,
+            0,
+            0,
+        ),
+        Some(&payer),
+    ));
+    assert!(bincode::serialize(&legacy_transaction).unwrap().len() > PACKET_DATA_SIZE);
+
+    let lookup_table_data = context
+        .banks_client
+        .get_account(lookup_table)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let lookup_table_account =
+        lookup_table::lookup_table_account(&lookup_table, &lookup_table_data).unwrap();
+    let recent_blockhash = context.get_new_latest_blockhash().await.unwrap();
+    let message = lookup_table::import_message(
+        &payer,
+        MigrationSource::Ethereum,
+        entries,
+        8100000000000000000,
+        0,
+        18,
+        0,
+        0,
+        &[lookup_table_account],
+        recent_blockhash,
+    )
+    .unwrap();
+    let transaction = VersionedTransaction::try_new(message, &[&context.payer]).unwrap();
+    assert!(bincode::serialize(&transaction).unwrap().len() <= PACKET_DATA_SIZE);
+
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+
+    for holder in &holders {
+        assert_eq!(
+            get_token_balance(&mut context, holder.pubkey()).await,
+            100000000
+        );
+    }
+    let vesting_state: VestingState = get_account(&mut context, VESTING_STATE_SEED).await;
+    assert_eq!(
+        vesting_state.wallet(WalletKind::Marketing).initial_balance,
+        1500000000
+    );
+}
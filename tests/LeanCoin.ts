@@ -8,6 +8,7 @@ import {
     Transaction,
     ComputeBudgetProgram,
     SYSVAR_RENT_PUBKEY,
+    SYSVAR_INSTRUCTIONS_PUBKEY,
     clusterApiUrl,
 } from "@solana/web3.js";
 
@@ -16,7 +17,11 @@ import { BN } from "bn.js";
 import { Leancoin } from "../target/types/leancoin";
 import { assert, expect } from "chai";
 import * as dotenv from "dotenv";
-import { findProgramAddress } from "./utils/pda";
+import {
+    findNextWithdrawalReceiptAddress,
+    findProgramAddress,
+    WalletKind,
+} from "./utils/pda";
 import { getOrCreateAssociatedTokenAccount } from "./utils/accounts";
 import * as mpl from "@metaplex-foundation/mpl-token-metadata";
 import { isBetween1and5 } from "./utils/time";
@@ -61,6 +66,9 @@ describe("LeanCoin", () => {
     let liquidity_account_address: anchor.web3.PublicKey = null;
     let liquidity_account_bump: number = null;
 
+    let labels_address: anchor.web3.PublicKey = null;
+    let burn_history_address: anchor.web3.PublicKey = null;
+
     let swap_account_address: anchor.web3.PublicKey = null;
 
     let swap_keypair: anchor.web3.Keypair = Keypair.generate();
@@ -70,6 +78,9 @@ describe("LeanCoin", () => {
 
     let amount_token_to_mint = new BN(0);
     let amount_token_to_burn = new BN(0);
+    const source_decimals = 18; // decimals of the Ethereum token
+    const community_unlock_delay_months = 0;
+    const withdrawal_grace_period_seconds = new BN(0);
 
     const test_account = Keypair.fromSecretKey(
         bs58.decode(
@@ -107,34 +118,57 @@ describe("LeanCoin", () => {
             [vesting_state_address, vesting_state_bump] =
                 findProgramAddress("vesting_state");
 
+            [labels_address] = findProgramAddress("labels");
+
+            [burn_history_address] = findProgramAddress("burn_history");
+
             [authority, authority_bump] = findProgramAddress("authority");
         });
 
-        it("should initialize the contract", async () => {
+        it("should initialize the contract state", async () => {
             const tx = await program.methods
-                .initialize(
-                    contract_state_bump,
-                    vesting_state_bump,
-                    mint_bump,
-                    program_account_bump,
-                    burning_account_bump,
-                    community_account_bump,
-                    liquidity_account_bump,
-                    marketing_account_bump,
-                    partnership_account_bump,
-                )
+                .initializeState(9)
                 .accounts({
                     contractState: contract_state_address,
                     vestingState: vesting_state_address,
-                    communityAccount: community_account_address,
-                    liquidityAccount: liquidity_account_address,
-                    marketingAccount: marketing_account_address,
-                    partnershipAccount: partnership_account_address,
                     mint: mint,
                     programAccount: program_account_address,
                     burningAccount: burning_account_address,
                     tokenProgram: TOKEN_PROGRAM_ID,
                     signer: provider.wallet.publicKey,
+                    payer: provider.wallet.publicKey,
+                    systemProgram: anchor.web3.SystemProgram.programId,
+                })
+                .rpc();
+        });
+
+        it("should initialize the vested wallets", async () => {
+            const tx = await program.methods
+                .initializeWallets(null, null)
+                .accounts({
+                    contractState: contract_state_address,
+                    vestingState: vesting_state_address,
+                    mint: mint,
+                    communityAccount: community_account_address,
+                    partnershipAccount: partnership_account_address,
+                    marketingAccount: marketing_account_address,
+                    liquidityAccount: liquidity_account_address,
+                    labels: labels_address,
+                    tokenProgram: TOKEN_PROGRAM_ID,
+                    signer: provider.wallet.publicKey,
+                    payer: provider.wallet.publicKey,
+                    systemProgram: anchor.web3.SystemProgram.programId,
+                })
+                .rpc();
+        });
+
+        it("should initialize the burn history", async () => {
+            const tx = await program.methods
+                .initializeBurnHistory()
+                .accounts({
+                    contractState: contract_state_address,
+                    burnHistory: burn_history_address,
+                    payer: provider.wallet.publicKey,
                     systemProgram: anchor.web3.SystemProgram.programId,
                 })
                 .rpc();
@@ -148,16 +182,17 @@ describe("LeanCoin", () => {
         });
 
         it("Initialize token accounts", async () => {
-            amount_token_to_mint = new BN("10000000000000000000"); // 100% of total supply
-            amount_token_to_burn = new BN("1800000000000000000"); // 18% of total supply
+            // amounts are expressed with 18 decimals like on Ethereum
+            amount_token_to_mint = new BN("10000000000000000000000000000"); // 100% of total supply
+            amount_token_to_burn = new BN("1800000000000000000000000000"); // 18% of total supply
 
             const amounts = {
-                burn: new BN("1470000000000000000"), // 14.7% of total supply
-                community: new BN("1000000000000000000"), // 10% of total supply
-                partnership: new BN("2000000000000000000"), // 20% of total supply
-                marketing: new BN("1500000000000000000"), // 15% of total supply
-                liquidity: new BN("1000000000000000000"), // 10% of total supply
-                swap: new BN("1230000000000000000"), // 12.3% of total supply
+                burn: new BN("1470000000000000000000000000"), // 14.7% of total supply
+                community: new BN("1000000000000000000000000000"), // 10% of total supply
+                partnership: new BN("2000000000000000000000000000"), // 20% of total supply
+                marketing: new BN("1500000000000000000000000000"), // 15% of total supply
+                liquidity: new BN("1000000000000000000000000000"), // 10% of total supply
+                swap: new BN("1230000000000000000000000000"), // 12.3% of total supply
             };
 
             const createAccount = (name, address) => {
@@ -214,6 +249,9 @@ describe("LeanCoin", () => {
                     user_info_ethereum_token_state_mapping,
                     amount_token_to_mint,
                     amount_token_to_burn,
+                    source_decimals,
+                    community_unlock_delay_months,
+                    withdrawal_grace_period_seconds,
                 )
                 .remainingAccounts(rem_accounts)
                 .accounts({
@@ -246,6 +284,9 @@ describe("LeanCoin", () => {
                         user_info_ethereum_token_state_mapping,
                         amount_token_to_mint,
                         amount_token_to_burn,
+                        source_decimals,
+                        community_unlock_delay_months,
+                        withdrawal_grace_period_seconds,
                     )
                     .remainingAccounts(rem_accounts)
                     .accounts({
@@ -320,6 +361,70 @@ describe("LeanCoin", () => {
             assert(swap_account_balance.value.amount == "1230000000000000000");
         });
 
+        it("should attest reserves", async () => {
+            const [attestation_address] = findProgramAddress("attestation");
+
+            // the attestation is run by a registered keeper
+            await program.methods
+                .addKeeper(test_account.publicKey)
+                .accounts({
+                    contractState: contract_state_address,
+                    signer: provider.wallet.publicKey,
+                })
+                .rpc();
+
+            await program.methods
+                .attestReserves()
+                .accounts({
+                    contractState: contract_state_address,
+                    vestingState: vesting_state_address,
+                    attestation: attestation_address,
+                    mint: mint,
+                    programAccount: program_account_address,
+                    burningAccount: burning_account_address,
+                    communityAccount: community_account_address,
+                    partnershipAccount: partnership_account_address,
+                    marketingAccount: marketing_account_address,
+                    liquidityAccount: liquidity_account_address,
+                    signer: test_account.publicKey,
+                    systemProgram: anchor.web3.SystemProgram.programId,
+                })
+                .signers([test_account])
+                .rpc();
+
+            const attestation = await program.account.attestation.fetch(
+                attestation_address,
+            );
+            assert(attestation.lastAttestationSlot.toNumber() > 0);
+        });
+
+        it("should return program addresses", async () => {
+            const addresses = await program.methods
+                .getProgramAddresses()
+                .accounts({
+                    contractState: contract_state_address,
+                    vestingState: vesting_state_address,
+                    labels: labels_address,
+                })
+                .view();
+
+            assert(addresses.mint.equals(mint));
+            assert(addresses.burningAccount.equals(burning_account_address));
+            assert(addresses.liquidityAccount.equals(liquidity_account_address));
+            assert(addresses.liquidityWalletNonce == liquidity_account_bump);
+        });
+
+        it("should return months since vesting start", async () => {
+            const months = await program.methods
+                .getMonthsSinceStart()
+                .accounts({
+                    vestingState: vesting_state_address,
+                })
+                .view();
+
+            assert(months.toNumber() == 0);
+        });
+
         it("Fail miss contract_state_address!", async () => {
             let fake_contract_state_address = new PublicKey(
                 "11111111111111111111111111111111",
@@ -331,6 +436,9 @@ describe("LeanCoin", () => {
                         user_info_ethereum_token_state_mapping,
                         amount_token_to_mint,
                         amount_token_to_burn,
+                        source_decimals,
+                        community_unlock_delay_months,
+                        withdrawal_grace_period_seconds,
                     )
                     .remainingAccounts(rem_accounts)
                     .accounts({
@@ -370,6 +478,9 @@ describe("LeanCoin", () => {
                         user_info_ethereum_token_state_mapping,
                         amount_token_to_mint,
                         amount_token_to_burn,
+                        source_decimals,
+                        community_unlock_delay_months,
+                        withdrawal_grace_period_seconds,
                     )
                     .remainingAccounts(rem_accounts)
                     .accounts({
@@ -409,6 +520,9 @@ describe("LeanCoin", () => {
                         user_info_ethereum_token_state_mapping,
                         amount_token_to_mint,
                         amount_token_to_burn,
+                        source_decimals,
+                        community_unlock_delay_months,
+                        withdrawal_grace_period_seconds,
                     )
                     .remainingAccounts(rem_accounts)
                     .accounts({
@@ -448,6 +562,9 @@ describe("LeanCoin", () => {
                         user_info_ethereum_token_state_mapping,
                         amount_token_to_mint,
                         amount_token_to_burn,
+                        source_decimals,
+                        community_unlock_delay_months,
+                        withdrawal_grace_period_seconds,
                     )
                     .remainingAccounts(rem_accounts)
                     .accounts({
@@ -487,6 +604,9 @@ describe("LeanCoin", () => {
                         user_info_ethereum_token_state_mapping,
                         amount_token_to_mint,
                         amount_token_to_burn,
+                        source_decimals,
+                        community_unlock_delay_months,
+                        withdrawal_grace_period_seconds,
                     )
                     .remainingAccounts(rem_accounts)
                     .accounts({
@@ -524,6 +644,9 @@ describe("LeanCoin", () => {
                         user_info_ethereum_token_state_mapping,
                         amount_token_to_mint,
                         amount_token_to_burn,
+                        source_decimals,
+                        community_unlock_delay_months,
+                        withdrawal_grace_period_seconds,
                     )
                     .remainingAccounts(rem_accounts)
                     .accounts({
@@ -560,6 +683,9 @@ describe("LeanCoin", () => {
                         user_info_ethereum_token_state_mapping,
                         amount_token_to_mint,
                         amount_token_to_burn,
+                        source_decimals,
+                        community_unlock_delay_months,
+                        withdrawal_grace_period_seconds,
                     )
                     .remainingAccounts(rem_accounts)
                     .accounts({
@@ -603,6 +729,12 @@ describe("LeanCoin", () => {
                         contractState: contract_state_address,
                         burningAccount: burning_account_address,
                         tokenProgram: TOKEN_PROGRAM_ID,
+                        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                        stakingState: null,
+                        rewardVault: null,
+                        callerTokenAccount: null,
+                        burnHistory: burn_history_address,
+                        signer: provider.wallet.publicKey,
                     })
                     .rpc({ commitment: "confirmed" });
 
@@ -639,6 +771,12 @@ describe("LeanCoin", () => {
                         contractState: fake_contract_state_address,
                         burningAccount: burning_account_address,
                         tokenProgram: TOKEN_PROGRAM_ID,
+                        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                        stakingState: null,
+                        rewardVault: null,
+                        callerTokenAccount: null,
+                        burnHistory: burn_history_address,
+                        signer: provider.wallet.publicKey,
                     })
                     .rpc();
             } catch (error) {
@@ -662,6 +800,12 @@ describe("LeanCoin", () => {
                         contractState: contract_state_address,
                         burningAccount: fake_program_account_address,
                         tokenProgram: TOKEN_PROGRAM_ID,
+                        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                        stakingState: null,
+                        rewardVault: null,
+                        callerTokenAccount: null,
+                        burnHistory: burn_history_address,
+                        signer: provider.wallet.publicKey,
                     })
                     .rpc();
             } catch (error) {
@@ -684,6 +828,12 @@ describe("LeanCoin", () => {
                         contractState: contract_state_address,
                         burningAccount: burning_account_address,
                         tokenProgram: fake_tokenProgram,
+                        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                        stakingState: null,
+                        rewardVault: null,
+                        callerTokenAccount: null,
+                        burnHistory: burn_history_address,
+                        signer: provider.wallet.publicKey,
                     })
                     .rpc();
             } catch (error) {
@@ -702,7 +852,7 @@ describe("LeanCoin", () => {
             );
 
             const tx = await program.methods
-                .withdrawTokensFromCommunityWallet(new BN(1000))
+                .withdrawTokensFromCommunityWallet(new BN(1000), null)
                 .accounts({
                     contractState: contract_state_address,
                     vestingState: vesting_state_address,
@@ -710,6 +860,12 @@ describe("LeanCoin", () => {
                     depositWallet: swap_account_address,
                     tokenProgram: TOKEN_PROGRAM_ID,
                     signer: provider.wallet.publicKey,
+                    instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                    withdrawalReceipt: await findNextWithdrawalReceiptAddress(
+                        WalletKind.Community,
+                    ),
+                    withdrawalIntent: null,
+                    systemProgram: anchor.web3.SystemProgram.programId,
                 })
                 .transaction();
 
@@ -745,7 +901,7 @@ describe("LeanCoin", () => {
             );
 
             const tx = await program.methods
-                .withdrawTokensFromCommunityWallet(new BN(1000000000))
+                .withdrawTokensFromCommunityWallet(new BN(1000000000), null)
                 .accounts({
                     contractState: contract_state_address,
                     vestingState: vesting_state_address,
@@ -753,6 +909,12 @@ describe("LeanCoin", () => {
                     depositWallet: swap_account_address,
                     tokenProgram: TOKEN_PROGRAM_ID,
                     signer: provider.wallet.publicKey,
+                    instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                    withdrawalReceipt: await findNextWithdrawalReceiptAddress(
+                        WalletKind.Community,
+                    ),
+                    withdrawalIntent: null,
+                    systemProgram: anchor.web3.SystemProgram.programId,
                 })
                 .transaction();
 
@@ -785,6 +947,7 @@ describe("LeanCoin", () => {
                 const tx = await program.methods
                     .withdrawTokensFromCommunityWallet(
                         new BN("1000000000000000000"),
+                        null,
                     )
                     .accounts({
                         contractState: contract_state_address,
@@ -793,6 +956,12 @@ describe("LeanCoin", () => {
                         depositWallet: swap_account_address,
                         tokenProgram: TOKEN_PROGRAM_ID,
                         signer: provider.wallet.publicKey,
+                        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                        withdrawalReceipt: await findNextWithdrawalReceiptAddress(
+                            WalletKind.Community,
+                        ),
+                        withdrawalIntent: null,
+                        systemProgram: anchor.web3.SystemProgram.programId,
                     })
                     .transaction();
 
@@ -821,7 +990,7 @@ describe("LeanCoin", () => {
                 let amount_to_withdraw = new BN(0);
 
                 const tx = await program.methods
-                    .withdrawTokensFromCommunityWallet(amount_to_withdraw)
+                    .withdrawTokensFromCommunityWallet(amount_to_withdraw, null)
                     .accounts({
                         contractState: fake_contract_state,
                         vestingState: vesting_state_address,
@@ -829,6 +998,12 @@ describe("LeanCoin", () => {
                         depositWallet: swap_account_address,
                         tokenProgram: TOKEN_PROGRAM_ID,
                         signer: provider.wallet.publicKey,
+                        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                        withdrawalReceipt: await findNextWithdrawalReceiptAddress(
+                            WalletKind.Community,
+                        ),
+                        withdrawalIntent: null,
+                        systemProgram: anchor.web3.SystemProgram.programId,
                     })
                     .transaction();
 
@@ -857,7 +1032,7 @@ describe("LeanCoin", () => {
                 let amount_to_withdraw = new BN(0);
 
                 const tx = await program.methods
-                    .withdrawTokensFromCommunityWallet(amount_to_withdraw)
+                    .withdrawTokensFromCommunityWallet(amount_to_withdraw, null)
                     .accounts({
                         contractState: contract_state_address,
                         vestingState: fake_vesting_state,
@@ -865,6 +1040,12 @@ describe("LeanCoin", () => {
                         depositWallet: swap_account_address,
                         tokenProgram: TOKEN_PROGRAM_ID,
                         signer: provider.wallet.publicKey,
+                        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                        withdrawalReceipt: await findNextWithdrawalReceiptAddress(
+                            WalletKind.Community,
+                        ),
+                        withdrawalIntent: null,
+                        systemProgram: anchor.web3.SystemProgram.programId,
                     })
                     .transaction();
 
@@ -893,7 +1074,7 @@ describe("LeanCoin", () => {
                 let amount_to_withdraw = new BN(0);
 
                 const tx = await program.methods
-                    .withdrawTokensFromCommunityWallet(amount_to_withdraw)
+                    .withdrawTokensFromCommunityWallet(amount_to_withdraw, null)
                     .accounts({
                         contractState: contract_state_address,
                         vestingState: vesting_state_address,
@@ -901,6 +1082,12 @@ describe("LeanCoin", () => {
                         depositWallet: swap_account_address,
                         tokenProgram: TOKEN_PROGRAM_ID,
                         signer: provider.wallet.publicKey,
+                        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                        withdrawalReceipt: await findNextWithdrawalReceiptAddress(
+                            WalletKind.Community,
+                        ),
+                        withdrawalIntent: null,
+                        systemProgram: anchor.web3.SystemProgram.programId,
                     })
                     .transaction();
 
@@ -923,7 +1110,7 @@ describe("LeanCoin", () => {
         it("Withdraw Tokens From Partnership Wallet 0 tokens", async () => {
             let amount_to_withdraw = new BN(0);
             const tx = await program.methods
-                .withdrawTokensFromPartnershipWallet(amount_to_withdraw)
+                .withdrawTokensFromPartnershipWallet(amount_to_withdraw, null)
                 .accounts({
                     contractState: contract_state_address,
                     vestingState: vesting_state_address,
@@ -931,6 +1118,12 @@ describe("LeanCoin", () => {
                     depositWallet: swap_account_address,
                     tokenProgram: TOKEN_PROGRAM_ID,
                     signer: provider.wallet.publicKey,
+                    instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                    withdrawalReceipt: await findNextWithdrawalReceiptAddress(
+                        WalletKind.Partnership,
+                    ),
+                    withdrawalIntent: null,
+                    systemProgram: anchor.web3.SystemProgram.programId,
                 })
                 .transaction();
 
@@ -951,7 +1144,10 @@ describe("LeanCoin", () => {
 
             try {
                 const tx = await program.methods
-                    .withdrawTokensFromPartnershipWallet(amount_to_withdraw)
+                    .withdrawTokensFromPartnershipWallet(
+                        amount_to_withdraw,
+                        null,
+                    )
                     .accounts({
                         contractState: contract_state_address,
                         vestingState: vesting_state_address,
@@ -959,6 +1155,12 @@ describe("LeanCoin", () => {
                         depositWallet: swap_account_address,
                         tokenProgram: TOKEN_PROGRAM_ID,
                         signer: provider.wallet.publicKey,
+                        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                        withdrawalReceipt: await findNextWithdrawalReceiptAddress(
+                            WalletKind.Partnership,
+                        ),
+                        withdrawalIntent: null,
+                        systemProgram: anchor.web3.SystemProgram.programId,
                     })
                     .transaction();
 
@@ -985,6 +1187,7 @@ describe("LeanCoin", () => {
                 const tx = await program.methods
                     .withdrawTokensFromPartnershipWallet(
                         new BN("1000000000000000000"),
+                        null,
                     )
                     .accounts({
                         contractState: contract_state_address,
@@ -993,6 +1196,12 @@ describe("LeanCoin", () => {
                         depositWallet: swap_account_address,
                         tokenProgram: TOKEN_PROGRAM_ID,
                         signer: provider.wallet.publicKey,
+                        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                        withdrawalReceipt: await findNextWithdrawalReceiptAddress(
+                            WalletKind.Partnership,
+                        ),
+                        withdrawalIntent: null,
+                        systemProgram: anchor.web3.SystemProgram.programId,
                     })
                     .transaction();
 
@@ -1022,7 +1231,10 @@ describe("LeanCoin", () => {
             try {
                 let amount_to_withdraw = new BN(0);
                 const tx = await program.methods
-                    .withdrawTokensFromPartnershipWallet(amount_to_withdraw)
+                    .withdrawTokensFromPartnershipWallet(
+                        amount_to_withdraw,
+                        null,
+                    )
                     .accounts({
                         contractState: fake_contract_state,
                         vestingState: vesting_state_address,
@@ -1030,6 +1242,12 @@ describe("LeanCoin", () => {
                         depositWallet: swap_account_address,
                         tokenProgram: TOKEN_PROGRAM_ID,
                         signer: provider.wallet.publicKey,
+                        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                        withdrawalReceipt: await findNextWithdrawalReceiptAddress(
+                            WalletKind.Partnership,
+                        ),
+                        withdrawalIntent: null,
+                        systemProgram: anchor.web3.SystemProgram.programId,
                     })
                     .transaction();
 
@@ -1057,7 +1275,10 @@ describe("LeanCoin", () => {
             try {
                 let amount_to_withdraw = new BN(0);
                 const tx = await program.methods
-                    .withdrawTokensFromPartnershipWallet(amount_to_withdraw)
+                    .withdrawTokensFromPartnershipWallet(
+                        amount_to_withdraw,
+                        null,
+                    )
                     .accounts({
                         contractState: contract_state_address,
                         vestingState: fake_vesting_state,
@@ -1065,6 +1286,12 @@ describe("LeanCoin", () => {
                         depositWallet: swap_account_address,
                         tokenProgram: TOKEN_PROGRAM_ID,
                         signer: provider.wallet.publicKey,
+                        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                        withdrawalReceipt: await findNextWithdrawalReceiptAddress(
+                            WalletKind.Partnership,
+                        ),
+                        withdrawalIntent: null,
+                        systemProgram: anchor.web3.SystemProgram.programId,
                     })
                     .transaction();
 
@@ -1092,7 +1319,10 @@ describe("LeanCoin", () => {
             try {
                 let amount_to_withdraw = new BN(0);
                 const tx = await program.methods
-                    .withdrawTokensFromPartnershipWallet(amount_to_withdraw)
+                    .withdrawTokensFromPartnershipWallet(
+                        amount_to_withdraw,
+                        null,
+                    )
                     .accounts({
                         contractState: contract_state_address,
                         vestingState: vesting_state_address,
@@ -1100,6 +1330,12 @@ describe("LeanCoin", () => {
                         depositWallet: swap_account_address,
                         tokenProgram: TOKEN_PROGRAM_ID,
                         signer: provider.wallet.publicKey,
+                        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                        withdrawalReceipt: await findNextWithdrawalReceiptAddress(
+                            WalletKind.Partnership,
+                        ),
+                        withdrawalIntent: null,
+                        systemProgram: anchor.web3.SystemProgram.programId,
                     })
                     .transaction();
 
@@ -1122,7 +1358,7 @@ describe("LeanCoin", () => {
         it("Withdraw Tokens From Marketing Wallet 0 tokens!", async () => {
             let amount_to_withdraw = new BN(0);
             const tx = await program.methods
-                .withdrawTokensFromMarketingWallet(amount_to_withdraw)
+                .withdrawTokensFromMarketingWallet(amount_to_withdraw, null)
                 .accounts({
                     contractState: contract_state_address,
                     vestingState: vesting_state_address,
@@ -1130,6 +1366,12 @@ describe("LeanCoin", () => {
                     depositWallet: swap_account_address,
                     tokenProgram: TOKEN_PROGRAM_ID,
                     signer: provider.wallet.publicKey,
+                    instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                    withdrawalReceipt: await findNextWithdrawalReceiptAddress(
+                        WalletKind.Marketing,
+                    ),
+                    withdrawalIntent: null,
+                    systemProgram: anchor.web3.SystemProgram.programId,
                 })
                 .transaction();
 
@@ -1148,7 +1390,7 @@ describe("LeanCoin", () => {
             let amount_to_withdraw = new BN(100);
             try {
                 const tx = await program.methods
-                    .withdrawTokensFromMarketingWallet(amount_to_withdraw)
+                    .withdrawTokensFromMarketingWallet(amount_to_withdraw, null)
                     .accounts({
                         contractState: contract_state_address,
                         vestingState: vesting_state_address,
@@ -1156,6 +1398,12 @@ describe("LeanCoin", () => {
                         depositWallet: swap_account_address,
                         tokenProgram: TOKEN_PROGRAM_ID,
                         signer: provider.wallet.publicKey,
+                        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                        withdrawalReceipt: await findNextWithdrawalReceiptAddress(
+                            WalletKind.Marketing,
+                        ),
+                        withdrawalIntent: null,
+                        systemProgram: anchor.web3.SystemProgram.programId,
                     })
                     .transaction();
 
@@ -1183,7 +1431,7 @@ describe("LeanCoin", () => {
             try {
                 let amount_to_withdraw = new BN(0);
                 const tx = await program.methods
-                    .withdrawTokensFromMarketingWallet(amount_to_withdraw)
+                    .withdrawTokensFromMarketingWallet(amount_to_withdraw, null)
                     .accounts({
                         contractState: fake_contract_state,
                         vestingState: vesting_state_address,
@@ -1191,6 +1439,12 @@ describe("LeanCoin", () => {
                         depositWallet: swap_account_address,
                         tokenProgram: TOKEN_PROGRAM_ID,
                         signer: provider.wallet.publicKey,
+                        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                        withdrawalReceipt: await findNextWithdrawalReceiptAddress(
+                            WalletKind.Marketing,
+                        ),
+                        withdrawalIntent: null,
+                        systemProgram: anchor.web3.SystemProgram.programId,
                     })
                     .transaction();
 
@@ -1218,7 +1472,7 @@ describe("LeanCoin", () => {
             try {
                 let amount_to_withdraw = new BN(0);
                 const tx = await program.methods
-                    .withdrawTokensFromMarketingWallet(amount_to_withdraw)
+                    .withdrawTokensFromMarketingWallet(amount_to_withdraw, null)
                     .accounts({
                         contractState: contract_state_address,
                         vestingState: fake_vesting_state,
@@ -1226,6 +1480,12 @@ describe("LeanCoin", () => {
                         depositWallet: swap_account_address,
                         tokenProgram: TOKEN_PROGRAM_ID,
                         signer: provider.wallet.publicKey,
+                        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                        withdrawalReceipt: await findNextWithdrawalReceiptAddress(
+                            WalletKind.Marketing,
+                        ),
+                        withdrawalIntent: null,
+                        systemProgram: anchor.web3.SystemProgram.programId,
                     })
                     .transaction();
 
@@ -1253,7 +1513,7 @@ describe("LeanCoin", () => {
             try {
                 let amount_to_withdraw = new BN(0);
                 const tx = await program.methods
-                    .withdrawTokensFromMarketingWallet(amount_to_withdraw)
+                    .withdrawTokensFromMarketingWallet(amount_to_withdraw, null)
                     .accounts({
                         contractState: contract_state_address,
                         vestingState: vesting_state_address,
@@ -1261,6 +1521,12 @@ describe("LeanCoin", () => {
                         depositWallet: swap_account_address,
                         tokenProgram: TOKEN_PROGRAM_ID,
                         signer: provider.wallet.publicKey,
+                        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                        withdrawalReceipt: await findNextWithdrawalReceiptAddress(
+                            WalletKind.Marketing,
+                        ),
+                        withdrawalIntent: null,
+                        systemProgram: anchor.web3.SystemProgram.programId,
                     })
                     .transaction();
 
@@ -1288,7 +1554,7 @@ describe("LeanCoin", () => {
             try {
                 let amount_to_withdraw = new BN(0);
                 const tx = await program.methods
-                    .withdrawTokensFromMarketingWallet(amount_to_withdraw)
+                    .withdrawTokensFromMarketingWallet(amount_to_withdraw, null)
                     .accounts({
                         contractState: contract_state_address,
                         vestingState: vesting_state_address,
@@ -1296,6 +1562,12 @@ describe("LeanCoin", () => {
                         depositWallet: fake_marketing_wallet,
                         tokenProgram: TOKEN_PROGRAM_ID,
                         signer: provider.wallet.publicKey,
+                        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                        withdrawalReceipt: await findNextWithdrawalReceiptAddress(
+                            WalletKind.Marketing,
+                        ),
+                        withdrawalIntent: null,
+                        systemProgram: anchor.web3.SystemProgram.programId,
                     })
                     .transaction();
 
@@ -1318,7 +1590,7 @@ describe("LeanCoin", () => {
         it("Withdraw Tokens From Liquidity Wallet!", async () => {
             let amount_to_withdraw = new BN(0);
             const tx = await program.methods
-                .withdrawTokensFromLiquidityWallet(amount_to_withdraw)
+                .withdrawTokensFromLiquidityWallet(amount_to_withdraw, null)
                 .accounts({
                     contractState: contract_state_address,
                     vestingState: vesting_state_address,
@@ -1326,6 +1598,12 @@ describe("LeanCoin", () => {
                     depositWallet: swap_account_address,
                     tokenProgram: TOKEN_PROGRAM_ID,
                     signer: provider.wallet.publicKey,
+                    instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                    withdrawalReceipt: await findNextWithdrawalReceiptAddress(
+                        WalletKind.Liquidity,
+                    ),
+                    withdrawalIntent: null,
+                    systemProgram: anchor.web3.SystemProgram.programId,
                 })
                 .transaction();
 
@@ -1347,7 +1625,7 @@ describe("LeanCoin", () => {
             try {
                 let amount_to_withdraw = new BN(0);
                 const tx = await program.methods
-                    .withdrawTokensFromLiquidityWallet(amount_to_withdraw)
+                    .withdrawTokensFromLiquidityWallet(amount_to_withdraw, null)
                     .accounts({
                         contractState: fake_contract_state,
                         vestingState: vesting_state_address,
@@ -1355,6 +1633,12 @@ describe("LeanCoin", () => {
                         depositWallet: swap_account_address,
                         tokenProgram: TOKEN_PROGRAM_ID,
                         signer: provider.wallet.publicKey,
+                        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                        withdrawalReceipt: await findNextWithdrawalReceiptAddress(
+                            WalletKind.Liquidity,
+                        ),
+                        withdrawalIntent: null,
+                        systemProgram: anchor.web3.SystemProgram.programId,
                     })
                     .transaction();
 
@@ -1382,7 +1666,7 @@ describe("LeanCoin", () => {
             try {
                 let amount_to_withdraw = new BN(0);
                 const tx = await program.methods
-                    .withdrawTokensFromLiquidityWallet(amount_to_withdraw)
+                    .withdrawTokensFromLiquidityWallet(amount_to_withdraw, null)
                     .accounts({
                         contractState: contract_state_address,
                         vestingState: fake_vesting_state,
@@ -1390,6 +1674,12 @@ describe("LeanCoin", () => {
                         depositWallet: swap_account_address,
                         tokenProgram: TOKEN_PROGRAM_ID,
                         signer: provider.wallet.publicKey,
+                        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                        withdrawalReceipt: await findNextWithdrawalReceiptAddress(
+                            WalletKind.Liquidity,
+                        ),
+                        withdrawalIntent: null,
+                        systemProgram: anchor.web3.SystemProgram.programId,
                     })
                     .transaction();
 
@@ -1417,7 +1707,7 @@ describe("LeanCoin", () => {
             try {
                 let amount_to_withdraw = new BN(0);
                 const tx = await program.methods
-                    .withdrawTokensFromLiquidityWallet(amount_to_withdraw)
+                    .withdrawTokensFromLiquidityWallet(amount_to_withdraw, null)
                     .accounts({
                         contractState: contract_state_address,
                         vestingState: vesting_state_address,
@@ -1425,6 +1715,12 @@ describe("LeanCoin", () => {
                         depositWallet: swap_account_address,
                         tokenProgram: TOKEN_PROGRAM_ID,
                         signer: provider.wallet.publicKey,
+                        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                        withdrawalReceipt: await findNextWithdrawalReceiptAddress(
+                            WalletKind.Liquidity,
+                        ),
+                        withdrawalIntent: null,
+                        systemProgram: anchor.web3.SystemProgram.programId,
                     })
                     .transaction();
 
@@ -1478,7 +1774,7 @@ describe("LeanCoin", () => {
                 "https://gateway.pinata.cloud/ipfs/QmYapT6pjy4YttmyU5AChgX69MG9vSFRyxsX9BuCbwfbuG?_gl=1*1md92uq*rs_ga*ODc0ODg2NzEzLjE2ODQ3ODY1OTQ.*rs_ga_5RMPXG14TE*MTY4NDkzNDAxNS41LjAuMTY4NDkzNDAxNS42MC4wLjA.";
 
             await program.methods
-                .setTokenMetadata(name, symbol, uri, { create: {} })
+                .setTokenMetadata(name, symbol, uri)
                 .accounts({
                     contractState: contract_state_address,
                     mint: mint,
@@ -1524,14 +1820,12 @@ describe("LeanCoin", () => {
                 "https://gateway.pinata.cloud/ipfs/QmYapT6pjy4YttmyU5AChgX69MG9vSFRyxsX9BuCbwfbuG?_gl=1*1md92uq*rs_ga*ODc0ODg2NzEzLjE2ODQ3ODY1OTQ.*rs_ga_5RMPXG14TE*MTY4NDkzNDAxNS41LjAuMTY4NDkzNDAxNS42MC4wLjA.2";
 
             await program.methods
-                .setTokenMetadata(name, symbol, uri, { update: {} })
+                .updateTokenMetadata(name, symbol, uri)
                 .accounts({
                     contractState: contract_state_address,
                     mint: mint,
                     signer: provider.wallet.publicKey,
                     metadataPda: metadataPDA,
-                    systemProgram: anchor.web3.SystemProgram.programId,
-                    tokenProgram: TOKEN_PROGRAM_ID,
                     metadataProgram: mpl.PROGRAM_ID,
                 })
                 .rpc({
@@ -1574,14 +1868,12 @@ describe("LeanCoin", () => {
 
             try {
                 let tx = await program.methods
-                    .setTokenMetadata(name, symbol, uri, { update: {} })
+                    .updateTokenMetadata(name, symbol, uri)
                     .accounts({
                         contractState: contract_state_address,
                         mint: mint,
                         metadataPda: metadataPDA,
                         signer: fake_signer.publicKey,
-                        systemProgram: anchor.web3.SystemProgram.programId,
-                        tokenProgram: TOKEN_PROGRAM_ID,
                         metadataProgram: mpl.PROGRAM_ID,
                     })
                     .signers([fake_signer])
@@ -1614,7 +1906,7 @@ describe("LeanCoin", () => {
 
             try {
                 let tx = await program.methods
-                    .setTokenMetadata(name, symbol, uri, { update: {} })
+                    .setTokenMetadata(name, symbol, uri)
                     .accounts({
                         contractState: contract_state_address,
                         mint: mint,
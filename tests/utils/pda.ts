@@ -13,3 +13,33 @@ export const findProgramAddress = (key: string): [PublicKey, number] => {
 
     return [_pda, _bump];
 };
+
+// the order of the vested wallets in `WalletKind`
+export enum WalletKind {
+    Community = 0,
+    Partnership = 1,
+    Marketing = 2,
+    Liquidity = 3,
+}
+
+// the receipt created by the next withdrawal from the vested wallet
+export const findNextWithdrawalReceiptAddress = async (
+    walletKind: WalletKind,
+): Promise<PublicKey> => {
+    const program = anchor.workspace.Leancoin as anchor.Program<Leancoin>;
+    const [vestingStateAddress] = findProgramAddress("vesting_state");
+    const vestingState =
+        await program.account.vestingState.fetch(vestingStateAddress);
+    const index: anchor.BN = vestingState.nextReceiptIndex[walletKind];
+
+    const [_pda] = PublicKey.findProgramAddressSync(
+        [
+            Buffer.from(anchor.utils.bytes.utf8.encode("receipt")),
+            Buffer.from([walletKind]),
+            index.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId,
+    );
+
+    return _pda;
+};